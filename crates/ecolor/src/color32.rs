@@ -345,6 +345,16 @@ impl Color32 {
         )
     }
 
+    /// Lerp this color towards `other` by `t` in the perceptual [`crate::Oklab`] color space.
+    ///
+    /// This is slower than [`Self::lerp_to_gamma`],
+    /// but avoids the dark or gray middle you get when e.g. going from blue to yellow.
+    pub fn lerp_oklab(self, other: Self, t: f32) -> Self {
+        crate::Oklab::from(self)
+            .lerp(crate::Oklab::from(other), t)
+            .into()
+    }
+
     /// Blend two colors in gamma space, so that `self` is behind the argument.
     pub fn blend(self, on_top: Self) -> Self {
         self.gamma_multiply_u8(255 - on_top.a()) + on_top
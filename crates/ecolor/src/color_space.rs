@@ -0,0 +1,74 @@
+use crate::{apply_color_matrix, Color32, ColorMatrix};
+
+/// The RGB color space of some colors, e.g. of a texture or of a display.
+///
+/// Both color spaces use the sRGB transfer function ("gamma") and the D65 white point,
+/// so they only differ in how saturated their primary colors are.
+///
+/// All [`Color32`]s in egui are in sRGB, unless otherwise noted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ColorSpace {
+    /// The standard color space of the web and most displays.
+    #[default]
+    Srgb,
+
+    /// The wide-gamut color space of many modern displays (e.g. Apple devices).
+    ///
+    /// It can show more saturated colors than sRGB.
+    DisplayP3,
+}
+
+impl ColorSpace {
+    /// The matrix that converts linear RGB in `self` to linear RGB in `target`,
+    /// or `None` if they are the same.
+    pub fn conversion_matrix(self, target: Self) -> Option<ColorMatrix> {
+        match (self, target) {
+            (Self::Srgb, Self::Srgb) | (Self::DisplayP3, Self::DisplayP3) => None,
+            (Self::Srgb, Self::DisplayP3) => Some([
+                [0.822_462_1, 0.177_538, 0.0],
+                [0.033_194_1, 0.966_805_8, 0.0],
+                [0.017_082_7, 0.072_397_4, 0.910_519_9],
+            ]),
+            (Self::DisplayP3, Self::Srgb) => Some([
+                [1.224_940_1, -0.224_940_4, 0.0],
+                [-0.042_056_9, 1.042_057_1, 0.0],
+                [-0.019_637_6, -0.078_636_1, 1.098_273_5],
+            ]),
+        }
+    }
+
+    /// Convert a color in this color space so it looks the same in `target`.
+    ///
+    /// Colors that `target` can't show (e.g. very saturated P3 colors in sRGB) are clamped.
+    pub fn convert(self, color: Color32, target: Self) -> Color32 {
+        match self.conversion_matrix(target) {
+            Some(matrix) => apply_color_matrix(color, &matrix),
+            None => color,
+        }
+    }
+}
+
+#[test]
+fn test_color_space_conversion() {
+    use ColorSpace::{DisplayP3, Srgb};
+
+    // White and grays are the same in both:
+    for gray in [0, 50, 128, 255] {
+        let color = Color32::from_gray(gray);
+        let p3 = Srgb.convert(color, DisplayP3);
+        for i in 0..3 {
+            assert!((p3[i] as i32 - gray as i32).abs() <= 1, "{p3:?}");
+        }
+    }
+
+    // sRGB red is less saturated in P3:
+    let red = Srgb.convert(Color32::RED, DisplayP3);
+    assert!(red.r() < 255 && red.g() > 0);
+
+    // …and survives the roundtrip:
+    let roundtrip = DisplayP3.convert(red, Srgb);
+    for i in 0..4 {
+        assert!((roundtrip[i] as i32 - Color32::RED[i] as i32).abs() <= 1);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{Color32, Rgba};
+
+/// A 3x3 matrix that is applied to linear RGB, row by row.
+pub type ColorMatrix = [[f32; 3]; 3];
+
+/// Apply a [`ColorMatrix`] to the linear RGB of a color, keeping its alpha.
+pub fn apply_color_matrix(color: Color32, matrix: &ColorMatrix) -> Color32 {
+    let rgba = Rgba::from(color);
+    let [r, g, b, a] = rgba.to_array();
+
+    // Rgba is premultiplied, so the result is clamped to the alpha (or to 1 for additive colors).
+    let max = if a == 0.0 { 1.0 } else { a };
+    let row = |m: [f32; 3]| (m[0] * r + m[1] * g + m[2] * b).clamp(0.0, max);
+
+    Rgba::from_rgba_premultiplied(row(matrix[0]), row(matrix[1]), row(matrix[2]), a).into()
+}
+
+/// Different kinds of color vision, used to simulate how colors look to people with color blindness.
+///
+/// Use this to check that a color scheme still works for everyone,
+/// e.g. that errors and warnings can be told apart.
+///
+/// The matrices for the dichromacies are from
+/// Machado, Oliveira & Fernandes (2009): "A Physiologically-based Model for Simulation of Color Vision Deficiency".
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ColorVision {
+    /// Normal (trichromatic) color vision. Colors are left as they are.
+    #[default]
+    Normal,
+
+    /// No red cones. The most common form of red-green color blindness, together with [`Self::Deuteranopia`].
+    Protanopia,
+
+    /// No green cones. The most common form of red-green color blindness, together with [`Self::Protanopia`].
+    Deuteranopia,
+
+    /// No blue cones. Blue-yellow color blindness.
+    Tritanopia,
+
+    /// No color vision at all, only luminance.
+    Achromatopsia,
+}
+
+impl ColorVision {
+    pub const ALL: [Self; 5] = [
+        Self::Normal,
+        Self::Protanopia,
+        Self::Deuteranopia,
+        Self::Tritanopia,
+        Self::Achromatopsia,
+    ];
+
+    /// The simulation matrix, to be used with [`apply_color_matrix`].
+    pub fn matrix(self) -> ColorMatrix {
+        match self {
+            Self::Normal => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            Self::Protanopia => [
+                [0.152_286, 1.052_583, -0.204_868],
+                [0.114_503, 0.786_281, 0.099_216],
+                [-0.003_882, -0.048_116, 1.051_998],
+            ],
+            Self::Deuteranopia => [
+                [0.367_322, 0.860_646, -0.227_968],
+                [0.280_085, 0.672_501, 0.047_413],
+                [-0.011_820, 0.042_940, 0.968_881],
+            ],
+            Self::Tritanopia => [
+                [1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411, 0.930_809, 0.147_602],
+                [0.004_733, 0.691_367, 0.303_900],
+            ],
+            Self::Achromatopsia => {
+                let luminance = [0.2126, 0.7152, 0.0722];
+                [luminance; 3]
+            }
+        }
+    }
+
+    /// How the given color looks with this kind of color vision.
+    pub fn simulate(self, color: Color32) -> Color32 {
+        if self == Self::Normal {
+            color
+        } else {
+            apply_color_matrix(color, &self.matrix())
+        }
+    }
+}
+
+#[test]
+fn test_color_vision() {
+    let colors = [
+        Color32::RED,
+        Color32::from_rgb(10, 100, 200),
+        Color32::from_white_alpha(40),
+        Color32::TRANSPARENT,
+    ];
+    for color in colors {
+        assert_eq!(ColorVision::Normal.simulate(color), color);
+        for vision in ColorVision::ALL {
+            // Grays stay gray, and alpha is untouched:
+            let gray = Color32::from_gray(128).gamma_multiply(color.a() as f32 / 255.0);
+            let simulated = vision.simulate(gray);
+            assert_eq!(simulated.a(), gray.a());
+            assert!(
+                (simulated.r() as i32 - gray.r() as i32).abs() <= 1,
+                "{vision:?}"
+            );
+            assert!(
+                (simulated.b() as i32 - gray.b() as i32).abs() <= 1,
+                "{vision:?}"
+            );
+        }
+    }
+
+    // Red and green are hard to tell apart without red or green cones:
+    let red = ColorVision::Deuteranopia.simulate(Color32::from_rgb(200, 60, 40));
+    let green = ColorVision::Deuteranopia.simulate(Color32::from_rgb(100, 140, 40));
+    assert!((red.r() as i32 - green.r() as i32).abs() < 40);
+    assert!((red.g() as i32 - green.g() as i32).abs() < 40);
+}
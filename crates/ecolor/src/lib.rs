@@ -27,12 +27,21 @@ mod cint_impl;
 mod color32;
 pub use color32::*;
 
+mod color_space;
+pub use color_space::*;
+
+mod color_vision;
+pub use color_vision::*;
+
 mod hsva_gamma;
 pub use hsva_gamma::*;
 
 mod hsva;
 pub use hsva::*;
 
+mod oklab;
+pub use oklab::*;
+
 #[cfg(feature = "color-hex")]
 mod hex_color_macro;
 #[cfg(feature = "color-hex")]
@@ -42,6 +51,9 @@ pub use color_hex;
 mod rgba;
 pub use rgba::*;
 
+mod rgba_f16;
+pub use rgba_f16::*;
+
 mod hex_color_runtime;
 pub use hex_color_runtime::*;
 
@@ -0,0 +1,240 @@
+use crate::{Color32, Rgba};
+
+/// A color in the [Oklab](https://bottosson.github.io/posts/oklab/) perceptual color space.
+///
+/// Oklab is designed so that the same distance anywhere in the space looks like the same amount of change.
+/// This makes it great for gradients and color blending,
+/// which otherwise often get muddy or dark around the middle.
+///
+/// No premultiplied alpha.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Oklab {
+    /// Perceived lightness, 0-1.
+    pub l: f32,
+
+    /// Green (negative) to red (positive), roughly -0.4 to 0.4.
+    pub a: f32,
+
+    /// Blue (negative) to yellow (positive), roughly -0.4 to 0.4.
+    pub b: f32,
+
+    /// Alpha 0-1. Zero alpha means an additive color (like for [`Rgba`]).
+    pub alpha: f32,
+}
+
+impl Oklab {
+    #[inline]
+    pub fn new(l: f32, a: f32, b: f32, alpha: f32) -> Self {
+        Self { l, a, b, alpha }
+    }
+
+    /// From linear RGB without premultiplied alpha.
+    pub fn from_linear_rgb([r, g, b]: [f32; 3], alpha: f32) -> Self {
+        let l = 0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b;
+        let m = 0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b;
+        let s = 0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b;
+
+        let (l, m, s) = (l.cbrt(), m.cbrt(), s.cbrt());
+
+        Self {
+            l: 0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            a: 1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            b: 0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+            alpha,
+        }
+    }
+
+    /// To linear RGB without premultiplied alpha.
+    ///
+    /// The result may be outside the 0-1 range if the color is outside the sRGB gamut.
+    pub fn to_linear_rgb(self) -> [f32; 3] {
+        let l = self.l + 0.396_337_78 * self.a + 0.215_803_76 * self.b;
+        let m = self.l - 0.105_561_346 * self.a - 0.063_854_17 * self.b;
+        let s = self.l - 0.089_484_18 * self.a - 1.291_485_5 * self.b;
+
+        let (l, m, s) = (l * l * l, m * m * m, s * s * s);
+
+        [
+            4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+            -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+            -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+        ]
+    }
+
+    /// Interpolate between two colors, where `t=0` is `self` and `t=1` is `other`.
+    ///
+    /// Alpha is taken into account, so fading towards a transparent color
+    /// doesn't darken the color on the way.
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        use emath::lerp;
+
+        // Interpolate premultiplied, so that the color of a transparent end doesn't bleed in.
+        // Fully transparent black gets no weight, but other additive colors do.
+        let lerp_weight = |c: Self| {
+            if c.alpha == 0.0 && c.l <= 0.0 {
+                0.0
+            } else {
+                weight(c.alpha)
+            }
+        };
+        let (wa, wb) = (lerp_weight(self), lerp_weight(other));
+        let alpha = lerp(self.alpha..=other.alpha, t);
+        let w = lerp(wa..=wb, t);
+        if w <= 0.0 {
+            return Self { alpha, ..self };
+        }
+        let mix = |x: f32, y: f32| lerp((x * wa)..=(y * wb), t) / w;
+        Self {
+            l: mix(self.l, other.l),
+            a: mix(self.a, other.a),
+            b: mix(self.b, other.b),
+            alpha,
+        }
+    }
+}
+
+/// Additive colors have zero alpha, but still have a color that should be interpolated.
+fn weight(alpha: f32) -> f32 {
+    if alpha == 0.0 {
+        1.0
+    } else {
+        alpha
+    }
+}
+
+impl From<Rgba> for Oklab {
+    fn from(rgba: Rgba) -> Self {
+        let [r, g, b, a] = rgba.to_array();
+        let w = weight(a);
+        Self::from_linear_rgb([r / w, g / w, b / w], a)
+    }
+}
+
+impl From<Oklab> for Rgba {
+    fn from(oklab: Oklab) -> Self {
+        let [r, g, b] = oklab.to_linear_rgb().map(|c| c.clamp(0.0, 1.0));
+        let w = weight(oklab.alpha);
+        Self::from_rgba_premultiplied(r * w, g * w, b * w, oklab.alpha)
+    }
+}
+
+impl From<Color32> for Oklab {
+    fn from(color: Color32) -> Self {
+        Rgba::from(color).into()
+    }
+}
+
+impl From<Oklab> for Color32 {
+    fn from(oklab: Oklab) -> Self {
+        Rgba::from(oklab).into()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// [`Oklab`] in polar coordinates: lightness, chroma and hue.
+///
+/// This is the perceptual equivalent of [`crate::Hsva`],
+/// i.e. a good space for picking colors or rotating their hue.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Oklch {
+    /// Perceived lightness, 0-1.
+    pub l: f32,
+
+    /// Chroma (colorfulness), 0 for grays and up to about 0.37 for the most saturated sRGB colors.
+    pub c: f32,
+
+    /// Hue 0-1, where 0 is pink-red, ~0.4 is green and ~0.7 is blue.
+    pub h: f32,
+
+    /// Alpha 0-1. Zero alpha means an additive color (like for [`Rgba`]).
+    pub alpha: f32,
+}
+
+impl Oklch {
+    #[inline]
+    pub fn new(l: f32, c: f32, h: f32, alpha: f32) -> Self {
+        Self { l, c, h, alpha }
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(Oklab { l, a, b, alpha }: Oklab) -> Self {
+        let h = b.atan2(a) / std::f32::consts::TAU;
+        Self {
+            l,
+            c: a.hypot(b),
+            h: h.rem_euclid(1.0),
+            alpha,
+        }
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(Oklch { l, c, h, alpha }: Oklch) -> Self {
+        let (sin, cos) = (h * std::f32::consts::TAU).sin_cos();
+        Self {
+            l,
+            a: c * cos,
+            b: c * sin,
+            alpha,
+        }
+    }
+}
+
+impl From<Color32> for Oklch {
+    fn from(color: Color32) -> Self {
+        Oklab::from(color).into()
+    }
+}
+
+impl From<Oklch> for Color32 {
+    fn from(oklch: Oklch) -> Self {
+        Oklab::from(oklch).into()
+    }
+}
+
+#[test]
+fn test_oklab_roundtrip() {
+    for r in (0..=255).step_by(15) {
+        for g in (0..=255).step_by(15) {
+            for b in (0..=255).step_by(15) {
+                for a in [0, 100, 255] {
+                    let color = Color32::from_rgba_unmultiplied(r, g, b, a);
+                    let from_oklab = Color32::from(Oklab::from(color));
+                    let from_oklch = Color32::from(Oklch::from(color));
+                    for roundtrip in [from_oklab, from_oklch] {
+                        for i in 0..4 {
+                            assert!(
+                                (roundtrip[i] as i32 - color[i] as i32).abs() <= 1,
+                                "{color:?} became {roundtrip:?}"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let white = Oklab::from(Color32::WHITE);
+    assert!((white.l - 1.0).abs() < 1e-3 && white.a.abs() < 1e-3 && white.b.abs() < 1e-3);
+}
+
+#[test]
+fn test_oklab_lerp() {
+    // The middle of a blue-yellow gradient should not be gray:
+    let mid = Color32::BLUE.lerp_oklab(Color32::YELLOW, 0.5);
+    let gamma_mid = Color32::BLUE.lerp_to_gamma(Color32::YELLOW, 0.5);
+    assert!(Oklch::from(mid).c > 0.05, "{mid:?}");
+    assert!(Oklch::from(gamma_mid).c < 0.05, "{gamma_mid:?}");
+
+    // Fading out should not change the color:
+    let red = Color32::from_rgb(200, 30, 30);
+    let half = red.lerp_oklab(Color32::TRANSPARENT, 0.5);
+    let expected = red.gamma_multiply(0.5);
+    for i in 0..4 {
+        assert!((half[i] as i32 - expected[i] as i32).abs() <= 1, "{half:?}");
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{Color32, Rgba};
+
+/// Linear `RGBA` color with premultiplied alpha, stored as four half-precision (16 bit) floats.
+///
+/// Unlike [`Color32`], this can store colors brighter than white,
+/// which is needed for HDR (high dynamic range) rendering.
+/// It is compact enough to be sent to the GPU as a vertex attribute
+/// (e.g. `wgpu::VertexFormat::Float16x4`).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+pub struct RgbaF16(pub [u16; 4]);
+
+impl RgbaF16 {
+    pub const TRANSPARENT: Self = Self([0; 4]);
+
+    /// From linear RGBA with premultiplied alpha. Values above 1 are kept.
+    #[inline]
+    pub fn from_rgba_premultiplied(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self([r, g, b, a].map(f16_bits_from_f32))
+    }
+
+    /// To linear RGBA with premultiplied alpha.
+    #[inline]
+    pub fn to_array(self) -> [f32; 4] {
+        self.0.map(f32_from_f16_bits)
+    }
+}
+
+impl From<Rgba> for RgbaF16 {
+    #[inline]
+    fn from(rgba: Rgba) -> Self {
+        let [r, g, b, a] = rgba.to_array();
+        Self::from_rgba_premultiplied(r, g, b, a)
+    }
+}
+
+impl From<RgbaF16> for Rgba {
+    #[inline]
+    fn from(color: RgbaF16) -> Self {
+        let [r, g, b, a] = color.to_array();
+        Self::from_rgba_premultiplied(r, g, b, a)
+    }
+}
+
+impl From<Color32> for RgbaF16 {
+    #[inline]
+    fn from(color: Color32) -> Self {
+        Rgba::from(color).into()
+    }
+}
+
+/// Round to the nearest half float, with ties to even.
+///
+/// Values too large for a half float become infinity.
+fn f16_bits_from_f32(value: f32) -> u16 {
+    let x = value.to_bits();
+    let sign = ((x >> 16) & 0x8000) as u16;
+    let exp = ((x >> 23) & 0xff) as i32;
+    let man = x & 0x007f_ffff;
+
+    if exp == 0xff {
+        // Infinity or NaN:
+        let nan_bit = if man == 0 { 0 } else { 0x0200 };
+        return sign | 0x7c00 | nan_bit;
+    }
+
+    let half_exp = exp - 127 + 15;
+    if half_exp >= 0x1f {
+        return sign | 0x7c00; // Too large: infinity
+    }
+
+    // Round up if the first dropped bit is set, and either the other dropped bits
+    // or the lowest kept bit is set (i.e. ties go to even).
+    let round = |man: u32, shift: u32| {
+        let round_bit = 1 << (shift - 1);
+        let rounded = man >> shift;
+        if man & round_bit != 0 && man & (3 * round_bit - 1) != 0 {
+            rounded + 1
+        } else {
+            rounded
+        }
+    };
+
+    if half_exp <= 0 {
+        if half_exp < -10 {
+            return sign; // Too small: zero
+        }
+        // Subnormal half float:
+        let man = man | 0x0080_0000;
+        return sign | round(man, (14 - half_exp) as u32) as u16;
+    }
+
+    // A carry out of the mantissa correctly bumps the exponent:
+    sign | ((((half_exp as u32) << 10) + round(man, 13)) as u16)
+}
+
+fn f32_from_f16_bits(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 == 0 { 1.0 } else { -1.0 };
+    let exp = ((bits >> 10) & 0x1f) as u32;
+    let man = (bits & 0x03ff) as u32;
+
+    if exp == 0 {
+        // Zero or subnormal:
+        sign * man as f32 * (1.0 / (1 << 24) as f32)
+    } else {
+        let sign_bit = ((bits & 0x8000) as u32) << 16;
+        let exp = if exp == 0x1f { 0xff } else { exp + 127 - 15 };
+        f32::from_bits(sign_bit | (exp << 23) | (man << 13))
+    }
+}
+
+#[test]
+fn test_f16_conversion() {
+    for value in [
+        0.0,
+        -0.0,
+        1.0,
+        -2.5,
+        0.5,
+        0.1,
+        1.0 / 3.0,
+        12.75,
+        65504.0,
+        1e-5,
+        6e-8,
+    ] {
+        let roundtrip = f32_from_f16_bits(f16_bits_from_f32(value));
+        assert!(
+            (roundtrip - value).abs() <= value.abs() / 1024.0 + 6e-8,
+            "{value} became {roundtrip}"
+        );
+    }
+    assert_eq!(f16_bits_from_f32(1.0), 0x3c00);
+    assert_eq!(f16_bits_from_f32(65504.0), 0x7bff);
+    assert_eq!(f16_bits_from_f32(1e6), 0x7c00);
+    assert_eq!(f16_bits_from_f32(-1e6), 0xfc00);
+    assert!(f32_from_f16_bits(f16_bits_from_f32(f32::NAN)).is_nan());
+
+    // Every half float survives the roundtrip:
+    for bits in 0..0x7c00_u16 {
+        assert_eq!(f16_bits_from_f32(f32_from_f16_bits(bits)), bits);
+    }
+}
+
+#[test]
+fn test_rgba_f16() {
+    let hdr = Rgba::from_rgb(4.0, 1.0, 0.25);
+    assert_eq!(Rgba::from(RgbaF16::from(hdr)), hdr);
+    assert_eq!(
+        Rgba::from(RgbaF16::from(Color32::WHITE)),
+        Rgba::from(Color32::WHITE)
+    );
+}
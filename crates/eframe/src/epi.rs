@@ -352,6 +352,44 @@ pub struct NativeOptions {
     #[cfg(any(feature = "glow", feature = "wgpu"))]
     pub window_builder: Option<WindowBuilderHook>,
 
+    /// Starts OS-level drags out of the app, for [`egui::Response::dnd_start_native_drag`].
+    ///
+    /// winit can't do this on its own, so without a handler such drags are ignored.
+    #[cfg(any(feature = "glow", feature = "wgpu"))]
+    pub start_drag_handler: Option<egui_winit::StartDragHandler>,
+
+    /// Shows native context menus, for [`egui::Response::native_context_menu`].
+    ///
+    /// winit has no menus, so if this is `None` eframe uses a built-in `TrackPopupMenu` on Windows,
+    /// and elsewhere egui draws the context menus itself.
+    #[cfg(any(feature = "glow", feature = "wgpu"))]
+    pub context_menu_handler: Option<egui_winit::ContextMenuHandler>,
+
+    /// Shows native menu bars, for [`egui::native_menu::menu_bar`].
+    ///
+    /// Report clicks with [`egui_winit::NativeEvents::native_menu_item_clicked`].
+    /// If this is `None` eframe uses a built-in `SetMenu` on Windows,
+    /// and elsewhere egui draws the menu bars itself.
+    #[cfg(any(feature = "glow", feature = "wgpu"))]
+    pub menu_bar_handler: Option<egui_winit::MenuBarHandler>,
+
+    /// Shows the tray icon, for [`egui::native_menu::TrayIcon`].
+    ///
+    /// Report clicks with [`egui_winit::NativeEvents::tray_icon_clicked`] and [`egui_winit::NativeEvents::native_menu_item_clicked`].
+    /// If this is `None` eframe uses a built-in `Shell_NotifyIcon` on Windows,
+    /// and elsewhere there is no tray icon.
+    #[cfg(any(feature = "glow", feature = "wgpu"))]
+    pub tray_handler: Option<egui_winit::TrayHandler>,
+
+    /// Shapes windows and sets up their mouse pass-through regions,
+    /// for [`egui::ViewportBuilder::with_shape`] and [`egui::ViewportBuilder::with_mouse_passthrough_regions`].
+    ///
+    /// If this is `None` eframe uses a built-in `SetWindowRgn` on Windows
+    /// (which can't let clicks through parts of a window) and the shape extension on X11,
+    /// and elsewhere windows stay rectangular.
+    #[cfg(any(feature = "glow", feature = "wgpu"))]
+    pub window_shape_handler: Option<egui_winit::WindowShapeHandler>,
+
     #[cfg(feature = "glow")]
     /// Needed for cross compiling for VirtualBox VMSVGA driver with OpenGL ES 2.0 and OpenGL 2.1 which doesn't support SRGB texture.
     /// See <https://github.com/emilk/egui/pull/1993>.
@@ -410,6 +448,21 @@ impl Clone for NativeOptions {
             #[cfg(any(feature = "glow", feature = "wgpu"))]
             window_builder: None, // Skip any builder callbacks if cloning
 
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            start_drag_handler: self.start_drag_handler.clone(),
+
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            context_menu_handler: self.context_menu_handler.clone(),
+
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            menu_bar_handler: self.menu_bar_handler.clone(),
+
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            tray_handler: self.tray_handler.clone(),
+
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            window_shape_handler: self.window_shape_handler.clone(),
+
             #[cfg(feature = "wgpu")]
             wgpu_options: self.wgpu_options.clone(),
 
@@ -446,6 +499,21 @@ impl Default for NativeOptions {
             #[cfg(any(feature = "glow", feature = "wgpu"))]
             window_builder: None,
 
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            start_drag_handler: None,
+
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            context_menu_handler: None,
+
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            menu_bar_handler: None,
+
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            tray_handler: None,
+
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            window_shape_handler: None,
+
             #[cfg(feature = "glow")]
             shader_version: None,
 
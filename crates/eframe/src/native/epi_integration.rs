@@ -166,6 +166,9 @@ pub struct EpiIntegration {
     #[cfg(feature = "persistence")]
     persist_window: bool,
     app_icon_setter: super::app_icon::AppTitleIconSetter,
+
+    /// Asked again whenever a window is focused, as the user may have changed it in the OS settings.
+    prefers_reduced_motion: Option<bool>,
 }
 
 impl EpiIntegration {
@@ -222,6 +225,7 @@ impl EpiIntegration {
             app_icon_setter,
             beginning: Instant::now(),
             is_first_frame: true,
+            prefers_reduced_motion: prefers_reduced_motion(),
         }
     }
 
@@ -267,6 +271,11 @@ impl EpiIntegration {
     ) -> egui::FullOutput {
         raw_input.time = Some(self.beginning.elapsed().as_secs_f64());
 
+        if raw_input.events.contains(&egui::Event::WindowFocused(true)) {
+            self.prefers_reduced_motion = prefers_reduced_motion();
+        }
+        raw_input.prefers_reduced_motion = self.prefers_reduced_motion;
+
         let close_requested = raw_input.viewport().close_requested();
 
         app.raw_input_hook(&self.egui_ctx, &mut raw_input);
@@ -338,7 +347,10 @@ impl EpiIntegration {
                     epi::set_value(
                         storage,
                         STORAGE_WINDOW_KEY,
-                        &WindowSettings::from_window(self.egui_ctx.zoom_factor(), window),
+                        &WindowSettings::from_window(
+                            self.egui_ctx.effective_zoom_factor(egui::ViewportId::ROOT),
+                            window,
+                        ),
                     );
                 }
             }
@@ -358,6 +370,42 @@ impl EpiIntegration {
     }
 }
 
+/// Ask the OS if the user wants less motion, for [`egui::RawInput::prefers_reduced_motion`].
+///
+/// `None` means unknown, which is always the case on Linux.
+#[allow(unsafe_code)]
+fn prefers_reduced_motion() -> Option<bool> {
+    #[cfg(target_os = "windows")]
+    {
+        use windows_sys::Win32::UI::WindowsAndMessaging::{
+            SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION,
+        };
+
+        let mut animate: windows_sys::Win32::Foundation::BOOL = 1;
+        // SAFETY: SPI_GETCLIENTAREAANIMATION writes a BOOL to the pointer we give it.
+        let ok = unsafe {
+            SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                std::ptr::from_mut(&mut animate).cast(),
+                0,
+            )
+        };
+        return (ok != 0).then_some(animate == 0);
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        // SAFETY: only reads a setting of the shared workspace.
+        return Some(unsafe {
+            objc2_app_kit::NSWorkspace::sharedWorkspace().accessibilityDisplayShouldReduceMotion()
+        });
+    }
+
+    #[allow(unreachable_code)]
+    None
+}
+
 fn load_default_egui_icon() -> egui::IconData {
     profiling::function_scope!();
     crate::icon_data::from_png_bytes(&include_bytes!("../../data/icon.png")[..]).unwrap()
@@ -38,7 +38,7 @@ use crate::{
 
 use super::{
     epi_integration, event_loop_context,
-    winit_integration::{create_egui_context, EventResult, UserEvent, WinitApp},
+    winit_integration::{self, create_egui_context, EventResult, UserEvent, WinitApp},
 };
 
 // ----------------------------------------------------------------------------
@@ -91,6 +91,8 @@ struct GlutinWindowContext {
 
     max_texture_side: Option<usize>,
 
+    platform_handlers: egui_winit::PlatformHandlers,
+
     current_gl_context: Option<glutin::context::PossiblyCurrentContext>,
     not_current_gl_context: Option<glutin::context::NotCurrentContext>,
 
@@ -150,7 +152,7 @@ impl<'app> GlowWinitApp<'app> {
         let window_settings = epi_integration::load_window_settings(storage);
 
         let winit_window_builder = epi_integration::viewport_builder(
-            egui_ctx.zoom_factor(),
+            egui_ctx.effective_zoom_factor(ViewportId::ROOT),
             event_loop,
             native_options,
             window_settings,
@@ -541,7 +543,13 @@ impl GlowWinitRunning<'_> {
             let Some(window) = viewport.window.as_ref() else {
                 return Ok(EventResult::Wait);
             };
-            egui_winit::update_viewport_info(&mut viewport.info, &egui_ctx, window, false);
+            egui_winit::update_viewport_info(
+                &mut viewport.info,
+                &egui_ctx,
+                viewport_id,
+                window,
+                false,
+            );
 
             let Some(egui_winit) = viewport.egui_winit.as_mut() else {
                 return Ok(EventResult::Wait);
@@ -623,8 +631,10 @@ impl GlowWinitRunning<'_> {
         let egui::FullOutput {
             platform_output,
             textures_delta,
+            snapshots,
             shapes,
             pixels_per_point,
+            tessellated,
             viewport_output,
         } = full_output;
 
@@ -646,9 +656,11 @@ impl GlowWinitRunning<'_> {
         let gl_surface = viewport.gl_surface.as_ref().unwrap();
         let egui_winit = viewport.egui_winit.as_mut().unwrap();
 
+        egui_winit.update_custom_cursors(event_loop);
         egui_winit.handle_platform_output(&window, platform_output);
 
-        let clipped_primitives = integration.egui_ctx.tessellate(shapes, pixels_per_point);
+        let clipped_primitives = tessellated
+            .unwrap_or_else(|| integration.egui_ctx.tessellate(shapes, pixels_per_point));
 
         {
             // We may need to switch contexts again, because of immediate viewports:
@@ -663,12 +675,15 @@ impl GlowWinitRunning<'_> {
             painter.clear(screen_size_in_pixels, clear_color);
         }
 
-        painter.paint_and_update_textures(
-            screen_size_in_pixels,
-            pixels_per_point,
-            &clipped_primitives,
-            &textures_delta,
-        );
+        painter.render_snapshots(snapshots);
+        integration.egui_ctx.profile_scope("paint", || {
+            painter.paint_and_update_textures(
+                screen_size_in_pixels,
+                pixels_per_point,
+                &clipped_primitives,
+                &textures_delta,
+            );
+        });
 
         {
             for action in viewport.actions_requested.drain() {
@@ -691,15 +706,7 @@ impl GlowWinitRunning<'_> {
                         egui_winit.egui_input_mut().events.push(egui::Event::Copy);
                     }
                     ActionRequested::Paste => {
-                        if let Some(contents) = egui_winit.clipboard_text() {
-                            let contents = contents.replace("\r\n", "\n");
-                            if !contents.is_empty() {
-                                egui_winit
-                                    .egui_input_mut()
-                                    .events
-                                    .push(egui::Event::Paste(contents));
-                            }
-                        }
+                        egui_winit.paste();
                     }
                 }
             }
@@ -983,7 +990,12 @@ impl GlutinWindowContext {
                 .map_err(|e| crate::Error::NoGlutinConfigs(config_template_builder.build(), e))?
         };
         if let Some(window) = &window {
-            egui_winit::apply_viewport_builder_to_window(egui_ctx, window, &viewport_builder);
+            egui_winit::apply_viewport_builder_to_window(
+                egui_ctx,
+                ViewportId::ROOT,
+                window,
+                &viewport_builder,
+            );
         }
 
         let gl_display = gl_config.display();
@@ -1035,7 +1047,7 @@ impl GlutinWindowContext {
         if let Some(window) = &window {
             viewport_from_window.insert(window.id(), ViewportId::ROOT);
             window_from_viewport.insert(ViewportId::ROOT, window.id());
-            egui_winit::update_viewport_info(&mut info, egui_ctx, window, true);
+            egui_winit::update_viewport_info(&mut info, egui_ctx, ViewportId::ROOT, window, true);
         }
 
         let mut viewports = ViewportIdMap::default();
@@ -1069,6 +1081,7 @@ impl GlutinWindowContext {
             viewports,
             viewport_from_window,
             max_texture_side: None,
+            platform_handlers: winit_integration::platform_handlers(native_options),
             window_from_viewport,
             focused_viewport: Some(ViewportId::ROOT),
         };
@@ -1125,24 +1138,33 @@ impl GlutinWindowContext {
                 glutin_winit::finalize_window(event_loop, window_attributes, &self.gl_config)?;
             egui_winit::apply_viewport_builder_to_window(
                 &self.egui_ctx,
+                viewport_id,
                 &window,
                 &viewport.builder,
             );
 
-            egui_winit::update_viewport_info(&mut viewport.info, &self.egui_ctx, &window, true);
+            egui_winit::update_viewport_info(
+                &mut viewport.info,
+                &self.egui_ctx,
+                viewport_id,
+                &window,
+                true,
+            );
             viewport.window.insert(Arc::new(window))
         };
 
         viewport.egui_winit.get_or_insert_with(|| {
             log::debug!("Initializing egui_winit for viewport {viewport_id:?}");
-            egui_winit::State::new(
+            let mut egui_winit = egui_winit::State::new(
                 self.egui_ctx.clone(),
                 viewport_id,
                 event_loop,
                 Some(window.scale_factor() as f32),
                 event_loop.system_theme(),
                 self.max_texture_side,
-            )
+            );
+            egui_winit.set_platform_handlers(self.platform_handlers.clone());
+            egui_winit
         });
 
         if viewport.gl_surface.is_none() {
@@ -1313,6 +1335,7 @@ impl GlutinWindowContext {
 
                 egui_winit::process_viewport_commands(
                     egui_ctx,
+                    viewport_id,
                     &mut viewport.info,
                     std::mem::take(&mut viewport.deferred_commands),
                     window,
@@ -1449,7 +1472,7 @@ fn render_immediate_viewport(
         let (Some(egui_winit), Some(window)) = (&mut viewport.egui_winit, &viewport.window) else {
             return;
         };
-        egui_winit::update_viewport_info(&mut viewport.info, egui_ctx, window, false);
+        egui_winit::update_viewport_info(&mut viewport.info, egui_ctx, viewport_id, window, false);
 
         let mut raw_input = egui_winit.take_egui_input(window);
         raw_input.viewports = glutin
@@ -1468,8 +1491,10 @@ fn render_immediate_viewport(
     let egui::FullOutput {
         platform_output,
         textures_delta,
+        snapshots,
         shapes,
         pixels_per_point,
+        tessellated,
         viewport_output,
     } = egui_ctx.run(input, |ctx| {
         viewport_ui_cb(ctx);
@@ -1477,7 +1502,8 @@ fn render_immediate_viewport(
 
     // ---------------------------------------------------
 
-    let clipped_primitives = egui_ctx.tessellate(shapes, pixels_per_point);
+    let clipped_primitives =
+        tessellated.unwrap_or_else(|| egui_ctx.tessellate(shapes, pixels_per_point));
 
     let mut glutin = glutin.borrow_mut();
 
@@ -1522,12 +1548,16 @@ fn render_immediate_viewport(
         [0.0, 0.0, 0.0, 0.0],
     );
 
-    painter.borrow_mut().paint_and_update_textures(
-        screen_size_in_pixels,
-        pixels_per_point,
-        &clipped_primitives,
-        &textures_delta,
-    );
+    {
+        let mut painter = painter.borrow_mut();
+        painter.render_snapshots(snapshots);
+        painter.paint_and_update_textures(
+            screen_size_in_pixels,
+            pixels_per_point,
+            &clipped_primitives,
+            &textures_delta,
+        );
+    }
 
     {
         profiling::scope!("swap_buffers");
@@ -1,7 +1,9 @@
 mod app_icon;
 mod epi_integration;
 mod event_loop_context;
+mod native_menus;
 pub mod run;
+mod window_shape;
 
 /// File storage which can be used by native backends.
 #[cfg(feature = "persistence")]
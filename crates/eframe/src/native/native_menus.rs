@@ -0,0 +1,455 @@
+//! Built-in native menus, for [`egui::Response::native_context_menu`], [`egui::native_menu::menu_bar`]
+//! and [`egui::native_menu::TrayIcon`].
+
+/// The context menu handler eframe uses when [`crate::NativeOptions::context_menu_handler`] is `None`.
+///
+/// Only Windows has one. Elsewhere egui draws the context menus itself.
+pub fn default_context_menu_handler() -> Option<egui_winit::ContextMenuHandler> {
+    #[cfg(target_os = "windows")]
+    return Some(std::sync::Arc::new(windows::show_context_menu));
+
+    #[allow(unreachable_code)]
+    None
+}
+
+/// The menu bar handler eframe uses when [`crate::NativeOptions::menu_bar_handler`] is `None`.
+///
+/// Only Windows has one. Elsewhere egui draws the menu bars itself.
+pub fn default_menu_bar_handler() -> Option<egui_winit::MenuBarHandler> {
+    #[cfg(target_os = "windows")]
+    return Some(std::sync::Arc::new(windows::set_menu_bar));
+
+    #[allow(unreachable_code)]
+    None
+}
+
+/// The tray handler eframe uses when [`crate::NativeOptions::tray_handler`] is `None`.
+///
+/// Only Windows has one. Elsewhere there is no tray icon.
+pub fn default_tray_handler() -> Option<egui_winit::TrayHandler> {
+    #[cfg(target_os = "windows")]
+    return Some(std::sync::Arc::new(windows::set_tray_icon));
+
+    #[allow(unreachable_code)]
+    None
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use egui::{
+        native_menu::TrayIcon, IconData, Id, ModifierNames, NativeMenuItem, PointerButton, Pos2,
+        ViewportId,
+    };
+    use egui_winit::NativeEvents;
+    use raw_window_handle::{HasWindowHandle as _, RawWindowHandle};
+    use windows_sys::Win32::{
+        Foundation::{HWND, LPARAM, LRESULT, POINT, WPARAM},
+        UI::{
+            Shell::{
+                DefSubclassProc, GetWindowSubclass, RemoveWindowSubclass, SetWindowSubclass,
+                Shell_NotifyIconW, NIF_ICON, NIF_MESSAGE, NIF_TIP, NIM_ADD, NIM_DELETE, NIM_MODIFY,
+                NOTIFYICONDATAW,
+            },
+            WindowsAndMessaging::{
+                AppendMenuW, CreateIcon, CreateMenu, CreatePopupMenu, DestroyIcon, DestroyMenu,
+                DrawMenuBar, GetCursorPos, GetMenu, PostMessageW, SetForegroundWindow, SetMenu,
+                ShowWindow, TrackPopupMenu, HICON, HMENU, MENU_ITEM_FLAGS, MF_CHECKED, MF_GRAYED,
+                MF_POPUP, MF_SEPARATOR, MF_STRING, SW_RESTORE, SW_SHOW, TPM_LEFTALIGN,
+                TPM_RETURNCMD, TPM_RIGHTBUTTON, TPM_TOPALIGN, WM_APP, WM_COMMAND, WM_LBUTTONUP,
+                WM_NCDESTROY, WM_NULL, WM_RBUTTONUP,
+            },
+        },
+    };
+    use winit::window::Window;
+
+    fn hwnd(window: &Window) -> Option<HWND> {
+        let handle = window.window_handle().ok()?;
+        let RawWindowHandle::Win32(handle) = handle.as_raw() else {
+            return None;
+        };
+        Some(handle.hwnd.get() as HWND)
+    }
+
+    pub fn show_context_menu(window: &Window, pos: Pos2, items: &[NativeMenuItem]) -> Option<Id> {
+        let hwnd = hwnd(window)?;
+
+        let inner_pos = window.inner_position().ok()?;
+        let pixels = pos.to_vec2() * window.scale_factor() as f32;
+        let x = inner_pos.x + pixels.x.round() as i32;
+        let y = inner_pos.y + pixels.y.round() as i32;
+
+        track_popup_menu(hwnd, items, x, y)
+    }
+
+    /// Shows the menu at the given screen position with `TrackPopupMenu`,
+    /// which blocks until the user picks a button or dismisses the menu.
+    #[allow(unsafe_code)]
+    fn track_popup_menu(hwnd: HWND, items: &[NativeMenuItem], x: i32, y: i32) -> Option<Id> {
+        let mut buttons = Vec::new();
+
+        // SAFETY: the menu is only used within this block, and destroyed (with its submenus) at the end.
+        let command = unsafe {
+            let menu = CreatePopupMenu();
+            if menu.is_null() {
+                return None;
+            }
+            append_items(menu, items, &mut buttons);
+            let command = TrackPopupMenu(
+                menu,
+                TPM_RETURNCMD | TPM_RIGHTBUTTON | TPM_LEFTALIGN | TPM_TOPALIGN,
+                x,
+                y,
+                0,
+                hwnd,
+                std::ptr::null(),
+            );
+            DestroyMenu(menu);
+            command
+        };
+
+        button_of_command(&buttons, usize::try_from(command).ok()?)
+    }
+
+    /// What the window procedure of a window with a menu bar needs to report clicks.
+    struct MenuBar {
+        egui_ctx: egui::Context,
+        native_events: NativeEvents,
+        viewport_id: ViewportId,
+        buttons: Vec<Id>,
+    }
+
+    const MENU_BAR_SUBCLASS_ID: usize = 0x6567_7569; // "egui"
+
+    /// Replaces the menu of the window with `SetMenu`,
+    /// and reports clicks (which arrive as `WM_COMMAND`) with [`NativeEvents::native_menu_item_clicked`].
+    #[allow(unsafe_code)]
+    pub fn set_menu_bar(
+        egui_ctx: &egui::Context,
+        native_events: &NativeEvents,
+        window: &Window,
+        viewport_id: ViewportId,
+        items: &[NativeMenuItem],
+    ) {
+        let Some(hwnd) = hwnd(window) else {
+            return;
+        };
+
+        let mut buttons = Vec::new();
+
+        // SAFETY: `hwnd` is the live window we are called for, on its thread.
+        // The `MenuBar` is owned by the subclass: it is freed when it is replaced, or the window is destroyed.
+        unsafe {
+            let menu = CreateMenu();
+            if menu.is_null() {
+                return;
+            }
+            append_items(menu, items, &mut buttons);
+            let menu_bar = Box::into_raw(Box::new(MenuBar {
+                egui_ctx: egui_ctx.clone(),
+                native_events: native_events.clone(),
+                viewport_id,
+                buttons,
+            }));
+
+            let mut previous: usize = 0;
+            let had_menu_bar = GetWindowSubclass(
+                hwnd,
+                Some(menu_bar_proc),
+                MENU_BAR_SUBCLASS_ID,
+                &mut previous,
+            ) != 0;
+            SetWindowSubclass(
+                hwnd,
+                Some(menu_bar_proc),
+                MENU_BAR_SUBCLASS_ID,
+                menu_bar as usize,
+            );
+            if had_menu_bar {
+                drop(Box::from_raw(previous as *mut MenuBar));
+            }
+
+            let previous_menu = GetMenu(hwnd);
+            SetMenu(hwnd, menu);
+            if !previous_menu.is_null() {
+                DestroyMenu(previous_menu);
+            }
+            DrawMenuBar(hwnd);
+        }
+    }
+
+    #[allow(unsafe_code)]
+    unsafe extern "system" fn menu_bar_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+        _subclass_id: usize,
+        ref_data: usize,
+    ) -> LRESULT {
+        // SAFETY: `ref_data` is the `MenuBar` given to `SetWindowSubclass`, which lives until `WM_NCDESTROY`.
+        unsafe {
+            let menu_bar = ref_data as *mut MenuBar;
+            match msg {
+                // The high word is zero for menus, and the low word is the command:
+                WM_COMMAND if (wparam >> 16) & 0xffff == 0 => {
+                    let menu_bar = &*menu_bar;
+                    if let Some(id) = button_of_command(&menu_bar.buttons, wparam & 0xffff) {
+                        menu_bar.native_events.native_menu_item_clicked(
+                            &menu_bar.egui_ctx,
+                            menu_bar.viewport_id,
+                            id,
+                        );
+                        return 0;
+                    }
+                }
+                WM_NCDESTROY => {
+                    RemoveWindowSubclass(hwnd, Some(menu_bar_proc), MENU_BAR_SUBCLASS_ID);
+                    drop(Box::from_raw(menu_bar));
+                }
+                _ => {}
+            }
+            DefSubclassProc(hwnd, msg, wparam, lparam)
+        }
+    }
+
+    /// What the window procedure of a window with a tray icon needs to handle clicks on it.
+    struct Tray {
+        egui_ctx: egui::Context,
+        native_events: NativeEvents,
+        menu: Vec<NativeMenuItem>,
+        hide_on_close: bool,
+        icon: HICON,
+    }
+
+    const TRAY_SUBCLASS_ID: usize = 0x7472_6179; // "tray"
+
+    /// Sent to the window by the tray icon, with the mouse message in `lparam`.
+    const WM_TRAY_ICON: u32 = WM_APP + 0x0e9;
+
+    /// Adds, updates or removes the tray icon with `Shell_NotifyIcon`.
+    ///
+    /// Clicks on the icon arrive at the window, which is shown again on a primary click with
+    /// [`TrayIcon::hide_on_close`], as a hidden window may not run any frames.
+    #[allow(unsafe_code)]
+    pub fn set_tray_icon(
+        egui_ctx: &egui::Context,
+        native_events: &NativeEvents,
+        window: &Window,
+        tray_icon: Option<&TrayIcon>,
+    ) {
+        let Some(hwnd) = hwnd(window) else {
+            return;
+        };
+
+        // SAFETY: `hwnd` is the live window we are called for, on its thread.
+        // The `Tray` is owned by the subclass: it is freed when it is replaced, removed, or the window is destroyed.
+        unsafe {
+            let mut previous: usize = 0;
+            let had_tray =
+                GetWindowSubclass(hwnd, Some(tray_proc), TRAY_SUBCLASS_ID, &mut previous) != 0;
+
+            let mut data = notify_icon_data(hwnd);
+            let Some(tray_icon) = tray_icon else {
+                if had_tray {
+                    Shell_NotifyIconW(NIM_DELETE, &data);
+                    RemoveWindowSubclass(hwnd, Some(tray_proc), TRAY_SUBCLASS_ID);
+                    free_tray(previous);
+                }
+                return;
+            };
+
+            let icon = create_icon(&tray_icon.icon);
+            let tray = Box::into_raw(Box::new(Tray {
+                egui_ctx: egui_ctx.clone(),
+                native_events: native_events.clone(),
+                menu: tray_icon.menu.clone(),
+                hide_on_close: tray_icon.hide_on_close,
+                icon,
+            }));
+            SetWindowSubclass(hwnd, Some(tray_proc), TRAY_SUBCLASS_ID, tray as usize);
+
+            data.uFlags = NIF_MESSAGE | NIF_ICON | NIF_TIP;
+            data.uCallbackMessage = WM_TRAY_ICON;
+            data.hIcon = icon;
+            // Keep the last character for the null terminator:
+            let tooltip = tray_icon.tooltip.encode_utf16().take(data.szTip.len() - 1);
+            for (dst, src) in data.szTip.iter_mut().zip(tooltip) {
+                *dst = src;
+            }
+            Shell_NotifyIconW(if had_tray { NIM_MODIFY } else { NIM_ADD }, &data);
+
+            if had_tray {
+                free_tray(previous);
+            }
+        }
+    }
+
+    #[allow(unsafe_code)]
+    unsafe extern "system" fn tray_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+        _subclass_id: usize,
+        ref_data: usize,
+    ) -> LRESULT {
+        // SAFETY: `ref_data` is the `Tray` given to `SetWindowSubclass`, which lives until it is replaced or removed.
+        unsafe {
+            match msg {
+                WM_TRAY_ICON => {
+                    let tray = &*(ref_data as *const Tray);
+                    match lparam as u32 {
+                        WM_LBUTTONUP => {
+                            if tray.hide_on_close {
+                                ShowWindow(hwnd, SW_SHOW);
+                                ShowWindow(hwnd, SW_RESTORE);
+                                SetForegroundWindow(hwnd);
+                            }
+                            tray.native_events
+                                .tray_icon_clicked(&tray.egui_ctx, PointerButton::Primary);
+                        }
+                        WM_RBUTTONUP => {
+                            tray.native_events
+                                .tray_icon_clicked(&tray.egui_ctx, PointerButton::Secondary);
+                            if !tray.menu.is_empty() {
+                                let mut pos = POINT { x: 0, y: 0 };
+                                GetCursorPos(&mut pos);
+                                // Otherwise the menu doesn't close when clicking elsewhere:
+                                SetForegroundWindow(hwnd);
+                                let clicked = track_popup_menu(hwnd, &tray.menu, pos.x, pos.y);
+                                PostMessageW(hwnd, WM_NULL, 0, 0);
+                                if let Some(id) = clicked {
+                                    tray.native_events.native_menu_item_clicked(
+                                        &tray.egui_ctx,
+                                        ViewportId::ROOT,
+                                        id,
+                                    );
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                    return 0;
+                }
+                WM_NCDESTROY => {
+                    Shell_NotifyIconW(NIM_DELETE, &notify_icon_data(hwnd));
+                    RemoveWindowSubclass(hwnd, Some(tray_proc), TRAY_SUBCLASS_ID);
+                    free_tray(ref_data);
+                }
+                _ => {}
+            }
+            DefSubclassProc(hwnd, msg, wparam, lparam)
+        }
+    }
+
+    /// Identifies the one tray icon of the window.
+    #[allow(unsafe_code)]
+    fn notify_icon_data(hwnd: HWND) -> NOTIFYICONDATAW {
+        // SAFETY: all zeros is a valid `NOTIFYICONDATAW`.
+        let mut data: NOTIFYICONDATAW = unsafe { std::mem::zeroed() };
+        data.cbSize = std::mem::size_of::<NOTIFYICONDATAW>() as u32;
+        data.hWnd = hwnd;
+        data.uID = 1;
+        data
+    }
+
+    /// # Safety
+    /// `tray` must come from `Box::into_raw` of a `Tray`, and not be used afterwards.
+    #[allow(unsafe_code)]
+    unsafe fn free_tray(tray: usize) {
+        // SAFETY: see above.
+        let tray = unsafe { Box::from_raw(tray as *mut Tray) };
+        if !tray.icon.is_null() {
+            // SAFETY: the icon was created by us, and is no longer shown.
+            unsafe { DestroyIcon(tray.icon) };
+        }
+    }
+
+    /// Null if the icon data is invalid.
+    #[allow(unsafe_code)]
+    fn create_icon(icon: &IconData) -> HICON {
+        let (width, height) = (icon.width as usize, icon.height as usize);
+        if icon.rgba.len() != width * height * 4 {
+            return std::ptr::null_mut();
+        }
+        let bgra: Vec<u8> = icon
+            .rgba
+            .chunks_exact(4)
+            .flat_map(|p| [p[2], p[1], p[0], p[3]])
+            .collect();
+        // With 32 bits per pixel the alpha channel is used, and the (word aligned) mask is ignored:
+        let mask = vec![0_u8; width.div_ceil(16) * 2 * height];
+
+        // SAFETY: both buffers have the size `CreateIcon` expects, and are copied by it.
+        unsafe {
+            CreateIcon(
+                std::ptr::null_mut(),
+                width as i32,
+                height as i32,
+                1,
+                32,
+                mask.as_ptr(),
+                bgra.as_ptr(),
+            )
+        }
+    }
+
+    /// The command of a button is its index in `buttons` plus one, as zero means nothing was picked.
+    fn button_of_command(buttons: &[Id], command: usize) -> Option<Id> {
+        buttons.get(command.checked_sub(1)?).copied()
+    }
+
+    #[allow(unsafe_code)]
+    fn append_items(menu: HMENU, items: &[NativeMenuItem], buttons: &mut Vec<Id>) {
+        for item in items {
+            match item {
+                NativeMenuItem::Button(button) => {
+                    buttons.push(button.id);
+
+                    let mut flags: MENU_ITEM_FLAGS = MF_STRING;
+                    if !button.enabled {
+                        flags |= MF_GRAYED;
+                    }
+                    if button.checked == Some(true) {
+                        flags |= MF_CHECKED;
+                    }
+
+                    let mut text = escape_mnemonics(&button.text);
+                    if let Some(shortcut) = &button.shortcut {
+                        // Windows right-aligns whatever comes after a tab:
+                        text += "\t";
+                        text += &shortcut.format(&ModifierNames::NAMES, false);
+                    }
+                    let text = wide(&text);
+                    // SAFETY: the text is null-terminated and outlives the call, which copies it.
+                    unsafe { AppendMenuW(menu, flags, buttons.len(), text.as_ptr()) };
+                }
+                NativeMenuItem::Submenu { text, items } => {
+                    // SAFETY: no preconditions.
+                    let submenu = unsafe { CreatePopupMenu() };
+                    if submenu.is_null() {
+                        continue;
+                    }
+                    append_items(submenu, items, buttons);
+                    let text = wide(&escape_mnemonics(text));
+                    // SAFETY: as above. The submenu is destroyed along with `menu`.
+                    unsafe { AppendMenuW(menu, MF_POPUP, submenu as usize, text.as_ptr()) };
+                }
+                NativeMenuItem::Separator => {
+                    // SAFETY: separators have no text.
+                    unsafe { AppendMenuW(menu, MF_SEPARATOR, 0, std::ptr::null()) };
+                }
+            }
+        }
+    }
+
+    /// A single `&` underlines the next character in a Windows menu.
+    fn escape_mnemonics(text: &str) -> String {
+        text.replace('&', "&&")
+    }
+
+    /// A null-terminated UTF-16 string.
+    fn wide(text: &str) -> Vec<u16> {
+        text.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+}
@@ -68,6 +68,7 @@ pub struct SharedState {
     egui_ctx: egui::Context,
     viewports: Viewports,
     painter: egui_wgpu::winit::Painter,
+    platform_handlers: egui_winit::PlatformHandlers,
     viewport_from_window: HashMap<WindowId, ViewportId>,
     focused_viewport: Option<ViewportId>,
 }
@@ -128,6 +129,7 @@ impl<'app> WgpuWinitApp<'app> {
         let SharedState {
             viewports,
             painter,
+            platform_handlers,
             viewport_from_window,
             ..
         } = &mut *shared;
@@ -138,7 +140,11 @@ impl<'app> WgpuWinitApp<'app> {
                 &running.integration.egui_ctx,
                 viewport_from_window,
                 painter,
+                platform_handlers,
             );
+            if let Some(egui_winit) = &mut viewport.egui_winit {
+                egui_winit.update_custom_cursors(event_loop);
+            }
         }
     }
 
@@ -149,6 +155,7 @@ impl<'app> WgpuWinitApp<'app> {
             viewports,
             viewport_from_window,
             painter,
+            platform_handlers,
             ..
         } = &mut *running.shared.borrow_mut();
 
@@ -160,7 +167,13 @@ impl<'app> WgpuWinitApp<'app> {
             None,
             painter,
         )
-        .initialize_window(event_loop, egui_ctx, viewport_from_window, painter);
+        .initialize_window(
+            event_loop,
+            egui_ctx,
+            viewport_from_window,
+            painter,
+            platform_handlers,
+        );
     }
 
     #[cfg(target_os = "android")]
@@ -236,7 +249,8 @@ impl<'app> WgpuWinitApp<'app> {
             });
         }
 
-        #[allow(unused_mut)] // used for accesskit
+        let platform_handlers = winit_integration::platform_handlers(&self.native_options);
+
         let mut egui_winit = egui_winit::State::new(
             egui_ctx.clone(),
             ViewportId::ROOT,
@@ -245,6 +259,7 @@ impl<'app> WgpuWinitApp<'app> {
             event_loop.system_theme(),
             painter.max_texture_side(),
         );
+        egui_winit.set_platform_handlers(platform_handlers.clone());
 
         #[cfg(feature = "accesskit")]
         {
@@ -275,7 +290,7 @@ impl<'app> WgpuWinitApp<'app> {
         viewport_from_window.insert(window.id(), ViewportId::ROOT);
 
         let mut info = ViewportInfo::default();
-        egui_winit::update_viewport_info(&mut info, &egui_ctx, &window, true);
+        egui_winit::update_viewport_info(&mut info, &egui_ctx, ViewportId::ROOT, &window, true);
 
         let mut viewports = Viewports::default();
         viewports.insert(
@@ -298,6 +313,7 @@ impl<'app> WgpuWinitApp<'app> {
             viewport_from_window,
             viewports,
             painter,
+            platform_handlers,
             focused_viewport: Some(ViewportId::ROOT),
         }));
 
@@ -590,7 +606,13 @@ impl WgpuWinitRunning<'_> {
             let Some(window) = window else {
                 return Ok(EventResult::Wait);
             };
-            egui_winit::update_viewport_info(info, &integration.egui_ctx, window, false);
+            egui_winit::update_viewport_info(
+                info,
+                &integration.egui_ctx,
+                viewport_id,
+                window,
+                false,
+            );
 
             {
                 profiling::scope!("set_window");
@@ -611,6 +633,7 @@ impl WgpuWinitRunning<'_> {
                 .collect();
 
             painter.handle_screenshots(&mut raw_input.events);
+            painter.handle_picks(&mut raw_input.events);
 
             (viewport_ui_cb, raw_input)
         };
@@ -636,8 +659,10 @@ impl WgpuWinitRunning<'_> {
         let FullOutput {
             platform_output,
             textures_delta,
+            snapshots,
             shapes,
             pixels_per_point,
+            tessellated,
             viewport_output,
         } = full_output;
 
@@ -660,7 +685,8 @@ impl WgpuWinitRunning<'_> {
 
         egui_winit.handle_platform_output(window, platform_output);
 
-        let clipped_primitives = egui_ctx.tessellate(shapes, pixels_per_point);
+        let clipped_primitives =
+            tessellated.unwrap_or_else(|| egui_ctx.tessellate(shapes, pixels_per_point));
 
         let mut screenshot_commands = vec![];
         viewport.actions_requested.retain(|cmd| {
@@ -671,14 +697,32 @@ impl WgpuWinitRunning<'_> {
                 true
             }
         });
-        let vsync_secs = painter.paint_and_update_textures(
-            viewport_id,
-            pixels_per_point,
-            app.clear_color(&egui_ctx.style().visuals),
-            &clipped_primitives,
-            &textures_delta,
-            screenshot_commands,
-        );
+        let pick_positions = viewport_output
+            .get(&viewport_id)
+            .map(|output| {
+                output
+                    .commands
+                    .iter()
+                    .filter_map(|command| match command {
+                        egui::ViewportCommand::ReadPick(pos) => Some(*pos),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        painter.read_picks(pick_positions);
+        painter.render_snapshots(snapshots);
+
+        let vsync_secs = egui_ctx.profile_scope("paint", || {
+            painter.paint_and_update_textures(
+                viewport_id,
+                pixels_per_point,
+                app.clear_color(&egui_ctx.style().visuals),
+                &clipped_primitives,
+                &textures_delta,
+                screenshot_commands,
+            )
+        });
 
         for action in viewport.actions_requested.drain() {
             match action {
@@ -692,15 +736,7 @@ impl WgpuWinitRunning<'_> {
                     egui_winit.egui_input_mut().events.push(egui::Event::Copy);
                 }
                 ActionRequested::Paste => {
-                    if let Some(contents) = egui_winit.clipboard_text() {
-                        let contents = contents.replace("\r\n", "\n");
-                        if !contents.is_empty() {
-                            egui_winit
-                                .egui_input_mut()
-                                .events
-                                .push(egui::Event::Paste(contents));
-                        }
-                    }
+                    egui_winit.paste();
                 }
             }
         }
@@ -857,6 +893,7 @@ impl Viewport {
         egui_ctx: &egui::Context,
         windows_id: &mut HashMap<WindowId, ViewportId>,
         painter: &mut egui_wgpu::winit::Painter,
+        platform_handlers: &egui_winit::PlatformHandlers,
     ) {
         if self.window.is_some() {
             return; // we already have one
@@ -866,7 +903,7 @@ impl Viewport {
 
         let viewport_id = self.ids.this;
 
-        match egui_winit::create_window(egui_ctx, event_loop, &self.builder) {
+        match egui_winit::create_window(egui_ctx, viewport_id, event_loop, &self.builder) {
             Ok(window) => {
                 windows_id.insert(window.id(), viewport_id);
 
@@ -878,16 +915,24 @@ impl Viewport {
                     log::error!("on set_window: viewport_id {viewport_id:?} {err}");
                 }
 
-                self.egui_winit = Some(egui_winit::State::new(
+                let mut egui_winit = egui_winit::State::new(
                     egui_ctx.clone(),
                     viewport_id,
                     event_loop,
                     Some(window.scale_factor() as f32),
                     event_loop.system_theme(),
                     painter.max_texture_side(),
-                ));
+                );
+                egui_winit.set_platform_handlers(platform_handlers.clone());
+                self.egui_winit = Some(egui_winit);
 
-                egui_winit::update_viewport_info(&mut self.info, egui_ctx, &window, true);
+                egui_winit::update_viewport_info(
+                    &mut self.info,
+                    egui_ctx,
+                    viewport_id,
+                    &window,
+                    true,
+                );
                 self.window = Some(window);
             }
             Err(err) => {
@@ -907,14 +952,15 @@ fn create_window(
 
     let window_settings = epi_integration::load_window_settings(storage);
     let viewport_builder = epi_integration::viewport_builder(
-        egui_ctx.zoom_factor(),
+        egui_ctx.effective_zoom_factor(ViewportId::ROOT),
         event_loop,
         native_options,
         window_settings,
     )
     .with_visible(false); // Start hidden until we render the first frame to fix white flash on startup (https://github.com/emilk/egui/pull/3631)
 
-    let window = egui_winit::create_window(egui_ctx, event_loop, &viewport_builder)?;
+    let window =
+        egui_winit::create_window(egui_ctx, ViewportId::ROOT, event_loop, &viewport_builder)?;
     epi_integration::apply_window_settings(&window, window_settings);
     Ok((window, viewport_builder))
 }
@@ -937,6 +983,7 @@ fn render_immediate_viewport(
             egui_ctx,
             viewports,
             painter,
+            platform_handlers,
             viewport_from_window,
             ..
         } = &mut *shared.borrow_mut();
@@ -951,14 +998,20 @@ fn render_immediate_viewport(
         );
         if viewport.window.is_none() {
             event_loop_context::with_current_event_loop(|event_loop| {
-                viewport.initialize_window(event_loop, egui_ctx, viewport_from_window, painter);
+                viewport.initialize_window(
+                    event_loop,
+                    egui_ctx,
+                    viewport_from_window,
+                    painter,
+                    platform_handlers,
+                );
             });
         }
 
         let (Some(window), Some(egui_winit)) = (&viewport.window, &mut viewport.egui_winit) else {
             return;
         };
-        egui_winit::update_viewport_info(&mut viewport.info, egui_ctx, window, false);
+        egui_winit::update_viewport_info(&mut viewport.info, egui_ctx, ids.this, window, false);
 
         let mut input = egui_winit.take_egui_input(window);
         input.viewports = viewports
@@ -978,8 +1031,10 @@ fn render_immediate_viewport(
     let egui::FullOutput {
         platform_output,
         textures_delta,
+        snapshots,
         shapes,
         pixels_per_point,
+        tessellated,
         viewport_output,
     } = egui_ctx.run(input, |ctx| {
         viewport_ui_cb(ctx);
@@ -1013,7 +1068,9 @@ fn render_immediate_viewport(
         }
     }
 
-    let clipped_primitives = egui_ctx.tessellate(shapes, pixels_per_point);
+    let clipped_primitives =
+        tessellated.unwrap_or_else(|| egui_ctx.tessellate(shapes, pixels_per_point));
+    painter.render_snapshots(snapshots);
     painter.paint_and_update_textures(
         ids.this,
         pixels_per_point,
@@ -1080,6 +1137,7 @@ fn handle_viewport_output(
 
             egui_winit::process_viewport_commands(
                 egui_ctx,
+                viewport_id,
                 &mut viewport.info,
                 std::mem::take(&mut viewport.deferred_commands),
                 window,
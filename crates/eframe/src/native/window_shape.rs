@@ -0,0 +1,232 @@
+//! Built-in window shapes, for [`egui::ViewportBuilder::with_shape`]
+//! and [`egui::ViewportBuilder::with_mouse_passthrough_regions`].
+
+/// The window shape handler eframe uses when [`crate::NativeOptions::window_shape_handler`] is `None`.
+///
+/// There is one for Windows, and one for X11 (with the `x11` feature).
+/// Elsewhere windows stay rectangular.
+pub fn default_window_shape_handler() -> Option<egui_winit::WindowShapeHandler> {
+    #[cfg(target_os = "windows")]
+    return Some(std::sync::Arc::new(windows::set_window_shape));
+
+    #[cfg(all(feature = "x11", target_os = "linux"))]
+    return Some(std::sync::Arc::new(x11::set_window_shape));
+
+    #[allow(unreachable_code)]
+    None
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use egui::{Rect, WindowShape};
+    use raw_window_handle::{HasWindowHandle as _, RawWindowHandle};
+    use windows_sys::Win32::{
+        Foundation::{HWND, POINT, TRUE},
+        Graphics::Gdi::{CreatePolyPolygonRgn, DeleteObject, SetWindowRgn, ALTERNATE},
+    };
+    use winit::window::Window;
+
+    /// Shapes the window with `SetWindowRgn`.
+    ///
+    /// Windows has no mouse pass-through regions: the region is where the window is drawn *and* clicked,
+    /// so the regions are ignored.
+    #[allow(unsafe_code)]
+    pub fn set_window_shape(window: &Window, shape: &WindowShape, passthrough_regions: &[Rect]) {
+        let Ok(handle) = window.window_handle() else {
+            return;
+        };
+        let RawWindowHandle::Win32(handle) = handle.as_raw() else {
+            return;
+        };
+        let hwnd = handle.hwnd.get() as HWND;
+
+        if !passthrough_regions.is_empty() {
+            log::debug!("Mouse pass-through regions are not supported on Windows");
+        }
+
+        if shape.is_empty() {
+            // SAFETY: `hwnd` is the live window we are called for. A null region makes it rectangular again.
+            unsafe {
+                SetWindowRgn(hwnd, std::ptr::null_mut(), TRUE);
+            }
+            return;
+        }
+
+        // The region is relative to the outer rect, the shape to the inner rect:
+        let offset = match (window.inner_position(), window.outer_position()) {
+            (Ok(inner), Ok(outer)) => {
+                egui::vec2((inner.x - outer.x) as f32, (inner.y - outer.y) as f32)
+            }
+            _ => egui::Vec2::ZERO,
+        };
+
+        let polygons = shape.polygons.iter().filter(|polygon| 3 <= polygon.len());
+        let points: Vec<POINT> = polygons
+            .clone()
+            .flatten()
+            .map(|point| POINT {
+                x: (point.x + offset.x).round() as i32,
+                y: (point.y + offset.y).round() as i32,
+            })
+            .collect();
+        let counts: Vec<i32> = polygons
+            .map(|polygon| i32::try_from(polygon.len()).unwrap_or(i32::MAX))
+            .collect();
+
+        // SAFETY: `points` and `counts` outlive the calls, and hold as many points as `counts` add up to.
+        // The window owns the region once `SetWindowRgn` succeeds, otherwise we delete it.
+        unsafe {
+            let region = CreatePolyPolygonRgn(
+                points.as_ptr(),
+                counts.as_ptr(),
+                counts.len() as i32,
+                ALTERNATE,
+            );
+            if region.is_null() {
+                log::warn!("CreatePolyPolygonRgn failed");
+                return;
+            }
+            if SetWindowRgn(hwnd, region, TRUE) == 0 {
+                log::warn!("SetWindowRgn failed");
+                DeleteObject(region);
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "x11", target_os = "linux"))]
+mod x11 {
+    use std::sync::OnceLock;
+
+    use egui::{Rect, WindowShape};
+    use raw_window_handle::{HasWindowHandle as _, RawWindowHandle};
+    use winit::window::Window;
+    use x11rb::{
+        connection::Connection as _,
+        protocol::{
+            shape::{ConnectionExt as _, SK, SO},
+            xproto::{ClipOrdering, Rectangle},
+        },
+        rust_connection::RustConnection,
+    };
+
+    /// A connection of our own to the X server of the windows, as winit doesn't share its own.
+    fn connection() -> Option<&'static RustConnection> {
+        static CONNECTION: OnceLock<Option<RustConnection>> = OnceLock::new();
+        CONNECTION
+            .get_or_init(|| match x11rb::connect(None) {
+                Ok((connection, _screen)) => Some(connection),
+                Err(err) => {
+                    log::warn!("Failed to connect to the X server to shape windows: {err}");
+                    None
+                }
+            })
+            .as_ref()
+    }
+
+    fn to_rectangle(rect: Rect) -> Rectangle {
+        let rect = Rect::from_min_max(rect.min.round(), rect.max.round());
+        Rectangle {
+            x: rect.min.x as i16,
+            y: rect.min.y as i16,
+            width: rect.width() as u16,
+            height: rect.height() as u16,
+        }
+    }
+
+    /// Shapes the window with the X11 shape extension:
+    /// the bounding region is where the window is drawn, the input region where it is clicked.
+    pub fn set_window_shape(window: &Window, shape: &WindowShape, passthrough_regions: &[Rect]) {
+        let Ok(handle) = window.window_handle() else {
+            return;
+        };
+        let xid = match handle.as_raw() {
+            RawWindowHandle::Xlib(handle) => handle.window as u32,
+            RawWindowHandle::Xcb(handle) => handle.window.get(),
+            _ => {
+                log::debug!("Window shapes are only supported on X11, not Wayland");
+                return;
+            }
+        };
+        let Some(connection) = connection() else {
+            return;
+        };
+
+        // The X server clips the regions to the window, so cover all it could grow to:
+        let everything = Rectangle {
+            x: 0,
+            y: 0,
+            width: i16::MAX as u16,
+            height: i16::MAX as u16,
+        };
+        let shape_rects: Vec<Rectangle> = if shape.is_empty() {
+            vec![]
+        } else {
+            let bounds = shape.bounding_rect().intersect(Rect::from_min_max(
+                egui::Pos2::ZERO,
+                egui::pos2(32767.0, 32767.0),
+            ));
+            shape
+                .to_rects(bounds)
+                .into_iter()
+                .map(to_rectangle)
+                .collect()
+        };
+        let passthrough: Vec<Rectangle> = passthrough_regions
+            .iter()
+            .copied()
+            .map(to_rectangle)
+            .collect();
+
+        let result = (|| {
+            if shape.is_empty() {
+                connection.shape_mask(SO::SET, SK::BOUNDING, xid, 0, 0, x11rb::NONE)?;
+            } else {
+                connection.shape_rectangles(
+                    SO::SET,
+                    SK::BOUNDING,
+                    ClipOrdering::UNSORTED,
+                    xid,
+                    0,
+                    0,
+                    &shape_rects,
+                )?;
+            }
+
+            if passthrough.is_empty() {
+                // The input region follows the bounding region:
+                connection.shape_mask(SO::SET, SK::INPUT, xid, 0, 0, x11rb::NONE)?;
+            } else {
+                let input = if shape.is_empty() {
+                    std::slice::from_ref(&everything)
+                } else {
+                    &shape_rects
+                };
+                connection.shape_rectangles(
+                    SO::SET,
+                    SK::INPUT,
+                    ClipOrdering::UNSORTED,
+                    xid,
+                    0,
+                    0,
+                    input,
+                )?;
+                connection.shape_rectangles(
+                    SO::SUBTRACT,
+                    SK::INPUT,
+                    ClipOrdering::UNSORTED,
+                    xid,
+                    0,
+                    0,
+                    &passthrough,
+                )?;
+            }
+
+            connection.flush()
+        })();
+
+        if let Err(err) = result {
+            log::warn!("Failed to shape the window: {err}");
+        }
+    }
+}
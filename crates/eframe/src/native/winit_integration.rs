@@ -9,6 +9,30 @@ use egui::ViewportId;
 #[cfg(feature = "accesskit")]
 use egui_winit::accesskit_winit;
 
+/// The platform handlers of the [`crate::NativeOptions`], for the [`egui_winit::State`] of each viewport.
+pub fn platform_handlers(native_options: &crate::NativeOptions) -> egui_winit::PlatformHandlers {
+    egui_winit::PlatformHandlers {
+        start_drag: native_options.start_drag_handler.clone(),
+        context_menu: native_options
+            .context_menu_handler
+            .clone()
+            .or_else(super::native_menus::default_context_menu_handler),
+        menu_bar: native_options
+            .menu_bar_handler
+            .clone()
+            .or_else(super::native_menus::default_menu_bar_handler),
+        tray: native_options
+            .tray_handler
+            .clone()
+            .or_else(super::native_menus::default_tray_handler),
+        window_shape: native_options
+            .window_shape_handler
+            .clone()
+            .or_else(super::window_shape::default_window_shape_handler),
+        native_events: Default::default(),
+    }
+}
+
 /// Create an egui context, restoring it from storage if possible.
 pub fn create_egui_context(storage: Option<&dyn crate::Storage>) -> egui::Context {
     profiling::function_scope!();
@@ -22,6 +22,7 @@ pub struct AppRunner {
 
     // Output for the last run:
     textures_delta: TexturesDelta,
+    snapshots: Vec<egui::TextureSnapshot>,
     clipped_primitives: Option<Vec<egui::ClippedPrimitive>>,
 }
 
@@ -63,6 +64,9 @@ impl AppRunner {
             // A user can still zoom egui separately by calling [`egui::Context::set_zoom_factor`].
             o.zoom_with_keyboard = false;
             o.zoom_factor = 1.0;
+
+            // We don't turn cursor images into CSS cursors, so let egui paint them:
+            o.paint_custom_cursors = true;
         });
 
         let cc = epi::CreationContext {
@@ -116,6 +120,7 @@ impl AppRunner {
             text_agent,
             screenshot_commands_with_frame_delay: vec![],
             textures_delta: Default::default(),
+            snapshots: Vec::new(),
             clipped_primitives: None,
         };
 
@@ -128,6 +133,7 @@ impl AppRunner {
             .or_default()
             .native_pixels_per_point = Some(super::native_pixels_per_point());
         runner.input.raw.system_theme = super::system_theme();
+        runner.input.raw.prefers_reduced_motion = super::prefers_reduced_motion();
 
         Ok(runner)
     }
@@ -236,8 +242,10 @@ impl AppRunner {
         let egui::FullOutput {
             platform_output,
             textures_delta,
+            snapshots,
             shapes,
             pixels_per_point,
+            tessellated,
             viewport_output,
         } = full_output;
 
@@ -251,6 +259,9 @@ impl AppRunner {
                         self.screenshot_commands_with_frame_delay
                             .push((user_data, 1));
                     }
+                    ViewportCommand::ReadPick(_) => {
+                        // Picking is not supported in the web backend yet.
+                    }
                     _ => {
                         // TODO(emilk): handle some of the commands
                         log::warn!(
@@ -263,12 +274,15 @@ impl AppRunner {
 
         self.handle_platform_output(platform_output);
         self.textures_delta.append(textures_delta);
-        self.clipped_primitives = Some(self.egui_ctx.tessellate(shapes, pixels_per_point));
+        self.snapshots.extend(snapshots);
+        self.clipped_primitives =
+            Some(tessellated.unwrap_or_else(|| self.egui_ctx.tessellate(shapes, pixels_per_point)));
     }
 
     /// Paint the results of the last call to [`Self::logic`].
     pub fn paint(&mut self) {
         let textures_delta = std::mem::take(&mut self.textures_delta);
+        let snapshots = std::mem::take(&mut self.snapshots);
         let clipped_primitives = std::mem::take(&mut self.clipped_primitives);
 
         if let Some(clipped_primitives) = clipped_primitives {
@@ -292,6 +306,7 @@ impl AppRunner {
                 &clipped_primitives,
                 self.egui_ctx.pixels_per_point(),
                 &textures_delta,
+                &snapshots,
                 screenshot_commands,
             ) {
                 log::error!("Failed to paint: {}", super::string_from_js_value(&err));
@@ -333,10 +348,17 @@ impl AppRunner {
                 egui::OutputCommand::CopyImage(image) => {
                     super::set_clipboard_image(&image);
                 }
+                egui::OutputCommand::Copy(data) => {
+                    super::set_clipboard_data(&data);
+                }
                 egui::OutputCommand::OpenUrl(open_url) => {
                     super::open_url(&open_url.url, open_url.new_tab);
                 }
-                egui::OutputCommand::SetPointerPosition(_) => {
+                egui::OutputCommand::SetPointerPosition(_)
+                | egui::OutputCommand::StartDrag(_)
+                | egui::OutputCommand::ShowContextMenu { .. }
+                | egui::OutputCommand::SetMenuBar(_)
+                | egui::OutputCommand::SetTrayIcon(_) => {
                     // Not supported on the web.
                 }
             }
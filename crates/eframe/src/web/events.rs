@@ -304,25 +304,93 @@ pub(crate) fn on_keyup(event: web_sys::KeyboardEvent, runner: &mut AppRunner) {
     }
 }
 
+/// Read and decode a pasted image, and send it together with the other pasted representations.
+fn paste_with_image(
+    runner_ref: &WebRunner,
+    file: &web_sys::File,
+    mut pasted: Vec<egui::ClipboardData>,
+) {
+    let future = wasm_bindgen_futures::JsFuture::from(file.array_buffer());
+    let runner_ref = runner_ref.clone();
+    let future = async move {
+        match future.await {
+            Ok(array_buffer) => {
+                let bytes = js_sys::Uint8Array::new(&array_buffer).to_vec();
+                match super::from_image_bytes(&bytes) {
+                    Ok(image) => pasted.push(egui::ClipboardData::Image(image)),
+                    Err(err) => log::warn!("Failed to decode pasted image: {err}"),
+                }
+            }
+            Err(err) => {
+                log::error!(
+                    "Failed to read pasted image: {}",
+                    string_from_js_value(&err)
+                );
+            }
+        }
+
+        if !pasted.is_empty() {
+            if let Some(mut runner_lock) = runner_ref.try_lock() {
+                runner_lock
+                    .input
+                    .raw
+                    .events
+                    .push(egui::Event::PasteData(pasted));
+                runner_lock.needs_repaint.repaint_asap();
+            }
+        }
+    };
+    wasm_bindgen_futures::spawn_local(future);
+}
+
 fn install_copy_cut_paste(runner_ref: &WebRunner, target: &EventTarget) -> Result<(), JsValue> {
-    runner_ref.add_event_listener(target, "paste", |event: web_sys::ClipboardEvent, runner| {
-        if let Some(data) = event.clipboard_data() {
-            if let Ok(text) = data.get_data("text") {
-                let text = text.replace("\r\n", "\n");
+    runner_ref.add_event_listener(target, "paste", {
+        let runner_ref = runner_ref.clone();
 
-                let mut should_propagate = false;
-                if !text.is_empty() && runner.input.raw.focused {
-                    let egui_event = egui::Event::Paste(text);
-                    should_propagate = (runner.web_options.should_propagate_event)(&egui_event);
-                    runner.input.raw.events.push(egui_event);
-                    runner.needs_repaint.repaint_asap();
-                }
+        move |event: web_sys::ClipboardEvent, runner| {
+            if let Some(data) = event.clipboard_data() {
+                if let Ok(text) = data.get_data("text") {
+                    let text = text.replace("\r\n", "\n");
+
+                    let mut should_propagate = false;
+                    if runner.input.raw.focused {
+                        let mut pasted = Vec::new();
+                        if !text.is_empty() {
+                            let egui_event = egui::Event::Paste(text.clone());
+                            should_propagate =
+                                (runner.web_options.should_propagate_event)(&egui_event);
+                            runner.input.raw.events.push(egui_event);
+                            pasted.push(egui::ClipboardData::Text(text.clone()));
+                        }
+                        if let Ok(html) = data.get_data("text/html") {
+                            if !html.is_empty() {
+                                pasted.push(egui::ClipboardData::Html {
+                                    html,
+                                    alt_text: text,
+                                });
+                            }
+                        }
 
-                // Use web options to tell if the web event should be propagated to parent elements based on the egui event.
-                if !should_propagate {
-                    event.stop_propagation();
+                        // Pasted images (e.g. screenshots) come as files, which are read asynchronously:
+                        let image_file = data.files().and_then(|files| {
+                            (0..files.length())
+                                .filter_map(|i| files.get(i))
+                                .find(|file| file.type_().starts_with("image/"))
+                        });
+                        if let Some(file) = image_file {
+                            paste_with_image(&runner_ref, &file, pasted);
+                        } else if !pasted.is_empty() {
+                            runner.input.raw.events.push(egui::Event::PasteData(pasted));
+                            runner.needs_repaint.repaint_asap();
+                        }
+                    }
+
+                    // Use web options to tell if the web event should be propagated to parent elements based on the egui event.
+                    if !should_propagate {
+                        event.stop_propagation();
+                    }
+                    event.prevent_default();
                 }
-                event.prevent_default();
             }
         }
     })?;
@@ -786,6 +854,7 @@ fn install_drag_and_drop(runner_ref: &WebRunner, target: &EventTarget) -> Result
     runner_ref.add_event_listener(target, "dragover", |event: web_sys::DragEvent, runner| {
         if let Some(data_transfer) = event.data_transfer() {
             runner.input.raw.hovered_files.clear();
+            let pos = pos_from_mouse_event(runner.canvas(), &event, runner.egui_ctx());
 
             // NOTE: data_transfer.files() is always empty in dragover
 
@@ -794,6 +863,7 @@ fn install_drag_and_drop(runner_ref: &WebRunner, target: &EventTarget) -> Result
                 if let Some(item) = items.get(i) {
                     runner.input.raw.hovered_files.push(egui::HoveredFile {
                         mime: item.type_(),
+                        pos: Some(pos),
                         ..Default::default()
                     });
                 }
@@ -801,11 +871,10 @@ fn install_drag_and_drop(runner_ref: &WebRunner, target: &EventTarget) -> Result
 
             if runner.input.raw.hovered_files.is_empty() {
                 // Fallback: just preview anything. Needed on Desktop Safari.
-                runner
-                    .input
-                    .raw
-                    .hovered_files
-                    .push(egui::HoveredFile::default());
+                runner.input.raw.hovered_files.push(egui::HoveredFile {
+                    pos: Some(pos),
+                    ..Default::default()
+                });
             }
 
             runner.needs_repaint.repaint_asap();
@@ -829,6 +898,7 @@ fn install_drag_and_drop(runner_ref: &WebRunner, target: &EventTarget) -> Result
                 // TODO(https://github.com/emilk/egui/issues/3702): support dropping folders
                 runner.input.raw.hovered_files.clear();
                 runner.needs_repaint.repaint_asap();
+                let pos = pos_from_mouse_event(runner.canvas(), &event, runner.egui_ctx());
 
                 if let Some(files) = data_transfer.files() {
                     for i in 0..files.length() {
@@ -856,6 +926,7 @@ fn install_drag_and_drop(runner_ref: &WebRunner, target: &EventTarget) -> Result
                                                     mime,
                                                     last_modified: Some(last_modified),
                                                     bytes: Some(bytes.into()),
+                                                    pos: Some(pos),
                                                     ..Default::default()
                                                 },
                                             );
@@ -119,6 +119,16 @@ pub fn system_theme() -> Option<egui::Theme> {
     Some(theme_from_dark_mode(dark_mode))
 }
 
+/// Ask the browser if the user wants less motion.
+///
+/// `None` means unknown.
+pub fn prefers_reduced_motion() -> Option<bool> {
+    let media_query_list = web_sys::window()?
+        .match_media("(prefers-reduced-motion: reduce)")
+        .ok()??;
+    Some(media_query_list.matches())
+}
+
 fn prefers_color_scheme_dark(window: &web_sys::Window) -> Result<Option<MediaQueryList>, JsValue> {
     window.match_media("(prefers-color-scheme: dark)")
 }
@@ -222,9 +232,7 @@ fn set_clipboard_image(image: &egui::ColorImage) {
             }
         };
 
-        let mime = "image/png";
-
-        let item = match create_clipboard_item(mime, &png_bytes) {
+        let item = match create_clipboard_item(&[("image/png", png_bytes)]) {
             Ok(item) => item,
             Err(err) => {
                 log::error!("Failed to copy image: {}", string_from_js_value(&err));
@@ -246,6 +254,74 @@ fn set_clipboard_image(image: &egui::ColorImage) {
     }
 }
 
+/// Put all representations we support on the clipboard, as one clipboard item.
+///
+/// RTF is not supported by browsers, and is skipped.
+/// Custom MIME types are written as [web custom formats](https://developer.chrome.com/blog/web-custom-formats-for-the-async-clipboard-api),
+/// which other browsers may refuse.
+fn set_clipboard_data(data: &[egui::ClipboardData]) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    if !window.is_secure_context() {
+        log::error!(
+            "Clipboard is not available because we are not in a secure context. \
+            See https://developer.mozilla.org/en-US/docs/Web/Security/Secure_Contexts"
+        );
+        return;
+    }
+
+    let mut parts: Vec<(String, Vec<u8>)> = Vec::new();
+    for item in data {
+        match item {
+            egui::ClipboardData::Text(text) => {
+                parts.push(("text/plain".to_owned(), text.as_bytes().to_vec()));
+            }
+            egui::ClipboardData::Html { html, alt_text } => {
+                parts.push(("text/html".to_owned(), html.as_bytes().to_vec()));
+                parts.push(("text/plain".to_owned(), alt_text.as_bytes().to_vec()));
+            }
+            egui::ClipboardData::Image(image) => {
+                match to_image(image).and_then(|image| to_png_bytes(&image)) {
+                    Ok(png_bytes) => parts.push(("image/png".to_owned(), png_bytes)),
+                    Err(err) => log::error!("Failed to encode image to png: {err}"),
+                }
+            }
+            egui::ClipboardData::Rtf(_) => {
+                log::debug!("Copying RTF is not supported on the web");
+            }
+            egui::ClipboardData::Custom { mime, data } => {
+                parts.push((format!("web {mime}"), data.clone()));
+            }
+        }
+    }
+
+    // Earlier items are preferred, so keep the first of each type:
+    let mut seen = std::collections::HashSet::new();
+    parts.retain(|(mime, _)| seen.insert(mime.clone()));
+    if parts.is_empty() {
+        log::warn!("None of the clipboard formats are supported: {data:?}");
+        return;
+    }
+
+    let item = match create_clipboard_item(&parts) {
+        Ok(item) => item,
+        Err(err) => {
+            log::error!("Failed to copy: {}", string_from_js_value(&err));
+            return;
+        }
+    };
+    let items = js_sys::Array::of1(&item);
+    let promise = window.navigator().clipboard().write(&items);
+    let future = wasm_bindgen_futures::JsFuture::from(promise);
+    let future = async move {
+        if let Err(err) = future.await {
+            log::error!("Copy/cut action failed: {}", string_from_js_value(&err));
+        }
+    };
+    wasm_bindgen_futures::spawn_local(future);
+}
+
 fn to_image(image: &egui::ColorImage) -> Result<image::RgbaImage, String> {
     profiling::function_scope!();
     image::RgbaImage::from_raw(
@@ -256,6 +332,18 @@ fn to_image(image: &egui::ColorImage) -> Result<image::RgbaImage, String> {
     .ok_or_else(|| "Invalid IconData".to_owned())
 }
 
+/// Decode an image file, e.g. a pasted PNG.
+fn from_image_bytes(bytes: &[u8]) -> Result<egui::ColorImage, String> {
+    profiling::function_scope!();
+    let image = image::load_from_memory(bytes).map_err(|err| err.to_string())?;
+    let image = image.to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(egui::ColorImage::from_rgba_unmultiplied(
+        size,
+        image.as_flat_samples().as_slice(),
+    ))
+}
+
 fn to_png_bytes(image: &image::RgbaImage) -> Result<Vec<u8>, String> {
     profiling::function_scope!();
     let mut png_bytes: Vec<u8> = Vec::new();
@@ -268,23 +356,30 @@ fn to_png_bytes(image: &image::RgbaImage) -> Result<Vec<u8>, String> {
     Ok(png_bytes)
 }
 
-fn create_clipboard_item(mime: &str, bytes: &[u8]) -> Result<web_sys::ClipboardItem, JsValue> {
-    let array = js_sys::Uint8Array::from(bytes);
-    let blob_parts = js_sys::Array::new();
-    blob_parts.push(&array);
+/// Create a clipboard item with one blob per `(mime, bytes)`.
+fn create_clipboard_item(
+    parts: &[(impl AsRef<str>, Vec<u8>)],
+) -> Result<web_sys::ClipboardItem, JsValue> {
+    let items = js_sys::Object::new();
 
-    let options = web_sys::BlobPropertyBag::new();
-    options.set_type(mime);
+    for (mime, bytes) in parts {
+        let mime = mime.as_ref();
 
-    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&blob_parts, &options)?;
+        let array = js_sys::Uint8Array::from(bytes.as_slice());
+        let blob_parts = js_sys::Array::new();
+        blob_parts.push(&array);
 
-    let items = js_sys::Object::new();
+        let options = web_sys::BlobPropertyBag::new();
+        options.set_type(mime);
 
-    // SAFETY: I hope so
-    #[allow(unsafe_code, unused_unsafe)] // Weird false positive
-    unsafe {
-        js_sys::Reflect::set(&items, &JsValue::from_str(mime), &blob)?
-    };
+        let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&blob_parts, &options)?;
+
+        // SAFETY: I hope so
+        #[allow(unsafe_code, unused_unsafe)] // Weird false positive
+        unsafe {
+            js_sys::Reflect::set(&items, &JsValue::from_str(mime), &blob)?
+        };
+    }
 
     let clipboard_item = web_sys::ClipboardItem::new_with_record_from_str_to_blob_promise(&items)?;
 
@@ -330,6 +425,7 @@ fn cursor_web_name(cursor: egui::CursorIcon) -> &'static str {
         egui::CursorIcon::Wait => "wait",
         egui::CursorIcon::ZoomIn => "zoom-in",
         egui::CursorIcon::ZoomOut => "zoom-out",
+        egui::CursorIcon::Custom(_) => "default",
     }
 }
 
@@ -16,7 +16,7 @@ pub(crate) trait WebPainter {
     /// Maximum size of a texture in one direction.
     fn max_texture_side(&self) -> usize;
 
-    /// Update all internal textures and paint gui.
+    /// Update all internal textures, render the `snapshots` into their textures, and paint gui.
     /// When `capture` isn't empty, the rendered screen should be captured.
     /// Once the screenshot is ready, the screenshot should be returned via [`Self::handle_screenshots`].
     fn paint_and_update_textures(
@@ -25,6 +25,7 @@ pub(crate) trait WebPainter {
         clipped_primitives: &[egui::ClippedPrimitive],
         pixels_per_point: f32,
         textures_delta: &egui::TexturesDelta,
+        snapshots: &[egui::TextureSnapshot],
         capture: Vec<UserData>,
     ) -> Result<(), JsValue>;
 
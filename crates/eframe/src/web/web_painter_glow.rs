@@ -56,6 +56,7 @@ impl WebPainter for WebPainterGlow {
         clipped_primitives: &[egui::ClippedPrimitive],
         pixels_per_point: f32,
         textures_delta: &egui::TexturesDelta,
+        snapshots: &[egui::TextureSnapshot],
         capture: Vec<UserData>,
     ) -> Result<(), JsValue> {
         let canvas_dimension = [self.canvas.width(), self.canvas.height()];
@@ -64,6 +65,10 @@ impl WebPainter for WebPainterGlow {
             self.painter.set_texture(*id, image_delta);
         }
 
+        for snapshot in snapshots {
+            self.painter.render_snapshot(snapshot);
+        }
+
         egui_glow::painter::clear(self.painter.gl(), canvas_dimension, clear_color);
         self.painter
             .paint_primitives(canvas_dimension, pixels_per_point, clipped_primitives);
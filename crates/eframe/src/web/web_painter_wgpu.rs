@@ -129,6 +129,7 @@ impl WebPainter for WebPainterWgpu {
         clipped_primitives: &[egui::ClippedPrimitive],
         pixels_per_point: f32,
         textures_delta: &egui::TexturesDelta,
+        snapshots: &[egui::TextureSnapshot],
         capture_data: Vec<UserData>,
     ) -> Result<(), JsValue> {
         let capture = !capture_data.is_empty();
@@ -164,6 +165,9 @@ impl WebPainter for WebPainterWgpu {
                     image_delta,
                 );
             }
+            for snapshot in snapshots {
+                renderer.render_snapshot(&render_state.device, &render_state.queue, snapshot);
+            }
 
             renderer.update_buffers(
                 &render_state.device,
@@ -2,16 +2,20 @@
 
 struct VertexOutput {
     @location(0) tex_coord: vec2<f32>,
-    @location(1) color: vec4<f32>, // gamma 0-1
+    @location(1) color: vec4<f32>, // gamma 0-1, or linear (and maybe above 1) for the HDR pipeline
     @builtin(position) position: vec4<f32>,
 };
 
 struct Locals {
     screen_size: vec2<f32>,
     dithering: u32, // 1 if dithering is enabled, 0 otherwise
-    // Uniform buffers need to be at least 16 bytes in WebGL.
+    tone_mapping: u32, // HDR only. 0: none, 1: clamp, 2: Reinhard, 3: ACES filmic
+    white_level: f32, // HDR only. Multiplier for all colors.
+    output_display_p3: u32, // 1 if the output is in Display P3, 0 if sRGB
+    // Uniform buffers need to be at least 16 bytes in WebGL, and a multiple of 16.
     // See https://github.com/gfx-rs/wgpu/issues/2072
-    _padding: u32,
+    _padding1: u32,
+    _padding2: u32,
 };
 @group(0) @binding(0) var<uniform> r_locals: Locals;
 
@@ -58,6 +62,27 @@ fn gamma_from_linear_rgba(linear_rgba: vec4<f32>) -> vec4<f32> {
     return vec4<f32>(gamma_from_linear_rgb(linear_rgba.rgb), linear_rgba.a);
 }
 
+// linear Display P3  from  linear sRGB
+fn display_p3_from_srgb(rgb: vec3<f32>) -> vec3<f32> {
+    // Columns of the conversion matrix:
+    let m = mat3x3<f32>(
+        vec3<f32>(0.8224621, 0.0331941, 0.0170827),
+        vec3<f32>(0.1775380, 0.9668058, 0.0723974),
+        vec3<f32>(0.0, 0.0, 0.9105199),
+    );
+    return m * rgb;
+}
+
+// Vertex colors are always sRGB, so convert them if we render to a Display P3 target.
+// Input and output are premultiplied and gamma-encoded.
+fn output_from_srgba_gamma(rgba: vec4<f32>) -> vec4<f32> {
+    if r_locals.output_display_p3 == 0u || rgba.a <= 0.0 {
+        return rgba;
+    }
+    let linear = linear_from_gamma_rgb(rgba.rgb / rgba.a);
+    return vec4<f32>(gamma_from_linear_rgb(display_p3_from_srgb(linear)) * rgba.a, rgba.a);
+}
+
 // [u8; 4] SRGB as u32 -> [r, g, b, a] in 0.-1
 fn unpack_color(color: u32) -> vec4<f32> {
     return vec4<f32>(
@@ -85,7 +110,7 @@ fn vs_main(
 ) -> VertexOutput {
     var out: VertexOutput;
     out.tex_coord = a_tex_coord;
-    out.color = unpack_color(a_color);
+    out.color = output_from_srgba_gamma(unpack_color(a_color));
     out.position = position_from_screen(a_pos);
     return out;
 }
@@ -127,3 +152,60 @@ fn fs_main_gamma_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
     }
     return out_color_gamma;
 }
+
+// -----------------------------------------------
+// HDR pipeline, used for floating point render targets.
+// Colors are blended in linear space, and may be brighter than white.
+
+@vertex
+fn vs_main_hdr(
+    @location(0) a_pos: vec2<f32>,
+    @location(1) a_tex_coord: vec2<f32>,
+    @location(3) a_hdr_color: vec4<f32>, // linear, premultiplied
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.tex_coord = a_tex_coord;
+    out.color = a_hdr_color;
+    if r_locals.output_display_p3 != 0u {
+        out.color = vec4<f32>(display_p3_from_srgb(a_hdr_color.rgb), a_hdr_color.a);
+    }
+    out.position = position_from_screen(a_pos);
+    return out;
+}
+
+fn tone_map(rgb: vec3<f32>) -> vec3<f32> {
+    switch r_locals.tone_mapping {
+        case 1u: {
+            return clamp(rgb, vec3<f32>(0.0), vec3<f32>(1.0));
+        }
+        case 2u: {
+            return rgb / (vec3<f32>(1.0) + rgb);
+        }
+        case 3u: {
+            // Narkowicz 2015, "ACES Filmic Tone Mapping Curve"
+            let mapped = (rgb * (2.51 * rgb + 0.03)) / (rgb * (2.43 * rgb + 0.59) + 0.14);
+            return clamp(mapped, vec3<f32>(0.0), vec3<f32>(1.0));
+        }
+        default: {
+            return rgb;
+        }
+    }
+}
+
+// Apply the white level and tone mapping to a linear, premultiplied color.
+fn hdr_output(color: vec4<f32>) -> vec4<f32> {
+    let rgb = color.rgb * r_locals.white_level;
+    if color.a > 0.0 {
+        // Tone map the unmultiplied color:
+        return vec4<f32>(tone_map(rgb / color.a) * color.a, color.a);
+    } else {
+        return vec4<f32>(tone_map(rgb), color.a);
+    }
+}
+
+@fragment
+fn fs_main_hdr(in: VertexOutput) -> @location(0) vec4<f32> {
+    // We always have an sRGB aware texture at the moment, so this is linear.
+    let tex_linear = textureSample(r_tex_color, r_tex_sampler, in.tex_coord);
+    return hdr_output(in.color * tex_linear);
+}
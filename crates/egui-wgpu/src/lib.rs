@@ -31,6 +31,9 @@ pub use setup::{NativeAdapterSelectorMethod, WgpuSetup, WgpuSetupCreateNew, Wgpu
 /// Helpers for capturing screenshots of the UI.
 pub mod capture;
 
+/// Helpers for reading back the pick ids of [`epaint::Shape::Pick`] groups.
+pub mod picking;
+
 /// Module for painting [`egui`](https://github.com/emilk/egui) with [`wgpu`] on [`winit`].
 #[cfg(feature = "winit")]
 pub mod winit;
@@ -0,0 +1,25 @@
+// Entry points for custom mesh shaders, see `Renderer::register_mesh_shader`.
+//
+// This is appended to `egui.wgsl` and the user code, which implements:
+// fn mesh_color(in: VertexOutput) -> vec4<f32> // premultiplied, gamma 0-1
+
+@fragment
+fn fs_mesh_gamma_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
+    return mesh_color(in);
+}
+
+@fragment
+fn fs_mesh_linear_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color_gamma = mesh_color(in);
+    return vec4<f32>(linear_from_gamma_rgb(color_gamma.rgb), color_gamma.a);
+}
+
+@fragment
+fn fs_mesh_hdr(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color_gamma = mesh_color(in);
+    var rgb = linear_from_gamma_rgb(color_gamma.rgb);
+    if r_locals.output_display_p3 != 0u {
+        rgb = display_p3_from_srgb(rgb);
+    }
+    return hdr_output(vec4<f32>(rgb, color_gamma.a));
+}
@@ -0,0 +1,37 @@
+// Entry points for rendering pick ids, see `Renderer::render_pick_ids`.
+//
+// This is appended to `egui.wgsl`.
+// The pick id of each mesh is passed as its instance index.
+
+struct PickVertexOutput {
+    @location(0) tex_coord: vec2<f32>,
+    @location(1) alpha: f32,
+    @location(2) @interpolate(flat) pick_id: u32,
+    @builtin(position) position: vec4<f32>,
+};
+
+@vertex
+fn vs_pick(
+    @location(0) a_pos: vec2<f32>,
+    @location(1) a_tex_coord: vec2<f32>,
+    @location(2) a_color: u32,
+    @builtin(instance_index) pick_id: u32,
+) -> PickVertexOutput {
+    var out: PickVertexOutput;
+    out.tex_coord = a_tex_coord;
+    out.alpha = unpack_color(a_color).a;
+    out.pick_id = pick_id;
+    out.position = position_from_screen(a_pos);
+    return out;
+}
+
+@fragment
+fn fs_pick(in: PickVertexOutput) -> @location(0) u32 {
+    // Only the mostly opaque parts can be picked,
+    // so that feathering and transparent texels (e.g. around glyphs) don't count:
+    let alpha = in.alpha * textureSample(r_tex_color, r_tex_sampler, in.tex_coord).a;
+    if alpha < 0.5 {
+        discard;
+    }
+    return in.pick_id;
+}
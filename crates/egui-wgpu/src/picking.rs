@@ -0,0 +1,165 @@
+use egui::{mutex::Mutex, Pos2, ViewportId, ViewportIdMap};
+use std::sync::{mpsc, Arc};
+
+/// The format of the texture [`crate::Renderer::render_pick_ids`] renders into.
+pub const PICK_TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R32Uint;
+
+pub type PickReceiver = mpsc::Receiver<(ViewportId, Vec<(Pos2, u32)>)>;
+pub type PickSender = mpsc::Sender<(ViewportId, Vec<(Pos2, u32)>)>;
+pub use mpsc::channel as pick_channel;
+
+/// A texture with the [`epaint::Mesh::pick_id`] of every pixel,
+/// and the means to read some of them back to the cpu.
+pub struct PickState {
+    pub texture: wgpu::Texture,
+    view: wgpu::TextureView,
+
+    /// The last pick ids read back for each viewport,
+    /// so we only repaint when they change.
+    previous: Arc<Mutex<ViewportIdMap<Vec<(Pos2, u32)>>>>,
+}
+
+impl PickState {
+    pub fn new(device: &wgpu::Device, size: wgpu::Extent3d) -> Self {
+        let (texture, view) = Self::create_texture(device, size);
+        Self {
+            texture,
+            view,
+            previous: Default::default(),
+        }
+    }
+
+    fn create_texture(
+        device: &wgpu::Device,
+        size: wgpu::Extent3d,
+    ) -> (wgpu::Texture, wgpu::TextureView) {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("egui_pick_texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: PICK_TEXTURE_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&Default::default());
+        (texture, view)
+    }
+
+    /// Updates the [`PickState`] if the size of the surface texture has changed
+    pub fn update(&mut self, device: &wgpu::Device, size: wgpu::Extent3d) {
+        if self.texture.size() != size {
+            (self.texture, self.view) = Self::create_texture(device, size);
+        }
+    }
+
+    /// Render into this with [`crate::Renderer::render_pick_ids`].
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    /// Copy the pick ids at the given positions (in points) to a buffer.
+    /// Pass the returned buffer to [`PickState::read_pick_ids`] to read them back to the cpu.
+    ///
+    /// Positions outside of the texture get the pick id 0.
+    pub fn copy_pick_ids(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        positions: &[Pos2],
+        pixels_per_point: f32,
+    ) -> wgpu::Buffer {
+        let texel_size = std::mem::size_of::<u32>() as u64;
+
+        // Buffers are zero-initialized, so positions we skip read back as 0.
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("egui_pick_buffer"),
+            size: texel_size * positions.len().max(1) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let size = self.texture.size();
+        for (i, pos) in positions.iter().enumerate() {
+            let pixel = (pixels_per_point * *pos).floor();
+            if pixel.x < 0.0
+                || pixel.y < 0.0
+                || size.width as f32 <= pixel.x
+                || size.height as f32 <= pixel.y
+            {
+                continue;
+            }
+
+            encoder.copy_texture_to_buffer(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &self.texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d {
+                        x: pixel.x as u32,
+                        y: pixel.y as u32,
+                        z: 0,
+                    },
+                    aspect: wgpu::TextureAspect::All,
+                },
+                wgpu::TexelCopyBufferInfo {
+                    buffer: &buffer,
+                    layout: wgpu::TexelCopyBufferLayout {
+                        offset: texel_size * i as u64,
+                        bytes_per_row: None,
+                        rows_per_image: None,
+                    },
+                },
+                wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+
+        buffer
+    }
+
+    /// Reads back the pick ids copied with [`PickState::copy_pick_ids`].
+    /// This function is non-blocking and will send the results to the given sender when they are ready,
+    /// and repaint the viewport if they changed since last time.
+    /// Make sure to call this after the encoder has been submitted.
+    pub fn read_pick_ids(
+        &self,
+        ctx: egui::Context,
+        buffer: wgpu::Buffer,
+        positions: Vec<Pos2>,
+        tx: PickSender,
+        viewport_id: ViewportId,
+    ) {
+        #[allow(clippy::arc_with_non_send_sync)]
+        let buffer = Arc::new(buffer);
+        let buffer_clone = buffer.clone();
+        let buffer_slice = buffer_clone.slice(..);
+        let previous = self.previous.clone();
+        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+            if let Err(err) = result {
+                log::error!("Failed to map buffer for reading: {:?}", err);
+                return;
+            }
+
+            let picks: Vec<(Pos2, u32)> = {
+                let data = buffer.slice(..).get_mapped_range();
+                let pick_ids: &[u32] = bytemuck::cast_slice(&data);
+                positions
+                    .into_iter()
+                    .zip(pick_ids.iter().copied())
+                    .collect()
+            };
+            buffer.unmap();
+
+            let changed =
+                previous.lock().insert(viewport_id, picks.clone()).as_ref() != Some(&picks);
+            tx.send((viewport_id, picks)).ok();
+            if changed {
+                ctx.request_repaint_of(viewport_id);
+            }
+        });
+    }
+}
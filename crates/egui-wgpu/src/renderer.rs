@@ -3,7 +3,7 @@
 use std::{borrow::Cow, num::NonZeroU64, ops::Range};
 
 use ahash::HashMap;
-use epaint::{emath::NumExt, PaintCallbackInfo, Primitive, Vertex};
+use epaint::{ecolor::ColorSpace, emath::NumExt, PaintCallbackInfo, Primitive, RgbaF16, Vertex};
 
 use wgpu::util::DeviceExt as _;
 
@@ -120,6 +120,27 @@ pub trait CallbackTrait: Send + Sync {
     );
 }
 
+/// What egui-wgpu passes as `backend` to [`epaint::BackendCallback::prepare`].
+///
+/// To [`epaint::BackendCallback::paint`] it passes the `wgpu::RenderPass<'static>`.
+/// The [`epaint::CallbackResources`] are kept in [`Renderer::callback_resources`].
+pub struct CallbackPrepare {
+    pub device: wgpu::Device,
+    pub queue: wgpu::Queue,
+
+    /// The color format of the render pass the callback paints in.
+    pub target_format: wgpu::TextureFormat,
+
+    /// The depth format of the render pass the callback paints in, if it has a depth buffer.
+    pub depth_format: Option<wgpu::TextureFormat>,
+
+    /// Number of samples of the render pass the callback paints in.
+    pub msaa_samples: u32,
+
+    /// Command buffers to submit before egui's own, e.g. to render into a texture.
+    pub command_buffers: Vec<wgpu::CommandBuffer>,
+}
+
 /// Information about the screen used for rendering.
 pub struct ScreenDescriptor {
     /// Size of the window in physical pixels.
@@ -139,21 +160,83 @@ impl ScreenDescriptor {
     }
 }
 
+/// How egui colors are written to a floating point (HDR) render target.
+///
+/// See [`Renderer::new_hdr`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HdrOptions {
+    /// All colors are multiplied by this.
+    ///
+    /// Use this to match egui's white with the "paper white" of the rest of your HDR content.
+    /// Default: `1.0`.
+    pub white_level: f32,
+
+    /// How to map colors brighter than white to what the render target can show.
+    pub tone_mapping: ToneMapping,
+}
+
+impl Default for HdrOptions {
+    fn default() -> Self {
+        Self {
+            white_level: 1.0,
+            tone_mapping: ToneMapping::default(),
+        }
+    }
+}
+
+/// How the HDR pipeline maps extended range colors, see [`HdrOptions`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ToneMapping {
+    /// Write the colors as they are, e.g. if you tone-map the whole frame later yourself.
+    #[default]
+    None,
+
+    /// Clamp to 0-1.
+    Clamp,
+
+    /// Reinhard: `c / (1 + c)`.
+    ///
+    /// Note that this makes normal egui colors darker too.
+    Reinhard,
+
+    /// An approximation of the ACES filmic curve (Narkowicz 2015).
+    ///
+    /// Note that this changes the look of normal egui colors too.
+    AcesFilmic,
+}
+
+impl ToneMapping {
+    fn as_u32(self) -> u32 {
+        match self {
+            Self::None => 0,
+            Self::Clamp => 1,
+            Self::Reinhard => 2,
+            Self::AcesFilmic => 3,
+        }
+    }
+}
+
 /// Uniform buffer used when rendering.
 #[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
 #[repr(C)]
 struct UniformBuffer {
     screen_size_in_points: [f32; 2],
     dithering: u32,
-    // Uniform buffers need to be at least 16 bytes in WebGL.
+    tone_mapping: u32,
+    white_level: f32,
+    output_display_p3: u32,
+    // Uniform buffers need to be at least 16 bytes in WebGL, and a multiple of 16.
     // See https://github.com/gfx-rs/wgpu/issues/2072
-    _padding: u32,
+    _padding: [u32; 2],
 }
 
 impl PartialEq for UniformBuffer {
     fn eq(&self, other: &Self) -> bool {
         self.screen_size_in_points == other.screen_size_in_points
             && self.dithering == other.dithering
+            && self.tone_mapping == other.tone_mapping
+            && self.white_level == other.white_level
+            && self.output_display_p3 == other.output_display_p3
     }
 }
 
@@ -175,18 +258,168 @@ pub struct Texture {
     pub options: Option<epaint::textures::TextureOptions>,
 }
 
+/// A mesh shader that converts packed YUV textures to RGB, for use with `egui::VideoSurface`.
+///
+/// ```ignore
+/// let yuv_shader = renderer.register_mesh_shader(device, egui_wgpu::YUV_MESH_SHADER);
+/// let surface = egui::VideoSurface::new("video").yuv_shader(Some(yuv_shader));
+/// ```
+pub const YUV_MESH_SHADER: &str = include_str!("yuv_mesh_shader.wgsl");
+
+/// Pipelines and uniforms of custom mesh shaders, see [`Renderer::register_mesh_shader`].
+struct MeshShaders {
+    pipelines: HashMap<epaint::MeshShaderId, wgpu::RenderPipeline>,
+    next_id: u64,
+    pipeline_layout: wgpu::PipelineLayout,
+    uniform_bind_group_layout: wgpu::BindGroupLayout,
+
+    /// One slot of [`Self::UNIFORM_SLOT_SIZE`] bytes for each mesh with a shader.
+    uniform_buffer: wgpu::Buffer,
+    uniform_bind_group: wgpu::BindGroup,
+}
+
+impl MeshShaders {
+    /// The size of the uniforms of each mesh.
+    ///
+    /// This is also the largest `min_uniform_buffer_offset_alignment` wgpu allows.
+    const UNIFORM_SLOT_SIZE: u64 = epaint::MeshShader::MAX_UNIFORMS_SIZE as u64;
+
+    fn new(
+        device: &wgpu::Device,
+        uniform_bind_group_layout: &wgpu::BindGroupLayout,
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        let mesh_uniform_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("egui_mesh_uniform_bind_group_layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        has_dynamic_offset: true,
+                        min_binding_size: NonZeroU64::new(Self::UNIFORM_SLOT_SIZE),
+                        ty: wgpu::BufferBindingType::Uniform,
+                    },
+                    count: None,
+                }],
+            });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("egui_mesh_shader_pipeline_layout"),
+            bind_group_layouts: &[
+                uniform_bind_group_layout,
+                texture_bind_group_layout,
+                &mesh_uniform_bind_group_layout,
+            ],
+            push_constant_ranges: &[],
+        });
+        let (uniform_buffer, uniform_bind_group) =
+            Self::create_uniform_buffer(device, &mesh_uniform_bind_group_layout, 16);
+
+        Self {
+            pipelines: HashMap::default(),
+            next_id: 0,
+            pipeline_layout,
+            uniform_bind_group_layout: mesh_uniform_bind_group_layout,
+            uniform_buffer,
+            uniform_bind_group,
+        }
+    }
+
+    fn create_uniform_buffer(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        num_slots: u64,
+    ) -> (wgpu::Buffer, wgpu::BindGroup) {
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("egui_mesh_uniform_buffer"),
+            size: num_slots * Self::UNIFORM_SLOT_SIZE,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("egui_mesh_uniform_bind_group"),
+            layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                    buffer: &buffer,
+                    offset: 0,
+                    size: NonZeroU64::new(Self::UNIFORM_SLOT_SIZE),
+                }),
+            }],
+        });
+        (buffer, bind_group)
+    }
+
+    /// Upload the uniforms of all meshes with a shader, in order.
+    fn update_uniforms(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        paint_jobs: &[epaint::ClippedPrimitive],
+    ) {
+        let slot_size = Self::UNIFORM_SLOT_SIZE as usize;
+        let mut data = Vec::new();
+        for clipped_primitive in paint_jobs {
+            if let Primitive::Mesh(mesh) = &clipped_primitive.primitive {
+                if let Some(shader) = &mesh.shader {
+                    let start = data.len();
+                    data.resize(start + slot_size, 0);
+                    if slot_size < shader.uniforms.len() {
+                        log::warn!(
+                            "Mesh shader uniforms are {} bytes, but at most {slot_size} are supported",
+                            shader.uniforms.len()
+                        );
+                    }
+                    let len = shader.uniforms.len().min(slot_size);
+                    data[start..start + len].copy_from_slice(&shader.uniforms[..len]);
+                }
+            }
+        }
+        if data.is_empty() {
+            return;
+        }
+
+        if self.uniform_buffer.size() < data.len() as u64 {
+            let num_slots = (data.len() / slot_size).next_power_of_two() as u64;
+            (self.uniform_buffer, self.uniform_bind_group) =
+                Self::create_uniform_buffer(device, &self.uniform_bind_group_layout, num_slots);
+        }
+        queue.write_buffer(&self.uniform_buffer, 0, &data);
+    }
+}
+
 /// Renderer for a egui based GUI.
 pub struct Renderer {
     pipeline: wgpu::RenderPipeline,
+    pipeline_layout: wgpu::PipelineLayout,
+    shader_module: wgpu::ShaderModule,
+
+    /// For rendering into egui textures, see [`Self::render_snapshot`]. Created when first needed.
+    snapshot_pipeline: Option<wgpu::RenderPipeline>,
+
+    /// For rendering pick ids, see [`Self::render_pick_ids`]. Created when first needed.
+    pick_pipeline: Option<wgpu::RenderPipeline>,
 
     index_buffer: SlicedBuffer,
     vertex_buffer: SlicedBuffer,
 
+    /// Per-vertex [`epaint::RgbaF16`] colors, only used by the HDR pipeline.
+    hdr_color_buffer: Option<SlicedBuffer>,
+
     uniform_buffer: wgpu::Buffer,
     previous_uniform_buffer_content: UniformBuffer,
     uniform_bind_group: wgpu::BindGroup,
+    uniform_bind_group_layout: wgpu::BindGroupLayout,
     texture_bind_group_layout: wgpu::BindGroupLayout,
 
+    output_color_format: wgpu::TextureFormat,
+    depth_stencil: Option<wgpu::DepthStencilState>,
+    msaa_samples: u32,
+
+    /// Created when the first one is registered, see [`Self::register_mesh_shader`].
+    mesh_shaders: Option<MeshShaders>,
+
     /// Map of egui texture IDs to textures and their associated bindgroups (texture view +
     /// sampler). The texture may be None if the `TextureId` is just a handle to a user-provided
     /// sampler.
@@ -196,6 +429,10 @@ pub struct Renderer {
 
     dithering: bool,
 
+    hdr: Option<HdrOptions>,
+
+    output_color_space: ColorSpace,
+
     /// Storage for resources shared with all invocations of [`CallbackTrait`]'s methods.
     ///
     /// See also [`CallbackTrait`].
@@ -213,6 +450,47 @@ impl Renderer {
         output_depth_format: Option<wgpu::TextureFormat>,
         msaa_samples: u32,
         dithering: bool,
+    ) -> Self {
+        Self::new_impl(
+            device,
+            output_color_format,
+            output_depth_format,
+            msaa_samples,
+            dithering,
+            None,
+        )
+    }
+
+    /// Creates a renderer for a floating point (HDR) render target,
+    /// e.g. [`wgpu::TextureFormat::Rgba16Float`].
+    ///
+    /// Colors are blended in linear space and written as linear values,
+    /// without being clamped to the 0-1 range.
+    /// Meshes with [`epaint::Mesh::hdr_colors`] can be brighter than white.
+    pub fn new_hdr(
+        device: &wgpu::Device,
+        output_color_format: wgpu::TextureFormat,
+        output_depth_format: Option<wgpu::TextureFormat>,
+        msaa_samples: u32,
+        hdr: HdrOptions,
+    ) -> Self {
+        Self::new_impl(
+            device,
+            output_color_format,
+            output_depth_format,
+            msaa_samples,
+            false,
+            Some(hdr),
+        )
+    }
+
+    fn new_impl(
+        device: &wgpu::Device,
+        output_color_format: wgpu::TextureFormat,
+        output_depth_format: Option<wgpu::TextureFormat>,
+        msaa_samples: u32,
+        dithering: bool,
+        hdr: Option<HdrOptions>,
     ) -> Self {
         profiling::function_scope!();
 
@@ -230,6 +508,9 @@ impl Renderer {
             contents: bytemuck::cast_slice(&[UniformBuffer {
                 screen_size_in_points: [0.0, 0.0],
                 dithering: u32::from(dithering),
+                tone_mapping: hdr.unwrap_or_default().tone_mapping.as_u32(),
+                white_level: hdr.unwrap_or_default().white_level,
+                output_display_p3: 0,
                 _padding: Default::default(),
             }]),
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
@@ -307,79 +588,39 @@ impl Renderer {
             bias: wgpu::DepthBiasState::default(),
         });
 
-        let pipeline = {
-            profiling::scope!("create_render_pipeline");
-            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-                label: Some("egui_pipeline"),
-                layout: Some(&pipeline_layout),
-                vertex: wgpu::VertexState {
-                    entry_point: Some("vs_main"),
-                    module: &module,
-                    buffers: &[wgpu::VertexBufferLayout {
-                        array_stride: 5 * 4,
-                        step_mode: wgpu::VertexStepMode::Vertex,
-                        // 0: vec2 position
-                        // 1: vec2 texture coordinates
-                        // 2: uint color
-                        attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Uint32],
-                    }],
-                    compilation_options: wgpu::PipelineCompilationOptions::default()
-                },
-                primitive: wgpu::PrimitiveState {
-                    topology: wgpu::PrimitiveTopology::TriangleList,
-                    unclipped_depth: false,
-                    conservative: false,
-                    cull_mode: None,
-                    front_face: wgpu::FrontFace::default(),
-                    polygon_mode: wgpu::PolygonMode::default(),
-                    strip_index_format: None,
-                },
-                depth_stencil,
-                multisample: wgpu::MultisampleState {
-                    alpha_to_coverage_enabled: false,
-                    count: msaa_samples,
-                    mask: !0,
-                },
-
-                fragment: Some(wgpu::FragmentState {
-                    module: &module,
-                    entry_point: Some(if output_color_format.is_srgb() {
-                        log::warn!("Detected a linear (sRGBA aware) framebuffer {:?}. egui prefers Rgba8Unorm or Bgra8Unorm", output_color_format);
-                        "fs_main_linear_framebuffer"
-                    } else {
-                        "fs_main_gamma_framebuffer" // this is what we prefer
-                    }),
-                    targets: &[Some(wgpu::ColorTargetState {
-                        format: output_color_format,
-                        blend: Some(wgpu::BlendState {
-                            color: wgpu::BlendComponent {
-                                src_factor: wgpu::BlendFactor::One,
-                                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                                operation: wgpu::BlendOperation::Add,
-                            },
-                            alpha: wgpu::BlendComponent {
-                                src_factor: wgpu::BlendFactor::OneMinusDstAlpha,
-                                dst_factor: wgpu::BlendFactor::One,
-                                operation: wgpu::BlendOperation::Add,
-                            },
-                        }),
-                        write_mask: wgpu::ColorWrites::ALL,
-                    })],
-                    compilation_options: wgpu::PipelineCompilationOptions::default()
-                }),
-                multiview: None,
-                cache: None,
-            }
-        )
+        let fragment_entry_point = if hdr.is_some() {
+            "fs_main_hdr"
+        } else if output_color_format.is_srgb() {
+            log::warn!("Detected a linear (sRGBA aware) framebuffer {:?}. egui prefers Rgba8Unorm or Bgra8Unorm", output_color_format);
+            "fs_main_linear_framebuffer"
+        } else {
+            "fs_main_gamma_framebuffer" // this is what we prefer
         };
 
+        let pipeline = create_render_pipeline(
+            device,
+            &pipeline_layout,
+            &module,
+            hdr.is_some(),
+            fragment_entry_point,
+            output_color_format,
+            depth_stencil.clone(),
+            msaa_samples,
+        );
+
         const VERTEX_BUFFER_START_CAPACITY: wgpu::BufferAddress =
             (std::mem::size_of::<Vertex>() * 1024) as _;
         const INDEX_BUFFER_START_CAPACITY: wgpu::BufferAddress =
             (std::mem::size_of::<u32>() * 1024 * 3) as _;
+        const HDR_COLOR_BUFFER_START_CAPACITY: wgpu::BufferAddress =
+            (std::mem::size_of::<RgbaF16>() * 1024) as _;
 
         Self {
             pipeline,
+            pipeline_layout,
+            shader_module: module,
+            snapshot_pipeline: None,
+            pick_pipeline: None,
             vertex_buffer: SlicedBuffer {
                 buffer: create_vertex_buffer(device, VERTEX_BUFFER_START_CAPACITY),
                 slices: Vec::with_capacity(64),
@@ -390,23 +631,66 @@ impl Renderer {
                 slices: Vec::with_capacity(64),
                 capacity: INDEX_BUFFER_START_CAPACITY,
             },
+            hdr_color_buffer: hdr.map(|_| SlicedBuffer {
+                buffer: create_vertex_buffer(device, HDR_COLOR_BUFFER_START_CAPACITY),
+                slices: Vec::with_capacity(64),
+                capacity: HDR_COLOR_BUFFER_START_CAPACITY,
+            }),
             uniform_buffer,
             // Buffers on wgpu are zero initialized, so this is indeed its current state!
             previous_uniform_buffer_content: UniformBuffer {
                 screen_size_in_points: [0.0, 0.0],
                 dithering: 0,
-                _padding: 0,
+                tone_mapping: 0,
+                white_level: 0.0,
+                output_display_p3: 0,
+                _padding: Default::default(),
             },
             uniform_bind_group,
+            uniform_bind_group_layout,
             texture_bind_group_layout,
+            output_color_format,
+            depth_stencil,
+            msaa_samples,
+            mesh_shaders: None,
             textures: HashMap::default(),
             next_user_texture_id: 0,
             samplers: HashMap::default(),
             dithering,
+            hdr,
+            output_color_space: ColorSpace::Srgb,
             callback_resources: CallbackResources::default(),
         }
     }
 
+    /// Change the [`HdrOptions`] of a renderer created with [`Self::new_hdr`].
+    ///
+    /// Takes effect on the next call to [`Self::update_buffers`].
+    pub fn set_hdr_options(&mut self, options: HdrOptions) {
+        if self.hdr.is_some() {
+            self.hdr = Some(options);
+        } else {
+            log::warn!("HDR options can only be set on a renderer created with Renderer::new_hdr");
+        }
+    }
+
+    /// Set the color space of the render target, e.g. [`ColorSpace::DisplayP3`] for a wide-gamut surface.
+    ///
+    /// egui colors are in sRGB, and would look too saturated if written as-is to a P3 surface.
+    /// With this set, vertex colors are converted in the shader,
+    /// and textures are converted on upload according to [`epaint::textures::TextureOptions::color_space`].
+    ///
+    /// Call this before any textures are uploaded, since textures are not converted again.
+    /// Default: [`ColorSpace::Srgb`].
+    pub fn set_output_color_space(&mut self, color_space: ColorSpace) {
+        self.output_color_space = color_space;
+    }
+
+    /// The color space of the render target, see [`Self::set_output_color_space`].
+    pub fn output_color_space(&self) -> ColorSpace {
+        self.output_color_space
+    }
+
     /// Executes the egui renderer onto an existing wgpu renderpass.
     ///
     /// Note that the lifetime of `render_pass` is `'static` which requires a call to [`wgpu::RenderPass::forget_lifetime`].
@@ -421,7 +705,27 @@ impl Renderer {
         screen_descriptor: &ScreenDescriptor,
     ) {
         profiling::function_scope!();
+        self.render_with_pipeline(
+            &self.pipeline,
+            self.mesh_shaders.as_ref(),
+            false,
+            render_pass,
+            paint_jobs,
+            screen_descriptor,
+        );
+    }
 
+    /// If `pick_ids` is set, only meshes with a [`epaint::Mesh::pick_id`] are drawn,
+    /// with the pick id as the instance index.
+    fn render_with_pipeline(
+        &self,
+        pipeline: &wgpu::RenderPipeline,
+        mesh_shaders: Option<&MeshShaders>,
+        pick_ids: bool,
+        render_pass: &mut wgpu::RenderPass<'static>,
+        paint_jobs: &[epaint::ClippedPrimitive],
+        screen_descriptor: &ScreenDescriptor,
+    ) {
         let pixels_per_point = screen_descriptor.pixels_per_point;
         let size_in_pixels = screen_descriptor.size_in_pixels;
 
@@ -431,6 +735,14 @@ impl Renderer {
 
         let mut index_buffer_slices = self.index_buffer.slices.iter();
         let mut vertex_buffer_slices = self.vertex_buffer.slices.iter();
+        let mut hdr_color_buffer_slices = self
+            .hdr_color_buffer
+            .as_ref()
+            .map(|buffer| buffer.slices.iter());
+
+        // Meshes with a custom shader each have a slot in the mesh uniform buffer:
+        let mut next_mesh_uniform_slot = 0;
+        let mut current_pipeline = pipeline;
 
         for epaint::ClippedPrimitive {
             clip_rect,
@@ -446,11 +758,20 @@ impl Renderer {
                     0.0,
                     1.0,
                 );
-                render_pass.set_pipeline(&self.pipeline);
+                render_pass.set_pipeline(pipeline);
                 render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+                current_pipeline = pipeline;
                 needs_reset = false;
             }
 
+            let mesh_uniform_slot = match primitive {
+                Primitive::Mesh(mesh) if mesh.shader.is_some() => {
+                    next_mesh_uniform_slot += 1;
+                    Some(next_mesh_uniform_slot - 1)
+                }
+                _ => None,
+            };
+
             {
                 let rect = ScissorRect::new(clip_rect, pixels_per_point, size_in_pixels);
 
@@ -460,6 +781,9 @@ impl Renderer {
                         // If this is a mesh, we need to advance the index and vertex buffer iterators:
                         index_buffer_slices.next().unwrap();
                         vertex_buffer_slices.next().unwrap();
+                        if let Some(slices) = &mut hdr_color_buffer_slices {
+                            slices.next().unwrap();
+                        }
                     }
                     continue;
                 }
@@ -471,8 +795,36 @@ impl Renderer {
                 Primitive::Mesh(mesh) => {
                     let index_buffer_slice = index_buffer_slices.next().unwrap();
                     let vertex_buffer_slice = vertex_buffer_slices.next().unwrap();
+                    let hdr_color_buffer_slice = hdr_color_buffer_slices
+                        .as_mut()
+                        .map(|slices| slices.next().unwrap());
+
+                    if pick_ids && mesh.pick_id == 0 {
+                        continue;
+                    }
 
                     if let Some(Texture { bind_group, .. }) = self.textures.get(&mesh.texture_id) {
+                        let mesh_shader = mesh_shaders.zip(mesh.shader.as_ref()).and_then(
+                            |(mesh_shaders, shader)| {
+                                Some((mesh_shaders, mesh_shaders.pipelines.get(&shader.id)?))
+                            },
+                        );
+                        let mesh_pipeline = mesh_shader.map_or(pipeline, |(_, pipeline)| pipeline);
+                        if !std::ptr::eq(mesh_pipeline, current_pipeline) {
+                            render_pass.set_pipeline(mesh_pipeline);
+                            render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+                            current_pipeline = mesh_pipeline;
+                        }
+                        if let (Some((mesh_shaders, _)), Some(slot)) =
+                            (mesh_shader, mesh_uniform_slot)
+                        {
+                            render_pass.set_bind_group(
+                                2,
+                                &mesh_shaders.uniform_bind_group,
+                                &[slot * MeshShaders::UNIFORM_SLOT_SIZE as u32],
+                            );
+                        }
+
                         render_pass.set_bind_group(1, bind_group, &[]);
                         render_pass.set_index_buffer(
                             self.index_buffer.buffer.slice(
@@ -486,16 +838,37 @@ impl Renderer {
                                 vertex_buffer_slice.start as u64..vertex_buffer_slice.end as u64,
                             ),
                         );
-                        render_pass.draw_indexed(0..mesh.indices.len() as u32, 0, 0..1);
+                        if let (Some(buffer), Some(slice)) =
+                            (&self.hdr_color_buffer, hdr_color_buffer_slice)
+                        {
+                            render_pass.set_vertex_buffer(
+                                1,
+                                buffer.buffer.slice(slice.start as u64..slice.end as u64),
+                            );
+                        }
+                        let instances = if pick_ids {
+                            mesh.pick_id..mesh.pick_id.saturating_add(1)
+                        } else {
+                            0..1
+                        };
+                        render_pass.draw_indexed(0..mesh.indices.len() as u32, 0, instances);
                     } else {
                         log::warn!("Missing texture: {:?}", mesh.texture_id);
                     }
                 }
                 Primitive::Callback(callback) => {
-                    let Some(cbfn) = callback.callback.downcast_ref::<Callback>() else {
+                    if pick_ids {
+                        continue;
+                    }
+
+                    let cbfn = callback.callback.downcast_ref::<Callback>();
+                    let backend_callback = callback
+                        .callback
+                        .downcast_ref::<epaint::BoxedBackendCallback>();
+                    if cbfn.is_none() && backend_callback.is_none() {
                         // We already warned in the `prepare` callback
                         continue;
-                    };
+                    }
 
                     let info = PaintCallbackInfo {
                         viewport: callback.rect,
@@ -527,7 +900,18 @@ impl Renderer {
                             1.0,
                         );
 
-                        cbfn.0.paint(info, render_pass, &self.callback_resources);
+                        if let Some(cbfn) = cbfn {
+                            cbfn.0.paint(info, render_pass, &self.callback_resources);
+                        } else if let Some(backend_callback) = backend_callback {
+                            let no_resources = epaint::CallbackResources::default();
+                            let resources = self
+                                .callback_resources
+                                .get::<epaint::CallbackResources>()
+                                .unwrap_or(&no_resources);
+                            backend_callback
+                                .callback()
+                                .paint(&info, render_pass, resources);
+                        }
                     }
                 }
             }
@@ -536,6 +920,236 @@ impl Renderer {
         render_pass.set_scissor_rect(0, 0, size_in_pixels[0], size_in_pixels[1]);
     }
 
+    /// Register a custom shader for drawing meshes, see [`epaint::Mesh::shader`].
+    ///
+    /// `wgsl` is compiled together with egui's own shader, and must implement
+    /// `fn mesh_color(in: VertexOutput) -> vec4<f32>`, returning a premultiplied color in gamma space.
+    /// It can sample the texture of the mesh with `r_tex_color` and `r_tex_sampler`,
+    /// and read the [`epaint::MeshShader::uniforms`] by declaring
+    /// `@group(2) @binding(0) var<uniform> r_mesh: YourUniforms;`
+    /// of at most [`epaint::MeshShader::MAX_UNIFORMS_SIZE`] bytes.
+    ///
+    /// ```wgsl
+    /// struct Dashes {
+    ///     color: vec4<f32>,
+    ///     period: f32,
+    /// };
+    /// @group(2) @binding(0) var<uniform> r_mesh: Dashes;
+    ///
+    /// fn mesh_color(in: VertexOutput) -> vec4<f32> {
+    ///     let on = fract(in.position.x / r_mesh.period) < 0.5;
+    ///     return select(vec4<f32>(0.0), in.color * r_mesh.color, on);
+    /// }
+    /// ```
+    ///
+    /// With [`Self::new_hdr`], the color gets the same white level and tone mapping
+    /// as the rest of egui, see [`HdrOptions`].
+    ///
+    /// Meshes with a shader that isn't registered are drawn normally.
+    pub fn register_mesh_shader(
+        &mut self,
+        device: &wgpu::Device,
+        wgsl: &str,
+    ) -> epaint::MeshShaderId {
+        profiling::function_scope!();
+
+        let mesh_shaders = self.mesh_shaders.get_or_insert_with(|| {
+            MeshShaders::new(
+                device,
+                &self.uniform_bind_group_layout,
+                &self.texture_bind_group_layout,
+            )
+        });
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("egui_mesh_shader"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Owned(format!(
+                "{}\n{wgsl}\n{}",
+                include_str!("egui.wgsl"),
+                include_str!("mesh_shader.wgsl")
+            ))),
+        });
+        let fragment_entry_point = if self.hdr.is_some() {
+            "fs_mesh_hdr"
+        } else if self.output_color_format.is_srgb() {
+            "fs_mesh_linear_framebuffer"
+        } else {
+            "fs_mesh_gamma_framebuffer"
+        };
+        let pipeline = create_render_pipeline(
+            device,
+            &mesh_shaders.pipeline_layout,
+            &module,
+            false,
+            fragment_entry_point,
+            self.output_color_format,
+            self.depth_stencil.clone(),
+            self.msaa_samples,
+        );
+
+        let id = epaint::MeshShaderId(mesh_shaders.next_id);
+        mesh_shaders.next_id += 1;
+        mesh_shaders.pipelines.insert(id, pipeline);
+        id
+    }
+
+    /// Forget a shader registered with [`Self::register_mesh_shader`].
+    pub fn free_mesh_shader(&mut self, id: epaint::MeshShaderId) {
+        if let Some(mesh_shaders) = &mut self.mesh_shaders {
+            mesh_shaders.pipelines.remove(&id);
+        }
+    }
+
+    /// Render a [`epaint::TextureSnapshot`] into its texture, replacing the previous contents.
+    ///
+    /// This submits its own commands to the queue, so call it after [`Self::update_texture`]
+    /// and before [`Self::update_buffers`] for the rest of the frame.
+    pub fn render_snapshot(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        snapshot: &epaint::TextureSnapshot,
+    ) {
+        profiling::function_scope!();
+
+        let Some(texture) = self
+            .textures
+            .get(&snapshot.texture_id)
+            .and_then(|texture| texture.texture.clone())
+        else {
+            log::warn!("Missing snapshot texture: {:?}", snapshot.texture_id);
+            return;
+        };
+        if texture.format() != wgpu::TextureFormat::Rgba8UnormSrgb
+            || !texture
+                .usage()
+                .contains(wgpu::TextureUsages::RENDER_ATTACHMENT)
+        {
+            log::warn!(
+                "Can't render a snapshot into texture {:?} of format {:?}",
+                snapshot.texture_id,
+                texture.format()
+            );
+            return;
+        }
+
+        let pipeline = self
+            .snapshot_pipeline
+            .get_or_insert_with(|| {
+                create_render_pipeline(
+                    device,
+                    &self.pipeline_layout,
+                    &self.shader_module,
+                    false,
+                    "fs_main_linear_framebuffer",
+                    texture.format(),
+                    None,
+                    1,
+                )
+            })
+            .clone();
+
+        let screen_descriptor = ScreenDescriptor {
+            size_in_pixels: [texture.width(), texture.height()],
+            pixels_per_point: snapshot.pixels_per_point,
+        };
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("egui_snapshot_encoder"),
+        });
+
+        // The texture is converted to the output color space when it is painted, not now:
+        let output_color_space = std::mem::take(&mut self.output_color_space);
+        let mut user_cmd_bufs = self.update_buffers(
+            device,
+            queue,
+            &mut encoder,
+            &snapshot.primitives,
+            &screen_descriptor,
+        );
+        self.output_color_space = output_color_space;
+
+        {
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+            let mut render_pass = encoder
+                .begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("egui_snapshot"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                })
+                .forget_lifetime();
+            // Custom mesh shaders are made for the output format, so we don't use them here.
+            self.render_with_pipeline(
+                &pipeline,
+                None,
+                false,
+                &mut render_pass,
+                &snapshot.primitives,
+                &screen_descriptor,
+            );
+        }
+
+        user_cmd_bufs.push(encoder.finish());
+        queue.submit(user_cmd_bufs);
+    }
+
+    /// Render the [`epaint::Mesh::pick_id`] of every pixel into `target`,
+    /// which must be a [`crate::picking::PICK_TEXTURE_FORMAT`] texture with the same size as the screen.
+    ///
+    /// Pixels not covered by any [`epaint::Shape::Pick`] group get the pick id 0.
+    /// Only the mostly opaque parts of a mesh can be picked, and paint callbacks are skipped.
+    ///
+    /// Call this after [`Self::update_buffers`], like [`Self::render`].
+    pub fn render_pick_ids(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        paint_jobs: &[epaint::ClippedPrimitive],
+        screen_descriptor: &ScreenDescriptor,
+    ) {
+        profiling::function_scope!();
+
+        let pipeline = self
+            .pick_pipeline
+            .get_or_insert_with(|| create_pick_pipeline(device, &self.pipeline_layout))
+            .clone();
+
+        let mut render_pass = encoder
+            .begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("egui_pick"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            })
+            .forget_lifetime();
+        self.render_with_pipeline(
+            &pipeline,
+            None,
+            true,
+            &mut render_pass,
+            paint_jobs,
+            screen_descriptor,
+        );
+    }
+
     /// Should be called before [`Self::render`].
     pub fn update_texture(
         &mut self,
@@ -555,6 +1169,13 @@ impl Renderer {
             depth_or_array_layers: 1,
         };
 
+        let mut format = wgpu::TextureFormat::Rgba8UnormSrgb; // Minspec for wgpu WebGL emulation is WebGL2, so this should always be supported.
+        let mut layout = wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(4 * width),
+            rows_per_image: Some(height),
+        };
+
         let data_color32 = match &image_delta.image {
             epaint::ImageData::Color(image) => {
                 assert_eq!(
@@ -562,7 +1183,15 @@ impl Renderer {
                     image.pixels.len(),
                     "Mismatch between texture size and texel count"
                 );
-                Cow::Borrowed(&image.pixels)
+                let color_space = image_delta.options.color_space;
+                if color_space == self.output_color_space {
+                    Cow::Borrowed(&image.pixels)
+                } else {
+                    profiling::scope!("convert color space");
+                    let mut image = (**image).clone();
+                    image.convert_color_space(color_space, self.output_color_space);
+                    Cow::Owned(image.pixels)
+                }
             }
             epaint::ImageData::Font(image) => {
                 assert_eq!(
@@ -573,8 +1202,38 @@ impl Renderer {
                 profiling::scope!("font -> sRGBA");
                 Cow::Owned(image.srgba_pixels(None).collect::<Vec<epaint::Color32>>())
             }
+            epaint::ImageData::Compressed(image) => {
+                let (compressed_format, required_features) =
+                    compressed_texture_format(image.format());
+                if !device.features().contains(required_features) {
+                    log::error!(
+                        "Can't upload texture {id:?}: {:?} textures need the {required_features:?} device feature",
+                        image.format()
+                    );
+                    return;
+                }
+                if let Some(pos) = image_delta.pos {
+                    if !image.format().is_block_aligned(pos) {
+                        log::error!(
+                            "Can't update texture {id:?} at {pos:?}: partial updates of {:?} textures must start on a block boundary",
+                            image.format()
+                        );
+                        return;
+                    }
+                }
+                format = compressed_format;
+                layout.bytes_per_row = Some(image.bytes_per_row() as u32);
+                layout.rows_per_image = Some(image.block_rows() as u32);
+                Cow::Owned(Vec::new()) // The compressed data is uploaded as-is, see below
+            }
+            epaint::ImageData::Empty(_) => Cow::Owned(Vec::new()), // Nothing to upload
+        };
+        let data_bytes: &[u8] = match &image_delta.image {
+            epaint::ImageData::Compressed(image) => image.data(),
+            epaint::ImageData::Color(_)
+            | epaint::ImageData::Font(_)
+            | epaint::ImageData::Empty(_) => bytemuck::cast_slice(data_color32.as_slice()),
         };
-        let data_bytes: &[u8] = bytemuck::cast_slice(data_color32.as_slice());
 
         let queue_write_data_to_texture = |texture, origin| {
             profiling::scope!("write_texture");
@@ -586,11 +1245,7 @@ impl Renderer {
                     aspect: wgpu::TextureAspect::All,
                 },
                 data_bytes,
-                wgpu::TexelCopyBufferLayout {
-                    offset: 0,
-                    bytes_per_row: Some(4 * width),
-                    rows_per_image: Some(height),
-                },
+                layout,
                 size,
             );
         };
@@ -611,6 +1266,21 @@ impl Renderer {
                 .expect("Tried to update a texture that has not been allocated yet.");
             let texture = texture.expect("Tried to update user texture.");
             let options = options.expect("Tried to update user texture.");
+            if texture.format() != format {
+                log::error!(
+                    "Can't update texture {id:?} of format {:?} with an image of format {format:?}",
+                    texture.format()
+                );
+                self.textures.insert(
+                    id,
+                    Texture {
+                        texture: Some(texture),
+                        bind_group,
+                        options: Some(options),
+                    },
+                );
+                return;
+            }
             let origin = wgpu::Origin3d {
                 x: pos[0] as u32,
                 y: pos[1] as u32,
@@ -638,9 +1308,16 @@ impl Renderer {
                     mip_level_count: 1,
                     sample_count: 1,
                     dimension: wgpu::TextureDimension::D2,
-                    format: wgpu::TextureFormat::Rgba8UnormSrgb, // Minspec for wgpu WebGL emulation is WebGL2, so this should always be supported.
-                    usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-                    view_formats: &[wgpu::TextureFormat::Rgba8UnormSrgb],
+                    format,
+                    usage: if format == wgpu::TextureFormat::Rgba8UnormSrgb {
+                        // So that we can render snapshots into it:
+                        wgpu::TextureUsages::TEXTURE_BINDING
+                            | wgpu::TextureUsages::COPY_DST
+                            | wgpu::TextureUsages::RENDER_ATTACHMENT
+                    } else {
+                        wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST
+                    },
+                    view_formats: &[format],
                 })
             };
             let origin = wgpu::Origin3d::ZERO;
@@ -670,7 +1347,9 @@ impl Renderer {
             })
         });
 
-        queue_write_data_to_texture(&texture, origin);
+        if !matches!(image_delta.image, epaint::ImageData::Empty(_)) {
+            queue_write_data_to_texture(&texture, origin);
+        }
         self.textures.insert(
             id,
             Texture {
@@ -853,9 +1532,13 @@ impl Renderer {
 
         let screen_size_in_points = screen_descriptor.screen_size_in_points();
 
+        let hdr = self.hdr.unwrap_or_default();
         let uniform_buffer_content = UniformBuffer {
             screen_size_in_points,
             dithering: u32::from(self.dithering),
+            tone_mapping: hdr.tone_mapping.as_u32(),
+            white_level: hdr.white_level,
+            output_display_p3: u32::from(self.output_color_space == ColorSpace::DisplayP3),
             _padding: Default::default(),
         };
         if uniform_buffer_content != self.previous_uniform_buffer_content {
@@ -870,6 +1553,7 @@ impl Renderer {
 
         // Determine how many vertices & indices need to be rendered, and gather prepare callbacks
         let mut callbacks = Vec::new();
+        let mut backend_callbacks = Vec::new();
         let (vertex_count, index_count) = {
             profiling::scope!("count_vertices_indices");
             paint_jobs.iter().fold((0, 0), |acc, clipped_primitive| {
@@ -880,8 +1564,13 @@ impl Renderer {
                     Primitive::Callback(callback) => {
                         if let Some(c) = callback.callback.downcast_ref::<Callback>() {
                             callbacks.push(c.0.as_ref());
+                        } else if let Some(c) = callback
+                            .callback
+                            .downcast_ref::<epaint::BoxedBackendCallback>()
+                        {
+                            backend_callbacks.push(c.callback());
                         } else {
-                            log::warn!("Unknown paint callback: expected `egui_wgpu::Callback`");
+                            log::warn!("Unknown paint callback: expected `egui_wgpu::Callback` or `epaint::BackendCallback`");
                         };
                         acc
                     }
@@ -966,6 +1655,15 @@ impl Renderer {
                 }
             }
         }
+        if let Some(hdr_color_buffer) = &mut self.hdr_color_buffer {
+            if vertex_count > 0 {
+                profiling::scope!("hdr colors");
+                update_hdr_color_buffer(device, queue, hdr_color_buffer, paint_jobs, vertex_count);
+            }
+        }
+        if let Some(mesh_shaders) = &mut self.mesh_shaders {
+            mesh_shaders.update_uniforms(device, queue, paint_jobs);
+        }
 
         let mut user_cmd_bufs = Vec::new();
         {
@@ -991,11 +1689,199 @@ impl Renderer {
                 ));
             }
         }
+        if !backend_callbacks.is_empty() {
+            profiling::scope!("prepare backend callbacks");
+            let mut prepare = CallbackPrepare {
+                device: device.clone(),
+                queue: queue.clone(),
+                target_format: self.output_color_format,
+                depth_format: self.depth_stencil.as_ref().map(|depth| depth.format),
+                msaa_samples: self.msaa_samples,
+                command_buffers: Vec::new(),
+            };
+            let resources = self
+                .callback_resources
+                .entry::<epaint::CallbackResources>()
+                .or_insert_with(Default::default);
+            for callback in backend_callbacks {
+                callback.prepare(&mut prepare, resources);
+            }
+            user_cmd_bufs.extend(prepare.command_buffers);
+        }
 
         user_cmd_bufs
     }
 }
 
+/// The wgpu texture format for a [`epaint::CompressedFormat`],
+/// and the device features needed to use it.
+pub fn compressed_texture_format(
+    format: epaint::CompressedFormat,
+) -> (wgpu::TextureFormat, wgpu::Features) {
+    use epaint::CompressedFormat;
+    use wgpu::{AstcBlock, AstcChannel, Features, TextureFormat};
+
+    match format {
+        CompressedFormat::Bc1 => (
+            TextureFormat::Bc1RgbaUnormSrgb,
+            Features::TEXTURE_COMPRESSION_BC,
+        ),
+        CompressedFormat::Bc3 => (
+            TextureFormat::Bc3RgbaUnormSrgb,
+            Features::TEXTURE_COMPRESSION_BC,
+        ),
+        CompressedFormat::Bc7 => (
+            TextureFormat::Bc7RgbaUnormSrgb,
+            Features::TEXTURE_COMPRESSION_BC,
+        ),
+        CompressedFormat::Etc2Rgb8 => (
+            TextureFormat::Etc2Rgb8UnormSrgb,
+            Features::TEXTURE_COMPRESSION_ETC2,
+        ),
+        CompressedFormat::Etc2Rgba8 => (
+            TextureFormat::Etc2Rgba8UnormSrgb,
+            Features::TEXTURE_COMPRESSION_ETC2,
+        ),
+        CompressedFormat::Astc4x4 => (
+            TextureFormat::Astc {
+                block: AstcBlock::B4x4,
+                channel: AstcChannel::UnormSrgb,
+            },
+            Features::TEXTURE_COMPRESSION_ASTC,
+        ),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_render_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    module: &wgpu::ShaderModule,
+    hdr: bool,
+    fragment_entry_point: &str,
+    output_color_format: wgpu::TextureFormat,
+    depth_stencil: Option<wgpu::DepthStencilState>,
+    msaa_samples: u32,
+) -> wgpu::RenderPipeline {
+    profiling::function_scope!();
+
+    let vertex_buffer_layouts = [
+        wgpu::VertexBufferLayout {
+            array_stride: 5 * 4,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            // 0: vec2 position
+            // 1: vec2 texture coordinates
+            // 2: uint color
+            attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Uint32],
+        },
+        wgpu::VertexBufferLayout {
+            array_stride: 4 * 2,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            // 3: linear hdr color
+            attributes: &wgpu::vertex_attr_array![3 => Float16x4],
+        },
+    ];
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("egui_pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            entry_point: Some(if hdr { "vs_main_hdr" } else { "vs_main" }),
+            module,
+            buffers: if hdr {
+                &vertex_buffer_layouts
+            } else {
+                &vertex_buffer_layouts[..1]
+            },
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            unclipped_depth: false,
+            conservative: false,
+            cull_mode: None,
+            front_face: wgpu::FrontFace::default(),
+            polygon_mode: wgpu::PolygonMode::default(),
+            strip_index_format: None,
+        },
+        depth_stencil,
+        multisample: wgpu::MultisampleState {
+            alpha_to_coverage_enabled: false,
+            count: msaa_samples,
+            mask: !0,
+        },
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: Some(fragment_entry_point),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: output_color_format,
+                blend: Some(wgpu::BlendState {
+                    color: wgpu::BlendComponent {
+                        src_factor: wgpu::BlendFactor::One,
+                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                        operation: wgpu::BlendOperation::Add,
+                    },
+                    alpha: wgpu::BlendComponent {
+                        src_factor: wgpu::BlendFactor::OneMinusDstAlpha,
+                        dst_factor: wgpu::BlendFactor::One,
+                        operation: wgpu::BlendOperation::Add,
+                    },
+                }),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn create_pick_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+) -> wgpu::RenderPipeline {
+    profiling::function_scope!();
+
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("egui_pick"),
+        source: wgpu::ShaderSource::Wgsl(Cow::Owned(format!(
+            "{}\n{}",
+            include_str!("egui.wgsl"),
+            include_str!("pick.wgsl")
+        ))),
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("egui_pick_pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            entry_point: Some("vs_pick"),
+            module: &module,
+            buffers: &[wgpu::VertexBufferLayout {
+                array_stride: 5 * 4,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Uint32],
+            }],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module: &module,
+            entry_point: Some("fs_pick"),
+            // Integer targets can't be blended, so the last mesh wins:
+            targets: &[Some(crate::picking::PICK_TEXTURE_FORMAT.into())],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
+
 fn create_sampler(
     options: epaint::textures::TextureOptions,
     device: &wgpu::Device,
@@ -1025,6 +1911,49 @@ fn create_sampler(
     })
 }
 
+/// Upload the [`RgbaF16`] color of every vertex, for the HDR pipeline.
+fn update_hdr_color_buffer(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    hdr_color_buffer: &mut SlicedBuffer,
+    paint_jobs: &[epaint::ClippedPrimitive],
+    vertex_count: usize,
+) {
+    hdr_color_buffer.slices.clear();
+
+    let required_size = (std::mem::size_of::<RgbaF16>() * vertex_count) as u64;
+    if hdr_color_buffer.capacity < required_size {
+        hdr_color_buffer.capacity = (hdr_color_buffer.capacity * 2).at_least(required_size);
+        hdr_color_buffer.buffer = create_vertex_buffer(device, hdr_color_buffer.capacity);
+    }
+
+    let Some(mut staging) = queue.write_buffer_with(
+        &hdr_color_buffer.buffer,
+        0,
+        NonZeroU64::new(required_size).unwrap(),
+    ) else {
+        panic!("Failed to create staging buffer for HDR color data. Vertex count: {vertex_count}. Required size: {required_size}");
+    };
+
+    let mut offset = 0;
+    for epaint::ClippedPrimitive { primitive, .. } in paint_jobs {
+        if let Primitive::Mesh(mesh) = primitive {
+            let size = mesh.vertices.len() * std::mem::size_of::<RgbaF16>();
+            let slice = offset..(size + offset);
+            if mesh.hdr_colors.len() == mesh.vertices.len() {
+                staging[slice.clone()].copy_from_slice(bytemuck::cast_slice(&mesh.hdr_colors));
+            } else {
+                let colors: Vec<RgbaF16> = (0..mesh.vertices.len())
+                    .map(|i| mesh.hdr_color(i))
+                    .collect();
+                staging[slice.clone()].copy_from_slice(bytemuck::cast_slice(&colors));
+            }
+            hdr_color_buffer.slices.push(slice);
+            offset += size;
+        }
+    }
+}
+
 fn create_vertex_buffer(device: &wgpu::Device, size: u64) -> wgpu::Buffer {
     profiling::function_scope!();
     device.create_buffer(&wgpu::BufferDescriptor {
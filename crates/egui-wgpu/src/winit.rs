@@ -2,8 +2,9 @@
 #![allow(clippy::undocumented_unsafe_blocks)]
 
 use crate::capture::{capture_channel, CaptureReceiver, CaptureSender, CaptureState};
+use crate::picking::{pick_channel, PickReceiver, PickSender, PickState};
 use crate::{renderer, RenderState, SurfaceErrorAction, WgpuConfiguration};
-use egui::{Context, Event, UserData, ViewportId, ViewportIdMap, ViewportIdSet};
+use egui::{Context, Event, Pos2, UserData, ViewportId, ViewportIdMap, ViewportIdSet};
 use std::{num::NonZeroU32, sync::Arc};
 
 struct SurfaceState {
@@ -26,6 +27,13 @@ pub struct Painter {
     dithering: bool,
     depth_format: Option<wgpu::TextureFormat>,
     screen_capture_state: Option<CaptureState>,
+    pick_state: Option<PickState>,
+
+    /// Read back the pick ids at these positions in the next call to [`Self::paint_and_update_textures`].
+    pick_positions: Vec<Pos2>,
+
+    /// Render these into their textures in the next call to [`Self::paint_and_update_textures`].
+    pending_snapshots: Vec<epaint::TextureSnapshot>,
 
     instance: wgpu::Instance,
     render_state: Option<RenderState>,
@@ -36,6 +44,8 @@ pub struct Painter {
     surfaces: ViewportIdMap<SurfaceState>,
     capture_tx: CaptureSender,
     capture_rx: CaptureReceiver,
+    pick_tx: PickSender,
+    pick_rx: PickReceiver,
 }
 
 impl Painter {
@@ -60,6 +70,7 @@ impl Painter {
         dithering: bool,
     ) -> Self {
         let (capture_tx, capture_rx) = capture_channel();
+        let (pick_tx, pick_rx) = pick_channel();
         let instance = configuration.wgpu_setup.new_instance().await;
 
         Self {
@@ -70,6 +81,9 @@ impl Painter {
             dithering,
             depth_format,
             screen_capture_state: None,
+            pick_state: None,
+            pick_positions: Vec::new(),
+            pending_snapshots: Vec::new(),
 
             instance,
             render_state: None,
@@ -80,6 +94,8 @@ impl Painter {
 
             capture_tx,
             capture_rx,
+            pick_tx,
+            pick_rx,
         }
     }
 
@@ -354,6 +370,8 @@ impl Painter {
     /// and the captures captured screenshot if it was requested.
     ///
     /// If `capture_data` isn't empty, a screenshot will be captured.
+    ///
+    /// Any snapshots queued with [`Self::render_snapshots`] are rendered into their textures before painting.
     pub fn paint_and_update_textures(
         &mut self,
         viewport_id: ViewportId,
@@ -366,6 +384,8 @@ impl Painter {
         profiling::function_scope!();
 
         let capture = !capture_data.is_empty();
+        let pick_positions = std::mem::take(&mut self.pick_positions);
+        let snapshots = std::mem::take(&mut self.pending_snapshots);
         let mut vsync_sec = 0.0;
 
         let Some(render_state) = self.render_state.as_mut() else {
@@ -398,6 +418,9 @@ impl Painter {
                     image_delta,
                 );
             }
+            for snapshot in &snapshots {
+                renderer.render_snapshot(&render_state.device, &render_state.queue, snapshot);
+            }
 
             renderer.update_buffers(
                 &render_state.device,
@@ -504,6 +527,29 @@ impl Painter {
             }
         }
 
+        let mut pick_buffer = None;
+        if !pick_positions.is_empty() {
+            let size = output_frame.texture.size();
+            let pick_state = self
+                .pick_state
+                .get_or_insert_with(|| PickState::new(&render_state.device, size));
+            pick_state.update(&render_state.device, size);
+
+            render_state.renderer.write().render_pick_ids(
+                &render_state.device,
+                &mut encoder,
+                pick_state.view(),
+                clipped_primitives,
+                &screen_descriptor,
+            );
+            pick_buffer = Some(pick_state.copy_pick_ids(
+                &render_state.device,
+                &mut encoder,
+                &pick_positions,
+                pixels_per_point,
+            ));
+        }
+
         let encoded = {
             profiling::scope!("CommandEncoder::finish");
             encoder.finish()
@@ -542,6 +588,16 @@ impl Painter {
             }
         }
 
+        if let (Some(pick_buffer), Some(pick_state)) = (pick_buffer, &self.pick_state) {
+            pick_state.read_pick_ids(
+                self.context.clone(),
+                pick_buffer,
+                pick_positions,
+                self.pick_tx.clone(),
+                viewport_id,
+            );
+        }
+
         {
             profiling::scope!("present");
             // wgpu doesn't document where vsync can happen. Maybe here?
@@ -567,6 +623,35 @@ impl Painter {
         }
     }
 
+    /// Render these [`epaint::TextureSnapshot`]s into their textures when painting the next frame,
+    /// see [`egui::Context::snapshot_ui`].
+    ///
+    /// They are rendered after the frame's texture updates, so they can use new font glyphs.
+    pub fn render_snapshots(&mut self, snapshots: Vec<epaint::TextureSnapshot>) {
+        self.pending_snapshots.extend(snapshots);
+    }
+
+    /// Read back the pick ids at these positions (in points) when painting the next frame,
+    /// see [`egui::ViewportCommand::ReadPick`].
+    ///
+    /// The results are returned by [`Self::handle_picks`].
+    pub fn read_picks(&mut self, positions: Vec<Pos2>) {
+        self.pick_positions = positions;
+    }
+
+    /// Call this at the beginning of each frame to receive the pick ids requested with [`Self::read_picks`].
+    pub fn handle_picks(&self, events: &mut Vec<Event>) {
+        for (viewport_id, picks) in self.pick_rx.try_iter() {
+            for (pos, pick_id) in picks {
+                events.push(Event::Pick {
+                    viewport_id,
+                    pos,
+                    pick_id,
+                });
+            }
+        }
+    }
+
     pub fn gc_viewports(&mut self, active_viewports: &ViewportIdSet) {
         self.surfaces.retain(|id, _| active_viewports.contains(id));
         self.depth_texture_view
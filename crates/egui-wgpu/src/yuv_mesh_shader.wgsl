@@ -0,0 +1,18 @@
+// Converts packed YUV (Y, U and V in the R, G and B channels) to RGB,
+// see `egui::VideoSurface` and `YUV_MESH_SHADER`.
+
+// The rows of the affine transform from `[y, u, v, 1]` to gamma RGB.
+struct YuvToRgb {
+    r: vec4<f32>,
+    g: vec4<f32>,
+    b: vec4<f32>,
+};
+@group(2) @binding(0) var<uniform> r_mesh: YuvToRgb;
+
+fn mesh_color(in: VertexOutput) -> vec4<f32> {
+    // The texture is sRGB, so undo the decoding to get the raw samples back.
+    let yuv = gamma_from_linear_rgba(textureSample(r_tex_color, r_tex_sampler, in.tex_coord));
+    let yuv1 = vec4<f32>(yuv.rgb, 1.0);
+    let rgb = vec3<f32>(dot(r_mesh.r, yuv1), dot(r_mesh.g, yuv1), dot(r_mesh.b, yuv1));
+    return in.color * vec4<f32>(clamp(rgb, vec3<f32>(0.0), vec3<f32>(1.0)), 1.0);
+}
@@ -126,6 +126,68 @@ impl Clipboard {
         log::error!("Copying images is not supported. Enable the 'clipboard' feature of `egui-winit` to enable it.");
         _ = image;
     }
+
+    /// Put the first of these representations that we support on the clipboard.
+    ///
+    /// HTML is copied together with its plain text alternative.
+    /// RTF and custom MIME types are not supported, and are skipped.
+    pub fn set_data(&mut self, data: &[egui::ClipboardData]) {
+        for item in data {
+            match item {
+                egui::ClipboardData::Text(text) => {
+                    self.set_text(text.clone());
+                    return;
+                }
+                egui::ClipboardData::Image(image) => {
+                    #[cfg(all(feature = "arboard", not(target_os = "android")))]
+                    if self.arboard.is_some() {
+                        self.set_image(image);
+                        return;
+                    }
+                    _ = image;
+                }
+                egui::ClipboardData::Html { html, alt_text } => {
+                    #[cfg(all(feature = "arboard", not(target_os = "android")))]
+                    if let Some(clipboard) = &mut self.arboard {
+                        if let Err(err) = clipboard.set_html(html, Some(alt_text)) {
+                            log::error!("arboard copy/cut error: {err}");
+                        }
+                        return;
+                    }
+                    _ = html;
+                    self.set_text(alt_text.clone());
+                    return;
+                }
+                egui::ClipboardData::Rtf(_) | egui::ClipboardData::Custom { .. } => {
+                    log::debug!("Skipping unsupported clipboard format {:?}", item.mime());
+                }
+            }
+        }
+
+        if !data.is_empty() {
+            log::warn!("None of the clipboard formats are supported: {data:?}");
+        }
+    }
+
+    /// Read an image from the clipboard, if there is one.
+    pub fn get_image(&mut self) -> Option<egui::ColorImage> {
+        #[cfg(all(feature = "arboard", not(target_os = "android")))]
+        if let Some(clipboard) = &mut self.arboard {
+            return match clipboard.get_image() {
+                Ok(image) => Some(egui::ColorImage::from_rgba_unmultiplied(
+                    [image.width, image.height],
+                    &image.bytes,
+                )),
+                Err(arboard::Error::ContentNotAvailable) => None,
+                Err(err) => {
+                    log::error!("arboard paste error: {err}");
+                    None
+                }
+            };
+        }
+
+        None
+    }
 }
 
 #[cfg(all(feature = "arboard", not(target_os = "android")))]
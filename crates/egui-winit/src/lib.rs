@@ -22,7 +22,7 @@ mod window_settings;
 
 pub use window_settings::WindowSettings;
 
-use ahash::HashSet;
+use ahash::{HashMap, HashSet};
 use raw_window_handle::HasDisplayHandle;
 
 use winit::{
@@ -44,13 +44,27 @@ pub fn screen_size_in_pixels(window: &Window) -> egui::Vec2 {
     egui::vec2(size.width as f32, size.height as f32)
 }
 
-/// Calculate the `pixels_per_point` for a given window, given the current egui zoom factor
+/// Calculate the `pixels_per_point` for a given window, given the current egui zoom factor.
+///
+/// This ignores any per-viewport zoom factor, see [`pixels_per_point_for`].
 pub fn pixels_per_point(egui_ctx: &egui::Context, window: &Window) -> f32 {
     let native_pixels_per_point = window.scale_factor() as f32;
     let egui_zoom_factor = egui_ctx.zoom_factor();
     egui_zoom_factor * native_pixels_per_point
 }
 
+/// Calculate the `pixels_per_point` for the window of the given viewport,
+/// using its own zoom factor if it has one (see [`egui::Context::set_viewport_zoom_factor`]).
+pub fn pixels_per_point_for(
+    egui_ctx: &egui::Context,
+    viewport_id: ViewportId,
+    window: &Window,
+) -> f32 {
+    let native_pixels_per_point = window.scale_factor() as f32;
+    let egui_zoom_factor = egui_ctx.effective_zoom_factor(viewport_id);
+    egui_zoom_factor * native_pixels_per_point
+}
+
 // ----------------------------------------------------------------------------
 
 #[must_use]
@@ -85,6 +99,12 @@ pub struct State {
     any_pointer_button_down: bool,
     current_cursor_icon: Option<egui::CursorIcon>,
 
+    /// Native cursors made from the images of [`egui::Context::set_cursor_image`].
+    custom_cursors: HashMap<egui::CursorIcon, (egui::CursorImage, winit::window::CustomCursor)>,
+
+    /// Images waiting for [`Self::update_custom_cursors`] to turn them into native cursors.
+    pending_custom_cursors: HashMap<egui::CursorIcon, egui::CursorImage>,
+
     clipboard: clipboard::Clipboard,
 
     /// If `true`, mouse inputs will be treated as touches.
@@ -106,6 +126,8 @@ pub struct State {
 
     allow_ime: bool,
     ime_rect_px: Option<egui::Rect>,
+
+    platform_handlers: PlatformHandlers,
 }
 
 impl State {
@@ -133,6 +155,8 @@ impl State {
             pointer_pos_in_points: None,
             any_pointer_button_down: false,
             current_cursor_icon: None,
+            custom_cursors: Default::default(),
+            pending_custom_cursors: Default::default(),
 
             clipboard: clipboard::Clipboard::new(
                 display_target.display_handle().ok().map(|h| h.as_raw()),
@@ -148,6 +172,8 @@ impl State {
 
             allow_ime: false,
             ime_rect_px: None,
+
+            platform_handlers: Default::default(),
         };
 
         slf.egui_input
@@ -179,6 +205,11 @@ impl State {
         ));
     }
 
+    /// Plug in implementations of what winit can't do on its own, e.g. OS-level drags.
+    pub fn set_platform_handlers(&mut self, platform_handlers: PlatformHandlers) {
+        self.platform_handlers = platform_handlers;
+    }
+
     /// Call this once a graphics context has been created to update the maximum texture dimensions
     /// that egui will use.
     pub fn set_max_texture_side(&mut self, max_texture_side: usize) {
@@ -195,6 +226,32 @@ impl State {
         self.clipboard.set_text(text);
     }
 
+    /// Read the clipboard and send it to egui, as if the user pressed Cmd+V.
+    ///
+    /// Sends [`egui::Event::Paste`] if there is any text,
+    /// followed by [`egui::Event::PasteData`] with everything we could read, including images.
+    pub fn paste(&mut self) {
+        let mut data = Vec::new();
+
+        if let Some(contents) = self.clipboard.get() {
+            let contents = contents.replace("\r\n", "\n");
+            if !contents.is_empty() {
+                self.egui_input
+                    .events
+                    .push(egui::Event::Paste(contents.clone()));
+                data.push(egui::ClipboardData::Text(contents));
+            }
+        }
+
+        if let Some(image) = self.clipboard.get_image() {
+            data.push(egui::ClipboardData::Image(image));
+        }
+
+        if !data.is_empty() {
+            self.egui_input.events.push(egui::Event::PasteData(data));
+        }
+    }
+
     /// Returns [`false`] or the last value that [`Window::set_ime_allowed()`] was called with, used for debouncing.
     pub fn allow_ime(&self) -> bool {
         self.allow_ime
@@ -241,7 +298,7 @@ impl State {
         // This solves an issue where egui window positions would be changed when minimizing on Windows.
         let screen_size_in_pixels = screen_size_in_pixels(window);
         let screen_size_in_points =
-            screen_size_in_pixels / pixels_per_point(&self.egui_ctx, window);
+            screen_size_in_pixels / pixels_per_point_for(&self.egui_ctx, self.viewport_id, window);
 
         self.egui_input.screen_rect = (screen_size_in_points.x > 0.0
             && screen_size_in_points.y > 0.0)
@@ -256,6 +313,14 @@ impl State {
             .or_default()
             .native_pixels_per_point = Some(window.scale_factor() as f32);
 
+        self.egui_input.native_menus.context_menu = self.platform_handlers.context_menu.is_some();
+        self.egui_input.native_menus.menu_bar = self.platform_handlers.menu_bar.is_some();
+        self.egui_input.native_menus.tray = self.platform_handlers.tray.is_some();
+
+        self.platform_handlers
+            .native_events
+            .take_into(self.viewport_id, &mut self.egui_input.events);
+
         self.egui_input.take()
     }
 
@@ -425,8 +490,11 @@ impl State {
                 }
             }
             WindowEvent::HoveredFile(path) => {
+                // winit doesn't say where the file is, and doesn't move the pointer while files are dragged,
+                // so the last pointer position would be stale.
                 self.egui_input.hovered_files.push(egui::HoveredFile {
                     path: Some(path.clone()),
+                    pos: None,
                     ..Default::default()
                 });
                 EventResponse {
@@ -445,6 +513,8 @@ impl State {
                 self.egui_input.hovered_files.clear();
                 self.egui_input.dropped_files.push(egui::DroppedFile {
                     path: Some(path.clone()),
+                    pos: None, // See `HoveredFile`
+                    is_dir: path.is_dir(),
                     ..Default::default()
                 });
                 EventResponse {
@@ -595,7 +665,7 @@ impl State {
         window: &Window,
         pos_in_pixels: winit::dpi::PhysicalPosition<f64>,
     ) {
-        let pixels_per_point = pixels_per_point(&self.egui_ctx, window);
+        let pixels_per_point = pixels_per_point_for(&self.egui_ctx, self.viewport_id, window);
 
         let pos_in_points = egui::pos2(
             pos_in_pixels.x as f32 / pixels_per_point,
@@ -625,7 +695,7 @@ impl State {
     }
 
     fn on_touch(&mut self, window: &Window, touch: &winit::event::Touch) {
-        let pixels_per_point = pixels_per_point(&self.egui_ctx, window);
+        let pixels_per_point = pixels_per_point_for(&self.egui_ctx, self.viewport_id, window);
 
         // Emit touch event
         self.egui_input.events.push(egui::Event::Touch {
@@ -690,7 +760,7 @@ impl State {
     }
 
     fn on_mouse_wheel(&mut self, window: &Window, delta: winit::event::MouseScrollDelta) {
-        let pixels_per_point = pixels_per_point(&self.egui_ctx, window);
+        let pixels_per_point = pixels_per_point_for(&self.egui_ctx, self.viewport_id, window);
 
         {
             let (unit, delta) = match delta {
@@ -772,12 +842,7 @@ impl State {
                     self.egui_input.events.push(egui::Event::Copy);
                     return;
                 } else if is_paste_command(self.egui_input.modifiers, active_key) {
-                    if let Some(contents) = self.clipboard.get() {
-                        let contents = contents.replace("\r\n", "\n");
-                        if !contents.is_empty() {
-                            self.egui_input.events.push(egui::Event::Paste(contents));
-                        }
-                    }
+                    self.paste();
                     return;
                 }
             }
@@ -853,12 +918,56 @@ impl State {
                 egui::OutputCommand::CopyImage(image) => {
                     self.clipboard.set_image(&image);
                 }
+                egui::OutputCommand::Copy(data) => {
+                    self.clipboard.set_data(&data);
+                }
                 egui::OutputCommand::OpenUrl(open_url) => {
                     open_url_in_browser(&open_url.url);
                 }
                 egui::OutputCommand::SetPointerPosition(egui::Pos2 { x, y }) => {
                     let _ = window.set_cursor_position(winit::dpi::LogicalPosition { x, y });
                 }
+                egui::OutputCommand::StartDrag(items) => {
+                    if let Some(handler) = &self.platform_handlers.start_drag {
+                        handler(window, items);
+                    } else {
+                        log::debug!(
+                            "Ignoring OutputCommand::StartDrag: winit can't start native drags. \
+                            Use PlatformHandlers::start_drag to plug in an implementation."
+                        );
+                    }
+                }
+                egui::OutputCommand::ShowContextMenu { pos, items } => {
+                    if let Some(handler) = &self.platform_handlers.context_menu {
+                        if let Some(id) = handler(window, pos, &items) {
+                            self.egui_input
+                                .events
+                                .push(egui::Event::NativeMenuItemClicked(id));
+                            self.egui_ctx.request_repaint_of(self.viewport_id);
+                        }
+                    }
+                }
+                egui::OutputCommand::SetMenuBar(items) => {
+                    if let Some(handler) = &self.platform_handlers.menu_bar {
+                        handler(
+                            &self.egui_ctx,
+                            &self.platform_handlers.native_events,
+                            window,
+                            self.viewport_id,
+                            &items,
+                        );
+                    }
+                }
+                egui::OutputCommand::SetTrayIcon(tray_icon) => {
+                    if let Some(handler) = &self.platform_handlers.tray {
+                        handler(
+                            &self.egui_ctx,
+                            &self.platform_handlers.native_events,
+                            window,
+                            tray_icon.as_ref(),
+                        );
+                    }
+                }
             }
         }
 
@@ -872,6 +981,8 @@ impl State {
             self.clipboard.set_text(copied_text);
         }
 
+        self.apply_window_shape(window);
+
         let allow_ime = ime.is_some();
         if self.allow_ime != allow_ime {
             self.allow_ime = allow_ime;
@@ -880,7 +991,7 @@ impl State {
         }
 
         if let Some(ime) = ime {
-            let pixels_per_point = pixels_per_point(&self.egui_ctx, window);
+            let pixels_per_point = pixels_per_point_for(&self.egui_ctx, self.viewport_id, window);
             let ime_rect_px = pixels_per_point * ime.rect;
             if self.ime_rect_px != Some(ime_rect_px)
                 || self.egui_ctx.input(|i| !i.events.is_empty())
@@ -911,7 +1022,97 @@ impl State {
         }
     }
 
+    /// Hand the window shape and mouse pass-through regions to [`PlatformHandlers::window_shape`]
+    /// when they have changed, or when the window has been scaled.
+    fn apply_window_shape(&self, window: &Window) {
+        let id = window_shape_state_id(window);
+        let Some(state) = self.egui_ctx.data(|d| d.get_temp::<WindowShapeState>(id)) else {
+            return; // Never shaped
+        };
+
+        let pixels_per_point = pixels_per_point_for(&self.egui_ctx, self.viewport_id, window);
+        if state.applied_pixels_per_point == Some(pixels_per_point) {
+            return;
+        }
+        self.egui_ctx.data_mut(|d| {
+            d.get_temp_mut_or_default::<WindowShapeState>(id)
+                .applied_pixels_per_point = Some(pixels_per_point);
+        });
+
+        if let Some(handler) = &self.platform_handlers.window_shape {
+            let regions: Vec<Rect> = state
+                .passthrough_regions
+                .iter()
+                .map(|rect| *rect * pixels_per_point)
+                .collect();
+            handler(window, &state.shape.scaled(pixels_per_point), &regions);
+        } else if !state.shape.is_empty() || !state.passthrough_regions.is_empty() {
+            log::warn!(
+                "Window shapes and mouse pass-through regions require a window shape handler. \
+                Use PlatformHandlers::window_shape to plug in an implementation."
+            );
+        }
+    }
+
+    /// Create native cursors for the images given to [`egui::Context::set_cursor_image`].
+    ///
+    /// Creating a cursor needs the event loop, so call this each frame before [`Self::handle_platform_output`].
+    /// Until a cursor has been created, the standard icon is shown.
+    pub fn update_custom_cursors(&mut self, event_loop: &ActiveEventLoop) {
+        for (icon, image) in self.pending_custom_cursors.drain() {
+            let [width, height] = image.image.size;
+            let (Ok(width), Ok(height), Ok(hotspot_x), Ok(hotspot_y)) = (
+                u16::try_from(width),
+                u16::try_from(height),
+                u16::try_from(image.hotspot[0]),
+                u16::try_from(image.hotspot[1]),
+            ) else {
+                log::warn!(
+                    "Cursor image for {icon:?} is too large: {width}x{height} with the hotspot at {:?}",
+                    image.hotspot
+                );
+                continue;
+            };
+            let rgba: Vec<u8> = image
+                .image
+                .pixels
+                .iter()
+                .flat_map(egui::Color32::to_srgba_unmultiplied)
+                .collect();
+            let source =
+                winit::window::CustomCursor::from_rgba(rgba, width, height, hotspot_x, hotspot_y);
+            match source {
+                Ok(source) => {
+                    let cursor = event_loop.create_custom_cursor(source);
+                    self.custom_cursors.insert(icon, (image, cursor));
+                    if self.current_cursor_icon == Some(icon) {
+                        self.current_cursor_icon = None; // Show the new cursor
+                    }
+                }
+                Err(err) => {
+                    log::warn!("Bad cursor image for {icon:?}: {err}");
+                }
+            }
+        }
+    }
+
     fn set_cursor_icon(&mut self, window: &Window, cursor_icon: egui::CursorIcon) {
+        let image = self.egui_ctx.cursor_image(cursor_icon);
+        let custom_cursor = match (&image, self.custom_cursors.get(&cursor_icon)) {
+            (Some(image), Some((cached, cursor))) if cached == image => Some(cursor.clone()),
+            (Some(image), _) => {
+                self.pending_custom_cursors
+                    .insert(cursor_icon, image.clone());
+                None
+            }
+            (None, _) => {
+                if self.custom_cursors.remove(&cursor_icon).is_some() {
+                    self.current_cursor_icon = None; // Go back to the standard icon
+                }
+                None
+            }
+        };
+
         if self.current_cursor_icon == Some(cursor_icon) {
             // Prevent flickering near frame boundary when Windows OS tries to control cursor icon for window resizing.
             // On other platforms: just early-out to save CPU.
@@ -922,7 +1123,10 @@ impl State {
         if is_pointer_in_window {
             self.current_cursor_icon = Some(cursor_icon);
 
-            if let Some(winit_cursor_icon) = translate_cursor(cursor_icon) {
+            if let Some(custom_cursor) = custom_cursor {
+                window.set_cursor_visible(true);
+                window.set_cursor(custom_cursor);
+            } else if let Some(winit_cursor_icon) = translate_cursor(cursor_icon) {
                 window.set_cursor_visible(true);
                 window.set_cursor(winit_cursor_icon);
             } else {
@@ -935,6 +1139,199 @@ impl State {
     }
 }
 
+impl Drop for State {
+    fn drop(&mut self) {
+        // The viewport is gone, so nobody will pass these on:
+        self.platform_handlers
+            .native_events
+            .remove_viewport(self.viewport_id);
+    }
+}
+
+/// Implementations of what winit can't do on its own, see [`State::set_platform_handlers`].
+///
+/// Each is `None` by default, and then egui-winit ignores the corresponding [`egui::OutputCommand`].
+#[derive(Clone, Default)]
+pub struct PlatformHandlers {
+    /// Handles [`egui::OutputCommand::StartDrag`].
+    ///
+    /// winit can't start drags of its own, so this is where you plug in
+    /// a platform-specific implementation (e.g. `NSDraggingSession` on macOS, or `DoDragDrop` on Windows).
+    pub start_drag: Option<StartDragHandler>,
+
+    /// Handles [`egui::OutputCommand::ShowContextMenu`], see [`egui::Response::native_context_menu`].
+    ///
+    /// winit has no menus of its own, so this is where you plug in a platform-specific implementation
+    /// (e.g. a modal `NSMenu` on macOS, or `TrackPopupMenu` on Windows).
+    /// Without one egui draws context menus itself.
+    pub context_menu: Option<ContextMenuHandler>,
+
+    /// Handles [`egui::OutputCommand::SetMenuBar`], see [`egui::native_menu::menu_bar`].
+    ///
+    /// This is where you plug in a platform-specific implementation
+    /// (e.g. `NSApp.mainMenu` on macOS, or `SetMenu` on Windows).
+    /// Without one egui draws menu bars itself.
+    pub menu_bar: Option<MenuBarHandler>,
+
+    /// Handles [`egui::OutputCommand::SetTrayIcon`], see [`egui::native_menu::TrayIcon`].
+    ///
+    /// winit has no tray icons, so this is where you plug in a platform-specific implementation
+    /// (e.g. `NSStatusItem` on macOS, or `Shell_NotifyIcon` on Windows).
+    /// Without one there is no tray icon.
+    pub tray: Option<TrayHandler>,
+
+    /// Applies [`egui::ViewportBuilder::with_shape`] and [`egui::ViewportBuilder::with_mouse_passthrough_regions`].
+    ///
+    /// winit can only make the whole window pass mouse clicks through,
+    /// so this is where you plug in a platform-specific implementation
+    /// (e.g. `SetWindowRgn` on Windows, or the X11 shape extension).
+    pub window_shape: Option<WindowShapeHandler>,
+
+    /// Where the [`MenuBarHandler`] and [`TrayHandler`] report clicks.
+    ///
+    /// Shared by all clones, so give the [`State`] of each viewport a clone of the same [`PlatformHandlers`].
+    pub native_events: NativeEvents,
+}
+
+/// Starts an OS-level drag of the given items from the given window.
+///
+/// See [`PlatformHandlers::start_drag`].
+pub type StartDragHandler =
+    std::sync::Arc<dyn Fn(&Window, Vec<egui::NativeDragItem>) + Send + Sync>;
+
+/// Shows a native context menu at the given position (in points) in the given window,
+/// and returns the id of the clicked button, if any.
+///
+/// See [`PlatformHandlers::context_menu`].
+pub type ContextMenuHandler = std::sync::Arc<
+    dyn Fn(&Window, egui::Pos2, &[egui::NativeMenuItem]) -> Option<egui::Id> + Send + Sync,
+>;
+
+/// Sets the native menu bar of the window of the given viewport.
+///
+/// When the user clicks a button in it, call [`NativeEvents::native_menu_item_clicked`]
+/// with the given context and viewport.
+///
+/// See [`PlatformHandlers::menu_bar`].
+pub type MenuBarHandler = std::sync::Arc<
+    dyn Fn(&egui::Context, &NativeEvents, &Window, ViewportId, &[egui::NativeMenuItem])
+        + Send
+        + Sync,
+>;
+
+/// Shows, updates or (given `None`) removes the icon in the system tray, next to the given window.
+///
+/// When the user clicks the icon, call [`NativeEvents::tray_icon_clicked`] with the given context,
+/// and when they click a button in its menu, call [`NativeEvents::native_menu_item_clicked`] with the root viewport.
+///
+/// With [`egui::native_menu::TrayIcon::hide_on_close`] the window may be hidden,
+/// and hidden windows don't run frames on every platform, so a primary click should also show the window.
+///
+/// See [`PlatformHandlers::tray`].
+pub type TrayHandler = std::sync::Arc<
+    dyn Fn(&egui::Context, &NativeEvents, &Window, Option<&egui::native_menu::TrayIcon>)
+        + Send
+        + Sync,
+>;
+
+/// Events from native menus and the tray that haven't been passed on to egui yet.
+///
+/// They are passed on by [`State::take_egui_input`] of their viewport,
+/// and dropped along with the [`State`] of their viewport.
+/// Can be used from any thread.
+#[derive(Clone, Default)]
+pub struct NativeEvents(std::sync::Arc<egui::mutex::Mutex<Vec<(ViewportId, egui::Event)>>>);
+
+impl NativeEvents {
+    /// Report a click on a button of a native menu of the given viewport,
+    /// e.g. from the menu bar set by a [`MenuBarHandler`].
+    ///
+    /// The click is passed on to egui as [`egui::Event::NativeMenuItemClicked`] during the next frame,
+    /// which is requested right away.
+    pub fn native_menu_item_clicked(
+        &self,
+        egui_ctx: &egui::Context,
+        viewport_id: ViewportId,
+        id: egui::Id,
+    ) {
+        self.push(
+            egui_ctx,
+            viewport_id,
+            egui::Event::NativeMenuItemClicked(id),
+        );
+    }
+
+    /// Report a click on the tray icon set by a [`TrayHandler`].
+    ///
+    /// The click is passed on to the root viewport as [`egui::Event::TrayIconClicked`].
+    pub fn tray_icon_clicked(&self, egui_ctx: &egui::Context, button: egui::PointerButton) {
+        self.push(
+            egui_ctx,
+            ViewportId::ROOT,
+            egui::Event::TrayIconClicked(button),
+        );
+    }
+
+    fn push(&self, egui_ctx: &egui::Context, viewport_id: ViewportId, event: egui::Event) {
+        self.0.lock().push((viewport_id, event));
+        egui_ctx.request_repaint_of(viewport_id);
+    }
+
+    fn take_into(&self, viewport_id: ViewportId, events: &mut Vec<egui::Event>) {
+        self.0.lock().retain(|(target, event)| {
+            if *target == viewport_id {
+                events.push(event.clone());
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    fn remove_viewport(&self, viewport_id: ViewportId) {
+        self.0.lock().retain(|(target, _)| *target != viewport_id);
+    }
+}
+
+/// Shapes a window and makes parts of it let mouse clicks through.
+///
+/// Called with the [`egui::WindowShape`] (empty for a rectangular window)
+/// and the mouse pass-through regions, both in physical pixels relative to the inner rect.
+///
+/// See [`PlatformHandlers::window_shape`].
+pub type WindowShapeHandler =
+    std::sync::Arc<dyn Fn(&Window, &egui::WindowShape, &[Rect]) + Send + Sync>;
+
+/// The shape and pass-through regions of a window, in points.
+#[derive(Clone, Default)]
+struct WindowShapeState {
+    shape: egui::WindowShape,
+    passthrough_regions: Vec<Rect>,
+
+    /// What they were last handed to the [`WindowShapeHandler`] at, if they have been since they changed.
+    applied_pixels_per_point: Option<f32>,
+}
+
+fn window_shape_state_id(window: &Window) -> egui::Id {
+    egui::Id::new("egui_winit_window_shape").with(window.id())
+}
+
+/// Viewport commands are processed without the [`State`] of the window,
+/// so store the change and apply it in [`State::handle_platform_output`] of the next frame.
+fn update_window_shape(
+    egui_ctx: &egui::Context,
+    viewport_id: ViewportId,
+    window: &Window,
+    update: impl FnOnce(&mut WindowShapeState),
+) {
+    egui_ctx.data_mut(|d| {
+        let state = d.get_temp_mut_or_default::<WindowShapeState>(window_shape_state_id(window));
+        update(state);
+        state.applied_pixels_per_point = None;
+    });
+    egui_ctx.request_repaint_of(viewport_id);
+}
+
 fn to_egui_theme(theme: winit::window::Theme) -> Theme {
     match theme {
         winit::window::Theme::Dark => Theme::Dark,
@@ -974,11 +1371,12 @@ pub fn outer_rect_in_points(window: &Window, pixels_per_point: f32) -> Option<Re
 pub fn update_viewport_info(
     viewport_info: &mut ViewportInfo,
     egui_ctx: &egui::Context,
+    viewport_id: ViewportId,
     window: &Window,
     is_init: bool,
 ) {
     profiling::function_scope!();
-    let pixels_per_point = pixels_per_point(egui_ctx, window);
+    let pixels_per_point = pixels_per_point_for(egui_ctx, viewport_id, window);
 
     let has_a_position = match window.is_minimized() {
         Some(true) => false,
@@ -997,15 +1395,41 @@ pub fn update_viewport_info(
         None
     };
 
-    let monitor_size = {
-        profiling::scope!("monitor_size");
-        if let Some(monitor) = window.current_monitor() {
-            let size = monitor.size().to_logical::<f32>(pixels_per_point.into());
-            Some(egui::vec2(size.width, size.height))
-        } else {
-            None
-        }
+    let current_monitor = window.current_monitor();
+
+    let monitor_size = current_monitor.as_ref().map(|monitor| {
+        let size = monitor.size().to_logical::<f32>(pixels_per_point.into());
+        egui::vec2(size.width, size.height)
+    });
+
+    // Enumerating the monitors is slow on some platforms, and winit has no event for monitors
+    // being plugged in or rearranged, so we only do it when they could look different to this window:
+    // when it moves to another monitor, that monitor or the scale changes, or the user comes back to it.
+    let monitor_changed = match (&current_monitor, viewport_info.monitor) {
+        (Some(handle), Some(index)) => viewport_info.monitors.get(index).map_or(true, |known| {
+            known.name != handle.name() || known.rect != monitor_rect(handle, pixels_per_point)
+        }),
+        (None, None) => false,
+        _ => true,
     };
+    let gained_focus = viewport_info.focused != Some(true) && window.has_focus();
+    if is_init || monitor_changed || gained_focus {
+        profiling::scope!("monitors");
+        let primary_monitor = window.primary_monitor();
+        let mut current = None;
+        let monitors: Vec<_> = window
+            .available_monitors()
+            .enumerate()
+            .map(|(i, handle)| {
+                if current_monitor.as_ref() == Some(&handle) {
+                    current = Some(i);
+                }
+                to_egui_monitor_info(&handle, primary_monitor.as_ref(), pixels_per_point)
+            })
+            .collect();
+        viewport_info.monitors = monitors.into();
+        viewport_info.monitor = current;
+    }
 
     viewport_info.title = Some(window.title());
     viewport_info.native_pixels_per_point = Some(window.scale_factor() as f32);
@@ -1026,6 +1450,91 @@ pub fn update_viewport_info(
     viewport_info.focused = Some(window.has_focus());
 }
 
+fn to_egui_monitor_info(
+    handle: &winit::monitor::MonitorHandle,
+    primary: Option<&winit::monitor::MonitorHandle>,
+    pixels_per_point: f32,
+) -> egui::MonitorInfo {
+    let rect = monitor_rect(handle, pixels_per_point);
+    let work_rect = physical_work_rect(handle)
+        .map_or(rect, |work_rect| work_rect / pixels_per_point)
+        .intersect(rect);
+    egui::MonitorInfo {
+        name: handle.name(),
+        rect,
+        work_rect,
+        scale_factor: handle.scale_factor() as f32,
+        is_primary: primary == Some(handle),
+    }
+}
+
+/// The whole monitor in egui points.
+fn monitor_rect(handle: &winit::monitor::MonitorHandle, pixels_per_point: f32) -> Rect {
+    let position = handle.position();
+    let size = handle.size();
+    Rect::from_min_size(
+        egui::pos2(position.x as f32, position.y as f32) / pixels_per_point,
+        egui::vec2(size.width as f32, size.height as f32) / pixels_per_point,
+    )
+}
+
+/// The part of the monitor not covered by task bars, docks and the like, in physical pixels.
+///
+/// winit doesn't know about those, so we ask the OS. `None` if we can't, e.g. on Linux.
+#[allow(unsafe_code)]
+fn physical_work_rect(handle: &winit::monitor::MonitorHandle) -> Option<Rect> {
+    #[cfg(target_os = "windows")]
+    {
+        use windows_sys::Win32::Graphics::Gdi::{GetMonitorInfoW, MONITORINFO};
+        use winit::platform::windows::MonitorHandleExtWindows as _;
+
+        // SAFETY: `MONITORINFO` is plain integers.
+        let mut info: MONITORINFO = unsafe { std::mem::zeroed() };
+        info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+        // SAFETY: `info` is a `MONITORINFO` with its `cbSize` set, as `GetMonitorInfoW` wants.
+        if unsafe { GetMonitorInfoW(handle.hmonitor() as _, &mut info) } == 0 {
+            return None;
+        }
+        let work = info.rcWork;
+        return Some(Rect::from_min_max(
+            egui::pos2(work.left as f32, work.top as f32),
+            egui::pos2(work.right as f32, work.bottom as f32),
+        ));
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        use objc2_app_kit::NSScreen;
+        use winit::platform::macos::MonitorHandleExtMacOS as _;
+
+        let screen = handle.ns_screen()?;
+        // SAFETY: winit gives us a pointer to a live `NSScreen`.
+        let screen: &NSScreen = unsafe { &*screen.cast::<NSScreen>() };
+        let (frame, visible) = (screen.frame(), screen.visibleFrame());
+        let scale = screen.backingScaleFactor() as f32;
+
+        // Cocoa has its origin at the bottom left, so the menu bar is at the top of the frame:
+        let left = (visible.origin.x - frame.origin.x) as f32;
+        let right =
+            ((frame.origin.x + frame.size.width) - (visible.origin.x + visible.size.width)) as f32;
+        let bottom = (visible.origin.y - frame.origin.y) as f32;
+        let top = ((frame.origin.y + frame.size.height) - (visible.origin.y + visible.size.height))
+            as f32;
+
+        let rect = monitor_rect(handle, 1.0);
+        return Some(Rect::from_min_max(
+            rect.min + scale * egui::vec2(left, top),
+            rect.max - scale * egui::vec2(right, bottom),
+        ));
+    }
+
+    #[allow(unreachable_code)]
+    {
+        let _ = handle;
+        None
+    }
+}
+
 fn open_url_in_browser(_url: &str) {
     #[cfg(feature = "webbrowser")]
     if let Err(err) = webbrowser::open(_url) {
@@ -1286,7 +1795,9 @@ fn translate_cursor(cursor_icon: egui::CursorIcon) -> Option<winit::window::Curs
         egui::CursorIcon::ContextMenu => Some(winit::window::CursorIcon::ContextMenu),
         egui::CursorIcon::Copy => Some(winit::window::CursorIcon::Copy),
         egui::CursorIcon::Crosshair => Some(winit::window::CursorIcon::Crosshair),
-        egui::CursorIcon::Default => Some(winit::window::CursorIcon::Default),
+        egui::CursorIcon::Default | egui::CursorIcon::Custom(_) => {
+            Some(winit::window::CursorIcon::Default)
+        }
         egui::CursorIcon::Grab => Some(winit::window::CursorIcon::Grab),
         egui::CursorIcon::Grabbing => Some(winit::window::CursorIcon::Grabbing),
         egui::CursorIcon::Help => Some(winit::window::CursorIcon::Help),
@@ -1332,18 +1843,27 @@ pub enum ActionRequested {
 
 pub fn process_viewport_commands(
     egui_ctx: &egui::Context,
+    viewport_id: ViewportId,
     info: &mut ViewportInfo,
     commands: impl IntoIterator<Item = ViewportCommand>,
     window: &Window,
     actions_requested: &mut HashSet<ActionRequested>,
 ) {
     for command in commands {
-        process_viewport_command(egui_ctx, window, command, info, actions_requested);
+        process_viewport_command(
+            egui_ctx,
+            viewport_id,
+            window,
+            command,
+            info,
+            actions_requested,
+        );
     }
 }
 
 fn process_viewport_command(
     egui_ctx: &egui::Context,
+    viewport_id: ViewportId,
     window: &Window,
     command: ViewportCommand,
     info: &mut ViewportInfo,
@@ -1355,13 +1875,13 @@ fn process_viewport_command(
 
     log::trace!("Processing ViewportCommand::{command:?}");
 
-    let pixels_per_point = pixels_per_point(egui_ctx, window);
+    let pixels_per_point = pixels_per_point_for(egui_ctx, viewport_id, window);
 
     match command {
         ViewportCommand::Close => {
             info.events.push(egui::ViewportEvent::Close);
         }
-        ViewportCommand::CancelClose => {
+        ViewportCommand::CancelClose | ViewportCommand::ReadPick(_) => {
             // Need to be handled elsewhere
         }
         ViewportCommand::StartDrag => {
@@ -1423,6 +1943,18 @@ fn process_viewport_command(
                 pixels_per_point * pos.y,
             ));
         }
+        ViewportCommand::CenterOnMonitor(index) => {
+            if let Some(monitor) = window.available_monitors().nth(index) {
+                let work_rect =
+                    physical_work_rect(&monitor).unwrap_or_else(|| monitor_rect(&monitor, 1.0));
+                let window_size = window.outer_size();
+                let window_size = egui::vec2(window_size.width as f32, window_size.height as f32);
+                let pos = work_rect.center() - 0.5 * window_size;
+                window.set_outer_position(PhysicalPosition::new(pos.x.round(), pos.y.round()));
+            } else {
+                log::warn!("ViewportCommand::CenterOnMonitor({index}): there is no such monitor");
+            }
+        }
         ViewportCommand::MinInnerSize(s) => {
             window.set_min_inner_size((s.is_finite() && s != Vec2::ZERO).then_some(
                 PhysicalSize::new(pixels_per_point * s.x, pixels_per_point * s.y),
@@ -1539,6 +2071,16 @@ fn process_viewport_command(
                 log::warn!("{command:?}: {err}");
             }
         }
+        ViewportCommand::MousePassthroughRegions(regions) => {
+            update_window_shape(egui_ctx, viewport_id, window, |state| {
+                state.passthrough_regions = regions;
+            });
+        }
+        ViewportCommand::Shape(shape) => {
+            update_window_shape(egui_ctx, viewport_id, window, |state| {
+                state.shape = shape;
+            });
+        }
         ViewportCommand::Screenshot(user_data) => {
             actions_requested.insert(ActionRequested::Screenshot(user_data));
         }
@@ -1562,6 +2104,7 @@ fn process_viewport_command(
 /// Possible causes of error include denied permission, incompatible system, and lack of memory.
 pub fn create_window(
     egui_ctx: &egui::Context,
+    viewport_id: ViewportId,
     event_loop: &ActiveEventLoop,
     viewport_builder: &ViewportBuilder,
 ) -> Result<Window, winit::error::OsError> {
@@ -1570,7 +2113,7 @@ pub fn create_window(
     let window_attributes =
         create_winit_window_attributes(egui_ctx, event_loop, viewport_builder.clone());
     let window = event_loop.create_window(window_attributes)?;
-    apply_viewport_builder_to_window(egui_ctx, &window, viewport_builder);
+    apply_viewport_builder_to_window(egui_ctx, viewport_id, &window, viewport_builder);
     Ok(window)
 }
 
@@ -1635,6 +2178,8 @@ pub fn create_winit_window_attributes(
         window_type: _window_type,
 
         mouse_passthrough: _, // handled in `apply_viewport_builder_to_window`
+        mouse_passthrough_regions: _, // handled in `apply_viewport_builder_to_window`
+        shape: _,             // handled in `apply_viewport_builder_to_window`
         clamp_size_to_monitor_size: _, // Handled in `viewport_builder` in `epi_integration.rs`
     } = viewport_builder;
 
@@ -1791,6 +2336,7 @@ fn to_winit_icon(icon: &egui::IconData) -> Option<winit::window::Icon> {
 /// Applies what `create_winit_window_builder` couldn't
 pub fn apply_viewport_builder_to_window(
     egui_ctx: &egui::Context,
+    viewport_id: ViewportId,
     window: &Window,
     builder: &ViewportBuilder,
 ) {
@@ -1800,13 +2346,24 @@ pub fn apply_viewport_builder_to_window(
         }
     }
 
+    if builder.shape.is_some() || builder.mouse_passthrough_regions.is_some() {
+        update_window_shape(egui_ctx, viewport_id, window, |state| {
+            if let Some(shape) = &builder.shape {
+                state.shape = shape.clone();
+            }
+            if let Some(regions) = &builder.mouse_passthrough_regions {
+                state.passthrough_regions = regions.clone();
+            }
+        });
+    }
+
     {
         // In `create_winit_window_builder` we didn't know
         // on what monitor the window would appear, so we didn't know
         // how to translate egui ui point to native physical pixels.
         // Now we do know:
 
-        let pixels_per_point = pixels_per_point(egui_ctx, window);
+        let pixels_per_point = pixels_per_point_for(egui_ctx, viewport_id, window);
 
         if let Some(size) = builder.inner_size {
             if window
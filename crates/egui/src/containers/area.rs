@@ -412,6 +412,11 @@ impl Area {
             layout,
         } = self;
 
+        ctx.profile_begin(|| match info.kind {
+            Some(kind) => format!("{kind:?} {id:?}"),
+            None => format!("Area {id:?}"),
+        });
+
         let constrain_rect = constrain_rect.unwrap_or_else(|| ctx.screen_rect());
 
         let layer_id = LayerId::new(order, id);
@@ -493,12 +498,23 @@ impl Area {
                     interact_rect: state.rect().intersect(constrain_rect),
                     sense,
                     enabled,
+                    parent_id: Id::NULL,
                 },
                 true,
             );
 
             if movable && move_response.dragged() {
-                if let Some(pivot_pos) = &mut state.pivot_pos {
+                let snapping = ctx.style().window_snapping;
+                if snapping.enabled && info.kind == Some(UiKind::Window) {
+                    snap_dragged_window(
+                        ctx,
+                        layer_id,
+                        &mut state,
+                        &move_response,
+                        constrain_rect,
+                        &snapping,
+                    );
+                } else if let Some(pivot_pos) = &mut state.pivot_pos {
                     *pivot_pos += move_response.drag_delta();
                 }
             }
@@ -582,7 +598,8 @@ impl Prepared {
             if let Some(last_became_visible_at) = self.state.last_became_visible_at {
                 let age =
                     ctx.input(|i| (i.time - last_became_visible_at) as f32 + i.predicted_dt / 2.0);
-                let opacity = crate::remap_clamp(age, 0.0..=ctx.style().animation_time, 0.0..=1.0);
+                let fade_time = ctx.animation_time(crate::style::AnimationKind::Fade);
+                let opacity = crate::remap_clamp(age, 0.0..=fade_time, 0.0..=1.0);
                 let opacity = emath::easing::quadratic_out(opacity); // slow fade-out = quick fade-in
                 ui.multiply_opacity(opacity);
                 if opacity < 1.0 {
@@ -634,6 +651,8 @@ impl Prepared {
             ctx.request_repaint();
         }
 
+        ctx.profile_end();
+
         response
     }
 }
@@ -647,6 +666,160 @@ fn pointer_pressed_on_area(ctx: &Context, layer_id: LayerId) -> bool {
     }
 }
 
+/// Move a dragged window, snapping it to the screen edges and other windows.
+///
+/// We keep track of where the window would be without snapping,
+/// so that the user can drag it away again once they move far enough.
+fn snap_dragged_window(
+    ctx: &Context,
+    layer_id: LayerId,
+    state: &mut AreaState,
+    move_response: &Response,
+    constrain_rect: Rect,
+    snapping: &crate::style::WindowSnapping,
+) {
+    let unsnapped_id = move_response.id.with("unsnapped_pos");
+    let unsnapped_pos = if move_response.drag_started() {
+        None
+    } else {
+        ctx.data(|data| data.get_temp::<Pos2>(unsnapped_id))
+    };
+    let unsnapped_pos =
+        unsnapped_pos.unwrap_or_else(|| state.left_top_pos()) + move_response.drag_delta();
+    ctx.data_mut(|data| data.insert_temp(unsnapped_id, unsnapped_pos));
+
+    let rect = Rect::from_min_size(unsnapped_pos, state.size.unwrap_or_default());
+
+    let screen = snapping.snap_to_screen_edges.then_some(constrain_rect);
+    let others: Vec<Rect> = if snapping.snap_to_windows {
+        ctx.memory(|mem| {
+            mem.areas()
+                .visible_windows()
+                .filter(|(id, _)| *id != layer_id)
+                .filter(|(_, state)| state.pivot_pos.is_some() && state.size.is_some())
+                .map(|(_, state)| state.rect())
+                .collect()
+        })
+    } else {
+        vec![]
+    };
+
+    let (offset, guides) = snap_rect(rect, screen, &others, snapping.snap_distance);
+    state.set_left_top_pos(unsnapped_pos + offset);
+
+    if snapping.show_guides && !guides.is_empty() {
+        let stroke = ctx.style().visuals.selection.stroke;
+        let painter = ctx.layer_painter(LayerId::new(
+            Order::Foreground,
+            Id::new("window_snapping_guides"),
+        ));
+        for guide in guides {
+            painter.extend(crate::Shape::dashed_line(&guide, stroke, 4.0, 4.0));
+        }
+    }
+}
+
+/// How much to move `rect` so that its edges line up with nearby edges.
+///
+/// Returns the offset, and the alignment guides (line segments) to paint for
+/// the edges that were snapped to other rectangles.
+fn snap_rect(
+    rect: Rect,
+    screen: Option<Rect>,
+    others: &[Rect],
+    snap_distance: f32,
+) -> (Vec2, Vec<[Pos2; 2]>) {
+    // The snap target along one axis: the offset to apply, and the other rectangle (if any).
+    fn best_snap(
+        min: f32,
+        max: f32,
+        targets: impl Iterator<Item = (f32, Option<Rect>)>,
+        snap_distance: f32,
+    ) -> Option<(f32, f32, Option<Rect>)> {
+        let mut best: Option<(f32, f32, Option<Rect>)> = None;
+        for (target, other) in targets {
+            for edge in [min, max] {
+                let offset = target - edge;
+                let is_better =
+                    best.map_or(true, |(best_offset, _, _)| offset.abs() < best_offset.abs());
+                if offset.abs() <= snap_distance && is_better {
+                    best = Some((offset, target, other));
+                }
+            }
+        }
+        best
+    }
+
+    // Only snap to windows that are close enough along the other axis to be neighbours.
+    let x_targets = screen
+        .map(|s| [(s.left(), None), (s.right(), None)])
+        .into_iter()
+        .flatten()
+        .chain(
+            others
+                .iter()
+                .filter(|o| o.y_range().expand(snap_distance).intersects(rect.y_range()))
+                .flat_map(|o| [(o.left(), Some(*o)), (o.right(), Some(*o))]),
+        );
+    let y_targets = screen
+        .map(|s| [(s.top(), None), (s.bottom(), None)])
+        .into_iter()
+        .flatten()
+        .chain(
+            others
+                .iter()
+                .filter(|o| o.x_range().expand(snap_distance).intersects(rect.x_range()))
+                .flat_map(|o| [(o.top(), Some(*o)), (o.bottom(), Some(*o))]),
+        );
+
+    let x_snap = best_snap(rect.left(), rect.right(), x_targets, snap_distance);
+    let y_snap = best_snap(rect.top(), rect.bottom(), y_targets, snap_distance);
+
+    let offset = Vec2::new(
+        x_snap.map_or(0.0, |(offset, _, _)| offset),
+        y_snap.map_or(0.0, |(offset, _, _)| offset),
+    );
+    let snapped = rect.translate(offset);
+
+    let mut guides = vec![];
+    if let Some((_, x, Some(other))) = x_snap {
+        let y_range = snapped.union(other).y_range();
+        guides.push([pos2(x, y_range.min), pos2(x, y_range.max)]);
+    }
+    if let Some((_, y, Some(other))) = y_snap {
+        let x_range = snapped.union(other).x_range();
+        guides.push([pos2(x_range.min, y), pos2(x_range.max, y)]);
+    }
+
+    (offset, guides)
+}
+
+#[test]
+fn test_snap_rect() {
+    let screen = Rect::from_min_size(Pos2::ZERO, Vec2::splat(1000.0));
+    let window = Rect::from_min_size(pos2(5.0, 500.0), Vec2::splat(100.0));
+
+    // Close to the left screen edge:
+    let (offset, guides) = snap_rect(window, Some(screen), &[], 10.0);
+    assert_eq!(offset, Vec2::new(-5.0, 0.0));
+    assert!(guides.is_empty(), "No guides for screen edges");
+
+    // Too far away:
+    let (offset, _) = snap_rect(window, Some(screen), &[], 4.0);
+    assert_eq!(offset, Vec2::ZERO);
+
+    // Next to another window, with its top almost aligned to ours:
+    let other = Rect::from_min_size(pos2(108.0, 503.0), Vec2::splat(100.0));
+    let (offset, guides) = snap_rect(window, None, &[other], 10.0);
+    assert_eq!(offset, Vec2::new(3.0, 3.0));
+    assert_eq!(guides.len(), 2);
+
+    // A window far away along the other axis is not a neighbour:
+    let far = Rect::from_min_size(pos2(108.0, 0.0), Vec2::splat(100.0));
+    let (offset, _) = snap_rect(window, None, &[far], 10.0);
+    assert_eq!(offset, Vec2::ZERO);
+}
+
 fn automatic_area_position(ctx: &Context, layer_id: LayerId) -> Pos2 {
     let mut existing: Vec<Rect> = ctx.memory(|mem| {
         mem.areas()
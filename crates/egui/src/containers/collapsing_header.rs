@@ -77,7 +77,13 @@ impl CollapsingState {
         if ctx.memory(|mem| mem.everything_is_visible()) {
             1.0
         } else {
-            ctx.animate_bool_responsive(self.id, self.state.open)
+            ctx.animate_bool_with_time_and_easing(
+                self.id,
+                self.state.open,
+                ctx.style()
+                    .animation_duration(crate::style::AnimationKind::Expand),
+                emath::easing::cubic_out,
+            )
         }
     }
 
@@ -93,7 +99,7 @@ impl CollapsingState {
             WidgetInfo::labeled(
                 WidgetType::Button,
                 ui.is_enabled(),
-                if self.is_open() { "Hide" } else { "Show" },
+                ui.translate(if self.is_open() { "Hide" } else { "Show" }),
             )
         });
 
@@ -1,9 +1,12 @@
+use std::sync::Arc;
+
 use epaint::Shape;
 
 use crate::{
     epaint, style::StyleModifier, style::WidgetVisuals, vec2, Align2, Context, Id, InnerResponse,
-    NumExt, Painter, Popup, PopupCloseBehavior, Rect, Response, ScrollArea, Sense, Stroke,
-    TextStyle, TextWrapMode, Ui, UiBuilder, Vec2, WidgetInfo, WidgetText, WidgetType,
+    Key, NumExt, Painter, Popup, PopupCloseBehavior, Rect, Response, ScrollArea, Sense, Spinner,
+    Stroke, TextEdit, TextStyle, TextWrapMode, Ui, UiBuilder, Vec2, WidgetInfo, WidgetText,
+    WidgetType,
 };
 
 #[allow(unused_imports)] // Documentation
@@ -12,6 +15,30 @@ use crate::style::Spacing;
 /// A function that paints the [`ComboBox`] icon
 pub type IconPainter = Box<dyn FnOnce(&Ui, Rect, &WidgetVisuals, bool)>;
 
+/// The items of a [`ComboBox::show_filtered`].
+///
+/// The items can be fetched lazily, e.g. from a server as the user types,
+/// and only the visible ones are ever asked for their label.
+pub trait ComboBoxItems {
+    /// Called every frame while the combo box is open, with the text the user has typed so far.
+    ///
+    /// Return `false` while the items matching `filter` are still being fetched,
+    /// and request a repaint when they arrive.
+    fn update(&mut self, filter: &str) -> bool;
+
+    /// How many items match the filter?
+    fn num_items(&self) -> usize;
+
+    /// The text of the item at `index` among the matching items.
+    fn label(&self, index: usize) -> WidgetText;
+
+    /// Is the item at `index` the selected one?
+    fn is_selected(&self, index: usize) -> bool;
+
+    /// The user picked the item at `index`.
+    fn select(&mut self, index: usize);
+}
+
 /// A drop-down selection menu with a descriptive label.
 ///
 /// ```
@@ -201,6 +228,31 @@ impl ComboBox {
         self,
         ui: &mut Ui,
         menu_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
+    ) -> InnerResponse<Option<R>> {
+        self.show_dyn(
+            ui,
+            Box::new(|ui, _popup_id, height| {
+                ScrollArea::vertical()
+                    .max_height(height)
+                    .show(ui, |ui| {
+                        // Often the button is very narrow, which means this popup
+                        // is also very narrow. Having wrapping on would therefore
+                        // result in labels that wrap very early.
+                        // Instead, we turn it off by default so that the labels
+                        // expand the width of the menu.
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        menu_contents(ui)
+                    })
+                    .inner
+            }),
+        )
+    }
+
+    /// Show the popup contents, given the id and max height of the popup.
+    fn show_dyn<'c, R>(
+        self,
+        ui: &mut Ui,
+        menu_contents: Box<dyn FnOnce(&mut Ui, Id, f32) -> R + 'c>,
     ) -> InnerResponse<Option<R>> {
         let Self {
             id_salt,
@@ -214,17 +266,19 @@ impl ComboBox {
         } = self;
 
         let button_id = ui.make_persistent_id(id_salt);
+        let popup_id = Self::widget_to_popup_id(button_id);
+        let height = height.unwrap_or_else(|| ui.spacing().combo_height);
 
         ui.horizontal(|ui| {
             let mut ir = combo_box_dyn(
                 ui,
                 button_id,
                 selected_text,
-                menu_contents,
+                Box::new(|ui| menu_contents(ui, popup_id, height)),
                 icon,
                 wrap_mode,
                 close_behavior,
-                (width, height),
+                width,
             );
             if let Some(label) = label {
                 ir.response.widget_info(|| {
@@ -286,6 +340,124 @@ impl ComboBox {
         response
     }
 
+    /// Show a combo box with a text field to filter the items,
+    /// and a scrollable list that only lays out the visible items,
+    /// so it can hold thousands of them.
+    ///
+    /// Pressing enter in the text field picks the first matching item.
+    ///
+    /// The returned [`Response`] is marked as changed when an item was picked.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// struct Countries<'a> {
+    ///     all: &'a [&'a str],
+    ///     matching: Vec<&'a str>,
+    ///     selected: &'a mut &'a str,
+    /// }
+    ///
+    /// impl egui::ComboBoxItems for Countries<'_> {
+    ///     fn update(&mut self, filter: &str) -> bool {
+    ///         self.matching = self.all.iter().copied().filter(|c| c.contains(filter)).collect();
+    ///         true // We don't need to wait for anything
+    ///     }
+    ///     fn num_items(&self) -> usize {
+    ///         self.matching.len()
+    ///     }
+    ///     fn label(&self, index: usize) -> egui::WidgetText {
+    ///         self.matching[index].into()
+    ///     }
+    ///     fn is_selected(&self, index: usize) -> bool {
+    ///         self.matching[index] == *self.selected
+    ///     }
+    ///     fn select(&mut self, index: usize) {
+    ///         *self.selected = self.matching[index];
+    ///     }
+    /// }
+    ///
+    /// let all = ["Denmark", "Norway", "Sweden"];
+    /// let mut selected = all[0];
+    /// egui::ComboBox::from_label("Country")
+    ///     .selected_text(selected)
+    ///     .show_filtered(ui, &mut Countries {
+    ///         all: &all,
+    ///         matching: vec![],
+    ///         selected: &mut selected,
+    ///     });
+    /// # });
+    /// ```
+    ///
+    /// For a list that is all in memory, [`Self::show_index_filtered`] is simpler.
+    pub fn show_filtered(mut self, ui: &mut Ui, items: &mut dyn ComboBoxItems) -> Response {
+        // Clicking the text field should not close the popup:
+        self.close_behavior = self
+            .close_behavior
+            .or(Some(PopupCloseBehavior::CloseOnClickOutside));
+
+        let InnerResponse {
+            inner: changed,
+            mut response,
+        } = self.show_dyn(
+            ui,
+            Box::new(|ui, popup_id, height| {
+                filtered_items_ui(ui, popup_id.with("filter"), height, items)
+            }),
+        );
+
+        match changed {
+            Some(true) => response.mark_changed(),
+            Some(false) => {}
+            None => {
+                // Closed, so start with an empty filter next time:
+                let popup_id = Self::widget_to_popup_id(response.id);
+                ui.data_mut(|d| d.remove_temp::<String>(popup_id.with("filter")));
+            }
+        }
+        response
+    }
+
+    /// Like [`Self::show_index`], but with a text field to filter the items,
+    /// see [`Self::show_filtered`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let alternatives: Vec<String> = (0..10_000).map(|i| format!("Item {i}")).collect();
+    /// let mut selected = 2;
+    /// egui::ComboBox::from_label("Select one!").show_index_filtered(
+    ///     ui,
+    ///     &mut selected,
+    ///     alternatives.len(),
+    ///     |i| &alternatives[i]
+    /// );
+    /// # });
+    /// ```
+    pub fn show_index_filtered<Text: Into<WidgetText>>(
+        self,
+        ui: &mut Ui,
+        selected: &mut usize,
+        len: usize,
+        get: impl Fn(usize) -> Text,
+    ) -> Response {
+        let matches_id =
+            Self::widget_to_popup_id(ui.make_persistent_id(self.id_salt)).with("matches");
+        let mut items = IndexItems {
+            selected,
+            len,
+            get,
+            matches: ui.data(|d| d.get_temp(matches_id)).unwrap_or_default(),
+        };
+        let response = self
+            .selected_text((items.get)(*items.selected))
+            .show_filtered(ui, &mut items);
+
+        if Self::is_open(ui.ctx(), response.id) {
+            ui.data_mut(|d| d.insert_temp(matches_id, items.matches));
+        } else {
+            ui.data_mut(|d| d.remove_temp::<IndexMatches>(matches_id));
+        }
+        response
+    }
+
     /// Check if the [`ComboBox`] with the given id has its popup menu currently opened.
     pub fn is_open(ctx: &Context, id: Id) -> bool {
         ctx.memory(|m| m.is_popup_open(Self::widget_to_popup_id(id)))
@@ -306,7 +478,7 @@ fn combo_box_dyn<'c, R>(
     icon: Option<IconPainter>,
     wrap_mode: Option<TextWrapMode>,
     close_behavior: Option<PopupCloseBehavior>,
-    (width, height): (Option<f32>, Option<f32>),
+    width: Option<f32>,
 ) -> InnerResponse<Option<R>> {
     let popup_id = ComboBox::widget_to_popup_id(button_id);
 
@@ -370,8 +542,6 @@ fn combo_box_dyn<'c, R>(
         }
     });
 
-    let height = height.unwrap_or_else(|| ui.spacing().combo_height);
-
     let inner = Popup::menu(&button_response)
         .id(popup_id)
         .style(StyleModifier::default())
@@ -379,19 +549,7 @@ fn combo_box_dyn<'c, R>(
         .close_behavior(close_behavior)
         .show(|ui| {
             ui.set_min_width(ui.available_width());
-
-            ScrollArea::vertical()
-                .max_height(height)
-                .show(ui, |ui| {
-                    // Often the button is very narrow, which means this popup
-                    // is also very narrow. Having wrapping on would therefore
-                    // result in labels that wrap very early.
-                    // Instead, we turn it off by default so that the labels
-                    // expand the width of the menu.
-                    ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
-                    menu_contents(ui)
-                })
-                .inner
+            menu_contents(ui)
         })
         .map(|r| r.inner);
 
@@ -401,6 +559,151 @@ fn combo_box_dyn<'c, R>(
     }
 }
 
+/// The contents of the popup of [`ComboBox::show_filtered`]. Returns `true` if an item was picked.
+fn filtered_items_ui(
+    ui: &mut Ui,
+    filter_id: Id,
+    max_height: f32,
+    items: &mut dyn ComboBoxItems,
+) -> bool {
+    let filter = ui.data(|d| d.get_temp::<String>(filter_id));
+    let just_opened = filter.is_none();
+    let mut filter = filter.unwrap_or_default();
+
+    let text_response = ui.add(
+        TextEdit::singleline(&mut filter)
+            .hint_text(format!("🔍 {}", ui.translate("Filter")))
+            .desired_width(f32::INFINITY),
+    );
+    if just_opened {
+        text_response.request_focus();
+    }
+    let picked_first = text_response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+    let mut picked = None;
+
+    if items.update(&filter) {
+        let num_items = items.num_items();
+        if picked_first && 0 < num_items {
+            picked = Some(0);
+        }
+
+        if num_items == 0 {
+            ui.weak(ui.translate("No matches"));
+        } else {
+            let padding = ui.spacing().button_padding.y;
+            let row_height = (ui.text_style_height(&TextStyle::Button) + 2.0 * padding)
+                .at_least(ui.spacing().interact_size.y);
+            let max_height = max_height - text_response.rect.height() - ui.spacing().item_spacing.y;
+
+            ScrollArea::vertical().max_height(max_height).show_rows(
+                ui,
+                row_height,
+                num_items,
+                |ui, row_range| {
+                    // See `ComboBox::show_ui` for why we don't wrap:
+                    ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                    for index in row_range {
+                        let label = items.label(index);
+                        if ui
+                            .selectable_label(items.is_selected(index), label)
+                            .clicked()
+                        {
+                            picked = Some(index);
+                        }
+                    }
+                },
+            );
+        }
+    } else {
+        ui.vertical_centered(|ui| ui.add(Spinner::new()));
+    }
+
+    ui.data_mut(|d| d.insert_temp(filter_id, filter));
+
+    if let Some(index) = picked {
+        items.select(index);
+        ui.close();
+    }
+    picked.is_some()
+}
+
+/// The items of [`ComboBox::show_index_filtered`].
+struct IndexItems<'a, Get> {
+    selected: &'a mut usize,
+    len: usize,
+    get: Get,
+    matches: IndexMatches,
+}
+
+/// Which items of [`ComboBox::show_index_filtered`] match the filter.
+///
+/// Filtering asks for the text of every item, so it is kept between frames
+/// and only redone when the filter or the number of items changes.
+#[derive(Clone, Default)]
+struct IndexMatches {
+    /// The lowercase filter, and the number of items, the matches are for.
+    filter: String,
+    len: usize,
+
+    /// Indices of the matching items, or `None` for all of them.
+    indices: Option<Arc<Vec<usize>>>,
+}
+
+impl IndexMatches {
+    fn item(&self, index: usize) -> usize {
+        self.indices
+            .as_ref()
+            .map_or(index, |indices| indices[index])
+    }
+}
+
+impl<Text: Into<WidgetText>, Get: Fn(usize) -> Text> ComboBoxItems for IndexItems<'_, Get> {
+    fn update(&mut self, filter: &str) -> bool {
+        let filter = filter.to_lowercase();
+        let matches = &mut self.matches;
+        if matches.len == self.len && matches.filter == filter {
+            return true;
+        }
+
+        matches.indices = if filter.is_empty() {
+            None
+        } else {
+            let is_match =
+                |&i: &usize| (self.get)(i).into().text().to_lowercase().contains(&filter);
+            let indices = match &matches.indices {
+                // Typing more can only remove matches:
+                Some(previous) if matches.len == self.len && filter.contains(&matches.filter) => {
+                    previous.iter().copied().filter(is_match).collect()
+                }
+                _ => (0..self.len).filter(is_match).collect(),
+            };
+            Some(Arc::new(indices))
+        };
+        matches.filter = filter;
+        matches.len = self.len;
+        true
+    }
+
+    fn num_items(&self) -> usize {
+        self.matches
+            .indices
+            .as_ref()
+            .map_or(self.len, |indices| indices.len())
+    }
+
+    fn label(&self, index: usize) -> WidgetText {
+        (self.get)(self.matches.item(index)).into()
+    }
+
+    fn is_selected(&self, index: usize) -> bool {
+        self.matches.item(index) == *self.selected
+    }
+
+    fn select(&mut self, index: usize) {
+        *self.selected = self.matches.item(index);
+    }
+}
+
 fn button_frame(
     ui: &mut Ui,
     id: Id,
@@ -465,3 +768,161 @@ fn paint_default_icon(painter: &Painter, rect: Rect, visuals: &WidgetVisuals) {
         Stroke::NONE,
     ));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CentralPanel, Event, Modifiers, RawInput};
+
+    /// Counts how many labels were asked for.
+    struct Items {
+        len: usize,
+        labels: std::cell::Cell<usize>,
+        selected: usize,
+    }
+
+    impl ComboBoxItems for Items {
+        fn update(&mut self, _filter: &str) -> bool {
+            true
+        }
+
+        fn num_items(&self) -> usize {
+            self.len
+        }
+
+        fn label(&self, index: usize) -> WidgetText {
+            self.labels.set(self.labels.get() + 1);
+            format!("Item {index}").into()
+        }
+
+        fn is_selected(&self, index: usize) -> bool {
+            index == self.selected
+        }
+
+        fn select(&mut self, index: usize) {
+            self.selected = index;
+        }
+    }
+
+    fn key_press(key: Key) -> Event {
+        Event::Key {
+            key,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: Modifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn filter_and_pick_with_enter() {
+        let ctx = Context::default();
+        let items: Vec<String> = (0..10_000).map(|i| format!("Item {i}")).collect();
+        let mut selected = 0;
+        let mut run = |events: Vec<Event>| {
+            let input = RawInput {
+                events,
+                ..Default::default()
+            };
+            let mut response = None;
+            let _ = ctx.run(input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    response = Some(ComboBox::from_id_salt("combo").show_index_filtered(
+                        ui,
+                        &mut selected,
+                        items.len(),
+                        |i| &items[i],
+                    ));
+                });
+            });
+            response.unwrap()
+        };
+
+        let id = run(vec![]).id;
+        ctx.memory_mut(|mem| mem.open_popup(ComboBox::widget_to_popup_id(id)));
+        run(vec![]); // Focuses the filter
+        run(vec![Event::Text("M 9999".to_lowercase())]);
+        let response = run(vec![key_press(Key::Enter)]);
+        assert!(response.changed());
+        assert!(!ComboBox::is_open(&ctx, id));
+        assert_eq!(selected, 9999);
+    }
+
+    #[test]
+    fn items_are_only_filtered_when_the_filter_changes() {
+        let ctx = Context::default();
+        let num_filtered = std::cell::Cell::new(0);
+        let mut selected = 0;
+        let mut run = |events: Vec<Event>| {
+            let input = RawInput {
+                events,
+                ..Default::default()
+            };
+            let mut response = None;
+            let _ = ctx.run(input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    response = Some(ComboBox::from_id_salt("combo").show_index_filtered(
+                        ui,
+                        &mut selected,
+                        1000,
+                        |i| {
+                            num_filtered.set(num_filtered.get() + 1);
+                            format!("Item {i}")
+                        },
+                    ));
+                });
+            });
+            response.unwrap()
+        };
+
+        let id = run(vec![]).id;
+        ctx.memory_mut(|mem| mem.open_popup(ComboBox::widget_to_popup_id(id)));
+        run(vec![]); // Focuses the filter
+
+        num_filtered.set(0);
+        run(vec![Event::Text("9".to_owned())]);
+        assert!(1000 <= num_filtered.get(), "All items are filtered");
+
+        num_filtered.set(0);
+        run(vec![]);
+        assert!(
+            num_filtered.get() < 100,
+            "Only the visible ones are asked for"
+        );
+
+        num_filtered.set(0);
+        run(vec![Event::Text("9".to_owned())]);
+        let num_matching_9 = 1000 - 729; // The numbers without a 9 in them
+        assert!(
+            num_filtered.get() < num_matching_9 + 100,
+            "Only the previous matches are filtered again"
+        );
+
+        run(vec![key_press(Key::Enter)]);
+        assert_eq!(selected, 99);
+    }
+
+    #[test]
+    fn only_visible_items_are_laid_out() {
+        let ctx = Context::default();
+        let mut items = Items {
+            len: 10_000,
+            labels: Default::default(),
+            selected: 0,
+        };
+        let run = |items: &mut Items| {
+            let mut id = None;
+            let _ = ctx.run(Default::default(), |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    id = Some(ComboBox::from_id_salt("combo").show_filtered(ui, items).id);
+                });
+            });
+            id.unwrap()
+        };
+        let id = run(&mut items);
+        ctx.memory_mut(|mem| mem.open_popup(ComboBox::widget_to_popup_id(id)));
+        run(&mut items);
+        let labels = items.labels.get();
+        assert!(0 < labels && labels < 100, "{labels} labels");
+    }
+}
@@ -0,0 +1,601 @@
+//! A flexbox-like layout, where the size of each item is resolved before it is placed.
+
+use emath::GuiRounding as _;
+
+use crate::{
+    pos2, vec2, Align, Context, Id, InnerResponse, Layout, NumExt as _, Rect, Ui, UiBuilder, Vec2,
+};
+
+/// The main axis of a [`Flex`] container.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FlexDirection {
+    /// Items are placed left-to-right.
+    #[default]
+    Horizontal,
+
+    /// Items are placed top-down.
+    Vertical,
+}
+
+/// How items are aligned along the cross axis of a [`Flex`] line
+/// (vertically in a horizontal [`Flex`]).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FlexAlign {
+    /// Left or top.
+    #[default]
+    Start,
+
+    Center,
+
+    /// Right or bottom.
+    End,
+
+    /// Make the item as tall (or wide) as the tallest (or widest) item on its line.
+    Stretch,
+}
+
+/// How the space left over on a [`Flex`] line is distributed when no item on it grows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FlexJustify {
+    /// Pack the items at the start of the line.
+    #[default]
+    Start,
+
+    /// Pack the items in the middle of the line.
+    Center,
+
+    /// Pack the items at the end of the line.
+    End,
+
+    /// The first item is at the start, the last at the end, with equal space between all items.
+    SpaceBetween,
+
+    /// Equal space around each item, so the space at the ends is half the space between items.
+    SpaceAround,
+
+    /// Equal space between the items and at both ends.
+    SpaceEvenly,
+}
+
+/// How a single item of a [`Flex`] container is sized and aligned.
+///
+/// By default an item has the size of its contents, and will shrink (but not grow)
+/// if there is not enough room on its line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlexItem {
+    grow: f32,
+    shrink: f32,
+    basis: Option<f32>,
+    min_size: Vec2,
+    max_size: Vec2,
+    align_self: Option<FlexAlign>,
+}
+
+impl Default for FlexItem {
+    fn default() -> Self {
+        Self {
+            grow: 0.0,
+            shrink: 1.0,
+            basis: None,
+            min_size: Vec2::ZERO,
+            max_size: Vec2::INFINITY,
+            align_self: None,
+        }
+    }
+}
+
+impl FlexItem {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How much of the free space on the line this item takes, relative to the other items.
+    ///
+    /// Default: `0.0` (don't grow).
+    #[inline]
+    pub fn grow(mut self, grow: f32) -> Self {
+        self.grow = grow;
+        self
+    }
+
+    /// How much this item shrinks, relative to the other items, when the line is too short.
+    ///
+    /// Items shrink in proportion to `shrink * basis`, so large items shrink more.
+    /// Default: `1.0`.
+    #[inline]
+    pub fn shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink;
+        self
+    }
+
+    /// The size along the main axis before growing or shrinking.
+    ///
+    /// Default: the size of the contents.
+    #[inline]
+    pub fn basis(mut self, basis: f32) -> Self {
+        self.basis = Some(basis);
+        self
+    }
+
+    /// The item is never made smaller than this.
+    #[inline]
+    pub fn min_size(mut self, min_size: impl Into<Vec2>) -> Self {
+        self.min_size = min_size.into();
+        self
+    }
+
+    /// The item is never made larger than this.
+    #[inline]
+    pub fn max_size(mut self, max_size: impl Into<Vec2>) -> Self {
+        self.max_size = max_size.into();
+        self
+    }
+
+    /// Override [`Flex::align_items`] for this item.
+    #[inline]
+    pub fn align_self(mut self, align: FlexAlign) -> Self {
+        self.align_self = Some(align);
+        self
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct State {
+    /// Each item, and the size of its contents, as of last frame.
+    items: Vec<(FlexItem, Vec2)>,
+}
+
+impl State {
+    fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.data_mut(|d| d.get_temp(id))
+    }
+
+    fn store(self, ctx: &Context, id: Id) {
+        // Like `Grid`, we don't persist this: the sizes are cheap to measure again.
+        ctx.data_mut(|d| d.insert_temp(id, self));
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A flexbox-like container, for responsive rows (or columns) of items.
+///
+/// Each item can grow into free space, shrink when space is short, wrap onto a new line,
+/// and be aligned on its line, all within min/max constraints.
+/// The layout is resolved before any item is placed, using the content sizes of the previous frame.
+/// Like [`crate::Grid`], the very first frame is therefore an invisible sizing pass.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// egui::Flex::horizontal().wrap(true).show(ui, |flex| {
+///     flex.add(egui::FlexItem::new(), |ui| ui.label("Name:"));
+///     flex.add(egui::FlexItem::new().grow(1.0), |ui| {
+///         ui.add_sized(ui.available_size(), egui::Button::new("Fills the rest"))
+///     });
+/// });
+/// # });
+/// ```
+///
+/// See also [`crate::GridTemplate`].
+#[must_use = "You should call .show()"]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Flex {
+    id_salt: Option<Id>,
+    direction: FlexDirection,
+    wrap: bool,
+    gap: Option<Vec2>,
+    justify: FlexJustify,
+    align_items: FlexAlign,
+}
+
+impl Flex {
+    /// Place items left-to-right.
+    #[inline]
+    pub fn horizontal() -> Self {
+        Self::default()
+    }
+
+    /// Place items top-down.
+    #[inline]
+    pub fn vertical() -> Self {
+        Self {
+            direction: FlexDirection::Vertical,
+            ..Default::default()
+        }
+    }
+
+    /// A source for the unique [`Id`], e.g. `.id_salt("second_flex")` or `.id_salt(loop_index)`.
+    ///
+    /// By default the next auto-id of the parent [`Ui`] is used.
+    #[inline]
+    pub fn id_salt(mut self, id_salt: impl std::hash::Hash) -> Self {
+        self.id_salt = Some(Id::new(id_salt));
+        self
+    }
+
+    #[inline]
+    pub fn direction(mut self, direction: FlexDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Start a new line when the items don't fit on one.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Horizontal (`x`) and vertical (`y`) space between items and between lines.
+    ///
+    /// Default: [`crate::style::Spacing::item_spacing`].
+    #[inline]
+    pub fn gap(mut self, gap: impl Into<Vec2>) -> Self {
+        self.gap = Some(gap.into());
+        self
+    }
+
+    /// How to distribute left-over space on a line.
+    ///
+    /// Default: [`FlexJustify::Start`].
+    #[inline]
+    pub fn justify(mut self, justify: FlexJustify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    /// How to align the items on each line along the cross axis.
+    ///
+    /// Default: [`FlexAlign::Start`].
+    #[inline]
+    pub fn align_items(mut self, align: FlexAlign) -> Self {
+        self.align_items = align;
+        self
+    }
+
+    pub fn show<R>(
+        self,
+        ui: &mut Ui,
+        add_contents: impl FnOnce(&mut FlexUi<'_>) -> R,
+    ) -> InnerResponse<R> {
+        self.show_dyn(ui, Box::new(add_contents))
+    }
+
+    fn show_dyn<'c, R>(
+        self,
+        ui: &mut Ui,
+        add_contents: Box<dyn FnOnce(&mut FlexUi<'_>) -> R + 'c>,
+    ) -> InnerResponse<R> {
+        let id = self.id_salt.map_or_else(
+            || ui.next_auto_id(),
+            |id_salt| ui.make_persistent_id(id_salt),
+        );
+        let prev_state = State::load(ui.ctx(), id);
+        let gap = self.gap.unwrap_or_else(|| ui.spacing().item_spacing);
+        let max_rect = ui.available_rect_before_wrap();
+
+        let mut ui_builder = UiBuilder::new().max_rect(max_rect);
+        if prev_state.is_none() {
+            if ui.is_visible() {
+                ui.ctx().request_discard("new Flex");
+            }
+            ui_builder = ui_builder.sizing_pass().invisible();
+        }
+
+        ui.scope_builder(ui_builder, |ui| {
+            let prev_state = prev_state.unwrap_or_default();
+            let rects = resolve_flex(&self, gap, max_rect.size(), &prev_state.items);
+            let mut flex_ui = FlexUi {
+                ui,
+                flex: self,
+                gap,
+                origin: max_rect.min,
+                rects,
+                state: State::default(),
+            };
+            let inner = add_contents(&mut flex_ui);
+
+            let FlexUi { ui, state, .. } = flex_ui;
+            if state != prev_state {
+                state.store(ui.ctx(), id);
+                ui.ctx().request_repaint();
+            }
+            inner
+        })
+    }
+}
+
+/// Add items to a [`Flex`] container with [`Self::add`].
+pub struct FlexUi<'a> {
+    ui: &'a mut Ui,
+    flex: Flex,
+    gap: Vec2,
+    origin: crate::Pos2,
+
+    /// Where to put each item, relative to `origin`, resolved from last frame.
+    rects: Vec<Rect>,
+
+    /// The items of this frame.
+    state: State,
+}
+
+impl FlexUi<'_> {
+    /// Add an item, sized and placed according to `item` and the other items of the container.
+    ///
+    /// The contents are laid out top-down, starting at the top left of the space given to the item.
+    pub fn add<R>(
+        &mut self,
+        item: FlexItem,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        let index = self.state.items.len();
+        let rect = if let Some(rect) = self.rects.get(index) {
+            rect.translate(self.origin.to_vec2())
+        } else {
+            // A new item: put it after the last one for now, and resolve its size next frame.
+            let last = self
+                .state
+                .items
+                .len()
+                .checked_sub(1)
+                .and_then(|i| self.rects.get(i));
+            let min = last.map_or(self.origin, |last| {
+                let last = last.translate(self.origin.to_vec2());
+                match self.flex.direction {
+                    FlexDirection::Horizontal => pos2(last.right() + self.gap.x, last.top()),
+                    FlexDirection::Vertical => pos2(last.left(), last.bottom() + self.gap.y),
+                }
+            });
+            Rect::from_min_max(min, self.ui.max_rect().max.max(min))
+        };
+
+        let mut child_ui = self.ui.new_child(
+            UiBuilder::new()
+                .id_salt(("flex_item", index))
+                .max_rect(rect)
+                .layout(Layout::top_down(Align::Min)),
+        );
+        let inner = add_contents(&mut child_ui);
+        let content_size = child_ui.min_rect().size();
+        self.state.items.push((item, content_size));
+
+        child_ui.expand_to_include_rect(rect);
+        self.ui.expand_to_include_rect(child_ui.min_rect());
+        InnerResponse::new(inner, child_ui.response())
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Grow (positive `free`) or shrink (negative `free`) `sizes` in proportion to `weights`,
+/// without going outside `min..=max`.
+///
+/// Returns the part of `free` that could not be handed out.
+pub(crate) fn distribute(
+    sizes: &mut [f32],
+    weights: &[f32],
+    min: &[f32],
+    max: &[f32],
+    mut free: f32,
+) -> f32 {
+    let mut frozen: Vec<bool> = weights.iter().map(|&weight| weight <= 0.0).collect();
+
+    // Each round either hands out all the free space, or freezes at least one item at its limit.
+    loop {
+        let total_weight: f32 = (0..sizes.len())
+            .filter(|&i| !frozen[i])
+            .map(|i| weights[i])
+            .sum();
+        if free == 0.0 || total_weight <= 0.0 {
+            return free;
+        }
+
+        let mut hit_limit = false;
+        let mut handed_out = 0.0;
+        for i in 0..sizes.len() {
+            if frozen[i] {
+                continue;
+            }
+            let wanted = sizes[i] + free * weights[i] / total_weight;
+            let new_size = wanted.clamp(min[i], max[i].at_least(min[i]));
+            if new_size != wanted {
+                frozen[i] = true;
+                hit_limit = true;
+            }
+            handed_out += new_size - sizes[i];
+            sizes[i] = new_size;
+        }
+        free -= handed_out;
+
+        if !hit_limit {
+            return 0.0;
+        }
+    }
+}
+
+/// Where to put each item, relative to the top left of the container.
+fn resolve_flex(flex: &Flex, gap: Vec2, available: Vec2, items: &[(FlexItem, Vec2)]) -> Vec<Rect> {
+    let horizontal = flex.direction == FlexDirection::Horizontal;
+    let main = |v: Vec2| if horizontal { v.x } else { v.y };
+    let cross = |v: Vec2| if horizontal { v.y } else { v.x };
+    let main_cross = |main: f32, cross: f32| {
+        if horizontal {
+            vec2(main, cross)
+        } else {
+            vec2(cross, main)
+        }
+    };
+
+    let available_main = main(available);
+    let main_gap = main(gap);
+    let cross_gap = cross(gap);
+
+    let hypothetical: Vec<f32> = items
+        .iter()
+        .map(|(item, content_size)| {
+            item.basis.unwrap_or_else(|| main(*content_size)).clamp(
+                main(item.min_size),
+                main(item.max_size).at_least(main(item.min_size)),
+            )
+        })
+        .collect();
+
+    // Break the items into lines:
+    let mut lines: Vec<std::ops::Range<usize>> = vec![];
+    let mut line_start = 0;
+    let mut line_main = 0.0;
+    for (i, &size) in hypothetical.iter().enumerate() {
+        let needed = if i == line_start {
+            size
+        } else {
+            line_main + main_gap + size
+        };
+        if flex.wrap && i != line_start && available_main < needed {
+            lines.push(line_start..i);
+            line_start = i;
+            line_main = size;
+        } else {
+            line_main = needed;
+        }
+    }
+    if line_start < items.len() {
+        lines.push(line_start..items.len());
+    }
+
+    let mut rects = Vec::with_capacity(items.len());
+    let mut cross_pos = 0.0;
+
+    for line in lines {
+        let line_items = &items[line.clone()];
+        let mut sizes = hypothetical[line.clone()].to_vec();
+        let mins: Vec<f32> = line_items
+            .iter()
+            .map(|(item, _)| main(item.min_size))
+            .collect();
+        let maxs: Vec<f32> = line_items
+            .iter()
+            .map(|(item, _)| main(item.max_size))
+            .collect();
+
+        let used = sizes.iter().sum::<f32>() + main_gap * (sizes.len() - 1) as f32;
+        let mut left_over = if available_main.is_finite() {
+            available_main - used
+        } else {
+            0.0
+        };
+        if 0.0 < left_over {
+            let weights: Vec<f32> = line_items.iter().map(|(item, _)| item.grow).collect();
+            left_over = distribute(&mut sizes, &weights, &mins, &maxs, left_over);
+        } else if left_over < 0.0 {
+            let weights: Vec<f32> = line_items
+                .iter()
+                .zip(&sizes)
+                .map(|((item, _), size)| item.shrink * size)
+                .collect();
+            left_over = distribute(&mut sizes, &weights, &mins, &maxs, left_over);
+        }
+        let left_over = left_over.at_least(0.0);
+
+        let n = sizes.len() as f32;
+        let (mut main_pos, spacing) = match flex.justify {
+            FlexJustify::SpaceBetween if 1.0 < n => (0.0, main_gap + left_over / (n - 1.0)),
+            FlexJustify::Start | FlexJustify::SpaceBetween => (0.0, main_gap),
+            FlexJustify::Center => (left_over / 2.0, main_gap),
+            FlexJustify::End => (left_over, main_gap),
+            FlexJustify::SpaceAround => (left_over / n / 2.0, main_gap + left_over / n),
+            FlexJustify::SpaceEvenly => (left_over / (n + 1.0), main_gap + left_over / (n + 1.0)),
+        };
+
+        let cross_sizes: Vec<f32> = line_items
+            .iter()
+            .map(|(item, content_size)| {
+                cross(*content_size).clamp(
+                    cross(item.min_size),
+                    cross(item.max_size).at_least(cross(item.min_size)),
+                )
+            })
+            .collect();
+        let line_cross = cross_sizes.iter().copied().fold(0.0, f32::max);
+
+        for (((item, _), &main_size), &cross_size) in
+            line_items.iter().zip(&sizes).zip(&cross_sizes)
+        {
+            let align = item.align_self.unwrap_or(flex.align_items);
+            let (cross_offset, cross_size) = match align {
+                FlexAlign::Start => (0.0, cross_size),
+                FlexAlign::Center => ((line_cross - cross_size) / 2.0, cross_size),
+                FlexAlign::End => (line_cross - cross_size, cross_size),
+                FlexAlign::Stretch => (0.0, line_cross.at_most(cross(item.max_size))),
+            };
+            let min = main_cross(main_pos, cross_pos + cross_offset);
+            let size = main_cross(main_size, cross_size);
+            rects.push(Rect::from_min_size(min.to_pos2(), size).round_ui());
+            main_pos += main_size + spacing;
+        }
+
+        cross_pos += line_cross + cross_gap;
+    }
+
+    rects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(grow: f32, width: f32) -> (FlexItem, Vec2) {
+        (FlexItem::new().grow(grow), vec2(width, 20.0))
+    }
+
+    #[test]
+    fn test_flex_grow_and_shrink() {
+        let flex = Flex::horizontal();
+        let gap = vec2(10.0, 10.0);
+
+        // 100 + 10 + 50 + 10 + 50 = 220 out of 400, so 180 left over, split 1:2
+        let items = [item(0.0, 100.0), item(1.0, 50.0), item(2.0, 50.0)];
+        let rects = resolve_flex(&flex, gap, vec2(400.0, 100.0), &items);
+        let widths: Vec<f32> = rects.iter().map(|r| r.width()).collect();
+        assert_eq!(widths, vec![100.0, 110.0, 170.0]);
+        assert_eq!(rects[2].right(), 400.0);
+
+        // Respect max size:
+        let items = [
+            (
+                FlexItem::new()
+                    .grow(1.0)
+                    .max_size(vec2(60.0, f32::INFINITY)),
+                vec2(50.0, 20.0),
+            ),
+            item(1.0, 50.0),
+        ];
+        let rects = resolve_flex(&flex, gap, vec2(200.0, 100.0), &items);
+        assert_eq!(rects[0].width(), 60.0);
+        assert_eq!(rects[1].width(), 130.0);
+
+        // Too little space: shrink in proportion to the size.
+        let items = [item(0.0, 100.0), item(0.0, 300.0)];
+        let rects = resolve_flex(&flex, Vec2::ZERO, vec2(200.0, 100.0), &items);
+        assert_eq!(rects[0].width(), 50.0);
+        assert_eq!(rects[1].width(), 150.0);
+    }
+
+    #[test]
+    fn test_flex_wrap_and_justify() {
+        let flex = Flex::horizontal()
+            .wrap(true)
+            .justify(FlexJustify::SpaceBetween);
+        let items = [item(0.0, 80.0), item(0.0, 80.0), item(0.0, 80.0)];
+        let rects = resolve_flex(&flex, vec2(10.0, 5.0), vec2(200.0, 100.0), &items);
+
+        // Two on the first line, pushed apart, and one on the second line:
+        assert_eq!(rects[0].left(), 0.0);
+        assert_eq!(rects[1].right(), 200.0);
+        assert_eq!(rects[2].left_top(), pos2(0.0, 25.0));
+    }
+}
@@ -1,8 +1,8 @@
 //! Frame container
 
 use crate::{
-    epaint, layers::ShapeIdx, InnerResponse, Response, Sense, Style, Ui, UiBuilder, UiKind,
-    UiStackInfo,
+    epaint, layers::ShapeIdx, ColorRole, InnerResponse, Response, Sense, Style, Ui, UiBuilder,
+    UiKind, UiStackInfo,
 };
 use epaint::{Color32, CornerRadius, Margin, MarginF32, Rect, Shadow, Shape, Stroke};
 
@@ -189,11 +189,13 @@ impl Frame {
     pub fn side_top_panel(style: &Style) -> Self {
         Self::new()
             .inner_margin(Margin::symmetric(8, 2))
-            .fill(style.visuals.panel_fill)
+            .fill(style.visuals.color(ColorRole::Background))
     }
 
     pub fn central_panel(style: &Style) -> Self {
-        Self::new().inner_margin(8).fill(style.visuals.panel_fill)
+        Self::new()
+            .inner_margin(8)
+            .fill(style.visuals.color(ColorRole::Background))
     }
 
     pub fn window(style: &Style) -> Self {
@@ -201,7 +203,7 @@ impl Frame {
             .inner_margin(style.spacing.window_margin)
             .corner_radius(style.visuals.window_corner_radius)
             .shadow(style.visuals.window_shadow)
-            .fill(style.visuals.window_fill())
+            .fill(style.visuals.color(ColorRole::Surface))
             .stroke(style.visuals.window_stroke())
     }
 
@@ -210,7 +212,7 @@ impl Frame {
             .inner_margin(style.spacing.menu_margin)
             .corner_radius(style.visuals.menu_corner_radius)
             .shadow(style.visuals.popup_shadow)
-            .fill(style.visuals.window_fill())
+            .fill(style.visuals.color(ColorRole::Surface))
             .stroke(style.visuals.window_stroke())
     }
 
@@ -219,7 +221,7 @@ impl Frame {
             .inner_margin(style.spacing.menu_margin)
             .corner_radius(style.visuals.menu_corner_radius)
             .shadow(style.visuals.popup_shadow)
-            .fill(style.visuals.window_fill())
+            .fill(style.visuals.color(ColorRole::Surface))
             .stroke(style.visuals.window_stroke())
     }
 
@@ -231,7 +233,7 @@ impl Frame {
         Self::new()
             .inner_margin(2)
             .corner_radius(style.visuals.widgets.noninteractive.corner_radius)
-            .fill(style.visuals.extreme_bg_color)
+            .fill(style.visuals.color(ColorRole::SurfaceSunken))
             .stroke(style.visuals.window_stroke())
     }
 
@@ -441,6 +443,8 @@ impl Frame {
 
         let widget_rect = self.widget_rect(content_rect);
 
+        // The border is aligned to pixels by `TessellationOptions::round_rects_to_pixels`.
+        // Its width is kept as is, since the content was laid out for it.
         let frame_shape = Shape::Rect(epaint::RectShape::new(
             widget_rect,
             corner_radius,
@@ -497,3 +501,28 @@ impl Prepared {
         self.allocate_space(ui)
     }
 }
+
+#[test]
+fn frame_stroke_keeps_its_width() {
+    use epaint::{Mesh, Pos2, TessellationOptions, Tessellator, Vec2};
+
+    let frame = Frame::new().stroke(Stroke::new(1.0, Color32::WHITE));
+    let shape = frame.paint(Rect::from_min_size(Pos2::ZERO, Vec2::splat(30.0)));
+    let options = TessellationOptions {
+        feathering: false,
+        ..Default::default()
+    };
+    let mut mesh = Mesh::default();
+    Tessellator::new(1.5, options, [1, 1], vec![]).tessellate_shape(shape, &mut mesh);
+
+    // The left border, laid out as 1pt wide, must not become 1.33pt (two pixels) wide:
+    let left_edges: Vec<f32> = mesh
+        .vertices
+        .iter()
+        .map(|vertex| vertex.pos.x)
+        .filter(|&x| x < 15.0)
+        .collect();
+    let outer = left_edges.iter().copied().fold(f32::INFINITY, f32::min);
+    let inner = left_edges.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    assert!((inner - outer - 1.0).abs() < 1e-4, "{outer}..{inner}");
+}
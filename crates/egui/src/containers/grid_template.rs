@@ -0,0 +1,401 @@
+//! A grid with explicitly sized column tracks, similar to `grid-template-columns` in CSS.
+
+use emath::GuiRounding as _;
+
+use crate::{
+    pos2, Align, Context, Id, InnerResponse, Layout, NumExt as _, Rect, Ui, UiBuilder, Vec2,
+};
+
+use super::flex::distribute;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TrackSize {
+    Fixed(f32),
+    Auto,
+    Fraction(f32),
+}
+
+/// The size of one column of a [`GridTemplate`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridTrack {
+    size: TrackSize,
+    min: f32,
+    max: f32,
+}
+
+impl GridTrack {
+    /// Exactly this wide.
+    #[inline]
+    pub fn fixed(width: f32) -> Self {
+        Self {
+            size: TrackSize::Fixed(width),
+            min: 0.0,
+            max: f32::INFINITY,
+        }
+    }
+
+    /// As wide as the widest cell in the column.
+    #[inline]
+    pub fn auto() -> Self {
+        Self {
+            size: TrackSize::Auto,
+            min: 0.0,
+            max: f32::INFINITY,
+        }
+    }
+
+    /// A fraction of the width left over by the fixed and auto columns,
+    /// in proportion to the `fr` of the other fractional columns.
+    #[inline]
+    pub fn fr(fr: f32) -> Self {
+        Self {
+            size: TrackSize::Fraction(fr),
+            min: 0.0,
+            max: f32::INFINITY,
+        }
+    }
+
+    /// Never make the column narrower than this.
+    #[inline]
+    pub fn at_least(mut self, min: f32) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Never make the column wider than this.
+    #[inline]
+    pub fn at_most(mut self, max: f32) -> Self {
+        self.max = max;
+        self
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct State {
+    /// The column span of each cell, and the size of its contents, as of last frame.
+    cells: Vec<(usize, Vec2)>,
+}
+
+impl State {
+    fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.data_mut(|d| d.get_temp(id))
+    }
+
+    fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|d| d.insert_temp(id, self));
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A grid where the width of each column is given up-front as a [`GridTrack`]:
+/// fixed, sized to its contents, or a fraction of the remaining width.
+///
+/// Cells are added left-to-right, top-down, wrapping to a new row after the last column.
+/// Each row is as tall as its tallest cell.
+/// As with [`crate::Flex`], sizes are resolved from the contents of the previous frame,
+/// so the first frame is an invisible sizing pass.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::GridTrack;
+/// egui::GridTemplate::new([GridTrack::auto(), GridTrack::fr(1.0), GridTrack::fixed(60.0)])
+///     .show(ui, |grid| {
+///         grid.cell(|ui| ui.label("Name"));
+///         grid.cell(|ui| ui.text_edit_singleline(&mut String::new()));
+///         grid.cell(|ui| ui.button("Save"));
+///
+///         grid.cell_span(3, |ui| ui.label("This cell spans all three columns"));
+///     });
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+#[derive(Clone, Debug)]
+pub struct GridTemplate {
+    id_salt: Option<Id>,
+    columns: Vec<GridTrack>,
+    gap: Option<Vec2>,
+}
+
+impl GridTemplate {
+    pub fn new(columns: impl Into<Vec<GridTrack>>) -> Self {
+        Self {
+            id_salt: None,
+            columns: columns.into(),
+            gap: None,
+        }
+    }
+
+    /// A source for the unique [`Id`], e.g. `.id_salt("second_grid")` or `.id_salt(loop_index)`.
+    ///
+    /// By default the next auto-id of the parent [`Ui`] is used.
+    #[inline]
+    pub fn id_salt(mut self, id_salt: impl std::hash::Hash) -> Self {
+        self.id_salt = Some(Id::new(id_salt));
+        self
+    }
+
+    /// Space between columns (`x`) and rows (`y`).
+    ///
+    /// Default: [`crate::style::Spacing::item_spacing`].
+    #[inline]
+    pub fn gap(mut self, gap: impl Into<Vec2>) -> Self {
+        self.gap = Some(gap.into());
+        self
+    }
+
+    pub fn show<R>(
+        self,
+        ui: &mut Ui,
+        add_contents: impl FnOnce(&mut GridTemplateUi<'_>) -> R,
+    ) -> InnerResponse<R> {
+        self.show_dyn(ui, Box::new(add_contents))
+    }
+
+    fn show_dyn<'c, R>(
+        self,
+        ui: &mut Ui,
+        add_contents: Box<dyn FnOnce(&mut GridTemplateUi<'_>) -> R + 'c>,
+    ) -> InnerResponse<R> {
+        let Self {
+            id_salt,
+            columns,
+            gap,
+        } = self;
+        let id = id_salt.map_or_else(
+            || ui.next_auto_id(),
+            |id_salt| ui.make_persistent_id(id_salt),
+        );
+        let prev_state = State::load(ui.ctx(), id);
+        let gap = gap.unwrap_or_else(|| ui.spacing().item_spacing);
+        let max_rect = ui.available_rect_before_wrap();
+
+        let mut ui_builder = UiBuilder::new().max_rect(max_rect);
+        if prev_state.is_none() {
+            if ui.is_visible() {
+                ui.ctx().request_discard("new GridTemplate");
+            }
+            ui_builder = ui_builder.sizing_pass().invisible();
+        }
+
+        ui.scope_builder(ui_builder, |ui| {
+            let prev_state = prev_state.unwrap_or_default();
+            let num_columns = columns.len().at_least(1);
+            let column_widths =
+                resolve_columns(&columns, gap.x, max_rect.width(), &prev_state.cells);
+            let row_heights = resolve_rows(num_columns, &prev_state.cells);
+
+            let mut grid_ui = GridTemplateUi {
+                ui,
+                gap,
+                origin: max_rect.min,
+                column_widths,
+                row_heights,
+                column: 0,
+                row: 0,
+                state: State::default(),
+            };
+            let inner = add_contents(&mut grid_ui);
+
+            let GridTemplateUi { ui, state, .. } = grid_ui;
+            if state != prev_state {
+                state.store(ui.ctx(), id);
+                ui.ctx().request_repaint();
+            }
+            inner
+        })
+    }
+}
+
+/// Add cells to a [`GridTemplate`] with [`Self::cell`].
+pub struct GridTemplateUi<'a> {
+    ui: &'a mut Ui,
+    gap: Vec2,
+    origin: crate::Pos2,
+    column_widths: Vec<f32>,
+
+    /// From last frame.
+    row_heights: Vec<f32>,
+
+    /// Where the next cell goes.
+    column: usize,
+    row: usize,
+
+    state: State,
+}
+
+impl GridTemplateUi<'_> {
+    /// Add a cell in the next column.
+    pub fn cell<R>(&mut self, add_contents: impl FnOnce(&mut Ui) -> R) -> InnerResponse<R> {
+        self.cell_span(1, add_contents)
+    }
+
+    /// Add a cell spanning the next `span` columns.
+    ///
+    /// If there are fewer than `span` columns left on this row, the cell starts on the next row.
+    pub fn cell_span<R>(
+        &mut self,
+        span: usize,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        let num_columns = self.column_widths.len();
+        let span = span.clamp(1, num_columns);
+        if num_columns < self.column + span {
+            self.column = 0;
+            self.row += 1;
+        }
+
+        let x: f32 = self.column_widths[..self.column]
+            .iter()
+            .map(|width| width + self.gap.x)
+            .sum();
+        let y: f32 = self.row_heights[..self.row.min(self.row_heights.len())]
+            .iter()
+            .map(|height| height + self.gap.y)
+            .sum();
+        let width = self.column_widths[self.column..self.column + span]
+            .iter()
+            .sum::<f32>()
+            + (span - 1) as f32 * self.gap.x;
+        let height = self.row_heights.get(self.row).copied().unwrap_or_default();
+        let rect =
+            Rect::from_min_size(self.origin + Vec2::new(x, y), Vec2::new(width, height)).round_ui();
+
+        let mut child_ui = self.ui.new_child(
+            UiBuilder::new()
+                .id_salt(("grid_cell", self.state.cells.len()))
+                .max_rect(Rect::from_min_max(
+                    rect.min,
+                    pos2(rect.max.x, self.ui.max_rect().max.y.at_least(rect.max.y)),
+                ))
+                .layout(Layout::top_down(Align::Min)),
+        );
+        let inner = add_contents(&mut child_ui);
+        self.state.cells.push((span, child_ui.min_rect().size()));
+
+        child_ui.expand_to_include_rect(rect);
+        self.ui.expand_to_include_rect(child_ui.min_rect());
+
+        self.column += span;
+        if num_columns <= self.column {
+            self.column = 0;
+            self.row += 1;
+        }
+
+        InnerResponse::new(inner, child_ui.response())
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Visit each cell with its column, row and span, following the same wrapping as [`GridTemplateUi`].
+fn place_cells(num_columns: usize, cells: &[(usize, Vec2)]) -> Vec<(usize, usize, usize)> {
+    let mut placed = Vec::with_capacity(cells.len());
+    let (mut column, mut row) = (0, 0);
+    for &(span, _) in cells {
+        let span = span.clamp(1, num_columns);
+        if num_columns < column + span {
+            column = 0;
+            row += 1;
+        }
+        placed.push((column, row, span));
+        column += span;
+        if num_columns <= column {
+            column = 0;
+            row += 1;
+        }
+    }
+    placed
+}
+
+fn resolve_columns(
+    columns: &[GridTrack],
+    gap: f32,
+    available_width: f32,
+    cells: &[(usize, Vec2)],
+) -> Vec<f32> {
+    if columns.is_empty() {
+        return vec![available_width];
+    }
+
+    // Only cells spanning a single column decide the width of auto columns:
+    let mut content_widths = vec![0.0_f32; columns.len()];
+    for ((column, _, span), (_, size)) in place_cells(columns.len(), cells).into_iter().zip(cells) {
+        if span == 1 {
+            content_widths[column] = content_widths[column].max(size.x);
+        }
+    }
+
+    let mins: Vec<f32> = columns.iter().map(|track| track.min).collect();
+    let maxs: Vec<f32> = columns
+        .iter()
+        .map(|track| track.max.at_least(track.min))
+        .collect();
+    let mut widths: Vec<f32> = columns
+        .iter()
+        .zip(&content_widths)
+        .map(|(track, &content_width)| {
+            let width = match track.size {
+                TrackSize::Fixed(width) => width,
+                TrackSize::Auto => content_width,
+                TrackSize::Fraction(_) => 0.0,
+            };
+            width.clamp(track.min, track.max.at_least(track.min))
+        })
+        .collect();
+
+    if available_width.is_finite() {
+        let used = widths.iter().sum::<f32>() + gap * (columns.len() - 1) as f32;
+        let fractions: Vec<f32> = columns
+            .iter()
+            .map(|track| match track.size {
+                TrackSize::Fraction(fr) => fr,
+                TrackSize::Fixed(_) | TrackSize::Auto => 0.0,
+            })
+            .collect();
+        distribute(
+            &mut widths,
+            &fractions,
+            &mins,
+            &maxs,
+            (available_width - used).at_least(0.0),
+        );
+    }
+
+    widths
+}
+
+fn resolve_rows(num_columns: usize, cells: &[(usize, Vec2)]) -> Vec<f32> {
+    let mut heights: Vec<f32> = vec![];
+    for ((_, row, _), (_, size)) in place_cells(num_columns, cells).into_iter().zip(cells) {
+        if heights.len() <= row {
+            heights.resize(row + 1, 0.0);
+        }
+        heights[row] = heights[row].max(size.y);
+    }
+    heights
+}
+
+#[test]
+fn test_grid_template_columns() {
+    let columns = [
+        GridTrack::fixed(50.0),
+        GridTrack::auto(),
+        GridTrack::fr(1.0),
+        GridTrack::fr(3.0),
+    ];
+    let cells = [
+        (1, Vec2::new(10.0, 10.0)),
+        (1, Vec2::new(80.0, 10.0)),
+        (2, Vec2::new(500.0, 10.0)), // spans two columns, so it doesn't affect their widths
+        (1, Vec2::new(10.0, 20.0)),
+        (1, Vec2::new(100.0, 30.0)),
+    ];
+    let widths = resolve_columns(&columns, 10.0, 400.0, &cells);
+    // 400 - 50 - 100 - 3 * 10 = 220 left for the fractions
+    assert_eq!(widths, vec![50.0, 100.0, 55.0, 165.0]);
+
+    assert_eq!(resolve_rows(columns.len(), &cells), vec![10.0, 30.0]);
+}
@@ -0,0 +1,212 @@
+//! A loupe showing a zoomed-in view of whatever is under the pointer.
+//!
+//! See [`Magnifier`].
+
+use emath::{vec2, Align2, Pos2, Rect, TSTransform, Vec2};
+use epaint::{ClippedShape, Shape, Stroke, StrokeKind};
+
+use crate::{Context, Id, LayerId, Order, TextStyle};
+
+/// A loupe next to the pointer, showing a zoomed-in view of what is under it.
+///
+/// Useful for picking colors and precise drawing.
+///
+/// The view is made by painting everything painted so far this pass again, scaled up,
+/// so call [`Self::show`] at the very end of the pass, after everything else has been painted.
+/// [`crate::PaintCallback`]s are not shown in the view.
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// # let magnify = true;
+/// egui::CentralPanel::default().show(ctx, |ui| {
+///     ui.label("Something small");
+/// });
+///
+/// if magnify {
+///     egui::Magnifier::new().magnification(6.0).show(ctx);
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+#[derive(Clone, Debug)]
+pub struct Magnifier {
+    id: Id,
+    magnification: f32,
+    size: Vec2,
+    crosshair: bool,
+    readout: bool,
+}
+
+impl Default for Magnifier {
+    fn default() -> Self {
+        Self {
+            id: Id::new("magnifier"),
+            magnification: 4.0,
+            size: Vec2::splat(150.0),
+            crosshair: true,
+            readout: true,
+        }
+    }
+}
+
+impl Magnifier {
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Needed if you show more than one magnifier at the same time.
+    #[inline]
+    pub fn id_salt(mut self, id_salt: impl std::hash::Hash) -> Self {
+        self.id = Id::new(id_salt);
+        self
+    }
+
+    /// How much the magnifier enlarges.
+    ///
+    /// Default: `4.0`.
+    #[inline]
+    pub fn magnification(mut self, magnification: f32) -> Self {
+        self.magnification = magnification.max(1.0);
+        self
+    }
+
+    /// The size of the loupe in points.
+    ///
+    /// Default: `150x150`.
+    #[inline]
+    pub fn size(mut self, size: impl Into<Vec2>) -> Self {
+        self.size = size.into();
+        self
+    }
+
+    /// Mark the pointer position with a crosshair.
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn crosshair(mut self, crosshair: bool) -> Self {
+        self.crosshair = crosshair;
+        self
+    }
+
+    /// Show the pointer position below the loupe.
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn readout(mut self, readout: bool) -> Self {
+        self.readout = readout;
+        self
+    }
+
+    /// Paint the loupe, if the pointer is over the window.
+    ///
+    /// Returns the area that is magnified, in global coordinates.
+    pub fn show(self, ctx: &Context) -> Option<Rect> {
+        let screen_rect = ctx.screen_rect();
+        let pos = ctx
+            .pointer_hover_pos()
+            .filter(|pos| screen_rect.contains(*pos))?;
+
+        let shapes = {
+            let (area_order, to_global) =
+                ctx.memory(|mem| (mem.areas().order().to_vec(), mem.to_global.clone()));
+            ctx.graphics(|graphics| graphics.all_shapes(&area_order, &to_global))
+        };
+
+        let rect = self.loupe_rect(pos, screen_rect);
+        let source = Rect::from_center_size(pos, rect.size() / self.magnification);
+        let to_loupe = TSTransform::new(
+            rect.center().to_vec2() - self.magnification * pos.to_vec2(),
+            self.magnification,
+        );
+
+        let painter = ctx.layer_painter(LayerId::new(Order::Tooltip, self.id));
+        let visuals = ctx.style().visuals.clone();
+        painter.rect_filled(rect, 0.0, visuals.extreme_bg_color);
+
+        for ClippedShape { clip_rect, shape } in shapes {
+            if matches!(shape, Shape::Callback(_))
+                || !clip_rect.intersects(source)
+                || !shape.visual_bounding_rect().intersects(source)
+            {
+                continue;
+            }
+            let mut shape = shape;
+            shape.transform(to_loupe);
+            painter
+                .with_clip_rect((to_loupe * clip_rect).intersect(rect))
+                .add(shape);
+        }
+
+        if self.crosshair {
+            let crosshair = Stroke::new(1.0, visuals.strong_text_color().gamma_multiply(0.6));
+            let gap = 0.5 * self.magnification + 2.0;
+            let center = rect.center();
+            painter.hline(rect.left()..=center.x - gap, center.y, crosshair);
+            painter.hline(center.x + gap..=rect.right(), center.y, crosshair);
+            painter.vline(center.x, rect.top()..=center.y - gap, crosshair);
+            painter.vline(center.x, center.y + gap..=rect.bottom(), crosshair);
+        }
+        painter.rect_stroke(rect, 0.0, visuals.window_stroke, StrokeKind::Outside);
+
+        if self.readout {
+            painter.text(
+                rect.center_bottom() + vec2(0.0, 2.0),
+                Align2::CENTER_TOP,
+                format!("{:.1}, {:.1}", pos.x, pos.y),
+                TextStyle::Small.resolve(&ctx.style()),
+                visuals.text_color(),
+            );
+        }
+
+        Some(source)
+    }
+
+    /// Below right of the pointer, unless that's off-screen.
+    fn loupe_rect(&self, pos: Pos2, screen_rect: Rect) -> Rect {
+        let offset = vec2(24.0, 24.0);
+        let mut min = pos + offset;
+        if screen_rect.right() < min.x + self.size.x {
+            min.x = pos.x - offset.x - self.size.x;
+        }
+        if screen_rect.bottom() < min.y + self.size.y {
+            min.y = pos.y - offset.y - self.size.y;
+        }
+        Rect::from_min_size(min, self.size)
+    }
+}
+
+#[test]
+fn test_magnifier_replays_shapes() {
+    use crate::{Color32, Event, RawInput};
+
+    let ctx = Context::default();
+    let mut source = None;
+    let output = ctx.run(
+        RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(400.0, 400.0))),
+            events: vec![Event::PointerMoved(Pos2::new(100.0, 100.0))],
+            ..Default::default()
+        },
+        |ctx| {
+            ctx.layer_painter(LayerId::background()).rect_filled(
+                Rect::from_center_size(Pos2::new(100.0, 100.0), Vec2::splat(4.0)),
+                0.0,
+                Color32::RED,
+            );
+            source = Magnifier::new().magnification(10.0).show(ctx);
+        },
+    );
+
+    assert_eq!(
+        source.map(|source| source.center()),
+        Some(Pos2::new(100.0, 100.0))
+    );
+
+    // The red square is painted again, ten times bigger:
+    let magnified = output.shapes.iter().any(|clipped| match &clipped.shape {
+        Shape::Rect(rect) => rect.fill == Color32::RED && rect.rect.width() == 40.0,
+        _ => false,
+    });
+    assert!(magnified);
+}
@@ -434,6 +434,8 @@ impl SubMenu {
             .align(RectAlign::RIGHT_START)
             .layout(Layout::top_down_justified(Align::Min))
             .gap(gap)
+            // Submenus sit right next to the menu, so there is no room for an arrow
+            .arrow_size(0.0)
             .style(menu_config.style.clone())
             .frame(frame)
             // The close behavior is handled by the menu (see below)
@@ -6,16 +6,21 @@ pub(crate) mod area;
 pub mod close_tag;
 pub mod collapsing_header;
 mod combo_box;
+mod flex;
 pub mod frame;
+mod grid_template;
+mod magnifier;
 pub mod menu;
 pub mod modal;
 pub mod old_popup;
 pub mod panel;
 mod popup;
+mod region_capture;
 pub(crate) mod resize;
-mod scene;
+pub(crate) mod scene;
 pub mod scroll_area;
 mod sides;
+mod splitter;
 mod tooltip;
 pub(crate) mod window;
 
@@ -23,15 +28,20 @@ pub use {
     area::{Area, AreaState},
     collapsing_header::{CollapsingHeader, CollapsingResponse},
     combo_box::*,
+    flex::{Flex, FlexAlign, FlexDirection, FlexItem, FlexJustify, FlexUi},
     frame::Frame,
+    grid_template::{GridTemplate, GridTemplateUi, GridTrack},
+    magnifier::Magnifier,
     modal::{Modal, ModalResponse},
     old_popup::*,
     panel::{CentralPanel, SidePanel, TopBottomPanel},
     popup::*,
+    region_capture::{CapturedRegion, RegionCapture, RegionCaptureResult},
     resize::Resize,
     scene::Scene,
     scroll_area::ScrollArea,
     sides::Sides,
+    splitter::{Splitter, SplitterOrientation, SplitterState},
     tooltip::*,
     window::Window,
 };
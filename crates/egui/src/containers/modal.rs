@@ -8,6 +8,11 @@ use emath::{Align2, Vec2};
 /// Similar to a [`crate::Window`] but centered and with a backdrop that
 /// blocks input to the rest of the UI.
 ///
+/// While a modal is open, keyboard focus is trapped inside it: tabbing only cycles through
+/// the widgets of the topmost modal, and widgets behind it can't be focused.
+/// When the modal closes (i.e. you stop calling [`Modal::show`]), focus is given back
+/// to the widget that had it before the modal was opened.
+///
 /// You can show multiple modals on top of each other. The topmost modal will always be
 /// the most recently shown one.
 /// If multiple modals are newly shown in the same frame, the order of the modals not undefined
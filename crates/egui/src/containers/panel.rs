@@ -18,12 +18,18 @@
 use emath::GuiRounding as _;
 
 use crate::{
-    lerp, vec2, Align, Context, CursorIcon, Frame, Id, InnerResponse, LayerId, Layout, NumExt,
+    lerp, pos2, vec2, Context, CursorIcon, Frame, Id, InnerResponse, LayerId, Layout, NumExt,
     Rangef, Rect, Sense, Stroke, Ui, UiBuilder, UiKind, UiStackInfo, Vec2,
 };
 
 fn animate_expansion(ctx: &Context, id: Id, is_expanded: bool) -> f32 {
-    ctx.animate_bool_responsive(id, is_expanded)
+    ctx.animate_bool_with_time_and_easing(
+        id,
+        is_expanded,
+        ctx.style()
+            .animation_duration(crate::style::AnimationKind::Expand),
+        emath::easing::cubic_out,
+    )
 }
 
 /// State regarding panels.
@@ -242,6 +248,13 @@ impl SidePanel {
             width_range,
         } = self;
 
+        // Left and right are swapped in a right-to-left ui:
+        let side = if ui.style().layout_direction.is_right_to_left() {
+            side.opposite()
+        } else {
+            side
+        };
+
         let available_rect = ui.available_rect_before_wrap();
         let mut panel_rect = available_rect;
         let mut width = default_width;
@@ -283,7 +296,7 @@ impl SidePanel {
                     Side::Right => UiKind::RightPanel,
                 }))
                 .max_rect(panel_rect)
-                .layout(Layout::top_down(Align::Min)),
+                .layout(Layout::top_down(ui.style().layout_direction.start_align())),
         );
         panel_ui.expand_to_include_rect(panel_rect);
         panel_ui.set_clip_rect(panel_rect); // If we overflow, don't do so visibly (#4475)
@@ -325,14 +338,14 @@ impl SidePanel {
 
         if resize_hover || is_resizing {
             let cursor_icon = if width <= width_range.min {
-                match self.side {
+                match side {
                     Side::Left => CursorIcon::ResizeEast,
                     Side::Right => CursorIcon::ResizeWest,
                 }
             } else if width < width_range.max {
                 CursorIcon::ResizeHorizontal
             } else {
-                match self.side {
+                match side {
                     Side::Left => CursorIcon::ResizeWest,
                     Side::Right => CursorIcon::ResizeEast,
                 }
@@ -358,7 +371,13 @@ impl SidePanel {
 
             // Make sure the line is on the inside of the panel:
             let resize_x = resize_x + 0.5 * side.sign() * stroke.width;
-            ui.painter().vline(resize_x, panel_rect.y_range(), stroke);
+            ui.painter().hairline(
+                [
+                    pos2(resize_x, panel_rect.top()),
+                    pos2(resize_x, panel_rect.bottom()),
+                ],
+                stroke,
+            );
         }
 
         inner_response
@@ -379,7 +398,11 @@ impl SidePanel {
         ctx: &Context,
         add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
     ) -> InnerResponse<R> {
-        let side = self.side;
+        let side = if ctx.style().layout_direction.is_right_to_left() {
+            self.side.opposite()
+        } else {
+            self.side
+        };
         let available_rect = ctx.available_rect();
         let mut panel_ui = Ui::new(
             ctx.clone(),
@@ -780,7 +803,7 @@ impl TopBottomPanel {
                     TopBottomSide::Bottom => UiKind::BottomPanel,
                 }))
                 .max_rect(panel_rect)
-                .layout(Layout::top_down(Align::Min)),
+                .layout(Layout::top_down(ui.style().layout_direction.start_align())),
         );
         panel_ui.expand_to_include_rect(panel_rect);
         panel_ui.set_clip_rect(panel_rect); // If we overflow, don't do so visibly (#4475)
@@ -855,7 +878,13 @@ impl TopBottomPanel {
 
             // Make sure the line is on the inside of the panel:
             let resize_y = resize_y + 0.5 * side.sign() * stroke.width;
-            ui.painter().hline(panel_rect.x_range(), resize_y, stroke);
+            ui.painter().hairline(
+                [
+                    pos2(panel_rect.left(), resize_y),
+                    pos2(panel_rect.right(), resize_y),
+                ],
+                stroke,
+            );
         }
 
         inner_response
@@ -1116,7 +1145,7 @@ impl CentralPanel {
             UiBuilder::new()
                 .ui_stack_info(UiStackInfo::new(UiKind::CentralPanel))
                 .max_rect(panel_rect)
-                .layout(Layout::top_down(Align::Min)),
+                .layout(Layout::top_down(ui.style().layout_direction.start_align())),
         );
         panel_ui.set_clip_rect(panel_rect); // If we overflow, don't do so visibly (#4475)
 
@@ -1166,3 +1195,40 @@ fn clamp_to_range(x: f32, range: Rangef) -> f32 {
     let range = range.as_positive();
     x.clamp(range.min, range.max)
 }
+
+#[test]
+fn test_side_panel_right_to_left() {
+    use crate::{style::LayoutDirection, RawInput};
+
+    let screen_rect = Rect::from_min_size(crate::Pos2::ZERO, vec2(400.0, 300.0));
+    for direction in [LayoutDirection::LeftToRight, LayoutDirection::RightToLeft] {
+        let ctx = Context::default();
+        ctx.style_mut(|style| style.layout_direction = direction);
+        let mut panel_rect = Rect::NOTHING;
+        let mut label_rect = Rect::NOTHING;
+        let input = RawInput {
+            screen_rect: Some(screen_rect),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            panel_rect = SidePanel::left("panel")
+                .show(ctx, |ui| label_rect = ui.label("Hello").rect)
+                .response
+                .rect;
+        });
+
+        match direction {
+            LayoutDirection::LeftToRight => {
+                assert_eq!(panel_rect.left(), screen_rect.left());
+                assert!(label_rect.center().x < panel_rect.center().x);
+            }
+            LayoutDirection::RightToLeft => {
+                assert_eq!(panel_rect.right(), screen_rect.right(), "Left is right");
+                assert!(
+                    panel_rect.center().x < label_rect.center().x,
+                    "The contents start on the right"
+                );
+            }
+        }
+    }
+}
@@ -1,10 +1,10 @@
 use crate::containers::menu::{menu_style, MenuConfig, MenuState};
 use crate::style::StyleModifier;
 use crate::{
-    Area, AreaState, Context, Frame, Id, InnerResponse, Key, LayerId, Layout, Order, Response,
-    Sense, Ui, UiKind, UiStackInfo,
+    Area, AreaState, Context, Frame, Id, InnerResponse, Key, LayerId, Layout, Order, Painter,
+    Response, Sense, Shape, Stroke, Ui, UiKind, UiStackInfo,
 };
-use emath::{vec2, Align, Pos2, Rect, RectAlign, Vec2};
+use emath::{pos2, vec2, Align, Pos2, Rect, RectAlign, Vec2};
 use std::iter::once;
 
 /// What should we anchor the popup to?
@@ -175,6 +175,9 @@ pub struct Popup<'a> {
     /// Gap between the anchor and the popup
     gap: f32,
 
+    /// `None` means [`crate::style::Spacing::popup_arrow_size`].
+    arrow_size: Option<f32>,
+
     /// Used later depending on close behavior
     widget_clicked_elsewhere: bool,
 
@@ -201,6 +204,7 @@ impl<'a> Popup<'a> {
             rect_align: RectAlign::BOTTOM_START,
             alternative_aligns: None,
             gap: 0.0,
+            arrow_size: None,
             widget_clicked_elsewhere: false,
             width: None,
             sense: Sense::click(),
@@ -358,6 +362,18 @@ impl<'a> Popup<'a> {
         self
     }
 
+    /// Draw an arrow of this size from the popup to the anchor.
+    ///
+    /// The gap is grown to fit the arrow. Set to `0.0` for no arrow.
+    ///
+    /// Default: [`crate::style::Spacing::popup_arrow_size`],
+    /// or no arrow for popups at the pointer, see [`Self::at_pointer`].
+    #[inline]
+    pub fn arrow_size(mut self, arrow_size: f32) -> Self {
+        self.arrow_size = Some(arrow_size);
+        self
+    }
+
     /// Set the frame of the popup.
     #[inline]
     pub fn frame(mut self, frame: Frame) -> Self {
@@ -421,18 +437,37 @@ impl<'a> Popup<'a> {
         self.anchor.rect(self.id, &self.ctx)
     }
 
+    fn get_arrow_size(&self) -> f32 {
+        self.arrow_size.unwrap_or_else(|| match self.anchor {
+            // The pointer already shows where these belong:
+            PopupAnchor::Pointer | PopupAnchor::PointerFixed => 0.0,
+            PopupAnchor::ParentRect(_) | PopupAnchor::Position(_) => {
+                self.ctx.style().spacing.popup_arrow_size
+            }
+        })
+    }
+
+    /// The gap between the anchor and the popup, including the arrow.
+    fn get_gap(&self) -> f32 {
+        self.gap.max(self.get_arrow_size())
+    }
+
     /// Get the expected rect the popup will be shown in.
     ///
+    /// This is on the side of the anchor picked by [`Self::get_best_align`],
+    /// shifted along it to fit on screen, see [`RectAlign::align_rect_within`].
+    ///
     /// Returns `None` if the popup wasn't shown before or anchor is `PopupAnchor::Pointer` and
     /// there is no pointer.
     pub fn get_popup_rect(&self) -> Option<Rect> {
-        let size = self.get_expected_size();
-        if let Some(size) = size {
-            self.get_anchor_rect()
-                .map(|anchor| self.get_best_align().align_rect(&anchor, size, self.gap))
-        } else {
-            None
-        }
+        let size = self.get_expected_size()?;
+        let anchor = self.get_anchor_rect()?;
+        Some(self.get_best_align().align_rect_within(
+            &anchor,
+            size,
+            self.get_gap(),
+            self.ctx.screen_rect(),
+        ))
     }
 
     /// Get the id of the popup.
@@ -481,7 +516,7 @@ impl<'a> Popup<'a> {
             ),
             self.ctx.screen_rect(),
             anchor_rect,
-            self.gap,
+            self.get_gap(),
             expected_popup_size,
         )
     }
@@ -491,6 +526,9 @@ impl<'a> Popup<'a> {
     /// no pointer.
     pub fn show<R>(self, content: impl FnOnce(&mut Ui) -> R) -> Option<InnerResponse<R>> {
         let best_align = self.get_best_align();
+        let arrow_size = self.get_arrow_size();
+        let gap = self.get_gap();
+        let expected_rect = self.get_popup_rect();
 
         let Popup {
             id,
@@ -503,7 +541,8 @@ impl<'a> Popup<'a> {
             layer_id,
             rect_align: _,
             alternative_aligns: _,
-            gap,
+            gap: _,
+            arrow_size: _,
             widget_clicked_elsewhere,
             width,
             sense,
@@ -552,7 +591,14 @@ impl<'a> Popup<'a> {
 
         let anchor_rect = anchor.rect(id, &ctx)?;
 
-        let (pivot, anchor) = best_align.pivot_pos(&anchor_rect, gap);
+        let (pivot, mut anchor) = best_align.pivot_pos(&anchor_rect, gap);
+        if let Some(expected_rect) = expected_rect {
+            // Shift along the anchor to fit on screen, keeping the pivot so the popup can still change size:
+            anchor += expected_rect.min
+                - best_align
+                    .align_rect(&anchor_rect, expected_rect.size(), gap)
+                    .min;
+        }
 
         let mut area = Area::new(id)
             .order(kind.order())
@@ -580,6 +626,18 @@ impl<'a> Popup<'a> {
             frame.show(ui, content).inner
         });
 
+        if 0.0 < arrow_size {
+            // The area may have been moved to fit on screen, so point from where it ended up:
+            let popup_rect = response.response.rect - frame.outer_margin;
+            paint_arrow(
+                &ctx.layer_painter(response.response.layer_id),
+                popup_rect,
+                anchor_rect,
+                arrow_size,
+                &frame,
+            );
+        }
+
         let closed_by_click = match close_behavior {
             PopupCloseBehavior::CloseOnClick => widget_clicked_elsewhere,
             PopupCloseBehavior::CloseOnClickOutside => {
@@ -616,3 +674,132 @@ impl<'a> Popup<'a> {
         Some(response)
     }
 }
+
+/// The corners of an arrow from the side of `popup_rect` facing `anchor_rect`, pointing at it.
+///
+/// Returns `[base_start, tip, base_end]`, or `None` if the two rectangles overlap.
+fn arrow_points(popup_rect: Rect, anchor_rect: Rect, size: f32, margin: f32) -> Option<[Pos2; 3]> {
+    let target = anchor_rect.center();
+
+    // Slide the arrow along the side to point at the anchor, but not into the rounded corners:
+    let x = target.x.clamp(
+        popup_rect.left() + margin + size,
+        (popup_rect.right() - margin - size).max(popup_rect.left() + margin + size),
+    );
+    let y = target.y.clamp(
+        popup_rect.top() + margin + size,
+        (popup_rect.bottom() - margin - size).max(popup_rect.top() + margin + size),
+    );
+
+    if anchor_rect.max.y <= popup_rect.min.y {
+        let top = popup_rect.top();
+        Some([
+            pos2(x - size, top),
+            pos2(x, top - size),
+            pos2(x + size, top),
+        ])
+    } else if popup_rect.max.y <= anchor_rect.min.y {
+        let bottom = popup_rect.bottom();
+        Some([
+            pos2(x + size, bottom),
+            pos2(x, bottom + size),
+            pos2(x - size, bottom),
+        ])
+    } else if anchor_rect.max.x <= popup_rect.min.x {
+        let left = popup_rect.left();
+        Some([
+            pos2(left, y + size),
+            pos2(left - size, y),
+            pos2(left, y - size),
+        ])
+    } else if popup_rect.max.x <= anchor_rect.min.x {
+        let right = popup_rect.right();
+        Some([
+            pos2(right, y - size),
+            pos2(right + size, y),
+            pos2(right, y + size),
+        ])
+    } else {
+        None
+    }
+}
+
+fn paint_arrow(painter: &Painter, popup_rect: Rect, anchor_rect: Rect, size: f32, frame: &Frame) {
+    let margin = frame.corner_radius.average();
+    let Some([start, tip, end]) = arrow_points(popup_rect, anchor_rect, size, margin) else {
+        return;
+    };
+
+    // Move the base into the frame, so the fill covers the frame stroke:
+    let inwards = (start + (end - start) * 0.5 - tip).normalized() * frame.stroke.width;
+    painter.add(Shape::convex_polygon(
+        vec![start + inwards, tip, end + inwards],
+        frame.fill,
+        Stroke::NONE,
+    ));
+    painter.add(Shape::line(vec![start, tip, end], frame.stroke));
+}
+
+#[test]
+fn test_arrow_points() {
+    let anchor = Rect::from_min_size(pos2(100.0, 100.0), vec2(20.0, 20.0));
+
+    // Below the anchor, pointing up at its center:
+    let popup = Rect::from_min_size(pos2(50.0, 130.0), vec2(200.0, 100.0));
+    let [_, tip, _] = arrow_points(popup, anchor, 8.0, 4.0).unwrap();
+    assert_eq!(tip, pos2(110.0, 122.0));
+
+    // Shifted far to the right, so the arrow stays at the edge of the popup:
+    let popup = Rect::from_min_size(pos2(300.0, 130.0), vec2(200.0, 100.0));
+    let [_, tip, _] = arrow_points(popup, anchor, 8.0, 4.0).unwrap();
+    assert_eq!(tip, pos2(312.0, 122.0));
+
+    // Above:
+    let popup = Rect::from_min_size(pos2(50.0, 0.0), vec2(200.0, 90.0));
+    let [_, tip, _] = arrow_points(popup, anchor, 8.0, 4.0).unwrap();
+    assert_eq!(tip, pos2(110.0, 98.0));
+
+    // To the right:
+    let popup = Rect::from_min_size(pos2(130.0, 50.0), vec2(100.0, 200.0));
+    let [_, tip, _] = arrow_points(popup, anchor, 8.0, 4.0).unwrap();
+    assert_eq!(tip, pos2(122.0, 110.0));
+
+    // Overlapping:
+    let popup = Rect::from_min_size(pos2(110.0, 110.0), vec2(100.0, 100.0));
+    assert!(arrow_points(popup, anchor, 8.0, 4.0).is_none());
+}
+
+#[test]
+fn test_popup_shifts_along_its_anchor() {
+    let ctx = Context::default();
+    let screen = Rect::from_min_size(Pos2::ZERO, vec2(200.0, 200.0));
+    let anchor = Rect::from_min_size(pos2(180.0, 10.0), vec2(10.0, 10.0));
+
+    let mut popup_rect = None;
+    let mut expected_rect = None;
+    for _ in 0..3 {
+        let input = crate::RawInput {
+            screen_rect: Some(screen),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            let popup = Popup::new(Id::new("popup"), ctx.clone(), anchor, LayerId::background())
+                .align_alternatives(&[]);
+            expected_rect = popup.get_popup_rect();
+            popup_rect = popup
+                .show(|ui| {
+                    ui.set_min_width(100.0);
+                    ui.label("Hello");
+                })
+                .map(|response| response.response.rect);
+        });
+    }
+
+    let popup_rect = popup_rect.unwrap();
+    assert_eq!(expected_rect, Some(popup_rect));
+    assert!(screen.contains_rect(popup_rect), "{popup_rect:?}");
+    assert!(
+        anchor.bottom() + ctx.style().spacing.popup_arrow_size <= popup_rect.top(),
+        "Still below the anchor, with room for the arrow: {popup_rect:?}"
+    );
+}
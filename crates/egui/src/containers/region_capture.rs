@@ -0,0 +1,564 @@
+//! A full-screen overlay for selecting a region of the screen and capturing it.
+//!
+//! See [`RegionCapture`].
+
+use std::sync::Arc;
+
+use emath::{vec2, Align2, Pos2, Rect, Vec2};
+use epaint::{ColorImage, CornerRadius, Stroke, StrokeKind, TextureHandle};
+
+use crate::{
+    Area, Color32, Context, CursorIcon, Event, Id, Key, Order, PointerButton, Sense, TextStyle,
+    TextureOptions, UserData, ViewportCommand,
+};
+
+/// A full-screen overlay where the user selects a region of the window, which is then captured.
+///
+/// When first shown it requests a screenshot with [`ViewportCommand::Screenshot`].
+/// Once that arrives the screenshot is shown frozen under a dimmed backdrop,
+/// and the user can drag out a selection, adjust it with its handles, or move it.
+/// A magnifier next to the pointer helps with pixel-exact selections.
+///
+/// Press `Enter` or double-click the selection to capture it, or `Escape` or right-click to cancel.
+///
+/// Keep calling [`Self::show`] every frame until it returns `Some`.
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// # let mut capturing = true;
+/// if capturing {
+///     match egui::RegionCapture::new("capture").show(ctx) {
+///         Some(egui::RegionCaptureResult::Captured(captured)) => {
+///             // Do something with `captured.image`
+///             capturing = false;
+///         }
+///         Some(egui::RegionCaptureResult::Cancelled) => capturing = false,
+///         None => {}
+///     }
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct RegionCapture {
+    id: Id,
+    backdrop_color: Color32,
+    magnifier: bool,
+    magnification: f32,
+}
+
+/// The selected region, returned by [`RegionCapture::show`].
+#[derive(Clone, Debug)]
+pub struct CapturedRegion {
+    /// The selected region, in points.
+    pub rect: Rect,
+
+    /// The pixels of the selected region.
+    pub image: ColorImage,
+}
+
+/// How the [`RegionCapture`] ended.
+#[derive(Clone, Debug)]
+pub enum RegionCaptureResult {
+    Captured(CapturedRegion),
+    Cancelled,
+}
+
+/// Which edges of the selection are being dragged.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Edges {
+    left: bool,
+    right: bool,
+    top: bool,
+    bottom: bool,
+}
+
+impl Edges {
+    fn cursor_icon(self) -> CursorIcon {
+        match (self.left || self.right, self.top || self.bottom) {
+            (true, false) => CursorIcon::ResizeHorizontal,
+            (false, true) => CursorIcon::ResizeVertical,
+            _ if (self.left && self.top) || (self.right && self.bottom) => CursorIcon::ResizeNwSe,
+            _ => CursorIcon::ResizeNeSw,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Drag {
+    /// Dragging out a new selection from this corner.
+    New(Pos2),
+    Move,
+    Resize(Edges),
+}
+
+#[derive(Clone)]
+enum State {
+    /// Waiting for the reply to our [`ViewportCommand::Screenshot`].
+    WaitingForScreenshot(UserData),
+
+    Selecting {
+        image: Arc<ColorImage>,
+        texture: TextureHandle,
+        selection: Option<Rect>,
+        drag: Option<Drag>,
+    },
+}
+
+#[derive(Clone)]
+struct StoredState {
+    state: State,
+
+    /// So we can start over if we weren't shown for a while.
+    last_pass: u64,
+}
+
+impl RegionCapture {
+    pub fn new(id_salt: impl std::hash::Hash) -> Self {
+        Self {
+            id: Id::new(id_salt),
+            backdrop_color: Color32::from_black_alpha(120),
+            magnifier: true,
+            magnification: 8.0,
+        }
+    }
+
+    /// The color dimming everything outside the selection.
+    ///
+    /// Default is `Color32::from_black_alpha(120)`.
+    #[inline]
+    pub fn backdrop_color(mut self, color: Color32) -> Self {
+        self.backdrop_color = color;
+        self
+    }
+
+    /// Show a magnified view of the pixels around the pointer.
+    ///
+    /// Default is `true`.
+    #[inline]
+    pub fn magnifier(mut self, magnifier: bool) -> Self {
+        self.magnifier = magnifier;
+        self
+    }
+
+    /// How much the magnifier enlarges.
+    ///
+    /// Default is `8.0`.
+    #[inline]
+    pub fn magnification(mut self, magnification: f32) -> Self {
+        self.magnification = magnification.max(1.0);
+        self
+    }
+
+    /// Show the overlay, returning `Some` once the user has captured a region or cancelled.
+    pub fn show(self, ctx: &Context) -> Option<RegionCaptureResult> {
+        let id = self.id;
+        let pass_nr = ctx.cumulative_pass_nr();
+        let stored = ctx.data(|d| d.get_temp::<StoredState>(id));
+        let state = match stored {
+            Some(stored) if pass_nr <= stored.last_pass + 1 => stored.state,
+            _ => {
+                let user_data = UserData::new(id);
+                ctx.send_viewport_cmd(ViewportCommand::Screenshot(user_data.clone()));
+                State::WaitingForScreenshot(user_data)
+            }
+        };
+
+        let (state, result) = self.step(ctx, state);
+
+        ctx.data_mut(|d| match state {
+            Some(state) => d.insert_temp(
+                id,
+                StoredState {
+                    state,
+                    last_pass: pass_nr,
+                },
+            ),
+            None => {
+                d.remove::<StoredState>(id);
+            }
+        });
+        result
+    }
+
+    fn step(&self, ctx: &Context, state: State) -> (Option<State>, Option<RegionCaptureResult>) {
+        if ctx.input_mut(|i| i.consume_key(crate::Modifiers::NONE, Key::Escape)) {
+            return (None, Some(RegionCaptureResult::Cancelled));
+        }
+
+        match state {
+            State::WaitingForScreenshot(user_data) => {
+                let image = ctx.input(|i| {
+                    i.raw.events.iter().find_map(|event| match event {
+                        Event::Screenshot {
+                            user_data: reply,
+                            image,
+                            ..
+                        } if *reply == user_data => Some(image.clone()),
+                        _ => None,
+                    })
+                });
+                if let Some(image) = image {
+                    let texture = ctx.load_texture(
+                        format!("{:?}", self.id),
+                        image.as_ref().clone(),
+                        TextureOptions::NEAREST,
+                    );
+                    ctx.request_repaint();
+                    (
+                        Some(State::Selecting {
+                            image,
+                            texture,
+                            selection: None,
+                            drag: None,
+                        }),
+                        None,
+                    )
+                } else {
+                    // Don't paint anything, or it would end up in the screenshot.
+                    (Some(State::WaitingForScreenshot(user_data)), None)
+                }
+            }
+            State::Selecting {
+                image,
+                texture,
+                mut selection,
+                mut drag,
+            } => {
+                let result = self.show_selection(ctx, &image, &texture, &mut selection, &mut drag);
+                if result.is_some() {
+                    (None, result)
+                } else {
+                    let state = State::Selecting {
+                        image,
+                        texture,
+                        selection,
+                        drag,
+                    };
+                    (Some(state), None)
+                }
+            }
+        }
+    }
+
+    fn show_selection(
+        &self,
+        ctx: &Context,
+        image: &ColorImage,
+        texture: &TextureHandle,
+        selection: &mut Option<Rect>,
+        drag: &mut Option<Drag>,
+    ) -> Option<RegionCaptureResult> {
+        let screen_rect = ctx.screen_rect();
+        let pixels_per_point = ctx.pixels_per_point();
+        let handle_radius = 4.0;
+
+        let area = Area::new(self.id)
+            .order(Order::Foreground)
+            .fixed_pos(screen_rect.min)
+            .interactable(true);
+
+        area.show(ctx, |ui| {
+            let response = ui.interact(
+                screen_rect,
+                self.id.with("backdrop"),
+                Sense::click_and_drag(),
+            );
+            let pointer_pos = ui.ctx().pointer_latest_pos();
+
+            // Which part of the selection is under this position?
+            let hit_test = |selection: Option<Rect>, pos: Pos2| -> Option<Drag> {
+                let rect = selection?;
+                let expanded = rect.expand(2.0 * handle_radius);
+                if !expanded.contains(pos) {
+                    return None;
+                }
+                let near = |a: f32, b: f32| (a - b).abs() <= 2.0 * handle_radius;
+                let edges = Edges {
+                    left: near(pos.x, rect.left()),
+                    right: near(pos.x, rect.right()),
+                    top: near(pos.y, rect.top()),
+                    bottom: near(pos.y, rect.bottom()),
+                };
+                if edges == Edges::default() {
+                    rect.contains(pos).then_some(Drag::Move)
+                } else {
+                    Some(Drag::Resize(edges))
+                }
+            };
+
+            if response.drag_started() {
+                let origin = ui.input(|i| i.pointer.press_origin()).or(pointer_pos);
+                *drag = origin.map(|origin| {
+                    hit_test(*selection, origin).unwrap_or(Drag::New(screen_rect.clamp(origin)))
+                });
+            }
+
+            if response.dragged() {
+                if let (Some(drag), Some(pos)) = (*drag, pointer_pos) {
+                    let pos = screen_rect.clamp(pos);
+                    *selection = match (drag, *selection) {
+                        (Drag::New(anchor), _) => Some(Rect::from_two_pos(anchor, pos)),
+                        (Drag::Move, Some(rect)) => {
+                            Some(move_within(rect, response.drag_delta(), screen_rect))
+                        }
+                        (Drag::Resize(edges), Some(mut rect)) => {
+                            if edges.left {
+                                rect.min.x = pos.x;
+                            }
+                            if edges.right {
+                                rect.max.x = pos.x;
+                            }
+                            if edges.top {
+                                rect.min.y = pos.y;
+                            }
+                            if edges.bottom {
+                                rect.max.y = pos.y;
+                            }
+                            Some(Rect::from_two_pos(rect.min, rect.max))
+                        }
+                        (_, None) => None,
+                    };
+                }
+            }
+
+            if response.drag_stopped() {
+                *drag = None;
+            }
+
+            let cursor_icon = match drag.or_else(|| hit_test(*selection, pointer_pos?)) {
+                Some(Drag::Move) => CursorIcon::Move,
+                Some(Drag::Resize(edges)) => edges.cursor_icon(),
+                Some(Drag::New(_)) | None => CursorIcon::Crosshair,
+            };
+            if response.hovered() || response.dragged() {
+                ui.ctx().set_cursor_icon(cursor_icon);
+            }
+
+            // Painting:
+            let painter = ui.painter();
+            let uv = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0));
+            let image_rect = Rect::from_min_size(
+                screen_rect.min,
+                vec2(image.width() as f32, image.height() as f32) / pixels_per_point,
+            );
+            painter.image(texture.id(), image_rect, uv, Color32::WHITE);
+
+            if let Some(rect) = *selection {
+                for dimmed in [
+                    Rect::from_x_y_ranges(screen_rect.x_range(), screen_rect.top()..=rect.top()),
+                    Rect::from_x_y_ranges(
+                        screen_rect.x_range(),
+                        rect.bottom()..=screen_rect.bottom(),
+                    ),
+                    Rect::from_x_y_ranges(screen_rect.left()..=rect.left(), rect.y_range()),
+                    Rect::from_x_y_ranges(rect.right()..=screen_rect.right(), rect.y_range()),
+                ] {
+                    painter.rect_filled(dimmed, 0.0, self.backdrop_color);
+                }
+
+                let stroke = Stroke::new(1.0, ui.visuals().selection.stroke.color);
+                painter.rect_stroke(rect, 0.0, stroke, StrokeKind::Outside);
+                for x in [rect.left(), rect.center().x, rect.right()] {
+                    for y in [rect.top(), rect.center().y, rect.bottom()] {
+                        if x != rect.center().x || y != rect.center().y {
+                            let handle = Rect::from_center_size(
+                                Pos2::new(x, y),
+                                Vec2::splat(2.0 * handle_radius),
+                            );
+                            painter.rect_filled(handle, CornerRadius::ZERO, Color32::WHITE);
+                            painter.rect_stroke(handle, 0.0, stroke, StrokeKind::Inside);
+                        }
+                    }
+                }
+
+                let size_in_pixels = (rect.size() * pixels_per_point).round();
+                painter.text(
+                    rect.left_top() - vec2(0.0, 4.0),
+                    Align2::LEFT_BOTTOM,
+                    format!("{} × {}", size_in_pixels.x, size_in_pixels.y),
+                    TextStyle::Small.resolve(ui.style()),
+                    Color32::WHITE,
+                );
+            } else {
+                painter.rect_filled(screen_rect, 0.0, self.backdrop_color);
+                painter.text(
+                    screen_rect.center_top() + vec2(0.0, 16.0),
+                    Align2::CENTER_TOP,
+                    "Drag to select a region. Enter to capture, Escape to cancel.",
+                    TextStyle::Body.resolve(ui.style()),
+                    Color32::WHITE,
+                );
+            }
+
+            if self.magnifier {
+                if let Some(pos) = pointer_pos.filter(|pos| screen_rect.contains(*pos)) {
+                    paint_magnifier(
+                        ui,
+                        texture,
+                        image_rect,
+                        pos,
+                        self.magnification,
+                        screen_rect,
+                    );
+                }
+            }
+
+            let confirmed = ui.input(|i| i.key_pressed(Key::Enter))
+                || (response.double_clicked()
+                    && selection
+                        .zip(pointer_pos)
+                        .is_some_and(|(rect, pos)| rect.contains(pos)));
+            if response.clicked_by(PointerButton::Secondary) {
+                return Some(RegionCaptureResult::Cancelled);
+            }
+            if confirmed {
+                let rect = selection.map(|rect| rect.intersect(image_rect))?;
+                if rect.width() <= 0.0 || rect.height() <= 0.0 {
+                    return None;
+                }
+                let image = image.region(
+                    &rect.translate(-screen_rect.min.to_vec2()),
+                    Some(pixels_per_point),
+                );
+                return Some(RegionCaptureResult::Captured(CapturedRegion {
+                    rect,
+                    image,
+                }));
+            }
+            None
+        })
+        .inner
+    }
+}
+
+/// Move `rect` by `delta`, but keep it inside of `bounds`.
+fn move_within(rect: Rect, delta: Vec2, bounds: Rect) -> Rect {
+    let rect = rect.translate(delta);
+    let mut offset = Vec2::ZERO;
+    offset.x += (bounds.left() - rect.left()).max(0.0);
+    offset.x -= (rect.right() - bounds.right()).max(0.0);
+    offset.y += (bounds.top() - rect.top()).max(0.0);
+    offset.y -= (rect.bottom() - bounds.bottom()).max(0.0);
+    rect.translate(offset)
+}
+
+fn paint_magnifier(
+    ui: &crate::Ui,
+    texture: &TextureHandle,
+    image_rect: Rect,
+    pos: Pos2,
+    magnification: f32,
+    screen_rect: Rect,
+) {
+    let size = Vec2::splat(120.0);
+    let offset = vec2(24.0, 24.0);
+
+    // Put the magnifier below right of the pointer, unless that's off-screen:
+    let mut min = pos + offset;
+    if screen_rect.right() < min.x + size.x {
+        min.x = pos.x - offset.x - size.x;
+    }
+    if screen_rect.bottom() < min.y + size.y {
+        min.y = pos.y - offset.y - size.y;
+    }
+    let rect = Rect::from_min_size(min, size);
+
+    let source = Rect::from_center_size(pos, size / magnification);
+    let uv = Rect::from_min_max(
+        ((source.min - image_rect.min) / image_rect.size()).to_pos2(),
+        ((source.max - image_rect.min) / image_rect.size()).to_pos2(),
+    );
+
+    let painter = ui.painter();
+    painter.rect_filled(rect, 0.0, Color32::BLACK);
+    painter.image(texture.id(), rect, uv, Color32::WHITE);
+
+    let crosshair = Stroke::new(1.0, Color32::from_white_alpha(160));
+    painter.hline(rect.x_range(), rect.center().y, crosshair);
+    painter.vline(rect.center().x, rect.y_range(), crosshair);
+    painter.rect_stroke(rect, 0.0, ui.visuals().window_stroke, StrokeKind::Outside);
+
+    let pixel = ((pos - image_rect.min) * ui.ctx().pixels_per_point()).floor();
+    painter.text(
+        rect.center_bottom() + vec2(0.0, 2.0),
+        Align2::CENTER_TOP,
+        format!("{}, {}", pixel.x, pixel.y),
+        TextStyle::Small.resolve(ui.style()),
+        Color32::WHITE,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RawInput, ViewportId};
+
+    #[test]
+    fn test_region_capture() {
+        let ctx = Context::default();
+        let screen_rect = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+        let run = |events: Vec<Event>| {
+            let mut result = None;
+            let output = ctx.run(
+                RawInput {
+                    screen_rect: Some(screen_rect),
+                    events,
+                    ..Default::default()
+                },
+                |ctx| result = RegionCapture::new("capture").show(ctx),
+            );
+            (output, result)
+        };
+
+        let (output, result) = run(vec![]);
+        assert!(result.is_none());
+        let user_data = output.viewport_output[&ViewportId::ROOT]
+            .commands
+            .iter()
+            .find_map(|command| match command {
+                ViewportCommand::Screenshot(user_data) => Some(user_data.clone()),
+                _ => None,
+            })
+            .expect("The overlay should ask for a screenshot");
+
+        let mut image = ColorImage::new([100, 100], Color32::BLACK);
+        image[(20, 30)] = Color32::RED;
+        let screenshot = Event::Screenshot {
+            viewport_id: ViewportId::ROOT,
+            user_data,
+            image: Arc::new(image),
+        };
+        assert!(run(vec![screenshot]).1.is_none());
+        // Let the overlay appear:
+        run(vec![]);
+        run(vec![]);
+
+        let button = |pos: Pos2, pressed: bool| Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        let (from, to) = (Pos2::new(20.0, 30.0), Pos2::new(60.0, 50.0));
+        run(vec![Event::PointerMoved(from), button(from, true)]);
+        run(vec![Event::PointerMoved(Pos2::new(40.0, 40.0))]);
+        run(vec![Event::PointerMoved(to)]);
+        run(vec![button(to, false)]);
+
+        let enter = Event::Key {
+            key: Key::Enter,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: Default::default(),
+        };
+        match run(vec![enter]).1 {
+            Some(RegionCaptureResult::Captured(captured)) => {
+                assert_eq!(captured.rect, Rect::from_two_pos(from, to));
+                assert_eq!(captured.image.size, [40, 20]);
+                assert_eq!(captured.image[(0, 0)], Color32::RED);
+            }
+            other => panic!("Expected a capture, got {other:?}"),
+        }
+    }
+}
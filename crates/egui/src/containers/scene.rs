@@ -3,7 +3,8 @@ use core::f32;
 use emath::{GuiRounding, Pos2};
 
 use crate::{
-    emath::TSTransform, InnerResponse, LayerId, Rangef, Rect, Response, Sense, Ui, UiBuilder, Vec2,
+    emath::TSTransform, InnerResponse, LayerId, Rangef, Rect, Response, Sense, Stroke, Ui,
+    UiBuilder, Vec2,
 };
 
 /// Creates a transformation that fits a given scene rectangle into the available screen size.
@@ -11,7 +12,7 @@ use crate::{
 /// The resulting visual scene bounds can be larger, due to letterboxing.
 ///
 /// Returns the transformation from `scene` to `global` coordinates.
-fn fit_to_rect_in_scene(
+pub(crate) fn fit_to_rect_in_scene(
     rect_in_global: Rect,
     rect_in_scene: Rect,
     zoom_range: Rangef,
@@ -45,6 +46,8 @@ fn fit_to_rect_in_scene(
 pub struct Scene {
     zoom_range: Rangef,
     max_inner_size: Vec2,
+    grid_spacing: Option<f32>,
+    fit_on_double_click: bool,
 }
 
 impl Default for Scene {
@@ -52,6 +55,8 @@ impl Default for Scene {
         Self {
             zoom_range: Rangef::new(f32::EPSILON, 1.0),
             max_inner_size: Vec2::splat(1000.0),
+            grid_spacing: None,
+            fit_on_double_click: false,
         }
     }
 }
@@ -82,6 +87,48 @@ impl Scene {
         self
     }
 
+    /// Paint a grid behind the contents, with lines every `spacing` scene units.
+    ///
+    /// The lines are one physical pixel wide at any zoom level,
+    /// and when zooming out the spacing grows so that the grid doesn't get too dense.
+    /// Every fifth line is emphasized.
+    #[inline]
+    pub fn grid(mut self, spacing: f32) -> Self {
+        self.grid_spacing = Some(spacing);
+        self
+    }
+
+    /// Zoom to fit all the contents when the background is double-clicked.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn fit_on_double_click(mut self, fit_on_double_click: bool) -> Self {
+        self.fit_on_double_click = fit_on_double_click;
+        self
+    }
+
+    /// The transform from scene coordinates to global coordinates of a scene shown in `parent_ui`.
+    ///
+    /// This is the transform of the last time the scene was shown.
+    pub fn to_global(parent_ui: &Ui) -> TSTransform {
+        parent_ui
+            .ctx()
+            .layer_transform_to_global(Self::layer_id(parent_ui))
+            .unwrap_or_default()
+    }
+
+    /// Convert a position in global coordinates (e.g. the pointer position) to scene coordinates,
+    /// for a scene shown in `parent_ui`.
+    pub fn global_to_scene(parent_ui: &Ui, pos: Pos2) -> Pos2 {
+        Self::to_global(parent_ui).inverse() * pos
+    }
+
+    /// Convert a position in scene coordinates to global coordinates,
+    /// for a scene shown in `parent_ui`.
+    pub fn scene_to_global(parent_ui: &Ui, pos: Pos2) -> Pos2 {
+        Self::to_global(parent_ui) * pos
+    }
+
     /// `scene_rect` contains the view bounds of the inner [`Ui`].
     ///
     /// `scene_rect` will be mutated by any panning/zooming done by the user.
@@ -111,7 +158,9 @@ impl Scene {
             r
         });
 
-        if ret.response.changed() {
+        if self.fit_on_double_click && ret.response.double_clicked() {
+            *scene_rect = inner_rect;
+        } else if ret.response.changed() {
             // Only update if changed, both to avoid numeric drift,
             // and to avoid expanding the scene rect unnecessarily.
             *scene_rect = to_global.inverse() * outer_rect;
@@ -127,6 +176,17 @@ impl Scene {
         ret
     }
 
+    /// The [`LayerId`] the contents of a scene shown in `parent_ui` are painted to.
+    ///
+    /// The shapes on this layer are in scene coordinates,
+    /// which is useful for e.g. a [`crate::Minimap`].
+    pub fn layer_id(parent_ui: &Ui) -> LayerId {
+        LayerId::new(
+            parent_ui.layer_id().order,
+            parent_ui.id().with("scene_area"),
+        )
+    }
+
     fn show_global_transform<R>(
         &self,
         parent_ui: &mut Ui,
@@ -135,10 +195,7 @@ impl Scene {
         add_contents: impl FnOnce(&mut Ui) -> R,
     ) -> InnerResponse<R> {
         // Create a new egui paint layer, where we can draw our contents:
-        let scene_layer_id = LayerId::new(
-            parent_ui.layer_id().order,
-            parent_ui.id().with("scene_area"),
-        );
+        let scene_layer_id = Self::layer_id(parent_ui);
 
         // Put the layer directly on-top of the main layer of the ui:
         parent_ui
@@ -160,6 +217,10 @@ impl Scene {
         // Set a correct global clip rect:
         local_ui.set_clip_rect(to_global.inverse() * outer_rect);
 
+        if let Some(spacing) = self.grid_spacing {
+            paint_grid(&local_ui, *to_global, spacing);
+        }
+
         // Add the actual contents to the area:
         let ret = add_contents(&mut local_ui);
 
@@ -219,3 +280,60 @@ impl Scene {
         }
     }
 }
+
+const GRID_MAJOR_EVERY: i64 = 5;
+
+/// Grow the grid spacing when zoomed out, so that we don't paint a solid wall of lines.
+fn adaptive_grid_spacing(mut spacing: f32, scaling: f32) -> f32 {
+    const MIN_SPACING_IN_POINTS: f32 = 8.0;
+    while spacing * scaling < MIN_SPACING_IN_POINTS {
+        spacing *= GRID_MAJOR_EVERY as f32;
+    }
+    spacing
+}
+
+/// Paint hairline grid lines covering the clip rect of the `ui`, which is in scene coordinates.
+fn paint_grid(ui: &Ui, to_global: TSTransform, spacing: f32) {
+    if spacing.is_nan() || spacing <= 0.0 || !to_global.is_valid() {
+        return;
+    }
+    let spacing = adaptive_grid_spacing(spacing, to_global.scaling);
+
+    let visible = ui.clip_rect();
+    let color = ui.visuals().widgets.noninteractive.bg_stroke.color;
+    let width = 1.0 / (ui.pixels_per_point() * to_global.scaling);
+    let minor = Stroke::new(width, color.gamma_multiply(0.4));
+    let major = Stroke::new(width, color);
+
+    let painter = ui.painter();
+    let first_x = (visible.min.x / spacing).floor() as i64;
+    let last_x = (visible.max.x / spacing).ceil() as i64;
+    for i in first_x..=last_x {
+        let x = i as f32 * spacing;
+        let stroke = if i % GRID_MAJOR_EVERY == 0 {
+            major
+        } else {
+            minor
+        };
+        painter.vline(x, visible.y_range(), stroke);
+    }
+    let first_y = (visible.min.y / spacing).floor() as i64;
+    let last_y = (visible.max.y / spacing).ceil() as i64;
+    for i in first_y..=last_y {
+        let y = i as f32 * spacing;
+        let stroke = if i % GRID_MAJOR_EVERY == 0 {
+            major
+        } else {
+            minor
+        };
+        painter.hline(visible.x_range(), y, stroke);
+    }
+}
+
+#[test]
+fn test_adaptive_grid_spacing() {
+    assert_eq!(adaptive_grid_spacing(10.0, 1.0), 10.0);
+    assert_eq!(adaptive_grid_spacing(10.0, 0.5), 50.0);
+    assert_eq!(adaptive_grid_spacing(10.0, 0.1), 250.0);
+    assert_eq!(adaptive_grid_spacing(10.0, 0.01), 1250.0);
+}
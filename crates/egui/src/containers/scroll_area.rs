@@ -1,15 +1,24 @@
 #![allow(clippy::needless_range_loop)]
 
 use crate::{
-    emath, epaint, lerp, pass_state, pos2, remap, remap_clamp, Context, Id, NumExt, Pos2, Rangef,
-    Rect, Sense, Ui, UiBuilder, UiKind, UiStackInfo, Vec2, Vec2b,
+    emath, epaint, lerp, pass_state, pos2, remap, remap_clamp, vec2, Align, ColorRole, Context, Id,
+    NumExt, Pos2, Rangef, Rect, Sense, Ui, UiBuilder, UiKind, UiStackInfo, Vec2, Vec2b,
 };
 
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 struct ScrollingToTarget {
     animation_time_span: (f64, f64),
+    start_offset: f32,
     target_offset: f32,
+
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_easing"))]
+    easing: fn(f32) -> f32,
+}
+
+#[cfg(feature = "serde")]
+fn default_easing() -> fn(f32) -> f32 {
+    emath::ease_in_ease_out
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -575,7 +584,14 @@ impl ScrollArea {
             inner_size
         };
 
-        let inner_rect = Rect::from_min_size(available_outer.min, inner_size);
+        // In a right-to-left ui the vertical scroll bar is on the left, before the content:
+        let bar_on_left = ui.style().layout_direction.is_right_to_left();
+        let inner_min = if bar_on_left {
+            available_outer.min + vec2(current_bar_use.x, 0.0)
+        } else {
+            available_outer.min
+        };
+        let inner_rect = Rect::from_min_size(inner_min, inner_size);
 
         let mut content_max_size = inner_size;
 
@@ -606,6 +622,8 @@ impl ScrollArea {
                 if scroll_enabled[d] {
                     content_clip_rect.min[d] = inner_rect.min[d] - clip_rect_margin;
                     content_clip_rect.max[d] = inner_rect.max[d] + clip_rect_margin;
+                } else if d == 0 && bar_on_left {
+                    content_clip_rect.min[d] = ui.clip_rect().min[d] + current_bar_use[d];
                 } else {
                     // Nice handling of forced resizing beyond the possible:
                     content_clip_rect.max[d] = ui.clip_rect().max[d] - current_bar_use[d];
@@ -644,23 +662,20 @@ impl ScrollArea {
                 }
             } else {
                 // Apply the cursor velocity to the scroll area when the user releases the drag.
+                let kinetic_scrolling = ui.style().kinetic_scrolling;
                 if content_response_option
                     .as_ref()
                     .is_some_and(|response| response.drag_stopped())
                 {
-                    state.vel =
-                        scroll_enabled.to_vec2() * ui.input(|input| input.pointer.velocity());
+                    let max_speed = kinetic_scrolling.max_speed;
+                    state.vel = (scroll_enabled.to_vec2()
+                        * ui.input(|input| input.pointer.velocity()))
+                    .clamp(Vec2::splat(-max_speed), Vec2::splat(max_speed));
                 }
                 for d in 0..2 {
                     // Kinetic scrolling
-                    let stop_speed = 20.0; // Pixels per second.
-                    let friction_coeff = 1000.0; // Pixels per second squared.
-
-                    let friction = friction_coeff * dt;
-                    if friction > state.vel[d].abs() || state.vel[d].abs() < stop_speed {
-                        state.vel[d] = 0.0;
-                    } else {
-                        state.vel[d] -= friction * state.vel[d].signum();
+                    state.vel[d] = kinetic_scrolling.step(state.vel[d], dt);
+                    if state.vel[d] != 0.0 {
                         // Offset has an inverted coordinate system compared to
                         // the velocity, so we subtract it instead of adding it
                         state.offset[d] -= state.vel[d] * dt;
@@ -682,15 +697,18 @@ impl ScrollArea {
                     state.offset_target[d] = None;
                 } else {
                     // Move towards target
-                    let t = emath::interpolation_factor(
-                        scroll_target.animation_time_span,
-                        ui.input(|i| i.time),
-                        dt,
-                        emath::ease_in_ease_out,
-                    );
+                    let (start_time, end_time) = scroll_target.animation_time_span;
+                    let duration = (end_time - start_time) as f32;
+                    let t = if duration > 0.0 {
+                        (ui.input(|i| i.time) - start_time) as f32 / duration
+                    } else {
+                        1.0
+                    };
                     if t < 1.0 {
-                        state.offset[d] =
-                            emath::lerp(state.offset[d]..=scroll_target.target_offset, t);
+                        state.offset[d] = emath::lerp(
+                            scroll_target.start_offset..=scroll_target.target_offset,
+                            (scroll_target.easing)(t.max(0.0)),
+                        );
                         ctx.request_repaint();
                     } else {
                         // Arrived
@@ -843,6 +861,17 @@ impl Prepared {
             .ctx()
             .pass_state_mut(|state| std::mem::take(&mut state.scroll_delta));
 
+        // A target meant for just this scroll area, e.g. a match of find-in-page:
+        let area_target = content_ui.ctx().pass_state_mut(|state| {
+            state
+                .scroll_areas
+                .push((id, ui.layer_id(), content_ui.clip_rect()));
+            state
+                .scroll_area_target
+                .take_if(|(target_id, _)| *target_id == id)
+                .map(|(_, rect)| rect)
+        });
+
         for d in 0..2 {
             // PassState::scroll_delta is inverted from the way we apply the delta, so we need to negate it.
             let mut delta = -scroll_delta.0[d];
@@ -852,7 +881,16 @@ impl Prepared {
             // is to avoid them leaking to other scroll areas.
             let scroll_target = content_ui
                 .ctx()
-                .pass_state_mut(|state| state.scroll_target[d].take());
+                .pass_state_mut(|state| state.scroll_target[d].take())
+                .or_else(|| {
+                    area_target.map(|rect| {
+                        pass_state::ScrollTarget::new(
+                            Rangef::new(rect.min[d], rect.max[d]),
+                            Some(Align::Center),
+                            ui.style().scroll_animation,
+                        )
+                    })
+                });
 
             if scroll_enabled[d] {
                 if let Some(target) = scroll_target {
@@ -906,9 +944,12 @@ impl Prepared {
                         let now = ui.input(|i| i.time);
                         let animation_duration = (delta.abs() / animation.points_per_second)
                             .clamp(animation.duration.min, animation.duration.max);
+                        let animation_duration = ui.ctx().scale_animation_time(animation_duration);
                         state.offset_target[d] = Some(ScrollingToTarget {
                             animation_time_span: (now, now + animation_duration as f64),
+                            start_offset: state.offset[d],
                             target_offset,
+                            easing: animation.easing,
                         });
                     }
                     ui.ctx().request_repaint();
@@ -941,7 +982,13 @@ impl Prepared {
             Rect::from_min_size(inner_rect.min, inner_size)
         };
 
-        let outer_rect = Rect::from_min_size(inner_rect.min, inner_rect.size() + current_bar_use);
+        let bar_on_left = ui.style().layout_direction.is_right_to_left();
+        let outer_min = if bar_on_left {
+            inner_rect.min - vec2(current_bar_use.x, 0.0)
+        } else {
+            inner_rect.min
+        };
+        let outer_rect = Rect::from_min_size(outer_min, inner_rect.size() + current_bar_use);
 
         let content_is_too_large = Vec2b::new(
             scroll_enabled[0] && inner_rect.width() < content_size.x,
@@ -1028,6 +1075,8 @@ impl Prepared {
                 // When we hover this area, we should show the full bar:
                 let max_bar_rect = if d == 0 {
                     outer_rect.with_min_y(outer_rect.max.y - outer_margin - scroll_style.bar_width)
+                } else if bar_on_left {
+                    outer_rect.with_max_x(outer_rect.min.x + outer_margin + scroll_style.bar_width)
                 } else {
                     outer_rect.with_min_x(outer_rect.max.x - outer_margin - scroll_style.bar_width)
                 };
@@ -1046,8 +1095,17 @@ impl Prepared {
                         is_hovering_bar_area_t,
                     );
 
-                let max_cross = outer_rect.max[1 - d] - outer_margin;
-                let min_cross = max_cross - width;
+                if d == 1 && bar_on_left {
+                    let min_cross = outer_rect.min.x + outer_margin;
+                    Rangef::new(min_cross, min_cross + width)
+                } else {
+                    let max_cross = outer_rect.max[1 - d] - outer_margin;
+                    let min_cross = max_cross - width;
+                    Rangef::new(min_cross, max_cross)
+                }
+            } else if d == 1 && bar_on_left {
+                let min_cross = outer_rect.min.x + outer_margin;
+                let max_cross = inner_rect.min.x - inner_margin;
                 Rangef::new(min_cross, max_cross)
             } else {
                 let min_cross = inner_rect.max[1 - d] + inner_margin;
@@ -1055,7 +1113,14 @@ impl Prepared {
                 Rangef::new(min_cross, max_cross)
             };
 
-            if ui.clip_rect().max[1 - d] < cross.max + outer_margin {
+            if d == 1 && bar_on_left {
+                if cross.min - outer_margin < ui.clip_rect().min.x {
+                    // Move the scrollbar so it is visible, like below.
+                    let width = cross.max - cross.min;
+                    cross.min = ui.clip_rect().min.x + outer_margin;
+                    cross.max = cross.min + width;
+                }
+            } else if ui.clip_rect().max[1 - d] < cross.max + outer_margin {
                 // Move the scrollbar so it is visible. This is needed in some cases.
                 // For instance:
                 // * When we have a vertical-only scroll area in a top level panel,
@@ -1232,7 +1297,7 @@ impl Prepared {
                     outer_scroll_bar_rect,
                     visuals.corner_radius,
                     ui.visuals()
-                        .extreme_bg_color
+                        .color(ColorRole::SurfaceSunken)
                         .gamma_multiply(background_opacity),
                 ));
 
@@ -1276,3 +1341,47 @@ impl Prepared {
         (content_size, state)
     }
 }
+
+#[test]
+fn test_vertical_scroll_bar_on_the_left_when_right_to_left() {
+    use crate::{style::LayoutDirection, style::ScrollStyle, CentralPanel, RawInput};
+
+    for direction in [LayoutDirection::LeftToRight, LayoutDirection::RightToLeft] {
+        let ctx = Context::default();
+        ctx.style_mut(|style| {
+            style.layout_direction = direction;
+            style.spacing.scroll = ScrollStyle::solid(); // So the bar takes up space
+        });
+        let mut rects = None;
+        // The bar is shown once the content is known to be too large, and then animates in:
+        for frame in 0..10 {
+            let input = RawInput {
+                screen_rect: Some(Rect::from_min_size(Pos2::ZERO, Vec2::splat(300.0))),
+                time: Some(frame as f64),
+                ..Default::default()
+            };
+            let _ = ctx.run(input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    let available = ui.available_rect_before_wrap();
+                    let output = ScrollArea::vertical().show(ui, |ui| {
+                        ui.set_min_size(Vec2::new(ui.available_width(), 1000.0));
+                    });
+                    rects = Some((available, output.inner_rect));
+                });
+            });
+        }
+        let (available, inner_rect) = rects.unwrap();
+
+        let bar_space = available.width() - inner_rect.width();
+        assert!(0.0 < bar_space, "Room is made for the bar");
+        match direction {
+            LayoutDirection::LeftToRight => {
+                assert_eq!(inner_rect.left(), available.left());
+            }
+            LayoutDirection::RightToLeft => {
+                assert_eq!(inner_rect.right(), available.right());
+                assert_eq!(inner_rect.left(), available.left() + bar_space);
+            }
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use emath::GuiRounding as _;
+
+use crate::{
+    vec2, Align, Context, CursorIcon, Id, InnerResponse, Layout, NumExt as _, Rect, Sense, Shape,
+    Ui, UiBuilder,
+};
+
+/// Is a [`Splitter`] split into left and right, or top and bottom?
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitterOrientation {
+    /// Side by side, with a vertical handle between them.
+    Horizontal,
+
+    /// Above each other, with a horizontal handle between them.
+    Vertical,
+}
+
+/// The persisted state of a [`Splitter`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SplitterState {
+    /// How much of the space goes to the first pane, from 0 to 1.
+    ///
+    /// With [`Splitter::collapsible`], `0.0` and `1.0` mean that the first or second pane is collapsed.
+    pub ratio: f32,
+}
+
+impl SplitterState {
+    pub fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.data_mut(|d| d.get_persisted(id))
+    }
+
+    pub fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|d| d.insert_persisted(id, self));
+    }
+
+    /// The size of the first pane, given the space of both panes.
+    fn first_size(self, available: f32, min_sizes: [f32; 2], collapsible: bool) -> f32 {
+        if collapsible && self.ratio <= 0.0 {
+            0.0
+        } else if collapsible && 1.0 <= self.ratio {
+            available
+        } else {
+            (self.ratio * available)
+                .at_most(available - min_sizes[1])
+                .at_least(min_sizes[0])
+                .at_most(available)
+        }
+    }
+
+    /// Set the size of the first pane, as when dragging the handle.
+    fn drag_to(&mut self, first_size: f32, available: f32, min_sizes: [f32; 2], collapsible: bool) {
+        if available <= 0.0 {
+            return;
+        }
+        self.ratio = if collapsible && first_size < 0.5 * min_sizes[0] {
+            0.0
+        } else if collapsible && available - first_size < 0.5 * min_sizes[1] {
+            1.0
+        } else {
+            Self {
+                ratio: first_size / available,
+            }
+            .first_size(available, min_sizes, false)
+                / available
+        };
+    }
+}
+
+/// Split the available space of a [`Ui`] in two panes, with a handle between them
+/// that the user can drag to resize them.
+///
+/// Double-click the handle to go back to the default ratio.
+/// The ratio is persisted, and splitters can be nested.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// egui::Splitter::horizontal("editor").min_size(100.0).show(
+///     ui,
+///     |ui| ui.label("Files"),
+///     |ui| {
+///         egui::Splitter::vertical("editor_and_console")
+///             .default_ratio(0.8)
+///             .collapsible(true)
+///             .show(ui, |ui| ui.label("Editor"), |ui| ui.label("Console"));
+///     },
+/// );
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct Splitter {
+    id_salt: Id,
+    orientation: SplitterOrientation,
+    default_ratio: f32,
+    min_sizes: [f32; 2],
+    collapsible: bool,
+}
+
+impl Splitter {
+    pub fn new(id_salt: impl std::hash::Hash, orientation: SplitterOrientation) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            orientation,
+            default_ratio: 0.5,
+            min_sizes: [0.0; 2],
+            collapsible: false,
+        }
+    }
+
+    /// Two panes side by side.
+    pub fn horizontal(id_salt: impl std::hash::Hash) -> Self {
+        Self::new(id_salt, SplitterOrientation::Horizontal)
+    }
+
+    /// Two panes above each other.
+    pub fn vertical(id_salt: impl std::hash::Hash) -> Self {
+        Self::new(id_salt, SplitterOrientation::Vertical)
+    }
+
+    /// How much of the space goes to the first pane, until the user drags the handle.
+    ///
+    /// Default: `0.5`.
+    #[inline]
+    pub fn default_ratio(mut self, default_ratio: f32) -> Self {
+        self.default_ratio = default_ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// The minimum size of both panes, in points.
+    #[inline]
+    pub fn min_size(mut self, min_size: f32) -> Self {
+        self.min_sizes = [min_size; 2];
+        self
+    }
+
+    /// The minimum sizes of the first and second panes, in points.
+    #[inline]
+    pub fn min_sizes(mut self, first: f32, second: f32) -> Self {
+        self.min_sizes = [first, second];
+        self
+    }
+
+    /// Can a pane be collapsed by dragging the handle past half of its minimum size?
+    ///
+    /// A collapsed pane is not shown.
+    /// Drag the handle back out, or double-click it, to show it again.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn collapsible(mut self, collapsible: bool) -> Self {
+        self.collapsible = collapsible;
+        self
+    }
+
+    /// Show the two panes, filling the available space.
+    ///
+    /// A collapsed pane is not shown, and returns `None`.
+    /// The [`InnerResponse::response`] is that of the handle.
+    pub fn show<R1, R2>(
+        self,
+        ui: &mut Ui,
+        add_first: impl FnOnce(&mut Ui) -> R1,
+        add_second: impl FnOnce(&mut Ui) -> R2,
+    ) -> InnerResponse<(Option<R1>, Option<R2>)> {
+        let Self {
+            id_salt,
+            orientation,
+            default_ratio,
+            min_sizes,
+            collapsible,
+        } = self;
+
+        let id = ui.make_persistent_id(id_salt);
+        let handle_id = id.with("handle");
+        let horizontal = orientation == SplitterOrientation::Horizontal;
+        let along = |v: emath::Vec2| if horizontal { v.x } else { v.y };
+
+        let rect = ui.available_rect_before_wrap();
+        let gap = along(ui.spacing().item_spacing);
+        let available = (along(rect.size()) - gap).at_least(0.0);
+        let min_sizes = min_sizes.map(|min_size| min_size.at_most(0.5 * available));
+
+        let mut state = SplitterState::load(ui.ctx(), id).unwrap_or(SplitterState {
+            ratio: default_ratio,
+        });
+
+        // Read the handle interaction before the panes are laid out, to avoid frame latency:
+        if let Some(handle_response) = ui.ctx().read_response(handle_id) {
+            if handle_response.double_clicked() {
+                state.ratio = default_ratio;
+            } else if handle_response.dragged() {
+                if let Some(pointer) = handle_response.interact_pointer_pos() {
+                    let first_size = along(pointer - rect.min) - 0.5 * gap;
+                    state.drag_to(first_size, available, min_sizes, collapsible);
+                }
+            }
+        }
+
+        let first_size = state
+            .first_size(available, min_sizes, collapsible)
+            .round_ui();
+        let (first_rect, handle_rect, second_rect) = if horizontal {
+            let handle_x = rect.left() + first_size;
+            (
+                Rect::from_x_y_ranges(rect.left()..=handle_x, rect.y_range()),
+                Rect::from_x_y_ranges(handle_x..=handle_x + gap, rect.y_range()),
+                Rect::from_x_y_ranges(handle_x + gap..=rect.right(), rect.y_range()),
+            )
+        } else {
+            let handle_y = rect.top() + first_size;
+            (
+                Rect::from_x_y_ranges(rect.x_range(), rect.top()..=handle_y),
+                Rect::from_x_y_ranges(rect.x_range(), handle_y..=handle_y + gap),
+                Rect::from_x_y_ranges(rect.x_range(), handle_y + gap..=rect.bottom()),
+            )
+        };
+
+        let mut add_pane = |index: usize, pane_rect: Rect| {
+            let mut pane_ui = ui.new_child(
+                UiBuilder::new()
+                    .id_salt(id.with(index))
+                    .max_rect(pane_rect)
+                    .layout(Layout::top_down(Align::Min)),
+            );
+            pane_ui.set_clip_rect(pane_rect.intersect(ui.clip_rect()));
+            pane_ui
+        };
+        let first = (0.0 < first_rect.width() && 0.0 < first_rect.height())
+            .then(|| add_first(&mut add_pane(0, first_rect)));
+        let second = (0.0 < second_rect.width() && 0.0 < second_rect.height())
+            .then(|| add_second(&mut add_pane(1, second_rect)));
+
+        // Interact with the handle on top of the panes, so their contents can't eat the drag:
+        let grab_radius = ui.style().interaction.resize_grab_radius_side;
+        let interact_rect = if horizontal {
+            handle_rect.expand2(vec2(grab_radius, 0.0))
+        } else {
+            handle_rect.expand2(vec2(0.0, grab_radius))
+        };
+        let handle_response = ui.interact(interact_rect, handle_id, Sense::click_and_drag());
+
+        if handle_response.hovered() || handle_response.dragged() {
+            ui.ctx().set_cursor_icon(if horizontal {
+                CursorIcon::ResizeColumn
+            } else {
+                CursorIcon::ResizeRow
+            });
+        }
+
+        if ui.is_rect_visible(handle_rect) {
+            let visuals = ui.style().interact(&handle_response);
+            let color = if handle_response.hovered() || handle_response.dragged() {
+                visuals.fg_stroke.color
+            } else {
+                ui.visuals().widgets.noninteractive.bg_stroke.color
+            };
+            let center = handle_rect.center();
+            let line = if horizontal {
+                [center.x, center.x, handle_rect.top(), handle_rect.bottom()]
+            } else {
+                [handle_rect.left(), handle_rect.right(), center.y, center.y]
+            };
+            let [x0, x1, y0, y1] = line;
+            ui.painter().extend(Shape::dotted_line(
+                &[emath::pos2(x0, y0), emath::pos2(x1, y1)],
+                color,
+                4.0,
+                1.0,
+            ));
+        }
+
+        state.store(ui.ctx(), id);
+        ui.advance_cursor_after_rect(rect);
+
+        InnerResponse::new((first, second), handle_response)
+    }
+}
+
+#[test]
+fn test_splitter_state() {
+    let min_sizes = [100.0, 50.0];
+
+    let state = SplitterState { ratio: 0.5 };
+    assert_eq!(state.first_size(400.0, min_sizes, false), 200.0);
+    assert_eq!(state.first_size(120.0, min_sizes, false), 100.0);
+
+    let mut state = SplitterState { ratio: 0.5 };
+    state.drag_to(380.0, 400.0, min_sizes, false);
+    assert_eq!(state.first_size(400.0, min_sizes, false), 350.0);
+    state.drag_to(10.0, 400.0, min_sizes, false);
+    assert_eq!(state.first_size(400.0, min_sizes, false), 100.0);
+
+    // Dragging past half of the minimum size collapses the pane:
+    state.drag_to(60.0, 400.0, min_sizes, true);
+    assert_eq!(state.first_size(400.0, min_sizes, true), 100.0);
+    state.drag_to(40.0, 400.0, min_sizes, true);
+    assert_eq!(state.first_size(400.0, min_sizes, true), 0.0);
+    state.drag_to(390.0, 400.0, min_sizes, true);
+    assert_eq!(state.first_size(400.0, min_sizes, true), 400.0);
+}
@@ -1,7 +1,7 @@
 use crate::pass_state::PerWidgetTooltipState;
 use crate::{
-    AreaState, Context, Id, InnerResponse, LayerId, Layout, Order, Popup, PopupAnchor, PopupKind,
-    Response, Sense,
+    AreaState, Context, Id, InnerResponse, KeyboardShortcut, Label, LayerId, Layout, Order, Popup,
+    PopupAnchor, PopupKind, Response, Sense, Ui, WidgetText,
 };
 use emath::Vec2;
 
@@ -9,6 +9,25 @@ pub struct Tooltip<'a> {
     pub popup: Popup<'a>,
     layer_id: LayerId,
     widget_id: Id,
+
+    /// Only open when hovering this widget, if it is enabled (`true`) or disabled (`false`).
+    hover: Option<(Response, bool)>,
+
+    group: Option<Id>,
+}
+
+/// When was a tooltip last shown, and for what group?
+#[derive(Clone, Copy)]
+struct LastTooltip {
+    time: f64,
+    group: Option<Id>,
+}
+
+/// When was a widget last hovered while showing its tooltip?
+#[derive(Clone, Copy)]
+struct LastTooltipHover {
+    widget_id: Id,
+    time: f64,
 }
 
 impl Tooltip<'_> {
@@ -27,6 +46,8 @@ impl Tooltip<'_> {
                 .sense(Sense::hover()),
             layer_id,
             widget_id,
+            hover: None,
+            group: None,
         }
     }
 
@@ -41,27 +62,50 @@ impl Tooltip<'_> {
             popup,
             layer_id: response.layer_id,
             widget_id: response.id,
+            hover: None,
+            group: None,
         }
     }
 
     /// Show a tooltip when hovering an enabled widget.
     pub fn for_enabled(response: &Response) -> Self {
         let mut tooltip = Self::for_widget(response);
-        tooltip.popup = tooltip
-            .popup
-            .open(response.enabled() && Self::should_show_tooltip(response));
+        tooltip.hover = Some((response.clone(), true));
         tooltip
     }
 
     /// Show a tooltip when hovering a disabled widget.
     pub fn for_disabled(response: &Response) -> Self {
         let mut tooltip = Self::for_widget(response);
-        tooltip.popup = tooltip
-            .popup
-            .open(!response.enabled() && Self::should_show_tooltip(response));
+        tooltip.hover = Some((response.clone(), false));
         tooltip
     }
 
+    /// Create a tooltip with a title, laid out together with an optional body and keyboard shortcut.
+    ///
+    /// Show it with [`Response::on_hover_rich`].
+    pub fn rich(title: impl Into<WidgetText>) -> RichTooltip {
+        RichTooltip {
+            title: title.into(),
+            body: None,
+            shortcut: None,
+            group: None,
+        }
+    }
+
+    /// Put this tooltip in a group, e.g. all the buttons of a toolbar.
+    ///
+    /// Once a tooltip of the group has been shown, the others show up without the
+    /// [`crate::style::Interaction::tooltip_delay`], as long as the pointer moves between them
+    /// within [`crate::style::Interaction::tooltip_group_grace_time`].
+    ///
+    /// This only matters for [`Self::for_enabled`] and [`Self::for_disabled`].
+    #[inline]
+    pub fn group(mut self, group: impl std::hash::Hash) -> Self {
+        self.group = Some(Id::new(group));
+        self
+    }
+
     /// Show the tooltip at the pointer position.
     #[inline]
     pub fn at_pointer(mut self) -> Self {
@@ -98,8 +142,15 @@ impl Tooltip<'_> {
             mut popup,
             layer_id: parent_layer,
             widget_id,
+            hover,
+            group,
         } = self;
 
+        if let Some((response, enabled)) = &hover {
+            let open = response.enabled() == *enabled && Self::should_show(response, group);
+            popup = popup.open(open);
+        }
+
         if !popup.is_open() {
             return None;
         }
@@ -145,7 +196,7 @@ impl Tooltip<'_> {
                 .response
                 .ctx
                 .pass_state_mut(|fs| fs.tooltips.widget_tooltips.insert(widget_id, state));
-            Self::remember_that_tooltip_was_shown(&response.response.ctx);
+            Self::remember_that_tooltip_was_shown(&response.response.ctx, group);
         }
 
         response
@@ -155,21 +206,69 @@ impl Tooltip<'_> {
         Id::new("when_was_a_toolip_last_shown")
     }
 
-    pub fn seconds_since_last_tooltip(ctx: &Context) -> f32 {
-        let when_was_a_toolip_last_shown =
-            ctx.data(|d| d.get_temp::<f64>(Self::when_was_a_toolip_last_shown_id()));
+    fn last_tooltip(ctx: &Context) -> Option<LastTooltip> {
+        ctx.data(|d| d.get_temp::<LastTooltip>(Self::when_was_a_toolip_last_shown_id()))
+    }
 
-        if let Some(when_was_a_toolip_last_shown) = when_was_a_toolip_last_shown {
+    pub fn seconds_since_last_tooltip(ctx: &Context) -> f32 {
+        if let Some(last_tooltip) = Self::last_tooltip(ctx) {
             let now = ctx.input(|i| i.time);
-            (now - when_was_a_toolip_last_shown) as f32
+            (now - last_tooltip.time) as f32
         } else {
             f32::INFINITY
         }
     }
 
-    fn remember_that_tooltip_was_shown(ctx: &Context) {
-        let now = ctx.input(|i| i.time);
-        ctx.data_mut(|data| data.insert_temp::<f64>(Self::when_was_a_toolip_last_shown_id(), now));
+    fn remember_that_tooltip_was_shown(ctx: &Context, group: Option<Id>) {
+        let time = ctx.input(|i| i.time);
+        ctx.data_mut(|data| {
+            data.insert_temp(
+                Self::when_was_a_toolip_last_shown_id(),
+                LastTooltip { time, group },
+            );
+        });
+    }
+
+    fn last_tooltip_hover_id() -> Id {
+        Id::new("last_tooltip_hover")
+    }
+
+    fn remember_tooltip_hover(response: &Response) {
+        let time = response.ctx.input(|i| i.time);
+        let hover = LastTooltipHover {
+            widget_id: response.id,
+            time,
+        };
+        response
+            .ctx
+            .data_mut(|data| data.insert_temp(Self::last_tooltip_hover_id(), hover));
+    }
+
+    /// Keep an open tooltip around for [`crate::style::Interaction::tooltip_hide_delay`]
+    /// after the pointer has left the widget, unless the pointer is now over something else to click.
+    fn should_linger(response: &Response) -> bool {
+        let ctx = &response.ctx;
+        let hide_delay = ctx.style().interaction.tooltip_hide_delay;
+        let Some(hover) =
+            ctx.data(|d| d.get_temp::<LastTooltipHover>(Self::last_tooltip_hover_id()))
+        else {
+            return false;
+        };
+        if hover.widget_id != response.id {
+            return false;
+        }
+        let hovering_something_else =
+            ctx.viewport(|vp| vp.hits.click.is_some_and(|w| w.id != response.id));
+        if hovering_something_else {
+            return false;
+        }
+        let time_left = hide_delay - (ctx.input(|i| i.time) - hover.time) as f32;
+        if 0.0 < time_left {
+            ctx.request_repaint_after_secs(time_left);
+            true
+        } else {
+            false
+        }
     }
 
     /// What is the id of the next tooltip for this widget?
@@ -189,6 +288,10 @@ impl Tooltip<'_> {
 
     /// Should we show a tooltip for this response?
     pub fn should_show_tooltip(response: &Response) -> bool {
+        Self::should_show(response, None)
+    }
+
+    fn should_show(response: &Response, group: Option<Id>) -> bool {
         if response.ctx.memory(|mem| mem.everything_is_visible()) {
             return true;
         }
@@ -206,7 +309,13 @@ impl Tooltip<'_> {
         let style = response.ctx.style();
 
         let tooltip_delay = style.interaction.tooltip_delay;
-        let tooltip_grace_time = style.interaction.tooltip_grace_time;
+        let tooltip_grace_time = if group.is_some()
+            && Self::last_tooltip(&response.ctx).is_some_and(|last| last.group == group)
+        {
+            style.interaction.tooltip_group_grace_time
+        } else {
+            style.interaction.tooltip_grace_time
+        };
 
         let (
             time_since_last_scroll,
@@ -258,6 +367,7 @@ impl Tooltip<'_> {
 
                     if let Some(pos) = pointer_pos {
                         if rect.contains(pos) {
+                            Self::remember_tooltip_hover(response);
                             return true; // hovering interactive tooltip
                         }
                         if pointer_dir != Vec2::ZERO
@@ -286,6 +396,7 @@ impl Tooltip<'_> {
 
             if pointer_pos.is_some_and(|pointer_pos| response.rect.contains(pointer_pos)) {
                 // Handle the case of a big tooltip that covers the widget:
+                Self::remember_tooltip_hover(response);
                 return true;
             }
         }
@@ -307,15 +418,15 @@ impl Tooltip<'_> {
         }
 
         // Fast early-outs:
-        if response.enabled() {
-            if !response.hovered() || !response.ctx.input(|i| i.pointer.has_pointer()) {
-                return false;
-            }
-        } else if !response
-            .ctx
-            .rect_contains_pointer(response.layer_id, response.rect)
-        {
-            return false;
+        let is_hovering = if response.enabled() {
+            response.hovered() && response.ctx.input(|i| i.pointer.has_pointer())
+        } else {
+            response
+                .ctx
+                .rect_contains_pointer(response.layer_id, response.rect)
+        };
+        if !is_hovering {
+            return is_our_tooltip_open && Self::should_linger(response);
         }
 
         // There is a tooltip_delay before showing the first tooltip,
@@ -362,6 +473,7 @@ impl Tooltip<'_> {
 
         // All checks passed: show the tooltip!
 
+        Self::remember_tooltip_hover(response);
         true
     }
 
@@ -374,3 +486,182 @@ impl Tooltip<'_> {
         })
     }
 }
+
+/// A tooltip with a title, an optional body text and an optional keyboard shortcut.
+///
+/// Create it with [`Tooltip::rich`], and show it with [`Response::on_hover_rich`].
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::{Key, KeyboardShortcut, Modifiers, Tooltip};
+///
+/// ui.button("💾").on_hover_rich(
+///     Tooltip::rich("Save")
+///         .body("Write the document to disk.")
+///         .shortcut(KeyboardShortcut::new(Modifiers::COMMAND, Key::S))
+///         .group("toolbar"),
+/// );
+/// # });
+/// ```
+#[must_use = "You should show the tooltip with `Response::on_hover_rich`"]
+pub struct RichTooltip {
+    title: WidgetText,
+    body: Option<WidgetText>,
+    shortcut: Option<KeyboardShortcut>,
+    group: Option<Id>,
+}
+
+impl RichTooltip {
+    /// The text below the title.
+    #[inline]
+    pub fn body(mut self, body: impl Into<WidgetText>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// The keyboard shortcut of the action, shown next to the title.
+    #[inline]
+    pub fn shortcut(mut self, shortcut: KeyboardShortcut) -> Self {
+        self.shortcut = Some(shortcut);
+        self
+    }
+
+    /// See [`Tooltip::group`].
+    #[inline]
+    pub fn group(mut self, group: impl std::hash::Hash) -> Self {
+        self.group = Some(Id::new(group));
+        self
+    }
+
+    /// Show the contents of the tooltip.
+    pub fn ui(self, ui: &mut Ui) {
+        let Self {
+            title,
+            body,
+            shortcut,
+            group: _,
+        } = self;
+
+        // Prevent `Area` auto-sizing from shrinking tooltips with dynamic content.
+        ui.set_max_width(ui.spacing().tooltip_width);
+
+        ui.horizontal(|ui| {
+            ui.add(Label::new(title.strong()));
+            if let Some(shortcut) = shortcut {
+                ui.weak(ui.ctx().format_shortcut(&shortcut));
+            }
+        });
+        if let Some(body) = body {
+            ui.add(Label::new(body));
+        }
+    }
+
+    pub(crate) fn show_on_hover(self, response: &Response) {
+        let mut tooltip = Tooltip::for_enabled(response);
+        tooltip.group = self.group;
+        tooltip.show(|ui| self.ui(ui));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CentralPanel, Context, Event, Pos2, RawInput, Rect, Tooltip};
+
+    struct Toolbar {
+        ctx: Context,
+        group: bool,
+        rects: [Rect; 2],
+        time: f64,
+    }
+
+    impl Toolbar {
+        fn new(group: bool) -> Self {
+            let mut toolbar = Self {
+                ctx: Context::default(),
+                group,
+                rects: [Rect::NOTHING; 2],
+                time: 0.0,
+            };
+            toolbar.run(None);
+            toolbar
+        }
+
+        /// Which tooltips were shown?
+        fn run(&mut self, pointer: Option<Pos2>) -> [bool; 2] {
+            let mut shown = [false; 2];
+            let input = RawInput {
+                time: Some(self.time),
+                events: pointer.map(Event::PointerMoved).into_iter().collect(),
+                ..Default::default()
+            };
+            let group = self.group;
+            let mut rects = self.rects;
+            let _ = self.ctx.run(input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        for (i, shown) in shown.iter_mut().enumerate() {
+                            let response = ui.button(format!("Button {i}"));
+                            rects[i] = response.rect;
+                            let mut tooltip = Tooltip::for_enabled(&response);
+                            if group {
+                                tooltip = tooltip.group("toolbar");
+                            }
+                            tooltip.show(|ui| {
+                                *shown = true;
+                                ui.label("Tooltip");
+                            });
+                        }
+                    });
+                });
+            });
+            self.rects = rects;
+            shown
+        }
+
+        /// Keep the pointer still until `time`, at 50 frames per second.
+        fn wait_until(&mut self, time: f64, pointer: Pos2) -> [bool; 2] {
+            let mut shown = self.run(Some(pointer));
+            while self.time + 0.02 <= time {
+                self.time += 0.02;
+                shown = self.run(Some(pointer));
+            }
+            shown
+        }
+
+        /// Move the pointer to `pointer` in the next frame.
+        fn move_to(&mut self, pointer: Pos2) -> [bool; 2] {
+            self.time += 0.02;
+            self.run(Some(pointer))
+        }
+    }
+
+    #[test]
+    fn tooltip_hide_delay() {
+        let mut toolbar = Toolbar::new(false);
+        toolbar
+            .ctx
+            .style_mut(|style| style.interaction.tooltip_hide_delay = 0.5);
+        let first = toolbar.rects[0].center();
+        assert_eq!(toolbar.wait_until(1.0, first), [true, false]);
+
+        let nowhere = first + crate::vec2(0.0, 100.0);
+        assert_eq!(toolbar.move_to(nowhere), [true, false], "Lingers");
+        assert_eq!(toolbar.wait_until(1.3, nowhere), [true, false], "Lingers");
+        assert_eq!(toolbar.wait_until(2.0, nowhere), [false, false]);
+    }
+
+    #[test]
+    fn tooltip_group() {
+        for group in [false, true] {
+            let mut toolbar = Toolbar::new(group);
+            let [first, second] = toolbar.rects.map(|rect| rect.center());
+            assert_eq!(toolbar.wait_until(1.0, first), [true, false]);
+
+            // Move away, and then to the other button, slower than the normal grace time:
+            let nowhere = first + crate::vec2(0.0, 100.0);
+            toolbar.move_to(nowhere);
+            toolbar.wait_until(1.5, nowhere);
+            assert_eq!(toolbar.move_to(second), [false, group], "group: {group}");
+        }
+    }
+}
@@ -18,6 +18,7 @@ use super::{area, resize, Area, Frame, Resize, ScrollArea};
 /// * default, minimum, maximum and/or fixed size, collapsed/expanded
 /// * if the window has a scroll area (off by default)
 /// * if the window can be collapsed (minimized) to just the title bar (yes, by default)
+/// * if the window can be maximized to fill the screen (no, by default)
 /// * if there should be a close button (none by default)
 ///
 /// ```
@@ -41,6 +42,7 @@ pub struct Window<'open> {
     resize: Resize,
     scroll: ScrollArea,
     collapsible: bool,
+    maximizable: bool,
     default_open: bool,
     with_title_bar: bool,
     fade_out: bool,
@@ -64,6 +66,7 @@ impl<'open> Window<'open> {
                 .default_size([340.0, 420.0]), // Default inner size of a window
             scroll: ScrollArea::neither().auto_shrink(false),
             collapsible: true,
+            maximizable: false,
             default_open: true,
             with_title_bar: true,
             fade_out: true,
@@ -349,6 +352,22 @@ impl<'open> Window<'open> {
         self
     }
 
+    /// Show a button in the title bar that maximizes the window to fill
+    /// the area it is constrained to (usually the whole screen), and restores it again.
+    ///
+    /// Double-clicking the title bar of a maximizable window toggles between
+    /// maximized and restored (instead of collapsing it).
+    ///
+    /// A maximized window can't be moved or resized.
+    /// Whether or not a window is maximized is remembered between sessions.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn maximizable(mut self, maximizable: bool) -> Self {
+        self.maximizable = maximizable;
+        self
+    }
+
     /// Show title bar on top of the window?
     /// If `false`, the window will not be collapsible nor have a close-button.
     #[inline]
@@ -435,11 +454,12 @@ impl Window<'_> {
         let Window {
             title,
             mut open,
-            area,
+            mut area,
             frame,
             resize,
             scroll,
             collapsible,
+            maximizable,
             default_open,
             with_title_bar,
             fade_out,
@@ -451,9 +471,11 @@ impl Window<'_> {
 
         let is_explicitly_closed = matches!(open, Some(false));
         let is_open = !is_explicitly_closed || ctx.memory(|mem| mem.everything_is_visible());
-        let opacity = ctx.animate_bool_with_easing(
+        let opacity = ctx.animate_bool_with_time_and_easing(
             area.id.with("fade-out"),
             is_open,
+            ctx.style()
+                .animation_duration(crate::style::AnimationKind::Fade),
             emath::easing::cubic_out,
         );
         if opacity <= 0.0 {
@@ -466,8 +488,19 @@ impl Window<'_> {
         let mut collapsing =
             CollapsingState::load_with_default_open(ctx, area_id.with("collapsing"), default_open);
 
+        let maximize_id = area_id.with("maximize");
+        let mut maximize_state = if maximizable {
+            MaximizeState::load(ctx, maximize_id).unwrap_or_default()
+        } else {
+            MaximizeState::default()
+        };
+        if maximize_state.maximized {
+            area = area.movable(false);
+        }
+
         let is_collapsed = with_title_bar && !collapsing.is_open();
-        let possible = PossibleInteractions::new(&area, &resize, is_collapsed);
+        let possible =
+            PossibleInteractions::new(&area, &resize, is_collapsed || maximize_state.maximized);
 
         let resize = resize.resizable(false); // We resize it manually
         let mut resize = resize.id(resize_id);
@@ -508,6 +541,22 @@ impl Window<'_> {
             resize.max_size.y = resize.max_size.y.min(max_height);
         }
 
+        let margins = window_frame.total_margin().sum()
+            + vec2(0.0, title_bar_height_with_margin + title_content_spacing);
+
+        let maximize_content_size = if maximizable {
+            animate_maximize(
+                ctx,
+                maximize_id,
+                &mut maximize_state,
+                &mut area,
+                margins,
+                resize_id,
+            )
+        } else {
+            None
+        };
+
         // First check for resize to avoid frame delay:
         let last_frame_outer_rect = area.state().rect();
         let resize_interaction = ctx.with_accessibility_parent(area.id(), || {
@@ -520,19 +569,14 @@ impl Window<'_> {
             )
         });
 
-        {
-            let margins = window_frame.total_margin().sum()
-                + vec2(0.0, title_bar_height_with_margin + title_content_spacing);
-
-            resize_response(
-                resize_interaction,
-                ctx,
-                margins,
-                area_layer_id,
-                &mut area,
-                resize_id,
-            );
-        }
+        resize_response(
+            resize_interaction,
+            ctx,
+            margins,
+            area_layer_id,
+            &mut area,
+            resize_id,
+        );
 
         let mut area_content_ui = area.content_ui(ctx);
         if is_open {
@@ -555,7 +599,7 @@ impl Window<'_> {
                     let title_bar = TitleBar::new(
                         &frame.content_ui,
                         title,
-                        show_close_button,
+                        show_close_button as usize + maximizable as usize,
                         collapsible,
                         window_frame,
                         title_bar_height_with_margin,
@@ -583,6 +627,10 @@ impl Window<'_> {
                 let (content_inner, content_response) = collapsing
                     .show_body_unindented(&mut frame.content_ui, |ui| {
                         resize.show(ui, |ui| {
+                            if let Some(size) = maximize_content_size {
+                                // The window is as large as its contents, so make them fill it:
+                                ui.set_min_size(size);
+                            }
                             if scroll.is_any_scroll_enabled() {
                                 scroll.show(ui, add_contents).inner
                             } else {
@@ -631,13 +679,23 @@ impl Window<'_> {
                         );
                     }
 
+                    let was_maximized = maximize_state.maximized;
                     title_bar.ui(
                         &mut area_content_ui,
                         &content_response,
                         open.as_deref_mut(),
                         &mut collapsing,
                         collapsible,
+                        maximizable.then_some(&mut maximize_state.maximized),
                     );
+                    if maximize_state.maximized && !was_maximized {
+                        maximize_state.restore_rect.get_or_insert(outer_rect);
+                        collapsing.set_open(true);
+                    }
+                }
+
+                if maximizable {
+                    maximize_state.store(ctx, maximize_id);
                 }
 
                 collapsing.store(ctx);
@@ -854,6 +912,67 @@ fn resize_response(
     ctx.memory_mut(|mem| mem.areas_mut().move_to_top(area_layer_id));
 }
 
+/// Whether or not a [`Window`] is maximized, and where to put it when it is restored.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+struct MaximizeState {
+    maximized: bool,
+
+    /// The outer rect of the window before it was maximized.
+    ///
+    /// Cleared once the window has been fully restored.
+    restore_rect: Option<Rect>,
+}
+
+impl MaximizeState {
+    fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.data_mut(|d| d.get_persisted(id))
+    }
+
+    fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|d| d.insert_persisted(id, self));
+    }
+}
+
+/// Move and resize a maximizable window towards its maximized or restored rect.
+///
+/// Returns the size the contents must fill while maximized or animating.
+fn animate_maximize(
+    ctx: &Context,
+    maximize_id: Id,
+    state: &mut MaximizeState,
+    area: &mut area::Prepared,
+    margins: Vec2,
+    resize_id: Id,
+) -> Option<Vec2> {
+    let t = ctx.animate_bool_with_time_and_easing(
+        maximize_id,
+        state.maximized,
+        ctx.style()
+            .animation_duration(crate::style::AnimationKind::Expand),
+        emath::easing::cubic_out,
+    );
+    let restore_rect = state.restore_rect?;
+
+    let (new_rect, animating) = if state.maximized || 0.0 < t {
+        let rect = restore_rect
+            .lerp_towards(&area.constrain_rect(), t)
+            .round_ui();
+        (rect, true)
+    } else {
+        state.restore_rect = None;
+        (restore_rect, false)
+    };
+
+    let content_size = new_rect.size() - margins;
+    area.state_mut().set_left_top_pos(new_rect.left_top());
+    if let Some(mut resize_state) = resize::State::load(ctx, resize_id) {
+        resize_state.requested_size = Some(content_size);
+        resize_state.store(ctx, resize_id);
+    }
+    animating.then_some(content_size)
+}
+
 /// Acts on outer rect (outside the stroke)
 fn move_and_resize_window(ctx: &Context, interaction: &ResizeInteraction) -> Option<Rect> {
     if !interaction.any_dragged() {
@@ -914,6 +1033,7 @@ fn resize_interaction(
                 interact_rect: rect,
                 sense: Sense::drag(),
                 enabled: true,
+                parent_id: Id::NULL,
             },
             true,
         );
@@ -1130,7 +1250,7 @@ impl TitleBar {
     fn new(
         ui: &Ui,
         title: WidgetText,
-        show_close_button: bool,
+        num_right_buttons: usize,
         collapsible: bool,
         window_frame: Frame,
         title_bar_height_with_margin: f32,
@@ -1155,9 +1275,11 @@ impl TitleBar {
             TextStyle::Heading,
         );
 
-        let minimum_width = if collapsible || show_close_button {
-            // If at least one button is shown we make room for both buttons (since title should be centered):
-            2.0 * (left_pad + button_size.x + item_spacing.x) + title_galley.size().x
+        let num_buttons_per_side = (collapsible as usize).max(num_right_buttons);
+        let minimum_width = if num_buttons_per_side > 0 {
+            // Make room for the same number of buttons on both sides (since title should be centered):
+            2.0 * (left_pad + num_buttons_per_side as f32 * (button_size.x + item_spacing.x))
+                + title_galley.size().x
         } else {
             left_pad + title_galley.size().x + left_pad
         };
@@ -1191,6 +1313,8 @@ impl TitleBar {
     ///   title if `collapsible` is `true`
     /// - `collapsible`: if `true`, double click on the title bar will be handled for a change
     ///   of `collapsing` state
+    /// - `maximized`: if `None`, no "Maximize" button will be rendered, otherwise renders and
+    ///   processes the "Maximize"/"Restore" button, and double clicks on the title bar
     fn ui(
         self,
         ui: &mut Ui,
@@ -1198,6 +1322,7 @@ impl TitleBar {
         open: Option<&mut bool>,
         collapsing: &mut CollapsingState,
         collapsible: bool,
+        maximized: Option<&mut bool>,
     ) {
         let window_frame = self.window_frame;
         let title_inner_rect = self.inner_rect;
@@ -1222,6 +1347,7 @@ impl TitleBar {
             });
         }
 
+        let has_close_button = open.is_some();
         if let Some(open) = open {
             // Add close button now that we know our full width:
             if self.close_button_ui(ui).clicked() {
@@ -1229,6 +1355,16 @@ impl TitleBar {
             }
         }
 
+        let mut maximized = maximized;
+        if let Some(maximized) = maximized.as_deref_mut() {
+            if self
+                .maximize_button_ui(ui, has_close_button, *maximized)
+                .clicked()
+            {
+                *maximized = !*maximized;
+            }
+        }
+
         let text_pos =
             emath::align::center_size_in_rect(self.title_galley.size(), title_inner_rect)
                 .left_top();
@@ -1250,12 +1386,19 @@ impl TitleBar {
             let y = title_inner_rect.bottom() + window_frame.stroke.width / 2.0;
 
             // To verify the sanity of this, use a very wide window stroke
-            ui.painter()
-                .hline(title_inner_rect.x_range(), y, window_frame.stroke);
+            ui.painter().hairline(
+                [
+                    pos2(title_inner_rect.left(), y),
+                    pos2(title_inner_rect.right(), y),
+                ],
+                window_frame.stroke,
+            );
         }
 
-        // Don't cover the close- and collapse buttons:
-        let double_click_rect = title_inner_rect.shrink2(vec2(32.0, 0.0));
+        // Don't cover the close-, maximize- and collapse buttons:
+        let num_right_buttons = has_close_button as usize + maximized.is_some() as usize;
+        let button_margin = 32.0_f32.max(num_right_buttons as f32 * title_inner_rect.height());
+        let double_click_rect = title_inner_rect.shrink2(vec2(button_margin, 0.0));
 
         if false {
             ui.ctx().debug_painter().debug_rect(
@@ -1270,9 +1413,12 @@ impl TitleBar {
         if ui
             .interact(double_click_rect, id, Sense::click())
             .double_clicked()
-            && collapsible
         {
-            collapsing.toggle(ui);
+            if let Some(maximized) = maximized {
+                *maximized = !*maximized;
+            } else if collapsible {
+                collapsing.toggle(ui);
+            }
         }
     }
 
@@ -1290,6 +1436,26 @@ impl TitleBar {
         let button_rect = button_rect.round_to_pixels(ui.pixels_per_point());
         close_button(ui, button_rect)
     }
+
+    /// Paints the "Maximize" (or "Restore") button in the title bar,
+    /// to the left of the "Close" button (if any), and processes clicks on it.
+    fn maximize_button_ui(
+        &self,
+        ui: &mut Ui,
+        has_close_button: bool,
+        is_maximized: bool,
+    ) -> Response {
+        let height = self.inner_rect.height();
+        let mut slot_rect =
+            Align2::RIGHT_CENTER.align_size_within_rect(Vec2::splat(height), self.inner_rect);
+        if has_close_button {
+            slot_rect = slot_rect.translate(vec2(-height, 0.0));
+        }
+        let button_size = Vec2::splat(ui.spacing().icon_width);
+        let button_rect = Rect::from_center_size(slot_rect.center(), button_size);
+        let button_rect = button_rect.round_to_pixels(ui.pixels_per_point());
+        maximize_button(ui, button_rect, is_maximized)
+    }
 }
 
 /// Paints the "Close" button of the window and processes clicks on it.
@@ -1305,8 +1471,13 @@ impl TitleBar {
 fn close_button(ui: &mut Ui, rect: Rect) -> Response {
     let close_id = ui.auto_id_with("window_close_button");
     let response = ui.interact(rect, close_id, Sense::click());
-    response
-        .widget_info(|| WidgetInfo::labeled(WidgetType::Button, ui.is_enabled(), "Close window"));
+    response.widget_info(|| {
+        WidgetInfo::labeled(
+            WidgetType::Button,
+            ui.is_enabled(),
+            ui.translate("Close window"),
+        )
+    });
 
     ui.expand_to_include_rect(response.rect);
 
@@ -1319,3 +1490,105 @@ fn close_button(ui: &mut Ui, rect: Rect) -> Response {
         .line_segment([rect.right_top(), rect.left_bottom()], stroke);
     response
 }
+
+/// Paints the "Maximize" button of the window and processes clicks on it.
+///
+/// A maximized window instead shows a "Restore" button: two overlapping squares.
+fn maximize_button(ui: &mut Ui, rect: Rect, is_maximized: bool) -> Response {
+    let maximize_id = ui.auto_id_with("window_maximize_button");
+    let response = ui.interact(rect, maximize_id, Sense::click());
+    let label = if is_maximized {
+        "Restore window"
+    } else {
+        "Maximize window"
+    };
+    response.widget_info(|| {
+        WidgetInfo::labeled(WidgetType::Button, ui.is_enabled(), ui.translate(label))
+    });
+
+    ui.expand_to_include_rect(response.rect);
+
+    let visuals = ui.style().interact(&response);
+    let rect = rect.shrink(2.0).expand(visuals.expansion);
+    let stroke = visuals.fg_stroke;
+    if is_maximized {
+        let offset = (rect.width() / 4.0).round_ui();
+        let front = Rect::from_min_max(
+            pos2(rect.min.x, rect.min.y + offset),
+            pos2(rect.max.x - offset, rect.max.y),
+        );
+        ui.painter()
+            .rect_stroke(front, 0.0, stroke, epaint::StrokeKind::Middle);
+        ui.painter().add(Shape::line(
+            vec![
+                pos2(front.min.x + offset, front.min.y),
+                pos2(front.min.x + offset, rect.min.y),
+                rect.right_top(),
+                pos2(rect.max.x, front.max.y - offset),
+                pos2(front.max.x, front.max.y - offset),
+            ],
+            stroke,
+        ));
+    } else {
+        ui.painter()
+            .rect_stroke(rect, 0.0, stroke, epaint::StrokeKind::Middle);
+    }
+    response
+}
+
+#[test]
+fn test_maximize_and_restore() {
+    use crate::{Event, PointerButton, RawInput};
+
+    let ctx = Context::default();
+    let screen_rect = Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0));
+    let mut time = 0.0;
+    let mut run = |events: Vec<Event>| {
+        time += 1.0; // Long enough for any animation to finish
+        let mut window_rect = Rect::NOTHING;
+        let _ = ctx.run(
+            RawInput {
+                screen_rect: Some(screen_rect),
+                time: Some(time),
+                events,
+                ..Default::default()
+            },
+            |ctx| {
+                let response = Window::new("Window")
+                    .maximizable(true)
+                    .default_pos(pos2(100.0, 100.0))
+                    .show(ctx, |ui| {
+                        ui.label("Contents");
+                    })
+                    .unwrap();
+                window_rect = response.response.rect;
+            },
+        );
+        window_rect
+    };
+    let click = |pos: Pos2| {
+        let button = |pressed| Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        vec![Event::PointerMoved(pos), button(true), button(false)]
+    };
+
+    run(vec![]);
+    let restored = run(vec![]);
+    assert!(restored.width() < screen_rect.width());
+
+    let double_click = |pos: Pos2| [click(pos), click(pos)].concat();
+    let title_of = |rect: Rect| rect.center_top() + vec2(0.0, 10.0);
+
+    run(double_click(title_of(restored)));
+    run(vec![]);
+    let maximized = run(vec![]);
+    assert_eq!(maximized, screen_rect, "Maximized to fill the screen");
+
+    run(double_click(title_of(maximized)));
+    run(vec![]);
+    assert_eq!(run(vec![]), restored, "Restored to where it was");
+}
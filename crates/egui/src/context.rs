@@ -11,8 +11,8 @@ use epaint::{
     tessellator,
     text::{FontInsert, FontPriority, Fonts},
     util::OrderedFloat,
-    vec2, ClippedPrimitive, ClippedShape, Color32, ImageData, ImageDelta, Pos2, Rect, StrokeKind,
-    TessellationOptions, TextureAtlas, TextureId, Vec2,
+    vec2, ClippedPrimitive, ClippedShape, Color32, ImageData, ImageDelta, PathShape, Pos2, Rect,
+    StrokeKind, TessellationOptions, TextureAtlas, TextureId, TextureSnapshot, Vec2,
 };
 
 use crate::{
@@ -32,11 +32,12 @@ use crate::{
     resize, response, scroll_area,
     util::IdTypeMap,
     viewport::ViewportClass,
-    Align2, CursorIcon, DeferredViewportUiCallback, FontDefinitions, Grid, Id, ImmediateViewport,
-    ImmediateViewportRendererCallback, Key, KeyboardShortcut, Label, LayerId, Memory,
-    ModifierNames, NumExt, Order, Painter, RawInput, Response, RichText, ScrollArea, Sense, Style,
-    TextStyle, TextureHandle, TextureOptions, Ui, ViewportBuilder, ViewportCommand, ViewportId,
-    ViewportIdMap, ViewportIdPair, ViewportIdSet, ViewportOutput, Widget, WidgetRect, WidgetText,
+    Align2, CursorIcon, DeferredViewportUiCallback, Event, FontDefinitions, Grid, Id,
+    ImmediateViewport, ImmediateViewportRendererCallback, Key, KeyboardShortcut, Label, LayerId,
+    Memory, ModifierNames, NumExt, Order, Painter, RawInput, Response, RichText, ScrollArea, Sense,
+    Style, TextStyle, TextureHandle, TextureOptions, TextureUploader, Ui, UiBuilder,
+    ViewportBuilder, ViewportCommand, ViewportId, ViewportIdMap, ViewportIdPair, ViewportIdSet,
+    ViewportOutput, Widget, WidgetRect, WidgetText,
 };
 
 #[cfg(feature = "accesskit")]
@@ -97,10 +98,27 @@ impl Default for WrappedTextureManager {
 /// Generic event callback.
 pub type ContextCallback = Arc<dyn Fn(&Context) + Send + Sync>;
 
+/// Called for each input [`Event`], see [`Context::on_event`].
+pub type EventCallback = Arc<dyn Fn(&Context, &Event) + Send + Sync>;
+
+/// Called for the [`Response`] of each interactive widget, see [`Context::on_response`].
+pub type ResponseCallback = Arc<dyn Fn(&Response) + Send + Sync>;
+
+/// Paints into a layer each pass, see [`Context::add_paint_pass`].
+pub type PaintPassCallback = Arc<dyn Fn(&Painter) + Send + Sync>;
+
+#[derive(Clone)]
+pub(crate) struct PaintPass {
+    debug_name: &'static str,
+    layer_id: LayerId,
+    order: i32,
+    callback: PaintPassCallback,
+}
+
 #[derive(Clone)]
-struct NamedContextCallback {
+struct NamedContextCallback<Callback = ContextCallback> {
     debug_name: &'static str,
-    callback: ContextCallback,
+    callback: Callback,
 }
 
 /// Callbacks that users can register
@@ -108,6 +126,11 @@ struct NamedContextCallback {
 struct Plugins {
     pub on_begin_pass: Vec<NamedContextCallback>,
     pub on_end_pass: Vec<NamedContextCallback>,
+    pub on_event: Vec<NamedContextCallback<EventCallback>>,
+    pub on_response: Vec<NamedContextCallback<ResponseCallback>>,
+
+    /// Sorted by [`PaintPass::order`].
+    pub paint_passes: Vec<PaintPass>,
 }
 
 impl Plugins {
@@ -130,6 +153,28 @@ impl Plugins {
     fn on_end_pass(&self, ctx: &Context) {
         Self::call(ctx, "on_end_pass", &self.on_end_pass);
     }
+
+    fn on_event(&self, ctx: &Context, event: &Event) {
+        for NamedContextCallback {
+            debug_name: _name,
+            callback,
+        } in &self.on_event
+        {
+            profiling::scope!("on_event", _name);
+            (callback)(ctx, event);
+        }
+    }
+
+    fn on_response(callbacks: &[NamedContextCallback<ResponseCallback>], response: &Response) {
+        for NamedContextCallback {
+            debug_name: _name,
+            callback,
+        } in callbacks
+        {
+            profiling::scope!("on_response", _name);
+            (callback)(response);
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -285,6 +330,16 @@ pub struct ViewportState {
     // ----------------------
     // Cross-frame statistics:
     pub num_multipass_in_row: usize,
+
+    /// Only used when [`crate::style::DebugOptions::show_paint_diff`] is on.
+    #[cfg(debug_assertions)]
+    pub(crate) paint_diff: crate::paint_diff::PaintDiff,
+
+    /// The futures started with [`Context::promise`].
+    pub(crate) promises: crate::promise::Promises,
+
+    /// The latest replies to [`ViewportCommand::ReadPick`], see [`Context::read_pick`].
+    pub(crate) picks: Vec<(Pos2, u32)>,
 }
 
 /// What called [`Context::request_repaint`] or [`Context::request_discard`]?
@@ -396,6 +451,9 @@ impl ViewportRepaintInfo {
 
 // ----------------------------------------------------------------------------
 
+/// See [`Context::set_translator`].
+type Translator = dyn Fn(&str, &str) -> Option<String> + Send + Sync;
+
 #[derive(Default)]
 struct ContextImpl {
     /// Since we could have multiple viewports across multiple monitors with
@@ -406,6 +464,16 @@ struct ContextImpl {
     fonts: std::collections::BTreeMap<OrderedFloat<f32>, Fonts>,
     font_definitions: FontDefinitions,
 
+    /// In which of [`Self::fonts_pass_nr`] each of [`Self::fonts`] was last used,
+    /// so we can drop those of old zoom levels, see [`epaint::text::FontAtlasOptions::evict_after_passes`].
+    fonts_last_used: std::collections::BTreeMap<OrderedFloat<f32>, u64>,
+
+    /// Counts the passes of all viewports.
+    fonts_pass_nr: u64,
+
+    /// Which of [`Self::fonts`] is currently uploaded to the font texture.
+    font_texture_pixels_per_point: Option<OrderedFloat<f32>>,
+
     memory: Memory,
     animation_manager: AnimationManager,
 
@@ -422,6 +490,21 @@ struct ContextImpl {
     /// Set during the pass, becomes active at the start of the next pass.
     new_zoom_factor: Option<f32>,
 
+    /// Only set while profiling, see [`Context::set_profiling`].
+    frame_profiler: Option<crate::frame_profiler::FrameProfiler>,
+
+    /// See [`Context::start_recording_input`].
+    input_recording: Option<crate::input_recording::InputRecording>,
+
+    /// See [`Context::set_cursor_image`].
+    cursor_images: crate::cursor_images::CursorImages,
+
+    /// Per-viewport overrides of [`Options::zoom_factor`].
+    viewport_zoom_factors: ViewportIdMap<f32>,
+
+    /// Set during the pass, becomes active at the start of the next pass of that viewport.
+    new_viewport_zoom_factors: ViewportIdMap<Option<f32>>,
+
     os: OperatingSystem,
 
     /// How deeply nested are we?
@@ -432,8 +515,14 @@ struct ContextImpl {
 
     paint_stats: PaintStats,
 
+    /// Requested with [`Context::snapshot_ui`], output at the end of the pass.
+    snapshots: Vec<TextureSnapshot>,
+
     request_repaint_callback: Option<Box<dyn Fn(RequestRepaintInfo) + Send + Sync>>,
 
+    /// See [`Context::set_translator`].
+    translator: Option<Arc<Translator>>,
+
     viewport_parents: ViewportIdMap<ViewportId>,
     viewports: ViewportIdMap<ViewportState>,
 
@@ -443,9 +532,23 @@ struct ContextImpl {
     is_accesskit_enabled: bool,
 
     loaders: Arc<Loaders>,
+
+    /// See [`Options::tessellate_in_background`].
+    ///
+    /// Behind a mutex, as the workers can be sent between threads, but not shared.
+    #[cfg(not(target_arch = "wasm32"))]
+    tessellation_workers:
+        epaint::mutex::Mutex<ViewportIdMap<crate::tessellation_worker::TessellationWorker>>,
 }
 
 impl ContextImpl {
+    fn effective_zoom_factor(&self, viewport_id: ViewportId) -> f32 {
+        self.viewport_zoom_factors
+            .get(&viewport_id)
+            .copied()
+            .unwrap_or(self.memory.options.zoom_factor)
+    }
+
     fn begin_pass(&mut self, mut new_raw_input: RawInput) {
         let viewport_id = new_raw_input.viewport_id;
         let parent_id = new_raw_input
@@ -460,28 +563,39 @@ impl ContextImpl {
 
         self.begin_pass_repaint_logic(viewport_id);
 
-        let viewport = self.viewports.entry(viewport_id).or_default();
-
+        let old_zoom_factor = self.effective_zoom_factor(viewport_id);
         if is_outermost_viewport {
             if let Some(new_zoom_factor) = self.new_zoom_factor.take() {
-                let ratio = self.memory.options.zoom_factor / new_zoom_factor;
                 self.memory.options.zoom_factor = new_zoom_factor;
-
-                let input = &viewport.input;
-                // This is a bit hacky, but is required to avoid jitter:
-                let mut rect = input.screen_rect;
-                rect.min = (ratio * rect.min.to_vec2()).to_pos2();
-                rect.max = (ratio * rect.max.to_vec2()).to_pos2();
-                new_raw_input.screen_rect = Some(rect);
-                // We should really scale everything else in the input too,
-                // but the `screen_rect` is the most important part.
             }
         }
+        match self.new_viewport_zoom_factors.remove(&viewport_id) {
+            Some(Some(zoom_factor)) => {
+                self.viewport_zoom_factors.insert(viewport_id, zoom_factor);
+            }
+            Some(None) => {
+                self.viewport_zoom_factors.remove(&viewport_id);
+            }
+            None => {}
+        }
+        let zoom_factor = self.effective_zoom_factor(viewport_id);
+
+        if zoom_factor != old_zoom_factor {
+            let ratio = old_zoom_factor / zoom_factor;
+            let input = &self.viewports.entry(viewport_id).or_default().input;
+            // This is a bit hacky, but is required to avoid jitter:
+            let mut rect = input.screen_rect;
+            rect.min = (ratio * rect.min.to_vec2()).to_pos2();
+            rect.max = (ratio * rect.max.to_vec2()).to_pos2();
+            new_raw_input.screen_rect = Some(rect);
+            // We should really scale everything else in the input too,
+            // but the `screen_rect` is the most important part.
+        }
         let native_pixels_per_point = new_raw_input
             .viewport()
             .native_pixels_per_point
             .unwrap_or(1.0);
-        let pixels_per_point = self.memory.options.zoom_factor * native_pixels_per_point;
+        let pixels_per_point = zoom_factor * native_pixels_per_point;
 
         let all_viewport_ids: ViewportIdSet = self.all_viewport_ids();
 
@@ -496,6 +610,24 @@ impl ContextImpl {
             &self.memory.options,
         );
 
+        let picks: Vec<(Pos2, u32)> = viewport
+            .input
+            .raw
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Pick {
+                    viewport_id: pick_viewport_id,
+                    pos,
+                    pick_id,
+                } if *pick_viewport_id == viewport_id => Some((*pos, *pick_id)),
+                _ => None,
+            })
+            .collect();
+        if !picks.is_empty() {
+            viewport.picks = picks;
+        }
+
         let screen_rect = viewport.input.screen_rect;
 
         viewport.this_pass.begin_pass(screen_rect);
@@ -505,14 +637,20 @@ impl ContextImpl {
             layers.sort_by(|&a, &b| self.memory.areas().compare_order(a, b));
 
             viewport.hits = if let Some(pos) = viewport.input.pointer.interact_pos() {
-                let interact_radius = self.memory.options.style().interaction.interact_radius;
+                let interaction = &self.memory.options.style().interaction;
+                let touch_target_size = if viewport.input.pointer.is_touch() {
+                    interaction.touch_target_size
+                } else {
+                    0.0
+                };
 
                 crate::hit_test::hit_test(
                     &viewport.prev_pass.widgets,
                     &layers,
                     &self.memory.to_global,
                     pos,
-                    interact_radius,
+                    interaction.interact_radius,
+                    touch_target_size,
                 )
             } else {
                 WidgetHits::default()
@@ -597,6 +735,22 @@ impl ContextImpl {
             log::trace!("Adding new fonts");
         }
 
+        let atlas_options = self.memory.options.font_atlas_options;
+
+        {
+            // Drop the fonts of zoom levels that are no longer used:
+            self.fonts_pass_nr += 1;
+            let pass_nr = self.fonts_pass_nr;
+            self.fonts_last_used
+                .insert(pixels_per_point.into(), pass_nr);
+            self.fonts_last_used.retain(|_, last_used| {
+                pass_nr - *last_used <= atlas_options.evict_after_passes as u64
+            });
+            let fonts_last_used = &self.fonts_last_used;
+            self.fonts
+                .retain(|pixels_per_point, _| fonts_last_used.contains_key(pixels_per_point));
+        }
+
         let mut is_new = false;
 
         let fonts = self
@@ -617,6 +771,7 @@ impl ContextImpl {
 
         {
             profiling::scope!("Fonts::begin_pass");
+            fonts.set_atlas_options(atlas_options);
             fonts.begin_pass(pixels_per_point, max_texture_side);
         }
 
@@ -750,6 +905,18 @@ impl std::cmp::PartialEq for Context {
     }
 }
 
+/// A reference to a [`Context`] that doesn't keep it alive,
+/// for things stored inside the context that need to get back to it.
+#[derive(Clone)]
+pub(crate) struct WeakContext(std::sync::Weak<RwLock<ContextImpl>>);
+
+impl WeakContext {
+    /// The [`Context`], unless it has been dropped.
+    pub fn upgrade(&self) -> Option<Context> {
+        self.0.upgrade().map(Context)
+    }
+}
+
 impl Default for Context {
     fn default() -> Self {
         let ctx_impl = ContextImpl {
@@ -760,8 +927,10 @@ impl Default for Context {
 
         // Register built-in plugins:
         crate::debug_text::register(&ctx);
+        crate::frame_profiler::register(&ctx);
         crate::text_selection::LabelSelectionState::register(&ctx);
         crate::DragAndDrop::register(&ctx);
+        crate::find_in_page::register(&ctx);
 
         ctx
     }
@@ -778,6 +947,11 @@ impl Context {
         writer(&mut self.0.write())
     }
 
+    /// A reference that doesn't keep the context alive, see [`WeakContext`].
+    pub(crate) fn downgrade(&self) -> WeakContext {
+        WeakContext(Arc::downgrade(&self.0))
+    }
+
     /// Run the ui code for one frame.
     ///
     /// At most [`Options::max_passes`] calls will be issued to `run_ui`,
@@ -864,9 +1038,65 @@ impl Context {
             }
         });
 
+        #[cfg(not(target_arch = "wasm32"))]
+        self.tessellate_in_background(viewport_id, &mut output);
+
         output
     }
 
+    /// See [`Options::tessellate_in_background`].
+    #[cfg(not(target_arch = "wasm32"))]
+    fn tessellate_in_background(&self, viewport_id: ViewportId, output: &mut FullOutput) {
+        let enabled = self.options(|options| options.tessellate_in_background);
+        let worker = self.read(|ctx| ctx.tessellation_workers.lock().remove(&viewport_id));
+        if !enabled && worker.is_none() {
+            return;
+        }
+        let mut worker = worker.unwrap_or_default();
+
+        // A frame is only painted once the next one is run, so we can only pipeline while repainting
+        // continuously. The viewports share the textures, so their deltas must be applied in order.
+        let pipeline = enabled
+            && output.viewport_output.len() == 1
+            && output
+                .viewport_output
+                .get(&viewport_id)
+                .is_some_and(|viewport| viewport.repaint_delay.is_zero());
+
+        if !pipeline {
+            if let Some(mut previous) = worker.flush() {
+                // We paint this frame instead, but the texture changes of the previous one still apply:
+                previous
+                    .textures_delta
+                    .append(std::mem::take(&mut output.textures_delta));
+                output.textures_delta = previous.textures_delta;
+            }
+        } else if worker.is_busy() {
+            let shapes = std::mem::take(&mut output.shapes);
+            let textures_delta = std::mem::take(&mut output.textures_delta);
+            if let Some(previous) =
+                worker.submit(self, shapes, textures_delta, output.pixels_per_point)
+            {
+                output.tessellated = Some(previous.primitives);
+                output.textures_delta = previous.textures_delta;
+                output.pixels_per_point = previous.pixels_per_point;
+            }
+        } else {
+            // Nothing to show yet, so this frame is tessellated by the backend as usual,
+            // and once more in the background, to show while the next one is tessellated.
+            worker.submit(
+                self,
+                output.shapes.clone(),
+                Default::default(),
+                output.pixels_per_point,
+            );
+        }
+
+        if enabled {
+            self.read(|ctx| ctx.tessellation_workers.lock().insert(viewport_id, worker));
+        }
+    }
+
     /// An alternative to calling [`Self::run`].
     ///
     /// It is usually better to use [`Self::run`], because
@@ -890,10 +1120,58 @@ impl Context {
     pub fn begin_pass(&self, new_input: RawInput) {
         profiling::function_scope!();
 
-        self.write(|ctx| ctx.begin_pass(new_input));
+        let is_first_pass = self.write(|ctx| {
+            if let Some(profiler) = &mut ctx.frame_profiler {
+                if ctx.viewport_stack.is_empty() {
+                    profiler.begin_frame();
+                }
+                profiler.begin_scope("input".to_owned());
+            }
+            let is_first_pass = ctx
+                .viewports
+                .get(&new_input.viewport_id)
+                .map_or(true, |viewport| viewport.output.num_completed_passes == 0);
+            if let Some(recording) = &mut ctx.input_recording {
+                // Only record the first pass of each frame of the top-level viewports.
+                // Later passes get the same input when replayed.
+                if ctx.viewport_stack.is_empty() && is_first_pass {
+                    recording.push(new_input.clone());
+                }
+            }
+            ctx.begin_pass(new_input);
+            is_first_pass
+        });
+
+        let plugins = self.read(|ctx| ctx.plugins.clone());
+
+        // Each event is only reported once, even if the pass is discarded and run again:
+        if is_first_pass && !plugins.on_event.is_empty() {
+            for event in self.input(|i| i.events.clone()) {
+                plugins.on_event(self, &event);
+            }
+        }
+
+        // Reserve a place at the bottom of each layer for its paint passes:
+        if !plugins.paint_passes.is_empty() {
+            let slots = self.graphics_mut(|graphics| {
+                plugins
+                    .paint_passes
+                    .iter()
+                    .map(|pass| {
+                        let slot = graphics
+                            .entry(pass.layer_id)
+                            .add(Rect::NOTHING, epaint::Shape::Noop);
+                        (pass.clone(), slot)
+                    })
+                    .collect()
+            });
+            self.pass_state_mut(|state| state.paint_pass_slots = slots);
+        }
 
         // Plugins run just after the pass starts:
-        self.read(|ctx| ctx.plugins.clone()).on_begin_pass(self);
+        plugins.on_begin_pass(self);
+
+        self.profile_end();
     }
 
     /// See [`Self::begin_pass`].
@@ -968,6 +1246,59 @@ impl Context {
         self.write(move |ctx| writer(&mut ctx.memory.data))
     }
 
+    /// Copy the persisted state of everything inside `namespace`,
+    /// e.g. which collapsing headers are open and how far scroll areas are scrolled.
+    ///
+    /// `namespace` is the [`Ui::unique_id`] of a [`Ui`] (e.g. one made with [`Ui::push_id`]),
+    /// or the [`Id`] of an [`crate::Area`] or [`crate::Window`].
+    /// Which widgets are inside it is taken from the previous pass, so call this after at least one pass.
+    ///
+    /// Use this to save UI state per document, and restore it with [`Self::import_memory`].
+    pub fn export_memory(&self, namespace: Id, app_version: u32) -> crate::MemorySnapshot {
+        let widgets = self.prev_pass_state(|state| state.widgets.clone());
+        let owners = self.memory(|mem| mem.persistent_id_owners.clone());
+        let parent_of = |id: Id| {
+            widgets
+                .get(id)
+                .map(|w| w.parent_id)
+                .or_else(|| owners.get(&id).copied())
+                .filter(|&parent| parent != Id::NULL)
+        };
+
+        let mut inside: crate::IdMap<bool> = Default::default();
+        inside.insert(namespace, true);
+
+        fn is_inside(
+            id: Id,
+            parent_of: &impl Fn(Id) -> Option<Id>,
+            inside: &mut crate::IdMap<bool>,
+        ) -> bool {
+            if let Some(&known) = inside.get(&id) {
+                return known;
+            }
+            // Mark as outside first, in case of cycles:
+            inside.insert(id, false);
+            let result = parent_of(id).is_some_and(|parent| is_inside(parent, parent_of, inside));
+            inside.insert(id, result);
+            result
+        }
+
+        self.memory(|mem| {
+            mem.export_snapshot(app_version, |id| is_inside(id, &parent_of, &mut inside))
+        })
+    }
+
+    /// Restore state saved with [`Self::export_memory`].
+    ///
+    /// # Errors
+    /// If the snapshot was made by a newer version of egui.
+    pub fn import_memory(
+        &self,
+        snapshot: crate::MemorySnapshot,
+    ) -> Result<(), crate::MemorySnapshotError> {
+        self.memory_mut(|mem| mem.import_snapshot(snapshot))
+    }
+
     /// Read-write access to [`GraphicLayers`], where painted [`crate::Shape`]s are written to.
     #[inline]
     pub fn graphics_mut<R>(&self, writer: impl FnOnce(&mut GraphicLayers) -> R) -> R {
@@ -1078,10 +1409,6 @@ impl Context {
     pub fn check_for_id_clash(&self, id: Id, new_rect: Rect, what: &str) {
         let prev_rect = self.pass_state_mut(move |state| state.used_ids.insert(id, new_rect));
 
-        if !self.options(|opt| opt.warn_on_id_clash) {
-            return;
-        }
-
         let Some(prev_rect) = prev_rect else { return };
 
         // It is ok to reuse the same ID for e.g. a frame around a widget,
@@ -1092,6 +1419,26 @@ impl Context {
             return;
         }
 
+        #[cfg(debug_assertions)]
+        let sources =
+            self.pass_state(|state| state.id_sources.get(&id).cloned().unwrap_or_default());
+        #[cfg(not(debug_assertions))]
+        let sources = vec![];
+
+        let clash = crate::IdClash {
+            id,
+            what: what.to_owned(),
+            first_rect: prev_rect,
+            second_rect: new_rect,
+            sources,
+        };
+        let sources_report = clash.sources_report();
+        self.pass_state_mut(|state| state.id_clashes.push(clash));
+
+        if !self.options(|opt| opt.warn_on_id_clash) {
+            return;
+        }
+
         let show_error = |widget_rect: Rect, text: String| {
             let screen_rect = self.screen_rect();
 
@@ -1139,12 +1486,49 @@ impl Context {
         };
 
         let id_str = id.short_debug_format();
+        let report = sources_report.map_or_else(String::new, |report| format!("\n{report}"));
 
         if prev_rect.min.distance(new_rect.min) < 4.0 {
-            show_error(new_rect, format!("Double use of {what} ID {id_str}"));
+            show_error(
+                new_rect,
+                format!("Double use of {what} ID {id_str}{report}"),
+            );
         } else {
             show_error(prev_rect, format!("First use of {what} ID {id_str}"));
-            show_error(new_rect, format!("Second use of {what} ID {id_str}"));
+            show_error(
+                new_rect,
+                format!("Second use of {what} ID {id_str}{report}"),
+            );
+        }
+    }
+
+    /// Remember where an [`Id`] made by [`Ui::push_id_stable`] came from.
+    #[cfg(debug_assertions)]
+    pub(crate) fn register_id_source(&self, id: Id, source: crate::IdSource) {
+        self.pass_state_mut(|state| state.id_sources.entry(id).or_default().push(source));
+    }
+
+    /// Where the given [`Id`] came from, if it was made with [`Ui::push_id_stable`].
+    ///
+    /// Looks in the current pass first, then in the previous one.
+    /// Always `None` in release builds, where this isn't recorded.
+    pub fn id_source(&self, id: Id) -> Option<crate::IdSource> {
+        #[cfg(debug_assertions)]
+        {
+            self.write(|ctx| {
+                let viewport = ctx.viewport();
+                viewport
+                    .this_pass
+                    .id_sources
+                    .get(&id)
+                    .or_else(|| viewport.prev_pass.id_sources.get(&id))
+                    .and_then(|sources| sources.first().cloned())
+            })
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            let _ = id;
+            None
         }
     }
 
@@ -1200,9 +1584,37 @@ impl Context {
             self.accesskit_node_builder(w.id, |builder| res.fill_accesskit_node_common(builder));
         }
 
+        if allow_focus && (w.sense.interactive() || w.sense.is_focusable()) {
+            // This is called for every widget, so don't clone anything unless there are callbacks:
+            let on_response = self.read(|ctx| {
+                let callbacks = &ctx.plugins.on_response;
+                (!callbacks.is_empty()).then(|| callbacks.clone())
+            });
+            if let Some(callbacks) = on_response {
+                Plugins::on_response(&callbacks, &res);
+            }
+        }
+
         res
     }
 
+    /// Like [`Self::create_widget`], but the widget can only be hit inside `hit_path`.
+    ///
+    /// `hit_path` is in local layer coordinates, like [`WidgetRect::rect`].
+    pub(crate) fn create_widget_with_hit_path(
+        &self,
+        w: WidgetRect,
+        hit_path: PathShape,
+    ) -> Response {
+        self.write(|ctx| {
+            ctx.viewport()
+                .this_pass
+                .widgets
+                .set_hit_path(w.id, hit_path);
+        });
+        self.create_widget(w, true)
+    }
+
     /// Read the response of some widget, which may be called _before_ creating the widget (!).
     ///
     /// This is because widget interaction happens at the start of the pass, using the widget rects from the previous pass.
@@ -1251,6 +1663,7 @@ impl Context {
             interact_rect,
             sense,
             enabled,
+            parent_id: _,
         } = widget_rect;
 
         // previous pass + "highlight next pass" == "highlight this pass"
@@ -1458,6 +1871,29 @@ impl Context {
         self.output_mut(|o| o.cursor_icon = cursor_icon);
     }
 
+    /// Show an image of your own for the given [`CursorIcon`], or `None` to go back to the default.
+    ///
+    /// Use [`CursorIcon::Custom`] to add cursors that the OS doesn't have.
+    ///
+    /// Integrations that support it show this as the OS cursor.
+    /// Others turn on [`Options::paint_custom_cursors`], so egui paints it on top of everything else.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// let pencil = egui::CursorIcon::Custom(egui::Id::new("pencil"));
+    /// let image = egui::ColorImage::new([16, 16], egui::Color32::BLACK);
+    /// ctx.set_cursor_image(pencil, Some(egui::CursorImage::new(image, [0, 15])));
+    /// ctx.set_cursor_icon(pencil);
+    /// ```
+    pub fn set_cursor_image(&self, cursor_icon: CursorIcon, image: Option<crate::CursorImage>) {
+        self.write(|ctx| ctx.cursor_images.set(cursor_icon, image));
+    }
+
+    /// The image set with [`Self::set_cursor_image`], if any.
+    pub fn cursor_image(&self, cursor_icon: CursorIcon) -> Option<crate::CursorImage> {
+        self.read(|ctx| ctx.cursor_images.images.get(&cursor_icon).cloned())
+    }
+
     /// Add a command to [`PlatformOutput::commands`],
     /// for the integration to execute at the end of the frame.
     pub fn send_cmd(&self, cmd: crate::OutputCommand) {
@@ -1494,11 +1930,28 @@ impl Context {
         self.send_cmd(crate::OutputCommand::CopyImage(image));
     }
 
+    /// Copy several representations of the same thing to the system clipboard,
+    /// ordered by preference, e.g. an image of a chart and its data as text.
+    ///
+    /// See [`crate::OutputCommand::Copy`].
+    pub fn copy_data(&self, data: Vec<crate::ClipboardData>) {
+        self.send_cmd(crate::OutputCommand::Copy(data));
+    }
+
     /// Set the mouse cursor position (if the platform supports it).
     pub fn set_pointer_position(&self, position: Pos2) {
         self.send_cmd(crate::OutputCommand::SetPointerPosition(position));
     }
 
+    /// Start an OS-level drag of these items out of the app (if the platform supports it).
+    ///
+    /// Call this when the user starts dragging, e.g. with [`Response::dnd_start_native_drag`].
+    pub fn start_native_drag(&self, items: Vec<crate::NativeDragItem>) {
+        if !items.is_empty() {
+            self.send_cmd(crate::OutputCommand::StartDrag(items));
+        }
+    }
+
     /// Format the given shortcut in a human-readable way (e.g. `Ctrl+Shift+X`).
     ///
     /// Can be used to get the text for [`crate::Button::shortcut_text`].
@@ -1713,6 +2166,49 @@ impl Context {
         self.write(|ctx| ctx.request_repaint_callback = Some(callback));
     }
 
+    /// Translate the built-in strings of egui, like the tooltips of the color picker,
+    /// so that non-English apps don't show a mix of languages.
+    ///
+    /// The translator is called with the [`crate::Style::locale`] and the English string,
+    /// and returns the translation, or `None` to keep the English string.
+    ///
+    /// Values are filled into strings after translation, so strings with values in them
+    /// have placeholders in braces, like `"Expected {placeholder}"`.
+    /// Keep the placeholders in the translation, wherever the language needs them.
+    ///
+    /// Note that only one translator can be set. Any new call overrides the previous translator.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.set_translator(|locale, text| match (locale, text) {
+    ///     ("de", "No matches") => Some("Keine Treffer".to_owned()),
+    ///     _ => None,
+    /// });
+    /// ctx.all_styles_mut(|style| style.locale = "de".to_owned());
+    /// assert_eq!(ctx.translate("de", "No matches"), "Keine Treffer");
+    /// ```
+    pub fn set_translator(
+        &self,
+        translator: impl Fn(&str, &str) -> Option<String> + Send + Sync + 'static,
+    ) {
+        let translator: Arc<Translator> = Arc::new(translator);
+        self.write(|ctx| ctx.translator = Some(translator));
+    }
+
+    /// Translate one of egui's built-in strings to the given locale,
+    /// with the translator set by [`Self::set_translator`].
+    ///
+    /// Returns the string as is if there is no translation.
+    /// See also [`Ui::translate`].
+    pub fn translate<'a>(&self, locale: &str, text: &'a str) -> Cow<'a, str> {
+        // Call the translator without holding the lock, in case it uses the context:
+        let translator = self.read(|ctx| ctx.translator.clone());
+        match translator.and_then(|translator| translator(locale, text)) {
+            Some(translation) => Cow::Owned(translation),
+            None => Cow::Borrowed(text),
+        }
+    }
+
     /// Request to discard the visual output of this pass,
     /// and to immediately do another one.
     ///
@@ -1791,6 +2287,113 @@ impl Context {
         };
         self.write(|ctx| ctx.plugins.on_end_pass.push(named_cb));
     }
+
+    /// Call the given callback for each input [`Event`], at the start of each pass that has events.
+    ///
+    /// This lets you observe all input in one place, e.g. for logging,
+    /// or to recognize custom gestures.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.on_event("log_keys", std::sync::Arc::new(|_ctx, event| {
+    ///     if let egui::Event::Key { key, pressed: true, .. } = event {
+    ///         println!("{key:?} was pressed");
+    ///     }
+    /// }));
+    /// ```
+    pub fn on_event(&self, debug_name: &'static str, cb: EventCallback) {
+        let named_cb = NamedContextCallback {
+            debug_name,
+            callback: cb,
+        };
+        self.write(|ctx| ctx.plugins.on_event.push(named_cb));
+    }
+
+    /// Call the given callback with the [`Response`] of each interactive widget, as it is created.
+    ///
+    /// This lets you observe interactions without wrapping every widget, e.g. for analytics.
+    /// Note that widgets may update their response afterwards, e.g. with [`Response::mark_changed`],
+    /// so prefer checking for clicks, drags and hovers over [`Response::changed`].
+    pub fn on_response(&self, debug_name: &'static str, cb: ResponseCallback) {
+        let named_cb = NamedContextCallback {
+            debug_name,
+            callback: cb,
+        };
+        self.write(|ctx| ctx.plugins.on_response.push(named_cb));
+    }
+
+    /// Paint into the given layer at the end of each pass of each viewport.
+    ///
+    /// What the callback paints ends up _behind_ everything else painted in that layer,
+    /// so it can be used for e.g. a background grid in [`LayerId::background`],
+    /// or for annotations in a layer of their own, e.g. with [`Order::Foreground`].
+    /// As the callback runs at the end of the pass, it can use what happened during it,
+    /// e.g. the widget rects.
+    ///
+    /// Passes in the same layer paint in increasing `order`.
+    /// Adding a pass with the same `debug_name` as an existing one replaces it.
+    /// The pass starts painting from the next pass.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.add_paint_pass("grid", egui::LayerId::background(), 0, std::sync::Arc::new(|painter| {
+    ///     let rect = painter.clip_rect();
+    ///     let stroke = egui::Stroke::new(1.0, egui::Color32::from_gray(40));
+    ///     for i in 0..(rect.width() / 50.0) as i32 {
+    ///         painter.vline(rect.left() + 50.0 * i as f32, rect.y_range(), stroke);
+    ///     }
+    /// }));
+    /// ```
+    pub fn add_paint_pass(
+        &self,
+        debug_name: &'static str,
+        layer_id: LayerId,
+        order: i32,
+        cb: PaintPassCallback,
+    ) {
+        self.write(|ctx| {
+            let passes = &mut ctx.plugins.paint_passes;
+            passes.retain(|pass| pass.debug_name != debug_name);
+            passes.push(PaintPass {
+                debug_name,
+                layer_id,
+                order,
+                callback: cb,
+            });
+            passes.sort_by_key(|pass| pass.order);
+        });
+    }
+
+    /// Remove a pass added with [`Self::add_paint_pass`].
+    pub fn remove_paint_pass(&self, debug_name: &'static str) {
+        self.write(|ctx| {
+            ctx.plugins
+                .paint_passes
+                .retain(|pass| pass.debug_name != debug_name);
+        });
+    }
+
+    /// Paint the passes reserved in [`Self::begin_pass`].
+    fn run_paint_passes(&self) {
+        let slots = self.pass_state_mut(|state| std::mem::take(&mut state.paint_pass_slots));
+
+        // How far each layer has moved its slots so far:
+        let mut offsets: ahash::HashMap<LayerId, usize> = Default::default();
+
+        for (pass, slot) in slots {
+            profiling::scope!("paint_pass", pass.debug_name);
+            let start = self.graphics_mut(|g| g.entry(pass.layer_id).next_idx());
+            let painter = Painter::new(self.clone(), pass.layer_id, self.screen_rect());
+            (pass.callback)(&painter);
+
+            let offset = offsets.entry(pass.layer_id).or_default();
+            let slot = crate::layers::ShapeIdx(slot.0 + *offset);
+            let num_shapes =
+                self.graphics_mut(|g| g.entry(pass.layer_id).move_into_slot(start, slot));
+            // An empty pass keeps its slot, so the offset is unchanged:
+            *offset += num_shapes.saturating_sub(1);
+        }
+    }
 }
 
 impl Context {
@@ -1963,6 +2566,25 @@ impl Context {
         });
     }
 
+    /// Restyle everything using the given [`crate::ThemeTokens`].
+    ///
+    /// The tokens are applied to the dark or light style depending on [`crate::ThemeTokens::dark_mode`].
+    /// Use [`Self::set_theme`] to choose which one is shown.
+    ///
+    /// You can use [`Ui::push_theme`] to use different tokens for a part of the ui.
+    ///
+    /// ```
+    /// # let mut ctx = egui::Context::default();
+    /// let mut tokens = egui::ThemeTokens::dark();
+    /// tokens.colors.accent = egui::Color32::DARK_GREEN;
+    /// ctx.set_theme_tokens(&tokens);
+    /// ```
+    pub fn set_theme_tokens(&self, tokens: &crate::ThemeTokens) {
+        self.style_mut_of(Theme::from_dark_mode(tokens.dark_mode), |style| {
+            tokens.apply(style);
+        });
+    }
+
     /// The [`crate::Visuals`] used by all subsequent windows, panels etc.
     ///
     /// You can also use [`Ui::visuals_mut`] to change the visuals of a single [`Ui`].
@@ -2054,15 +2676,122 @@ impl Context {
         });
     }
 
-    /// Allocate a texture.
+    /// Turn the built-in frame profiler on or off.
     ///
-    /// This is for advanced users.
-    /// Most users should use [`crate::Ui::image`] or [`Self::try_load_texture`]
-    /// instead.
+    /// While on, egui records how long the phases of each frame take
+    /// (input, the layout of each [`crate::Area`], the end of the pass, and tessellation)
+    /// and shows them in a timeline overlay.
     ///
-    /// In order to display an image you must convert it to a texture using this function.
-    /// The function will hand over the image data to the egui backend, which will
-    /// upload it to the GPU.
+    /// See [`crate::frame_profiler`].
+    pub fn set_profiling(&self, profiling: bool) {
+        self.write(|ctx| {
+            if profiling != ctx.frame_profiler.is_some() {
+                ctx.frame_profiler = profiling.then(Default::default);
+            }
+        });
+    }
+
+    /// Is the frame profiler on? See [`Self::set_profiling`].
+    pub fn is_profiling(&self) -> bool {
+        self.read(|ctx| ctx.frame_profiler.is_some())
+    }
+
+    /// The timings of the last completed frame, if profiling is on.
+    ///
+    /// See [`Self::set_profiling`].
+    pub fn frame_profile(&self) -> Option<Arc<crate::frame_profiler::FrameProfile>> {
+        self.read(|ctx| ctx.frame_profiler.as_ref()?.last_frame())
+    }
+
+    /// Start recording the [`RawInput`] of every frame, replacing any ongoing recording.
+    ///
+    /// See [`crate::input_recording`].
+    pub fn start_recording_input(&self) {
+        self.write(|ctx| ctx.input_recording = Some(Default::default()));
+    }
+
+    /// Stop recording, and return what was recorded since [`Self::start_recording_input`].
+    pub fn stop_recording_input(&self) -> Option<crate::input_recording::InputRecording> {
+        self.write(|ctx| ctx.input_recording.take())
+    }
+
+    /// Are we recording input? See [`Self::start_recording_input`].
+    pub fn is_recording_input(&self) -> bool {
+        self.read(|ctx| ctx.input_recording.is_some())
+    }
+
+    /// Time `add_contents` as a scope of the frame profiler, if it is on.
+    ///
+    /// Use this to add your own scopes to the profile, e.g. for painting in an integration.
+    /// See [`Self::set_profiling`].
+    pub fn profile_scope<R>(&self, name: impl Into<String>, add_contents: impl FnOnce() -> R) -> R {
+        self.profile_begin(|| name.into());
+        let result = add_contents();
+        self.profile_end();
+        result
+    }
+
+    /// Start a scope of the frame profiler. Only calls `name` if profiling is on.
+    pub(crate) fn profile_begin(&self, name: impl FnOnce() -> String) {
+        self.write(|ctx| {
+            if let Some(profiler) = &mut ctx.frame_profiler {
+                profiler.begin_scope(name());
+            }
+        });
+    }
+
+    /// End the latest scope started with [`Self::profile_begin`].
+    pub(crate) fn profile_end(&self) {
+        self.write(|ctx| {
+            if let Some(profiler) = &mut ctx.frame_profiler {
+                profiler.end_scope();
+            }
+        });
+    }
+
+    /// The zoom factor override of the given viewport, if any.
+    ///
+    /// See [`Self::set_viewport_zoom_factor`].
+    pub fn viewport_zoom_factor(&self, viewport_id: ViewportId) -> Option<f32> {
+        self.read(|ctx| ctx.viewport_zoom_factors.get(&viewport_id).copied())
+    }
+
+    /// The zoom factor actually used by the given viewport:
+    /// its [`Self::viewport_zoom_factor`] if set, else the global [`Self::zoom_factor`].
+    pub fn effective_zoom_factor(&self, viewport_id: ViewportId) -> f32 {
+        self.read(|ctx| ctx.effective_zoom_factor(viewport_id))
+    }
+
+    /// Override the global [`Self::zoom_factor`] for one viewport.
+    ///
+    /// With `None` the viewport goes back to following the global zoom factor.
+    /// Will become active at the start of the next pass of that viewport,
+    /// when fonts are re-rasterized for the new `pixels_per_point`.
+    ///
+    /// While a viewport has an override, the built-in zoom shortcuts
+    /// (see [`Options::zoom_with_keyboard`]) change the override instead of the global zoom.
+    pub fn set_viewport_zoom_factor(&self, viewport_id: ViewportId, zoom_factor: Option<f32>) {
+        let cause = RepaintCause::new();
+        self.write(|ctx| {
+            if ctx.viewport_zoom_factors.get(&viewport_id).copied() != zoom_factor {
+                ctx.new_viewport_zoom_factors
+                    .insert(viewport_id, zoom_factor);
+                ctx.request_repaint(viewport_id, cause);
+            } else {
+                ctx.new_viewport_zoom_factors.remove(&viewport_id);
+            }
+        });
+    }
+
+    /// Allocate a texture.
+    ///
+    /// This is for advanced users.
+    /// Most users should use [`crate::Ui::image`] or [`Self::try_load_texture`]
+    /// instead.
+    ///
+    /// In order to display an image you must convert it to a texture using this function.
+    /// The function will hand over the image data to the egui backend, which will
+    /// upload it to the GPU.
     ///
     /// ⚠️ Make sure to only call this ONCE for each image, i.e. NOT in your main GUI code.
     /// The call is NOT immediate safe.
@@ -2116,6 +2845,103 @@ impl Context {
         TextureHandle::new(tex_mngr, tex_id)
     }
 
+    /// Allocate a texture of the given size to be filled in later, e.g. by a background thread.
+    ///
+    /// The texture is allocated without uploading anything (see [`ImageData::Empty`]),
+    /// so it starts out transparent on most backends. Fill it in with a [`TextureUploader`]
+    /// from [`Self::texture_uploader`], which spreads the upload out over several frames
+    /// so that loading huge images doesn't stall the UI.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// let texture = ctx.load_texture_streamed("huge", [2048, 2048], Default::default());
+    /// let uploader = ctx.texture_uploader(&texture);
+    /// std::thread::spawn(move || {
+    ///     let image = egui::ColorImage::new([2048, 2048], egui::Color32::RED); // decode here
+    ///     uploader.set_tiled(&image, Default::default(), 512);
+    /// });
+    /// ```
+    pub fn load_texture_streamed(
+        &self,
+        name: impl Into<String>,
+        size: [usize; 2],
+        options: TextureOptions,
+    ) -> TextureHandle {
+        self.load_texture(name, ImageData::Empty(size), options)
+    }
+
+    /// A [`TextureUploader`] for the given texture, which requests a repaint after each update.
+    pub fn texture_uploader(&self, texture: &TextureHandle) -> TextureUploader {
+        let ctx = self.clone();
+        texture
+            .uploader()
+            .on_update(Arc::new(move || ctx.request_repaint()))
+    }
+
+    /// Render some ui into a texture instead of onto the screen, e.g. for thumbnails,
+    /// previews, or a minimap of a large canvas.
+    ///
+    /// The ui is laid out in a rectangle at the origin, the size of the texture divided by
+    /// `pixels_per_point`, so a `pixels_per_point` below [`Self::pixels_per_point`] shrinks it.
+    /// The previous contents of the texture are replaced by the rendering,
+    /// which the backend does before painting this frame.
+    ///
+    /// The ui is only painted, not interacted with, so it can't be clicked or focused.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let texture = ui.ctx().load_texture(
+    ///     "thumbnail",
+    ///     egui::ColorImage::new([200, 150], egui::Color32::TRANSPARENT),
+    ///     egui::TextureOptions::LINEAR,
+    /// );
+    /// ui.ctx().snapshot_ui(&texture, 0.25, |ui| {
+    ///     ui.heading("A big canvas, at a quarter of the size");
+    /// });
+    /// ui.image(&texture);
+    /// # });
+    /// ```
+    pub fn snapshot_ui<R>(
+        &self,
+        texture: &TextureHandle,
+        pixels_per_point: f32,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> R {
+        let texture_id = texture.id();
+        let size = texture.size();
+        let id = Id::new("snapshot").with(texture_id);
+        let layer_id = LayerId::new(Order::Background, id);
+        let rect = Rect::from_min_size(Pos2::ZERO, texture.size_vec2() / pixels_per_point);
+
+        let inner = {
+            let mut ui = Ui::new(
+                self.clone(),
+                id,
+                UiBuilder::new().layer_id(layer_id).max_rect(rect),
+            );
+            add_contents(&mut ui)
+        };
+
+        let shapes = self.graphics_mut(|graphics| graphics.take_layer(layer_id));
+
+        self.write(|ctx| {
+            ctx.viewport().this_pass.widgets.remove_layer(layer_id);
+
+            let fonts_pixels_per_point = ctx.pixels_per_point();
+            let primitives = ctx
+                .tessellator(pixels_per_point, fonts_pixels_per_point)
+                .tessellate_shapes(shapes);
+            ctx.snapshots.push(TextureSnapshot {
+                texture_id,
+                size,
+                pixels_per_point,
+                primitives,
+            });
+        });
+
+        inner
+    }
+
     /// Low-level texture manager.
     ///
     /// In general it is easier to use [`Self::load_texture`] and [`TextureHandle`].
@@ -2150,6 +2976,8 @@ impl Context {
     pub fn end_pass(&self) -> FullOutput {
         profiling::function_scope!();
 
+        self.profile_begin(|| "end_pass".to_owned());
+
         if self.options(|o| o.zoom_with_keyboard) {
             crate::gui_zoom::zoom_with_keyboard(self);
         }
@@ -2157,10 +2985,15 @@ impl Context {
         // Plugins run just before the pass ends.
         self.read(|ctx| ctx.plugins.clone()).on_end_pass(self);
 
+        self.run_paint_passes();
+        self.paint_custom_cursor();
+
         #[cfg(debug_assertions)]
         self.debug_painting();
 
-        self.write(|ctx| ctx.end_pass())
+        let output = self.write(|ctx| ctx.end_pass());
+        self.profile_end();
+        output
     }
 
     /// Call at the end of each frame if you called [`Context::begin_pass`].
@@ -2170,6 +3003,48 @@ impl Context {
         self.end_pass()
     }
 
+    /// Paint the image of the current cursor, if [`Options::paint_custom_cursors`] is on.
+    fn paint_custom_cursor(&self) {
+        if !self.options(|o| o.paint_custom_cursors) {
+            return;
+        }
+        let icon = self.output(|o| o.cursor_icon);
+        let Some(image) = self.cursor_image(icon) else {
+            return;
+        };
+        let Some(pointer_pos) = self.input(|i| i.pointer.hover_pos()) else {
+            return;
+        };
+
+        let texture = self.read(|ctx| ctx.cursor_images.textures.get(&icon).cloned());
+        let texture = texture.unwrap_or_else(|| {
+            let texture = self.load_texture(
+                format!("egui_cursor_{icon:?}"),
+                image.image.clone(),
+                TextureOptions::LINEAR,
+            );
+            self.write(|ctx| ctx.cursor_images.textures.insert(icon, texture.clone()));
+            texture
+        });
+
+        let pixels_per_point = self.pixels_per_point();
+        let hotspot = vec2(image.hotspot[0] as f32, image.hotspot[1] as f32) / pixels_per_point;
+        let rect = Rect::from_min_size(
+            pointer_pos - hotspot,
+            texture.size_vec2() / pixels_per_point,
+        );
+        let layer_id = LayerId::new(Order::Tooltip, Id::new("egui_custom_cursor"));
+        Painter::new(self.clone(), layer_id, Rect::EVERYTHING).image(
+            texture.id(),
+            rect,
+            Rect::from_min_max(emath::pos2(0.0, 0.0), emath::pos2(1.0, 1.0)),
+            Color32::WHITE,
+        );
+
+        // Hide the OS cursor so it isn't painted on top of ours:
+        self.set_cursor_icon(CursorIcon::None);
+    }
+
     /// Called at the end of the pass.
     #[cfg(debug_assertions)]
     fn debug_painting(&self) {
@@ -2298,6 +3173,19 @@ impl Context {
             debug_rect.paint(&self.debug_painter());
         }
 
+        if self.style().debug.show_paint_diff {
+            let mut text = "Repainted because of:".to_owned();
+            for cause in self.repaint_causes() {
+                text += &format!("\n  {cause}");
+            }
+            self.debug_painter().debug_text(
+                self.screen_rect().left_bottom(),
+                Align2::LEFT_BOTTOM,
+                Color32::RED,
+                text,
+            );
+        }
+
         let num_multipass_in_row = self.viewport(|vp| vp.num_multipass_in_row);
         if 3 <= num_multipass_in_row {
             // If you see this message, it means we've been paying the cost of multi-pass for multiple frames in a row.
@@ -2324,36 +3212,45 @@ impl ContextImpl {
 
         viewport.repaint.cumulative_pass_nr += 1;
 
-        self.memory.end_pass(&viewport.this_pass.used_ids);
+        self.memory
+            .end_pass(&viewport.this_pass.used_ids, |layer_id| {
+                viewport
+                    .graphics
+                    .get(layer_id)
+                    .is_some_and(|list| !list.is_empty())
+            });
+        viewport.promises.end_pass();
 
         if let Some(fonts) = self.fonts.get(&pixels_per_point.into()) {
             let tex_mngr = &mut self.tex_manager.0.write();
-            if let Some(font_image_delta) = fonts.font_image_delta() {
-                // A partial font atlas update, e.g. a new glyph has been entered.
-                tex_mngr.set(TextureId::default(), font_image_delta);
-            }
+            let font_image_delta = fonts.font_image_delta();
 
-            if 1 < self.fonts.len() {
-                // We have multiple different `pixels_per_point`,
+            if self.font_texture_pixels_per_point == Some(pixels_per_point.into()) {
+                if let Some(font_image_delta) = font_image_delta {
+                    // A partial font atlas update, e.g. a new glyph has been entered.
+                    tex_mngr.set(TextureId::default(), font_image_delta);
+                }
+            } else {
+                // We have switched to another `pixels_per_point`,
                 // e.g. because we have many viewports spread across
-                // monitors with different DPI scaling.
+                // monitors with different DPI scaling, or because the zoom changed.
                 // All viewports share the same texture namespace and renderer,
                 // so the all use `TextureId::default()` for the font texture.
-                // This is a problem.
-                // We solve this with a hack: we always upload the full font atlas
-                // every frame, for all viewports.
-                // This ensures it is up-to-date, solving
-                // https://github.com/emilk/egui/issues/3664
-                // at the cost of a lot of performance.
-                // (This will override any smaller delta that was uploaded above.)
+                // So we upload the full font atlas whenever we switch,
+                // solving https://github.com/emilk/egui/issues/3664
+                // without paying for it every frame.
                 profiling::scope!("full_font_atlas_update");
                 let full_delta = ImageDelta::full(fonts.image(), TextureAtlas::texture_options());
                 tex_mngr.set(TextureId::default(), full_delta);
+                self.font_texture_pixels_per_point = Some(pixels_per_point.into());
             }
         }
 
         // Inform the backend of all textures that have been updated (including font atlas).
-        let textures_delta = self.tex_manager.0.write().take_delta();
+        let (textures_delta, has_pending_uploads) = {
+            let mut tex_mngr = self.tex_manager.0.write();
+            (tex_mngr.take_delta(), tex_mngr.has_pending_uploads())
+        };
 
         #[cfg_attr(not(feature = "accesskit"), allow(unused_mut))]
         let mut platform_output: PlatformOutput = std::mem::take(&mut viewport.output);
@@ -2383,10 +3280,18 @@ impl ContextImpl {
             }
         }
 
-        let shapes = viewport
+        #[cfg_attr(not(debug_assertions), allow(unused_mut))]
+        let mut shapes = viewport
             .graphics
             .drain(self.memory.areas().order(), &self.memory.to_global);
 
+        #[cfg(debug_assertions)]
+        if self.memory.options.style().debug.show_paint_diff {
+            profiling::scope!("paint_diff");
+            viewport.paint_diff.update(&shapes);
+            shapes.extend(viewport.paint_diff.highlight_shapes());
+        }
+
         let mut repaint_needed = false;
 
         if self.memory.options.repaint_on_widget_change {
@@ -2404,6 +3309,14 @@ impl ContextImpl {
             self.request_repaint_after(delay, ended_viewport_id, RepaintCause::new());
         }
 
+        if has_pending_uploads {
+            // Keep sending streamed textures to the backend:
+            self.request_repaint(
+                ended_viewport_id,
+                RepaintCause::new_reason("texture upload"),
+            );
+        }
+
         //  -------------------
 
         let all_viewport_ids = self.all_viewport_ids();
@@ -2483,6 +3396,10 @@ impl ContextImpl {
             self.viewports.retain(|id, _| all_viewport_ids.contains(id));
             self.viewport_parents
                 .retain(|id, _| all_viewport_ids.contains(id));
+            #[cfg(not(target_arch = "wasm32"))]
+            self.tessellation_workers
+                .lock()
+                .retain(|id, _| all_viewport_ids.contains(id));
         } else {
             let viewport_id = self.viewport_id();
             self.memory.set_viewport_id(viewport_id);
@@ -2511,13 +3428,50 @@ impl ContextImpl {
         FullOutput {
             platform_output,
             textures_delta,
+            snapshots: std::mem::take(&mut self.snapshots),
             shapes,
             pixels_per_point,
+            tessellated: None,
             viewport_output,
         }
     }
 }
 
+impl ContextImpl {
+    /// A tessellator for painting at `pixels_per_point`,
+    /// with the glyphs of the fonts rasterized for `fonts_pixels_per_point`.
+    fn tessellator(
+        &self,
+        pixels_per_point: f32,
+        fonts_pixels_per_point: f32,
+    ) -> tessellator::Tessellator {
+        let tessellation_options = self.memory.options.tessellation_options;
+        let texture_atlas = if let Some(fonts) = self.fonts.get(&fonts_pixels_per_point.into()) {
+            fonts.texture_atlas()
+        } else {
+            #[cfg(feature = "log")]
+            log::warn!("No font size matching {fonts_pixels_per_point} pixels per point found.");
+            self.fonts
+                .iter()
+                .next()
+                .expect("No fonts loaded")
+                .1
+                .texture_atlas()
+        };
+        let (font_tex_size, prepared_discs) = {
+            let atlas = texture_atlas.lock();
+            (atlas.size(), atlas.prepared_discs())
+        };
+
+        tessellator::Tessellator::new(
+            pixels_per_point,
+            tessellation_options,
+            font_tex_size,
+            prepared_discs,
+        )
+    }
+}
+
 impl Context {
     /// Tessellate the given shapes into triangle meshes.
     ///
@@ -2535,39 +3489,29 @@ impl Context {
         // shapes are the same, but just comparing the shapes takes about 50% of the time
         // it takes to tessellate them, so it is not a worth optimization.
 
-        self.write(|ctx| {
-            let tessellation_options = ctx.memory.options.tessellation_options;
-            let texture_atlas = if let Some(fonts) = ctx.fonts.get(&pixels_per_point.into()) {
-                fonts.texture_atlas()
-            } else {
-                #[cfg(feature = "log")]
-                log::warn!("No font size matching {pixels_per_point} pixels per point found.");
-                ctx.fonts
-                    .iter()
-                    .next()
-                    .expect("No fonts loaded")
-                    .1
-                    .texture_atlas()
-            };
-            let (font_tex_size, prepared_discs) = {
-                let atlas = texture_atlas.lock();
-                (atlas.size(), atlas.prepared_discs())
-            };
-
+        self.profile_begin(|| "tessellation".to_owned());
+        let clipped_primitives = self.write(|ctx| {
             let paint_stats = PaintStats::from_shapes(&shapes);
+            let mut tessellator = ctx.tessellator(pixels_per_point, pixels_per_point);
             let clipped_primitives = {
                 profiling::scope!("tessellator::tessellate_shapes");
-                tessellator::Tessellator::new(
-                    pixels_per_point,
-                    tessellation_options,
-                    font_tex_size,
-                    prepared_discs,
-                )
-                .tessellate_shapes(shapes)
+                tessellator.tessellate_shapes(shapes)
             };
             ctx.paint_stats = paint_stats.with_clipped_primitives(&clipped_primitives);
+            ctx.paint_stats.num_mesh_splits = tessellator.num_mesh_splits();
             clipped_primitives
-        })
+        });
+        self.profile_end();
+        clipped_primitives
+    }
+
+    /// The [`tessellator::Tessellator`] that [`Self::tessellate`] would use,
+    /// set up with the current [`TessellationOptions`] and font atlas.
+    ///
+    /// Use this to tessellate somewhere else, e.g. on another thread,
+    /// as done by [`crate::tessellation_worker::TessellationWorker`].
+    pub fn tessellator(&self, pixels_per_point: f32) -> tessellator::Tessellator {
+        self.write(|ctx| ctx.tessellator(pixels_per_point, pixels_per_point))
     }
 
     // ---------------------------------------------------------------------
@@ -2797,6 +3741,19 @@ impl Context {
         self.memory_mut(|mem| mem.areas_mut().set_sublayer(parent, child));
     }
 
+    /// Set the z-index of a layer within its [`Order`].
+    ///
+    /// Layers with a higher z-index are always above those with a lower one in the same [`Order`],
+    /// so you can control the stacking of overlapping layers without changing their [`Order`].
+    /// The default z-index is zero. Takes effect at the end of the frame.
+    ///
+    /// The z-index is forgotten once the layer is neither shown nor painted to for a frame.
+    ///
+    /// See also [`crate::Painter::set_z`].
+    pub fn set_layer_z(&self, layer_id: LayerId, z: i32) {
+        self.memory_mut(|mem| mem.areas_mut().set_z(layer_id, z));
+    }
+
     /// Retrieve the [`LayerId`] of the top level windows.
     pub fn top_layer_id(&self) -> Option<LayerId> {
         self.memory(|mem| mem.areas().top_layer_id(Order::Middle))
@@ -2860,11 +3817,10 @@ impl Context {
     ///
     /// The function will call [`Self::request_repaint()`] when appropriate.
     ///
-    /// The animation time is taken from [`Style::animation_time`].
+    /// The animation time is taken from [`crate::style::AnimationStyle::interaction`].
     #[track_caller] // To track repaint cause
     pub fn animate_bool(&self, id: Id, value: bool) -> f32 {
-        let animation_time = self.style().animation_time;
-        self.animate_bool_with_time_and_easing(id, value, animation_time, emath::easing::linear)
+        self.animate_bool_with_easing(id, value, emath::easing::linear)
     }
 
     /// Like [`Self::animate_bool`], but uses an easing function that makes the value move
@@ -2879,7 +3835,9 @@ impl Context {
     /// Like [`Self::animate_bool`] but allows you to control the easing function.
     #[track_caller] // To track repaint cause
     pub fn animate_bool_with_easing(&self, id: Id, value: bool, easing: fn(f32) -> f32) -> f32 {
-        let animation_time = self.style().animation_time;
+        let animation_time = self
+            .style()
+            .animation_duration(crate::style::AnimationKind::Interaction);
         self.animate_bool_with_time_and_easing(id, value, animation_time, easing)
     }
 
@@ -2901,6 +3859,8 @@ impl Context {
     ///
     /// The easing function flips when `target_value` is `false`,
     /// so that when going back towards 0.0, we get
+    ///
+    /// `animation_time` is scaled with [`Self::scale_animation_time`].
     #[track_caller] // To track repaint cause
     pub fn animate_bool_with_time_and_easing(
         &self,
@@ -2909,6 +3869,7 @@ impl Context {
         animation_time: f32,
         easing: fn(f32) -> f32,
     ) -> f32 {
+        let animation_time = self.scale_animation_time(animation_time);
         let animated_value = self.write(|ctx| {
             ctx.animation_manager.animate_bool(
                 &ctx.viewports.entry(ctx.viewport_id()).or_default().input,
@@ -2933,9 +3894,11 @@ impl Context {
     /// Smoothly animate an `f32` value.
     ///
     /// At the first call the value is written to memory.
-    /// When it is called with a new value, it linearly interpolates to it in the given time.
+    /// When it is called with a new value, it linearly interpolates to it in the given time,
+    /// scaled with [`Self::scale_animation_time`].
     #[track_caller] // To track repaint cause
     pub fn animate_value_with_time(&self, id: Id, target_value: f32, animation_time: f32) -> f32 {
+        let animation_time = self.scale_animation_time(animation_time);
         let animated_value = self.write(|ctx| {
             ctx.animation_manager.animate_value(
                 &ctx.viewports.entry(ctx.viewport_id()).or_default().input,
@@ -2952,6 +3915,34 @@ impl Context {
         animated_value
     }
 
+    /// Should animations be turned off?
+    ///
+    /// True if [`crate::style::AnimationStyle::reduced_motion`] is set,
+    /// or if the OS asks for it (see [`crate::RawInput::prefers_reduced_motion`]).
+    pub fn is_reduced_motion(&self) -> bool {
+        let prefers_reduced_motion = self.input(|i| i.raw.prefers_reduced_motion);
+        self.style()
+            .animation
+            .is_reduced_motion(prefers_reduced_motion)
+    }
+
+    /// How long an animation should actually take, given the [`crate::style::AnimationStyle`].
+    ///
+    /// Zero if [`Self::is_reduced_motion`].
+    pub fn scale_animation_time(&self, seconds: f32) -> f32 {
+        let prefers_reduced_motion = self.input(|i| i.raw.prefers_reduced_motion);
+        self.style()
+            .animation
+            .scale(seconds, prefers_reduced_motion)
+    }
+
+    /// How long an animation of the given kind should take, in seconds.
+    ///
+    /// Use this when animating something yourself, e.g. fading something in.
+    pub fn animation_time(&self, kind: crate::style::AnimationKind) -> f32 {
+        self.scale_animation_time(self.style().animation_duration(kind))
+    }
+
     /// Clear memory of any animations.
     pub fn clear_animations(&self) {
         self.write(|ctx| ctx.animation_manager = Default::default());
@@ -3094,6 +4085,25 @@ impl Context {
             });
     }
 
+    /// Show the widgets of the previous pass as a tree, arranged by parent [`Ui`].
+    ///
+    /// Hover or click a widget in the tree to outline it on screen,
+    /// and see its rect, [`Sense`] and state.
+    /// Any [`Id`] clashes are listed together with where they happened.
+    pub fn widget_inspector_ui(&self, ui: &mut Ui) {
+        ui.scope(|ui| crate::widget_inspector::widget_inspector_ui(self, ui));
+    }
+
+    /// Show the widget inspector in its own [`crate::Window`].
+    ///
+    /// See [`Self::widget_inspector_ui`].
+    pub fn show_widget_inspector(&self, open: &mut bool) {
+        crate::Window::new("🔍 Widget inspector")
+            .open(open)
+            .vscroll(false)
+            .show(self, |ui| self.widget_inspector_ui(ui));
+    }
+
     /// Show stats about the allocated textures.
     pub fn texture_ui(&self, ui: &mut crate::Ui) {
         let tex_mngr = self.tex_manager();
@@ -3647,6 +4657,44 @@ impl Context {
         self.write(|ctx| ctx.viewport_for(id).commands.push(command));
     }
 
+    /// Which [`epaint::Shape::Pick`] group covers `pos` (in points), if any.
+    ///
+    /// This reads back the pick ids the backend rendered,
+    /// so it is exact for any geometry, but always one or more frames late:
+    /// the first call for a position returns `None`,
+    /// and the backend repaints once the answer (or a change of it) is ready.
+    /// Call this every frame you want to know.
+    ///
+    /// Always `None` if the backend doesn't support picking.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let painter = ui.painter();
+    /// for (i, center) in [egui::pos2(20.0, 20.0), egui::pos2(40.0, 20.0)].into_iter().enumerate() {
+    ///     let circle = egui::Shape::circle_filled(center, 8.0, egui::Color32::GRAY);
+    ///     painter.add(egui::Shape::pick(i as u32 + 1, vec![circle]));
+    /// }
+    /// if let Some(pos) = ui.ctx().pointer_hover_pos() {
+    ///     if let Some(pick_id) = ui.ctx().read_pick(pos) {
+    ///         ui.label(format!("Hovering circle {pick_id}"));
+    ///     }
+    /// }
+    /// # });
+    /// ```
+    pub fn read_pick(&self, pos: Pos2) -> Option<u32> {
+        self.write(|ctx| {
+            let viewport = ctx.viewport();
+            // Not `send_viewport_cmd`, since that would repaint forever:
+            viewport.commands.push(ViewportCommand::ReadPick(pos));
+            viewport
+                .picks
+                .iter()
+                .find(|(pick_pos, _)| *pick_pos == pos)
+                .map(|(_, pick_id)| *pick_id)
+                .filter(|pick_id| *pick_id != 0)
+        })
+    }
+
     /// Show a deferred viewport, creating a new native window, if possible.
     ///
     /// The given id must be unique for each viewport.
@@ -3783,6 +4831,54 @@ impl Context {
     }
 }
 
+/// ## Async
+impl Context {
+    /// Run a [`std::future::Future`] as part of the UI, returning its output once it has finished.
+    ///
+    /// The future is started the first time this is called with the given `id`,
+    /// and is polled each pass this is called again, until it finishes.
+    /// Whenever the future makes progress the viewport is repainted, so you don't need to poll it yourself.
+    ///
+    /// The future is owned by the widget: if this isn't called with the same `id` during a pass,
+    /// e.g. because the widget was hidden, the future is dropped, which cancels it.
+    /// Use [`Self::forget_promise`] to start over, e.g. to reload something.
+    ///
+    /// The future is polled on the UI thread, so it shouldn't block.
+    /// To run work in the background, spawn it with your async runtime of choice
+    /// and pass the future of its result (e.g. a `JoinHandle`) here.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let id = ui.id().with("answer");
+    /// match ui.ctx().promise(id, || async { 42 }) {
+    ///     Some(answer) => ui.label(format!("The answer is {answer}")),
+    ///     None => ui.spinner(),
+    /// };
+    /// # });
+    /// ```
+    pub fn promise<T, F>(&self, id: Id, future: impl FnOnce() -> F) -> Option<T>
+    where
+        T: Clone + Send + 'static,
+        F: std::future::Future<Output = T> + Send + 'static,
+    {
+        let task = if let Some(task) = self.write(|ctx| ctx.viewport().promises.get(id)) {
+            task
+        } else {
+            // Create the future outside of the lock, in case it uses the `Context`:
+            let task = crate::promise::SharedTask::new(future());
+            self.write(|ctx| ctx.viewport().promises.insert(id, &task));
+            task
+        };
+        task.poll(self, self.viewport_id())
+    }
+
+    /// Drop the future of [`Self::promise`] with the given `id`,
+    /// so that the next call starts a new one.
+    pub fn forget_promise(&self, id: Id) {
+        self.write(|ctx| ctx.viewport().promises.remove(id));
+    }
+}
+
 /// ## Interaction
 impl Context {
     /// Read you what widgets are currently being interacted with.
@@ -3870,7 +4966,7 @@ fn context_impl_send_sync() {
 
 #[cfg(test)]
 mod test {
-    use super::Context;
+    use super::{Context, RawInput, ViewportId};
 
     #[test]
     fn test_single_pass() {
@@ -4010,4 +5106,506 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn test_snapshot_ui() {
+        let ctx = Context::default();
+        let texture = ctx.load_texture(
+            "snapshot",
+            crate::ColorImage::new([100, 50], crate::Color32::TRANSPARENT),
+            Default::default(),
+        );
+
+        let output = ctx.run(Default::default(), |ctx| {
+            let button_rect = ctx.snapshot_ui(&texture, 0.5, |ui| {
+                assert_eq!(ui.max_rect().size(), crate::vec2(200.0, 100.0));
+                ui.button("Not on screen").rect
+            });
+            let is_interactive = ctx.write(|ctx| {
+                ctx.viewport()
+                    .this_pass
+                    .widgets
+                    .layers()
+                    .any(|(_, rects)| rects.iter().any(|widget| widget.rect == button_rect))
+            });
+            assert!(!is_interactive, "The snapshot should not be interactive");
+        });
+
+        assert!(
+            output.shapes.is_empty(),
+            "Nothing should be painted on screen"
+        );
+        assert_eq!(output.snapshots.len(), 1);
+        let snapshot = &output.snapshots[0];
+        assert_eq!(snapshot.texture_id, texture.id());
+        assert_eq!(snapshot.size, [100, 50]);
+        assert!(!snapshot.primitives.is_empty());
+    }
+
+    #[test]
+    fn test_viewport_zoom_factor_override() {
+        let ctx = Context::default();
+        let input = |viewport_id| RawInput {
+            viewport_id,
+            viewports: std::iter::once((viewport_id, Default::default())).collect(),
+            ..Default::default()
+        };
+        let child = ViewportId::from_hash_of("child");
+
+        let _ = ctx.run(input(ViewportId::ROOT), |ctx| {
+            ctx.set_viewport_zoom_factor(child, Some(2.0));
+            assert_eq!(ctx.viewport_zoom_factor(child), None, "Applied next pass");
+        });
+
+        let _ = ctx.run(input(child), |ctx| {
+            assert_eq!(ctx.pixels_per_point(), 2.0);
+            crate::gui_zoom::zoom_in(ctx);
+        });
+        let _ = ctx.run(input(child), |ctx| {
+            assert_eq!(ctx.viewport_zoom_factor(child), Some(2.1));
+            assert_eq!(
+                ctx.zoom_factor(),
+                1.0,
+                "The global zoom should be untouched"
+            );
+        });
+        let _ = ctx.run(input(ViewportId::ROOT), |ctx| {
+            assert_eq!(ctx.pixels_per_point(), 1.0);
+            ctx.set_viewport_zoom_factor(child, None);
+        });
+        let _ = ctx.run(input(child), |ctx| {
+            assert_eq!(ctx.pixels_per_point(), 1.0);
+        });
+    }
+
+    #[test]
+    fn test_zooming_drops_old_fonts() {
+        let ctx = Context::default();
+        ctx.options_mut(|o| o.font_atlas_options.evict_after_passes = 3);
+
+        for i in 0..10 {
+            ctx.set_zoom_factor(1.0 + 0.1 * i as f32);
+            let _ = ctx.run(Default::default(), |_| {});
+        }
+        assert!(ctx.read(|ctx| ctx.fonts.len()) <= 4);
+
+        // Without zooming, only the changes to the font atlas are uploaded:
+        let output = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| ui.label("Ω"));
+        });
+        let font_delta = output
+            .textures_delta
+            .set
+            .iter()
+            .find(|(id, _)| *id == crate::TextureId::default());
+        assert!(font_delta.map_or(true, |(_, delta)| delta.pos.is_some()));
+    }
+
+    #[test]
+    fn test_event_and_response_hooks() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let ctx = Context::default();
+        let key_presses = Arc::new(AtomicUsize::new(0));
+        let responses = Arc::new(AtomicUsize::new(0));
+        ctx.on_event("count_keys", {
+            let key_presses = key_presses.clone();
+            Arc::new(move |_ctx, event| {
+                if matches!(event, crate::Event::Key { pressed: true, .. }) {
+                    key_presses.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        });
+        ctx.on_response("count_buttons", {
+            let responses = responses.clone();
+            Arc::new(move |response| {
+                if response.sense.senses_click() {
+                    responses.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        });
+
+        let input = RawInput {
+            events: vec![crate::Event::Key {
+                key: crate::Key::A,
+                physical_key: None,
+                pressed: true,
+                repeat: false,
+                modifiers: Default::default(),
+            }],
+            ..Default::default()
+        };
+        let _ = ctx.run(input.clone(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                let _ = ui.button("One");
+                let _ = ui.button("Two");
+            });
+        });
+
+        assert_eq!(key_presses.load(Ordering::Relaxed), 1);
+        assert_eq!(responses.load(Ordering::Relaxed), 2);
+
+        // A discarded pass doesn't report the events again:
+        let output = ctx.run(input, |ctx| ctx.request_discard("test"));
+        assert_eq!(output.platform_output.num_completed_passes, 2);
+        assert_eq!(key_presses.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_paint_passes() {
+        use crate::{Color32, LayerId, Rect};
+        use std::sync::Arc;
+
+        let ctx = Context::default();
+        let grid = Rect::from_min_size(crate::pos2(1.0, 2.0), crate::vec2(3.0, 4.0));
+        let paint = |color| -> super::PaintPassCallback {
+            Arc::new(move |painter| {
+                painter.rect_filled(grid, 0.0, color);
+            })
+        };
+        ctx.add_paint_pass("second", LayerId::background(), 1, paint(Color32::GREEN));
+        ctx.add_paint_pass("first", LayerId::background(), 0, paint(Color32::RED));
+
+        let output = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                ui.label("Painted after the passes");
+            });
+        });
+
+        let fills: Vec<Color32> = output
+            .shapes
+            .iter()
+            .filter_map(|clipped| match &clipped.shape {
+                epaint::Shape::Rect(rect) if rect.rect == grid => Some(rect.fill),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(fills, [Color32::RED, Color32::GREEN]);
+
+        let first_non_pass_shape = output
+            .shapes
+            .iter()
+            .position(|clipped| match &clipped.shape {
+                epaint::Shape::Rect(rect) => rect.rect != grid,
+                epaint::Shape::Noop => false,
+                _ => true,
+            })
+            .unwrap();
+        assert_eq!(
+            first_non_pass_shape, 2,
+            "The passes are painted behind the panel"
+        );
+
+        ctx.remove_paint_pass("first");
+        ctx.remove_paint_pass("second");
+        let output = ctx.run(Default::default(), |_| {});
+        assert!(output.shapes.is_empty());
+    }
+
+    #[test]
+    fn test_empty_paint_pass() {
+        use crate::{Color32, LayerId, Rect};
+        use std::sync::Arc;
+
+        let grid = Rect::from_min_size(crate::pos2(1.0, 2.0), crate::vec2(3.0, 4.0));
+        let run = |ctx: &Context| {
+            let output = ctx.run(Default::default(), |ctx| {
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    ui.label("Painted after the passes");
+                });
+            });
+            let (grid_shapes, other_shapes): (Vec<_>, Vec<_>) = output
+                .shapes
+                .into_iter()
+                .filter(|clipped| !matches!(clipped.shape, epaint::Shape::Noop))
+                .partition(|clipped| {
+                    matches!(&clipped.shape, epaint::Shape::Rect(rect) if rect.rect == grid)
+                });
+            (grid_shapes.len(), other_shapes.len())
+        };
+
+        let ctx = Context::default();
+        let (_, num_panel_shapes) = run(&ctx);
+
+        ctx.add_paint_pass("empty", LayerId::background(), 0, Arc::new(|_| {}));
+        ctx.add_paint_pass(
+            "grid",
+            LayerId::background(),
+            1,
+            Arc::new(move |painter| {
+                painter.rect_filled(grid, 0.0, Color32::RED);
+            }),
+        );
+        assert_eq!(
+            run(&ctx),
+            (1, num_panel_shapes),
+            "The empty pass doesn't shift the grid onto a panel shape"
+        );
+    }
+
+    #[test]
+    fn test_memory_snapshot() {
+        use crate::{collapsing_header::CollapsingState, Id, ScrollArea};
+
+        // Returns the namespace and the collapsing header id of each document:
+        fn docs_ui(ctx: &Context) -> Vec<(Id, Id)> {
+            crate::CentralPanel::default()
+                .show(ctx, |ui| {
+                    ["a", "b"].map(|doc| {
+                        ui.push_id(doc, |ui| {
+                            let header = crate::CollapsingHeader::new("Header")
+                                .show(ui, |ui| ui.label("Body"));
+                            ScrollArea::vertical().max_height(20.0).show(ui, |ui| {
+                                for i in 0..20 {
+                                    ui.label(format!("{doc} {i}"));
+                                }
+                            });
+                            (ui.unique_id(), header.header_response.id)
+                        })
+                        .inner
+                    })
+                })
+                .inner
+                .to_vec()
+        }
+
+        let ctx = Context::default();
+        let mut docs = vec![];
+        for _ in 0..2 {
+            let _ = ctx.run(Default::default(), |ctx| docs = docs_ui(ctx));
+        }
+        let [(namespace_a, header_a), (namespace_b, _)] = docs[..] else {
+            panic!("Expected two documents");
+        };
+
+        let mut state = CollapsingState::load_with_default_open(&ctx, header_a, false);
+        state.set_open(true);
+        state.store(&ctx);
+
+        let snapshot = ctx.export_memory(namespace_a, 7);
+        let ids_a: Vec<Id> = snapshot.data.ids().collect();
+        let ids_b: Vec<Id> = ctx.export_memory(namespace_b, 7).data.ids().collect();
+        assert_eq!(snapshot.app_version, 7);
+        assert!(ids_a.contains(&header_a));
+        assert!(
+            ids_a.len() >= 2,
+            "Both the collapsing header and the scroll area are saved"
+        );
+        assert!(ids_a.iter().all(|id| !ids_b.contains(id)));
+
+        ctx.data_mut(|data| data.clear());
+        assert!(CollapsingState::load(&ctx, header_a).is_none());
+        ctx.import_memory(snapshot.clone()).unwrap();
+        assert!(CollapsingState::load(&ctx, header_a).is_some_and(|state| state.is_open()));
+
+        let future = crate::MemorySnapshot {
+            format_version: crate::MemorySnapshot::FORMAT_VERSION + 1,
+            ..snapshot
+        };
+        assert_eq!(
+            ctx.import_memory(future),
+            Err(crate::MemorySnapshotError::UnsupportedFormatVersion(
+                crate::MemorySnapshot::FORMAT_VERSION + 1
+            ))
+        );
+    }
+
+    #[test]
+    fn test_push_id_stable() {
+        let ctx = Context::default();
+        let mut ids = vec![];
+        let _ = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                for key in ["apple", "pear", "pear"] {
+                    ids.push(
+                        ui.push_id_stable(key, |ui| {
+                            ui.label(key);
+                            ui.id()
+                        })
+                        .inner,
+                    );
+                }
+            });
+        });
+
+        assert_eq!(ids[1], ids[2]);
+        let clashes = ctx.prev_pass_state(|state| state.id_clashes.clone());
+        assert_eq!(clashes.len(), 1);
+        assert_eq!(clashes[0].id, ids[1]);
+
+        #[cfg(debug_assertions)]
+        {
+            let source = ctx.id_source(ids[0]).unwrap();
+            assert_eq!(source.key, "\"apple\"");
+            assert_eq!(source.location.file(), file!());
+
+            assert_eq!(clashes[0].sources.len(), 2);
+            let report = clashes[0].sources_report().unwrap();
+            assert!(
+                report.contains("the key \"pear\" more than once"),
+                "{report}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_reduced_motion() {
+        let id = crate::Id::new("anim");
+        let animate = |ctx: &Context, prefers_reduced_motion, value| {
+            let input = crate::RawInput {
+                prefers_reduced_motion,
+                ..Default::default()
+            };
+            let mut t = 0.0;
+            let _ = ctx.run(input, |ctx| t = ctx.animate_bool(id, value));
+            t
+        };
+
+        let ctx = Context::default();
+        assert_eq!(animate(&ctx, None, false), 0.0);
+        let t = animate(&ctx, None, true);
+        assert!(0.0 < t && t < 1.0, "Animating: {t}");
+
+        let ctx = Context::default();
+        assert_eq!(animate(&ctx, Some(true), false), 0.0);
+        assert_eq!(
+            animate(&ctx, Some(true), true),
+            1.0,
+            "The OS asked for no motion"
+        );
+
+        let ctx = Context::default();
+        ctx.style_mut(|style| style.animation.follow_system_reduced_motion = false);
+        assert_eq!(animate(&ctx, Some(true), false), 0.0);
+        assert!(animate(&ctx, Some(true), true) < 1.0);
+
+        ctx.style_mut(|style| style.animation.speed = 2.0);
+        assert_eq!(ctx.scale_animation_time(1.0), 0.5);
+        assert_eq!(
+            ctx.animation_time(crate::style::AnimationKind::Expand),
+            ctx.style().animation.expand / 2.0
+        );
+
+        // The old setting still works:
+        #[allow(deprecated)]
+        ctx.style_mut(|style| style.animation_time = 0.5);
+        assert_eq!(
+            ctx.animation_time(crate::style::AnimationKind::Fade),
+            0.5 / 2.0
+        );
+    }
+
+    #[test]
+    fn test_read_pick() {
+        use crate::{Event, ViewportCommand};
+
+        let ctx = Context::default();
+        let pos = emath::pos2(10.0, 20.0);
+        let mut pick = Some(1);
+
+        let output = ctx.run(RawInput::default(), |ctx| pick = ctx.read_pick(pos));
+        assert_eq!(pick, None, "No reply yet");
+        assert!(output.viewport_output[&ViewportId::ROOT]
+            .commands
+            .contains(&ViewportCommand::ReadPick(pos)));
+
+        let input = RawInput {
+            events: vec![Event::Pick {
+                viewport_id: ViewportId::ROOT,
+                pos,
+                pick_id: 7,
+            }],
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| pick = ctx.read_pick(pos));
+        assert_eq!(pick, Some(7));
+
+        let _ = ctx.run(RawInput::default(), |ctx| pick = ctx.read_pick(pos));
+        assert_eq!(pick, Some(7), "Kept until the next reply");
+    }
+
+    #[test]
+    fn test_painted_custom_cursor() {
+        use crate::{Color32, CursorIcon, Event};
+
+        let pencil = CursorIcon::Custom(crate::Id::new("pencil"));
+        let image = epaint::ColorImage::new([8, 8], Color32::RED);
+
+        let ctx = Context::default();
+        ctx.set_cursor_image(pencil, Some(crate::CursorImage::new(image, [0, 7])));
+        assert!(ctx.cursor_image(pencil).is_some());
+
+        let input = crate::RawInput {
+            events: vec![Event::PointerMoved(emath::pos2(50.0, 50.0))],
+            ..Default::default()
+        };
+        let output = ctx.run(input.clone(), |ctx| ctx.set_cursor_icon(pencil));
+        assert_eq!(
+            output.platform_output.cursor_icon, pencil,
+            "Left to the integration by default"
+        );
+
+        ctx.options_mut(|o| o.paint_custom_cursors = true);
+        let output = ctx.run(input, |ctx| ctx.set_cursor_icon(pencil));
+        assert_eq!(output.platform_output.cursor_icon, CursorIcon::None);
+        let cursor_rect = output
+            .shapes
+            .iter()
+            .find_map(|clipped| match &clipped.shape {
+                epaint::Shape::Mesh(mesh) if mesh.texture_id != epaint::TextureId::default() => {
+                    Some(mesh.calc_bounds())
+                }
+                _ => None,
+            })
+            .expect("The cursor should be painted");
+        assert_eq!(cursor_rect.left_bottom(), emath::pos2(50.0, 51.0));
+    }
+
+    #[test]
+    fn test_layer_z_is_forgotten_with_the_layer() {
+        use crate::{Id, LayerId, Order};
+
+        let ctx = Context::default();
+        let layer_id = LayerId::new(Order::Foreground, Id::new("layer"));
+        let paint = |ctx: &Context| {
+            ctx.layer_painter(layer_id).rect_filled(
+                emath::Rect::from_min_size(emath::Pos2::ZERO, emath::Vec2::splat(10.0)),
+                0.0,
+                epaint::Color32::RED,
+            );
+        };
+
+        let _ = ctx.run(Default::default(), |ctx| {
+            ctx.set_layer_z(layer_id, 3);
+            paint(ctx);
+        });
+        let _ = ctx.run(Default::default(), paint);
+        assert_eq!(ctx.memory(|mem| mem.areas().z(layer_id)), 3);
+
+        // The layer is gone:
+        let _ = ctx.run(Default::default(), |_ctx| {});
+        assert_eq!(ctx.memory(|mem| mem.areas().z(layer_id)), 0);
+    }
+
+    #[test]
+    fn test_translate() {
+        use std::borrow::Cow;
+
+        let ctx = Context::default();
+        assert!(matches!(ctx.translate("de", "Hue"), Cow::Borrowed("Hue")));
+
+        ctx.set_translator(|locale, text| match (locale, text) {
+            ("de", "Hue") => Some("Farbton".to_owned()),
+            _ => None,
+        });
+        assert_eq!(ctx.translate("de", "Hue"), "Farbton");
+        assert!(
+            matches!(ctx.translate("de", "Alpha"), Cow::Borrowed("Alpha")),
+            "Untranslated strings are not copied"
+        );
+        assert!(matches!(ctx.translate("fr", "Hue"), Cow::Borrowed("Hue")));
+    }
 }
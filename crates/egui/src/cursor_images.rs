@@ -0,0 +1,45 @@
+//! Custom images for [`CursorIcon`]s, see [`crate::Context::set_cursor_image`].
+
+use std::{collections::HashMap, sync::Arc};
+
+use epaint::{ColorImage, TextureHandle};
+
+use crate::CursorIcon;
+
+/// An image to show as the mouse cursor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CursorImage {
+    pub image: Arc<ColorImage>,
+
+    /// The pixel of the image that is the tip of the pointer,
+    /// e.g. `[0, 0]` for an arrow pointing to the top left.
+    pub hotspot: [usize; 2],
+}
+
+impl CursorImage {
+    pub fn new(image: impl Into<Arc<ColorImage>>, hotspot: [usize; 2]) -> Self {
+        Self {
+            image: image.into(),
+            hotspot,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct CursorImages {
+    pub images: HashMap<CursorIcon, CursorImage>,
+
+    /// Uploaded when painting the cursor ourselves, see [`crate::Options::paint_custom_cursors`].
+    pub textures: HashMap<CursorIcon, TextureHandle>,
+}
+
+impl CursorImages {
+    pub fn set(&mut self, icon: CursorIcon, image: Option<CursorImage>) {
+        self.textures.remove(&icon);
+        if let Some(image) = image {
+            self.images.insert(icon, image);
+        } else {
+            self.images.remove(&icon);
+        }
+    }
+}
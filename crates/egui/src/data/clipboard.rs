@@ -0,0 +1,88 @@
+/// One representation of something on the clipboard.
+///
+/// Used both for copying, with [`crate::OutputCommand::Copy`],
+/// and pasting, with [`crate::Event::PasteData`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ClipboardData {
+    /// Plain text.
+    Text(String),
+
+    /// An image, e.g. a screenshot.
+    Image(crate::ColorImage),
+
+    /// Rich text as HTML, with a plain text alternative for apps that don't understand HTML.
+    Html { html: String, alt_text: String },
+
+    /// Rich text as RTF.
+    Rtf(String),
+
+    /// Data of any other MIME type, e.g. `"application/x-my-app-nodes"`.
+    Custom { mime: String, data: Vec<u8> },
+}
+
+impl ClipboardData {
+    /// The MIME type of this data.
+    ///
+    /// Images are reported as `"image/png"`, as that is how most platforms exchange them.
+    pub fn mime(&self) -> &str {
+        match self {
+            Self::Text(_) => "text/plain",
+            Self::Image(_) => "image/png",
+            Self::Html { .. } => "text/html",
+            Self::Rtf(_) => "text/rtf",
+            Self::Custom { mime, .. } => mime,
+        }
+    }
+
+    /// The text, if this is [`Self::Text`].
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Self::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// The image, if this is [`Self::Image`].
+    pub fn as_image(&self) -> Option<&crate::ColorImage> {
+        match self {
+            Self::Image(image) => Some(image),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Debug for ClipboardData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text(text) => f.debug_tuple("Text").field(text).finish(),
+            Self::Image(image) => write!(f, "Image({}x{})", image.width(), image.height()),
+            Self::Html { html, alt_text } => f
+                .debug_struct("Html")
+                .field("html", html)
+                .field("alt_text", alt_text)
+                .finish(),
+            Self::Rtf(rtf) => f.debug_tuple("Rtf").field(rtf).finish(),
+            Self::Custom { mime, data } => f
+                .debug_struct("Custom")
+                .field("mime", mime)
+                .field("data", &format_args!("{} bytes", data.len()))
+                .finish(),
+        }
+    }
+}
+
+#[test]
+fn test_clipboard_data_mime() {
+    let custom = ClipboardData::Custom {
+        mime: "application/x-nodes".to_owned(),
+        data: vec![1, 2, 3],
+    };
+    assert_eq!(custom.mime(), "application/x-nodes");
+    assert_eq!(
+        format!("{custom:?}"),
+        r#"Custom { mime: "application/x-nodes", data: 3 bytes }"#
+    );
+    assert_eq!(ClipboardData::Text("hi".to_owned()).as_text(), Some("hi"));
+    assert_eq!(ClipboardData::Rtf(String::new()).as_text(), None);
+}
@@ -81,6 +81,17 @@ pub struct RawInput {
     ///
     /// `None` means "don't know".
     pub system_theme: Option<Theme>,
+
+    /// Has the user asked the OS to minimize non-essential motion?
+    ///
+    /// `None` means "don't know".
+    /// See [`crate::style::AnimationStyle::follow_system_reduced_motion`].
+    pub prefers_reduced_motion: Option<bool>,
+
+    /// Which native menus the integration can show.
+    ///
+    /// Widgets fall back to menus drawn by egui for the others.
+    pub native_menus: crate::NativeMenuSupport,
 }
 
 impl Default for RawInput {
@@ -98,6 +109,8 @@ impl Default for RawInput {
             dropped_files: Default::default(),
             focused: true, // integrations opt into global focus tracking
             system_theme: None,
+            prefers_reduced_motion: None,
+            native_menus: Default::default(),
         }
     }
 }
@@ -131,6 +144,8 @@ impl RawInput {
             dropped_files: std::mem::take(&mut self.dropped_files),
             focused: self.focused,
             system_theme: self.system_theme,
+            prefers_reduced_motion: self.prefers_reduced_motion,
+            native_menus: self.native_menus,
         }
     }
 
@@ -149,6 +164,8 @@ impl RawInput {
             mut dropped_files,
             focused,
             system_theme,
+            prefers_reduced_motion,
+            native_menus,
         } = newer;
 
         self.viewport_id = viewport_ids;
@@ -163,6 +180,8 @@ impl RawInput {
         self.dropped_files.append(&mut dropped_files);
         self.focused = focused;
         self.system_theme = system_theme;
+        self.prefers_reduced_motion = prefers_reduced_motion;
+        self.native_menus = native_menus;
     }
 }
 
@@ -211,6 +230,14 @@ pub struct ViewportInfo {
     /// Current monitor size in egui points.
     pub monitor_size: Option<Vec2>,
 
+    /// All connected monitors.
+    ///
+    /// Empty if the integration doesn't know (e.g. on the web).
+    pub monitors: std::sync::Arc<[crate::MonitorInfo]>,
+
+    /// The monitor the viewport is on, as an index into [`Self::monitors`].
+    pub monitor: Option<usize>,
+
     /// The inner rectangle of the native window, in monitor space and ui points scale.
     ///
     /// This is the content rectangle of the viewport.
@@ -269,6 +296,8 @@ impl ViewportInfo {
             events: std::mem::take(&mut self.events),
             native_pixels_per_point: self.native_pixels_per_point,
             monitor_size: self.monitor_size,
+            monitors: self.monitors.clone(),
+            monitor: self.monitor,
             inner_rect: self.inner_rect,
             outer_rect: self.outer_rect,
             minimized: self.minimized,
@@ -285,6 +314,8 @@ impl ViewportInfo {
             events,
             native_pixels_per_point,
             monitor_size,
+            monitors,
+            monitor,
             inner_rect,
             outer_rect,
             minimized,
@@ -314,6 +345,14 @@ impl ViewportInfo {
             ui.label(opt_as_str(monitor_size));
             ui.end_row();
 
+            ui.label("Monitors:");
+            ui.label(format!("{}", monitors.len()));
+            ui.end_row();
+
+            ui.label("Monitor:");
+            ui.label(opt_as_str(monitor));
+            ui.end_row();
+
             ui.label("Inner rect:");
             ui.label(opt_rect_as_string(inner_rect));
             ui.end_row();
@@ -360,9 +399,18 @@ pub struct HoveredFile {
 
     /// With the `eframe` web backend, this is set to the mime-type of the file (if available).
     pub mime: String,
+
+    /// Where the file is being held, in the same coordinates as the pointer.
+    ///
+    /// `None` if the integration doesn't know, in which case the latest pointer position is used.
+    /// `egui-winit` doesn't know, as winit doesn't report it,
+    /// and the pointer position may be stale, since the pointer often isn't tracked during a drag from outside the app.
+    pub pos: Option<Pos2>,
 }
 
 /// A file dropped into egui.
+///
+/// Use [`crate::Response::dropped_files`] to only get the files dropped onto a specific widget.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct DroppedFile {
@@ -380,6 +428,94 @@ pub struct DroppedFile {
 
     /// Set by the `eframe` web backend.
     pub bytes: Option<std::sync::Arc<[u8]>>,
+
+    /// Where the file was dropped, in the same coordinates as the pointer.
+    ///
+    /// `None` if the integration doesn't know, in which case the latest pointer position is used.
+    /// `egui-winit` doesn't know, as winit doesn't report it,
+    /// and the pointer position may be stale, since the pointer often isn't tracked during a drag from outside the app.
+    pub pos: Option<Pos2>,
+
+    /// Is this a directory on the local file system?
+    ///
+    /// Set by the `egui-winit` backend. Always `false` on the web.
+    pub is_dir: bool,
+}
+
+impl DroppedFile {
+    /// If this is a directory, recursively list all files in it, sorted by path.
+    ///
+    /// Otherwise, returns the file itself.
+    /// The listed files have the same [`Self::pos`] as the directory.
+    /// Symbolic links to directories are followed, but each directory is only listed once.
+    ///
+    /// # Errors
+    /// If a directory could not be read.
+    #[cfg(feature = "dropped_dirs")]
+    pub fn expand_dir(&self) -> std::io::Result<Vec<Self>> {
+        let Some(dir) = self.path.as_ref().filter(|_| self.is_dir) else {
+            return Ok(vec![self.clone()]);
+        };
+
+        let paths = list_files_recursively(dir)?;
+
+        Ok(paths
+            .into_iter()
+            .map(|path| Self {
+                name: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                path: Some(path),
+                pos: self.pos,
+                ..Default::default()
+            })
+            .collect())
+    }
+}
+
+/// All files in `dir` and its subdirectories, sorted by path.
+#[cfg(any(test, feature = "dropped_dirs"))]
+fn list_files_recursively(dir: &std::path::Path) -> std::io::Result<Vec<std::path::PathBuf>> {
+    fn list(
+        dir: &std::path::Path,
+        visited: &mut ahash::HashSet<std::path::PathBuf>,
+        files: &mut Vec<std::path::PathBuf>,
+    ) -> std::io::Result<()> {
+        if !visited.insert(dir.canonicalize()?) {
+            return Ok(()); // Reached through a symbolic link, maybe in a cycle
+        }
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                list(&path, visited, files)?;
+            } else {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    list(dir, &mut Default::default(), &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(unix)]
+#[test]
+#[allow(clippy::disallowed_methods)] // Ok for tests
+fn test_list_files_with_symlink_cycle() {
+    let root = std::env::temp_dir().join(format!("egui_dropped_dir_{}", std::process::id()));
+    let sub = root.join("sub");
+    std::fs::create_dir_all(&sub).unwrap();
+    std::fs::write(root.join("a.txt"), "a").unwrap();
+    std::fs::write(sub.join("b.txt"), "b").unwrap();
+    std::os::unix::fs::symlink(&root, sub.join("back_to_root")).unwrap();
+
+    let files = list_files_recursively(&root);
+    std::fs::remove_dir_all(&root).unwrap();
+    assert_eq!(files.unwrap(), vec![root.join("a.txt"), sub.join("b.txt")]);
 }
 
 /// An input event generated by the integration.
@@ -397,6 +533,19 @@ pub enum Event {
     /// The integration detected a "paste" event (e.g. Cmd+V).
     Paste(String),
 
+    /// Everything the integration could read from the clipboard on a "paste" event,
+    /// e.g. an image and its textual representation.
+    ///
+    /// This is sent in addition to [`Self::Paste`] (which is only sent if there is text),
+    /// so that widgets like a canvas can accept pasted screenshots.
+    PasteData(Vec<crate::ClipboardData>),
+
+    /// A button of a native menu was clicked, see [`crate::NativeMenuButton::id`].
+    NativeMenuItemClicked(crate::Id),
+
+    /// The icon in the system tray was clicked, see [`crate::native_menu::TrayIcon`].
+    TrayIconClicked(PointerButton),
+
     /// Text input, e.g. via keyboard.
     ///
     /// When the user presses enter/return, do not send a [`Text`](Event::Text) (just [`Key::Enter`]).
@@ -545,6 +694,17 @@ pub enum Event {
 
         image: std::sync::Arc<ColorImage>,
     },
+
+    /// The reply to [`crate::ViewportCommand::ReadPick`].
+    Pick {
+        viewport_id: crate::ViewportId,
+
+        /// The position that was asked for, in points.
+        pos: Pos2,
+
+        /// The [`epaint::Mesh::pick_id`] rendered there, or 0 if none.
+        pick_id: u32,
+    },
 }
 
 /// IME event.
@@ -1099,6 +1259,8 @@ impl RawInput {
             dropped_files,
             focused,
             system_theme,
+            prefers_reduced_motion,
+            native_menus,
         } = self;
 
         ui.label(format!("Active viewport: {viewport_id:?}"));
@@ -1124,6 +1286,10 @@ impl RawInput {
         ui.label(format!("dropped_files: {}", dropped_files.len()));
         ui.label(format!("focused: {focused}"));
         ui.label(format!("system_theme: {system_theme:?}"));
+        ui.label(format!(
+            "prefers_reduced_motion: {prefers_reduced_motion:?}"
+        ));
+        ui.label(format!("native_menus: {native_menus:?}"));
         ui.scope(|ui| {
             ui.set_min_height(150.0);
             ui.label(format!("events: {events:#?}"))
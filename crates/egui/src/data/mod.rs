@@ -1,9 +1,11 @@
 //! All the data sent between egui and the backend
 
+mod clipboard;
 pub mod input;
 mod key;
 pub mod output;
 mod user_data;
 
+pub use clipboard::ClipboardData;
 pub use key::Key;
 pub use user_data::UserData;
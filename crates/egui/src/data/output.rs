@@ -18,6 +18,12 @@ pub struct FullOutput {
     /// It is assumed that all egui viewports share the same painter and texture namespace.
     pub textures_delta: epaint::textures::TexturesDelta,
 
+    /// Ui to render into textures, requested with [`crate::Context::snapshot_ui`].
+    ///
+    /// The backend needs to render these _after_ applying [`crate::TexturesDelta::set`],
+    /// and _before_ painting [`Self::shapes`].
+    pub snapshots: Vec<epaint::TextureSnapshot>,
+
     /// What to paint.
     ///
     /// You can use [`crate::Context::tessellate`] to turn this into triangles.
@@ -28,6 +34,12 @@ pub struct FullOutput {
     /// You can pass this to [`crate::Context::tessellate`] together with [`Self::shapes`].
     pub pixels_per_point: f32,
 
+    /// The previous frame, already tessellated, see [`crate::Options::tessellate_in_background`].
+    ///
+    /// When this is set, paint it instead of [`Self::shapes`].
+    /// [`Self::textures_delta`] and [`Self::pixels_per_point`] then belong to it.
+    pub tessellated: Option<Vec<epaint::ClippedPrimitive>>,
+
     /// All the active viewports, including the root.
     ///
     /// It is up to the integration to spawn a native window for each viewport,
@@ -41,15 +53,19 @@ impl FullOutput {
         let Self {
             platform_output,
             textures_delta,
+            snapshots,
             shapes,
             pixels_per_point,
+            tessellated,
             viewport_output,
         } = newer;
 
         self.platform_output.append(platform_output);
         self.textures_delta.append(textures_delta);
+        self.snapshots = snapshots; // Only render the latest
         self.shapes = shapes; // Only paint the latest
         self.pixels_per_point = pixels_per_point; // Use latest
+        self.tessellated = tessellated;
 
         for (id, new_viewport) in viewport_output {
             match self.viewport_output.entry(id) {
@@ -93,11 +109,80 @@ pub enum OutputCommand {
     /// Put this image to the system clipboard.
     CopyImage(crate::ColorImage),
 
+    /// Put these representations of the same thing to the system clipboard,
+    /// e.g. a chart as both an image and a table of text.
+    ///
+    /// They are ordered by preference: integrations that can only put one thing
+    /// on the clipboard use the first one they support.
+    Copy(Vec<crate::ClipboardData>),
+
     /// Open this url in a browser.
     OpenUrl(OpenUrl),
 
     /// Set the mouse cursor position (if the platform supports it).
     SetPointerPosition(emath::Pos2),
+
+    /// Start an OS-level drag of these items out of the app (if the platform supports it),
+    /// e.g. to drop files onto the desktop.
+    ///
+    /// See [`crate::Response::dnd_start_native_drag`].
+    StartDrag(Vec<NativeDragItem>),
+
+    /// Show a native context menu at this position (in points) in the current viewport.
+    ///
+    /// The clicked button is reported back with [`crate::Event::NativeMenuItemClicked`].
+    /// Only sent if the integration reports support in [`crate::RawInput::native_menus`].
+    ///
+    /// See [`crate::Response::native_context_menu`].
+    ShowContextMenu {
+        pos: emath::Pos2,
+        items: Vec<crate::NativeMenuItem>,
+    },
+
+    /// Set the native menu bar of the current viewport.
+    ///
+    /// Clicks are reported back with [`crate::Event::NativeMenuItemClicked`].
+    /// Only sent if the integration reports support in [`crate::RawInput::native_menus`].
+    ///
+    /// See [`crate::native_menu::menu_bar`].
+    SetMenuBar(Vec<crate::NativeMenuItem>),
+
+    /// Show, update or (with `None`) remove the icon in the system tray.
+    ///
+    /// Clicks are reported back to the root viewport with [`crate::Event::TrayIconClicked`]
+    /// and [`crate::Event::NativeMenuItemClicked`].
+    /// Only sent if the integration reports support in [`crate::RawInput::native_menus`].
+    ///
+    /// See [`crate::native_menu::TrayIcon`].
+    SetTrayIcon(Option<crate::native_menu::TrayIcon>),
+}
+
+/// Something that can be dragged out of the app, see [`OutputCommand::StartDrag`].
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum NativeDragItem {
+    /// A file or directory on disk.
+    File(std::path::PathBuf),
+
+    /// Plain text.
+    Text(String),
+
+    /// Data of a custom MIME type, e.g. `"application/x-my-app-node"`.
+    Custom { mime: String, data: Vec<u8> },
+}
+
+impl std::fmt::Debug for NativeDragItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(path) => f.debug_tuple("File").field(path).finish(),
+            Self::Text(text) => f.debug_tuple("Text").field(text).finish(),
+            Self::Custom { mime, data } => f
+                .debug_struct("Custom")
+                .field("mime", mime)
+                .field("data", &format_args!("{} bytes", data.len()))
+                .finish(),
+        }
+    }
 }
 
 /// The non-rendering part of what egui emits each frame.
@@ -295,7 +380,7 @@ pub enum UserAttentionType {
 /// egui emits a [`CursorIcon`] in [`PlatformOutput`] each frame as a request to the integration.
 ///
 /// Loosely based on <https://developer.mozilla.org/en-US/docs/Web/CSS/cursor>.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum CursorIcon {
     /// Normal cursor icon, whatever that is.
@@ -416,6 +501,12 @@ pub enum CursorIcon {
 
     /// Let's get a better overview
     ZoomOut,
+
+    // ------------------------------------
+    /// An icon of your own, showing the image given to [`crate::Context::set_cursor_image`].
+    ///
+    /// Integrations that don't have the image show [`Self::Default`] instead.
+    Custom(crate::Id),
 }
 
 impl CursorIcon {
@@ -0,0 +1,310 @@
+//! CAD-style measurement annotations: linear, radial and angular dimensions.
+//!
+//! See [`Dimension`].
+
+use std::f32::consts::TAU;
+
+use emath::{Pos2, Rangef, Rot2, Vec2};
+use epaint::{Color32, Shape, Stroke, TextShape};
+
+use crate::{layers::ShapeIdx, style::DimensionStyle, Painter};
+
+/// What a [`Dimension`] measures.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DimensionKind {
+    /// The distance between two points.
+    ///
+    /// The dimension line is parallel to the measured points, moved `offset` points to the side.
+    /// Positive offsets put it above a measurement from left to right.
+    Linear { from: Pos2, to: Pos2, offset: f32 },
+
+    /// The radius of a circle, with a leader from the center in the direction of `angle`.
+    Radial {
+        center: Pos2,
+        radius: f32,
+        angle: f32,
+    },
+
+    /// The angle between two rays from `center`, with the arc drawn at `radius`.
+    ///
+    /// The angles are in radians, clockwise from the positive x-axis (like [`Vec2::angled`]).
+    Angular {
+        center: Pos2,
+        radius: f32,
+        angles: Rangef,
+    },
+}
+
+/// A measurement annotation, like in a technical drawing:
+/// a dimension line with arrows, extension lines to what is measured, and the measurement.
+///
+/// Paint it with [`Painter::dimension`], which uses [`crate::Style::dimension`].
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::{pos2, Dimension};
+///
+/// let painter = ui.painter();
+/// painter.dimension(&Dimension::linear(pos2(10.0, 60.0), pos2(110.0, 60.0), 20.0).suffix(" mm"));
+/// painter.dimension(&Dimension::radial(pos2(200.0, 60.0), 40.0, -0.7));
+/// painter.dimension(&Dimension::angular(pos2(300.0, 80.0), 50.0, -1.2..=-0.2));
+/// # });
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dimension {
+    pub kind: DimensionKind,
+
+    /// Shown instead of the measurement, if set.
+    pub label: Option<String>,
+
+    /// Lengths are multiplied by this, e.g. to go from points to millimeters.
+    pub scale: f32,
+
+    /// Shown after the measurement, e.g. a unit.
+    pub suffix: String,
+}
+
+impl Dimension {
+    pub fn new(kind: DimensionKind) -> Self {
+        Self {
+            kind,
+            label: None,
+            scale: 1.0,
+            suffix: String::new(),
+        }
+    }
+
+    /// See [`DimensionKind::Linear`].
+    pub fn linear(from: Pos2, to: Pos2, offset: f32) -> Self {
+        Self::new(DimensionKind::Linear { from, to, offset })
+    }
+
+    /// See [`DimensionKind::Radial`].
+    pub fn radial(center: Pos2, radius: f32, angle: f32) -> Self {
+        Self::new(DimensionKind::Radial {
+            center,
+            radius,
+            angle,
+        })
+    }
+
+    /// See [`DimensionKind::Angular`].
+    pub fn angular(center: Pos2, radius: f32, angles: impl Into<Rangef>) -> Self {
+        Self::new(DimensionKind::Angular {
+            center,
+            radius,
+            angles: angles.into(),
+        })
+    }
+
+    /// Show this instead of the measurement.
+    #[inline]
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Multiply lengths by this, e.g. to go from points to millimeters.
+    ///
+    /// Does not affect angles.
+    #[inline]
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Show this after the measurement, e.g. a unit.
+    #[inline]
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = suffix.into();
+        self
+    }
+
+    /// The measured length (scaled), or the angle in degrees.
+    pub fn measurement(&self) -> f32 {
+        match self.kind {
+            DimensionKind::Linear { from, to, .. } => self.scale * from.distance(to),
+            DimensionKind::Radial { radius, .. } => self.scale * radius,
+            DimensionKind::Angular { angles, .. } => angles.span().to_degrees(),
+        }
+    }
+
+    /// The text shown, e.g. `R12.5 mm`.
+    pub fn text(&self, decimals: usize) -> String {
+        if let Some(label) = &self.label {
+            return label.clone();
+        }
+        let value = format!("{:.*}", decimals, self.measurement());
+        match self.kind {
+            DimensionKind::Linear { .. } => format!("{value}{}", self.suffix),
+            DimensionKind::Radial { .. } => format!("R{value}{}", self.suffix),
+            DimensionKind::Angular { .. } => format!("{value}°"),
+        }
+    }
+
+    fn shapes(&self, painter: &Painter, style: &DimensionStyle, color: Color32) -> Vec<Shape> {
+        let stroke = Stroke::new(style.stroke_width, color);
+        let font_id = style.text_style.resolve(&painter.ctx().style());
+        let galley = painter.layout_no_wrap(self.text(style.decimals), font_id, color);
+        let text_size = galley.size();
+        let mut shapes = vec![];
+
+        // Where to center the text, and the direction to write it in:
+        let (text_pos, text_dir) = match self.kind {
+            DimensionKind::Linear { from, to, offset } => {
+                let dir = (to - from).normalized();
+                let side = dir.rot90() * offset.signum();
+                let normal = dir.rot90() * offset;
+                let (a, b) = (from + normal, to + normal);
+
+                for (measured, end) in [(from, a), (to, b)] {
+                    if offset.abs() > style.extension_gap {
+                        shapes.push(Shape::line_segment(
+                            [
+                                measured + style.extension_gap * side,
+                                end + style.extension_overshoot * side,
+                            ],
+                            stroke,
+                        ));
+                    }
+                }
+                shapes.push(Shape::line_segment([a, b], stroke));
+                shapes.push(arrowhead(a, -dir, style.arrow_size, color));
+                shapes.push(arrowhead(b, dir, style.arrow_size, color));
+
+                let outward = if offset == 0.0 { dir.rot90() } else { side };
+                (a + 0.5 * (b - a) + (0.5 * text_size.y + 2.0) * outward, dir)
+            }
+
+            DimensionKind::Radial {
+                center,
+                radius,
+                angle,
+            } => {
+                let dir = Vec2::angled(angle);
+                let edge = center + radius * dir;
+                shapes.push(Shape::line_segment([center, edge], stroke));
+                shapes.push(arrowhead(edge, dir, style.arrow_size, color));
+
+                // Above the middle of the leader:
+                let mut above = dir.rot90();
+                if above.y > 0.0 {
+                    above = -above;
+                }
+                (
+                    center + 0.5 * radius * dir + (0.5 * text_size.y + 2.0) * above,
+                    dir,
+                )
+            }
+
+            DimensionKind::Angular {
+                center,
+                radius,
+                angles,
+            } => {
+                for angle in [angles.min, angles.max] {
+                    let dir = Vec2::angled(angle);
+                    shapes.push(Shape::line_segment(
+                        [
+                            center + style.extension_gap * dir,
+                            center + (radius + style.extension_overshoot) * dir,
+                        ],
+                        stroke,
+                    ));
+                }
+
+                let segments = ((angles.span().abs() * radius / 4.0).ceil() as usize).clamp(1, 256);
+                let arc: Vec<Pos2> = (0..=segments)
+                    .map(|i| {
+                        let angle = emath::lerp(angles, i as f32 / segments as f32);
+                        center + radius * Vec2::angled(angle)
+                    })
+                    .collect();
+                shapes.push(Shape::line(arc, stroke));
+
+                // The arrows point along the arc, away from its middle.
+                // `rot90` turns counter-clockwise on screen, i.e. towards smaller angles:
+                let start_tangent = Vec2::angled(angles.min).rot90();
+                let end_tangent = -Vec2::angled(angles.max).rot90();
+                let (start_tangent, end_tangent) = if angles.span() < 0.0 {
+                    (-start_tangent, -end_tangent)
+                } else {
+                    (start_tangent, end_tangent)
+                };
+                shapes.push(arrowhead(
+                    center + radius * Vec2::angled(angles.min),
+                    start_tangent,
+                    style.arrow_size,
+                    color,
+                ));
+                shapes.push(arrowhead(
+                    center + radius * Vec2::angled(angles.max),
+                    end_tangent,
+                    style.arrow_size,
+                    color,
+                ));
+
+                let middle = Vec2::angled(angles.center());
+                (
+                    center + (radius + 0.5 * text_size.y + 2.0) * middle,
+                    middle.rot90(),
+                )
+            }
+        };
+
+        // Never upside down:
+        let mut angle = text_dir.angle();
+        if angle.abs() > TAU / 4.0 + 1e-3 {
+            angle += TAU / 2.0;
+        }
+        let top_left = text_pos - Rot2::from_angle(angle) * (0.5 * text_size);
+        shapes.push(
+            TextShape::new(top_left, galley, color)
+                .with_angle(angle)
+                .into(),
+        );
+
+        shapes
+    }
+}
+
+/// A filled arrowhead with its tip at `tip`, pointing in the direction of `dir`.
+fn arrowhead(tip: Pos2, dir: Vec2, size: f32, color: Color32) -> Shape {
+    let dir = dir.normalized();
+    let back = tip - size * dir;
+    let half_width = 0.3 * size * dir.rot90();
+    Shape::convex_polygon(
+        vec![tip, back + half_width, back - half_width],
+        color,
+        Stroke::NONE,
+    )
+}
+
+/// ## Dimensions
+impl Painter {
+    /// Paint a measurement annotation, styled by [`crate::Style::dimension`].
+    pub fn dimension(&self, dimension: &Dimension) -> ShapeIdx {
+        let style = self.ctx().style();
+        let color = style
+            .dimension
+            .color
+            .unwrap_or_else(|| style.visuals.text_color());
+        self.add(Shape::Vec(dimension.shapes(self, &style.dimension, color)))
+    }
+}
+
+#[test]
+fn test_dimension_text() {
+    use emath::pos2;
+
+    let linear = Dimension::linear(pos2(0.0, 0.0), pos2(30.0, 40.0), 10.0);
+    assert_eq!(linear.measurement(), 50.0);
+    assert_eq!(linear.clone().scale(0.5).suffix(" mm").text(1), "25.0 mm");
+    assert_eq!(linear.label("width").text(1), "width");
+
+    let radial = Dimension::radial(pos2(0.0, 0.0), 12.5, 0.0);
+    assert_eq!(radial.text(2), "R12.50");
+
+    let angular = Dimension::angular(pos2(0.0, 0.0), 20.0, 0.0..=TAU / 8.0);
+    assert_eq!(angular.text(0), "45°");
+}
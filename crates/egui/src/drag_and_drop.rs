@@ -1,6 +1,9 @@
 use std::{any::Any, sync::Arc};
 
-use crate::{Context, CursorIcon, Id};
+use crate::{
+    vec2, Context, CursorIcon, Id, LayerId, Order, Pos2, Rect, Response, StrokeKind, TextStyle,
+    ViewportId,
+};
 
 /// Tracking of drag-and-drop payload.
 ///
@@ -12,6 +15,12 @@ use crate::{Context, CursorIcon, Id};
 /// - [`crate::Response::dnd_set_drag_payload`]
 /// - [`crate::Response::dnd_hover_payload`]
 /// - [`crate::Response::dnd_release_payload`]
+/// - [`crate::Response::dnd_drop_target`]
+///
+/// The payload is shared by all viewports of the same [`Context`],
+/// so it can be dragged from one native window and dropped in another.
+/// This needs the integration to report the position of every viewport
+/// in [`crate::RawInput::viewports`], which isn't possible on e.g. Wayland.
 ///
 /// See [this example](https://github.com/emilk/egui/blob/master/crates/egui_demo_lib/src/demo/drag_and_drop.rs).
 #[doc(alias = "drag and drop")]
@@ -19,6 +28,46 @@ use crate::{Context, CursorIcon, Id};
 pub struct DragAndDrop {
     /// If set, something is currently being dragged
     payload: Option<Arc<dyn Any + Send + Sync>>,
+
+    /// Text shown next to the cursor while dragging, see [`Self::set_preview`].
+    preview: Option<Arc<str>>,
+
+    /// The drop regions registered by each viewport during its last pass.
+    drop_regions: Vec<DropRegion>,
+
+    /// The drop regions each viewport registered during its previous pass,
+    /// used for routing dropped files.
+    previous_drop_regions: Vec<DropRegion>,
+
+    /// The innermost drop region under the pointer, as of the last pass.
+    hovered_drop_region: Option<Id>,
+
+    /// The viewport the drag started in, which receives the pointer events while dragging.
+    source_viewport: ViewportId,
+
+    /// The pointer position while dragging, in desktop coordinates (points).
+    screen_pointer_pos: Option<Pos2>,
+
+    /// The viewport under the pointer while dragging.
+    hovered_viewport: Option<ViewportId>,
+
+    /// The payload was released over a drop region in another viewport,
+    /// which can pick it up during its next pass.
+    pending_drop: Option<(ViewportId, Id)>,
+}
+
+/// See [`DragAndDrop::register_drop_region`].
+#[derive(Clone, Copy)]
+struct DropRegion {
+    id: Id,
+    viewport_id: ViewportId,
+
+    /// In desktop coordinates (points), or viewport coordinates if we don't know
+    /// where the viewport is.
+    screen_rect: Rect,
+
+    /// Does the (viewport-local) pointer hover it?
+    contains_pointer: bool,
 }
 
 impl DragAndDrop {
@@ -31,6 +80,52 @@ impl DragAndDrop {
     ///
     /// This needs to happen at frame start so we can properly capture the escape key.
     fn begin_pass(ctx: &Context) {
+        let viewport_id = ctx.viewport_id();
+        let open_viewports: Vec<ViewportId> =
+            ctx.input(|i| i.raw.viewports.keys().copied().collect());
+        ctx.data_mut(|data| {
+            let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
+            state.hovered_drop_region = state
+                .drop_regions
+                .iter()
+                .filter(|region| state.is_region_hovered(region))
+                .min_by(|a, b| a.screen_rect.area().total_cmp(&b.screen_rect.area()))
+                .map(|region| region.id);
+
+            state
+                .previous_drop_regions
+                .retain(|region| region.viewport_id != viewport_id);
+            let this_viewport = state
+                .drop_regions
+                .iter()
+                .filter(|region| region.viewport_id == viewport_id);
+            state.previous_drop_regions.extend(this_viewport);
+
+            // This viewport registers its regions anew during this pass,
+            // and viewports that were closed never will:
+            let is_open =
+                |id: &ViewportId| open_viewports.is_empty() || open_viewports.contains(id);
+            state
+                .drop_regions
+                .retain(|region| region.viewport_id != viewport_id && is_open(&region.viewport_id));
+
+            if state.hovered_viewport.is_some_and(|id| !is_open(&id)) {
+                state.hovered_viewport = None;
+            }
+        });
+
+        let target_closed = ctx.data(|data| {
+            data.get_temp::<Self>(Id::NULL)
+                .and_then(|state| state.pending_drop)
+                .is_some_and(|(target_viewport, _)| {
+                    !open_viewports.is_empty() && !open_viewports.contains(&target_viewport)
+                })
+        });
+        if target_closed {
+            // The viewport the payload was dropped in closed before it could pick it up.
+            Self::clear_payload(ctx);
+        }
+
         let has_any_payload = Self::has_any_payload(ctx);
 
         if has_any_payload {
@@ -49,13 +144,37 @@ impl DragAndDrop {
     /// This must happen at end-of-frame such that we don't shadow the mouse release event from user
     /// code.
     fn end_pass(ctx: &Context) {
+        let viewport_id = ctx.viewport_id();
+
+        let unclaimed_drop = ctx.data(|data| {
+            data.get_temp::<Self>(Id::NULL)
+                .and_then(|state| state.pending_drop)
+                .is_some_and(|(target_viewport, _)| target_viewport == viewport_id)
+        });
+        if unclaimed_drop {
+            // The drop target in this viewport didn't pick up the payload.
+            Self::clear_payload(ctx);
+            return;
+        }
+        if ctx.data(|data| {
+            data.get_temp::<Self>(Id::NULL)
+                .is_some_and(|state| state.pending_drop.is_some())
+        }) {
+            // Waiting for another viewport to pick up the payload.
+            return;
+        }
+
         let has_any_payload = Self::has_any_payload(ctx);
 
         if has_any_payload {
+            Self::track_pointer_across_viewports(ctx);
+
             let abort_dnd_due_to_mouse_release = ctx.input_mut(|i| i.pointer.any_released());
 
             if abort_dnd_due_to_mouse_release {
-                Self::clear_payload(ctx);
+                if !Self::drop_in_other_viewport(ctx) {
+                    Self::clear_payload(ctx);
+                }
             } else {
                 // We set the cursor icon only if its default, as the user code might have
                 // explicitly set it already.
@@ -64,8 +183,151 @@ impl DragAndDrop {
                         o.cursor_icon = CursorIcon::Grabbing;
                     }
                 });
+
+                Self::paint_preview(ctx);
+            }
+        }
+    }
+
+    /// Is the region under the pointer, either in the viewport receiving the pointer events,
+    /// or in another viewport the pointer is over?
+    fn is_region_hovered(&self, region: &DropRegion) -> bool {
+        if region.viewport_id == self.source_viewport {
+            region.contains_pointer
+        } else {
+            self.hovered_viewport == Some(region.viewport_id)
+                && self
+                    .screen_pointer_pos
+                    .is_some_and(|pos| region.screen_rect.contains(pos))
+        }
+    }
+
+    /// Where the viewport is on the desktop, if known.
+    fn viewport_offset(ctx: &Context) -> Option<crate::Vec2> {
+        ctx.input(|i| i.viewport().inner_rect)
+            .map(|rect| rect.min.to_vec2())
+    }
+
+    /// While dragging in the source viewport, find out which viewport the pointer is over,
+    /// and make sure that viewport repaints so it can show the preview.
+    fn track_pointer_across_viewports(ctx: &Context) {
+        let viewport_id = ctx.viewport_id();
+        let is_source = ctx.data(|data| {
+            data.get_temp::<Self>(Id::NULL)
+                .is_some_and(|state| state.source_viewport == viewport_id)
+        });
+        if !is_source {
+            return;
+        }
+
+        let screen_pointer_pos = Self::viewport_offset(ctx)
+            .zip(ctx.pointer_latest_pos())
+            .map(|(offset, pos)| pos + offset);
+        let hovered_viewport = screen_pointer_pos.and_then(|pos| {
+            ctx.input(|i| {
+                let contains = |id: &ViewportId| {
+                    i.raw.viewports.get(id).is_some_and(|info| {
+                        info.inner_rect.is_some_and(|rect| rect.contains(pos))
+                            && info.minimized != Some(true)
+                    })
+                };
+                if contains(&viewport_id) {
+                    Some(viewport_id)
+                } else {
+                    i.raw.viewports.keys().copied().find(contains)
+                }
+            })
+        });
+
+        if let Some(hovered_viewport) = hovered_viewport {
+            if hovered_viewport != viewport_id {
+                ctx.request_repaint_of(hovered_viewport);
             }
         }
+
+        ctx.data_mut(|data| {
+            let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
+            state.screen_pointer_pos = screen_pointer_pos;
+            state.hovered_viewport = hovered_viewport;
+        });
+    }
+
+    /// The payload was released: if it was over a drop region in another viewport,
+    /// hand it over to that viewport and return `true`.
+    fn drop_in_other_viewport(ctx: &Context) -> bool {
+        let viewport_id = ctx.viewport_id();
+        let target = ctx.data_mut(|data| {
+            let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
+            let target_viewport = state.hovered_viewport.filter(|&id| id != viewport_id)?;
+            let pos = state.screen_pointer_pos?;
+            let region = state
+                .drop_regions
+                .iter()
+                .filter(|region| {
+                    region.viewport_id == target_viewport && region.screen_rect.contains(pos)
+                })
+                .min_by(|a, b| a.screen_rect.area().total_cmp(&b.screen_rect.area()))?;
+            state.pending_drop = Some((target_viewport, region.id));
+            Some(target_viewport)
+        });
+
+        if let Some(target) = target {
+            ctx.request_repaint_of(target);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Paint the [`Self::set_preview`] text as a translucent ghost next to the cursor,
+    /// in whichever viewport the cursor is over.
+    fn paint_preview(ctx: &Context) {
+        let viewport_id = ctx.viewport_id();
+        let Some((preview, pointer_pos)) = ctx.data(|data| {
+            let state = data.get_temp::<Self>(Id::NULL)?;
+            let preview = state.preview?;
+            if state.source_viewport == viewport_id
+                && state.hovered_viewport.map_or(true, |id| id == viewport_id)
+            {
+                Some((preview, None))
+            } else if state.hovered_viewport == Some(viewport_id) {
+                Some((preview, state.screen_pointer_pos))
+            } else {
+                None
+            }
+        }) else {
+            return;
+        };
+        let pointer_pos = match pointer_pos {
+            Some(screen_pos) => Self::viewport_offset(ctx).map(|offset| screen_pos - offset),
+            None => ctx.pointer_latest_pos(),
+        };
+        let Some(pointer_pos) = pointer_pos else {
+            return;
+        };
+
+        let style = ctx.style();
+        let visuals = &style.visuals;
+        let galley = ctx.fonts(|fonts| {
+            fonts.layout_no_wrap(
+                preview.to_string(),
+                TextStyle::Body.resolve(&style),
+                visuals.text_color(),
+            )
+        });
+
+        let margin = style.spacing.button_padding;
+        let rect =
+            Rect::from_min_size(pointer_pos + vec2(12.0, 12.0), galley.size() + 2.0 * margin);
+        let painter = ctx.layer_painter(LayerId::new(Order::Tooltip, Id::new("dnd_preview")));
+        painter.rect(
+            rect,
+            visuals.menu_corner_radius,
+            visuals.window_fill.gamma_multiply(0.8),
+            visuals.window_stroke,
+            StrokeKind::Inside,
+        );
+        painter.galley(rect.min + margin, galley, visuals.text_color());
     }
 
     /// Set a drag-and-drop payload.
@@ -75,9 +337,21 @@ impl DragAndDrop {
     where
         Payload: Any + Send + Sync,
     {
+        let viewport_id = ctx.viewport_id();
         ctx.data_mut(|data| {
             let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
             state.payload = Some(Arc::new(payload));
+            state.source_viewport = viewport_id;
+        });
+    }
+
+    /// Show this text next to the cursor while dragging the current payload.
+    ///
+    /// The preview is cleared together with the payload.
+    pub fn set_preview(ctx: &Context, preview: impl Into<Arc<str>>) {
+        ctx.data_mut(|data| {
+            let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
+            state.preview = Some(preview.into());
         });
     }
 
@@ -86,9 +360,103 @@ impl DragAndDrop {
         ctx.data_mut(|data| {
             let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
             state.payload = None;
+            state.preview = None;
+            state.screen_pointer_pos = None;
+            state.hovered_viewport = None;
+            state.pending_drop = None;
+        });
+    }
+
+    /// Register a drop region, so that nested or overlapping drop targets
+    /// in different [`crate::Ui`]s can agree on which one is hovered.
+    ///
+    /// Call this each pass for each drop target.
+    /// [`crate::Response::dnd_drop_target`] does this for you.
+    pub fn register_drop_region(response: &Response) {
+        let ctx = &response.ctx;
+        let mut screen_rect = ctx
+            .layer_transform_to_global(response.layer_id)
+            .map_or(response.rect, |to_global| to_global * response.rect);
+        if let Some(offset) = Self::viewport_offset(ctx) {
+            screen_rect = screen_rect.translate(offset);
+        }
+        let region = DropRegion {
+            id: response.id,
+            viewport_id: ctx.viewport_id(),
+            screen_rect,
+            contains_pointer: response.contains_pointer(),
+        };
+        ctx.data_mut(|data| {
+            let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
+            state.drop_regions.push(region);
         });
     }
 
+    /// The id of the innermost registered drop region under the pointer, as of last pass.
+    ///
+    /// See [`Self::register_drop_region`].
+    pub fn hovered_drop_region(ctx: &Context) -> Option<Id> {
+        ctx.data(|data| data.get_temp::<Self>(Id::NULL)?.hovered_drop_region)
+    }
+
+    /// Is this the innermost drop region under the pointer?
+    ///
+    /// Also true if the pointer is over it but no region was hovered last pass,
+    /// to avoid a frame of delay.
+    pub(crate) fn is_hovered_drop_region(response: &Response) -> bool {
+        match Self::hovered_drop_region(&response.ctx) {
+            Some(id) => id == response.id,
+            None => response.contains_pointer(),
+        }
+    }
+
+    /// Is this the innermost drop region at `pos` (in the coordinates of the pointer),
+    /// as of the previous pass?
+    ///
+    /// If no region was registered there, this falls back to checking the rectangle of the response.
+    pub(crate) fn is_drop_region_at(response: &Response, pos: Pos2) -> bool {
+        let ctx = &response.ctx;
+        let viewport_id = ctx.viewport_id();
+        let screen_pos = Self::viewport_offset(ctx).map_or(pos, |offset| pos + offset);
+        let innermost = ctx.data(|data| {
+            data.get_temp::<Self>(Id::NULL)?
+                .previous_drop_regions
+                .iter()
+                .filter(|region| {
+                    region.viewport_id == viewport_id && region.screen_rect.contains(screen_pos)
+                })
+                .min_by(|a, b| a.screen_rect.area().total_cmp(&b.screen_rect.area()))
+                .map(|region| region.id)
+        });
+        if let Some(id) = innermost {
+            id == response.id
+        } else {
+            let local_pos = ctx
+                .layer_transform_from_global(response.layer_id)
+                .map_or(pos, |from_global| from_global * pos);
+            response.interact_rect.contains(local_pos)
+                && ctx.layer_id_at(pos) == Some(response.layer_id)
+        }
+    }
+
+    /// If the payload was dropped on this region from another viewport, take it.
+    pub(crate) fn take_pending_drop<Payload>(response: &Response) -> Option<Arc<Payload>>
+    where
+        Payload: Any + Send + Sync,
+    {
+        let viewport_id = response.ctx.viewport_id();
+        let is_target = response.ctx.data(|data| {
+            data.get_temp::<Self>(Id::NULL)
+                .and_then(|state| state.pending_drop)
+                == Some((viewport_id, response.id))
+        });
+        if is_target {
+            Self::take_payload(&response.ctx)
+        } else {
+            None
+        }
+    }
+
     /// Retrieve the payload, if any.
     ///
     /// Returns `None` if there is no payload, or if it is not of the requested type.
@@ -118,6 +486,8 @@ impl DragAndDrop {
     {
         ctx.data_mut(|data| {
             let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
+            state.preview = None;
+            state.pending_drop = None;
             let payload = state.payload.take()?;
             payload.downcast().ok()
         })
@@ -145,3 +515,162 @@ impl DragAndDrop {
         })
     }
 }
+
+#[test]
+fn test_preview_is_cleared_with_payload() {
+    let ctx = Context::default();
+    let preview = |ctx: &Context| ctx.data(|data| data.get_temp::<DragAndDrop>(Id::NULL)?.preview);
+
+    DragAndDrop::set_payload(&ctx, 42_i32);
+    DragAndDrop::set_preview(&ctx, "The answer");
+    assert_eq!(preview(&ctx).as_deref(), Some("The answer"));
+
+    assert_eq!(DragAndDrop::take_payload::<i32>(&ctx).as_deref(), Some(&42));
+    assert_eq!(preview(&ctx), None);
+}
+
+#[test]
+fn test_drop_region_hovered_across_viewports() {
+    let other_viewport = ViewportId::from_hash_of("other");
+    let region = |viewport_id, contains_pointer| DropRegion {
+        id: Id::new("region"),
+        viewport_id,
+        screen_rect: Rect::from_min_size(Pos2::new(500.0, 0.0), vec2(100.0, 100.0)),
+        contains_pointer,
+    };
+    let state = DragAndDrop {
+        screen_pointer_pos: Some(Pos2::new(550.0, 50.0)),
+        hovered_viewport: Some(other_viewport),
+        ..Default::default()
+    };
+
+    // In the source viewport we trust the pointer of that viewport:
+    assert!(state.is_region_hovered(&region(ViewportId::ROOT, true)));
+    assert!(!state.is_region_hovered(&region(ViewportId::ROOT, false)));
+
+    // In other viewports we go by the desktop position:
+    assert!(state.is_region_hovered(&region(other_viewport, false)));
+    let elsewhere = DragAndDrop {
+        screen_pointer_pos: Some(Pos2::new(50.0, 50.0)),
+        ..state
+    };
+    assert!(!elsewhere.is_region_hovered(&region(other_viewport, false)));
+}
+
+#[test]
+fn test_dropped_files_go_to_innermost_target() {
+    use crate::{CentralPanel, DroppedFile, RawInput, Sense};
+
+    let ctx = Context::default();
+    let run = |dropped_files: Vec<DroppedFile>| {
+        let mut received = (0, 0);
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(400.0, 400.0))),
+            dropped_files,
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let outer = ui.interact(ui.max_rect(), Id::new("outer"), Sense::hover());
+                let inner_rect = Rect::from_min_size(Pos2::new(50.0, 50.0), vec2(50.0, 50.0));
+                let inner = ui.interact(inner_rect, Id::new("inner"), Sense::hover());
+                received.0 += outer.dropped_files().len();
+                received.1 += inner.dropped_files().len();
+            });
+        });
+        received
+    };
+
+    let file_at = |x, y| DroppedFile {
+        name: "file.txt".to_owned(),
+        pos: Some(Pos2::new(x, y)),
+        ..Default::default()
+    };
+    run(vec![]);
+    assert_eq!(run(vec![file_at(75.0, 75.0)]), (0, 1));
+    assert_eq!(run(vec![file_at(300.0, 300.0)]), (1, 0));
+}
+
+#[test]
+fn test_native_drag_sends_start_drag() {
+    use crate::{
+        CentralPanel, Event, NativeDragItem, OutputCommand, PointerButton, RawInput, Sense,
+    };
+
+    let ctx = Context::default();
+    let run = |events: Vec<Event>| {
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(400.0, 400.0))),
+            events,
+            ..Default::default()
+        };
+        let output = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let rect = Rect::from_min_size(Pos2::new(50.0, 50.0), vec2(50.0, 50.0));
+                ui.interact(rect, Id::new("source"), Sense::drag())
+                    .dnd_start_native_drag(|| vec![NativeDragItem::Text("hello".to_owned())]);
+            });
+        });
+        output
+            .platform_output
+            .commands
+            .into_iter()
+            .filter_map(|cmd| match cmd {
+                OutputCommand::StartDrag(items) => Some(items),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+    let press = |pos, pressed| Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Default::default(),
+    };
+
+    let start = Pos2::new(75.0, 75.0);
+    assert!(run(vec![Event::PointerMoved(start)]).is_empty());
+
+    // A widget that only senses drags starts dragging as soon as it is pressed:
+    let drags = run(vec![press(start, true)]);
+    assert_eq!(drags.len(), 1);
+    assert!(matches!(&drags[0][..], [NativeDragItem::Text(text)] if text == "hello"));
+
+    // Only sent once per drag:
+    assert!(run(vec![Event::PointerMoved(start + vec2(30.0, 0.0))]).is_empty());
+}
+
+#[test]
+fn test_pending_drop_is_cleared_when_the_target_closes() {
+    use crate::{RawInput, ViewportInfo};
+
+    let ctx = Context::default();
+    let target = ViewportId::from_hash_of("target");
+    let run = |open: &[ViewportId]| {
+        let input = RawInput {
+            viewports: open
+                .iter()
+                .map(|&id| (id, ViewportInfo::default()))
+                .collect(),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |_| {});
+    };
+
+    DragAndDrop::set_payload(&ctx, 42_i32);
+    ctx.data_mut(|data| {
+        let state = data.get_temp_mut_or_default::<DragAndDrop>(Id::NULL);
+        state.pending_drop = Some((target, Id::new("region")));
+        state.hovered_viewport = Some(target);
+    });
+
+    // Still waiting for the target to pick it up:
+    run(&[ViewportId::ROOT, target]);
+    assert!(DragAndDrop::has_any_payload(&ctx));
+
+    run(&[ViewportId::ROOT]);
+    assert!(!DragAndDrop::has_any_payload(&ctx));
+    let state = ctx.data(|data| data.get_temp::<DragAndDrop>(Id::NULL).unwrap());
+    assert!(state.pending_drop.is_none());
+    assert!(state.hovered_viewport.is_none());
+}
@@ -0,0 +1,463 @@
+//! A built-in overlay to find text on the page, opened with Cmd+F (Ctrl+F), like in a browser.
+//!
+//! Turn it on with [`crate::Options::find_in_page`].
+//! It searches the text painted this pass, outlines the matches,
+//! and scrolls between them with Enter and Shift+Enter.
+//!
+//! Text that isn't laid out, like the rows of [`crate::ScrollArea::show_rows`] that are out of view,
+//! can be searched with [`search_offscreen`].
+
+use std::sync::Arc;
+
+use emath::OrderedFloat;
+use epaint::{RectShape, StrokeKind};
+
+use crate::{
+    Align2, Area, Context, Frame, Galley, Id, Key, KeyboardShortcut, LayerId, Modifiers, Order,
+    Rect, Shape, Stroke, TextEdit, Ui, Vec2,
+};
+
+/// Register this plugin on the given egui context,
+/// so that it will be called every pass.
+///
+/// This is a built-in plugin in egui,
+/// meaning [`Context`] calls this from its `Default` implementation,
+/// so this is marked as `pub(crate)`.
+pub(crate) fn register(ctx: &Context) {
+    ctx.on_begin_pass("find_in_page", Arc::new(State::begin_pass));
+    ctx.on_end_pass("find_in_page", Arc::new(State::end_pass));
+}
+
+/// What the user is looking for, if the find overlay is open and they have typed something.
+pub fn query(ctx: &Context) -> Option<String> {
+    ctx.data(|data| data.get_temp::<State>(State::id()))
+        .filter(|state| state.open && !state.query.is_empty())
+        .map(|state| state.query)
+}
+
+/// Add matches in text that isn't laid out this pass,
+/// e.g. the rows of a virtualized list that are out of view.
+///
+/// `search` is only called while the user is looking for something, with what they are looking for.
+/// It returns where each match would be, in the same coordinates as `ui`,
+/// so that the overlay can count them and scroll to them.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// let rows: Vec<String> = (0..1000).map(|i| format!("Row {i}")).collect();
+/// let row_height = ui.text_style_height(&egui::TextStyle::Body);
+/// let row_height_with_spacing = row_height + ui.spacing().item_spacing.y;
+///
+/// egui::ScrollArea::vertical().show_rows(ui, row_height, rows.len(), |ui, visible_rows| {
+///     let top_of_first_row =
+///         ui.max_rect().top() - visible_rows.start as f32 * row_height_with_spacing;
+///     egui::find_in_page::search_offscreen(ui, |query| {
+///         let query = query.to_lowercase();
+///         (0..rows.len())
+///             .filter(|i| !visible_rows.contains(i) && rows[*i].to_lowercase().contains(&query))
+///             .map(|i| {
+///                 let top = top_of_first_row + i as f32 * row_height_with_spacing;
+///                 egui::Rect::from_x_y_ranges(ui.max_rect().x_range(), top..=top + row_height)
+///             })
+///             .collect()
+///     });
+///
+///     for row in &rows[visible_rows] {
+///         ui.label(row);
+///     }
+/// });
+/// # });
+/// ```
+pub fn search_offscreen(ui: &Ui, search: impl FnOnce(&str) -> Vec<Rect>) {
+    let Some(query) = query(ui.ctx()) else {
+        return;
+    };
+    let rects = search(&query);
+    let (layer_id, clip_rect) = (ui.layer_id(), ui.clip_rect());
+    ui.ctx().data_mut(|data| {
+        let state = data.get_temp_mut_or_default::<State>(State::id());
+        state.offscreen.extend(rects.into_iter().map(|rect| Match {
+            layer_id,
+            clip_rect,
+            rect,
+        }));
+    });
+}
+
+/// A match, in the coordinates of its layer.
+#[derive(Clone, Copy, Debug)]
+struct Match {
+    layer_id: LayerId,
+    clip_rect: Rect,
+    rect: Rect,
+}
+
+#[derive(Clone, Debug, Default)]
+struct State {
+    open: bool,
+
+    /// Give the query field keyboard focus the next time it is shown.
+    focus: bool,
+
+    query: String,
+
+    /// Index of the current match, in visual order.
+    current: usize,
+
+    /// How many matches there were last pass.
+    num_matches: usize,
+
+    /// Scroll to the current match at the end of this pass.
+    scroll_to_current: bool,
+
+    /// The [`crate::ScrollArea`] to scroll the next pass, and where to, in layer coordinates.
+    scroll_target: Option<(Id, Rect)>,
+
+    /// Matches in text that isn't laid out, added this pass with [`search_offscreen`].
+    offscreen: Vec<Match>,
+}
+
+impl State {
+    fn id() -> Id {
+        Id::new("egui_find_in_page")
+    }
+
+    fn load(ctx: &Context) -> Self {
+        ctx.data(|data| data.get_temp(Self::id()))
+            .unwrap_or_default()
+    }
+
+    fn store(self, ctx: &Context) {
+        ctx.data_mut(|data| data.insert_temp(Self::id(), self));
+    }
+
+    fn begin_pass(ctx: &Context) {
+        if !ctx.options(|o| o.find_in_page) {
+            return;
+        }
+
+        let mut state = Self::load(ctx);
+
+        let shortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::F);
+        if ctx.input_mut(|i| i.consume_shortcut(&shortcut)) {
+            state.open = true;
+            state.focus = true;
+        }
+
+        if let Some(target) = state.scroll_target.take() {
+            ctx.pass_state_mut(|pass_state| pass_state.scroll_area_target = Some(target));
+        }
+
+        state.offscreen.clear();
+        state.store(ctx);
+    }
+
+    fn end_pass(ctx: &Context) {
+        let mut state = Self::load(ctx);
+        if !state.open || !ctx.options(|o| o.find_in_page) {
+            return;
+        }
+
+        let overlay = Area::new(Self::id())
+            .order(Order::Foreground)
+            .anchor(Align2::RIGHT_TOP, Vec2::new(-8.0, 8.0))
+            .show(ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| state.ui(ui));
+            });
+
+        if !state.open || state.query.is_empty() {
+            state.num_matches = 0;
+            state.store(ctx);
+            return;
+        }
+
+        let mut matches = ctx.graphics(|graphics| {
+            let mut matches = std::mem::take(&mut state.offscreen);
+            for (layer_id, list) in graphics.iter() {
+                if layer_id != overlay.response.layer_id {
+                    for clipped_shape in list.all_entries() {
+                        add_matches(
+                            &clipped_shape.shape,
+                            layer_id,
+                            clipped_shape.clip_rect,
+                            &state.query,
+                            &mut matches,
+                        );
+                    }
+                }
+            }
+            matches
+        });
+
+        // Top-down, then left-to-right, like the text is read:
+        let global_min = |m: &Match| {
+            ctx.layer_transform_to_global(m.layer_id)
+                .map_or(m.rect, |to_global| to_global * m.rect)
+                .min
+        };
+        matches.sort_by_cached_key(|m| {
+            let min = global_min(m);
+            (OrderedFloat(min.y), OrderedFloat(min.x))
+        });
+
+        if state.num_matches != matches.len() {
+            ctx.request_repaint(); // Show the new count
+        }
+        state.num_matches = matches.len();
+        state.current = state.current.min(matches.len().saturating_sub(1));
+
+        if state.scroll_to_current {
+            state.scroll_to_current = false;
+            if let Some(current) = matches.get(state.current) {
+                // Scroll the innermost scroll area that contains the match, if any:
+                let scroll_area = ctx.pass_state(|pass_state| {
+                    pass_state
+                        .scroll_areas
+                        .iter()
+                        .find(|(_, layer_id, clip_rect)| {
+                            *layer_id == current.layer_id
+                                && clip_rect.contains(current.clip_rect.center())
+                        })
+                        .map(|(id, _, _)| *id)
+                });
+                if let Some(scroll_area) = scroll_area {
+                    state.scroll_target = Some((scroll_area, current.rect));
+                    ctx.request_repaint();
+                }
+            }
+        }
+
+        let visuals = ctx.style().visuals.clone();
+        ctx.graphics_mut(|graphics| {
+            for (i, m) in matches.iter().enumerate() {
+                let stroke = if i == state.current {
+                    Stroke::new(2.0, visuals.warn_fg_color)
+                } else {
+                    Stroke::new(1.0, visuals.selection.bg_fill)
+                };
+                graphics.entry(m.layer_id).add(
+                    m.clip_rect,
+                    RectShape::stroke(m.rect, 2.0, stroke, StrokeKind::Outside).into(),
+                );
+            }
+        });
+
+        state.store(ctx);
+    }
+
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                TextEdit::singleline(&mut self.query)
+                    .id(Self::id().with("query"))
+                    .hint_text(ui.translate("Find in page"))
+                    .desired_width(160.0),
+            );
+            if std::mem::take(&mut self.focus) {
+                response.request_focus();
+            }
+            if response.changed() {
+                self.current = 0;
+                self.scroll_to_current = true;
+            }
+
+            let (enter, shift, escape) = ui.input(|i| {
+                (
+                    i.key_pressed(Key::Enter),
+                    i.modifiers.shift,
+                    i.key_pressed(Key::Escape),
+                )
+            });
+
+            if self.query.is_empty() {
+                // Nothing to count
+            } else if self.num_matches == 0 {
+                ui.weak(ui.translate("No matches"));
+            } else {
+                ui.label(format!("{}/{}", self.current + 1, self.num_matches));
+            }
+
+            let previous = ui
+                .button("⏶")
+                .on_hover_text(ui.translate("Previous match (Shift+Enter)"));
+            let next = ui
+                .button("⏷")
+                .on_hover_text(ui.translate("Next match (Enter)"));
+            let close = ui.button("🗙").on_hover_text(ui.translate("Close (Escape)"));
+
+            let submitted = response.lost_focus() && enter;
+            if submitted {
+                response.request_focus();
+            }
+
+            if 0 < self.num_matches {
+                if previous.clicked() || (submitted && shift) {
+                    self.current = (self.current + self.num_matches - 1) % self.num_matches;
+                    self.scroll_to_current = true;
+                } else if next.clicked() || submitted {
+                    self.current = (self.current + 1) % self.num_matches;
+                    self.scroll_to_current = true;
+                }
+            }
+
+            if close.clicked() || (response.lost_focus() || response.has_focus()) && escape {
+                self.open = false;
+            }
+        });
+    }
+}
+
+/// Find the matches in the text of the shape.
+fn add_matches(
+    shape: &Shape,
+    layer_id: LayerId,
+    clip_rect: Rect,
+    query: &str,
+    out: &mut Vec<Match>,
+) {
+    match shape {
+        Shape::Vec(shapes) | Shape::Pick { shapes, .. } | Shape::PixelSnapped { shapes, .. } => {
+            for shape in shapes {
+                add_matches(shape, layer_id, clip_rect, query, out);
+            }
+        }
+        Shape::Text(text_shape) if text_shape.angle == 0.0 => {
+            out.extend(
+                galley_matches(&text_shape.galley, query)
+                    .into_iter()
+                    .map(|rect| Match {
+                        layer_id,
+                        clip_rect,
+                        rect: rect.translate(text_shape.pos.to_vec2()),
+                    }),
+            );
+        }
+        _ => {}
+    }
+}
+
+/// The rectangles of the matches of `query` in the galley, ignoring case.
+///
+/// One rectangle per row of each match.
+fn galley_matches(galley: &Galley, query: &str) -> Vec<Rect> {
+    fn fold_case(c: char) -> char {
+        c.to_lowercase().next().unwrap_or(c)
+    }
+
+    let query: Vec<char> = query.chars().map(fold_case).collect();
+    let glyphs: Vec<(usize, &epaint::text::Glyph)> = galley
+        .rows
+        .iter()
+        .enumerate()
+        .flat_map(|(row_index, row)| row.glyphs.iter().map(move |glyph| (row_index, glyph)))
+        .collect();
+    if query.is_empty() || glyphs.len() < query.len() {
+        return vec![];
+    }
+
+    let mut rects = vec![];
+    let mut start = 0;
+    while start + query.len() <= glyphs.len() {
+        let candidate = &glyphs[start..start + query.len()];
+        let is_match = candidate
+            .iter()
+            .zip(&query)
+            .all(|((_, glyph), &c)| fold_case(glyph.chr) == c);
+        if !is_match {
+            start += 1;
+            continue;
+        }
+
+        for row_glyphs in candidate.chunk_by(|(a, _), (b, _)| a == b) {
+            let (row_index, first) = row_glyphs[0];
+            let (_, last) = row_glyphs[row_glyphs.len() - 1];
+            let row_rect = galley.rows[row_index].rect;
+            rects.push(Rect::from_x_y_ranges(
+                first.pos.x..=last.max_x(),
+                row_rect.y_range(),
+            ));
+        }
+        start += query.len();
+    }
+    rects
+}
+
+#[test]
+fn test_galley_matches() {
+    let ctx = Context::default();
+    let _ = ctx.run(Default::default(), |_ctx| {});
+    let galley = ctx.fonts(|fonts| {
+        fonts.layout_no_wrap(
+            "Hello, hello!".to_owned(),
+            epaint::FontId::default(),
+            crate::Color32::WHITE,
+        )
+    });
+
+    let rects = galley_matches(&galley, "HELLO");
+    assert_eq!(rects.len(), 2);
+    assert!(rects[0].right() <= rects[1].left());
+    assert_eq!(rects[0].height(), galley.rows[0].rect.height());
+
+    assert!(galley_matches(&galley, "goodbye").is_empty());
+    assert!(galley_matches(&galley, "").is_empty());
+}
+
+#[test]
+fn test_scroll_to_match_in_its_scroll_area() {
+    use crate::{Event, RawInput, ScrollArea};
+
+    let ctx = Context::default();
+    ctx.options_mut(|o| o.find_in_page = true);
+    ctx.all_styles_mut(|style| style.scroll_animation = crate::style::ScrollAnimation::none());
+
+    let run = |events: Vec<Event>| {
+        let mut offsets = [0.0; 2];
+        let _ = ctx.run(
+            RawInput {
+                screen_rect: Some(Rect::from_min_size(crate::Pos2::ZERO, Vec2::splat(400.0))),
+                events,
+                ..Default::default()
+            },
+            |ctx| {
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    for (i, salt) in ["first", "second"].into_iter().enumerate() {
+                        offsets[i] = ScrollArea::vertical()
+                            .id_salt(salt)
+                            .max_height(100.0)
+                            .show(ui, |ui| {
+                                for row in 0..50 {
+                                    if i == 1 && row == 5 {
+                                        ui.label("Needle");
+                                    } else {
+                                        ui.label(format!("Row {row}"));
+                                    }
+                                }
+                            })
+                            .state
+                            .offset
+                            .y;
+                    }
+                });
+            },
+        );
+        offsets
+    };
+
+    run(vec![]);
+    let open = Event::Key {
+        key: Key::F,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: Modifiers::COMMAND,
+    };
+    run(vec![open]);
+    run(vec![]);
+    run(vec![Event::Text("needle".to_owned())]);
+    run(vec![]);
+    let [first, second] = run(vec![]);
+
+    assert_eq!(first, 0.0, "The other scroll area doesn't scroll");
+    assert!(0.0 < second, "The scroll area with the match centers it");
+}
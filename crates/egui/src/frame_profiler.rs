@@ -0,0 +1,235 @@
+//! A built-in profiler that records how long the phases of each frame take,
+//! and shows them in a timeline overlay.
+//!
+//! Turn it on with [`Context::set_profiling`].
+//! egui records the input handling, the layout of each [`crate::Area`], the end of the pass
+//! and tessellation. Integrations and apps can add their own scopes with [`Context::profile_scope`].
+
+use std::sync::Arc;
+
+use web_time::Instant;
+
+use crate::{Align2, Area, Color32, Context, FontId, Frame, Id, Order, Pos2, Rect, Sense, Vec2};
+
+/// The timings recorded during one frame, see [`Context::frame_profile`].
+#[derive(Clone, Debug, Default)]
+pub struct FrameProfile {
+    /// All scopes, in the order they were started.
+    pub scopes: Vec<ProfileScope>,
+
+    /// From the start of this frame to the start of the next one, in seconds.
+    pub duration: f64,
+}
+
+impl FrameProfile {
+    /// The total duration of all top-level scopes with this name, in seconds.
+    pub fn total(&self, name: &str) -> f64 {
+        self.scopes
+            .iter()
+            .filter(|scope| scope.depth == 0 && scope.name == name)
+            .map(|scope| scope.duration)
+            .sum()
+    }
+}
+
+/// One timed scope of a [`FrameProfile`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfileScope {
+    pub name: String,
+
+    /// How many scopes this is nested in.
+    pub depth: usize,
+
+    /// Seconds since the start of the frame.
+    pub start: f64,
+
+    /// In seconds.
+    pub duration: f64,
+}
+
+/// Lives in the [`Context`] while profiling is on.
+pub(crate) struct FrameProfiler {
+    frame_start: Instant,
+    scopes: Vec<ProfileScope>,
+
+    /// Indices into `scopes` of the scopes that haven't ended yet.
+    open: Vec<usize>,
+
+    last_frame: Option<Arc<FrameProfile>>,
+}
+
+impl Default for FrameProfiler {
+    fn default() -> Self {
+        Self {
+            frame_start: Instant::now(),
+            scopes: Vec::new(),
+            open: Vec::new(),
+            last_frame: None,
+        }
+    }
+}
+
+impl FrameProfiler {
+    /// Finish the previous frame and start a new one.
+    pub(crate) fn begin_frame(&mut self) {
+        let now = Instant::now();
+        if !self.scopes.is_empty() {
+            let duration = (now - self.frame_start).as_secs_f64();
+            for &index in &self.open {
+                let scope = &mut self.scopes[index];
+                scope.duration = duration - scope.start;
+            }
+            self.last_frame = Some(Arc::new(FrameProfile {
+                scopes: std::mem::take(&mut self.scopes),
+                duration,
+            }));
+        }
+        self.frame_start = now;
+        self.open.clear();
+    }
+
+    pub(crate) fn begin_scope(&mut self, name: String) {
+        self.open.push(self.scopes.len());
+        self.scopes.push(ProfileScope {
+            name,
+            depth: self.open.len() - 1,
+            start: self.frame_start.elapsed().as_secs_f64(),
+            duration: 0.0,
+        });
+    }
+
+    pub(crate) fn end_scope(&mut self) {
+        if let Some(index) = self.open.pop() {
+            let scope = &mut self.scopes[index];
+            scope.duration = self.frame_start.elapsed().as_secs_f64() - scope.start;
+        }
+    }
+
+    pub(crate) fn last_frame(&self) -> Option<Arc<FrameProfile>> {
+        self.last_frame.clone()
+    }
+}
+
+/// Register the overlay on the given egui context.
+///
+/// This is a built-in plugin in egui.
+pub(crate) fn register(ctx: &Context) {
+    ctx.on_end_pass("frame_profiler", Arc::new(show_overlay));
+}
+
+fn show_overlay(ctx: &Context) {
+    let Some(profile) = ctx.frame_profile() else {
+        return;
+    };
+
+    let screen_rect = ctx.screen_rect();
+    let width = (screen_rect.width() - 32.0).clamp(100.0, 640.0);
+    let row_height = 16.0;
+    let font_id = FontId::monospace(10.0);
+
+    Area::new(Id::new("egui_frame_profiler"))
+        .order(Order::Debug)
+        .anchor(Align2::LEFT_BOTTOM, Vec2::new(8.0, -8.0))
+        .interactable(true)
+        .show(ctx, |ui| {
+            Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(format!(
+                    "Frame: {:.2} ms, tessellation: {:.2} ms",
+                    1e3 * profile.duration,
+                    1e3 * profile.total("tessellation"),
+                ));
+
+                let max_depth = profile.scopes.iter().map(|s| s.depth).max().unwrap_or(0);
+                let size = Vec2::new(width, row_height * (max_depth + 1) as f32);
+                let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+                let painter = ui.painter_at(rect);
+                painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+
+                let duration = profile.duration.max(1e-6);
+                let mut hovered = None;
+                for scope in &profile.scopes {
+                    let x = |t: f64| rect.left() + (t / duration) as f32 * rect.width();
+                    let top = rect.top() + row_height * scope.depth as f32;
+                    let bar = Rect::from_min_max(
+                        Pos2::new(x(scope.start), top),
+                        Pos2::new(
+                            x(scope.start + scope.duration).max(x(scope.start) + 1.0),
+                            top + row_height - 1.0,
+                        ),
+                    );
+                    painter.rect_filled(bar, 2.0, scope_color(&scope.name));
+                    if 40.0 < bar.width() {
+                        let galley = painter.layout_no_wrap(
+                            scope.name.clone(),
+                            font_id.clone(),
+                            Color32::WHITE,
+                        );
+                        let painter = painter.with_clip_rect(bar.intersect(rect));
+                        painter.galley(
+                            bar.left_center() + Vec2::new(3.0, -0.5 * galley.size().y),
+                            galley,
+                            Color32::WHITE,
+                        );
+                    }
+                    if response
+                        .hover_pos()
+                        .is_some_and(|pos| bar.expand2(Vec2::X).contains(pos))
+                    {
+                        hovered = Some(scope);
+                    }
+                }
+
+                if let Some(scope) = hovered {
+                    response.on_hover_text_at_pointer(format!(
+                        "{}\n{:.3} ms",
+                        scope.name,
+                        1e3 * scope.duration
+                    ));
+                }
+            });
+        });
+}
+
+/// A stable color for the scope name.
+fn scope_color(name: &str) -> Color32 {
+    let hash = Id::new(name).value();
+    let hue = (hash % 360) as f32 / 360.0;
+    crate::ecolor::Hsva::new(hue, 0.5, 0.55, 1.0).into()
+}
+
+#[test]
+fn test_frame_profile() {
+    let ctx = Context::default();
+    ctx.set_profiling(true);
+
+    for _ in 0..2 {
+        let output = ctx.run(Default::default(), |ctx| {
+            Area::new(Id::new("slow area")).show(ctx, |ui| {
+                let ctx = ui.ctx().clone();
+                ctx.profile_scope("my scope", || ui.label("Hello"));
+            });
+        });
+        let _ = ctx.tessellate(output.shapes, output.pixels_per_point);
+    }
+
+    let profile = ctx.frame_profile().expect("We've completed a frame");
+    let names: Vec<&str> = profile.scopes.iter().map(|s| s.name.as_str()).collect();
+    for name in ["input", "end_pass", "tessellation", "my scope"] {
+        assert!(names.contains(&name), "Missing {name:?} in {names:?}");
+    }
+    assert!(names.iter().any(|name| name.starts_with("GenericArea")));
+
+    let my_scope = profile
+        .scopes
+        .iter()
+        .find(|s| s.name == "my scope")
+        .unwrap();
+    assert_eq!(my_scope.depth, 1, "Nested inside the area");
+    assert!(profile
+        .scopes
+        .iter()
+        .all(|s| s.duration <= profile.duration));
+
+    ctx.set_profiling(false);
+    assert!(ctx.frame_profile().is_none());
+}
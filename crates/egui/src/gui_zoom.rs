@@ -28,11 +28,14 @@ pub mod kb_shortcuts {
 /// Let the user scale the GUI (change [`Context::zoom_factor`]) by pressing
 /// Cmd+Plus, Cmd+Minus or Cmd+0, just like in a browser.
 ///
+/// If the current viewport has its own zoom factor (see [`Context::set_viewport_zoom_factor`]),
+/// that is changed instead.
+///
 /// By default, [`crate::Context`] calls this function at the end of each frame,
 /// controllable by [`crate::Options::zoom_with_keyboard`].
 pub(crate) fn zoom_with_keyboard(ctx: &Context) {
     if ctx.input_mut(|i| i.consume_shortcut(&kb_shortcuts::ZOOM_RESET)) {
-        ctx.set_zoom_factor(1.0);
+        set_zoom(ctx, 1.0);
     } else {
         if ctx.input_mut(|i| i.consume_shortcut(&kb_shortcuts::ZOOM_IN))
             || ctx.input_mut(|i| i.consume_shortcut(&kb_shortcuts::ZOOM_IN_SECONDARY))
@@ -48,22 +51,54 @@ pub(crate) fn zoom_with_keyboard(ctx: &Context) {
 const MIN_ZOOM_FACTOR: f32 = 0.2;
 const MAX_ZOOM_FACTOR: f32 = 5.0;
 
+/// The zoom factor of the current viewport.
+fn current_zoom(ctx: &Context) -> f32 {
+    ctx.effective_zoom_factor(ctx.viewport_id())
+}
+
+/// Change the zoom factor of the current viewport if it has its own, else the global one.
+fn set_zoom(ctx: &Context, zoom_factor: f32) {
+    let viewport_id = ctx.viewport_id();
+    if ctx.viewport_zoom_factor(viewport_id).is_some() {
+        ctx.set_viewport_zoom_factor(viewport_id, Some(zoom_factor));
+    } else {
+        ctx.set_zoom_factor(zoom_factor);
+    }
+}
+
 /// Make everything larger by increasing [`Context::zoom_factor`].
 pub fn zoom_in(ctx: &Context) {
-    let mut zoom_factor = ctx.zoom_factor();
+    let mut zoom_factor = current_zoom(ctx);
     zoom_factor += 0.1;
     zoom_factor = zoom_factor.clamp(MIN_ZOOM_FACTOR, MAX_ZOOM_FACTOR);
     zoom_factor = (zoom_factor * 10.).round() / 10.;
-    ctx.set_zoom_factor(zoom_factor);
+    set_zoom(ctx, zoom_factor);
 }
 
 /// Make everything smaller by decreasing [`Context::zoom_factor`].
 pub fn zoom_out(ctx: &Context) {
-    let mut zoom_factor = ctx.zoom_factor();
+    let mut zoom_factor = current_zoom(ctx);
     zoom_factor -= 0.1;
     zoom_factor = zoom_factor.clamp(MIN_ZOOM_FACTOR, MAX_ZOOM_FACTOR);
     zoom_factor = (zoom_factor * 10.).round() / 10.;
-    ctx.set_zoom_factor(zoom_factor);
+    set_zoom(ctx, zoom_factor);
+}
+
+/// Show a drag value for fine-grained (fractional) zooming of the current viewport,
+/// shown in percent.
+pub fn zoom_drag_value(ui: &mut Ui) {
+    let ctx = ui.ctx().clone();
+    let mut percent = current_zoom(&ctx) * 100.0;
+    let response = ui.add(
+        crate::DragValue::new(&mut percent)
+            .range(MIN_ZOOM_FACTOR * 100.0..=MAX_ZOOM_FACTOR * 100.0)
+            .speed(1.0)
+            .fixed_decimals(0)
+            .suffix("%"),
+    );
+    if response.changed() {
+        set_zoom(&ctx, percent / 100.0);
+    }
 }
 
 /// Show buttons for zooming the ui.
@@ -82,7 +117,7 @@ pub fn zoom_menu_buttons(ui: &mut Ui) {
 
     if ui
         .add_enabled(
-            ui.ctx().zoom_factor() < MAX_ZOOM_FACTOR,
+            current_zoom(ui.ctx()) < MAX_ZOOM_FACTOR,
             button(ui.ctx(), "Zoom In", &kb_shortcuts::ZOOM_IN),
         )
         .clicked()
@@ -92,7 +127,7 @@ pub fn zoom_menu_buttons(ui: &mut Ui) {
 
     if ui
         .add_enabled(
-            ui.ctx().zoom_factor() > MIN_ZOOM_FACTOR,
+            current_zoom(ui.ctx()) > MIN_ZOOM_FACTOR,
             button(ui.ctx(), "Zoom Out", &kb_shortcuts::ZOOM_OUT),
         )
         .clicked()
@@ -102,11 +137,11 @@ pub fn zoom_menu_buttons(ui: &mut Ui) {
 
     if ui
         .add_enabled(
-            ui.ctx().zoom_factor() != 1.0,
+            current_zoom(ui.ctx()) != 1.0,
             button(ui.ctx(), "Reset Zoom", &kb_shortcuts::ZOOM_RESET),
         )
         .clicked()
     {
-        ui.ctx().set_zoom_factor(1.0);
+        set_zoom(ui.ctx(), 1.0);
     }
 }
@@ -38,13 +38,20 @@ pub struct WidgetHits {
 }
 
 /// Find the top or closest widgets to the given position,
-/// none which is closer than `search_radius`.
+/// none of which is farther away than `search_radius`.
+///
+/// Widgets that sense clicks or drags, and are smaller than `min_size` in their layer,
+/// are hit as if they were grown around their center to that size.
+/// Widgets with a [`WidgetRects::hit_path`] are never grown.
+/// The returned [`WidgetRect`]s are the original ones, so a grown widget
+/// can be in [`WidgetHits::contains_pointer`] without its `interact_rect` containing `pos`.
 pub fn hit_test(
     widgets: &WidgetRects,
     layer_order: &[LayerId],
     layer_to_global: &HashMap<LayerId, TSTransform>,
     pos: Pos2,
     search_radius: f32,
+    min_size: f32,
 ) -> WidgetHits {
     profiling::function_scope!();
 
@@ -64,7 +71,16 @@ pub fn hit_test(
         .iter()
         .filter(|layer| layer.order.allow_interaction())
         .flat_map(|&layer_id| widgets.get_layer(layer_id))
-        .filter(|&w| {
+        .copied()
+        .map(|mut w| {
+            // Widgets with an exact outline are not grown, or they could be hit outside of it:
+            if (w.sense.senses_click() || w.sense.senses_drag()) && widgets.hit_path(w.id).is_none()
+            {
+                w.interact_rect = grow_to_min_size(w.interact_rect, min_size);
+            }
+            w
+        })
+        .filter(|w| {
             if w.interact_rect.is_negative() {
                 return false;
             }
@@ -73,15 +89,20 @@ pub fn hit_test(
             // TODO(emilk): we should probably do the distance testing in global space instead
             let dist_sq = w.interact_rect.distance_sq_to_pos(pos_in_layer);
 
+            if let Some(path) = widgets.hit_path(w.id) {
+                if !path.contains(pos_in_layer) {
+                    return false;
+                }
+            }
+
             // In tie, pick last = topmost.
             if dist_sq <= closest_dist_sq {
                 closest_dist_sq = dist_sq;
-                closest_hit = Some(w);
+                closest_hit = Some(*w);
             }
 
             dist_sq <= search_radius_sq
         })
-        .copied()
         .collect();
 
     // Transform to global coordinates:
@@ -193,6 +214,15 @@ pub fn hit_test(
     hits
 }
 
+/// Grow the rectangle around its center, so it is at least `min_size` wide and high.
+fn grow_to_min_size(rect: Rect, min_size: f32) -> Rect {
+    if rect.is_negative() {
+        return rect;
+    }
+    let size = rect.size().max(emath::Vec2::splat(min_size));
+    Rect::from_center_size(rect.center(), size)
+}
+
 /// Returns true if the rectangle contains the whole circle.
 fn contains_circle(interact_rect: emath::Rect, pos: Pos2, radius: f32) -> bool {
     interact_rect.shrink(radius).contains(pos)
@@ -480,6 +510,7 @@ mod tests {
             interact_rect: rect,
             sense,
             enabled: true,
+            parent_id: Id::NULL,
         }
     }
 
@@ -573,4 +604,89 @@ mod tests {
         assert_eq!(hits.click.unwrap().id, Id::new("fg-right-label"));
         assert_eq!(hits.drag.unwrap().id, Id::new("fg-right-label"));
     }
+
+    #[test]
+    fn touch_target_size() {
+        let mut widgets = WidgetRects::default();
+        for w in [
+            wr(
+                Id::new("bg-area"),
+                Sense::drag(),
+                Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 100.0)),
+            ),
+            wr(
+                Id::new("small-button"),
+                Sense::click(),
+                Rect::from_min_size(pos2(40.0, 40.0), vec2(10.0, 10.0)),
+            ),
+            wr(
+                Id::new("label"),
+                Sense::hover(),
+                Rect::from_min_size(pos2(40.0, 70.0), vec2(10.0, 10.0)),
+            ),
+        ] {
+            widgets.insert(w.layer_id, w);
+        }
+        let layers = [LayerId::background()];
+        let test =
+            |pos, min_size| hit_test(&widgets, &layers, &Default::default(), pos, 0.0, min_size);
+
+        // Too far away for a mouse:
+        let hits = test(pos2(30.0, 45.0), 0.0);
+        assert_eq!(hits.click, None);
+        assert_eq!(hits.drag.unwrap().id, Id::new("bg-area"));
+
+        // …but close enough for a finger:
+        let hits = test(pos2(30.0, 45.0), 40.0);
+        assert_eq!(hits.click.unwrap().id, Id::new("small-button"));
+        let contains_pointer: Vec<Id> = hits.contains_pointer.iter().map(|w| w.id).collect();
+        assert!(contains_pointer.contains(&Id::new("small-button")));
+        assert_eq!(
+            hits.click.unwrap().interact_rect.width(),
+            10.0,
+            "The widget itself is unchanged"
+        );
+
+        // Non-interactive widgets are not grown:
+        let hits = test(pos2(30.0, 75.0), 40.0);
+        let contains_pointer: Vec<Id> = hits.contains_pointer.iter().map(|w| w.id).collect();
+        assert_eq!(contains_pointer, vec![Id::new("bg-area")]);
+    }
+
+    #[test]
+    fn hit_paths() {
+        let mut widgets = WidgetRects::default();
+        let bg = wr(
+            Id::new("bg-area"),
+            Sense::drag(),
+            Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 100.0)),
+        );
+        // A triangle in the top-left half of its rect:
+        let triangle = wr(
+            Id::new("triangle"),
+            Sense::click(),
+            Rect::from_min_size(pos2(10.0, 10.0), vec2(40.0, 40.0)),
+        );
+        widgets.insert(bg.layer_id, bg);
+        widgets.insert(triangle.layer_id, triangle);
+        widgets.set_hit_path(
+            triangle.id,
+            epaint::PathShape::convex_polygon(
+                vec![pos2(10.0, 10.0), pos2(50.0, 10.0), pos2(10.0, 50.0)],
+                epaint::Color32::WHITE,
+                epaint::Stroke::NONE,
+            ),
+        );
+        let layers = [LayerId::background()];
+        let test = |pos| hit_test(&widgets, &layers, &Default::default(), pos, 0.0, 40.0);
+
+        let hits = test(pos2(20.0, 20.0));
+        assert_eq!(hits.click.unwrap().id, Id::new("triangle"));
+
+        // Inside the rect, but outside the triangle:
+        let hits = test(pos2(45.0, 45.0));
+        assert_eq!(hits.click, None);
+        let contains_pointer: Vec<Id> = hits.contains_pointer.iter().map(|w| w.id).collect();
+        assert_eq!(contains_pointer, vec![Id::new("bg-area")]);
+    }
 }
@@ -44,7 +44,7 @@ impl Id {
     pub const NULL: Self = Self(NonZeroU64::MAX);
 
     #[inline]
-    const fn from_hash(hash: u64) -> Self {
+    pub(crate) const fn from_hash(hash: u64) -> Self {
         if let Some(nonzero) = NonZeroU64::new(hash) {
             Self(nonzero)
         } else {
@@ -106,6 +106,27 @@ impl From<String> for Id {
     }
 }
 
+/// Where an [`Id`] came from, see [`crate::Context::id_source`].
+///
+/// Only recorded in debug builds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdSource {
+    /// The [`Id`] of the [`crate::Ui`] the key was pushed onto.
+    pub parent: Id,
+
+    /// The key, formatted with [`std::fmt::Debug`].
+    pub key: String,
+
+    /// The line of code that made the [`Id`].
+    pub location: &'static std::panic::Location<'static>,
+}
+
+impl std::fmt::Display for IdSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "key {} at {}", self.key, self.location)
+    }
+}
+
 #[test]
 fn id_size() {
     assert_eq!(std::mem::size_of::<Id>(), 8);
@@ -0,0 +1,194 @@
+//! Record the [`RawInput`] given to a [`Context`], and replay it later.
+//!
+//! Since each [`RawInput`] carries its own [`RawInput::time`] and [`RawInput::predicted_dt`],
+//! replaying a recording gives the exact same animations and double-click timings,
+//! which makes it easy to reproduce a bug, or to turn it into a regression test:
+//!
+//! ```
+//! # let ctx = egui::Context::default();
+//! # let ui = |ctx: &egui::Context| {};
+//! ctx.start_recording_input();
+//! // … run your app as usual …
+//! let recording = ctx.stop_recording_input().unwrap_or_default();
+//!
+//! let ctx = egui::Context::default();
+//! let mut replayer = egui::input_recording::InputReplayer::new(recording);
+//! while let Some(input) = replayer.next_input() {
+//!     let _ = ctx.run(input, ui);
+//! }
+//! ```
+//!
+//! Only the root viewport and deferred viewports are recorded, not immediate viewports.
+
+use std::time::Duration;
+
+use crate::{Context, FullOutput, RawInput};
+
+/// The input of a sequence of frames, see [`Context::start_recording_input`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct InputRecording {
+    /// The input of each frame, in order.
+    pub frames: Vec<RawInput>,
+}
+
+impl InputRecording {
+    pub fn push(&mut self, input: RawInput) {
+        self.frames.push(input);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Seconds from the first to the last frame, if the input had timestamps.
+    pub fn duration(&self) -> Option<f64> {
+        let first = self.frames.first()?.time?;
+        let last = self.frames.last()?.time?;
+        Some(last - first)
+    }
+
+    /// Parse a recording from [RON](https://github.com/ron-rs/ron), e.g. as written by [`Self::to_ron`].
+    ///
+    /// # Errors
+    /// If the RON is malformed, or doesn't describe an [`InputRecording`].
+    #[cfg(feature = "persistence")]
+    pub fn from_ron(ron: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(ron)
+    }
+
+    /// Write the recording as [RON](https://github.com/ron-rs/ron).
+    ///
+    /// # Errors
+    /// Only if serialization itself fails, which should not happen.
+    #[cfg(feature = "persistence")]
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::to_string(self)
+    }
+
+    /// Load a recording saved with [`Self::save`].
+    ///
+    /// # Errors
+    /// If the file can't be read or parsed.
+    #[cfg(feature = "persistence")]
+    pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let ron = std::fs::read_to_string(path)?;
+        Self::from_ron(&ron)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+
+    /// Save the recording as RON to a file.
+    ///
+    /// # Errors
+    /// If the file can't be written.
+    #[cfg(feature = "persistence")]
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let ron = self
+            .to_ron()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        std::fs::write(path, ron)
+    }
+}
+
+/// Feeds the frames of an [`InputRecording`] back, one at a time.
+#[derive(Clone, Debug)]
+pub struct InputReplayer {
+    recording: InputRecording,
+    next: usize,
+}
+
+impl InputReplayer {
+    pub fn new(recording: InputRecording) -> Self {
+        Self { recording, next: 0 }
+    }
+
+    /// The input for the next frame, or `None` when the recording has been played to the end.
+    ///
+    /// Pass it to [`Context::run`].
+    pub fn next_input(&mut self) -> Option<RawInput> {
+        let input = self.recording.frames.get(self.next)?.clone();
+        self.next += 1;
+        Some(input)
+    }
+
+    /// How long to wait before the next frame, to replay in real time.
+    ///
+    /// `None` when finished, or if the recording has no timestamps.
+    pub fn delay_until_next(&self) -> Option<Duration> {
+        let prev = self.recording.frames.get(self.next.checked_sub(1)?)?.time?;
+        let next = self.recording.frames.get(self.next)?.time?;
+        Some(Duration::from_secs_f64((next - prev).max(0.0)))
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.recording.frames.len() <= self.next
+    }
+
+    /// Play the rest of the recording as fast as possible, returning the output of the last frame.
+    pub fn run_to_end(&mut self, ctx: &Context, mut run_ui: impl FnMut(&Context)) -> FullOutput {
+        let mut output = FullOutput::default();
+        while let Some(input) = self.next_input() {
+            output = ctx.run(input, &mut run_ui);
+        }
+        output
+    }
+}
+
+#[test]
+fn test_replay() {
+    use crate::{pos2, Event, PointerButton};
+
+    fn app(ctx: &Context, clicks: &mut usize) {
+        crate::CentralPanel::default().show(ctx, |ui| {
+            if ui.button("Click me").clicked() {
+                *clicks += 1;
+            }
+        });
+    }
+
+    let input = |time: f64, events: Vec<Event>| RawInput {
+        time: Some(time),
+        events,
+        ..Default::default()
+    };
+    let button_pos = pos2(30.0, 18.0);
+    let click = |pressed| Event::PointerButton {
+        pos: button_pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Default::default(),
+    };
+
+    let ctx = Context::default();
+    ctx.start_recording_input();
+    let mut clicks = 0;
+    for (i, events) in [
+        vec![],
+        vec![Event::PointerMoved(button_pos)],
+        vec![click(true)],
+        vec![click(false)],
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let _ = ctx.run(input(i as f64 * 0.1, events), |ctx| app(ctx, &mut clicks));
+    }
+    let recording = ctx.stop_recording_input().unwrap();
+    assert_eq!(clicks, 1);
+    assert_eq!(recording.frames.len(), 4);
+    assert!(!ctx.is_recording_input());
+
+    let mut replayer = InputReplayer::new(recording);
+    let _ = replayer.next_input();
+    assert_eq!(
+        replayer.delay_until_next(),
+        Some(Duration::from_secs_f64(0.1))
+    );
+
+    let ctx = Context::default();
+    let mut replayed_clicks = 0;
+    let mut replayer = InputReplayer::new(replayer.recording);
+    replayer.run_to_end(&ctx, |ctx| app(ctx, &mut replayed_clicks));
+    assert!(replayer.is_finished());
+    assert_eq!(replayed_clicks, 1);
+}
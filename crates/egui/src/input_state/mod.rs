@@ -875,6 +875,9 @@ pub struct PointerState {
     /// All button events that occurred this frame
     pub(crate) pointer_events: Vec<PointerEvent>,
 
+    /// Does the pointer come from a touch screen?
+    is_touch: bool,
+
     /// Input state management configuration.
     ///
     /// This gets copied from `egui::Options` at the start of each frame for convenience.
@@ -901,6 +904,7 @@ impl Default for PointerState {
             last_last_click_time: f64::NEG_INFINITY,
             last_move_time: f64::NEG_INFINITY,
             pointer_events: vec![],
+            is_touch: false,
             input_options: Default::default(),
         }
     }
@@ -927,6 +931,17 @@ impl PointerState {
             self.motion = Some(Vec2::ZERO);
         }
 
+        // Touches also produce pointer events, so only a pointer event without any touches is a mouse:
+        if new.events.iter().any(|e| matches!(e, Event::Touch { .. })) {
+            self.is_touch = true;
+        } else if new
+            .events
+            .iter()
+            .any(|e| matches!(e, Event::PointerMoved(_) | Event::PointerButton { .. }))
+        {
+            self.is_touch = false;
+        }
+
         let mut clear_history_after_velocity_calculation = false;
         for event in &new.events {
             match event {
@@ -1139,6 +1154,14 @@ impl PointerState {
         self.latest_pos.is_some()
     }
 
+    /// Was the latest pointer input from a touch screen, rather than a mouse?
+    ///
+    /// This stays `true` after the finger is lifted, until the mouse is used.
+    #[inline(always)]
+    pub fn is_touch(&self) -> bool {
+        self.is_touch
+    }
+
     /// Is the pointer currently still?
     /// This is smoothed so a few frames of stillness is required before this returns `true`.
     #[inline(always)]
@@ -1465,6 +1488,7 @@ impl PointerState {
             last_last_click_time,
             pointer_events,
             last_move_time,
+            is_touch,
             input_options: _,
         } = self;
 
@@ -1490,5 +1514,6 @@ impl PointerState {
         ui.label(format!("last_last_click_time: {last_last_click_time:#?}"));
         ui.label(format!("last_move_time: {last_move_time:#?}"));
         ui.label(format!("pointer_events: {pointer_events:?}"));
+        ui.label(format!("is_touch: {is_touch}"));
     }
 }
@@ -97,6 +97,7 @@ impl Widget for &epaint::stats::PaintStats {
                 clipped_primitives,
                 vertices,
                 indices,
+                num_mesh_splits,
             } = self;
 
             ui.label("Intermediate:");
@@ -122,6 +123,10 @@ impl Widget for &epaint::stats::PaintStats {
                 .on_hover_text("Number of separate clip rectangles");
             label(ui, vertices, "vertices");
             label(ui, indices, "indices").on_hover_text("Three 32-bit indices per triangles");
+            ui.label(format!("{num_mesh_splits:6} mesh splits"))
+                .on_hover_text(
+                    "Extra meshes because a mesh had more vertices than the index type allows",
+                );
             ui.add_space(10.0);
 
             // ui.label("Total:");
@@ -146,6 +151,10 @@ impl Widget for &mut epaint::TessellationOptions {
                 round_text_to_pixels,
                 round_line_segments_to_pixels,
                 round_rects_to_pixels,
+                pixel_snapping,
+                decimate_paths,
+                mesh_index_type,
+                max_vertices_per_mesh: _,
                 debug_paint_clip_rects,
                 debug_paint_text_rects,
                 debug_ignore_clip_rects,
@@ -179,6 +188,17 @@ impl Widget for &mut epaint::TessellationOptions {
                 ).on_hover_text("Only available if epaint was compiled with the rayon feature")
                 .on_disabled_hover_text("epaint was not compiled with the rayon feature");
 
+            ui.checkbox(decimate_paths, "Simplify dense paths")
+                .on_hover_text("Keep only the points that make a visible difference, when a path has many more points than the pixels it covers.");
+
+            ui.horizontal(|ui| {
+                ui.label("Mesh indices:");
+                ui.selectable_value(mesh_index_type, epaint::MeshIndexType::U16, "16-bit");
+                ui.selectable_value(mesh_index_type, epaint::MeshIndexType::U32, "32-bit");
+            })
+            .response
+            .on_hover_text("Meshes with more vertices than the index type can address are split into several.");
+
             ui.checkbox(validate_meshes, "Validate meshes").on_hover_text("Check that incoming meshes are valid, i.e. that all indices are in range, etc.");
 
             ui.collapsing("Align to pixel grid", |ui| {
@@ -190,6 +210,12 @@ impl Widget for &mut epaint::TessellationOptions {
 
                 ui.checkbox(round_rects_to_pixels, "Rectangles")
                     .on_hover_text("Makes line segments appear crisp on any display.");
+
+                ui.checkbox(&mut pixel_snapping.position, "Positions of all shapes")
+                    .on_hover_text("Rounds the points of lines, paths, circles and rectangles so that their strokes cover whole pixels.");
+
+                ui.checkbox(&mut pixel_snapping.stroke, "Stroke widths")
+                    .on_hover_text("Rounds stroke widths to a whole number of physical pixels.");
             });
 
             ui.collapsing("Debug", |ui| {
@@ -207,6 +233,35 @@ impl Widget for &mut epaint::TessellationOptions {
     }
 }
 
+impl Widget for &mut epaint::text::FontAtlasOptions {
+    fn ui(self, ui: &mut Ui) -> Response {
+        ui.vertical(|ui| {
+            let epaint::text::FontAtlasOptions {
+                max_side,
+                max_fill_ratio,
+                evict_after_passes,
+            } = self;
+
+            ui.horizontal(|ui| {
+                ui.label("Max size");
+                ui.add(crate::DragValue::new(max_side).range(1024..=16 * 1024).suffix(" px"))
+                    .on_hover_text("The largest width and height of the font atlas. It is also limited by the backend.");
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Clear when fuller than");
+                ui.add(crate::DragValue::new(max_fill_ratio).range(0.1..=1.0).speed(0.01));
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Drop unused zoom levels after");
+                ui.add(crate::DragValue::new(evict_after_passes).range(1..=10_000).suffix(" passes"));
+            });
+        })
+        .response
+    }
+}
+
 impl Widget for &memory::InteractionState {
     fn ui(self, ui: &mut Ui) -> Response {
         let memory::InteractionState {
@@ -0,0 +1,188 @@
+//! Rubber-band selection, see [`crate::Ui::lasso_select`].
+
+use crate::{epaint::PathShape, Id, Painter, Pos2, Rect, Response, Shape, Stroke, Ui};
+
+/// The shape of a lasso selection, see [`crate::Ui::lasso_select`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum LassoMode {
+    /// Drag out a rectangle. Selects everything the rectangle touches.
+    #[default]
+    Rect,
+
+    /// Draw a freehand outline. Selects everything whose center is inside it.
+    Freehand,
+}
+
+/// The region covered by a lasso.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LassoRegion {
+    Rect(Rect),
+
+    /// A closed outline.
+    Freehand(PathShape),
+}
+
+impl LassoRegion {
+    /// Does the lasso select an item covering `rect`?
+    pub fn selects(&self, rect: Rect) -> bool {
+        match self {
+            Self::Rect(region) => region.intersects(rect),
+            Self::Freehand(path) => path.contains(rect.center()),
+        }
+    }
+
+    /// The outline of the region.
+    pub fn outline(&self) -> Vec<Pos2> {
+        match self {
+            Self::Rect(rect) => vec![
+                rect.left_top(),
+                rect.right_top(),
+                rect.right_bottom(),
+                rect.left_bottom(),
+                rect.left_top(),
+            ],
+            Self::Freehand(path) => {
+                let mut points = path.points.clone();
+                points.extend(path.points.first().copied());
+                points
+            }
+        }
+    }
+}
+
+/// The result of [`crate::Ui::lasso_select`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct LassoSelection {
+    /// What the lasso covers.
+    pub region: LassoRegion,
+
+    /// The ids of the selected items, in the order they were given.
+    pub selected: Vec<Id>,
+
+    /// Did the user just release the lasso?
+    ///
+    /// While this is `false`, the selection is a live preview.
+    pub finished: bool,
+}
+
+/// The points of a lasso in progress, stored in temporary memory.
+#[derive(Clone, Default)]
+struct LassoState(Vec<Pos2>);
+
+/// Minimum distance between consecutive points of a freehand lasso.
+const MIN_POINT_DISTANCE: f32 = 2.0;
+
+pub(crate) fn lasso_select(
+    ui: &Ui,
+    response: &Response,
+    mode: LassoMode,
+    items: impl IntoIterator<Item = (Id, Rect)>,
+) -> Option<LassoSelection> {
+    let ctx = ui.ctx();
+    let state_id = response.id.with("lasso");
+
+    let pointer = response.interact_pointer_pos();
+    if response.drag_started() {
+        let from_global = ctx.layer_transform_from_global(response.layer_id);
+        let origin = ctx
+            .input(|i| i.pointer.press_origin())
+            .map(|pos| from_global.map_or(pos, |t| t * pos))
+            .or(pointer)?;
+        ctx.data_mut(|d| d.insert_temp(state_id, LassoState(vec![origin])));
+    }
+
+    let finished = response.drag_stopped();
+    if !response.dragged() && !finished {
+        return None;
+    }
+
+    let points = ctx.data_mut(|d| {
+        let state = d.get_temp_mut_or_default::<LassoState>(state_id);
+        if let Some(pointer) = pointer {
+            match mode {
+                LassoMode::Rect => {
+                    state.0.truncate(1);
+                    state.0.push(pointer);
+                }
+                LassoMode::Freehand => {
+                    if state
+                        .0
+                        .last()
+                        .map_or(true, |last| last.distance(pointer) >= MIN_POINT_DISTANCE)
+                    {
+                        state.0.push(pointer);
+                    }
+                }
+            }
+        }
+        state.0.clone()
+    });
+    if finished {
+        ctx.data_mut(|d| d.remove_temp::<LassoState>(state_id));
+    }
+
+    let region = match mode {
+        LassoMode::Rect => LassoRegion::Rect(Rect::from_points(&points)),
+        LassoMode::Freehand => LassoRegion::Freehand(PathShape::closed_line(points, Stroke::NONE)),
+    };
+    let selected = items
+        .into_iter()
+        .filter(|(_, rect)| region.selects(*rect))
+        .map(|(id, _)| id)
+        .collect();
+
+    if !finished {
+        let time = ctx.input(|i| i.time);
+        paint_marching_ants(ui.painter(), &region.outline(), ui.visuals(), time);
+        ctx.request_repaint();
+    }
+
+    Some(LassoSelection {
+        region,
+        selected,
+        finished,
+    })
+}
+
+/// Paint an animated dashed outline.
+fn paint_marching_ants(painter: &Painter, outline: &[Pos2], visuals: &crate::Visuals, time: f64) {
+    const DASH: f32 = 4.0;
+    const SPEED: f64 = 16.0; // points per second
+
+    let width = 1.0 / painter.pixels_per_point();
+    let offset = (time * SPEED).rem_euclid(2.0 * DASH as f64) as f32;
+
+    painter.add(Shape::line(
+        outline.to_vec(),
+        Stroke::new(width, visuals.extreme_bg_color),
+    ));
+    painter.extend(Shape::dashed_line_with_offset(
+        outline,
+        Stroke::new(width, visuals.strong_text_color()),
+        &[DASH],
+        &[DASH],
+        offset,
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pos2, vec2};
+
+    #[test]
+    fn test_lasso_region_selects() {
+        let rect = LassoRegion::Rect(Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 10.0)));
+        assert!(rect.selects(Rect::from_min_size(pos2(8.0, 8.0), vec2(5.0, 5.0))));
+        assert!(!rect.selects(Rect::from_min_size(pos2(11.0, 0.0), vec2(5.0, 5.0))));
+
+        // A triangle only selects items whose center is inside:
+        let triangle = LassoRegion::Freehand(PathShape::closed_line(
+            vec![pos2(0.0, 0.0), pos2(10.0, 0.0), pos2(0.0, 10.0)],
+            Stroke::NONE,
+        ));
+        assert!(triangle.selects(Rect::from_center_size(pos2(2.0, 2.0), vec2(1.0, 1.0))));
+        assert!(!triangle.selects(Rect::from_center_size(pos2(8.0, 8.0), vec2(1.0, 1.0))));
+    }
+}
@@ -1,7 +1,7 @@
 //! Handles paint layers, i.e. how things
 //! are sometimes painted behind or in front of other things.
 
-use crate::{ahash, epaint, Id, IdMap, Rect};
+use crate::{ahash, epaint, Id, IdMap, Rect, Vec2};
 use epaint::{emath::TSTransform, ClippedShape, Shape};
 
 /// Different layer categories
@@ -111,71 +111,129 @@ impl std::fmt::Debug for LayerId {
 
 /// A unique identifier of a specific [`Shape`] in a [`PaintList`].
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ShapeIdx(pub usize);
 
+/// Where to paint a shape added with [`PaintList::add_sorted`],
+/// relative to the other shapes in the same [`PaintList`].
+///
+/// At the end of the frame, the shapes of a layer are painted in the order of their sort keys.
+/// A shape added the normal way has its own index as its key, with [`Self::sub`] zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SortKey {
+    /// Paint next to the shape with this index.
+    pub idx: ShapeIdx,
+
+    /// Negative to paint behind the shape at [`Self::idx`],
+    /// positive to paint in front of it (but behind the shapes after it).
+    ///
+    /// Shapes with the same key are painted in the order they were added,
+    /// except for negative keys, where the shape added last is painted furthest back.
+    /// That way nested backgrounds, each painted behind the same index once its contents are done,
+    /// end up with the outermost one at the back.
+    pub sub: i32,
+}
+
+impl SortKey {
+    /// Paint behind the shape at `idx`, and so behind everything painted after it too.
+    ///
+    /// Use [`PaintList::next_idx`] before painting some contents
+    /// to later paint something behind all of it.
+    #[inline]
+    pub fn behind(idx: ShapeIdx) -> Self {
+        Self { idx, sub: -1 }
+    }
+
+    /// Paint in front of the shape at `idx`, but behind the shapes painted after it.
+    #[inline]
+    pub fn in_front_of(idx: ShapeIdx) -> Self {
+        Self { idx, sub: 1 }
+    }
+}
+
 /// A list of [`Shape`]s paired with a clip rectangle.
 #[derive(Clone, Default)]
-pub struct PaintList(Vec<ClippedShape>);
+pub struct PaintList {
+    shapes: Vec<ClippedShape>,
+
+    /// The index and [`SortKey`] of each shape added with [`Self::add_sorted`].
+    sort_keys: Vec<(usize, SortKey)>,
+}
 
 impl PaintList {
     #[inline(always)]
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.shapes.is_empty()
     }
 
     pub fn next_idx(&self) -> ShapeIdx {
-        ShapeIdx(self.0.len())
+        ShapeIdx(self.shapes.len())
     }
 
     /// Returns the index of the new [`Shape`] that can be used with `PaintList::set`.
     #[inline(always)]
     pub fn add(&mut self, clip_rect: Rect, shape: Shape) -> ShapeIdx {
         let idx = self.next_idx();
-        self.0.push(ClippedShape { clip_rect, shape });
+        self.shapes.push(ClippedShape { clip_rect, shape });
         idx
     }
 
     pub fn extend<I: IntoIterator<Item = Shape>>(&mut self, clip_rect: Rect, shapes: I) {
-        self.0.extend(
+        self.shapes.extend(
             shapes
                 .into_iter()
                 .map(|shape| ClippedShape { clip_rect, shape }),
         );
     }
 
+    /// Add a [`Shape`] that is painted according to `key` rather than in the order it was added.
+    ///
+    /// The shapes are sorted into place at the end of the frame.
+    /// This lets you paint a frame behind some contents after the contents have been painted,
+    /// once you know how large it needs to be:
+    /// take `let key = SortKey::behind(paint_list.next_idx());` before painting the contents,
+    /// and then `paint_list.add_sorted(key, cr, frame);` after.
+    ///
+    /// Returns the index of the new [`Shape`], which can be used with [`Self::set`] until the end of the frame.
+    pub fn add_sorted(&mut self, key: SortKey, clip_rect: Rect, shape: Shape) -> ShapeIdx {
+        let idx = self.add(clip_rect, shape);
+        self.sort_keys.push((idx.0, key));
+        idx
+    }
+
     /// Modify an existing [`Shape`].
     ///
     /// Sometimes you want to paint a frame behind some contents, but don't know how large the frame needs to be
     /// until the contents have been added, and therefor also painted to the [`PaintList`].
     ///
     /// The solution is to allocate a [`Shape`] using `let idx = paint_list.add(cr, Shape::Noop);`
-    /// and then later setting it using `paint_list.set(idx, cr, frame);`.
+    /// and then later setting it using `paint_list.set(idx, cr, frame);`,
+    /// or to use [`Self::add_sorted`].
     #[inline(always)]
     pub fn set(&mut self, idx: ShapeIdx, clip_rect: Rect, shape: Shape) {
-        if self.0.len() <= idx.0 {
+        if self.shapes.len() <= idx.0 {
             #[cfg(feature = "log")]
             log::warn!("Index {} is out of bounds for PaintList", idx.0);
             return;
         }
 
-        self.0[idx.0] = ClippedShape { clip_rect, shape };
+        self.shapes[idx.0] = ClippedShape { clip_rect, shape };
     }
 
     /// Set the given shape to be empty (a `Shape::Noop`).
     #[inline(always)]
     pub fn reset_shape(&mut self, idx: ShapeIdx) {
-        self.0[idx.0].shape = Shape::Noop;
+        self.shapes[idx.0].shape = Shape::Noop;
     }
 
     /// Mutate the shape at the given index, if any.
     pub fn mutate_shape(&mut self, idx: ShapeIdx, f: impl FnOnce(&mut ClippedShape)) {
-        self.0.get_mut(idx.0).map(f);
+        self.shapes.get_mut(idx.0).map(f);
     }
 
     /// Transform each [`Shape`] and clip rectangle by this much, in-place
     pub fn transform(&mut self, transform: TSTransform) {
-        for ClippedShape { clip_rect, shape } in &mut self.0 {
+        for ClippedShape { clip_rect, shape } in &mut self.shapes {
             *clip_rect = transform.mul_rect(*clip_rect);
             shape.transform(transform);
         }
@@ -183,15 +241,115 @@ impl PaintList {
 
     /// Transform each [`Shape`] and clip rectangle in range by this much, in-place
     pub fn transform_range(&mut self, start: ShapeIdx, end: ShapeIdx, transform: TSTransform) {
-        for ClippedShape { clip_rect, shape } in &mut self.0[start.0..end.0] {
+        for ClippedShape { clip_rect, shape } in &mut self.shapes[start.0..end.0] {
             *clip_rect = transform.mul_rect(*clip_rect);
             shape.transform(transform);
         }
     }
 
-    /// Read-only access to all held shapes.
+    /// Scale each [`Shape`] in range separately along x and y, and then move them, in-place.
+    ///
+    /// Unlike [`Self::transform_range`], the clip rectangles are left as they are,
+    /// so the shapes are still clipped to where they were painted.
+    ///
+    /// See [`Shape::transform_non_uniform`].
+    pub fn transform_range_non_uniform(
+        &mut self,
+        start: ShapeIdx,
+        end: ShapeIdx,
+        translation: Vec2,
+        scale: Vec2,
+    ) {
+        for ClippedShape { shape, .. } in &mut self.shapes[start.0..end.0] {
+            shape.transform_non_uniform(translation, scale);
+        }
+    }
+
+    /// Read-only access to all held shapes, in the order they were added.
     pub fn all_entries(&self) -> impl ExactSizeIterator<Item = &ClippedShape> {
-        self.0.iter()
+        self.shapes.iter()
+    }
+
+    /// Read-only access to all held shapes, in the order they will be painted.
+    ///
+    /// See [`Self::add_sorted`].
+    pub fn sorted_entries(&self) -> impl ExactSizeIterator<Item = &ClippedShape> {
+        let order = (!self.sort_keys.is_empty()).then(|| self.paint_order());
+        (0..self.shapes.len())
+            .map(move |i| &self.shapes[order.as_ref().map_or(i, |order| order[i])])
+    }
+
+    /// The indices of the shapes, in the order they will be painted.
+    fn paint_order(&self) -> Vec<usize> {
+        let mut keys: Vec<SortKey> = (0..self.shapes.len())
+            .map(|i| SortKey {
+                idx: ShapeIdx(i),
+                sub: 0,
+            })
+            .collect();
+        for &(i, key) in &self.sort_keys {
+            keys[i] = key;
+        }
+        let mut order: Vec<usize> = (0..self.shapes.len()).collect();
+        // Among equal keys, shapes behind something are painted last-added first:
+        order.sort_by_key(|&i| {
+            let key = keys[i];
+            let added = if key.sub < 0 {
+                -(i as isize)
+            } else {
+                i as isize
+            };
+            (key, added)
+        });
+        order
+    }
+
+    /// Sort the shapes added with [`Self::add_sorted`] into place.
+    ///
+    /// This invalidates the [`ShapeIdx`]:s of the shapes.
+    fn resolve_sort_keys(&mut self) {
+        if self.sort_keys.is_empty() {
+            return;
+        }
+        let order = self.paint_order();
+        let mut shapes: Vec<Option<ClippedShape>> = std::mem::take(&mut self.shapes)
+            .into_iter()
+            .map(Some)
+            .collect();
+        self.shapes = order.into_iter().filter_map(|i| shapes[i].take()).collect();
+        self.sort_keys.clear();
+    }
+
+    /// Move all shapes from `start` onwards so they replace the shape at `slot`,
+    /// i.e. paint them behind whatever was added after `slot`.
+    ///
+    /// If there are no shapes from `start` onwards, the shape at `slot` is kept.
+    ///
+    /// Returns how many shapes were moved.
+    pub(crate) fn move_into_slot(&mut self, start: ShapeIdx, slot: ShapeIdx) -> usize {
+        if start.0 <= slot.0 || self.shapes.len() <= start.0 {
+            return 0;
+        }
+        let shapes = self.shapes.split_off(start.0);
+        let num_shapes = shapes.len();
+        self.shapes.splice(slot.0..=slot.0, shapes);
+
+        // Keep the sort keys pointing at the same shapes:
+        let moved = |i: usize| {
+            if start.0 <= i {
+                i - start.0 + slot.0
+            } else if slot.0 < i {
+                i + num_shapes - 1
+            } else {
+                i
+            }
+        };
+        for (i, key) in &mut self.sort_keys {
+            *i = moved(*i);
+            key.idx = ShapeIdx(moved(key.idx.0));
+        }
+
+        num_shapes
     }
 }
 
@@ -217,6 +375,71 @@ impl GraphicLayers {
         self.0[layer_id.order as usize].get_mut(&layer_id.id)
     }
 
+    /// All layers with their [`PaintList`], in no particular order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (LayerId, &PaintList)> + '_ {
+        Order::ALL.into_iter().flat_map(move |order| {
+            self.0[order as usize]
+                .iter()
+                .map(move |(id, list)| (LayerId::new(order, *id), list))
+        })
+    }
+
+    /// Remove all shapes painted to the given layer so far.
+    pub(crate) fn take_layer(&mut self, layer_id: LayerId) -> Vec<ClippedShape> {
+        self.0[layer_id.order as usize]
+            .remove(&layer_id.id)
+            .map(|mut list| {
+                list.resolve_sort_keys();
+                list.shapes
+            })
+            .unwrap_or_default()
+    }
+
+    /// The layers in the order they are painted: those in `area_order` first, then the rest.
+    fn paint_order(&self, area_order: &[LayerId]) -> Vec<LayerId> {
+        let in_area_order: ahash::HashSet<LayerId> = area_order.iter().copied().collect();
+        Order::ALL
+            .iter()
+            .flat_map(|&order| {
+                let order_map = &self.0[order as usize];
+                let ordered = area_order.iter().copied().filter(move |layer_id| {
+                    layer_id.order == order && order_map.contains_key(&layer_id.id)
+                });
+                let missing_from_area_order = order_map
+                    .keys()
+                    .map(move |&id| LayerId::new(order, id))
+                    .filter(|layer_id| !in_area_order.contains(layer_id));
+                ordered.chain(missing_from_area_order)
+            })
+            .collect()
+    }
+
+    /// All shapes painted so far this pass, in global coordinates, in the order they will be painted.
+    ///
+    /// Like [`Self::drain`], but leaves the shapes in place.
+    pub fn all_shapes(
+        &self,
+        area_order: &[LayerId],
+        to_global: &ahash::HashMap<LayerId, TSTransform>,
+    ) -> Vec<ClippedShape> {
+        let mut all_shapes = vec![];
+
+        for layer_id in self.paint_order(area_order) {
+            let list = &self.0[layer_id.order as usize][&layer_id.id];
+            let to_global = to_global.get(&layer_id).copied();
+            all_shapes.extend(list.sorted_entries().map(|clipped_shape| {
+                let mut clipped_shape = clipped_shape.clone();
+                if let Some(to_global) = to_global {
+                    clipped_shape.clip_rect = to_global * clipped_shape.clip_rect;
+                    clipped_shape.shape.transform(to_global);
+                }
+                clipped_shape
+            }));
+        }
+
+        all_shapes
+    }
+
     pub fn drain(
         &mut self,
         area_order: &[LayerId],
@@ -226,44 +449,124 @@ impl GraphicLayers {
 
         let mut all_shapes: Vec<_> = Default::default();
 
-        for &order in &Order::ALL {
-            let order_map = &mut self.0[order as usize];
-
-            // If a layer is empty at the start of the frame
-            // then nobody has added to it, and it is old and defunct.
-            // Free it to save memory:
+        // If a layer is empty at the start of the frame
+        // then nobody has added to it, and it is old and defunct.
+        // Free it to save memory:
+        for order_map in &mut self.0 {
             order_map.retain(|_, list| !list.is_empty());
+        }
 
-            // First do the layers part of area_order:
-            for layer_id in area_order {
-                if layer_id.order == order {
-                    if let Some(list) = order_map.get_mut(&layer_id.id) {
-                        if let Some(to_global) = to_global.get(layer_id) {
-                            for clipped_shape in &mut list.0 {
-                                clipped_shape.clip_rect = *to_global * clipped_shape.clip_rect;
-                                clipped_shape.shape.transform(*to_global);
-                            }
-                        }
-                        all_shapes.append(&mut list.0);
-                    }
-                }
-            }
-
-            // Also draw areas that are missing in `area_order`:
-            for (id, list) in order_map {
-                let layer_id = LayerId::new(order, *id);
-
-                if let Some(to_global) = to_global.get(&layer_id) {
-                    for clipped_shape in &mut list.0 {
-                        clipped_shape.clip_rect = *to_global * clipped_shape.clip_rect;
-                        clipped_shape.shape.transform(*to_global);
-                    }
+        for layer_id in self.paint_order(area_order) {
+            let Some(list) = self.0[layer_id.order as usize].get_mut(&layer_id.id) else {
+                continue;
+            };
+            list.resolve_sort_keys();
+            if let Some(to_global) = to_global.get(&layer_id) {
+                for clipped_shape in &mut list.shapes {
+                    clipped_shape.clip_rect = *to_global * clipped_shape.clip_rect;
+                    clipped_shape.shape.transform(*to_global);
                 }
-
-                all_shapes.append(&mut list.0);
             }
+            all_shapes.append(&mut list.shapes);
         }
 
         all_shapes
     }
 }
+
+#[test]
+fn test_sort_keys() {
+    use epaint::{Color32, Pos2};
+
+    let circle = |radius: f32| Shape::circle_filled(Pos2::ZERO, radius, Color32::RED);
+    let radius = |shape: &ClippedShape| match &shape.shape {
+        Shape::Circle(circle) => circle.radius,
+        _ => panic!("Expected a circle"),
+    };
+
+    let mut list = PaintList::default();
+    list.add(Rect::EVERYTHING, circle(0.0));
+    let start = list.next_idx();
+    let first = list.add(Rect::EVERYTHING, circle(1.0));
+    list.add(Rect::EVERYTHING, circle(2.0));
+    list.add_sorted(SortKey::behind(start), Rect::EVERYTHING, circle(3.0));
+    list.add_sorted(SortKey::in_front_of(first), Rect::EVERYTHING, circle(4.0));
+
+    let sorted: Vec<f32> = list.sorted_entries().map(radius).collect();
+    assert_eq!(sorted, [0.0, 3.0, 1.0, 4.0, 2.0]);
+
+    list.resolve_sort_keys();
+    let resolved: Vec<f32> = list.all_entries().map(radius).collect();
+    assert_eq!(resolved, sorted);
+}
+
+#[test]
+fn test_sort_keys_nested_frames() {
+    use epaint::{Color32, Pos2};
+
+    let circle = |radius: f32| Shape::circle_filled(Pos2::ZERO, radius, Color32::RED);
+    let radius = |shape: &ClippedShape| match &shape.shape {
+        Shape::Circle(circle) => circle.radius,
+        _ => panic!("Expected a circle"),
+    };
+
+    // An outer frame containing an inner frame, both painting their background once their contents are done:
+    let mut list = PaintList::default();
+    let outer = SortKey::behind(list.next_idx());
+    let inner = SortKey::behind(list.next_idx());
+    list.add(Rect::EVERYTHING, circle(0.0)); // contents
+    list.add_sorted(inner, Rect::EVERYTHING, circle(1.0)); // inner background
+    list.add_sorted(outer, Rect::EVERYTHING, circle(2.0)); // outer background
+
+    let sorted: Vec<f32> = list.sorted_entries().map(radius).collect();
+    assert_eq!(
+        sorted,
+        [2.0, 1.0, 0.0],
+        "The outer background is painted at the back"
+    );
+}
+
+#[test]
+fn test_all_shapes_matches_drain() {
+    use epaint::{Color32, Pos2};
+
+    let circle = |radius: f32| Shape::circle_filled(Pos2::ZERO, radius, Color32::RED);
+    let radius = |shape: &ClippedShape| match &shape.shape {
+        Shape::Circle(circle) => circle.radius,
+        _ => panic!("Expected a circle"),
+    };
+
+    let background = LayerId::background();
+    let window = LayerId::new(Order::Middle, Id::new("window"));
+    let other_window = LayerId::new(Order::Middle, Id::new("other window"));
+    let tooltip = LayerId::new(Order::Tooltip, Id::new("tooltip"));
+
+    let mut layers = GraphicLayers::default();
+    layers.entry(tooltip).add(Rect::EVERYTHING, circle(4.0));
+    layers
+        .entry(other_window)
+        .add(Rect::EVERYTHING, circle(2.0));
+    layers.entry(window).add(Rect::EVERYTHING, circle(3.0));
+    let start = layers.entry(background).next_idx();
+    layers.entry(background).add(Rect::EVERYTHING, circle(1.0));
+    layers
+        .entry(background)
+        .add_sorted(SortKey::behind(start), Rect::EVERYTHING, circle(0.0));
+
+    // The tooltip is missing from the area order, but painted anyway:
+    let area_order = [background, other_window, window];
+    let to_global = Default::default();
+    let all: Vec<f32> = layers
+        .all_shapes(&area_order, &to_global)
+        .iter()
+        .map(radius)
+        .collect();
+    assert_eq!(all, [0.0, 1.0, 2.0, 3.0, 4.0]);
+
+    let drained: Vec<f32> = layers
+        .drain(&area_order, &to_global)
+        .iter()
+        .map(radius)
+        .collect();
+    assert_eq!(drained, all);
+}
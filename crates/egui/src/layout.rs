@@ -309,6 +309,32 @@ impl Layout {
             ..self
         }
     }
+
+    /// Mirror the layout horizontally, e.g. for a right-to-left language.
+    ///
+    /// A left-to-right layout becomes right-to-left (and vice versa),
+    /// and a left-aligned vertical layout becomes right-aligned.
+    ///
+    /// See [`crate::style::LayoutDirection`].
+    pub fn mirrored(self) -> Self {
+        let main_dir = match self.main_dir {
+            Direction::LeftToRight => Direction::RightToLeft,
+            Direction::RightToLeft => Direction::LeftToRight,
+            Direction::TopDown | Direction::BottomUp => self.main_dir,
+        };
+        if self.is_horizontal() {
+            Self {
+                main_dir,
+                main_align: self.main_align.flip(),
+                ..self
+            }
+        } else {
+            Self {
+                cross_align: self.cross_align.flip(),
+                ..self
+            }
+        }
+    }
 }
 
 /// ## Inspectors
@@ -408,35 +408,54 @@ mod animation_manager;
 pub mod cache;
 pub mod containers;
 mod context;
+mod cursor_images;
 mod data;
 pub mod debug_text;
+pub mod dimension;
 mod drag_and_drop;
+pub mod find_in_page;
+pub mod frame_profiler;
 pub(crate) mod grid;
 pub mod gui_zoom;
 mod hit_test;
 mod id;
+pub mod input_recording;
 mod input_state;
 mod interaction;
 pub mod introspection;
+pub mod lasso;
 pub mod layers;
 mod layout;
 pub mod load;
 mod memory;
 #[deprecated = "Use `egui::containers::menu` instead"]
 pub mod menu;
+pub mod native_menu;
+pub mod node_graph;
 pub mod os;
+#[cfg(debug_assertions)]
+mod paint_diff;
 mod painter;
 mod pass_state;
 pub(crate) mod placer;
+pub mod plot;
+mod promise;
 pub mod response;
+pub mod rulers;
 mod sense;
+pub mod snap;
 pub mod style;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tessellation_worker;
 pub mod text_selection;
+pub mod theme_tokens;
 mod ui;
 mod ui_builder;
 mod ui_stack;
+pub mod units;
 pub mod util;
 pub mod viewport;
+mod widget_inspector;
 mod widget_rect;
 pub mod widget_text;
 pub mod widgets;
@@ -445,6 +464,9 @@ pub mod widgets;
 #[cfg(debug_assertions)]
 mod callstack;
 
+#[cfg(feature = "style_hot_reload")]
+pub mod style_hot_reload;
+
 #[cfg(feature = "accesskit")]
 pub use accesskit;
 
@@ -465,48 +487,59 @@ pub use emath::{
 pub use epaint::{
     mutex,
     text::{FontData, FontDefinitions, FontFamily, FontId, FontTweak},
-    textures::{TextureFilter, TextureOptions, TextureWrapMode, TexturesDelta},
-    ClippedPrimitive, ColorImage, CornerRadius, FontImage, ImageData, Margin, Mesh, PaintCallback,
-    PaintCallbackInfo, Shadow, Shape, Stroke, StrokeKind, TextureHandle, TextureId,
+    textures::{TextureFilter, TextureOptions, TextureSnapshot, TextureWrapMode, TexturesDelta},
+    BackendCallback, CallbackResources, ClippedPrimitive, ColorImage, CompressedFormat,
+    CompressedImage, CornerRadius, FontImage, ImageData, LivePath, Margin, Mesh, MeshShader,
+    MeshShaderId, PaintCallback, PaintCallbackInfo, PixelSnapping, Shadow, Shape, Stroke,
+    StrokeKind, TextureHandle, TextureId, TextureUploader,
 };
 
 pub mod text {
     pub use crate::text_selection::CCursorRange;
     pub use epaint::text::{
-        cursor::CCursor, FontData, FontDefinitions, FontFamily, Fonts, Galley, LayoutJob,
-        LayoutSection, TextFormat, TextWrapping, TAB_SIZE,
+        cursor::CCursor, DecorationMetrics, DecorationOverride, FontData, FontDefinitions,
+        FontFamily, Fonts, Galley, GlyphOutline, GlyphUsage, LayoutJob, LayoutSection, TextFormat,
+        TextWrapping, UnderlineStyle, TAB_SIZE,
     };
 }
 
 pub use self::{
     containers::*,
     context::{Context, RepaintCause, RequestRepaintInfo},
+    cursor_images::CursorImage,
     data::{
         input::*,
         output::{
-            self, CursorIcon, FullOutput, OpenUrl, OutputCommand, PlatformOutput,
+            self, CursorIcon, FullOutput, NativeDragItem, OpenUrl, OutputCommand, PlatformOutput,
             UserAttentionType, WidgetInfo,
         },
-        Key, UserData,
+        ClipboardData, Key, UserData,
     },
+    dimension::{Dimension, DimensionKind},
     drag_and_drop::DragAndDrop,
     epaint::text::TextWrapMode,
     grid::Grid,
-    id::{Id, IdMap},
+    id::{Id, IdMap, IdSource},
     input_state::{InputState, MultiTouchInfo, PointerState},
-    layers::{LayerId, Order},
+    lasso::{LassoMode, LassoRegion, LassoSelection},
+    layers::{LayerId, Order, SortKey},
     layout::*,
     load::SizeHint,
-    memory::{Memory, Options, Theme, ThemePreference},
-    painter::Painter,
+    memory::{Memory, MemorySnapshot, MemorySnapshotError, Options, Theme, ThemePreference},
+    native_menu::{NativeMenuButton, NativeMenuItem, NativeMenuSupport},
+    painter::{GridStyle, PaintRegion, Painter, PointMarker, PointStyle},
     response::{InnerResponse, Response},
     sense::Sense,
-    style::{FontSelection, Spacing, Style, TextStyle, Visuals},
-    text::{Galley, TextFormat},
+    snap::{SnapGuide, SnapManager, Snapped},
+    style::{FontSelection, LayoutDirection, Spacing, Style, TextStyle, Visuals},
+    text::{Galley, TextFormat, UnderlineStyle},
+    theme_tokens::{ColorRole, ThemeTokens},
     ui::Ui,
     ui_builder::UiBuilder,
     ui_stack::*,
+    units::{NumberLocale, Unit, UnitRegistry},
     viewport::*,
+    widget_inspector::IdClash,
     widget_rect::{WidgetRect, WidgetRects},
     widget_text::{RichText, WidgetText},
     widgets::*,
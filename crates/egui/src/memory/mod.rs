@@ -10,7 +10,9 @@ use crate::{
     ViewportId, ViewportIdMap, ViewportIdSet,
 };
 
+mod snapshot;
 mod theme;
+pub use snapshot::{MemorySnapshot, MemorySnapshotError};
 pub use theme::{Theme, ThemePreference};
 
 // ----------------------------------------------------------------------------
@@ -99,6 +101,12 @@ pub struct Memory {
     #[cfg_attr(feature = "persistence", serde(skip))]
     everything_is_visible: bool,
 
+    /// The [`crate::Ui::unique_id`] of the [`crate::Ui`] that made each [`crate::Ui::make_persistent_id`].
+    ///
+    /// Kept between passes, so that it only needs updating when a new id is made.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub(crate) persistent_id_owners: crate::IdMap<Id>,
+
     /// Transforms per layer.
     ///
     /// Instead of using this directly, use:
@@ -132,6 +140,7 @@ impl Default for Memory {
             to_global: Default::default(),
             popup: Default::default(),
             everything_is_visible: Default::default(),
+            persistent_id_owners: Default::default(),
             add_fonts: Default::default(),
         };
         slf.interactions.entry(slf.viewport_id).or_default();
@@ -233,9 +242,21 @@ pub struct Options {
     #[cfg_attr(feature = "serde", serde(skip))]
     pub zoom_with_keyboard: bool,
 
+    /// If `true`, Cmd+F (Ctrl+F) opens an overlay to find text on the page,
+    /// like in a browser.
+    ///
+    /// See [`crate::find_in_page`].
+    ///
+    /// This is `false` by default.
+    pub find_in_page: bool,
+
     /// Controls the tessellator.
     pub tessellation_options: epaint::TessellationOptions,
 
+    /// How large the font atlas may grow, when it is cleared,
+    /// and when the glyphs of unused zoom levels are dropped.
+    pub font_atlas_options: epaint::text::FontAtlasOptions,
+
     /// If any widget moves or changes id, repaint everything.
     ///
     /// It is recommended you keep this OFF, as it may
@@ -260,6 +281,19 @@ pub struct Options {
     /// See [`crate::Context::request_discard`] for more.
     pub max_passes: NonZeroUsize,
 
+    /// Tessellate in the background while the next frame is being built.
+    ///
+    /// [`crate::Context::run`] then hands the shapes to a
+    /// [`crate::tessellation_worker::TessellationWorker`], and returns the previous frame,
+    /// already tessellated, in [`crate::FullOutput::tessellated`].
+    /// This puts what you see one frame behind, so it is only done while egui repaints
+    /// continuously, and only with a single viewport.
+    ///
+    /// Ignored on the web, which has no threads.
+    ///
+    /// Default is `false`.
+    pub tessellate_in_background: bool,
+
     /// This is a signal to any backend that we want the [`crate::PlatformOutput::events`] read out loud.
     ///
     /// The only change to egui is that labels can be focused by pressing tab.
@@ -271,6 +305,11 @@ pub struct Options {
     /// which is supported by `eframe`.
     pub screen_reader: bool,
 
+    /// Paint the images of [`crate::Context::set_cursor_image`] with egui, and hide the OS cursor while doing so.
+    ///
+    /// Integrations that can't set custom cursor images turn this on.
+    pub paint_custom_cursors: bool,
+
     /// If true, the most common glyphs (ASCII) are pre-rendered to the texture atlas.
     ///
     /// Only the fonts in [`Style::text_styles`] will be pre-cached.
@@ -327,10 +366,14 @@ impl Default for Options {
             system_theme: None,
             zoom_factor: 1.0,
             zoom_with_keyboard: true,
+            find_in_page: false,
             tessellation_options: Default::default(),
+            font_atlas_options: Default::default(),
             repaint_on_widget_change: false,
             max_passes: NonZeroUsize::new(2).unwrap(),
+            tessellate_in_background: false,
             screen_reader: false,
+            paint_custom_cursors: false,
             preload_font_glyphs: true,
             warn_on_id_clash: cfg!(debug_assertions),
 
@@ -385,10 +428,14 @@ impl Options {
             system_theme: _,
             zoom_factor: _, // TODO(emilk)
             zoom_with_keyboard,
+            find_in_page,
             tessellation_options,
+            font_atlas_options,
             repaint_on_widget_change,
             max_passes,
-            screen_reader: _, // needs to come from the integration
+            tessellate_in_background: _, // needs support from the integration
+            screen_reader: _,            // needs to come from the integration
+            paint_custom_cursors: _,     // needs to come from the integration
             preload_font_glyphs: _,
             warn_on_id_clash,
 
@@ -419,6 +466,8 @@ impl Options {
                     "Zoom with keyboard (Cmd +, Cmd -, Cmd 0)",
                 );
 
+                ui.checkbox(find_in_page, "Find in page (Cmd F)");
+
                 ui.checkbox(warn_on_id_clash, "Warn if two widgets have the same Id");
 
                 ui.checkbox(reduce_texture_memory, "Reduce texture memory");
@@ -443,6 +492,13 @@ impl Options {
                 ui.vertical_centered(|ui| {
                     crate::reset_button(ui, tessellation_options, "Reset paint settings");
                 });
+
+                ui.collapsing("Font atlas", |ui| {
+                    font_atlas_options.ui(ui);
+                    ui.vertical_centered(|ui| {
+                        crate::reset_button(ui, font_atlas_options, "Reset font atlas settings");
+                    });
+                });
             });
 
         CollapsingHeader::new("🖱 Input")
@@ -528,6 +584,12 @@ pub(crate) struct Focus {
     /// The top-most modal layer from the current frame.
     top_modal_layer_current_frame: Option<LayerId>,
 
+    /// The currently open modal layers, bottom to top,
+    /// together with the widget that had focus before each was opened.
+    ///
+    /// Used to restore focus once a modal closes.
+    modal_focus_stack: Vec<(LayerId, Option<Id>)>,
+
     /// A cache of widget IDs that are interested in focus with their corresponding rectangles.
     focus_widgets_cache: IdMap<Rect>,
 }
@@ -639,9 +701,52 @@ impl Focus {
             }
         }
 
+        self.update_modal_focus_stack();
+
         self.top_modal_layer = self.top_modal_layer_current_frame.take();
     }
 
+    /// Trap focus inside newly opened modals, and give it back once they close.
+    fn update_modal_focus_stack(&mut self) {
+        let new_top = self.top_modal_layer_current_frame;
+        if self.modal_focus_stack.last().map(|(layer, _)| *layer) == new_top {
+            return;
+        }
+
+        let still_open = new_top.and_then(|layer| {
+            self.modal_focus_stack
+                .iter()
+                .position(|(open_layer, _)| *open_layer == layer)
+        });
+
+        if let Some(index) = still_open {
+            // One or more modals above this one were closed.
+            let (_, restore) = self.modal_focus_stack[index + 1];
+            self.modal_focus_stack.truncate(index + 1);
+            self.restore_focus(restore);
+        } else if let Some(layer) = new_top {
+            // A new modal was opened on top: whatever had focus is now behind it,
+            // unless focus was given to a widget (inside the modal) this very frame.
+            let previous = self.id_previous_frame;
+            self.modal_focus_stack.push((layer, previous));
+            if self.focused() == previous {
+                self.focused_widget = None;
+                self.give_to_next = false;
+            }
+        } else if let Some((_, restore)) = self.modal_focus_stack.first().copied() {
+            // All modals were closed.
+            self.modal_focus_stack.clear();
+            self.restore_focus(restore);
+        }
+    }
+
+    fn restore_focus(&mut self, id: Option<Id>) {
+        if let Some(id) = id {
+            self.focused_widget = None;
+            self.id_next_frame = Some(id); // frame-delay so gained_focus works
+        }
+    }
+
     pub(crate) fn had_focus_last_frame(&self, id: Id) -> bool {
         self.id_previous_frame == Some(id)
     }
@@ -803,8 +908,14 @@ impl Memory {
             .begin_pass(new_raw_input);
     }
 
-    pub(crate) fn end_pass(&mut self, used_ids: &IdMap<Rect>) {
+    /// `is_painted` tells which layers were painted to this pass.
+    pub(crate) fn end_pass(
+        &mut self,
+        used_ids: &IdMap<Rect>,
+        is_painted: impl Fn(LayerId) -> bool,
+    ) {
         self.caches.update();
+        self.areas_mut().forget_z_of_removed_layers(is_painted);
         self.areas_mut().end_pass();
         self.focus_mut().end_pass(used_ids);
 
@@ -834,6 +945,49 @@ impl Memory {
         self.areas.entry(self.viewport_id).or_default()
     }
 
+    /// Copy the state of the given [`Id`]s, i.e. their [`Self::data`] and [`Area`](crate::containers::area::Area) positions.
+    ///
+    /// See [`crate::Context::export_memory`] for an easier way to pick the [`Id`]s.
+    pub fn export_snapshot(
+        &self,
+        app_version: u32,
+        mut keep: impl FnMut(Id) -> bool,
+    ) -> MemorySnapshot {
+        MemorySnapshot {
+            format_version: MemorySnapshot::FORMAT_VERSION,
+            app_version,
+            data: self.data.filter_ids(&mut keep),
+            areas: self
+                .areas()
+                .areas
+                .iter()
+                .filter(|(id, _)| keep(**id))
+                .map(|(id, state)| (*id, *state))
+                .collect(),
+        }
+    }
+
+    /// Restore state saved with [`Self::export_snapshot`], replacing the current state of those [`Id`]s.
+    ///
+    /// # Errors
+    /// If the snapshot was made by a newer version of egui.
+    pub fn import_snapshot(&mut self, snapshot: MemorySnapshot) -> Result<(), MemorySnapshotError> {
+        let MemorySnapshot {
+            format_version,
+            app_version: _,
+            data,
+            areas,
+        } = snapshot;
+        if MemorySnapshot::FORMAT_VERSION < format_version {
+            return Err(MemorySnapshotError::UnsupportedFormatVersion(
+                format_version,
+            ));
+        }
+        self.data.extend(data);
+        self.areas_mut().areas.extend(areas);
+        Ok(())
+    }
+
     /// Top-most layer at the given position.
     pub fn layer_id_at(&self, pos: Pos2) -> Option<LayerId> {
         self.areas()
@@ -1227,6 +1381,11 @@ pub struct Areas {
     ///
     /// The parent sublayer is moved directly above the child sublayers in the ordering.
     sublayers: ahash::HashMap<LayerId, HashSet<LayerId>>,
+
+    /// The z-index of layers within their [`Order`], see [`Self::set_z`].
+    ///
+    /// Layers not in here have a z-index of zero.
+    z_indices: ahash::HashMap<LayerId, i32>,
 }
 
 impl Areas {
@@ -1347,6 +1506,40 @@ impl Areas {
         }
     }
 
+    /// Set the z-index of a layer within its [`Order`].
+    ///
+    /// Layers with a higher z-index are always above those with a lower one in the same [`Order`],
+    /// e.g. to control the stacking of overlapping items on a canvas.
+    /// Layers with the same z-index are ordered as usual, e.g. by [`Self::move_to_top`].
+    /// The default z-index is zero.
+    ///
+    /// The z-index is remembered for as long as the layer is shown or painted to,
+    /// and takes effect at the end of the frame.
+    /// Sublayers are always directly above their parent, regardless of their own z-index.
+    pub fn set_z(&mut self, layer_id: LayerId, z: i32) {
+        if z == 0 {
+            self.z_indices.remove(&layer_id);
+        } else {
+            self.z_indices.insert(layer_id, z);
+        }
+
+        if !self.order.contains(&layer_id) {
+            self.order.push(layer_id);
+        }
+    }
+
+    /// The z-index of a layer within its [`Order`], see [`Self::set_z`].
+    pub fn z(&self, layer_id: LayerId) -> i32 {
+        self.z_indices.get(&layer_id).copied().unwrap_or(0)
+    }
+
+    /// Forget the z-index of layers that were neither shown nor painted to this pass.
+    pub(crate) fn forget_z_of_removed_layers(&mut self, is_painted: impl Fn(LayerId) -> bool) {
+        let visible_areas = &self.visible_areas_current_frame;
+        self.z_indices
+            .retain(|layer_id, _| visible_areas.contains(layer_id) || is_painted(*layer_id));
+    }
+
     pub fn top_layer_id(&self, order: Order) -> Option<LayerId> {
         self.order
             .iter()
@@ -1382,13 +1575,20 @@ impl Areas {
             order,
             wants_to_be_on_top,
             sublayers,
+            z_indices,
             ..
         } = self;
 
         std::mem::swap(visible_areas_last_frame, visible_areas_current_frame);
         visible_areas_current_frame.clear();
 
-        order.sort_by_key(|layer| (layer.order, wants_to_be_on_top.contains(layer)));
+        order.sort_by_key(|layer| {
+            (
+                layer.order,
+                z_indices.get(layer).copied().unwrap_or(0),
+                wants_to_be_on_top.contains(layer),
+            )
+        });
         wants_to_be_on_top.clear();
 
         // For all layers with sublayers, put the sublayers directly after the parent layer:
@@ -1469,3 +1669,72 @@ fn order_map_total_ordering() {
         }
     }
 }
+
+#[test]
+fn z_index_ordering() {
+    let [a, b, c] = ["a", "b", "c"].map(|name| LayerId::new(Order::Middle, Id::new(name)));
+    let tooltip = LayerId::new(Order::Tooltip, Id::new("tooltip"));
+
+    let mut areas = Areas::default();
+    for layer in [tooltip, a, b, c] {
+        areas.set_state(layer, crate::AreaState::default());
+    }
+    areas.set_z(a, 2);
+    areas.set_z(tooltip, -5);
+    areas.move_to_top(b);
+    areas.end_pass();
+    assert_eq!(areas.order(), [c, b, a, tooltip]);
+
+    // Moving to the top doesn't go above a higher z-index:
+    areas.move_to_top(c);
+    areas.end_pass();
+    assert_eq!(areas.order(), [b, c, a, tooltip]);
+
+    areas.set_z(a, 0);
+    areas.end_pass();
+    assert_eq!(areas.z(a), 0);
+    assert_eq!(areas.order(), [b, c, a, tooltip], "Keeps its place");
+    assert_eq!(areas.compare_order(c, b), std::cmp::Ordering::Greater);
+}
+
+#[test]
+fn modal_traps_and_restores_focus() {
+    let ctx = crate::Context::default();
+    let behind_id = Id::new("behind");
+    let inside_id = Id::new("inside");
+
+    let run = |show_modal: bool, request_behind: bool| {
+        let _ = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                let response = ui.interact(ui.max_rect(), behind_id, crate::Sense::click());
+                if request_behind {
+                    response.request_focus();
+                }
+            });
+            if show_modal {
+                crate::Modal::new(Id::new("modal")).show(ctx, |ui| {
+                    ui.interact(ui.max_rect(), inside_id, crate::Sense::click());
+                });
+            }
+        });
+    };
+
+    run(false, true);
+    run(false, false);
+    assert_eq!(ctx.memory(|mem| mem.focused()), Some(behind_id));
+
+    // Opening the modal takes focus away from the widget behind it…
+    run(true, false);
+    run(true, false);
+    assert_eq!(ctx.memory(|mem| mem.focused()), None);
+
+    // …which can't grab it back while the modal is open…
+    run(true, true);
+    run(true, false);
+    assert_eq!(ctx.memory(|mem| mem.focused()), None);
+
+    // …but gets it back once the modal is closed.
+    run(false, false);
+    run(false, false);
+    assert_eq!(ctx.memory(|mem| mem.focused()), Some(behind_id));
+}
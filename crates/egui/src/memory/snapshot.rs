@@ -0,0 +1,66 @@
+use crate::{area::AreaState, util::id_type_map::SerializableAny, util::IdTypeMap, Id};
+
+/// The persisted UI state of a part of the UI, e.g. of one document.
+///
+/// Create one with [`crate::Context::export_memory`] and restore it with [`crate::Context::import_memory`].
+/// With the `persistence` feature it can be serialized, e.g. next to the document it belongs to.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+pub struct MemorySnapshot {
+    /// The version of the snapshot format itself, see [`Self::FORMAT_VERSION`].
+    pub format_version: u32,
+
+    /// A version of your choosing, so you can tell apart snapshots of older versions of your app,
+    /// and [`Self::migrate`] them.
+    pub app_version: u32,
+
+    /// The values of [`crate::Memory::data`], e.g. the state of collapsing headers and scroll areas.
+    pub data: IdTypeMap,
+
+    /// The positions and sizes of [`crate::Area`]s and [`crate::Window`]s.
+    pub areas: Vec<(Id, AreaState)>,
+}
+
+impl MemorySnapshot {
+    /// The current version of the snapshot format.
+    ///
+    /// Importing a snapshot with a newer format fails with [`MemorySnapshotError::UnsupportedFormatVersion`].
+    pub const FORMAT_VERSION: u32 = 1;
+
+    /// Replace every value of type `Old` with the result of `migrate`.
+    ///
+    /// Use this when the type of some stored state has changed between versions of your app.
+    pub fn migrate<Old: SerializableAny, New: SerializableAny>(
+        &mut self,
+        migrate: impl Fn(Old) -> New,
+    ) {
+        let ids: Vec<Id> = self.data.ids().collect();
+        for id in ids {
+            if let Some(old) = self.data.get_persisted::<Old>(id) {
+                self.data.remove::<Old>(id);
+                self.data.insert_persisted(id, migrate(old));
+            }
+        }
+    }
+}
+
+/// Why a [`MemorySnapshot`] couldn't be imported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemorySnapshotError {
+    /// The snapshot was made with a newer version of egui.
+    UnsupportedFormatVersion(u32),
+}
+
+impl std::fmt::Display for MemorySnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedFormatVersion(version) => write!(
+                f,
+                "Unsupported memory snapshot format version {version}, expected at most {}",
+                MemorySnapshot::FORMAT_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MemorySnapshotError {}
@@ -0,0 +1,610 @@
+//! Menus described as data, so that the integration can show them as native OS menus.
+//!
+//! If the integration can't, egui shows them itself, see [`show_items`].
+
+use std::sync::Arc;
+
+use crate::{
+    Button, Context, Event, IconData, Id, KeyboardShortcut, PointerButton, Ui, ViewportCommand,
+    ViewportId,
+};
+
+/// Which kinds of native menus the integration can show.
+///
+/// Set by the integration in [`crate::RawInput::native_menus`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct NativeMenuSupport {
+    /// Context menus, see [`crate::OutputCommand::ShowContextMenu`].
+    pub context_menu: bool,
+
+    /// The menu bar of the window (or the global menu bar on macOS),
+    /// see [`crate::OutputCommand::SetMenuBar`].
+    pub menu_bar: bool,
+
+    /// An icon in the system tray, see [`crate::OutputCommand::SetTrayIcon`].
+    pub tray: bool,
+}
+
+/// A clickable entry of a [`NativeMenuItem`] list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct NativeMenuButton {
+    /// Sent back in [`Event::NativeMenuItemClicked`] when the button is clicked.
+    pub id: Id,
+
+    pub text: String,
+
+    /// Shown next to the text. Native menus may also handle the shortcut themselves.
+    pub shortcut: Option<KeyboardShortcut>,
+
+    pub enabled: bool,
+
+    /// `Some` for buttons with a check mark.
+    pub checked: Option<bool>,
+}
+
+impl NativeMenuButton {
+    pub fn new(id: impl Into<Id>, text: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            text: text.into(),
+            shortcut: None,
+            enabled: true,
+            checked: None,
+        }
+    }
+
+    #[inline]
+    pub fn shortcut(mut self, shortcut: KeyboardShortcut) -> Self {
+        self.shortcut = Some(shortcut);
+        self
+    }
+
+    #[inline]
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Show a check mark if `checked` is true.
+    #[inline]
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+}
+
+/// An entry in a native menu.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum NativeMenuItem {
+    Button(NativeMenuButton),
+
+    Submenu { text: String, items: Vec<Self> },
+
+    Separator,
+}
+
+impl NativeMenuItem {
+    /// Shorthand for a [`NativeMenuButton`] without shortcut or check mark.
+    pub fn button(id: impl Into<Id>, text: impl Into<String>) -> Self {
+        Self::Button(NativeMenuButton::new(id, text))
+    }
+
+    pub fn submenu(text: impl Into<String>, items: Vec<Self>) -> Self {
+        Self::Submenu {
+            text: text.into(),
+            items,
+        }
+    }
+
+    /// Call `visit` on all buttons, including those in submenus.
+    pub fn visit_buttons(items: &[Self], visit: &mut impl FnMut(&NativeMenuButton)) {
+        for item in items {
+            match item {
+                Self::Button(button) => visit(button),
+                Self::Submenu { items, .. } => Self::visit_buttons(items, visit),
+                Self::Separator => {}
+            }
+        }
+    }
+}
+
+impl From<NativeMenuButton> for NativeMenuItem {
+    #[inline]
+    fn from(button: NativeMenuButton) -> Self {
+        Self::Button(button)
+    }
+}
+
+/// Show the items with egui, inside a menu.
+///
+/// Returns the id of the clicked button, if any.
+/// This is what is shown when the integration can't show a native menu.
+pub fn show_items(ui: &mut Ui, items: &[NativeMenuItem]) -> Option<Id> {
+    let mut clicked = None;
+    for item in items {
+        match item {
+            NativeMenuItem::Button(button) => {
+                let mut widget = Button::new(&button.text);
+                if let Some(shortcut) = &button.shortcut {
+                    widget = widget.shortcut_text(ui.ctx().format_shortcut(shortcut));
+                }
+                if let Some(checked) = button.checked {
+                    widget = widget.selected(checked);
+                }
+                if ui.add_enabled(button.enabled, widget).clicked() {
+                    clicked = Some(button.id);
+                }
+            }
+            NativeMenuItem::Submenu { text, items } => {
+                if let Some(Some(id)) = ui.menu_button(text, |ui| show_items(ui, items)).inner {
+                    clicked = Some(id);
+                }
+            }
+            NativeMenuItem::Separator => {
+                ui.separator();
+            }
+        }
+    }
+    clicked
+}
+
+/// Show a menu bar, natively if the integration supports it
+/// (see [`NativeMenuSupport::menu_bar`]), otherwise as an egui [`crate::containers::menu::Bar`].
+///
+/// The top-level items are usually submenus, like "File" and "Edit".
+/// Call this every frame. The native menu bar is only updated when the items change.
+///
+/// When shown natively nothing is added to the `ui`, so you may want to only show
+/// the surrounding panel when [`is_menu_bar_native`] is false.
+///
+/// Returns the id of the clicked button, if any.
+pub fn menu_bar(ui: &mut Ui, items: &[NativeMenuItem]) -> Option<Id> {
+    let ctx = ui.ctx().clone();
+    if !is_menu_bar_native(&ctx) {
+        return crate::containers::menu::Bar::new()
+            .ui(ui, |ui| show_items(ui, items))
+            .inner;
+    }
+
+    let state_id = Id::new("native_menu_bar").with(ctx.viewport_id());
+    let changed = ctx.data(|d| {
+        d.get_temp::<Vec<NativeMenuItem>>(state_id)
+            .map_or(true, |previous| previous != items)
+    });
+    if changed {
+        ctx.data_mut(|d| d.insert_temp(state_id, items.to_vec()));
+        ctx.send_cmd(crate::OutputCommand::SetMenuBar(items.to_vec()));
+    }
+
+    let mut ids = Vec::new();
+    NativeMenuItem::visit_buttons(items, &mut |button| ids.push(button.id));
+    clicked_item(&ctx, &ids)
+}
+
+/// Does the integration show [`menu_bar`] natively?
+pub fn is_menu_bar_native(ctx: &Context) -> bool {
+    ctx.input(|i| i.raw.native_menus.menu_bar)
+}
+
+/// An icon in the system tray (notification area), with a menu.
+///
+/// Only shown if the integration supports it (see [`NativeMenuSupport::tray`]),
+/// as egui has nowhere to draw it otherwise.
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// # let icon = std::sync::Arc::new(egui::IconData::default());
+/// use egui::{native_menu::TrayIcon, NativeMenuItem};
+///
+/// let response = TrayIcon::new(icon)
+///     .tooltip("My app")
+///     .menu(vec![NativeMenuItem::button("quit", "Quit")])
+///     .hide_on_close(true)
+///     .show(ctx);
+/// if response.menu_item == Some(egui::Id::new("quit")) {
+///     ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+/// }
+/// # });
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TrayIcon {
+    pub icon: Arc<IconData>,
+
+    /// Shown when hovering the icon.
+    pub tooltip: String,
+
+    /// Shown when right-clicking the icon (or clicking it, on macOS).
+    pub menu: Vec<NativeMenuItem>,
+
+    /// Hide the root window instead of closing it, and show it again when the icon is clicked.
+    ///
+    /// The integration should show the window on a click too,
+    /// as hidden windows don't run frames on every platform.
+    pub hide_on_close: bool,
+}
+
+/// What the user did with a [`TrayIcon`] this frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TrayResponse {
+    /// The icon itself was clicked with this button.
+    pub clicked: Option<PointerButton>,
+
+    /// This button of the menu was clicked.
+    pub menu_item: Option<Id>,
+}
+
+impl TrayIcon {
+    pub fn new(icon: Arc<IconData>) -> Self {
+        Self {
+            icon,
+            tooltip: String::new(),
+            menu: Vec::new(),
+            hide_on_close: false,
+        }
+    }
+
+    #[inline]
+    pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = tooltip.into();
+        self
+    }
+
+    #[inline]
+    pub fn menu(mut self, menu: Vec<NativeMenuItem>) -> Self {
+        self.menu = menu;
+        self
+    }
+
+    /// Hide the root window instead of closing it, and show it again when the icon is clicked.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn hide_on_close(mut self, hide_on_close: bool) -> Self {
+        self.hide_on_close = hide_on_close;
+        self
+    }
+
+    /// Show the icon, or update it if it changed.
+    ///
+    /// Call this every frame of the root viewport, which is where the clicks are reported.
+    pub fn show(self, ctx: &Context) -> TrayResponse {
+        if !is_tray_supported(ctx) {
+            return TrayResponse::default();
+        }
+
+        let state_id = tray_icon_state_id();
+        let changed = ctx.data(|d| {
+            d.get_temp::<Self>(state_id)
+                .map_or(true, |previous| previous != self)
+        });
+        if changed {
+            ctx.send_cmd(crate::OutputCommand::SetTrayIcon(Some(self.clone())));
+            ctx.data_mut(|d| d.insert_temp(state_id, self.clone()));
+        }
+
+        if self.hide_on_close && ctx.input(|i| i.viewport().close_requested()) {
+            ctx.send_viewport_cmd(ViewportCommand::CancelClose);
+            hide_to_tray(ctx);
+        }
+
+        let clicked = ctx.input(|i| {
+            i.events.iter().find_map(|event| match event {
+                Event::TrayIconClicked(button) => Some(*button),
+                _ => None,
+            })
+        });
+        if self.hide_on_close && clicked == Some(PointerButton::Primary) {
+            restore_from_tray(ctx);
+        }
+
+        let mut ids = Vec::new();
+        NativeMenuItem::visit_buttons(&self.menu, &mut |button| ids.push(button.id));
+        TrayResponse {
+            clicked,
+            menu_item: clicked_item(ctx, &ids),
+        }
+    }
+
+    /// Remove the icon from the tray.
+    pub fn remove(ctx: &Context) {
+        let state_id = tray_icon_state_id();
+        let was_shown = ctx.data_mut(|d| {
+            let was_shown = d.get_temp::<Self>(state_id).is_some();
+            d.remove::<Self>(state_id);
+            was_shown
+        });
+        if was_shown {
+            ctx.send_cmd(crate::OutputCommand::SetTrayIcon(None));
+        }
+    }
+}
+
+/// Where the shown [`TrayIcon`] is remembered.
+fn tray_icon_state_id() -> Id {
+    Id::new("native_tray_icon")
+}
+
+/// Can the integration show a [`TrayIcon`]?
+pub fn is_tray_supported(ctx: &Context) -> bool {
+    ctx.input(|i| i.raw.native_menus.tray)
+}
+
+/// Hide the root window, e.g. when it is closed while there is a [`TrayIcon`].
+///
+/// If no tray icon is shown, the window is minimized instead,
+/// as the user would have no way to get it back.
+pub fn hide_to_tray(ctx: &Context) {
+    let has_tray_icon = is_tray_supported(ctx)
+        && ctx.data(|d| d.get_temp::<TrayIcon>(tray_icon_state_id()).is_some());
+    let command = if has_tray_icon {
+        ViewportCommand::Visible(false)
+    } else {
+        ViewportCommand::Minimized(true)
+    };
+    ctx.send_viewport_cmd_to(ViewportId::ROOT, command);
+}
+
+/// Show the root window again after [`hide_to_tray`], and give it focus.
+pub fn restore_from_tray(ctx: &Context) {
+    ctx.send_viewport_cmd_to(ViewportId::ROOT, ViewportCommand::Visible(true));
+    ctx.send_viewport_cmd_to(ViewportId::ROOT, ViewportCommand::Minimized(false));
+    ctx.send_viewport_cmd_to(ViewportId::ROOT, ViewportCommand::Focus);
+}
+
+/// The id of a button among `items` that was clicked in a native menu this frame, if any.
+pub(crate) fn clicked_item(ctx: &Context, items: &[Id]) -> Option<Id> {
+    ctx.input(|i| {
+        i.events.iter().find_map(|event| match event {
+            Event::NativeMenuItemClicked(id) if items.contains(id) => Some(*id),
+            _ => None,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pos2, vec2, CentralPanel, Modifiers, OutputCommand, PointerButton, RawInput, Rect,
+    };
+
+    #[test]
+    fn test_native_context_menu_round_trip() {
+        let ctx = Context::default();
+        let run = |events: Vec<Event>| {
+            let input = RawInput {
+                screen_rect: Some(Rect::from_min_size(pos2(0.0, 0.0), vec2(200.0, 200.0))),
+                native_menus: NativeMenuSupport {
+                    context_menu: true,
+                    ..Default::default()
+                },
+                events,
+                ..Default::default()
+            };
+            let mut clicked = None;
+            let output = ctx.run(input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    let response =
+                        ui.interact(ui.max_rect(), Id::new("target"), crate::Sense::click());
+                    clicked = response.native_context_menu(|| {
+                        vec![
+                            NativeMenuItem::button("copy", "Copy"),
+                            NativeMenuItem::submenu(
+                                "More",
+                                vec![NativeMenuItem::button("delete", "Delete")],
+                            ),
+                        ]
+                    });
+                });
+            });
+            (clicked, output.platform_output.commands)
+        };
+
+        let button = |pressed| Event::PointerButton {
+            pos: pos2(50.0, 50.0),
+            button: PointerButton::Secondary,
+            pressed,
+            modifiers: Modifiers::NONE,
+        };
+        run(vec![Event::PointerMoved(pos2(50.0, 50.0))]);
+        run(vec![button(true)]);
+        let (clicked, commands) = run(vec![button(false)]);
+        assert_eq!(clicked, None);
+        assert!(commands
+            .iter()
+            .any(|command| matches!(command, OutputCommand::ShowContextMenu { items, .. } if items.len() == 2)));
+
+        let (clicked, _) = run(vec![Event::NativeMenuItemClicked(Id::new("delete"))]);
+        assert_eq!(clicked, Some(Id::new("delete")));
+    }
+
+    #[test]
+    fn test_native_context_menu_is_forgotten_when_dismissed() {
+        let ctx = Context::default();
+        let run = |events: Vec<Event>, discard: bool| {
+            let input = RawInput {
+                screen_rect: Some(Rect::from_min_size(pos2(0.0, 0.0), vec2(200.0, 200.0))),
+                native_menus: NativeMenuSupport {
+                    context_menu: true,
+                    ..Default::default()
+                },
+                events,
+                ..Default::default()
+            };
+            let mut clicked = None;
+            let _ = ctx.run(input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    let response =
+                        ui.interact(ui.max_rect(), Id::new("target"), crate::Sense::click());
+                    clicked = response
+                        .native_context_menu(|| vec![NativeMenuItem::button("copy", "Copy")]);
+                });
+                if discard {
+                    ctx.request_discard("test");
+                }
+            });
+            clicked
+        };
+        let is_open = || {
+            ctx.data(|d| {
+                d.get_temp::<crate::response::OpenNativeContextMenu>(
+                    Id::new("target").with("native_context_menu"),
+                )
+                .is_some()
+            })
+        };
+        let right_click = || {
+            let button = |pressed| Event::PointerButton {
+                pos: pos2(50.0, 50.0),
+                button: PointerButton::Secondary,
+                pressed,
+                modifiers: Modifiers::NONE,
+            };
+            vec![button(true), button(false)]
+        };
+        let copy = || vec![Event::NativeMenuItemClicked(Id::new("copy"))];
+
+        run(vec![Event::PointerMoved(pos2(50.0, 50.0))], false);
+
+        // Dismissed without a click:
+        run(right_click(), false);
+        assert!(is_open());
+        assert_eq!(run(vec![], false), None);
+        assert!(!is_open());
+
+        // A later click with the same id doesn't count, as the menu is closed:
+        assert_eq!(run(copy(), false), None);
+
+        // The click still arrives if the frame the menu was opened in had several passes:
+        run(right_click(), true);
+        assert!(is_open());
+        assert_eq!(run(copy(), false), Some(Id::new("copy")));
+        assert!(!is_open());
+    }
+
+    #[test]
+    fn test_native_menu_bar_is_only_sent_when_changed() {
+        let ctx = Context::default();
+        let run = |label: &'static str| {
+            let input = RawInput {
+                native_menus: NativeMenuSupport {
+                    menu_bar: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let output = ctx.run(input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    let items = [NativeMenuItem::submenu(
+                        "File",
+                        vec![NativeMenuItem::button("quit", label)],
+                    )];
+                    menu_bar(ui, &items);
+                });
+            });
+            output
+                .platform_output
+                .commands
+                .iter()
+                .filter(|command| matches!(command, OutputCommand::SetMenuBar(_)))
+                .count()
+        };
+
+        assert_eq!(run("Quit"), 1);
+        assert_eq!(run("Quit"), 0);
+        assert_eq!(run("Exit"), 1);
+    }
+
+    #[test]
+    fn test_hide_to_tray_and_back() {
+        let ctx = Context::default();
+        let run = |tray: bool, close: bool, events: Vec<Event>| {
+            let mut input = RawInput {
+                native_menus: NativeMenuSupport {
+                    tray: true,
+                    ..Default::default()
+                },
+                events,
+                ..Default::default()
+            };
+            if close {
+                input
+                    .viewports
+                    .entry(ViewportId::ROOT)
+                    .or_default()
+                    .events
+                    .push(crate::ViewportEvent::Close);
+            }
+            let output = ctx.run(input, |ctx| {
+                if tray {
+                    TrayIcon::new(Arc::new(IconData::default()))
+                        .hide_on_close(true)
+                        .show(ctx);
+                } else {
+                    TrayIcon::remove(ctx);
+                    if close {
+                        hide_to_tray(ctx);
+                    }
+                }
+            });
+            output.viewport_output[&ViewportId::ROOT].commands.clone()
+        };
+
+        let commands = run(true, true, vec![]);
+        assert!(commands.contains(&ViewportCommand::CancelClose));
+        assert!(commands.contains(&ViewportCommand::Visible(false)));
+
+        let commands = run(
+            true,
+            false,
+            vec![Event::TrayIconClicked(PointerButton::Primary)],
+        );
+        assert!(commands.contains(&ViewportCommand::Visible(true)));
+        assert!(commands.contains(&ViewportCommand::Focus));
+
+        // Without a tray icon the window couldn't be shown again:
+        let commands = run(false, true, vec![]);
+        assert!(commands.contains(&ViewportCommand::Minimized(true)));
+        assert!(!commands.contains(&ViewportCommand::Visible(false)));
+    }
+
+    #[test]
+    fn test_native_menu_bar_click() {
+        let ctx = Context::default();
+        let run = |events: Vec<Event>| {
+            let input = RawInput {
+                native_menus: NativeMenuSupport {
+                    menu_bar: true,
+                    ..Default::default()
+                },
+                events,
+                ..Default::default()
+            };
+            let mut clicked = None;
+            let _ = ctx.run(input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    let items = [NativeMenuItem::submenu(
+                        "File",
+                        vec![NativeMenuItem::button("quit", "Quit")],
+                    )];
+                    clicked = menu_bar(ui, &items);
+                });
+            });
+            clicked
+        };
+
+        assert_eq!(run(vec![]), None);
+        assert_eq!(
+            run(vec![Event::NativeMenuItemClicked(Id::new("quit"))]),
+            Some(Id::new("quit"))
+        );
+        assert_eq!(
+            run(vec![Event::NativeMenuItemClicked(Id::new("other"))]),
+            None
+        );
+    }
+}
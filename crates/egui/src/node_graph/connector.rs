@@ -0,0 +1,215 @@
+use emath::{pos2, Pos2, Vec2};
+use epaint::{Color32, CubicBezierShape, Shape, Stroke};
+
+/// How a [`ConnectorShape`] is routed between its ends.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ConnectorKind {
+    /// A smooth curve leaving and entering the ends in their directions.
+    #[default]
+    Bezier,
+
+    /// Horizontal and vertical segments only, like a circuit diagram.
+    Orthogonal,
+}
+
+/// A wire between two ports of a node graph, or anything else that has a direction at each end.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::node_graph::{ConnectorKind, ConnectorShape};
+///
+/// let wire = ConnectorShape::new(
+///     egui::pos2(10.0, 10.0),
+///     egui::pos2(200.0, 80.0),
+///     ConnectorKind::Bezier,
+///     egui::Stroke::new(2.0, egui::Color32::GRAY),
+/// );
+/// let hovered = ui
+///     .ctx()
+///     .pointer_hover_pos()
+///     .is_some_and(|pos| wire.hit_test(pos, 4.0));
+/// ui.painter().add(wire);
+/// # });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConnectorShape {
+    pub from: Pos2,
+
+    /// The direction the wire leaves `from` in.
+    pub from_dir: Vec2,
+
+    pub to: Pos2,
+
+    /// The direction the wire leaves `to` in, i.e. the opposite of the direction it arrives in.
+    pub to_dir: Vec2,
+
+    pub kind: ConnectorKind,
+
+    pub stroke: Stroke,
+}
+
+impl ConnectorShape {
+    /// How far an orthogonal wire goes straight out of its ends before turning.
+    const STUB_LENGTH: f32 = 16.0;
+
+    /// A wire from an output on the right side of something to an input on the left side of something else.
+    pub fn new(from: Pos2, to: Pos2, kind: ConnectorKind, stroke: impl Into<Stroke>) -> Self {
+        Self {
+            from,
+            from_dir: Vec2::X,
+            to,
+            to_dir: -Vec2::X,
+            kind,
+            stroke: stroke.into(),
+        }
+    }
+
+    /// The directions the wire leaves its ends in, e.g. [`Vec2::Y`] for a port at the bottom.
+    #[inline]
+    pub fn directions(mut self, from_dir: Vec2, to_dir: Vec2) -> Self {
+        self.from_dir = from_dir;
+        self.to_dir = to_dir;
+        self
+    }
+
+    /// The control points of the curve, for [`ConnectorKind::Bezier`].
+    fn bezier(&self) -> CubicBezierShape {
+        let offset = (0.5 * self.from.distance(self.to)).clamp(24.0, 160.0);
+        CubicBezierShape::from_points_stroke(
+            [
+                self.from,
+                self.from + offset * self.from_dir.normalized(),
+                self.to + offset * self.to_dir.normalized(),
+                self.to,
+            ],
+            false,
+            Color32::TRANSPARENT,
+            self.stroke,
+        )
+    }
+
+    /// The corners of the wire, for [`ConnectorKind::Orthogonal`].
+    fn orthogonal(&self) -> Vec<Pos2> {
+        // Route as if the wire leaves horizontally, transposing vertical wires:
+        let horizontal = self.from_dir.x.abs() >= self.from_dir.y.abs();
+        let transpose = |p: Pos2| if horizontal { p } else { pos2(p.y, p.x) };
+        let along = |dir: Vec2| if horizontal { dir.x } else { dir.y };
+
+        let (from, to) = (transpose(self.from), transpose(self.to));
+        let (from_sign, to_sign) = (along(self.from_dir).signum(), along(self.to_dir).signum());
+        let from_x = from.x + from_sign * Self::STUB_LENGTH;
+        let to_x = to.x + to_sign * Self::STUB_LENGTH;
+
+        let points = if from_sign == to_sign {
+            // Both ends face the same way: go out past the one furthest out, then back.
+            let x = if 0.0 < from_sign {
+                from_x.max(to_x)
+            } else {
+                from_x.min(to_x)
+            };
+            vec![from, pos2(x, from.y), pos2(x, to.y), to]
+        } else if 0.0 <= (to_x - from_x) * from_sign {
+            // The ends face each other: turn once, halfway.
+            let x = 0.5 * (from_x + to_x);
+            vec![from, pos2(x, from.y), pos2(x, to.y), to]
+        } else {
+            // The ends face away from each other: loop back between them.
+            let y = 0.5 * (from.y + to.y);
+            vec![
+                from,
+                pos2(from_x, from.y),
+                pos2(from_x, y),
+                pos2(to_x, y),
+                pos2(to_x, to.y),
+                to,
+            ]
+        };
+        points.into_iter().map(transpose).collect()
+    }
+
+    /// The wire as a line, flattening curves to within `tolerance` points.
+    pub fn points(&self, tolerance: f32) -> Vec<Pos2> {
+        match self.kind {
+            ConnectorKind::Bezier => self.bezier().flatten(Some(tolerance)),
+            ConnectorKind::Orthogonal => self.orthogonal(),
+        }
+    }
+
+    /// The distance from `pos` to the middle of the wire.
+    pub fn distance_to(&self, pos: Pos2) -> f32 {
+        self.points(0.5)
+            .windows(2)
+            .map(|segment| distance_to_segment(pos, segment[0], segment[1]))
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    /// Is `pos` on the wire, or within `tolerance` points of its edge?
+    pub fn hit_test(&self, pos: Pos2, tolerance: f32) -> bool {
+        self.distance_to(pos) <= 0.5 * self.stroke.width + tolerance
+    }
+}
+
+impl From<ConnectorShape> for Shape {
+    fn from(connector: ConnectorShape) -> Self {
+        match connector.kind {
+            ConnectorKind::Bezier => connector.bezier().into(),
+            ConnectorKind::Orthogonal => Self::line(connector.orthogonal(), connector.stroke),
+        }
+    }
+}
+
+fn distance_to_segment(pos: Pos2, a: Pos2, b: Pos2) -> f32 {
+    let ab = b - a;
+    let t = if ab.length_sq() > 0.0 {
+        ((pos - a).dot(ab) / ab.length_sq()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    pos.distance(a + t * ab)
+}
+
+#[test]
+fn test_orthogonal_routing() {
+    let stroke = Stroke::new(1.0, Color32::WHITE);
+    let forward = ConnectorShape::new(
+        pos2(0.0, 0.0),
+        pos2(100.0, 50.0),
+        ConnectorKind::Orthogonal,
+        stroke,
+    );
+    assert_eq!(
+        forward.points(1.0),
+        vec![
+            pos2(0.0, 0.0),
+            pos2(50.0, 0.0),
+            pos2(50.0, 50.0),
+            pos2(100.0, 50.0)
+        ]
+    );
+    assert!(forward.hit_test(pos2(52.0, 25.0), 2.0));
+    assert!(!forward.hit_test(pos2(25.0, 25.0), 2.0));
+
+    // An input to the left of the output has to loop back:
+    let backward = ConnectorShape {
+        to: pos2(-100.0, 50.0),
+        ..forward
+    };
+    let points = backward.points(1.0);
+    assert_eq!(points.len(), 6);
+    assert_eq!(points[2], pos2(16.0, 25.0));
+    assert_eq!(points[3], pos2(-116.0, 25.0));
+}
+
+#[test]
+fn test_bezier_hit_test() {
+    let wire = ConnectorShape::new(
+        pos2(0.0, 0.0),
+        pos2(100.0, 100.0),
+        ConnectorKind::Bezier,
+        Stroke::new(2.0, Color32::WHITE),
+    );
+    // Symmetric, so it goes through the middle:
+    assert!(wire.distance_to(pos2(50.0, 50.0)) < 0.5);
+    assert!(!wire.hit_test(pos2(100.0, 0.0), 4.0));
+}
@@ -0,0 +1,508 @@
+//! Building blocks for node editors: nodes that can be selected and moved,
+//! ports that can be connected by dragging, and wires between them.
+//!
+//! The graph itself (which nodes there are, and how they are connected) is yours to store;
+//! egui only remembers the selection and any ongoing drag.
+//!
+//! ```
+//! # egui::__run_test_ui(|ui| {
+//! use egui::node_graph::{Connection, NodeGraph, PortId};
+//!
+//! struct Node {
+//!     id: egui::Id,
+//!     pos: egui::Pos2,
+//! }
+//!
+//! # let mut nodes = vec![
+//! #     Node { id: egui::Id::new(1), pos: egui::pos2(10.0, 10.0) },
+//! #     Node { id: egui::Id::new(2), pos: egui::pos2(200.0, 60.0) },
+//! # ];
+//! # let mut connections: Vec<Connection> = vec![];
+//! let response = NodeGraph::new("graph").show(ui, |ui, graph| {
+//!     for node in &mut nodes {
+//!         graph.node(ui, node.id, &mut node.pos, |ui, graph| {
+//!             ui.horizontal(|ui| {
+//!                 graph.port(ui, PortId::input(node.id, 0));
+//!                 ui.label("in");
+//!                 ui.label("out");
+//!                 graph.port(ui, PortId::output(node.id, 0));
+//!             });
+//!         });
+//!     }
+//!
+//!     // Wires go after the nodes, but are painted below them:
+//!     connections.retain(|&connection| !graph.wire(connection).secondary_clicked);
+//! });
+//!
+//! if let Some(connection) = response.new_connection {
+//!     connections.push(connection);
+//! }
+//! # });
+//! ```
+
+mod connector;
+
+pub use self::connector::{ConnectorKind, ConnectorShape};
+
+use emath::{Pos2, Rect, Vec2};
+use epaint::{Shape, Stroke};
+
+use crate::{id::IdSet, layers::ShapeIdx, Frame, Id, Painter, Response, Sense, Ui, UiBuilder};
+
+/// Whether a port takes values into a node, or gives them out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PortKind {
+    /// Wires arrive from the left.
+    Input,
+
+    /// Wires leave to the right.
+    Output,
+}
+
+/// Identifies a port: the node it is on, whether it is an input or an output, and which one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PortId {
+    pub node: Id,
+    pub kind: PortKind,
+    pub index: usize,
+}
+
+impl PortId {
+    pub fn input(node: Id, index: usize) -> Self {
+        Self {
+            node,
+            kind: PortKind::Input,
+            index,
+        }
+    }
+
+    pub fn output(node: Id, index: usize) -> Self {
+        Self {
+            node,
+            kind: PortKind::Output,
+            index,
+        }
+    }
+
+    /// The direction wires leave the port in.
+    fn direction(self) -> Vec2 {
+        match self.kind {
+            PortKind::Input => -Vec2::X,
+            PortKind::Output => Vec2::X,
+        }
+    }
+}
+
+/// A wire from an output to an input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Connection {
+    pub from: PortId,
+    pub to: PortId,
+}
+
+impl Connection {
+    /// Connect two ports, in whatever order, if one is an output and the other an input on another node.
+    pub fn between(a: PortId, b: PortId) -> Option<Self> {
+        if a.node == b.node {
+            return None;
+        }
+        match (a.kind, b.kind) {
+            (PortKind::Output, PortKind::Input) => Some(Self { from: a, to: b }),
+            (PortKind::Input, PortKind::Output) => Some(Self { from: b, to: a }),
+            _ => None,
+        }
+    }
+}
+
+/// What happened to a wire, see [`NodeGraphUi::wire`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WireResponse {
+    pub hovered: bool,
+    pub clicked: bool,
+    pub secondary_clicked: bool,
+}
+
+/// What [`NodeGraph::show`] returns.
+pub struct NodeGraphResponse<R> {
+    pub inner: R,
+
+    /// The background of the graph. Clicking it clears the selection.
+    pub response: Response,
+
+    /// A wire the user dragged from one port to another this frame.
+    ///
+    /// It is up to you to add it to the graph, or not.
+    pub new_connection: Option<Connection>,
+}
+
+/// What egui remembers about a graph between frames.
+#[derive(Clone, Debug, Default)]
+struct NodeGraphMemory {
+    selected: IdSet,
+
+    /// The node being dragged by its background, moving all selected nodes.
+    moving: Option<Id>,
+
+    /// The port a new wire is being dragged from, and the id of its widget.
+    connecting: Option<(PortId, Id)>,
+}
+
+/// A node editor, see [the module docs](self).
+#[must_use = "You should call .show()"]
+pub struct NodeGraph {
+    id_salt: Id,
+    connector_kind: ConnectorKind,
+    port_radius: f32,
+    max_node_width: f32,
+}
+
+impl NodeGraph {
+    pub fn new(id_salt: impl std::hash::Hash) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            connector_kind: ConnectorKind::default(),
+            port_radius: 5.0,
+            max_node_width: 200.0,
+        }
+    }
+
+    /// How wires are routed.
+    ///
+    /// Default: [`ConnectorKind::Bezier`].
+    #[inline]
+    pub fn connector_kind(mut self, connector_kind: ConnectorKind) -> Self {
+        self.connector_kind = connector_kind;
+        self
+    }
+
+    /// The size of the ports.
+    ///
+    /// Default: `5.0`.
+    #[inline]
+    pub fn port_radius(mut self, port_radius: f32) -> Self {
+        self.port_radius = port_radius;
+        self
+    }
+
+    /// How wide a node can get before its contents wrap.
+    ///
+    /// Default: `200.0`.
+    #[inline]
+    pub fn max_node_width(mut self, max_node_width: f32) -> Self {
+        self.max_node_width = max_node_width;
+        self
+    }
+
+    /// Add nodes and wires in `add_contents`.
+    ///
+    /// The graph uses all of `ui`, so put it in e.g. a [`crate::containers::Scene`] to get panning and zooming.
+    pub fn show<R>(
+        self,
+        ui: &mut Ui,
+        add_contents: impl FnOnce(&mut Ui, &mut NodeGraphUi) -> R,
+    ) -> NodeGraphResponse<R> {
+        let id = ui.make_persistent_id(self.id_salt);
+        // Registered first, so it is below everything in the graph:
+        let response = ui.interact(ui.max_rect(), id, Sense::click());
+
+        let painter = ui.painter().clone();
+        let mut graph = NodeGraphUi {
+            memory: ui.data(|data| data.get_temp(id)).unwrap_or_default(),
+            wires_idx: painter.add(Shape::Noop),
+            painter,
+            pointer_pos: pointer_pos(ui),
+            settings: self,
+            ports: Vec::new(),
+            node_rects: Vec::new(),
+            wires: Vec::new(),
+            wire_hovered: false,
+        };
+
+        let inner = add_contents(ui, &mut graph);
+        let new_connection = graph.finish(ui, &response);
+        ui.data_mut(|data| data.insert_temp(id, graph.memory));
+
+        NodeGraphResponse {
+            inner,
+            response,
+            new_connection,
+        }
+    }
+}
+
+/// Adds nodes, ports and wires to a [`NodeGraph`].
+pub struct NodeGraphUi {
+    settings: NodeGraph,
+    memory: NodeGraphMemory,
+    painter: Painter,
+
+    /// Reserved below the nodes, so wires can be added after them.
+    wires_idx: ShapeIdx,
+
+    /// In the coordinates of the graph's layer.
+    pointer_pos: Option<Pos2>,
+
+    /// The ports shown this frame, and their rectangles.
+    ports: Vec<(PortId, Rect)>,
+    node_rects: Vec<Rect>,
+    wires: Vec<Shape>,
+    wire_hovered: bool,
+}
+
+impl NodeGraphUi {
+    /// A node at `pos`, which is updated as the node is dragged.
+    ///
+    /// Dragging a selected node moves all selected nodes along.
+    /// Clicking a node selects it; hold down shift or ctrl/cmd to add to the selection.
+    pub fn node<R>(
+        &mut self,
+        ui: &mut Ui,
+        node: Id,
+        pos: &mut Pos2,
+        add_contents: impl FnOnce(&mut Ui, &mut Self) -> R,
+    ) -> crate::InnerResponse<R> {
+        let selected = self.is_selected(node);
+        if selected
+            && self
+                .memory
+                .moving
+                .is_some_and(|id| ui.ctx().is_being_dragged(id))
+        {
+            let mut delta = ui.input(|i| i.pointer.delta());
+            if let Some(from_global) = ui.ctx().layer_transform_from_global(ui.layer_id()) {
+                delta *= from_global.scaling;
+            }
+            *pos += delta;
+        }
+
+        let mut frame = Frame::window(ui.style());
+        if selected {
+            frame = frame.stroke(ui.visuals().selection.stroke);
+        }
+        let max_rect =
+            Rect::from_min_size(*pos, Vec2::new(self.settings.max_node_width, f32::INFINITY));
+        let builder = UiBuilder::new()
+            .id_salt(node)
+            .max_rect(max_rect)
+            .sense(Sense::click_and_drag());
+        let crate::InnerResponse { inner, response } = ui.scope_builder(builder, |ui| {
+            frame.show(ui, |ui| add_contents(ui, self)).inner
+        });
+
+        let add_to_selection = ui.input(|i| i.modifiers.shift || i.modifiers.command);
+        if response.clicked() || (response.drag_started() && !selected) {
+            if !add_to_selection {
+                self.memory.selected.clear();
+            }
+            if response.clicked() && add_to_selection && selected {
+                self.memory.selected.remove(&node);
+            } else {
+                self.memory.selected.insert(node);
+            }
+        }
+        if response.drag_started() {
+            self.memory.moving = Some(response.id);
+        }
+
+        self.node_rects.push(response.rect);
+        crate::InnerResponse { inner, response }
+    }
+
+    /// A port that wires can be dragged from and to.
+    ///
+    /// Put it at the left edge of a node for inputs, and at the right edge for outputs.
+    pub fn port(&mut self, ui: &mut Ui, port: PortId) -> Response {
+        let radius = self.settings.port_radius;
+        let (rect, response) = ui.allocate_exact_size(Vec2::splat(2.0 * radius), Sense::drag());
+
+        if response.drag_started() {
+            self.memory.connecting = Some((port, response.id));
+        }
+
+        // Highlight the ports a new wire can be dropped on:
+        let target = self.memory.connecting.is_some_and(|(from, _)| {
+            Connection::between(from, port).is_some() && self.is_hovering(rect)
+        });
+        if ui.is_rect_visible(rect) {
+            let visuals = ui.style().interact(&response);
+            let radius = if target || response.hovered() {
+                1.25 * radius
+            } else {
+                radius
+            };
+            ui.painter().circle(
+                rect.center(),
+                radius,
+                visuals.fg_stroke.color,
+                visuals.bg_stroke,
+            );
+        }
+
+        self.ports.push((port, rect));
+        response
+    }
+
+    /// A wire between two ports shown this frame.
+    ///
+    /// Add wires after the nodes: they are painted below the nodes,
+    /// and only hovered where no node covers them.
+    pub fn wire(&mut self, connection: Connection) -> WireResponse {
+        let (Some(from), Some(to)) = (self.port_pos(connection.from), self.port_pos(connection.to))
+        else {
+            return WireResponse::default();
+        };
+
+        let ctx = self.painter.ctx().clone();
+        let mut wire = self.connector(from, connection.from, to, connection.to.direction());
+        let idle = self
+            .memory
+            .moving
+            .or(self.memory.connecting.map(|(_, id)| id));
+        let hovered = !self.wire_hovered
+            && idle.map_or(true, |id| !ctx.is_being_dragged(id))
+            && self.pointer_pos.is_some_and(|pos| {
+                !self.node_rects.iter().any(|rect| rect.contains(pos))
+                    && self.painter.clip_rect().contains(pos)
+                    && wire.hit_test(pos, ctx.style().interaction.interact_radius)
+            });
+        if hovered {
+            self.wire_hovered = true;
+            wire.stroke = ctx.style().visuals.widgets.hovered.fg_stroke;
+            wire.stroke.width += 1.0;
+        }
+        self.wires.push(wire.into());
+
+        let (clicked, secondary_clicked) = ctx.input(|i| {
+            (
+                hovered && i.pointer.primary_clicked(),
+                hovered && i.pointer.secondary_clicked(),
+            )
+        });
+        WireResponse {
+            hovered,
+            clicked,
+            secondary_clicked,
+        }
+    }
+
+    /// Is this node selected?
+    pub fn is_selected(&self, node: Id) -> bool {
+        self.memory.selected.contains(&node)
+    }
+
+    /// Select or deselect a node.
+    pub fn set_selected(&mut self, node: Id, selected: bool) {
+        if selected {
+            self.memory.selected.insert(node);
+        } else {
+            self.memory.selected.remove(&node);
+        }
+    }
+
+    /// The selected nodes, in no particular order.
+    pub fn selected(&self) -> impl Iterator<Item = Id> + '_ {
+        self.memory.selected.iter().copied()
+    }
+
+    fn port_pos(&self, port: PortId) -> Option<Pos2> {
+        self.ports
+            .iter()
+            .find(|(id, _)| *id == port)
+            .map(|(_, rect)| rect.center())
+    }
+
+    fn is_hovering(&self, rect: Rect) -> bool {
+        self.pointer_pos
+            .is_some_and(|pos| rect.expand(2.0).contains(pos))
+    }
+
+    /// A wire from the `from` port to `to`, with the style of the graph.
+    fn connector(&self, from: Pos2, from_port: PortId, to: Pos2, to_dir: Vec2) -> ConnectorShape {
+        let stroke = Stroke::new(
+            2.0,
+            self.painter
+                .ctx()
+                .style()
+                .visuals
+                .widgets
+                .inactive
+                .fg_stroke
+                .color,
+        );
+        ConnectorShape::new(from, to, self.settings.connector_kind, stroke)
+            .directions(from_port.direction(), to_dir)
+    }
+
+    /// Paint the wires, and the one being dragged out. Returns it if it was dropped on a port.
+    fn finish(&mut self, ui: &Ui, response: &Response) -> Option<Connection> {
+        self.painter
+            .set(self.wires_idx, Shape::Vec(std::mem::take(&mut self.wires)));
+
+        if self
+            .memory
+            .moving
+            .is_some_and(|id| !ui.ctx().is_being_dragged(id))
+        {
+            self.memory.moving = None;
+        }
+        if response.clicked() && !self.wire_hovered {
+            self.memory.selected.clear();
+        }
+
+        let (from, drag_id) = self.memory.connecting?;
+        let from_pos = self.port_pos(from)?;
+        let target = self.ports.iter().find_map(|&(port, rect)| {
+            Connection::between(from, port)
+                .filter(|_| self.is_hovering(rect))
+                .map(|connection| (connection, port, rect.center()))
+        });
+
+        if !ui.ctx().is_being_dragged(drag_id) {
+            self.memory.connecting = None;
+            return target.map(|(connection, ..)| connection);
+        }
+
+        // Preview the wire, snapping to the port it would connect to:
+        let (to, to_dir) = match (target, self.pointer_pos) {
+            (Some((_, port, pos)), _) => (pos, port.direction()),
+            (None, Some(pointer)) => (pointer, -from.direction()),
+            (None, None) => return None,
+        };
+        let preview = self.connector(from_pos, from, to, to_dir);
+        self.painter.add(preview);
+        None
+    }
+}
+
+/// Where the pointer is, in the coordinates of the layer of `ui`.
+fn pointer_pos(ui: &Ui) -> Option<Pos2> {
+    let pos = ui.ctx().pointer_latest_pos()?;
+    Some(match ui.ctx().layer_transform_from_global(ui.layer_id()) {
+        Some(from_global) => from_global * pos,
+        None => pos,
+    })
+}
+
+#[test]
+fn test_connection_between() {
+    let (a, b) = (Id::new("a"), Id::new("b"));
+    let connection = Connection::between(PortId::input(b, 1), PortId::output(a, 0));
+    assert_eq!(
+        connection,
+        Some(Connection {
+            from: PortId::output(a, 0),
+            to: PortId::input(b, 1),
+        })
+    );
+    assert_eq!(
+        Connection::between(PortId::output(a, 0), PortId::output(b, 0)),
+        None
+    );
+    assert_eq!(
+        Connection::between(PortId::output(a, 0), PortId::input(a, 0)),
+        None
+    );
+}
@@ -0,0 +1,106 @@
+//! Find which shapes changed between two frames.
+//!
+//! Turned on with [`crate::style::DebugOptions::show_paint_diff`].
+
+use epaint::{ClippedShape, Shape, Stroke, StrokeKind};
+
+use crate::{Color32, Rect};
+
+/// How far ahead in the previous frame we look for a matching shape.
+///
+/// Keeps the diff linear, at the cost of reporting big reorderings as changes.
+const LOOKAHEAD: usize = 64;
+
+/// Compares the shapes of each frame to those of the previous frame.
+#[derive(Default)]
+pub(crate) struct PaintDiff {
+    prev_shapes: Vec<ClippedShape>,
+
+    /// The shapes that are new or changed compared to the previous frame.
+    pub changed: Vec<Rect>,
+
+    /// The shapes of the previous frame that are gone.
+    pub removed: Vec<Rect>,
+}
+
+impl PaintDiff {
+    /// Compare with the shapes of the previous frame, and remember these for the next one.
+    pub fn update(&mut self, shapes: &[ClippedShape]) {
+        self.changed.clear();
+        self.removed.clear();
+
+        let prev = &self.prev_shapes;
+        let mut cursor = 0;
+        for shape in shapes {
+            let end = (cursor + LOOKAHEAD).min(prev.len());
+            if let Some(offset) = prev[cursor..end].iter().position(|p| p == shape) {
+                for skipped in &prev[cursor..cursor + offset] {
+                    self.removed.push(visible_rect(skipped));
+                }
+                cursor += offset + 1;
+            } else {
+                self.changed.push(visible_rect(shape));
+            }
+        }
+        for skipped in &prev[cursor..] {
+            self.removed.push(visible_rect(skipped));
+        }
+
+        self.changed.retain(|rect| rect.is_positive());
+        self.removed.retain(|rect| rect.is_positive());
+        self.prev_shapes = shapes.to_vec();
+    }
+
+    /// Outlines of the changed (red) and removed (blue) shapes.
+    pub fn highlight_shapes(&self) -> impl Iterator<Item = ClippedShape> + '_ {
+        let outline = |rect: &Rect, color: Color32| ClippedShape {
+            clip_rect: Rect::EVERYTHING,
+            shape: Shape::rect_stroke(*rect, 0.0, Stroke::new(1.0, color), StrokeKind::Outside),
+        };
+        let changed = self.changed.iter().map(move |r| outline(r, Color32::RED));
+        let removed = self
+            .removed
+            .iter()
+            .map(move |r| outline(r, Color32::LIGHT_BLUE));
+        changed.chain(removed)
+    }
+}
+
+fn visible_rect(shape: &ClippedShape) -> Rect {
+    shape
+        .shape
+        .visual_bounding_rect()
+        .intersect(shape.clip_rect)
+}
+
+#[test]
+fn test_paint_diff() {
+    let clipped = |x: f32| ClippedShape {
+        clip_rect: Rect::EVERYTHING,
+        shape: Shape::rect_filled(
+            Rect::from_min_size(crate::pos2(x, 0.0), crate::Vec2::splat(10.0)),
+            0.0,
+            Color32::WHITE,
+        ),
+    };
+
+    let mut diff = PaintDiff::default();
+    diff.update(&[clipped(0.0), clipped(20.0)]);
+    assert_eq!(
+        diff.changed.len(),
+        2,
+        "Everything is new on the first frame"
+    );
+
+    diff.update(&[clipped(0.0), clipped(20.0)]);
+    assert!(diff.changed.is_empty());
+    assert!(diff.removed.is_empty());
+
+    // Insert one, move one:
+    diff.update(&[clipped(-20.0), clipped(0.0), clipped(25.0)]);
+    assert_eq!(diff.changed.len(), 2);
+    assert_eq!(diff.changed[0].min.x, -20.0);
+    assert_eq!(diff.changed[1].min.x, 25.0);
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0].min.x, 20.0);
+}
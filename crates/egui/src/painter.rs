@@ -3,12 +3,13 @@ use std::sync::Arc;
 use emath::GuiRounding as _;
 use epaint::{
     text::{Fonts, Galley, LayoutJob},
-    CircleShape, ClippedShape, CornerRadius, PathStroke, RectShape, Shape, Stroke, StrokeKind,
+    CircleShape, ClippedShape, CornerRadius, PathStroke, PixelSnapping, RectShape, Shape, Stroke,
+    StrokeKind,
 };
 
 use crate::{
-    emath::{Align2, Pos2, Rangef, Rect, Vec2},
-    layers::{LayerId, PaintList, ShapeIdx},
+    emath::{vec2, Align2, NumExt as _, Pos2, Rangef, Rect, Vec2},
+    layers::{LayerId, PaintList, ShapeIdx, SortKey},
     Color32, Context, FontId,
 };
 
@@ -40,6 +41,9 @@ pub struct Painter {
     /// this value as the factor.
     /// This is used to make interfaces semi-transparent.
     opacity_factor: f32,
+
+    /// If set, overrides [`epaint::TessellationOptions::pixel_snapping`] for everything painted.
+    pixel_snapping: Option<PixelSnapping>,
 }
 
 impl Painter {
@@ -53,6 +57,7 @@ impl Painter {
             clip_rect,
             fade_to_color: None,
             opacity_factor: 1.0,
+            pixel_snapping: None,
         }
     }
 
@@ -82,6 +87,13 @@ impl Painter {
         self.layer_id = layer_id;
     }
 
+    /// Set the z-index of the layer this painter paints to, see [`Context::set_layer_z`].
+    ///
+    /// This moves everything painted to the layer, not only what this painter paints.
+    pub fn set_z(&self, z: i32) {
+        self.ctx.set_layer_z(self.layer_id, z);
+    }
+
     /// If set, colors will be modified to look like this
     pub fn set_fade_to_color(&mut self, fade_to_color: Option<Color32>) {
         self.fade_to_color = fade_to_color;
@@ -116,6 +128,20 @@ impl Painter {
         self.opacity_factor
     }
 
+    /// Align everything painted by this painter from this point forward to the physical pixel grid like this,
+    /// regardless of [`epaint::TessellationOptions::pixel_snapping`].
+    ///
+    /// `None` (the default) uses the global [`epaint::TessellationOptions::pixel_snapping`].
+    pub fn set_pixel_snapping(&mut self, pixel_snapping: Option<PixelSnapping>) {
+        self.pixel_snapping = pixel_snapping;
+    }
+
+    /// See [`Self::set_pixel_snapping`].
+    #[inline]
+    pub fn pixel_snapping(&self) -> Option<PixelSnapping> {
+        self.pixel_snapping
+    }
+
     /// If `false`, nothing you paint will show up.
     ///
     /// Also checks [`Context::will_discard`].
@@ -237,6 +263,10 @@ impl Painter {
         if self.opacity_factor < 1.0 {
             multiply_opacity(shape, self.opacity_factor);
         }
+        if let Some(pixel_snapping) = self.pixel_snapping {
+            let inner = std::mem::replace(shape, Shape::Noop);
+            *shape = Shape::pixel_snapped(pixel_snapping, vec![inner]);
+        }
     }
 
     /// It is up to the caller to make sure there is room for this.
@@ -259,7 +289,10 @@ impl Painter {
         if self.fade_to_color == Some(Color32::TRANSPARENT) || self.opacity_factor == 0.0 {
             return;
         }
-        if self.fade_to_color.is_some() || self.opacity_factor < 1.0 {
+        if self.fade_to_color.is_some()
+            || self.opacity_factor < 1.0
+            || self.pixel_snapping.is_some()
+        {
             let shapes = shapes.into_iter().map(|mut shape| {
                 self.transform_shape(&mut shape);
                 shape
@@ -270,6 +303,42 @@ impl Painter {
         }
     }
 
+    /// The index the next [`Shape`] added to this layer will get.
+    ///
+    /// Use with [`SortKey::behind`] and [`Self::add_sorted`].
+    pub fn next_idx(&self) -> ShapeIdx {
+        self.paint_list(|l| l.next_idx())
+    }
+
+    /// Add a [`Shape`] that is painted according to `key` rather than in the order it was added.
+    ///
+    /// This lets you paint a background behind some contents once you know how large the contents are:
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::SortKey;
+    ///
+    /// let painter = ui.painter().clone();
+    /// let behind_contents = SortKey::behind(painter.next_idx());
+    /// let rect = ui.label("Contents").rect;
+    /// painter.add_sorted(
+    ///     behind_contents,
+    ///     egui::Shape::rect_filled(rect.expand(4.0), 2.0, ui.visuals().faint_bg_color),
+    /// );
+    /// # });
+    /// ```
+    ///
+    /// See also [`Self::set`].
+    pub fn add_sorted(&self, key: SortKey, shape: impl Into<Shape>) -> ShapeIdx {
+        if self.fade_to_color == Some(Color32::TRANSPARENT) || self.opacity_factor == 0.0 {
+            self.paint_list(|l| l.add(self.clip_rect, Shape::Noop))
+        } else {
+            let mut shape = shape.into();
+            self.transform_shape(&mut shape);
+            self.paint_list(|l| l.add_sorted(key, self.clip_rect, shape))
+        }
+    }
+
     /// Modify an existing [`Shape`].
     pub fn set(&self, idx: ShapeIdx, shape: impl Into<Shape>) {
         if self.fade_to_color == Some(Color32::TRANSPARENT) {
@@ -292,6 +361,90 @@ impl Painter {
     }
 }
 
+/// Shapes painted relative to a placeholder rectangle,
+/// to be moved into place with [`Self::place`] once the final rectangle is known.
+///
+/// This solves the common problem of painting a background behind some contents
+/// before you know how large the contents will be.
+/// Created with [`Painter::paint_region`].
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// let placeholder = egui::Rect::from_min_size(ui.cursor().min, egui::vec2(100.0, 20.0));
+/// let region = ui.painter().paint_region(placeholder, |painter| {
+///     painter.rect_filled(placeholder, 4.0, painter.ctx().style().visuals.faint_bg_color);
+/// });
+/// let rect = ui.label("Contents of unknown size").rect;
+/// region.place(ui.ctx(), rect.expand(4.0));
+/// # });
+/// ```
+#[must_use = "You should call .place()"]
+#[derive(Clone, Copy, Debug)]
+pub struct PaintRegion {
+    layer_id: LayerId,
+    placeholder: Rect,
+    start: ShapeIdx,
+    end: ShapeIdx,
+}
+
+impl PaintRegion {
+    /// The rectangle the shapes were painted relative to.
+    #[inline]
+    pub fn placeholder(&self) -> Rect {
+        self.placeholder
+    }
+
+    /// The translation and scale that map the placeholder onto `rect`,
+    /// for [`Shape::transform_non_uniform`].
+    ///
+    /// The shapes are stretched to fill `rect`.
+    /// Along an axis where the placeholder has no size, they are only moved.
+    pub fn translation_and_scale_to(&self, rect: Rect) -> (Vec2, Vec2) {
+        let ratio = |to: f32, from: f32| if from > 0.0 { to / from } else { 1.0 };
+        let scale = Vec2::new(
+            ratio(rect.width(), self.placeholder.width()),
+            ratio(rect.height(), self.placeholder.height()),
+        );
+        let translation = rect.center().to_vec2() - scale * self.placeholder.center().to_vec2();
+        (translation, scale)
+    }
+
+    /// Move the shapes painted relative to the placeholder into `rect`, stretching them to fill it.
+    ///
+    /// The shapes are still clipped to the clip rectangle of the painter they were painted with.
+    ///
+    /// Must be called in the same pass as the shapes were painted.
+    pub fn place(self, ctx: &Context, rect: Rect) {
+        let (translation, scale) = self.translation_and_scale_to(rect);
+        ctx.graphics_mut(|g| {
+            g.entry(self.layer_id).transform_range_non_uniform(
+                self.start,
+                self.end,
+                translation,
+                scale,
+            );
+        });
+    }
+}
+
+/// ## Paint later at final rect
+impl Painter {
+    /// Paint some shapes relative to `placeholder`,
+    /// and then move them into place with [`PaintRegion::place`] once you know where they should go.
+    ///
+    /// See [`PaintRegion`].
+    pub fn paint_region(&self, placeholder: Rect, add_contents: impl FnOnce(&Self)) -> PaintRegion {
+        let start = self.next_idx();
+        add_contents(self);
+        PaintRegion {
+            layer_id: self.layer_id,
+            placeholder,
+            start,
+            end: self.next_idx(),
+        }
+    }
+}
+
 /// ## Debug painting
 impl Painter {
     #[allow(clippy::needless_pass_by_value)]
@@ -354,6 +507,13 @@ impl Painter {
         })
     }
 
+    /// Paints a thin line that is always crisp, see [`Shape::hairline`].
+    ///
+    /// Use this for separators, borders and grid lines.
+    pub fn hairline(&self, points: [Pos2; 2], stroke: impl Into<Stroke>) -> ShapeIdx {
+        self.add(Shape::hairline(points, stroke))
+    }
+
     /// Paints a line connecting the points.
     /// NOTE: all coordinates are screen coordinates!
     pub fn line(&self, points: Vec<Pos2>, stroke: impl Into<PathStroke>) -> ShapeIdx {
@@ -485,6 +645,546 @@ impl Painter {
     ) -> ShapeIdx {
         self.add(Shape::image(texture_id, rect, uv, tint))
     }
+
+    /// An image rotated by `angle` radians (clockwise) around its center,
+    /// e.g. a sprite or a compass needle.
+    ///
+    /// `size` is the size of the image before rotation.
+    /// See [`Self::image`] for `uv` and `tint`.
+    pub fn image_rotated(
+        &self,
+        texture_id: epaint::TextureId,
+        center: Pos2,
+        size: Vec2,
+        angle: f32,
+        uv: Rect,
+        tint: Color32,
+    ) -> ShapeIdx {
+        self.add(Shape::image_rotated(
+            texture_id, center, size, angle, uv, tint,
+        ))
+    }
+
+    /// An image stretched onto four arbitrary corners, given clockwise
+    /// starting with where the top-left of `uv` goes.
+    ///
+    /// See [`Self::image`] for `uv` and `tint`.
+    pub fn image_quad(
+        &self,
+        texture_id: epaint::TextureId,
+        corners: [Pos2; 4],
+        uv: Rect,
+        tint: Color32,
+    ) -> ShapeIdx {
+        self.add(Shape::image_quad(texture_id, corners, uv, tint))
+    }
+
+    /// Paint an outline around the outside of a shape (or a [`Shape::Vec`] of shapes),
+    /// e.g. to show that it is selected or hovered.
+    ///
+    /// The outline should be painted below the shape.
+    /// If you only know whether to show it after painting the shape,
+    /// reserve a spot for it first:
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::epaint::Glow;
+    ///
+    /// let painter = ui.painter();
+    /// let glow_idx = painter.add(egui::Shape::Noop);
+    /// let shape = egui::Shape::circle_filled(ui.max_rect().center(), 20.0, egui::Color32::GRAY);
+    /// painter.add(shape.clone());
+    ///
+    /// let glow = Glow::new(3.0, ui.visuals().selection.bg_fill).with_blur(6.0);
+    /// painter.set(glow_idx, glow.as_shape(&shape));
+    /// # });
+    /// ```
+    pub fn glow(&self, shape: &Shape, glow: epaint::Glow) -> ShapeIdx {
+        self.add(glow.as_shape(shape))
+    }
+
+    /// Paint a line that keeps growing, tessellating only the points added since last frame.
+    ///
+    /// See [`crate::LivePath`].
+    pub fn live_path(&self, path: &mut crate::LivePath) -> ShapeIdx {
+        let options = self.ctx.tessellation_options(|options| *options);
+        self.add(path.shape(self.pixels_per_point, &options))
+    }
+}
+
+/// ## Mini charts
+///
+/// Tiny charts without axes or labels, e.g. for table cells.
+/// The values are scaled to fill the rect vertically, and spread evenly over its width.
+/// The baseline is at zero, or at the edge of the rect closest to it if all values have the same sign.
+/// Values that aren't finite, e.g. NaN for missing data, are skipped.
+impl Painter {
+    /// A line through the values.
+    pub fn sparkline(&self, rect: Rect, values: &[f32], stroke: impl Into<Stroke>) -> ShapeIdx {
+        let (points, _) = sparkline_points(rect, values);
+        self.add(Shape::line(points, stroke.into()))
+    }
+
+    /// A line through the values, with the area down to the baseline filled.
+    ///
+    /// The fill goes from `fill[0]` at the top (or bottom) of the rect to `fill[1]` at the baseline.
+    /// Use the same color twice for a solid fill, or e.g. `[color, Color32::TRANSPARENT]`
+    /// to fade out towards the baseline.
+    pub fn sparkline_area(
+        &self,
+        rect: Rect,
+        values: &[f32],
+        stroke: impl Into<Stroke>,
+        fill: [Color32; 2],
+    ) -> ShapeIdx {
+        let (points, baseline) = sparkline_points(rect, values);
+
+        // Split the segments where they cross the baseline, so every quad is on one side of it:
+        let mut outline: Vec<Pos2> = Vec::with_capacity(points.len());
+        for &point in &points {
+            if let Some(&previous) = outline.last() {
+                let (a, b) = (previous.y - baseline, point.y - baseline);
+                if a * b < 0.0 {
+                    let t = a / (a - b);
+                    outline.push(Pos2::new(emath::lerp(previous.x..=point.x, t), baseline));
+                }
+            }
+            outline.push(point);
+        }
+
+        let color_at = |y: f32| {
+            let extreme = if y < baseline {
+                rect.top()
+            } else {
+                rect.bottom()
+            };
+            let t = emath::remap_clamp(y, baseline..=extreme, 0.0..=1.0);
+            fill[1].lerp_to_gamma(fill[0], t)
+        };
+        let mut mesh = epaint::Mesh::default();
+        for (i, point) in outline.iter().enumerate() {
+            mesh.colored_vertex(Pos2::new(point.x, baseline), fill[1]);
+            mesh.colored_vertex(*point, color_at(point.y));
+            if 0 < i {
+                let base = 2 * i as u32 - 2;
+                mesh.add_triangle(base, base + 1, base + 3);
+                mesh.add_triangle(base, base + 3, base + 2);
+            }
+        }
+
+        self.add(Shape::Vec(vec![
+            mesh.into(),
+            Shape::line(points, stroke.into()),
+        ]))
+    }
+
+    /// One bar per value, from the baseline to the value.
+    pub fn sparkline_bars(&self, rect: Rect, values: &[f32], fill: Color32) -> ShapeIdx {
+        let (y, baseline) = sparkline_y(rect, values);
+        let bar_width = rect.width() / values.len().max(1) as f32;
+        // Leave a gap between the bars, unless they are too thin:
+        let gap = if 3.0 <= bar_width { 1.0 } else { 0.0 };
+
+        let mut mesh = epaint::Mesh::default();
+        for (i, &value) in values.iter().enumerate() {
+            if !value.is_finite() {
+                continue;
+            }
+            let left = rect.left() + i as f32 * bar_width;
+            let bar = Rect::from_x_y_ranges(
+                left + 0.5 * gap..=left + bar_width - 0.5 * gap,
+                Rangef::new(y(value), baseline),
+            );
+            mesh.add_colored_rect(bar, fill);
+        }
+        self.add(mesh)
+    }
+}
+
+/// Where the finite values of a mini chart go in `rect`, and the y coordinate of the baseline.
+fn sparkline_points(rect: Rect, values: &[f32]) -> (Vec<Pos2>, f32) {
+    let (y, baseline) = sparkline_y(rect, values);
+    let x = |i: usize| {
+        if values.len() <= 1 {
+            rect.center().x
+        } else {
+            emath::remap(i as f32, 0.0..=(values.len() - 1) as f32, rect.x_range())
+        }
+    };
+    let points = values
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| value.is_finite())
+        .map(|(i, &value)| Pos2::new(x(i), y(value)))
+        .collect();
+    (points, baseline)
+}
+
+/// How the values of a mini chart map to y coordinates in `rect`, and the y coordinate of the baseline.
+fn sparkline_y(rect: Rect, values: &[f32]) -> (impl Fn(f32) -> f32, f32) {
+    let mut range = values
+        .iter()
+        .filter(|value| value.is_finite())
+        .fold(Rangef::NOTHING, |range, &value| {
+            Rangef::new(range.min.min(value), range.max.max(value))
+        });
+    if range.min > range.max {
+        range = Rangef::point(0.0);
+    }
+    if range.span() <= 0.0 {
+        range = range.expand(range.min.abs().max(1.0));
+    }
+
+    let y = move |value: f32| emath::remap(value, range, rect.bottom()..=rect.top());
+    (y, y(0.0_f32.clamp(range.min, range.max)))
+}
+
+/// The shape of the markers drawn by [`Painter::points`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PointMarker {
+    /// A filled circle (a dot).
+    #[default]
+    Circle,
+
+    /// A filled, axis-aligned square.
+    Square,
+
+    /// A diagonal cross: `×`.
+    Cross,
+
+    /// A square rotated 45°.
+    Diamond,
+}
+
+/// Round caps give round dots, the others give square dots.
+impl From<epaint::LineCap> for PointMarker {
+    fn from(cap: epaint::LineCap) -> Self {
+        match cap {
+            epaint::LineCap::Round => Self::Circle,
+            epaint::LineCap::Butt | epaint::LineCap::Square => Self::Square,
+        }
+    }
+}
+
+/// How [`Painter::points`] draws each point.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PointStyle {
+    pub marker: PointMarker,
+
+    /// The color of every point, unless [`Self::colors`] says otherwise.
+    pub color: Color32,
+
+    /// One color per point, e.g. to color a scatter plot by value.
+    ///
+    /// Points past the end of this use [`Self::color`].
+    pub colors: Vec<Color32>,
+}
+
+impl PointStyle {
+    pub fn new(marker: impl Into<PointMarker>, color: Color32) -> Self {
+        Self {
+            marker: marker.into(),
+            color,
+            colors: Vec::new(),
+        }
+    }
+
+    /// One color per point, see [`Self::colors`].
+    #[inline]
+    pub fn colors(mut self, colors: Vec<Color32>) -> Self {
+        self.colors = colors;
+        self
+    }
+}
+
+/// Dots of the given color.
+impl From<Color32> for PointStyle {
+    fn from(color: Color32) -> Self {
+        Self::new(PointMarker::Circle, color)
+    }
+}
+
+impl From<(PointMarker, Color32)> for PointStyle {
+    fn from((marker, color): (PointMarker, Color32)) -> Self {
+        Self::new(marker, color)
+    }
+}
+
+/// ## Point markers
+impl Painter {
+    /// Paint a marker at each of the points, e.g. for a scatter plot over a canvas.
+    ///
+    /// `radius` is the distance from the center of each marker to its edge.
+    ///
+    /// All markers go into a single [`epaint::Mesh`], so this is much faster than
+    /// adding a [`Shape`] per point, even for hundreds of thousands of points.
+    /// Points outside of the clip rectangle are skipped.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::{Color32, PointMarker, PointStyle, pos2};
+    ///
+    /// let points = [pos2(10.0, 10.0), pos2(20.0, 15.0), pos2(30.0, 12.0)];
+    /// ui.painter().points(&points, 2.0, Color32::RED);
+    /// ui.painter().points(
+    ///     &points,
+    ///     3.0,
+    ///     PointStyle::new(PointMarker::Diamond, Color32::WHITE)
+    ///         .colors(vec![Color32::RED, Color32::GREEN, Color32::BLUE]),
+    /// );
+    /// # });
+    /// ```
+    pub fn points(&self, points: &[Pos2], radius: f32, style: impl Into<PointStyle>) -> ShapeIdx {
+        let style = style.into();
+        let feathering = if self.ctx.tessellation_options(|options| options.feathering) {
+            1.0 / self.pixels_per_point
+        } else {
+            0.0
+        };
+        let outline = marker_outline(style.marker, radius, self.pixels_per_point);
+        let cull_rect = self.clip_rect.expand(radius + feathering);
+
+        let mut mesh = epaint::Mesh::default();
+        for (i, &point) in points.iter().enumerate() {
+            if !cull_rect.contains(point) {
+                continue;
+            }
+            let color = style.colors.get(i).copied().unwrap_or(style.color);
+            for polygon in &outline {
+                add_convex_polygon(&mut mesh, point, polygon, color, feathering);
+            }
+        }
+        self.add(mesh)
+    }
+}
+
+/// The convex polygons making up a marker centered on the origin.
+fn marker_outline(marker: PointMarker, radius: f32, pixels_per_point: f32) -> Vec<Vec<Vec2>> {
+    let r = radius;
+    match marker {
+        PointMarker::Circle => {
+            let num_points = (4.0 * (radius * pixels_per_point).sqrt()).ceil() as usize;
+            let num_points = num_points.clamp(8, 64);
+            vec![(0..num_points)
+                .map(|i| r * Vec2::angled(std::f32::consts::TAU * i as f32 / num_points as f32))
+                .collect()]
+        }
+        PointMarker::Square => vec![vec![vec2(-r, -r), vec2(r, -r), vec2(r, r), vec2(-r, r)]],
+        PointMarker::Diamond => vec![vec![
+            vec2(0.0, -r),
+            vec2(r, 0.0),
+            vec2(0.0, r),
+            vec2(-r, 0.0),
+        ]],
+        PointMarker::Cross => {
+            // Two bars, one along each diagonal:
+            let half_width = (0.2 * r).at_least(0.5 / pixels_per_point);
+            [vec2(1.0, 1.0), vec2(1.0, -1.0)]
+                .into_iter()
+                .map(|dir| {
+                    let end = r * dir.normalized();
+                    let side = half_width * dir.normalized().rot90();
+                    vec![-end - side, end - side, end + side, -end + side]
+                })
+                .collect()
+        }
+    }
+}
+
+/// Add a filled convex polygon, with an anti-aliased edge `feathering` points wide.
+fn add_convex_polygon(
+    mesh: &mut epaint::Mesh,
+    center: Pos2,
+    outline: &[Vec2],
+    color: Color32,
+    feathering: f32,
+) {
+    let n = outline.len() as u32;
+    let base = mesh.vertices.len() as u32;
+
+    if feathering <= 0.0 {
+        for &offset in outline {
+            mesh.colored_vertex(center + offset, color);
+        }
+        for i in 1..n.saturating_sub(1) {
+            mesh.add_triangle(base, base + i, base + i + 1);
+        }
+        return;
+    }
+
+    // Each corner gets an inner vertex with the color, and an outer transparent one:
+    for (i, &corner) in outline.iter().enumerate() {
+        let prev = outline[(i + outline.len() - 1) % outline.len()];
+        let next = outline[(i + 1) % outline.len()];
+        let outward = |a: Vec2, b: Vec2| {
+            let normal = (b - a).normalized().rot90();
+            if normal.dot(a + b) < 0.0 {
+                -normal
+            } else {
+                normal
+            }
+        };
+        let (n0, n1) = (outward(prev, corner), outward(corner, next));
+        let miter = (n0 + n1) / (1.0 + n0.dot(n1)).at_least(0.1);
+        mesh.colored_vertex(center + corner - 0.5 * feathering * miter, color);
+        mesh.colored_vertex(
+            center + corner + 0.5 * feathering * miter,
+            Color32::TRANSPARENT,
+        );
+    }
+    for i in 1..n.saturating_sub(1) {
+        mesh.add_triangle(base, base + 2 * i, base + 2 * (i + 1));
+    }
+    for i in 0..n {
+        let j = (i + 1) % n;
+        let (inner_i, outer_i) = (base + 2 * i, base + 2 * i + 1);
+        let (inner_j, outer_j) = (base + 2 * j, base + 2 * j + 1);
+        mesh.add_triangle(inner_i, outer_i, outer_j);
+        mesh.add_triangle(inner_i, outer_j, inner_j);
+    }
+}
+
+/// How the lines of [`Painter::grid`] look.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct GridStyle {
+    /// The color of the major lines, and of the minor lines once they have faded in.
+    pub color: Color32,
+
+    /// How many minor lines there are per major line.
+    ///
+    /// This is also how many times further apart the lines get when zooming out.
+    pub subdivisions: u32,
+
+    /// Lines closer than this on screen are hidden, in points.
+    ///
+    /// The finest visible lines fade in as they get further apart than this.
+    pub min_spacing: f32,
+
+    /// The color of the lines through the origin, if they should stand out.
+    pub axis_color: Option<Color32>,
+}
+
+impl Default for GridStyle {
+    fn default() -> Self {
+        Self {
+            color: Color32::from_rgba_unmultiplied(128, 128, 128, 72),
+            subdivisions: 5,
+            min_spacing: 8.0,
+            axis_color: None,
+        }
+    }
+}
+
+/// ## Grids
+impl Painter {
+    /// Paint grid lines over `rect`, e.g. for a canvas, plot or node editor that can be panned and zoomed.
+    ///
+    /// `transform` maps canvas coordinates to screen coordinates,
+    /// and `spacing` is the distance between the finest lines, in canvas units.
+    ///
+    /// When zooming out, lines closer than [`GridStyle::min_spacing`] are hidden,
+    /// leaving only every [`GridStyle::subdivisions`]th line, and so on.
+    /// The finest visible lines fade in as you zoom in, so lines never pop in or out.
+    /// The lines are one physical pixel wide, however far you zoom.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let rect = ui.max_rect();
+    /// let canvas_to_screen = egui::emath::TSTransform::new(rect.min.to_vec2(), 2.0);
+    /// ui.painter().grid(rect, canvas_to_screen, 10.0, egui::GridStyle::default());
+    /// # });
+    /// ```
+    pub fn grid(
+        &self,
+        rect: Rect,
+        transform: emath::TSTransform,
+        spacing: f32,
+        style: GridStyle,
+    ) -> ShapeIdx {
+        let stroke_width = 1.0 / self.pixels_per_point;
+        let mut shapes = vec![];
+        for vertical in [true, false] {
+            for (screen, color) in grid_lines(rect, transform, spacing, &style, vertical) {
+                let stroke = Stroke::new(stroke_width, color);
+                let points = if vertical {
+                    let x = self.round_to_pixel_center(screen);
+                    [Pos2::new(x, rect.top()), Pos2::new(x, rect.bottom())]
+                } else {
+                    let y = self.round_to_pixel_center(screen);
+                    [Pos2::new(rect.left(), y), Pos2::new(rect.right(), y)]
+                };
+                shapes.push(Shape::LineSegment { points, stroke });
+            }
+        }
+        self.add(Shape::Vec(shapes))
+    }
+}
+
+/// The distance between the finest visible grid lines, in canvas units, and how faded in they are,
+/// for a grid with the given `spacing` shown at `scale` points per canvas unit.
+fn grid_level(spacing: f32, scale: f32, style: &GridStyle) -> Option<(f32, f32)> {
+    if !(spacing > 0.0 && scale > 0.0 && spacing.is_finite() && scale.is_finite()) {
+        return None;
+    }
+    let subdivisions = style.subdivisions.max(2) as f32;
+    let min_spacing = style.min_spacing.max(1.0);
+
+    // Go up as many levels as needed for the lines to be far enough apart:
+    let ratio = min_spacing / (spacing * scale);
+    let levels = if ratio <= 1.0 {
+        0.0
+    } else {
+        (ratio.ln() / subdivisions.ln()).ceil()
+    };
+    let mut step = spacing * subdivisions.powf(levels);
+    if step * scale < min_spacing * 0.999 {
+        step *= subdivisions; // rounding errors
+    }
+
+    let fade = emath::remap_clamp(
+        step * scale,
+        min_spacing..=min_spacing * subdivisions,
+        0.0..=1.0,
+    );
+    Some((step, fade))
+}
+
+/// The screen coordinates and colors of the vertical (or horizontal) lines of [`Painter::grid`].
+fn grid_lines(
+    rect: Rect,
+    transform: emath::TSTransform,
+    spacing: f32,
+    style: &GridStyle,
+    vertical: bool,
+) -> Vec<(f32, Color32)> {
+    let Some((step, fade)) = grid_level(spacing, transform.scaling, style) else {
+        return vec![];
+    };
+    let subdivisions = style.subdivisions.max(2) as i64;
+
+    let to_canvas = transform.inverse();
+    let (min, max) = (to_canvas * rect.min, to_canvas * rect.max);
+    let (range, offset) = if vertical {
+        (Rangef::new(min.x, max.x), transform.translation.x)
+    } else {
+        (Rangef::new(min.y, max.y), transform.translation.y)
+    };
+
+    let first = (range.min / step).ceil() as i64;
+    let last = (range.max / step).floor() as i64;
+    (first..=last)
+        .filter_map(|n| {
+            let color = match style.axis_color {
+                Some(axis_color) if n == 0 => axis_color,
+                _ if n % subdivisions == 0 => style.color,
+                _ => style.color.gamma_multiply(fade),
+            };
+            (color != Color32::TRANSPARENT)
+                .then_some((offset + transform.scaling * n as f32 * step, color))
+        })
+        .collect()
 }
 
 /// ## Text
@@ -609,3 +1309,166 @@ fn multiply_opacity(shape: &mut Shape, opacity: f32) {
         }
     });
 }
+
+#[test]
+fn test_sparkline_points() {
+    let rect = Rect::from_min_max(Pos2::new(0.0, 0.0), Pos2::new(30.0, 10.0));
+
+    let (points, baseline) = sparkline_points(rect, &[1.0, 3.0, 2.0, 5.0]);
+    let ys: Vec<f32> = points.iter().map(|p| p.y).collect();
+    assert_eq!(ys, [10.0, 5.0, 7.5, 0.0]);
+    assert_eq!(points[3].x, 30.0);
+    assert_eq!(
+        baseline, 10.0,
+        "All positive, so the baseline is at the bottom"
+    );
+
+    let (_, baseline) = sparkline_points(rect, &[-1.0, 1.0]);
+    assert_eq!(baseline, 5.0);
+
+    let (points, _) = sparkline_points(rect, &[4.0, 4.0]);
+    assert!(points.iter().all(|p| rect.contains(*p)));
+
+    // Missing values are skipped, but keep their place:
+    let (points, _) = sparkline_points(rect, &[1.0, f32::NAN, 3.0, f32::INFINITY]);
+    assert_eq!(points, [Pos2::new(0.0, 10.0), Pos2::new(20.0, 0.0)]);
+}
+
+#[test]
+fn test_grid_level() {
+    let style = GridStyle::default();
+
+    // Lines 10 points apart are just visible, and barely faded in:
+    let (step, fade) = grid_level(10.0, 1.0, &style).unwrap();
+    assert_eq!(step, 10.0);
+    assert!(0.0 < fade && fade < 0.1);
+
+    // Zoomed out, only every fifth line is left:
+    let (step, _) = grid_level(10.0, 0.5, &style).unwrap();
+    assert_eq!(step, 50.0);
+
+    // Zoomed far in, the finest lines are fully faded in:
+    assert_eq!(grid_level(10.0, 8.0, &style), Some((10.0, 1.0)));
+
+    assert_eq!(grid_level(0.0, 1.0, &style), None);
+}
+
+#[test]
+fn test_grid_lines() {
+    let rect = Rect::from_min_max(Pos2::new(0.0, 0.0), Pos2::new(100.0, 50.0));
+    let style = GridStyle {
+        axis_color: Some(Color32::RED),
+        ..Default::default()
+    };
+    let transform = emath::TSTransform::from_translation(Vec2::new(50.0, 0.0));
+    let lines = grid_lines(rect, transform, 10.0, &style, true);
+    let xs: Vec<f32> = lines.iter().map(|(x, _)| *x).collect();
+    assert_eq!(
+        xs,
+        [0.0, 10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 90.0, 100.0]
+    );
+    assert_eq!(lines[5].1, Color32::RED, "The axis is at canvas x = 0");
+    assert_eq!(lines[0].1, style.color, "Major line at canvas x = -50");
+    assert_ne!(lines[1].1, style.color, "Minor lines are faded");
+}
+
+#[test]
+fn test_paint_region_place() {
+    use emath::{pos2, vec2};
+
+    let ctx = Context::default();
+    let _ = ctx.run(Default::default(), |ctx| {
+        let clip_rect = Rect::from_min_size(Pos2::ZERO, vec2(110.0, 120.0));
+        let painter = Painter::new(ctx.clone(), LayerId::background(), clip_rect);
+        let placeholder = Rect::from_min_size(Pos2::ZERO, vec2(10.0, 10.0));
+        let region = painter.paint_region(placeholder, |painter| {
+            painter.rect_filled(placeholder, 0.0, Color32::RED);
+        });
+        painter.rect_filled(placeholder, 0.0, Color32::BLUE);
+
+        let target = Rect::from_min_size(pos2(100.0, 100.0), vec2(20.0, 40.0));
+        assert_eq!(
+            region.translation_and_scale_to(target),
+            (vec2(100.0, 100.0), vec2(2.0, 4.0))
+        );
+        region.place(ctx, target);
+
+        // Only the shapes painted in the region are moved:
+        let mut rects = vec![];
+        painter.for_each_shape(|clipped| {
+            if let Shape::Rect(rect) = &clipped.shape {
+                rects.push(rect.rect);
+            }
+            assert_eq!(clipped.clip_rect, clip_rect, "The clip rect is not moved");
+        });
+        assert_eq!(rects, [target, placeholder]);
+    });
+}
+
+#[test]
+fn test_points() {
+    let ctx = Context::default();
+    let _ = ctx.run(Default::default(), |ctx| {
+        let clip_rect = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+        let painter = Painter::new(ctx.clone(), LayerId::background(), clip_rect);
+        let points = [
+            Pos2::new(10.0, 10.0),
+            Pos2::new(500.0, 10.0), // outside the clip rect
+            Pos2::new(50.0, 50.0),
+        ];
+        let style = PointStyle::new(PointMarker::Square, Color32::WHITE)
+            .colors(vec![Color32::RED, Color32::GREEN]);
+        painter.points(&points, 2.0, style);
+
+        let mut mesh = None;
+        painter.for_each_shape(|clipped| {
+            if let Shape::Mesh(m) = &clipped.shape {
+                mesh = Some(m.clone());
+            }
+        });
+        let mesh = mesh.expect("points should be a single mesh");
+
+        // Two visible squares, each with an inner and an outer (feathered) vertex per corner:
+        assert_eq!(mesh.vertices.len(), 2 * 8);
+        assert_eq!(mesh.vertices[0].color, Color32::RED);
+        assert_eq!(mesh.vertices[8].color, Color32::WHITE);
+        assert_eq!(mesh.vertices[1].color, Color32::TRANSPARENT);
+        assert!(mesh.calc_bounds().contains_rect(Rect::from_center_size(
+            Pos2::new(50.0, 50.0),
+            vec2(4.0, 4.0)
+        )));
+    });
+}
+
+#[test]
+fn test_live_path_is_not_copied() {
+    use epaint::{pos2, LivePath, Mesh};
+
+    let ctx = Context::default();
+    let mut trace = LivePath::new(Stroke::new(1.0, Color32::WHITE));
+    let frame = |trace: &mut LivePath, point| {
+        trace.push(point);
+        let output = ctx.run(Default::default(), |ctx| {
+            let painter = ctx.layer_painter(LayerId::background());
+            painter.live_path(trace);
+        });
+        let meshes: Vec<Arc<Mesh>> = output
+            .shapes
+            .into_iter()
+            .filter_map(|clipped| match clipped.shape {
+                Shape::Mesh(mesh) => Some(mesh),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(meshes.len(), 1);
+        Arc::as_ptr(&meshes[0])
+    };
+
+    trace.push(pos2(0.0, 0.0));
+    let first = frame(&mut trace, pos2(10.0, 10.0));
+    let second = frame(&mut trace, pos2(20.0, 0.0));
+    assert_eq!(
+        first, second,
+        "Once the shapes of the last frame are gone, the mesh is extended in place"
+    );
+}
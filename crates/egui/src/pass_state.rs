@@ -190,6 +190,9 @@ pub struct PassState {
     /// All [`Id`]s that were used this pass.
     pub used_ids: IdMap<Rect>,
 
+    /// The [`Id`]s that were used by more than one widget this pass.
+    pub id_clashes: Vec<crate::IdClash>,
+
     /// All widgets produced this pass.
     pub widgets: WidgetRects,
 
@@ -214,6 +217,15 @@ pub struct PassState {
     /// The current scroll area should scroll to this range (horizontal, vertical).
     pub scroll_target: [Option<ScrollTarget>; 2],
 
+    /// The [`crate::ScrollArea`] with this id should scroll so that this rectangle is centered.
+    ///
+    /// Unlike [`Self::scroll_target`], this only affects that one scroll area.
+    pub(crate) scroll_area_target: Option<(Id, Rect)>,
+
+    /// The [`crate::ScrollArea`]s shown this pass, innermost first,
+    /// with the layer and clip rectangle of their contents.
+    pub(crate) scroll_areas: Vec<(Id, LayerId, Rect)>,
+
     /// The current scroll area should scroll by this much.
     ///
     /// The delta dictates how the _content_ should move.
@@ -231,14 +243,22 @@ pub struct PassState {
     /// Highlight these widgets the next pass.
     pub highlight_next_pass: IdSet,
 
+    /// The paint passes of this pass, and where in their layer they will paint.
+    pub(crate) paint_pass_slots: Vec<(crate::context::PaintPass, crate::layers::ShapeIdx)>,
+
     #[cfg(debug_assertions)]
     pub debug_rect: Option<DebugRect>,
+
+    /// Every call site that made each [`Id`] with [`crate::Ui::push_id_stable`] this pass.
+    #[cfg(debug_assertions)]
+    pub(crate) id_sources: IdMap<Vec<crate::IdSource>>,
 }
 
 impl Default for PassState {
     fn default() -> Self {
         Self {
             used_ids: Default::default(),
+            id_clashes: Default::default(),
             widgets: Default::default(),
             layers: Default::default(),
             tooltips: Default::default(),
@@ -246,13 +266,18 @@ impl Default for PassState {
             unused_rect: Rect::NAN,
             used_by_panels: Rect::NAN,
             scroll_target: [None, None],
+            scroll_area_target: None,
+            scroll_areas: Default::default(),
             scroll_delta: (Vec2::default(), style::ScrollAnimation::none()),
             #[cfg(feature = "accesskit")]
             accesskit_state: None,
             highlight_next_pass: Default::default(),
+            paint_pass_slots: Default::default(),
 
             #[cfg(debug_assertions)]
             debug_rect: None,
+            #[cfg(debug_assertions)]
+            id_sources: Default::default(),
         }
     }
 }
@@ -262,6 +287,7 @@ impl PassState {
         profiling::function_scope!();
         let Self {
             used_ids,
+            id_clashes,
             widgets,
             tooltips,
             layers,
@@ -269,16 +295,23 @@ impl PassState {
             unused_rect,
             used_by_panels,
             scroll_target,
+            scroll_area_target,
+            scroll_areas,
             scroll_delta,
             #[cfg(feature = "accesskit")]
             accesskit_state,
             highlight_next_pass,
+            paint_pass_slots,
 
             #[cfg(debug_assertions)]
             debug_rect,
+            #[cfg(debug_assertions)]
+            id_sources,
         } = self;
 
         used_ids.clear();
+        id_clashes.clear();
+        paint_pass_slots.clear();
         widgets.clear();
         tooltips.clear();
         layers.clear();
@@ -286,11 +319,14 @@ impl PassState {
         *unused_rect = screen_rect;
         *used_by_panels = Rect::NOTHING;
         *scroll_target = [None, None];
+        *scroll_area_target = None;
+        scroll_areas.clear();
         *scroll_delta = Default::default();
 
         #[cfg(debug_assertions)]
         {
             *debug_rect = None;
+            id_sources.clear();
         }
 
         #[cfg(feature = "accesskit")]
@@ -0,0 +1,118 @@
+//! Small charts that don't need a full plotting library: polar plots and radar charts.
+//!
+//! For line plots, bar charts etc. with zooming and panning, see
+//! [`egui_plot`](https://github.com/emilk/egui_plot).
+
+mod polar;
+mod radar;
+
+pub use self::{
+    polar::{PolarLine, PolarPlot},
+    radar::{RadarChart, RadarSeries},
+};
+
+use std::f32::consts::TAU;
+
+use emath::{vec2, Pos2, Rangef};
+use epaint::{ArcShape, Color32, Shape, Stroke};
+
+/// Maps polar coordinates to the screen.
+///
+/// Angles are in radians, counter-clockwise from the positive x-axis (pointing right).
+#[derive(Clone, Copy, Debug)]
+struct PolarTransform {
+    center: Pos2,
+
+    /// The radius of the plot, in points.
+    radius: f32,
+
+    /// The value at the outer edge of the plot.
+    max_value: f32,
+}
+
+impl PolarTransform {
+    fn to_screen(self, value: f32, angle: f32) -> Pos2 {
+        let r = self.radius * value / self.max_value;
+        self.center + r * vec2(angle.cos(), -angle.sin())
+    }
+
+    /// A circle, or part of one, of constant value.
+    fn ring(&self, value: f32, angles: Rangef, stroke: Stroke) -> Shape {
+        let radius = self.radius * value / self.max_value;
+        if TAU <= angles.span() {
+            return Shape::circle_stroke(self.center, radius, stroke);
+        }
+
+        // Angles grow clockwise on the screen, so we go from the end of the range to its start:
+        let arc = ArcShape::new(
+            self.center,
+            self.to_screen(value, angles.max),
+            self.to_screen(value, angles.min),
+            vec2(radius, radius),
+            0.0,
+            false,
+            true,
+            Color32::TRANSPARENT,
+            stroke,
+        );
+        // Only stroke the curve, not the chord closing it:
+        Shape::line(arc.flatten(Some(4.0)), stroke)
+    }
+}
+
+/// The smallest number of the form `{1, 2, 5} * 10^n` that is at least `value`.
+pub(crate) fn nice_ceil(value: f32) -> f32 {
+    if value <= 0.0 || !value.is_finite() {
+        return 1.0;
+    }
+    let magnitude = 10_f32.powf(value.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|step| step * magnitude)
+        .find(|&nice| value <= nice * 1.0001)
+        .unwrap_or(10.0 * magnitude)
+}
+
+/// Formats a value for a grid label, without needless decimals.
+///
+/// Uses the named unit of [`crate::Style::units`], if any.
+pub(crate) fn format_value(style: &crate::Style, unit: Option<&str>, value: f32) -> String {
+    let rounded = (value * 1000.0).round() / 1000.0;
+    crate::units::format_value(style, unit, rounded as f64, 0..=3)
+}
+
+/// The default colors of the series, in order.
+fn auto_color(index: usize) -> Color32 {
+    // Spread the hues with the golden ratio:
+    let hue = (index as f32 * 0.618_034).fract();
+    crate::ecolor::Hsva::new(hue, 0.85, 0.5, 1.0).into()
+}
+
+/// A point to center a label on, just outside the plot at `angle`.
+fn label_pos(transform: &PolarTransform, angle: f32, margin: f32) -> Pos2 {
+    let pos = transform.to_screen(transform.max_value, angle);
+    pos + margin * vec2(angle.cos(), -angle.sin())
+}
+
+#[test]
+fn test_nice_ceil() {
+    assert_eq!(nice_ceil(0.7), 1.0);
+    assert_eq!(nice_ceil(1.0), 1.0);
+    assert_eq!(nice_ceil(1.3), 2.0);
+    assert_eq!(nice_ceil(42.0), 50.0);
+    assert_eq!(nice_ceil(510.0), 1000.0);
+    assert_eq!(nice_ceil(0.0), 1.0);
+}
+
+#[test]
+fn test_polar_transform() {
+    use emath::pos2;
+
+    let transform = PolarTransform {
+        center: pos2(100.0, 100.0),
+        radius: 50.0,
+        max_value: 10.0,
+    };
+    let up = transform.to_screen(10.0, TAU / 4.0);
+    assert!((up - pos2(100.0, 50.0)).length() < 1e-3);
+}
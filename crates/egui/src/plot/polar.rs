@@ -0,0 +1,280 @@
+use std::f32::consts::TAU;
+
+use emath::{Align2, Pos2, Rangef, Vec2};
+use epaint::{Color32, Shape, Stroke};
+
+use super::{auto_color, format_value, label_pos, nice_ceil, PolarTransform};
+use crate::{Response, Sense, TextStyle, Ui};
+
+/// Points in a [`PolarPlot`], connected by straight lines.
+#[derive(Clone, Debug)]
+pub struct PolarLine {
+    points: Vec<[f32; 2]>,
+    name: String,
+    color: Option<Color32>,
+    width: f32,
+    closed: bool,
+}
+
+impl PolarLine {
+    /// The points are `[radius, angle]`, with the angle in radians,
+    /// counter-clockwise from the positive x-axis.
+    pub fn new(points: impl IntoIterator<Item = [f32; 2]>) -> Self {
+        Self {
+            points: points.into_iter().collect(),
+            name: String::new(),
+            color: None,
+            width: 1.5,
+            closed: false,
+        }
+    }
+
+    /// Shown when hovering the points.
+    #[inline]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Default: picked from the index of the line.
+    #[inline]
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// The width of the line, in points.
+    ///
+    /// Default: `1.5`.
+    #[inline]
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Connect the last point to the first one.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+}
+
+/// A plot of values against angles, with circular gridlines.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use std::f32::consts::TAU;
+///
+/// let spiral = egui::plot::PolarLine::new(
+///     (0..100).map(|i| [i as f32, i as f32 / 100.0 * 2.0 * TAU]),
+/// )
+/// .name("Spiral");
+/// egui::plot::PolarPlot::new().show(ui, [spiral]);
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct PolarPlot {
+    size: Vec2,
+    max_value: Option<f32>,
+    angles: Rangef,
+    num_rings: usize,
+    num_spokes: usize,
+    unit: Option<String>,
+}
+
+impl Default for PolarPlot {
+    fn default() -> Self {
+        Self {
+            size: Vec2::splat(200.0),
+            max_value: None,
+            angles: Rangef::new(0.0, TAU),
+            num_rings: 4,
+            num_spokes: 12,
+            unit: None,
+        }
+    }
+}
+
+impl PolarPlot {
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The size of the plot, including the angle labels.
+    ///
+    /// Default: `200x200`.
+    #[inline]
+    pub fn size(mut self, size: impl Into<Vec2>) -> Self {
+        self.size = size.into();
+        self
+    }
+
+    /// The value at the outer ring.
+    ///
+    /// Default: the largest value of the lines, rounded up to a round number.
+    #[inline]
+    pub fn max_value(mut self, max_value: f32) -> Self {
+        self.max_value = Some(max_value);
+        self
+    }
+
+    /// Only show this range of angles, in radians, e.g. `0.0..=PI` for the upper half.
+    ///
+    /// Default: the full circle.
+    #[inline]
+    pub fn angle_range(mut self, angles: impl Into<Rangef>) -> Self {
+        self.angles = angles.into();
+        self
+    }
+
+    /// How many circles to draw between the center and the edge.
+    ///
+    /// Default: `4`.
+    #[inline]
+    pub fn num_rings(mut self, num_rings: usize) -> Self {
+        self.num_rings = num_rings;
+        self
+    }
+
+    /// How many lines to draw from the center out, spread evenly over the angle range.
+    ///
+    /// Default: `12`, i.e. one every 30° for the full circle.
+    #[inline]
+    pub fn num_spokes(mut self, num_spokes: usize) -> Self {
+        self.num_spokes = num_spokes;
+        self
+    }
+
+    /// Show the values (the radii) in one of the units of [`crate::Style::units`], e.g. `"length"`.
+    #[inline]
+    pub fn unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+
+    /// Show the lines.
+    ///
+    /// Hovering a point shows its value and angle.
+    pub fn show(self, ui: &mut Ui, lines: impl IntoIterator<Item = PolarLine>) -> Response {
+        let Self {
+            size,
+            max_value,
+            angles,
+            num_rings,
+            num_spokes,
+            unit,
+        } = self;
+        let unit = unit.as_deref();
+
+        let lines: Vec<PolarLine> = lines.into_iter().collect();
+        let max_value = max_value.unwrap_or_else(|| {
+            let largest = lines
+                .iter()
+                .flat_map(|line| &line.points)
+                .map(|[value, _]| value.abs())
+                .fold(0.0, f32::max);
+            nice_ceil(largest)
+        });
+
+        let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+        if !ui.is_rect_visible(rect) {
+            return response;
+        }
+
+        let font_id = TextStyle::Small.resolve(ui.style());
+        let label_margin = 2.0 * font_id.size;
+        let transform = PolarTransform {
+            center: rect.center(),
+            radius: (0.5 * rect.size().min_elem() - label_margin).max(1.0),
+            max_value,
+        };
+
+        let visuals = ui.visuals();
+        let grid_stroke = visuals.widgets.noninteractive.bg_stroke;
+        let text_color = visuals.weak_text_color();
+        let painter = ui.painter_at(rect);
+
+        // The grid:
+        for ring in 1..=num_rings {
+            let value = max_value * ring as f32 / num_rings as f32;
+            painter.add(transform.ring(value, angles, grid_stroke));
+            painter.text(
+                transform.to_screen(value, angles.min),
+                Align2::LEFT_BOTTOM,
+                format_value(ui.style(), unit, value),
+                font_id.clone(),
+                text_color,
+            );
+        }
+        let full_circle = TAU <= angles.span();
+        let num_spoke_angles = if full_circle {
+            num_spokes
+        } else {
+            num_spokes + 1
+        };
+        for spoke in 0..num_spoke_angles {
+            let angle = angles.min + angles.span() * spoke as f32 / num_spokes.max(1) as f32;
+            painter.line_segment(
+                [transform.center, transform.to_screen(max_value, angle)],
+                grid_stroke,
+            );
+            painter.text(
+                label_pos(&transform, angle, 0.5 * label_margin),
+                Align2::CENTER_CENTER,
+                format!("{:.0}°", angle.to_degrees()),
+                font_id.clone(),
+                text_color,
+            );
+        }
+
+        // The lines, and the point closest to the pointer:
+        let mut hovered: Option<(f32, &PolarLine, [f32; 2], Color32)> = None;
+        let hover_pos = response.hover_pos();
+        for (index, line) in lines.iter().enumerate() {
+            let color = line.color.unwrap_or_else(|| auto_color(index));
+            let points: Vec<Pos2> = line
+                .points
+                .iter()
+                .map(|&[value, angle]| transform.to_screen(value, angle))
+                .collect();
+
+            if let Some(hover_pos) = hover_pos {
+                for (pos, point) in points.iter().zip(&line.points) {
+                    let distance = pos.distance(hover_pos);
+                    if hovered.map_or(true, |(closest, ..)| distance < closest) {
+                        hovered = Some((distance, line, *point, color));
+                    }
+                }
+            }
+
+            let stroke = Stroke::new(line.width, color);
+            if line.closed {
+                painter.add(Shape::closed_line(points, stroke));
+            } else {
+                painter.add(Shape::line(points, stroke));
+            }
+        }
+
+        let max_hover_distance = ui.style().interaction.interact_radius.max(8.0);
+        if let Some((distance, line, [value, angle], color)) = hovered {
+            if distance <= max_hover_distance {
+                let pos = transform.to_screen(value, angle);
+                painter.circle_filled(pos, 3.0, color);
+                let mut text = format!(
+                    "r = {}\nθ = {}°",
+                    format_value(ui.style(), unit, value),
+                    format_value(ui.style(), None, angle.to_degrees())
+                );
+                if !line.name.is_empty() {
+                    text = format!("{}\n{text}", line.name);
+                }
+                return response.on_hover_text_at_pointer(text);
+            }
+        }
+        response
+    }
+}
@@ -0,0 +1,256 @@
+use std::f32::consts::TAU;
+
+use emath::{Align2, Pos2, Rangef, Vec2};
+use epaint::{Color32, Mesh, Shape, Stroke};
+
+use super::{auto_color, format_value, label_pos, nice_ceil, PolarTransform};
+use crate::{Response, Sense, TextStyle, Ui};
+
+/// One polygon in a [`RadarChart`], with one value per axis.
+#[derive(Clone, Debug)]
+pub struct RadarSeries {
+    name: String,
+    values: Vec<f32>,
+    color: Option<Color32>,
+    fill: bool,
+}
+
+impl RadarSeries {
+    /// The values are in the order of the axes of the chart.
+    pub fn new(name: impl Into<String>, values: impl IntoIterator<Item = f32>) -> Self {
+        Self {
+            name: name.into(),
+            values: values.into_iter().collect(),
+            color: None,
+            fill: true,
+        }
+    }
+
+    /// Default: picked from the index of the series.
+    #[inline]
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Fill the polygon with a translucent version of the color.
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn fill(mut self, fill: bool) -> Self {
+        self.fill = fill;
+        self
+    }
+}
+
+/// Compares a few values over several axes, e.g. the stats of characters in a game.
+///
+/// Also known as a spider chart.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{RadarChart, RadarSeries};
+///
+/// RadarChart::new(["Speed", "Power", "Range", "Armor", "Cost"])
+///     .max_value(10.0)
+///     .show(
+///         ui,
+///         [
+///             RadarSeries::new("Scout", [9.0, 3.0, 6.0, 2.0, 3.0]),
+///             RadarSeries::new("Tank", [3.0, 8.0, 4.0, 9.0, 8.0]),
+///         ],
+///     );
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct RadarChart {
+    axes: Vec<String>,
+    size: Vec2,
+    max_value: Option<f32>,
+    num_rings: usize,
+    round_grid: bool,
+    unit: Option<String>,
+}
+
+impl RadarChart {
+    /// The axes go clockwise, starting at the top.
+    pub fn new(axes: impl IntoIterator<Item = impl ToString>) -> Self {
+        Self {
+            axes: axes.into_iter().map(|axis| axis.to_string()).collect(),
+            size: Vec2::splat(200.0),
+            max_value: None,
+            num_rings: 4,
+            round_grid: false,
+            unit: None,
+        }
+    }
+
+    /// The size of the chart, including the axis labels.
+    ///
+    /// Default: `200x200`.
+    #[inline]
+    pub fn size(mut self, size: impl Into<Vec2>) -> Self {
+        self.size = size.into();
+        self
+    }
+
+    /// The value at the end of the axes.
+    ///
+    /// Default: the largest value of the series, rounded up to a round number.
+    #[inline]
+    pub fn max_value(mut self, max_value: f32) -> Self {
+        self.max_value = Some(max_value);
+        self
+    }
+
+    /// How many gridlines to draw between the center and the edge.
+    ///
+    /// Default: `4`.
+    #[inline]
+    pub fn num_rings(mut self, num_rings: usize) -> Self {
+        self.num_rings = num_rings;
+        self
+    }
+
+    /// Draw the gridlines as circles instead of polygons.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn round_grid(mut self, round_grid: bool) -> Self {
+        self.round_grid = round_grid;
+        self
+    }
+
+    /// Show the values in one of the units of [`crate::Style::units`], e.g. `"bytes"`.
+    #[inline]
+    pub fn unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+
+    /// The angle of an axis, in radians counter-clockwise from the positive x-axis.
+    fn axis_angle(&self, axis: usize) -> f32 {
+        TAU / 4.0 - TAU * axis as f32 / self.axes.len() as f32
+    }
+
+    /// Show the series.
+    ///
+    /// Hovering a corner of a series shows its value.
+    pub fn show(self, ui: &mut Ui, series: impl IntoIterator<Item = RadarSeries>) -> Response {
+        let series: Vec<RadarSeries> = series.into_iter().collect();
+        let max_value = self.max_value.unwrap_or_else(|| {
+            let largest = series
+                .iter()
+                .flat_map(|series| &series.values)
+                .fold(0.0, |largest: f32, value| largest.max(value.abs()));
+            nice_ceil(largest)
+        });
+
+        let (rect, response) = ui.allocate_exact_size(self.size, Sense::hover());
+        if !ui.is_rect_visible(rect) || self.axes.is_empty() {
+            return response;
+        }
+
+        let font_id = TextStyle::Small.resolve(ui.style());
+        let label_margin = 2.0 * font_id.size;
+        let transform = PolarTransform {
+            center: rect.center(),
+            radius: (0.5 * rect.size().min_elem() - label_margin).max(1.0),
+            max_value,
+        };
+
+        let visuals = ui.visuals();
+        let grid_stroke = visuals.widgets.noninteractive.bg_stroke;
+        let text_color = visuals.weak_text_color();
+        let painter = ui.painter_at(rect);
+
+        let corners = |values: &mut dyn Iterator<Item = f32>| -> Vec<Pos2> {
+            values
+                .enumerate()
+                .map(|(axis, value)| transform.to_screen(value, self.axis_angle(axis)))
+                .collect()
+        };
+
+        // The grid:
+        for ring in 1..=self.num_rings {
+            let value = max_value * ring as f32 / self.num_rings as f32;
+            if self.round_grid {
+                painter.add(transform.ring(value, Rangef::new(0.0, TAU), grid_stroke));
+            } else {
+                let ring = corners(&mut std::iter::repeat(value).take(self.axes.len()));
+                painter.add(Shape::closed_line(ring, grid_stroke));
+            }
+            painter.text(
+                transform.to_screen(value, self.axis_angle(0)),
+                Align2::LEFT_CENTER,
+                format!(" {}", format_value(ui.style(), self.unit.as_deref(), value)),
+                font_id.clone(),
+                text_color,
+            );
+        }
+        for (axis, name) in self.axes.iter().enumerate() {
+            let angle = self.axis_angle(axis);
+            painter.line_segment(
+                [transform.center, transform.to_screen(max_value, angle)],
+                grid_stroke,
+            );
+            painter.text(
+                label_pos(&transform, angle, 0.5 * label_margin),
+                Align2::CENTER_CENTER,
+                name,
+                font_id.clone(),
+                visuals.text_color(),
+            );
+        }
+
+        // The series, and the corner closest to the pointer:
+        let mut hovered: Option<(f32, &RadarSeries, usize, Pos2, Color32)> = None;
+        let hover_pos = response.hover_pos();
+        for (index, series) in series.iter().enumerate() {
+            let color = series.color.unwrap_or_else(|| auto_color(index));
+            let points = corners(&mut series.values.iter().copied().take(self.axes.len()));
+
+            if series.fill {
+                // Every corner is on its own axis, so a fan from the center covers the polygon,
+                // even when it isn't convex:
+                let fill = color.gamma_multiply(0.25);
+                let mut mesh = Mesh::default();
+                mesh.colored_vertex(transform.center, fill);
+                for &point in &points {
+                    mesh.colored_vertex(point, fill);
+                }
+                let n = points.len() as u32;
+                for i in 0..n {
+                    mesh.add_triangle(0, 1 + i, 1 + (i + 1) % n);
+                }
+                painter.add(mesh);
+            }
+
+            if let Some(hover_pos) = hover_pos {
+                for (axis, &pos) in points.iter().enumerate() {
+                    let distance = pos.distance(hover_pos);
+                    if hovered.map_or(true, |(closest, ..)| distance < closest) {
+                        hovered = Some((distance, series, axis, pos, color));
+                    }
+                }
+            }
+
+            painter.add(Shape::closed_line(points, Stroke::new(1.5, color)));
+        }
+
+        let max_hover_distance = ui.style().interaction.interact_radius.max(8.0);
+        if let Some((distance, series, axis, pos, color)) = hovered {
+            if distance <= max_hover_distance {
+                painter.circle_filled(pos, 3.0, color);
+                let text = format!(
+                    "{}\n{}: {}",
+                    series.name,
+                    self.axes[axis],
+                    format_value(ui.style(), self.unit.as_deref(), series.values[axis])
+                );
+                return response.on_hover_text_at_pointer(text);
+            }
+        }
+        response
+    }
+}
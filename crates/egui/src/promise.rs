@@ -0,0 +1,177 @@
+//! Run a [`Future`] from the UI, see [`crate::Context::promise`].
+
+use std::{
+    any::Any,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Poll, Wake, Waker},
+};
+
+use epaint::mutex::Mutex;
+
+use crate::{context::WeakContext, Context, Id, IdMap, ViewportId};
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+enum Task<T> {
+    Pending(BoxFuture<T>),
+    Ready(T),
+}
+
+/// A future, or its output once it has finished.
+pub(crate) struct SharedTask<T>(Arc<Mutex<Task<T>>>);
+
+impl<T: Send + 'static> SharedTask<T> {
+    pub fn new(future: impl Future<Output = T> + Send + 'static) -> Self {
+        Self(Arc::new(Mutex::new(Task::Pending(Box::pin(future)))))
+    }
+
+    /// Poll the future if it isn't done yet, and return its output if it is.
+    ///
+    /// When the future makes progress later, `viewport_id` is repainted so we poll again.
+    pub fn poll(&self, ctx: &Context, viewport_id: ViewportId) -> Option<T>
+    where
+        T: Clone,
+    {
+        let mut task = self.0.lock();
+        if let Task::Pending(future) = &mut *task {
+            let waker = Waker::from(Arc::new(RepaintWaker {
+                ctx: ctx.downgrade(),
+                viewport_id,
+            }));
+            let mut cx = std::task::Context::from_waker(&waker);
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                *task = Task::Ready(output);
+            }
+        }
+        match &*task {
+            Task::Pending(_) => None,
+            Task::Ready(output) => Some(output.clone()),
+        }
+    }
+}
+
+/// Weak, since the waker is usually kept by the future, which is kept by the context.
+struct RepaintWaker {
+    ctx: WeakContext,
+    viewport_id: ViewportId,
+}
+
+impl Wake for RepaintWaker {
+    fn wake(self: Arc<Self>) {
+        if let Some(ctx) = self.ctx.upgrade() {
+            ctx.request_repaint_of(self.viewport_id);
+        }
+    }
+}
+
+struct Entry {
+    used_this_pass: bool,
+
+    /// A `Mutex<Task<T>>`.
+    task: Arc<dyn Any + Send + Sync>,
+}
+
+/// The running futures of a viewport.
+///
+/// A future that isn't asked for during a pass is dropped, which cancels it.
+#[derive(Default)]
+pub(crate) struct Promises {
+    entries: IdMap<Entry>,
+}
+
+impl Promises {
+    /// The task with the given id, if there is one with output `T`.
+    pub fn get<T: Send + 'static>(&mut self, id: Id) -> Option<SharedTask<T>> {
+        let entry = self.entries.get_mut(&id)?;
+        let task = entry.task.clone().downcast::<Mutex<Task<T>>>().ok()?;
+        entry.used_this_pass = true;
+        Some(SharedTask(task))
+    }
+
+    pub fn insert<T: Send + 'static>(&mut self, id: Id, task: &SharedTask<T>) {
+        let entry = Entry {
+            used_this_pass: true,
+            task: task.0.clone(),
+        };
+        self.entries.insert(id, entry);
+    }
+
+    pub fn remove(&mut self, id: Id) {
+        self.entries.remove(&id);
+    }
+
+    /// Drop the tasks that weren't used this pass.
+    pub fn end_pass(&mut self) {
+        self.entries
+            .retain(|_, entry| std::mem::take(&mut entry.used_this_pass));
+    }
+}
+
+#[test]
+fn test_promise() {
+    /// Finishes once a value is sent.
+    #[derive(Clone, Default)]
+    struct Oneshot(Arc<Mutex<(Option<u32>, Option<Waker>)>>);
+
+    impl Future for Oneshot {
+        type Output = u32;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<u32> {
+            let mut state = self.0.lock();
+            if let Some(value) = state.0 {
+                Poll::Ready(value)
+            } else {
+                state.1 = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    let ctx = Context::default();
+    let id = Id::new("task");
+    let oneshot = Oneshot::default();
+    let run = |show: bool| {
+        let mut output = None;
+        let _ = ctx.run(Default::default(), |ctx| {
+            if show {
+                output = ctx.promise(id, || oneshot.clone());
+            }
+        });
+        output
+    };
+
+    assert_eq!(run(true), None);
+    assert_eq!(run(true), None);
+    assert!(!ctx.has_requested_repaint(), "Nothing happened yet");
+
+    // Finish the future, e.g. from another thread:
+    let waker = {
+        let mut state = oneshot.0.lock();
+        state.0 = Some(42);
+        state.1.take().unwrap()
+    };
+    waker.wake();
+    assert!(ctx.has_requested_repaint());
+    assert_eq!(run(true), Some(42));
+    assert_eq!(run(true), Some(42));
+
+    // Hiding the widget cancels an unfinished future:
+    let pending = Oneshot::default();
+    let _ = ctx.run(Default::default(), |ctx| {
+        let _ = ctx.promise(Id::new("other"), || pending.clone());
+    });
+    assert_eq!(Arc::strong_count(&pending.0), 2);
+    run(false);
+    assert_eq!(Arc::strong_count(&pending.0), 1);
+
+    // A pending future doesn't keep the context alive:
+    let _ = ctx.run(Default::default(), |ctx| {
+        let _ = ctx.promise(Id::new("other"), || pending.clone());
+    });
+    let waker = pending.0.lock().1.take().unwrap();
+    drop(ctx);
+    assert_eq!(Arc::strong_count(&pending.0), 1, "Dropped with the context");
+    waker.wake();
+}
@@ -320,8 +320,14 @@ impl Response {
     }
 
     /// Request that this widget get keyboard focus.
+    ///
+    /// Widgets behind an open [`crate::Modal`] can not be focused.
     pub fn request_focus(&self) {
-        self.ctx.memory_mut(|mem| mem.request_focus(self.id));
+        self.ctx.memory_mut(|mem| {
+            if mem.is_above_modal_layer(self.layer_id) {
+                mem.request_focus(self.id);
+            }
+        });
     }
 
     /// Surrender keyboard focus for this widget.
@@ -442,6 +448,70 @@ impl Response {
         }
     }
 
+    /// Like [`Self::dnd_set_drag_payload`], but also shows `preview` next to the cursor
+    /// while dragging.
+    #[doc(alias = "drag and drop")]
+    pub fn dnd_set_drag_payload_with_preview<Payload: Any + Send + Sync>(
+        &self,
+        payload: Payload,
+        preview: impl Into<Arc<str>>,
+    ) {
+        if self.drag_started() {
+            crate::DragAndDrop::set_preview(&self.ctx, preview);
+        }
+        self.dnd_set_drag_payload(payload);
+    }
+
+    /// If the user started dragging this widget this frame,
+    /// start an OS-level drag of these items, so they can be dropped outside the app
+    /// (e.g. files onto the desktop, or text into another app).
+    ///
+    /// This is only supported by some integrations, see [`crate::OutputCommand::StartDrag`].
+    /// The items are only created when the drag starts.
+    #[doc(alias = "drag and drop")]
+    pub fn dnd_start_native_drag(&self, items: impl FnOnce() -> Vec<crate::NativeDragItem>) {
+        if self.drag_started() {
+            self.ctx.start_native_drag(items());
+        }
+
+        if self.hovered() && !self.sense.senses_click() {
+            self.ctx.set_cursor_icon(CursorIcon::Grab);
+        }
+    }
+
+    /// Drag-and-Drop: Make this widget a drop target for payloads of the given type.
+    ///
+    /// While such a payload is being dragged, an animated outline is painted around the widget,
+    /// which grows stronger when the payload is held over it.
+    ///
+    /// Nested or overlapping drop targets are resolved so that only the innermost is hovered,
+    /// see [`crate::DragAndDrop::register_drop_region`].
+    ///
+    /// Returns what is being held over this widget, if any.
+    /// Use [`Self::dnd_release_payload`] to check if it was dropped.
+    #[doc(alias = "drag and drop")]
+    pub fn dnd_drop_target<Payload: Any + Send + Sync>(&self) -> Option<Arc<Payload>> {
+        crate::DragAndDrop::register_drop_region(self);
+
+        let payload = crate::DragAndDrop::payload::<Payload>(&self.ctx)?;
+        let hovered = crate::DragAndDrop::is_hovered_drop_region(self);
+
+        let t = self
+            .ctx
+            .animate_bool_responsive(self.id.with("dnd_drop_target"), hovered);
+        let mut stroke = self.ctx.style().visuals.selection.stroke;
+        stroke.width = emath::lerp(1.0..=2.0 * stroke.width.max(1.0), t);
+        stroke.color = stroke.color.gamma_multiply(emath::lerp(0.3..=1.0, t));
+        self.ctx.layer_painter(self.layer_id).rect_stroke(
+            self.rect,
+            self.ctx.style().visuals.widgets.inactive.corner_radius,
+            stroke,
+            crate::StrokeKind::Outside,
+        );
+
+        hovered.then_some(payload)
+    }
+
     /// Drag-and-Drop: Return what is being held over this widget, if any.
     ///
     /// Only returns something if [`Self::contains_pointer`] is true,
@@ -461,7 +531,11 @@ impl Response {
     ///
     /// Only returns something if [`Self::contains_pointer`] is true,
     /// the user is drag-dropping something of this type,
-    /// and they released it this frame
+    /// and they released it this frame.
+    ///
+    /// Also returns payloads dropped here from another viewport,
+    /// if this widget was registered with [`crate::DragAndDrop::register_drop_region`]
+    /// (e.g. with [`Self::dnd_drop_target`] or [`crate::Ui::dnd_drop_zone`]).
     #[doc(alias = "drag and drop")]
     pub fn dnd_release_payload<Payload: Any + Send + Sync>(&self) -> Option<Arc<Payload>> {
         // NOTE: we use `response.contains_pointer` here instead of `hovered`, because
@@ -469,10 +543,51 @@ impl Response {
         if self.contains_pointer() && self.ctx.input(|i| i.pointer.any_released()) {
             crate::DragAndDrop::take_payload::<Payload>(&self.ctx)
         } else {
-            None
+            // It may have been dropped here from another viewport:
+            crate::DragAndDrop::take_pending_drop::<Payload>(self)
         }
     }
 
+    /// The files that are being dragged over this widget from outside the app, if any.
+    ///
+    /// Like [`Self::dnd_drop_target`], this registers the widget as a drop region,
+    /// so that only the innermost of nested targets gets the files.
+    /// Use [`Self::dropped_files`] to get the files once they are dropped.
+    #[doc(alias = "drag and drop")]
+    pub fn hovered_files(&self) -> Vec<crate::HoveredFile> {
+        crate::DragAndDrop::register_drop_region(self);
+        let files = self.ctx.input(|i| i.raw.hovered_files.clone());
+        self.files_at_this_widget(files, |file| file.pos)
+    }
+
+    /// The files that were dropped onto this widget from outside the app this frame, if any.
+    ///
+    /// Unlike [`crate::RawInput::dropped_files`], this only contains the files
+    /// dropped onto this widget (or the innermost drop target containing it).
+    #[doc(alias = "drag and drop")]
+    pub fn dropped_files(&self) -> Vec<crate::DroppedFile> {
+        crate::DragAndDrop::register_drop_region(self);
+        let files = self.ctx.input(|i| i.raw.dropped_files.clone());
+        self.files_at_this_widget(files, |file| file.pos)
+    }
+
+    fn files_at_this_widget<File>(
+        &self,
+        mut files: Vec<File>,
+        pos: impl Fn(&File) -> Option<Pos2>,
+    ) -> Vec<File> {
+        if files.is_empty() {
+            return files;
+        }
+        let pointer_pos = self.ctx.pointer_latest_pos();
+        files.retain(|file| {
+            pos(file)
+                .or(pointer_pos)
+                .is_some_and(|pos| crate::DragAndDrop::is_drop_region_at(self, pos))
+        });
+        files
+    }
+
     /// Where the pointer (mouse/touch) were when this widget was clicked or dragged.
     ///
     /// `None` if the widget is not being interacted with.
@@ -646,6 +761,15 @@ impl Response {
         })
     }
 
+    /// Show a tooltip with a title, and optionally a body and keyboard shortcut, if the widget was hovered.
+    ///
+    /// See [`Tooltip::rich`].
+    #[doc(alias = "tooltip")]
+    pub fn on_hover_rich(self, tooltip: crate::RichTooltip) -> Self {
+        tooltip.show_on_hover(&self);
+        self
+    }
+
     /// Highlight this widget, to make it look like it is hovered, even if it isn't.
     ///
     /// The highlight takes one frame to take effect if you call this after the widget has been fully rendered.
@@ -726,6 +850,7 @@ impl Response {
                 interact_rect: self.interact_rect,
                 sense: self.sense | sense,
                 enabled: self.enabled(),
+                parent_id: Id::NULL,
             },
             true,
         )
@@ -952,6 +1077,73 @@ impl Response {
         Popup::context_menu(self).is_open()
     }
 
+    /// Like [`Self::context_menu`], but with the menu described as data,
+    /// so that it can be shown as a native OS menu.
+    ///
+    /// If the integration can't show native context menus (see [`crate::RawInput::native_menus`]),
+    /// the items are shown in an egui context menu instead.
+    ///
+    /// Returns the id of the clicked button, if any.
+    /// With a native menu this comes a frame later, since the menu is shown at the end of the frame.
+    ///
+    /// ```
+    /// # use egui::{NativeMenuItem, Sense};
+    /// # egui::__run_test_ui(|ui| {
+    /// let response = ui.add(egui::Label::new("Right-click me!").sense(Sense::click()));
+    /// let clicked = response.native_context_menu(|| {
+    ///     vec![
+    ///         NativeMenuItem::button("copy", "Copy"),
+    ///         NativeMenuItem::Separator,
+    ///         NativeMenuItem::button("delete", "Delete"),
+    ///     ]
+    /// });
+    /// if clicked == Some(egui::Id::new("delete")) {
+    ///     // …
+    /// }
+    /// # });
+    /// ```
+    pub fn native_context_menu(
+        &self,
+        items: impl FnOnce() -> Vec<crate::NativeMenuItem>,
+    ) -> Option<Id> {
+        if !self.ctx.input(|i| i.raw.native_menus.context_menu) {
+            let mut clicked = None;
+            self.context_menu(|ui| clicked = crate::native_menu::show_items(ui, &items()));
+            return clicked;
+        }
+
+        let state_id = self.id.with("native_context_menu");
+
+        if self.secondary_clicked() {
+            let pos = self.ctx.input(|i| i.pointer.interact_pos())?;
+            let items = items();
+            let mut buttons = Vec::new();
+            crate::NativeMenuItem::visit_buttons(&items, &mut |button| buttons.push(button.id));
+            let open = OpenNativeContextMenu {
+                pass_nr: self.ctx.cumulative_pass_nr(),
+                buttons,
+            };
+            self.ctx.data_mut(|d| d.insert_temp(state_id, open));
+            self.ctx
+                .send_cmd(crate::OutputCommand::ShowContextMenu { pos, items });
+            return None;
+        }
+
+        let open = self
+            .ctx
+            .data(|d| d.get_temp::<OpenNativeContextMenu>(state_id))?;
+        let is_first_pass = self.ctx.output(|o| o.num_completed_passes == 0);
+        if open.pass_nr == self.ctx.cumulative_pass_nr() || !is_first_pass {
+            return None;
+        }
+
+        // The menu is shown at the end of the frame it was opened in, and is closed before the next one,
+        // so this is our only chance to see the click, and there is none if it was dismissed.
+        self.ctx
+            .data_mut(|d| d.remove::<OpenNativeContextMenu>(state_id));
+        crate::native_menu::clicked_item(&self.ctx, &open.buttons)
+    }
+
     /// Draw a debug rectangle over the response displaying the response's id and whether it is
     /// enabled and/or hovered.
     ///
@@ -977,6 +1169,14 @@ impl Response {
     }
 }
 
+/// The buttons of a native context menu that was opened, see [`Response::native_context_menu`].
+#[derive(Clone)]
+pub(crate) struct OpenNativeContextMenu {
+    /// [`Context::cumulative_pass_nr`] of the pass it was opened in.
+    pass_nr: u64,
+    buttons: Vec<Id>,
+}
+
 impl Response {
     /// A logical "or" operation.
     /// For instance `a.union(b).hovered` means "was either a or b hovered?".
@@ -0,0 +1,460 @@
+//! Rulers along the edges of a canvas, with guide lines dragged out of them, like in a design tool.
+//!
+//! See [`Rulers`].
+
+use emath::{pos2, vec2, Align2, Pos2, Rangef, Rect, TSTransform};
+use epaint::{Color32, Stroke};
+
+use crate::{
+    plot::{format_value, nice_ceil},
+    Context, CursorIcon, Id, Painter, Response, Sense, SnapManager, TextStyle, Ui,
+};
+
+/// The guide lines of some [`Rulers`], in canvas coordinates.
+///
+/// Persisted in [`crate::Memory`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Guides {
+    /// The x coordinates of the vertical guides.
+    pub vertical: Vec<f32>,
+
+    /// The y coordinates of the horizontal guides.
+    pub horizontal: Vec<f32>,
+}
+
+impl Guides {
+    pub fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.data_mut(|d| d.get_persisted(id))
+    }
+
+    pub fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|d| d.insert_persisted(id, self));
+    }
+
+    /// Let dragged rectangles snap to the guides.
+    ///
+    /// The [`SnapManager`] should work in canvas coordinates.
+    pub fn add_to(&self, snap: &mut SnapManager) {
+        for &x in &self.vertical {
+            snap.add_vertical_line(x);
+        }
+        for &y in &self.horizontal {
+            snap.add_horizontal_line(y);
+        }
+    }
+}
+
+/// What [`Rulers::show`] returns.
+pub struct RulersResponse {
+    /// The response of the two rulers.
+    ///
+    /// Marked as changed when a guide is added, moved or removed.
+    pub response: Response,
+
+    /// The guides after this frame's changes.
+    pub guides: Guides,
+}
+
+/// Horizontal and vertical rulers along the top and left edges of a canvas,
+/// showing the canvas coordinates as it is panned and zoomed.
+///
+/// Drag from a ruler onto the canvas to add a guide line,
+/// drag a guide to move it, and drag it back onto a ruler to remove it.
+/// The guides are remembered in [`crate::Memory`] as [`Guides`],
+/// and can be snapped to with [`Guides::add_to`].
+///
+/// Show the rulers after the canvas, so that they are on top of it.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut scene_rect = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(400.0, 300.0));
+/// use egui::rulers::Rulers;
+///
+/// let canvas_rect = ui.available_rect_before_wrap();
+/// let scene_layer = egui::Scene::layer_id(ui);
+/// egui::Scene::new().show(ui, &mut scene_rect, |ui| {
+///     ui.label("A design");
+/// });
+///
+/// let to_screen = ui.ctx().layer_transform_to_global(scene_layer).unwrap_or_default();
+/// let rulers = Rulers::new("rulers").show(ui, canvas_rect, to_screen);
+///
+/// let mut snap = egui::SnapManager::new();
+/// rulers.guides.add_to(&mut snap);
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct Rulers {
+    id_salt: Id,
+    thickness: f32,
+    guides: bool,
+    guide_color: Option<Color32>,
+}
+
+impl Rulers {
+    pub fn new(id_salt: impl std::hash::Hash) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            thickness: 20.0,
+            guides: true,
+            guide_color: None,
+        }
+    }
+
+    /// The width of the rulers, in points.
+    ///
+    /// Default: `20.0`.
+    #[inline]
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Can guides be dragged out of the rulers?
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn guides(mut self, guides: bool) -> Self {
+        self.guides = guides;
+        self
+    }
+
+    /// Default: the color of the selection stroke.
+    #[inline]
+    pub fn guide_color(mut self, color: Color32) -> Self {
+        self.guide_color = Some(color);
+        self
+    }
+
+    /// Show the rulers along the top and left edges of `rect`.
+    ///
+    /// `to_screen` maps canvas coordinates to the coordinates of `ui`.
+    pub fn show(self, ui: &Ui, rect: Rect, to_screen: TSTransform) -> RulersResponse {
+        let id = ui.make_persistent_id(self.id_salt);
+        let mut guides = Guides::load(ui.ctx(), id).unwrap_or_default();
+
+        let thickness = self.thickness;
+        let top_ruler = Rect::from_min_max(
+            pos2(rect.left() + thickness, rect.top()),
+            pos2(rect.right(), rect.top() + thickness),
+        );
+        let left_ruler = Rect::from_min_max(
+            pos2(rect.left(), rect.top() + thickness),
+            pos2(rect.left() + thickness, rect.bottom()),
+        );
+        let canvas = Rect::from_min_max(rect.min + vec2(thickness, thickness), rect.max);
+
+        let top = ui.interact(top_ruler, id.with("top"), Sense::drag());
+        let left = ui.interact(left_ruler, id.with("left"), Sense::drag());
+        let mut response = top.clone().union(left.clone());
+        if !to_screen.is_valid() {
+            return RulersResponse { response, guides };
+        }
+        let to_canvas = to_screen.inverse();
+
+        let visuals = ui.visuals();
+        let guide_stroke = Stroke::new(
+            1.0 / ui.pixels_per_point(),
+            self.guide_color.unwrap_or(visuals.selection.stroke.color),
+        );
+        let painter = ui.painter_at(canvas);
+        let pointer = ui.ctx().pointer_latest_pos();
+        let mut changed = false;
+
+        if self.guides {
+            changed |= move_guides(ui, id, canvas, to_screen, &mut guides);
+
+            // Drag out new guides:
+            for (new, vertical) in [(&top, false), (&left, true)] {
+                let Some(pos) = pointer.filter(|pos| canvas.contains(*pos)) else {
+                    continue;
+                };
+                if new.dragged() {
+                    ui.ctx().set_cursor_icon(if vertical {
+                        CursorIcon::ResizeColumn
+                    } else {
+                        CursorIcon::ResizeRow
+                    });
+                    paint_guide(&painter, canvas, vertical, pos, guide_stroke);
+                }
+                if new.drag_stopped() {
+                    let pos = to_canvas * pos;
+                    if vertical {
+                        guides.vertical.push(pos.x);
+                    } else {
+                        guides.horizontal.push(pos.y);
+                    }
+                    changed = true;
+                }
+            }
+        }
+
+        for &x in &guides.vertical {
+            let pos = to_screen * pos2(x, 0.0);
+            paint_guide(&painter, canvas, true, pos, guide_stroke);
+        }
+        for &y in &guides.horizontal {
+            let pos = to_screen * pos2(0.0, y);
+            paint_guide(&painter, canvas, false, pos, guide_stroke);
+        }
+
+        if ui.is_rect_visible(rect) {
+            paint_ruler(ui, top_ruler, false, to_screen, pointer);
+            paint_ruler(ui, left_ruler, true, to_screen, pointer);
+            let corner = Rect::from_min_size(rect.min, vec2(thickness, thickness));
+            ui.painter()
+                .rect_filled(corner, 0.0, ui.visuals().faint_bg_color);
+        }
+
+        if changed {
+            response.mark_changed();
+            guides.clone().store(ui.ctx(), id);
+        }
+        RulersResponse { response, guides }
+    }
+}
+
+/// Let the user drag the existing guides, removing the ones dropped outside of the canvas.
+fn move_guides(ui: &Ui, id: Id, canvas: Rect, to_screen: TSTransform, guides: &mut Guides) -> bool {
+    let to_canvas = to_screen.inverse();
+    let pointer = ui.ctx().pointer_latest_pos();
+    let mut changed = false;
+
+    for vertical in [true, false] {
+        let positions = if vertical {
+            &mut guides.vertical
+        } else {
+            &mut guides.horizontal
+        };
+
+        let mut removed = None;
+        for (i, position) in positions.iter_mut().enumerate() {
+            let screen = if vertical {
+                (to_screen * pos2(*position, 0.0)).x
+            } else {
+                (to_screen * pos2(0.0, *position)).y
+            };
+            let hit_rect = if vertical {
+                Rect::from_x_y_ranges(Rangef::point(screen).expand(3.0), canvas.y_range())
+            } else {
+                Rect::from_x_y_ranges(canvas.x_range(), Rangef::point(screen).expand(3.0))
+            };
+            let guide_id = id.with((vertical, i));
+            let is_dragged =
+                ui.ctx().is_being_dragged(guide_id) || ui.ctx().drag_stopped_id() == Some(guide_id);
+            let hit_rect = if is_dragged {
+                // Keep interacting while it is dragged onto a ruler, so that we see it dropped there.
+                hit_rect
+            } else if canvas.intersects(hit_rect) {
+                hit_rect.intersect(canvas)
+            } else {
+                continue;
+            };
+
+            let response = ui.interact(hit_rect, guide_id, Sense::drag());
+            if response.hovered() || response.dragged() {
+                ui.ctx().set_cursor_icon(if vertical {
+                    CursorIcon::ResizeColumn
+                } else {
+                    CursorIcon::ResizeRow
+                });
+            }
+            if let Some(pos) = pointer.filter(|_| response.dragged()) {
+                let pos = to_canvas * pos;
+                *position = if vertical { pos.x } else { pos.y };
+                changed = true;
+            }
+            if response.drag_stopped() && !pointer.is_some_and(|pos| canvas.contains(pos)) {
+                removed = Some(i);
+            }
+        }
+
+        if let Some(i) = removed {
+            positions.remove(i);
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// A hairline across the canvas through `pos`.
+fn paint_guide(painter: &Painter, canvas: Rect, vertical: bool, pos: Pos2, stroke: Stroke) {
+    if vertical {
+        let x = painter.round_to_pixel_center(pos.x);
+        painter.vline(x, canvas.y_range(), stroke);
+    } else {
+        let y = painter.round_to_pixel_center(pos.y);
+        painter.hline(canvas.x_range(), y, stroke);
+    }
+}
+
+/// The distance between labeled ticks, and how many minor ticks each step is divided into,
+/// for a ruler showing `scale` points per canvas unit.
+fn tick_steps(scale: f32) -> (f32, i64) {
+    const MIN_LABEL_SPACING: f32 = 64.0;
+
+    let step = nice_ceil(MIN_LABEL_SPACING / scale);
+    let mantissa = step / 10_f32.powf(step.log10().floor());
+    let divisions = if (mantissa - 2.0).abs() < 0.01 { 4 } else { 5 };
+    (step, divisions)
+}
+
+fn paint_ruler(ui: &Ui, rect: Rect, vertical: bool, to_screen: TSTransform, pointer: Option<Pos2>) {
+    let painter = ui.painter_at(rect);
+    let visuals = ui.visuals();
+    painter.rect_filled(rect, 0.0, visuals.faint_bg_color);
+
+    let hairline = 1.0 / ui.pixels_per_point();
+    let tick_stroke = Stroke::new(hairline, visuals.weak_text_color());
+    let font_id = TextStyle::Small.resolve(ui.style());
+
+    // Which canvas coordinates are visible along the ruler:
+    let to_canvas = to_screen.inverse();
+    let (along, across) = if vertical {
+        (rect.y_range(), rect.x_range())
+    } else {
+        (rect.x_range(), rect.y_range())
+    };
+    let canvas_range = if vertical {
+        Rangef::new(
+            (to_canvas * pos2(0.0, along.min)).y,
+            (to_canvas * pos2(0.0, along.max)).y,
+        )
+    } else {
+        Rangef::new(
+            (to_canvas * pos2(along.min, 0.0)).x,
+            (to_canvas * pos2(along.max, 0.0)).x,
+        )
+    };
+
+    let (step, divisions) = tick_steps(to_screen.scaling);
+    let minor_step = step / divisions as f32;
+    let first = (canvas_range.min / minor_step).ceil() as i64;
+    let last = (canvas_range.max / minor_step).floor() as i64;
+    for n in first..=last {
+        let value = n as f32 * minor_step;
+        let screen = if vertical {
+            (to_screen * pos2(0.0, value)).y
+        } else {
+            (to_screen * pos2(value, 0.0)).x
+        };
+        let screen = painter.round_to_pixel_center(screen);
+        let is_major = n % divisions == 0;
+        let length = if is_major {
+            across.span()
+        } else {
+            0.25 * across.span()
+        };
+        let ticks = Rangef::new(across.max - length, across.max);
+        if vertical {
+            painter.hline(ticks, screen, tick_stroke);
+        } else {
+            painter.vline(screen, ticks, tick_stroke);
+        }
+
+        if is_major {
+            let label_pos = if vertical {
+                pos2(across.min + 2.0, screen + 1.0)
+            } else {
+                pos2(screen + 2.0, across.min + 1.0)
+            };
+            painter.text(
+                label_pos,
+                Align2::LEFT_TOP,
+                format_value(ui.style(), None, value),
+                font_id.clone(),
+                visuals.weak_text_color(),
+            );
+        }
+    }
+
+    // Show where the pointer is:
+    if let Some(pointer) = pointer {
+        let marker = Stroke::new(hairline, visuals.strong_text_color());
+        if vertical && along.contains(pointer.y) {
+            painter.hline(across, painter.round_to_pixel_center(pointer.y), marker);
+        } else if !vertical && along.contains(pointer.x) {
+            painter.vline(painter.round_to_pixel_center(pointer.x), across, marker);
+        }
+    }
+
+    let edge = Stroke::new(hairline, visuals.widgets.noninteractive.bg_stroke.color);
+    if vertical {
+        painter.vline(
+            painter.round_to_pixel_center(rect.right() - hairline),
+            along,
+            edge,
+        );
+    } else {
+        painter.hline(
+            along,
+            painter.round_to_pixel_center(rect.bottom() - hairline),
+            edge,
+        );
+    }
+}
+
+#[test]
+fn test_tick_steps() {
+    // At 1:1, labels every 100 points, with minor ticks every 20:
+    assert_eq!(tick_steps(1.0), (100.0, 5));
+
+    // Zoomed in 4x: 64 / 4 = 16, so labels every 20 canvas units, with minor ticks every 5:
+    assert_eq!(tick_steps(4.0), (20.0, 4));
+
+    // Zoomed out:
+    assert_eq!(tick_steps(0.1), (1000.0, 5));
+}
+
+#[test]
+fn test_drop_guide_on_ruler() {
+    use crate::{Event, PointerButton, RawInput};
+
+    let ctx = Context::default();
+    let screen_rect = Rect::from_min_size(Pos2::ZERO, vec2(200.0, 200.0));
+    let run = |events: Vec<Event>| {
+        let mut guides = Guides::default();
+        let _ = ctx.run(
+            RawInput {
+                screen_rect: Some(screen_rect),
+                events,
+                ..Default::default()
+            },
+            |ctx| {
+                crate::CentralPanel::default()
+                    .frame(crate::Frame::NONE)
+                    .show(ctx, |ui| {
+                        guides = Rulers::new("rulers")
+                            .show(ui, screen_rect, TSTransform::IDENTITY)
+                            .guides;
+                    });
+            },
+        );
+        guides
+    };
+    let button = |pos: Pos2, pressed: bool| Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Default::default(),
+    };
+    let drag = |from: Pos2, to: Pos2| {
+        run(vec![Event::PointerMoved(from), button(from, true)]);
+        run(vec![Event::PointerMoved(from.lerp(to, 0.5))]);
+        run(vec![Event::PointerMoved(to)]);
+        run(vec![button(to, false)]);
+        run(vec![])
+    };
+
+    run(vec![]); // Lay out the rulers before interacting with them
+
+    // Drag a guide out of the top ruler:
+    let guides = drag(pos2(100.0, 10.0), pos2(100.0, 120.0));
+    assert_eq!(guides.horizontal, vec![120.0]);
+
+    // …and back onto it:
+    let guides = drag(pos2(100.0, 120.0), pos2(100.0, 10.0));
+    assert!(guides.horizontal.is_empty(), "The guide is removed");
+}
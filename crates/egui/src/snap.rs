@@ -0,0 +1,320 @@
+//! Snapping of dragged rectangles to a grid and to the edges and centers of other rectangles.
+
+use crate::{pos2, Painter, Pos2, Rangef, Rect, Shape, Stroke, Vec2};
+
+/// A line that a dragged rectangle snapped to, for showing to the user.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SnapGuide {
+    /// A vertical line at `x`, spanning `y`.
+    Vertical { x: f32, y: Rangef },
+
+    /// A horizontal line at `y`, spanning `x`.
+    Horizontal { y: f32, x: Rangef },
+}
+
+impl SnapGuide {
+    /// The two end points of the line.
+    pub fn points(&self) -> [Pos2; 2] {
+        match *self {
+            Self::Vertical { x, y } => [pos2(x, y.min), pos2(x, y.max)],
+            Self::Horizontal { y, x } => [pos2(x.min, y), pos2(x.max, y)],
+        }
+    }
+}
+
+/// The result of [`SnapManager::snap_rect`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapped {
+    /// The rectangle, moved so that it snaps.
+    pub rect: Rect,
+
+    /// How much the rectangle was moved.
+    pub offset: Vec2,
+
+    /// The alignment guides to show, see [`Self::paint_guides`].
+    ///
+    /// Empty if the rectangle only snapped to the grid (or not at all).
+    pub guides: Vec<SnapGuide>,
+}
+
+impl Snapped {
+    /// Paint the alignment guides as dashed lines.
+    pub fn paint_guides(&self, painter: &Painter, stroke: impl Into<Stroke>) {
+        let stroke = stroke.into();
+        let mut shapes = Vec::new();
+        for guide in &self.guides {
+            Shape::dashed_line_many(&guide.points(), stroke, 4.0, 3.0, &mut shapes);
+        }
+        painter.extend(shapes);
+    }
+}
+
+/// Snaps dragged rectangles to a grid, and to the edges and centers of other rectangles,
+/// like in a design tool.
+///
+/// Register the rectangles of the items that can be snapped to,
+/// then call [`Self::snap_rect`] with the rectangle that is being dragged,
+/// and paint the resulting alignment guides with [`Snapped::paint_guides`].
+///
+/// All coordinates (including [`Self::threshold`] and [`Self::grid`]) are in the same space,
+/// e.g. the scene coordinates of a [`crate::Scene`].
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let others = [egui::Rect::from_min_size(egui::pos2(0.0, 0.0), egui::vec2(50.0, 20.0))];
+/// # let dragged_rect = egui::Rect::from_min_size(egui::pos2(52.0, 40.0), egui::vec2(30.0, 20.0));
+/// let mut snap = egui::SnapManager::new().grid(10.0);
+/// for rect in others {
+///     snap.add_rect(rect);
+/// }
+/// let snapped = snap.snap_rect(dragged_rect);
+/// snapped.paint_guides(ui.painter(), ui.visuals().selection.stroke);
+/// // … move the dragged item to `snapped.rect`
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+pub struct SnapManager {
+    threshold: f32,
+    grid: Option<f32>,
+    rects: Vec<Rect>,
+
+    /// The x coordinates of vertical lines to snap to.
+    vertical_lines: Vec<f32>,
+
+    /// The y coordinates of horizontal lines to snap to.
+    horizontal_lines: Vec<f32>,
+}
+
+impl Default for SnapManager {
+    fn default() -> Self {
+        Self {
+            threshold: 6.0,
+            grid: None,
+            rects: Vec::new(),
+            vertical_lines: Vec::new(),
+            horizontal_lines: Vec::new(),
+        }
+    }
+}
+
+impl SnapManager {
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// How close an edge or center must be to snap.
+    ///
+    /// Default: `6.0`.
+    #[inline]
+    pub fn threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Snap the top-left corner to a grid with this spacing,
+    /// unless there is an alignment guide to snap to.
+    ///
+    /// Default: no grid.
+    #[inline]
+    pub fn grid(mut self, spacing: f32) -> Self {
+        self.grid = (spacing > 0.0).then_some(spacing);
+        self
+    }
+
+    /// Register a rectangle that others can snap to.
+    ///
+    /// Don't add the rectangle that is being dragged.
+    pub fn add_rect(&mut self, rect: Rect) {
+        if rect.is_finite() {
+            self.rects.push(rect);
+        }
+    }
+
+    /// Register a vertical line at `x` that rectangles can snap to,
+    /// e.g. a guide from [`crate::rulers::Rulers`].
+    pub fn add_vertical_line(&mut self, x: f32) {
+        if x.is_finite() {
+            self.vertical_lines.push(x);
+        }
+    }
+
+    /// Register a horizontal line at `y` that rectangles can snap to,
+    /// e.g. a guide from [`crate::rulers::Rulers`].
+    pub fn add_horizontal_line(&mut self, y: f32) {
+        if y.is_finite() {
+            self.horizontal_lines.push(y);
+        }
+    }
+
+    /// Forget all registered rectangles and lines.
+    pub fn clear(&mut self) {
+        self.rects.clear();
+        self.vertical_lines.clear();
+        self.horizontal_lines.clear();
+    }
+
+    /// Snap `rect` along each axis independently.
+    ///
+    /// The left edge, center and right edge of `rect` can snap to the left edges,
+    /// centers and right edges of the registered rectangles, and likewise vertically.
+    /// They can also snap to the registered lines.
+    /// If nothing is within [`Self::threshold`], it snaps to the [`Self::grid`] instead, if any.
+    pub fn snap_rect(&self, rect: Rect) -> Snapped {
+        let x = self.snap_axis(
+            rect.x_range(),
+            self.rects
+                .iter()
+                .map(|other| (other.x_range(), other.y_range()))
+                // Lines have no extent, so their guides only span the snapped rectangle:
+                .chain(
+                    self.vertical_lines
+                        .iter()
+                        .map(|&x| (Rangef::point(x), Rangef::NOTHING)),
+                ),
+        );
+        let y = self.snap_axis(
+            rect.y_range(),
+            self.rects
+                .iter()
+                .map(|other| (other.y_range(), other.x_range()))
+                .chain(
+                    self.horizontal_lines
+                        .iter()
+                        .map(|&y| (Rangef::point(y), Rangef::NOTHING)),
+                ),
+        );
+
+        let offset = Vec2::new(x.offset, y.offset);
+        let snapped_rect = rect.translate(offset);
+
+        let mut guides = Vec::new();
+        if let Some((x, others)) = x.line {
+            guides.push(SnapGuide::Vertical {
+                x,
+                y: others.union(snapped_rect.y_range()),
+            });
+        }
+        if let Some((y, others)) = y.line {
+            guides.push(SnapGuide::Horizontal {
+                y,
+                x: others.union(snapped_rect.x_range()),
+            });
+        }
+
+        Snapped {
+            rect: snapped_rect,
+            offset,
+            guides,
+        }
+    }
+
+    /// Snap the range of a rectangle along one axis to the `others`,
+    /// given as their range along this axis and the cross axis.
+    fn snap_axis(&self, range: Rangef, others: impl Iterator<Item = (Rangef, Rangef)>) -> AxisSnap {
+        let ours = [range.min, range.center(), range.max];
+
+        // The closest line, and the span of all rects that line up with it on the cross axis:
+        let mut best: Option<(f32, f32, Rangef)> = None;
+        for (other, cross) in others {
+            for theirs in [other.min, other.center(), other.max] {
+                for our in ours {
+                    let offset = theirs - our;
+                    if offset.abs() > self.threshold {
+                        continue;
+                    }
+                    match &mut best {
+                        Some((best_offset, best_line, span))
+                            if *best_line == theirs && *best_offset == offset =>
+                        {
+                            *span = span.union(cross);
+                        }
+                        Some((best_offset, _, _)) if offset.abs() >= best_offset.abs() => {}
+                        _ => best = Some((offset, theirs, cross)),
+                    }
+                }
+            }
+        }
+
+        if let Some((offset, line, span)) = best {
+            AxisSnap {
+                offset,
+                line: Some((line, span)),
+            }
+        } else if let Some(grid) = self.grid {
+            AxisSnap {
+                offset: (range.min / grid).round() * grid - range.min,
+                line: None,
+            }
+        } else {
+            AxisSnap {
+                offset: 0.0,
+                line: None,
+            }
+        }
+    }
+}
+
+struct AxisSnap {
+    offset: f32,
+
+    /// The line we snapped to, and the span of the rectangles along it.
+    line: Option<(f32, Rangef)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec2;
+
+    #[test]
+    fn test_snap_to_edges_and_centers() {
+        let mut snap = SnapManager::new().threshold(5.0);
+        snap.add_rect(Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 20.0)));
+
+        // Left edge snaps to left edge, nothing vertically:
+        let snapped = snap.snap_rect(Rect::from_min_size(pos2(3.0, 50.0), vec2(10.0, 10.0)));
+        assert_eq!(snapped.offset, vec2(-3.0, 0.0));
+        assert_eq!(
+            snapped.guides,
+            vec![SnapGuide::Vertical {
+                x: 0.0,
+                y: Rangef::new(0.0, 60.0)
+            }]
+        );
+
+        // Center snaps to center:
+        let snapped = snap.snap_rect(Rect::from_center_size(pos2(52.0, 50.0), vec2(10.0, 10.0)));
+        assert_eq!(snapped.rect.center().x, 50.0);
+
+        // Too far away:
+        let snapped = snap.snap_rect(Rect::from_min_size(pos2(20.0, 50.0), vec2(10.0, 10.0)));
+        assert_eq!(snapped.offset, Vec2::ZERO);
+        assert!(snapped.guides.is_empty());
+    }
+
+    #[test]
+    fn test_snap_to_lines() {
+        let mut snap = SnapManager::new().threshold(5.0).grid(10.0);
+        snap.add_vertical_line(42.0);
+
+        // The right edge snaps to the line, the top to the grid:
+        let snapped = snap.snap_rect(Rect::from_min_size(pos2(30.0, 13.0), vec2(10.0, 10.0)));
+        assert_eq!(snapped.rect.min, pos2(32.0, 10.0));
+        assert_eq!(
+            snapped.guides,
+            vec![SnapGuide::Vertical {
+                x: 42.0,
+                y: Rangef::new(10.0, 20.0)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_snap_to_grid() {
+        let snap = SnapManager::new().grid(10.0);
+        let snapped = snap.snap_rect(Rect::from_min_size(pos2(13.0, 27.0), vec2(5.0, 5.0)));
+        assert_eq!(snapped.rect.min, pos2(10.0, 30.0));
+        assert!(snapped.guides.is_empty());
+    }
+}
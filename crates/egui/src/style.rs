@@ -282,6 +282,28 @@ pub struct Style {
     #[cfg_attr(feature = "serde", serde(skip))]
     pub number_formatter: NumberFormatter,
 
+    /// The language of the user interface, e.g. `"en"` or `"pt-BR"`.
+    ///
+    /// Built-in strings are translated to it by the translator set with [`crate::Context::set_translator`].
+    /// Widgets can also consult it for their own strings.
+    pub locale: String,
+
+    /// The decimal and thousands separators of numbers shown and typed in e.g. a [`crate::DragValue`].
+    pub number_locale: crate::NumberLocale,
+
+    /// The units that e.g. [`crate::DragValue::unit`] can show values in.
+    ///
+    /// You can add your own here.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub units: crate::UnitRegistry,
+
+    /// Whether the user interface reads left-to-right or right-to-left.
+    ///
+    /// In [`LayoutDirection::RightToLeft`] the default layouts, side panels, sliders
+    /// and vertical scroll bars are all mirrored.
+    /// Use [`crate::UiBuilder::layout_direction`] to override it for a part of the ui.
+    pub layout_direction: LayoutDirection,
+
     /// If set, labels, buttons, etc. will use this to determine whether to wrap the text at the
     /// right edge of the [`Ui`] they are in. By default, this is `None`.
     ///
@@ -310,7 +332,13 @@ pub struct Style {
     /// Colors etc.
     pub visuals: Visuals,
 
+    /// How long animations last, and whether to animate at all.
+    pub animation: AnimationStyle,
+
     /// How many seconds a typical animation should last.
+    ///
+    /// If changed from its default, it is used for all kinds of animations instead of [`Self::animation`].
+    #[deprecated = "Use `Style::animation` instead"]
     pub animation_time: f32,
 
     /// Options to help debug why egui behaves strangely.
@@ -333,10 +361,63 @@ pub struct Style {
     /// The animation that should be used when scrolling a [`crate::ScrollArea`] using e.g. [`Ui::scroll_to_rect`].
     pub scroll_animation: ScrollAnimation,
 
+    /// How a [`crate::ScrollArea`] keeps moving after the user lets go of a drag (e.g. a touch swipe).
+    pub kinetic_scrolling: KineticScrolling,
+
+    /// How [`crate::Window`]s snap to the screen edges and to each other while being dragged.
+    pub window_snapping: WindowSnapping,
+
+    /// How [`crate::Dimension`]s (measurement annotations) look.
+    pub dimension: DimensionStyle,
+
     /// Use a more compact style for menus.
     pub compact_menu_style: bool,
 }
 
+#[cfg(feature = "persistence")]
+#[test]
+fn style_ron_round_trip() {
+    let mut style = Style::default();
+    style.visuals.panel_fill = Color32::from_rgb(30, 30, 60);
+    style.spacing.item_spacing = vec2(3.0, 4.0);
+    let ron = style.to_ron().unwrap();
+    let parsed = Style::from_ron(&ron).unwrap();
+    assert_eq!(parsed.visuals, style.visuals);
+    assert_eq!(parsed.spacing, style.spacing);
+    assert_eq!(
+        parsed.to_ron().unwrap(),
+        ron,
+        "Callbacks can't be compared, but everything else can"
+    );
+
+    // The example in the docs of `from_ron`:
+    let style = Style::from_ron("(visuals: (panel_fill: ((30, 30, 60, 255))))").unwrap();
+    assert_eq!(style.visuals.panel_fill, Color32::from_rgb(30, 30, 60));
+    assert_eq!(style.spacing, Spacing::default());
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn style_toml_round_trip() {
+    let mut style = Style::default();
+    style.visuals.panel_fill = Color32::from_rgb(30, 30, 60);
+    style.spacing.item_spacing = vec2(3.0, 4.0);
+    let toml = style.to_toml().unwrap();
+    let parsed = Style::from_toml(&toml).unwrap();
+    assert_eq!(parsed.visuals, style.visuals);
+    assert_eq!(parsed.spacing, style.spacing);
+    assert_eq!(
+        parsed.to_toml().unwrap(),
+        toml,
+        "Callbacks can't be compared, but everything else can"
+    );
+
+    // The example in the docs of `from_toml`:
+    let style = Style::from_toml("[visuals]\npanel_fill = [30, 30, 60, 255]").unwrap();
+    assert_eq!(style.visuals.panel_fill, Color32::from_rgb(30, 30, 60));
+    assert_eq!(style.spacing, Spacing::default());
+}
+
 #[test]
 fn style_impl_send_sync() {
     fn assert_send_sync<T: Send + Sync>() {}
@@ -372,6 +453,66 @@ impl Style {
     pub fn text_styles(&self) -> Vec<TextStyle> {
         self.text_styles.keys().cloned().collect()
     }
+
+    /// Seconds for the given kind of animation, before scaling by [`AnimationStyle::speed`].
+    ///
+    /// This is [`AnimationStyle::duration`], unless the deprecated [`Self::animation_time`] was changed.
+    #[allow(deprecated, clippy::float_cmp_const)]
+    pub fn animation_duration(&self, kind: AnimationKind) -> f32 {
+        if self.animation_time == DEFAULT_ANIMATION_TIME {
+            self.animation.duration(kind)
+        } else {
+            self.animation_time
+        }
+    }
+
+    /// Parse a style from [RON](https://github.com/ron-rs/ron), e.g. as written by [`Self::to_ron`].
+    ///
+    /// Fields missing from the RON get their default values, so a style file
+    /// only needs to list what it changes, e.g. `(visuals: (panel_fill: ((30, 30, 60, 255))))`
+    /// (a [`Color32`] is a struct wrapping its four bytes, hence the double parentheses).
+    ///
+    /// See also [`crate::style_hot_reload`].
+    ///
+    /// # Errors
+    /// If the RON is malformed, or doesn't describe a [`Style`].
+    #[cfg(feature = "persistence")]
+    pub fn from_ron(ron: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(ron)
+    }
+
+    /// Write the whole style as pretty-printed [RON](https://github.com/ron-rs/ron).
+    ///
+    /// # Errors
+    /// Only if serialization itself fails, which should not happen.
+    #[cfg(feature = "persistence")]
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    /// Parse a style from [TOML](https://toml.io), e.g. as written by [`Self::to_toml`].
+    ///
+    /// Like with [`Self::from_ron`], a style file only needs to list what it changes, e.g.
+    /// ```toml
+    /// [visuals]
+    /// panel_fill = [30, 30, 60, 255]
+    /// ```
+    ///
+    /// # Errors
+    /// If the TOML is malformed, or doesn't describe a [`Style`].
+    #[cfg(feature = "toml")]
+    pub fn from_toml(toml: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(toml)
+    }
+
+    /// Write the whole style as pretty-printed [TOML](https://toml.io).
+    ///
+    /// # Errors
+    /// If the style has something TOML can't express, e.g. a [`TextStyle::Name`] in [`Self::text_styles`].
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string_pretty(self)
+    }
 }
 
 /// Controls the sizes and distances between widgets.
@@ -448,6 +589,11 @@ pub struct Spacing {
     /// Horizontal distance between a menu and a submenu.
     pub menu_spacing: f32,
 
+    /// Size of the arrow pointing from a popup (menu, combo box, tooltip, …) to what it belongs to.
+    ///
+    /// `0.0` means no arrow. Popups at the pointer have none either. See [`crate::Popup::arrow_size`].
+    pub popup_arrow_size: f32,
+
     /// End indented regions with a horizontal line
     pub indent_ends_with_horizontal_line: bool,
 
@@ -747,10 +893,138 @@ impl ScrollStyle {
 
 // ----------------------------------------------------------------------------
 
+/// What is being animated, see [`AnimationStyle::duration`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnimationKind {
+    /// Widgets reacting to the pointer, e.g. hover highlights and checkboxes.
+    Interaction,
+
+    /// Things opening and closing, e.g. collapsing headers, panels and maximized windows.
+    Expand,
+
+    /// Windows, tooltips and popups fading in and out.
+    Fade,
+}
+
+/// How long animations last, and whether to animate at all.
+///
+/// [`Self::speed`] and [`Self::reduced_motion`] apply to all animations (see [`crate::Context::scale_animation_time`]),
+/// including [`ScrollAnimation`]s and calls to [`crate::Context::animate_bool_with_time`] with an explicit duration.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AnimationStyle {
+    /// How fast all animations play, e.g. `2.0` for twice as fast.
+    ///
+    /// Zero (or less) turns animations off.
+    pub speed: f32,
+
+    /// Seconds for [`AnimationKind::Interaction`], the default for [`crate::Context::animate_bool`].
+    pub interaction: f32,
+
+    /// Seconds for [`AnimationKind::Expand`].
+    pub expand: f32,
+
+    /// Seconds for [`AnimationKind::Fade`].
+    pub fade: f32,
+
+    /// Turn off animations, for users that are bothered by motion.
+    pub reduced_motion: bool,
+
+    /// Also turn off animations when the OS asks for it,
+    /// see [`crate::RawInput::prefers_reduced_motion`].
+    pub follow_system_reduced_motion: bool,
+}
+
+const DEFAULT_ANIMATION_TIME: f32 = 1.0 / 12.0;
+
+impl Default for AnimationStyle {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            interaction: DEFAULT_ANIMATION_TIME,
+            expand: DEFAULT_ANIMATION_TIME,
+            fade: DEFAULT_ANIMATION_TIME,
+            reduced_motion: false,
+            follow_system_reduced_motion: true,
+        }
+    }
+}
+
+impl AnimationStyle {
+    /// Seconds for the given kind of animation, before scaling by [`Self::speed`].
+    pub fn duration(&self, kind: AnimationKind) -> f32 {
+        match kind {
+            AnimationKind::Interaction => self.interaction,
+            AnimationKind::Expand => self.expand,
+            AnimationKind::Fade => self.fade,
+        }
+    }
+
+    /// Should animations be off, given the preference of the OS?
+    pub fn is_reduced_motion(&self, prefers_reduced_motion: Option<bool>) -> bool {
+        self.reduced_motion
+            || (self.follow_system_reduced_motion && prefers_reduced_motion == Some(true))
+    }
+
+    /// Scale a duration by [`Self::speed`], or return zero if motion is reduced.
+    pub fn scale(&self, seconds: f32, prefers_reduced_motion: Option<bool>) -> f32 {
+        if self.is_reduced_motion(prefers_reduced_motion) || self.speed <= 0.0 {
+            0.0
+        } else {
+            seconds / self.speed
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut crate::Ui) {
+        let Self {
+            speed,
+            interaction,
+            expand,
+            fade,
+            reduced_motion,
+            follow_system_reduced_motion,
+        } = self;
+
+        ui.checkbox(reduced_motion, "Reduce motion");
+        ui.checkbox(
+            follow_system_reduced_motion,
+            "Reduce motion when the OS asks for it",
+        );
+        crate::Grid::new("animation").show(ui, |ui| {
+            ui.label("Speed:");
+            ui.add(
+                DragValue::new(speed)
+                    .speed(0.05)
+                    .range(0.0..=10.0)
+                    .suffix("×"),
+            );
+            ui.end_row();
+
+            for (name, seconds) in [
+                ("Interaction:", interaction),
+                ("Expand:", expand),
+                ("Fade:", fade),
+            ] {
+                ui.label(name);
+                ui.add(
+                    DragValue::new(seconds)
+                        .range(0.0..=1.0)
+                        .speed(0.02)
+                        .suffix(" s"),
+                );
+                ui.end_row();
+            }
+        });
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// Scroll animation configuration, used when programmatically scrolling somewhere (e.g. with `[crate::Ui::scroll_to_cursor]`)
 /// The animation duration is calculated based on the distance to be scrolled via `[ScrollAnimation::points_per_second]`
 /// and can be clamped to a min / max duration via `[ScrollAnimation::duration]`.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(default))]
 pub struct ScrollAnimation {
@@ -759,6 +1033,12 @@ pub struct ScrollAnimation {
 
     /// The min / max scroll duration.
     pub duration: Rangef,
+
+    /// The easing curve of the animation, mapping `[0, 1]` time to `[0, 1]` progress.
+    ///
+    /// See [`emath::easing`] for a selection of curves. Default: [`emath::ease_in_ease_out`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub easing: fn(f32) -> f32,
 }
 
 impl Default for ScrollAnimation {
@@ -766,16 +1046,25 @@ impl Default for ScrollAnimation {
         Self {
             points_per_second: 1000.0,
             duration: Rangef::new(0.1, 0.3),
+            easing: emath::ease_in_ease_out,
         }
     }
 }
 
+impl PartialEq for ScrollAnimation {
+    /// Ignores [`Self::easing`], since function pointers can't be reliably compared.
+    fn eq(&self, other: &Self) -> bool {
+        self.points_per_second == other.points_per_second && self.duration == other.duration
+    }
+}
+
 impl ScrollAnimation {
     /// New scroll animation
     pub fn new(points_per_second: f32, duration: Rangef) -> Self {
         Self {
             points_per_second,
             duration,
+            easing: emath::ease_in_ease_out,
         }
     }
 
@@ -784,6 +1073,7 @@ impl ScrollAnimation {
         Self {
             points_per_second: f32::INFINITY,
             duration: Rangef::new(0.0, 0.0),
+            easing: emath::ease_in_ease_out,
         }
     }
 
@@ -792,9 +1082,17 @@ impl ScrollAnimation {
         Self {
             points_per_second: f32::INFINITY,
             duration: Rangef::new(t, t),
+            easing: emath::ease_in_ease_out,
         }
     }
 
+    /// Use a different easing curve, e.g. [`emath::easing::cubic_out`].
+    #[inline]
+    pub fn with_easing(mut self, easing: fn(f32) -> f32) -> Self {
+        self.easing = easing;
+        self
+    }
+
     pub fn ui(&mut self, ui: &mut crate::Ui) {
         crate::Grid::new("scroll_animation").show(ui, |ui| {
             ui.label("Scroll animation:");
@@ -829,6 +1127,372 @@ impl ScrollAnimation {
 
 // ----------------------------------------------------------------------------
 
+/// How the velocity of a flung [`crate::ScrollArea`] decays over time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum FrictionCurve {
+    /// Constant deceleration, in points per second squared.
+    ///
+    /// The scroll area slows down linearly and stops after `speed / deceleration` seconds.
+    Linear { deceleration: f32 },
+
+    /// The velocity is multiplied by `exp(-decay_rate * dt)` each frame.
+    ///
+    /// This gives a long, gentle tail that feels like native touch scrolling on most platforms.
+    /// A `decay_rate` of `4.0` loses ~98% of the velocity in one second.
+    Exponential { decay_rate: f32 },
+}
+
+impl FrictionCurve {
+    /// Apply the friction for `dt` seconds to a velocity in points per second.
+    pub fn apply(&self, velocity: f32, dt: f32) -> f32 {
+        match *self {
+            Self::Linear { deceleration } => {
+                let friction = deceleration * dt;
+                if friction > velocity.abs() {
+                    0.0
+                } else {
+                    velocity - friction * velocity.signum()
+                }
+            }
+            Self::Exponential { decay_rate } => velocity * (-decay_rate * dt).exp(),
+        }
+    }
+}
+
+/// Configuration of kinetic (inertial) scrolling of a [`crate::ScrollArea`].
+///
+/// When the user drags the contents of a scroll area (e.g. with a finger on a touch screen)
+/// and lets go, the scroll area keeps moving with the release velocity,
+/// slowing down according to [`Self::friction`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct KineticScrolling {
+    /// If `false`, the scroll area stops as soon as the drag is released.
+    pub enabled: bool,
+
+    /// How the velocity decays.
+    pub friction: FrictionCurve,
+
+    /// Below this speed (in points per second) the scroll area comes to a stop.
+    pub stop_speed: f32,
+
+    /// The release velocity is clamped to this (in points per second).
+    pub max_speed: f32,
+}
+
+impl Default for KineticScrolling {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            friction: FrictionCurve::Linear {
+                deceleration: 1000.0,
+            },
+            stop_speed: 20.0,
+            max_speed: 10_000.0,
+        }
+    }
+}
+
+impl KineticScrolling {
+    /// No kinetic scrolling: stop as soon as the user lets go.
+    pub fn none() -> Self {
+        Self {
+            enabled: false,
+            ..Default::default()
+        }
+    }
+
+    /// Exponential decay, similar to the scrolling of mobile operating systems.
+    pub fn native() -> Self {
+        Self {
+            friction: FrictionCurve::Exponential { decay_rate: 3.0 },
+            ..Default::default()
+        }
+    }
+
+    /// The velocity after `dt` seconds of friction, or zero if it has come to a stop.
+    pub fn step(&self, velocity: f32, dt: f32) -> f32 {
+        if !self.enabled || velocity.abs() < self.stop_speed {
+            return 0.0;
+        }
+        let velocity = self.friction.apply(velocity, dt);
+        if velocity.abs() < self.stop_speed {
+            0.0
+        } else {
+            velocity
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut crate::Ui) {
+        let Self {
+            enabled,
+            friction,
+            stop_speed,
+            max_speed,
+        } = self;
+
+        ui.checkbox(enabled, "Kinetic scrolling");
+        ui.add_enabled_ui(*enabled, |ui| {
+            crate::Grid::new("kinetic_scrolling").show(ui, |ui| {
+                ui.label("Friction:");
+                ui.horizontal(|ui| {
+                    let is_linear = matches!(friction, FrictionCurve::Linear { .. });
+                    if ui.radio(is_linear, "Linear").clicked() && !is_linear {
+                        *friction = FrictionCurve::Linear {
+                            deceleration: 1000.0,
+                        };
+                    }
+                    if ui.radio(!is_linear, "Exponential").clicked() && is_linear {
+                        *friction = FrictionCurve::Exponential { decay_rate: 3.0 };
+                    }
+                });
+                ui.end_row();
+
+                match friction {
+                    FrictionCurve::Linear { deceleration } => {
+                        ui.label("Deceleration:");
+                        ui.add(
+                            DragValue::new(deceleration)
+                                .speed(10.0)
+                                .range(0.0..=10_000.0)
+                                .suffix(" points/s²"),
+                        );
+                    }
+                    FrictionCurve::Exponential { decay_rate } => {
+                        ui.label("Decay rate:");
+                        ui.add(
+                            DragValue::new(decay_rate)
+                                .speed(0.05)
+                                .range(0.0..=20.0)
+                                .suffix(" /s"),
+                        );
+                    }
+                }
+                ui.end_row();
+
+                ui.label("Stop speed:");
+                ui.add(
+                    DragValue::new(stop_speed)
+                        .speed(1.0)
+                        .range(0.0..=200.0)
+                        .suffix(" points/s"),
+                );
+                ui.end_row();
+
+                ui.label("Max speed:");
+                ui.add(
+                    DragValue::new(max_speed)
+                        .speed(100.0)
+                        .range(0.0..=f32::INFINITY)
+                        .suffix(" points/s"),
+                );
+                ui.end_row();
+            });
+        });
+    }
+}
+
+#[test]
+fn kinetic_scrolling_comes_to_a_stop() {
+    for kinetic in [KineticScrolling::default(), KineticScrolling::native()] {
+        let mut velocity = 3000.0;
+        let mut frames = 0;
+        while velocity != 0.0 {
+            let next = kinetic.step(velocity, 1.0 / 60.0);
+            assert!(
+                next.abs() < velocity.abs(),
+                "{kinetic:?} is not slowing down"
+            );
+            velocity = next;
+            frames += 1;
+            assert!(frames < 60 * 10, "{kinetic:?} never stops");
+        }
+    }
+    assert_eq!(KineticScrolling::none().step(3000.0, 1.0 / 60.0), 0.0);
+}
+
+// ----------------------------------------------------------------------------
+
+/// Configuration of how a dragged [`crate::Window`] snaps into place.
+///
+/// When an edge of the window comes within [`Self::snap_distance`] of a screen edge
+/// or of an edge of another window, the window sticks to it until the user
+/// drags it further away than that.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct WindowSnapping {
+    /// If `false`, windows move freely. Off by default.
+    pub enabled: bool,
+
+    /// Snap to the edges of the area the window is constrained to (usually the screen).
+    pub snap_to_screen_edges: bool,
+
+    /// Snap to, and align with, the edges of other visible windows.
+    pub snap_to_windows: bool,
+
+    /// How close (in points) an edge has to be for it to snap.
+    pub snap_distance: f32,
+
+    /// Paint dashed alignment guides while a window is snapped to another window.
+    pub show_guides: bool,
+}
+
+impl Default for WindowSnapping {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            snap_to_screen_edges: true,
+            snap_to_windows: true,
+            snap_distance: 10.0,
+            show_guides: true,
+        }
+    }
+}
+
+impl WindowSnapping {
+    pub fn ui(&mut self, ui: &mut crate::Ui) {
+        let Self {
+            enabled,
+            snap_to_screen_edges,
+            snap_to_windows,
+            snap_distance,
+            show_guides,
+        } = self;
+
+        ui.checkbox(enabled, "Snap windows while dragging");
+        ui.add_enabled_ui(*enabled, |ui| {
+            ui.checkbox(snap_to_screen_edges, "Snap to screen edges");
+            ui.checkbox(snap_to_windows, "Snap to other windows");
+            ui.add_enabled(
+                *snap_to_windows,
+                crate::Checkbox::new(show_guides, "Show alignment guides"),
+            );
+            ui.horizontal(|ui| {
+                ui.label("Snap distance:");
+                ui.add(
+                    DragValue::new(snap_distance)
+                        .speed(0.5)
+                        .range(0.0..=100.0)
+                        .suffix(" points"),
+                );
+            });
+        });
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// How CAD-style measurement annotations ([`crate::Dimension`]) look.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DimensionStyle {
+    /// The width of the lines, in points.
+    pub stroke_width: f32,
+
+    /// The color of the lines, arrows and text.
+    ///
+    /// If `None`, [`Visuals::text_color`] is used.
+    pub color: Option<Color32>,
+
+    /// The length of the arrowheads.
+    pub arrow_size: f32,
+
+    /// The gap between the measured points and the start of the extension lines.
+    pub extension_gap: f32,
+
+    /// How far the extension lines go past the dimension line.
+    pub extension_overshoot: f32,
+
+    /// The font of the measurement.
+    pub text_style: TextStyle,
+
+    /// The number of decimals shown in measurements.
+    pub decimals: usize,
+}
+
+impl Default for DimensionStyle {
+    fn default() -> Self {
+        Self {
+            stroke_width: 1.0,
+            color: None,
+            arrow_size: 8.0,
+            extension_gap: 2.0,
+            extension_overshoot: 4.0,
+            text_style: TextStyle::Small,
+            decimals: 1,
+        }
+    }
+}
+
+impl DimensionStyle {
+    pub fn ui(&mut self, ui: &mut crate::Ui) {
+        let Self {
+            stroke_width,
+            color,
+            arrow_size,
+            extension_gap,
+            extension_overshoot,
+            text_style,
+            decimals,
+        } = self;
+
+        Grid::new("dimension_style").show(ui, |ui| {
+            ui.label("Stroke width");
+            ui.add(DragValue::new(stroke_width).speed(0.1).range(0.0..=10.0));
+            ui.end_row();
+
+            ui.label("Color");
+            ui.horizontal(|ui| {
+                let mut custom = color.is_some();
+                if ui.checkbox(&mut custom, "Custom").changed() {
+                    *color = custom.then_some(Color32::GRAY);
+                }
+                if let Some(color) = color {
+                    ui.color_edit_button_srgba(color);
+                }
+            });
+            ui.end_row();
+
+            ui.label("Arrow size");
+            ui.add(DragValue::new(arrow_size).speed(0.1).range(0.0..=50.0));
+            ui.end_row();
+
+            ui.label("Extension gap");
+            ui.add(DragValue::new(extension_gap).speed(0.1).range(0.0..=50.0));
+            ui.end_row();
+
+            ui.label("Extension overshoot");
+            ui.add(
+                DragValue::new(extension_overshoot)
+                    .speed(0.1)
+                    .range(0.0..=50.0),
+            );
+            ui.end_row();
+
+            ui.label("Text style");
+            ComboBox::from_id_salt("dimension_text_style")
+                .selected_text(text_style.to_string())
+                .show_ui(ui, |ui| {
+                    for style in ui.style().text_styles.keys().cloned().collect::<Vec<_>>() {
+                        let text = style.to_string();
+                        ui.selectable_value(text_style, style, text);
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Decimals");
+            ui.add(DragValue::new(decimals).range(0..=6));
+            ui.end_row();
+        });
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// How and when interaction happens.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -840,6 +1504,16 @@ pub struct Interaction {
     /// which is important for e.g. touch screens.
     pub interact_radius: f32,
 
+    /// When using a touch screen, clickable and draggable widgets smaller than this (in points)
+    /// get a larger interactive area.
+    ///
+    /// The interactive area is grown around the widget to at least this width and height,
+    /// without changing its looks or the layout, so small checkboxes and icon buttons are easier to tap.
+    /// Widgets with an exact outline, see [`crate::WidgetRects::set_hit_path`], are not grown.
+    ///
+    /// `0.0` turns this off. Around `40.0` is a good size for fingers.
+    pub touch_target_size: f32,
+
     /// Radius of the interactive area of the side of a window during drag-to-resize.
     pub resize_grab_radius_side: f32,
 
@@ -859,11 +1533,25 @@ pub struct Interaction {
     /// This lets the user quickly move over some dead space to hover the next thing.
     pub tooltip_grace_time: f32,
 
+    /// How long a tooltip stays open after the pointer has left its widget, in seconds.
+    ///
+    /// Hovering another widget closes it right away.
+    pub tooltip_hide_delay: f32,
+
+    /// Like [`Self::tooltip_grace_time`], but for moving between tooltips of the same group,
+    /// e.g. the buttons of a toolbar.
+    ///
+    /// See [`crate::Tooltip::group`].
+    pub tooltip_group_grace_time: f32,
+
     /// Can you select the text on a [`crate::Label`] by default?
     pub selectable_labels: bool,
 
     /// Can the user select text that span multiple labels?
     ///
+    /// The selection can span all labels in the same panel or window,
+    /// and their text is copied in the order it is read: top-down, then left-to-right.
+    ///
     /// The default is `true`, but text selection can be slightly glitchy,
     /// so you may want to disable it.
     pub multi_widget_text_select: bool,
@@ -1076,6 +1764,43 @@ pub struct Selection {
     pub stroke: Stroke,
 }
 
+/// The reading direction of the user interface, see [`Style::layout_direction`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum LayoutDirection {
+    /// Like English: things start on the left.
+    #[default]
+    LeftToRight,
+
+    /// Like Arabic and Hebrew: things start on the right.
+    RightToLeft,
+}
+
+impl LayoutDirection {
+    #[inline]
+    pub fn is_right_to_left(self) -> bool {
+        self == Self::RightToLeft
+    }
+
+    /// Where things start horizontally: [`Align::LEFT`] or [`Align::RIGHT`].
+    #[inline]
+    pub fn start_align(self) -> Align {
+        match self {
+            Self::LeftToRight => Align::LEFT,
+            Self::RightToLeft => Align::RIGHT,
+        }
+    }
+
+    /// The other direction.
+    #[inline]
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::LeftToRight => Self::RightToLeft,
+            Self::RightToLeft => Self::LeftToRight,
+        }
+    }
+}
+
 /// Shape of the handle for sliders and similar widgets.
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -1222,6 +1947,12 @@ pub struct DebugOptions {
     ///
     /// See [`emath::GuiRounding`] for more.
     pub show_unaligned: bool,
+
+    /// Outline the shapes that changed since the previous frame,
+    /// and list what requested the repaint.
+    ///
+    /// Useful for tracking down why egui keeps repainting.
+    pub show_paint_diff: bool,
 }
 
 #[cfg(debug_assertions)]
@@ -1238,6 +1969,7 @@ impl Default for DebugOptions {
             show_interactive_widgets: false,
             show_widget_hits: false,
             show_unaligned: cfg!(debug_assertions),
+            show_paint_diff: false,
         }
     }
 }
@@ -1268,18 +2000,26 @@ impl Default for Style {
             text_styles: default_text_styles(),
             drag_value_text_style: TextStyle::Button,
             number_formatter: NumberFormatter(Arc::new(emath::format_with_decimals_in_range)),
+            locale: "en".to_owned(),
+            number_locale: Default::default(),
+            units: Default::default(),
+            layout_direction: LayoutDirection::LeftToRight,
             wrap: None,
             wrap_mode: None,
             spacing: Spacing::default(),
             interaction: Interaction::default(),
             visuals: Visuals::default(),
-            animation_time: 1.0 / 12.0,
+            animation: AnimationStyle::default(),
+            animation_time: DEFAULT_ANIMATION_TIME,
             #[cfg(debug_assertions)]
             debug: Default::default(),
             explanation_tooltips: false,
             url_in_tooltip: false,
             always_scroll_the_only_direction: false,
             scroll_animation: ScrollAnimation::default(),
+            kinetic_scrolling: KineticScrolling::default(),
+            window_snapping: WindowSnapping::default(),
+            dimension: DimensionStyle::default(),
             compact_menu_style: true,
         }
     }
@@ -1305,6 +2045,7 @@ impl Default for Spacing {
             tooltip_width: 500.0,
             menu_width: 400.0,
             menu_spacing: 2.0,
+            popup_arrow_size: 6.0,
             combo_height: 200.0,
             scroll: Default::default(),
             indent_ends_with_horizontal_line: false,
@@ -1316,11 +2057,14 @@ impl Default for Interaction {
     fn default() -> Self {
         Self {
             interact_radius: 5.0,
+            touch_target_size: 0.0,
             resize_grab_radius_side: 5.0,
             resize_grab_radius_corner: 10.0,
             show_tooltips_only_when_still: true,
             tooltip_delay: 0.5,
             tooltip_grace_time: 0.2,
+            tooltip_hide_delay: 0.0,
+            tooltip_group_grace_time: 1.0,
             selectable_labels: true,
             multi_widget_text_select: true,
         }
@@ -1425,6 +2169,181 @@ impl Visuals {
             ..Self::dark()
         }
     }
+
+    /// Dark theme with maximum contrast: white text and outlines on black.
+    ///
+    /// Every widget has a visible outline, and hovered widgets are outlined in yellow.
+    pub fn high_contrast_dark() -> Self {
+        let accent = Color32::from_rgb(255, 230, 0); // yellow
+        Self {
+            widgets: Widgets::high_contrast(Color32::BLACK, Color32::WHITE, accent),
+            selection: Selection {
+                bg_fill: Color32::from_rgb(0, 80, 200),
+                stroke: Stroke::new(1.5, Color32::WHITE),
+            },
+            hyperlink_color: Color32::from_rgb(120, 200, 255),
+            faint_bg_color: Color32::from_gray(24),
+            extreme_bg_color: Color32::BLACK,
+            code_bg_color: Color32::from_gray(40),
+            warn_fg_color: Color32::from_rgb(255, 170, 0),
+            error_fg_color: Color32::from_rgb(255, 100, 100),
+
+            window_fill: Color32::BLACK,
+            window_stroke: Stroke::new(1.5, Color32::WHITE),
+            panel_fill: Color32::BLACK,
+
+            text_cursor: TextCursorStyle {
+                stroke: Stroke::new(2.0, accent),
+                ..Default::default()
+            },
+
+            ..Self::dark()
+        }
+    }
+
+    /// Light theme with maximum contrast: black text and outlines on white.
+    ///
+    /// Every widget has a visible outline, and hovered widgets are outlined in dark blue.
+    pub fn high_contrast_light() -> Self {
+        let accent = Color32::from_rgb(0, 60, 200); // dark blue
+        Self {
+            widgets: Widgets::high_contrast(Color32::WHITE, Color32::BLACK, accent),
+            selection: Selection {
+                bg_fill: Color32::from_rgb(0, 60, 200),
+                stroke: Stroke::new(1.5, Color32::WHITE),
+            },
+            hyperlink_color: Color32::from_rgb(0, 50, 180),
+            faint_bg_color: Color32::from_gray(235),
+            extreme_bg_color: Color32::WHITE,
+            code_bg_color: Color32::from_gray(225),
+            warn_fg_color: Color32::from_rgb(150, 70, 0),
+            error_fg_color: Color32::from_rgb(190, 0, 0),
+
+            window_fill: Color32::WHITE,
+            window_stroke: Stroke::new(1.5, Color32::BLACK),
+            panel_fill: Color32::WHITE,
+
+            text_cursor: TextCursorStyle {
+                stroke: Stroke::new(2.0, accent),
+                ..Default::default()
+            },
+
+            ..Self::light()
+        }
+    }
+
+    /// Like [`Self::dark`], but with colors that can be told apart
+    /// with red-green color blindness (protanopia and deuteranopia).
+    ///
+    /// Warnings are yellow and errors are magenta, so they differ along the blue-yellow axis,
+    /// which is preserved with red-green color blindness.
+    pub fn color_blind_safe_dark() -> Self {
+        Self {
+            hyperlink_color: Color32::from_rgb(86, 180, 233), // sky blue
+            warn_fg_color: Color32::from_rgb(240, 228, 66),   // yellow
+            error_fg_color: Color32::from_rgb(230, 120, 190), // reddish purple
+            ..Self::dark()
+        }
+    }
+
+    /// Like [`Self::light`], but with colors that can be told apart
+    /// with red-green color blindness (protanopia and deuteranopia).
+    ///
+    /// See [`Self::color_blind_safe_dark`].
+    pub fn color_blind_safe_light() -> Self {
+        Self {
+            hyperlink_color: Color32::from_rgb(0, 114, 178), // blue
+            warn_fg_color: Color32::from_rgb(160, 110, 0),   // dark yellow
+            error_fg_color: Color32::from_rgb(170, 40, 120), // reddish purple
+            ..Self::light()
+        }
+    }
+
+    /// Change every color of these visuals with the given function.
+    ///
+    /// Useful for deriving a new theme from an existing one,
+    /// e.g. by darkening it or changing its saturation.
+    pub fn map_colors(&mut self, mut f: impl FnMut(Color32) -> Color32) {
+        let Self {
+            dark_mode: _,
+            override_text_color,
+            widgets,
+            selection,
+            hyperlink_color,
+            faint_bg_color,
+            extreme_bg_color,
+            code_bg_color,
+            warn_fg_color,
+            error_fg_color,
+            window_corner_radius: _,
+            window_shadow,
+            window_fill,
+            window_stroke,
+            window_highlight_topmost: _,
+            menu_corner_radius: _,
+            panel_fill,
+            popup_shadow,
+            resize_corner_size: _,
+            text_cursor,
+            clip_rect_margin: _,
+            button_frame: _,
+            collapsing_header_frame: _,
+            indent_has_left_vline: _,
+            striped: _,
+            slider_trailing_fill: _,
+            handle_shape: _,
+            interact_cursor: _,
+            image_loading_spinners: _,
+            numeric_color_space: _,
+        } = self;
+
+        if let Some(color) = override_text_color {
+            *color = f(*color);
+        }
+        let Widgets {
+            noninteractive,
+            inactive,
+            hovered,
+            active,
+            open,
+        } = widgets;
+        for widget in [noninteractive, inactive, hovered, active, open] {
+            widget.bg_fill = f(widget.bg_fill);
+            widget.weak_bg_fill = f(widget.weak_bg_fill);
+            widget.bg_stroke.color = f(widget.bg_stroke.color);
+            widget.fg_stroke.color = f(widget.fg_stroke.color);
+        }
+        selection.bg_fill = f(selection.bg_fill);
+        selection.stroke.color = f(selection.stroke.color);
+        for color in [
+            hyperlink_color,
+            faint_bg_color,
+            extreme_bg_color,
+            code_bg_color,
+            warn_fg_color,
+            error_fg_color,
+            &mut window_shadow.color,
+            window_fill,
+            &mut window_stroke.color,
+            panel_fill,
+            &mut popup_shadow.color,
+            &mut text_cursor.stroke.color,
+        ] {
+            *color = f(*color);
+        }
+    }
+
+    /// Show these visuals as they would look with the given kind of color vision.
+    ///
+    /// Use this to check that a custom theme works with color blindness,
+    /// e.g. `ctx.set_visuals(visuals.simulate_color_vision(ColorVision::Deuteranopia))`.
+    ///
+    /// Use [`Self::map_colors`] with [`crate::ecolor::apply_color_matrix`] to use your own matrix.
+    pub fn simulate_color_vision(&self, vision: crate::ecolor::ColorVision) -> Self {
+        let mut visuals = self.clone();
+        visuals.map_colors(|color| vision.simulate(color));
+        visuals
+    }
 }
 
 impl Default for Visuals {
@@ -1433,6 +2352,26 @@ impl Default for Visuals {
     }
 }
 
+#[test]
+fn color_blind_safe_warnings_and_errors_differ() {
+    use crate::ecolor::ColorVision;
+
+    fn distance(a: Color32, b: Color32) -> i32 {
+        (0..3).map(|i| (a[i] as i32 - b[i] as i32).abs()).sum()
+    }
+
+    for visuals in [
+        Visuals::color_blind_safe_dark(),
+        Visuals::color_blind_safe_light(),
+    ] {
+        for vision in [ColorVision::Protanopia, ColorVision::Deuteranopia] {
+            let simulated = visuals.simulate_color_vision(vision);
+            let d = distance(simulated.warn_fg_color, simulated.error_fg_color);
+            assert!(d > 150, "{vision:?}: {d}");
+        }
+    }
+}
+
 impl Selection {
     fn dark() -> Self {
         Self {
@@ -1545,6 +2484,30 @@ impl Widgets {
             },
         }
     }
+
+    /// Widgets with an outline in the `fg` color, on a `bg` background,
+    /// highlighted with `accent` when hovered or clicked.
+    fn high_contrast(bg: Color32, fg: Color32, accent: Color32) -> Self {
+        let widget = |bg_stroke: Stroke, fg_stroke: Stroke, expansion: f32| WidgetVisuals {
+            weak_bg_fill: bg,
+            bg_fill: bg,
+            bg_stroke,
+            fg_stroke,
+            corner_radius: CornerRadius::same(2),
+            expansion,
+        };
+        Self {
+            noninteractive: widget(Stroke::new(1.0, fg), Stroke::new(1.0, fg), 0.0),
+            inactive: widget(Stroke::new(1.0, fg), Stroke::new(1.0, fg), 0.0),
+            hovered: widget(Stroke::new(2.0, accent), Stroke::new(1.5, fg), 1.0),
+            active: WidgetVisuals {
+                weak_bg_fill: accent,
+                bg_fill: accent,
+                ..widget(Stroke::new(2.0, accent), Stroke::new(2.0, bg), 1.0)
+            },
+            open: widget(Stroke::new(1.5, fg), Stroke::new(1.0, fg), 0.0),
+        }
+    }
 }
 
 impl Default for Widgets {
@@ -1570,18 +2533,26 @@ impl Style {
             text_styles,
             drag_value_text_style,
             number_formatter: _, // can't change callbacks in the UI
+            locale,
+            number_locale,
+            units: _,
+            layout_direction,
             wrap: _,
             wrap_mode,
             spacing,
             interaction,
             visuals,
-            animation_time,
+            animation,
+            animation_time: _,
             #[cfg(debug_assertions)]
             debug,
             explanation_tooltips,
             url_in_tooltip,
             always_scroll_the_only_direction,
             scroll_animation,
+            kinetic_scrolling,
+            window_snapping,
+            dimension,
             compact_menu_style,
         } = self;
 
@@ -1652,6 +2623,46 @@ impl Style {
                 });
             ui.end_row();
 
+            ui.label("Locale");
+            ui.add(crate::TextEdit::singleline(locale).desired_width(60.0));
+            ui.end_row();
+
+            ui.label("Number locale");
+            let locales = [
+                (crate::NumberLocale::default(), "1234.5"),
+                (crate::NumberLocale::ENGLISH, "1,234.5"),
+                (crate::NumberLocale::GERMAN, "1.234,5"),
+                (crate::NumberLocale::FRENCH, "1\u{202F}234,5"),
+            ];
+            crate::ComboBox::from_id_salt("number_locale")
+                .selected_text(
+                    locales
+                        .iter()
+                        .find(|(locale, _)| locale == number_locale)
+                        .map_or("Custom", |(_, example)| example),
+                )
+                .show_ui(ui, |ui| {
+                    for (locale, example) in locales {
+                        ui.selectable_value(number_locale, locale, example);
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Layout direction");
+            ui.horizontal(|ui| {
+                ui.selectable_value(
+                    layout_direction,
+                    LayoutDirection::LeftToRight,
+                    "Left to right",
+                );
+                ui.selectable_value(
+                    layout_direction,
+                    LayoutDirection::RightToLeft,
+                    "Right to left",
+                );
+            });
+            ui.end_row();
+
             ui.label("Text Wrap Mode");
             crate::ComboBox::from_id_salt("text_wrap_mode")
                 .selected_text(format!("{wrap_mode:?}"))
@@ -1668,22 +2679,17 @@ impl Style {
                     }
                 });
             ui.end_row();
-
-            ui.label("Animation duration");
-            ui.add(
-                DragValue::new(animation_time)
-                    .range(0.0..=1.0)
-                    .speed(0.02)
-                    .suffix(" s"),
-            );
-            ui.end_row();
         });
 
         ui.collapsing("🔠 Text Styles", |ui| text_styles_ui(ui, text_styles));
         ui.collapsing("📏 Spacing", |ui| spacing.ui(ui));
         ui.collapsing("☝ Interaction", |ui| interaction.ui(ui));
         ui.collapsing("🎨 Visuals", |ui| visuals.ui(ui));
+        ui.collapsing("🎞 Animation", |ui| animation.ui(ui));
         ui.collapsing("🔄 Scroll Animation", |ui| scroll_animation.ui(ui));
+        ui.collapsing("🖐 Kinetic Scrolling", |ui| kinetic_scrolling.ui(ui));
+        ui.collapsing("🧲 Window Snapping", |ui| window_snapping.ui(ui));
+        ui.collapsing("📐 Dimensions", |ui| dimension.ui(ui));
 
         #[cfg(debug_assertions)]
         ui.collapsing("🐛 Debug", |ui| debug.ui(ui));
@@ -1740,6 +2746,7 @@ impl Spacing {
             tooltip_width,
             menu_width,
             menu_spacing,
+            popup_arrow_size,
             indent_ends_with_horizontal_line,
             combo_height,
             scroll,
@@ -1808,6 +2815,11 @@ impl Spacing {
                 ui.add(DragValue::new(menu_spacing).range(0.0..=10.0));
                 ui.end_row();
 
+                ui.label("Popup arrow size")
+                    .on_hover_text("Arrow from popups to what they belong to. 0 = no arrow");
+                ui.add(DragValue::new(popup_arrow_size).range(0.0..=20.0));
+                ui.end_row();
+
                 ui.label("Checkboxes etc");
                 ui.vertical(|ui| {
                     ui.add(
@@ -1851,11 +2863,14 @@ impl Interaction {
     pub fn ui(&mut self, ui: &mut crate::Ui) {
         let Self {
             interact_radius,
+            touch_target_size,
             resize_grab_radius_side,
             resize_grab_radius_corner,
             show_tooltips_only_when_still,
             tooltip_delay,
             tooltip_grace_time,
+            tooltip_hide_delay,
+            tooltip_group_grace_time,
             selectable_labels,
             multi_widget_text_select,
         } = self;
@@ -1871,6 +2886,12 @@ impl Interaction {
                 ui.add(DragValue::new(interact_radius).range(0.0..=20.0));
                 ui.end_row();
 
+                ui.label("touch_target_size").on_hover_text(
+                    "When using a touch screen, make smaller widgets at least this easy to hit. 0 = off",
+                );
+                ui.add(DragValue::new(touch_target_size).range(0.0..=80.0));
+                ui.end_row();
+
                 ui.label("resize_grab_radius_side").on_hover_text("Radius of the interactive area of the side of a window during drag-to-resize");
                 ui.add(DragValue::new(resize_grab_radius_side).range(0.0..=20.0));
                 ui.end_row();
@@ -1900,6 +2921,28 @@ impl Interaction {
                         .suffix(" s"),
                 );
                 ui.end_row();
+
+                ui.label("Tooltip hide delay").on_hover_text(
+                    "How long a tooltip stays open after the pointer has left its widget",
+                );
+                ui.add(
+                    DragValue::new(tooltip_hide_delay)
+                        .range(0.0..=1.0)
+                        .speed(0.05)
+                        .suffix(" s"),
+                );
+                ui.end_row();
+
+                ui.label("Tooltip group grace time").on_hover_text(
+                    "Like the grace time, but when moving between tooltips of the same group, e.g. in a toolbar",
+                );
+                ui.add(
+                    DragValue::new(tooltip_group_grace_time)
+                        .range(0.0..=5.0)
+                        .speed(0.05)
+                        .suffix(" s"),
+                );
+                ui.end_row();
             });
 
         ui.checkbox(
@@ -2252,6 +3295,7 @@ impl DebugOptions {
             show_interactive_widgets,
             show_widget_hits,
             show_unaligned,
+            show_paint_diff,
         } = self;
 
         {
@@ -2286,6 +3330,11 @@ impl DebugOptions {
             "Show rectangles not aligned to integer point coordinates",
         );
 
+        ui.checkbox(
+            show_paint_diff,
+            "Show shapes that changed since last frame, and why we repainted",
+        );
+
         ui.vertical_centered(|ui| reset_button(ui, self, "Reset debug options"));
     }
 }
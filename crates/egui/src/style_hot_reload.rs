@@ -0,0 +1,156 @@
+//! Reload the [`Style`] from a file whenever the file changes,
+//! so that colors, strokes, corner radii etc. can be tweaked in a running app.
+//!
+//! The file is polled for changes, so this needs no extra dependencies,
+//! but it only works on platforms with a file system (i.e. not on the web).
+//!
+//! ```no_run
+//! # let ctx = egui::Context::default();
+//! # #[cfg(feature = "persistence")]
+//! egui::style_hot_reload::StyleFileWatcher::ron("assets/style.ron").install(&ctx);
+//! ```
+//!
+//! Use [`StyleFileWatcher::toml`] for TOML files (with the `toml` feature),
+//! or pass a parser for any other format to [`StyleFileWatcher::new`].
+
+use std::{path::PathBuf, sync::Arc, time::SystemTime};
+
+use epaint::mutex::Mutex;
+
+use crate::{Context, Style, Theme};
+
+/// Turns the contents of a style file into a [`Style`].
+pub type StyleParser = fn(&str) -> Result<Style, String>;
+
+/// Watches a style file, and applies it to a [`Context`] whenever it changes.
+///
+/// Either call [`Self::poll`] yourself each frame, or let egui do it with [`Self::install`].
+pub struct StyleFileWatcher {
+    path: PathBuf,
+    parse: StyleParser,
+    theme: Option<Theme>,
+    poll_interval: f32,
+
+    last_poll: Option<f64>,
+    last_modified: Option<SystemTime>,
+    last_error: Option<String>,
+}
+
+impl StyleFileWatcher {
+    /// Watch the file at `path`, using `parse` to read it.
+    pub fn new(path: impl Into<PathBuf>, parse: StyleParser) -> Self {
+        Self {
+            path: path.into(),
+            parse,
+            theme: None,
+            poll_interval: 0.5,
+            last_poll: None,
+            last_modified: None,
+            last_error: None,
+        }
+    }
+
+    /// Watch a [RON](https://github.com/ron-rs/ron) file, see [`Style::from_ron`].
+    #[cfg(feature = "persistence")]
+    pub fn ron(path: impl Into<PathBuf>) -> Self {
+        Self::new(path, |text| {
+            Style::from_ron(text).map_err(|err| err.to_string())
+        })
+    }
+
+    /// Watch a [TOML](https://toml.io) file, see [`Style::from_toml`].
+    #[cfg(feature = "toml")]
+    pub fn toml(path: impl Into<PathBuf>) -> Self {
+        Self::new(path, |text| {
+            Style::from_toml(text).map_err(|err| err.to_string())
+        })
+    }
+
+    /// Only change the style of this theme.
+    ///
+    /// By default the style of whatever theme is in use is replaced.
+    #[inline]
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// How often to check if the file changed, in seconds.
+    ///
+    /// egui will repaint at least this often while the file is watched.
+    /// Default: `0.5`.
+    #[inline]
+    pub fn poll_interval(mut self, seconds: f32) -> Self {
+        self.poll_interval = seconds;
+        self
+    }
+
+    /// Why the file could not be read or parsed the last time it changed, if it couldn't.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// Check if the file has changed, and if so, load it and apply it to `ctx`.
+    ///
+    /// Returns `true` if a new style was applied.
+    pub fn poll(&mut self, ctx: &Context) -> bool {
+        let now = ctx.input(|i| i.time);
+        ctx.request_repaint_after_secs(self.poll_interval);
+        if let Some(last_poll) = self.last_poll {
+            if now < last_poll + self.poll_interval as f64 {
+                return false;
+            }
+        }
+        self.last_poll = Some(now);
+
+        let modified = std::fs::metadata(&self.path).and_then(|metadata| metadata.modified());
+        let modified = match modified {
+            Ok(modified) => modified,
+            Err(err) => {
+                self.set_error(format!("Failed to read {:?}: {err}", self.path));
+                return false;
+            }
+        };
+        if self.last_modified == Some(modified) {
+            return false;
+        }
+        self.last_modified = Some(modified);
+
+        let style = std::fs::read_to_string(&self.path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| (self.parse)(&text));
+        match style {
+            Ok(style) => {
+                self.last_error = None;
+                match self.theme {
+                    Some(theme) => ctx.set_style_of(theme, style),
+                    None => ctx.set_style(style),
+                }
+                true
+            }
+            Err(err) => {
+                self.set_error(format!("Failed to load style from {:?}: {err}", self.path));
+                false
+            }
+        }
+    }
+
+    /// Poll the file at the start of every pass of `ctx`.
+    pub fn install(self, ctx: &Context) {
+        let watcher = Mutex::new(self);
+        ctx.on_begin_pass(
+            "style_hot_reload",
+            Arc::new(move |ctx| {
+                watcher.lock().poll(ctx);
+            }),
+        );
+    }
+
+    fn set_error(&mut self, error: String) {
+        if self.last_error.as_ref() != Some(&error) {
+            #[cfg(feature = "log")]
+            log::warn!("{error}");
+        }
+        self.last_error = Some(error);
+    }
+}
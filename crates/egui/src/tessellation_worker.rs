@@ -0,0 +1,271 @@
+//! Tessellate on a background thread, so that building the next frame overlaps with tessellating this one.
+//!
+//! ```
+//! # let ctx = egui::Context::default();
+//! # let mut paint = |_frame: egui::tessellation_worker::TessellatedFrame| {};
+//! let mut worker = egui::tessellation_worker::TessellationWorker::new();
+//! for _ in 0..3 {
+//!     let output = ctx.run(Default::default(), |ctx| {
+//!         egui::CentralPanel::default().show(ctx, |ui| ui.label("Hello"));
+//!     });
+//!     // Paint the previous frame while this one is being tessellated:
+//!     let previous = worker.submit(
+//!         &ctx,
+//!         output.shapes,
+//!         output.textures_delta,
+//!         output.pixels_per_point,
+//!     );
+//!     if let Some(frame) = previous {
+//!         paint(frame);
+//!     }
+//! }
+//! if let Some(frame) = worker.flush() {
+//!     paint(frame);
+//! }
+//! ```
+//!
+//! Turn on [`crate::Options::tessellate_in_background`] to have [`crate::Context::run`] do this for you.
+//!
+//! Each frame is one frame behind the UI, which is usually made up for
+//! by the time saved in building and tessellating in parallel.
+
+use std::sync::mpsc;
+
+use epaint::{tessellator::Tessellator, textures::TexturesDelta, ClippedPrimitive, ClippedShape};
+
+use crate::Context;
+
+struct Job {
+    tessellator: Tessellator,
+    shapes: Vec<ClippedShape>,
+    textures_delta: TexturesDelta,
+    pixels_per_point: f32,
+}
+
+/// A frame tessellated by a [`TessellationWorker`], ready to paint.
+pub struct TessellatedFrame {
+    pub primitives: Vec<ClippedPrimitive>,
+
+    /// The texture changes of this frame.
+    ///
+    /// Apply [`TexturesDelta::set`] before painting the [`Self::primitives`],
+    /// and [`TexturesDelta::free`] after.
+    /// Since these travel with the frame, a texture freed by a later frame is never freed
+    /// before the frames using it have been painted.
+    pub textures_delta: TexturesDelta,
+
+    pub pixels_per_point: f32,
+}
+
+/// Tessellates frames on a background thread, one frame at a time.
+///
+/// Dropping the worker stops the thread.
+pub struct TessellationWorker {
+    jobs: Option<mpsc::Sender<Job>>,
+    frames: mpsc::Receiver<TessellatedFrame>,
+    thread: Option<std::thread::JoinHandle<()>>,
+
+    /// Is a frame being tessellated?
+    in_flight: bool,
+}
+
+impl Default for TessellationWorker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TessellationWorker {
+    /// Start the background thread.
+    pub fn new() -> Self {
+        let (job_tx, job_rx) = mpsc::channel::<Job>();
+        let (frame_tx, frame_rx) = mpsc::channel();
+        let thread = std::thread::Builder::new()
+            .name("egui_tessellator".to_owned())
+            .spawn(move || {
+                for job in job_rx {
+                    let Job {
+                        mut tessellator,
+                        shapes,
+                        textures_delta,
+                        pixels_per_point,
+                    } = job;
+                    let primitives = {
+                        profiling::scope!("tessellator::tessellate_shapes");
+                        tessellator.tessellate_shapes(shapes)
+                    };
+                    let frame = TessellatedFrame {
+                        primitives,
+                        textures_delta,
+                        pixels_per_point,
+                    };
+                    if frame_tx.send(frame).is_err() {
+                        break; // The worker was dropped
+                    }
+                }
+            })
+            .expect("Failed to spawn tessellation thread");
+
+        Self {
+            jobs: Some(job_tx),
+            frames: frame_rx,
+            thread: Some(thread),
+            in_flight: false,
+        }
+    }
+
+    /// Start tessellating the output of a pass, and return the previously submitted frame.
+    ///
+    /// Blocks until the previous frame is done, which it usually is,
+    /// since it has been tessellating while you ran the last pass.
+    ///
+    /// The [`Context`] is only used to set up the tessellator with the current
+    /// [`crate::epaint::TessellationOptions`] and font atlas, so the texture coordinates
+    /// match the `textures_delta` of the same pass.
+    pub fn submit(
+        &mut self,
+        ctx: &Context,
+        shapes: Vec<ClippedShape>,
+        textures_delta: TexturesDelta,
+        pixels_per_point: f32,
+    ) -> Option<TessellatedFrame> {
+        let previous = self.flush();
+        let job = Job {
+            tessellator: ctx.tessellator(pixels_per_point),
+            shapes,
+            textures_delta,
+            pixels_per_point,
+        };
+        if let Some(jobs) = &self.jobs {
+            self.in_flight = jobs.send(job).is_ok();
+        }
+        previous
+    }
+
+    /// Wait for the frame that is being tessellated, if any.
+    pub fn flush(&mut self) -> Option<TessellatedFrame> {
+        if !self.in_flight {
+            return None;
+        }
+        self.in_flight = false;
+        self.frames.recv().ok()
+    }
+
+    /// Is a frame being tessellated?
+    pub fn is_busy(&self) -> bool {
+        self.in_flight
+    }
+}
+
+impl Drop for TessellationWorker {
+    fn drop(&mut self) {
+        self.jobs = None; // Ends the loop of the thread
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
+
+#[test]
+fn test_tessellation_worker() {
+    let ctx = Context::default();
+    let run = || {
+        ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                ui.label("Hello");
+                let _ = ui.button("World");
+            });
+        })
+    };
+
+    let mut worker = TessellationWorker::new();
+    assert!(worker.flush().is_none());
+
+    let first = run();
+    let expected = ctx.tessellate(first.shapes.clone(), first.pixels_per_point);
+    assert!(worker
+        .submit(
+            &ctx,
+            first.shapes,
+            first.textures_delta,
+            first.pixels_per_point,
+        )
+        .is_none());
+    assert!(worker.is_busy());
+
+    let second = run();
+    let frame = worker
+        .submit(
+            &ctx,
+            second.shapes,
+            second.textures_delta,
+            second.pixels_per_point,
+        )
+        .unwrap();
+    assert_eq!(frame.primitives.len(), expected.len());
+    assert!(
+        !frame.textures_delta.set.is_empty(),
+        "The font atlas of the first frame travels with it"
+    );
+    for (got, expected) in frame.primitives.iter().zip(&expected) {
+        assert_eq!(got.clip_rect, expected.clip_rect);
+        match (&got.primitive, &expected.primitive) {
+            (epaint::Primitive::Mesh(got), epaint::Primitive::Mesh(expected)) => {
+                assert_eq!(got, expected);
+            }
+            _ => panic!("Expected meshes"),
+        }
+    }
+
+    assert!(worker.flush().is_some());
+    assert!(!worker.is_busy());
+}
+
+#[test]
+fn test_tessellate_in_background() {
+    let ctx = Context::default();
+    ctx.options_mut(|o| o.tessellate_in_background = true);
+    let run = |text: &str| {
+        ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| ui.label(text));
+            ctx.request_repaint();
+        })
+    };
+
+    // Nothing tessellated yet, so the first frame is left for the backend:
+    let first = run("Hello");
+    assert!(first.tessellated.is_none());
+    assert!(!first.shapes.is_empty());
+    assert!(!first.textures_delta.set.is_empty());
+    let expected = ctx.tessellate(first.shapes, first.pixels_per_point);
+
+    // From then on we get the previous frame:
+    let second = run("Hello λ");
+    assert!(second.shapes.is_empty());
+    let second_primitives = second.tessellated.unwrap();
+    assert_eq!(second_primitives.len(), expected.len());
+    for (got, expected) in second_primitives.iter().zip(&expected) {
+        match (&got.primitive, &expected.primitive) {
+            (epaint::Primitive::Mesh(got), epaint::Primitive::Mesh(expected)) => {
+                assert_eq!(got, expected);
+            }
+            _ => panic!("Expected meshes"),
+        }
+    }
+    assert!(
+        second.textures_delta.set.is_empty(),
+        "The glyph of the new frame is not shown yet"
+    );
+
+    // The glyph comes with the frame that shows it:
+    let third = run("Hello μ");
+    assert!(!third.tessellated.unwrap().is_empty());
+    assert!(!third.textures_delta.set.is_empty());
+
+    // Once we stop pipelining, the frame in flight is dropped, but its texture changes are kept:
+    ctx.options_mut(|o| o.tessellate_in_background = false);
+    let last = run("Hello");
+    assert!(last.tessellated.is_none());
+    assert!(!last.shapes.is_empty());
+    assert!(!last.textures_delta.set.is_empty());
+}
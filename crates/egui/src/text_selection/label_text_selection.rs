@@ -63,6 +63,12 @@ struct CurrentSelection {
     /// This is to constrain a selection to a single Window.
     pub layer_id: LayerId,
 
+    /// The selection is in the panel or area [`Ui`] with this id.
+    ///
+    /// This is to constrain a selection to a single panel,
+    /// since the panels share a layer.
+    pub scope: Id,
+
     /// When selecting with a mouse, this is where the mouse was released.
     /// When moving with e.g. shift+arrows, this is what moves.
     /// Note that the two ends can come in any order, and also be equal (no selection).
@@ -96,9 +102,10 @@ pub struct LabelSelectionState {
     /// Have we reached the widget containing the secondary selection?
     has_reached_secondary: bool,
 
-    /// Accumulated text to copy.
-    text_to_copy: String,
-    last_copied_galley_rect: Option<Rect>,
+    /// The selected text of each label this frame, to copy in visual order.
+    ///
+    /// The screen rectangle of each label, its text and its row height.
+    text_to_copy: Vec<(Rect, String, f32)>,
 
     /// Painted selections this frame.
     ///
@@ -117,7 +124,6 @@ impl Default for LabelSelectionState {
             has_reached_primary: Default::default(),
             has_reached_secondary: Default::default(),
             text_to_copy: Default::default(),
-            last_copied_galley_rect: Default::default(),
             painted_selections: Default::default(),
         }
     }
@@ -157,7 +163,6 @@ impl LabelSelectionState {
         state.has_reached_primary = false;
         state.has_reached_secondary = false;
         state.text_to_copy.clear();
-        state.last_copied_galley_rect = None;
         state.painted_selections.clear();
 
         state.store(ctx);
@@ -219,7 +224,7 @@ impl LabelSelectionState {
             state.is_dragging = false;
         }
 
-        let text_to_copy = std::mem::take(&mut state.text_to_copy);
+        let text_to_copy = text_in_visual_order(std::mem::take(&mut state.text_to_copy));
         if !text_to_copy.is_empty() {
             ctx.copy_text(text_to_copy);
         }
@@ -237,48 +242,10 @@ impl LabelSelectionState {
 
     fn copy_text(&mut self, new_galley_rect: Rect, galley: &Galley, cursor_range: &CCursorRange) {
         let new_text = selected_text(galley, cursor_range);
-        if new_text.is_empty() {
-            return;
-        }
-
-        if self.text_to_copy.is_empty() {
-            self.text_to_copy = new_text;
-            self.last_copied_galley_rect = Some(new_galley_rect);
-            return;
-        }
-
-        let Some(last_copied_galley_rect) = self.last_copied_galley_rect else {
-            self.text_to_copy = new_text;
-            self.last_copied_galley_rect = Some(new_galley_rect);
-            return;
-        };
-
-        // We need to append or prepend the new text to the already copied text.
-        // We need to do so intelligently.
-
-        if last_copied_galley_rect.bottom() <= new_galley_rect.top() {
-            self.text_to_copy.push('\n');
-            let vertical_distance = new_galley_rect.top() - last_copied_galley_rect.bottom();
-            if estimate_row_height(galley) * 0.5 < vertical_distance {
-                self.text_to_copy.push('\n');
-            }
-        } else {
-            let existing_ends_with_space =
-                self.text_to_copy.chars().last().map(|c| c.is_whitespace());
-
-            let new_text_starts_with_space_or_punctuation = new_text
-                .chars()
-                .next()
-                .is_some_and(|c| c.is_whitespace() || c.is_ascii_punctuation());
-
-            if existing_ends_with_space == Some(false) && !new_text_starts_with_space_or_punctuation
-            {
-                self.text_to_copy.push(' ');
-            }
+        if !new_text.is_empty() {
+            self.text_to_copy
+                .push((new_galley_rect, new_text, estimate_row_height(galley)));
         }
-
-        self.text_to_copy.push_str(&new_text);
-        self.last_copied_galley_rect = Some(new_galley_rect);
     }
 
     /// Handle text selection state for a label or similar widget.
@@ -322,8 +289,10 @@ impl LabelSelectionState {
             return TextCursorState::default();
         };
 
-        if selection.layer_id != response.layer_id {
-            // Selection is in another layer
+        let scope = selection_scope(ui);
+
+        if selection.layer_id != response.layer_id || selection.scope != scope {
+            // Selection is in another layer or panel
             return TextCursorState::default();
         }
 
@@ -400,6 +369,7 @@ impl LabelSelectionState {
                         } else {
                             // A new selection in a new layer.
                             selection.layer_id = response.layer_id;
+                            selection.scope = scope;
                             selection.secondary = selection.primary;
                         }
                     }
@@ -571,6 +541,7 @@ impl LabelSelectionState {
                 let secondary_changed = Some(range.secondary) != old_range.map(|r| r.secondary);
 
                 selection.layer_id = response.layer_id;
+                selection.scope = selection_scope(ui);
 
                 if primary_changed || !ui.style().interaction.multi_widget_text_select {
                     selection.primary =
@@ -590,6 +561,7 @@ impl LabelSelectionState {
                 // Start of a new selection
                 self.selection = Some(CurrentSelection {
                     layer_id: response.layer_id,
+                    scope: selection_scope(ui),
                     primary: WidgetTextCursor::new(
                         widget_id,
                         range.primary,
@@ -656,6 +628,65 @@ impl LabelSelectionState {
     }
 }
 
+/// The id of the closest panel or area containing the [`Ui`], to which text selection is constrained.
+fn selection_scope(ui: &Ui) -> Id {
+    ui.stack()
+        .iter()
+        .find(|frame| frame.is_panel_ui() || frame.is_area_ui() || frame.is_root_ui())
+        .map_or(Id::NULL, |frame| frame.id)
+}
+
+/// Join the selected text of several labels,
+/// top-down and then left-to-right, like the text is read.
+///
+/// Takes the screen rectangle of each label, its text, and its row height.
+fn text_in_visual_order(mut pieces: Vec<(Rect, String, f32)>) -> String {
+    pieces.sort_by(|(a, ..), (b, ..)| a.top().total_cmp(&b.top()));
+
+    // Group labels that share a line, e.g. in a horizontal layout:
+    let mut lines: Vec<Vec<(Rect, String, f32)>> = vec![];
+    for piece in pieces {
+        match lines.last_mut() {
+            Some(line) if piece.0.center().y < line[0].0.bottom() => line.push(piece),
+            _ => lines.push(vec![piece]),
+        }
+    }
+
+    let mut text = String::new();
+    let mut last_line_bottom = None;
+    for mut line in lines {
+        line.sort_by(|(a, ..), (b, ..)| a.left().total_cmp(&b.left()));
+
+        if let Some(last_line_bottom) = last_line_bottom {
+            text.push('\n');
+            let (rect, _, row_height) = &line[0];
+            if row_height * 0.5 < rect.top() - last_line_bottom {
+                // A paragraph break:
+                text.push('\n');
+            }
+        }
+        last_line_bottom = line.iter().map(|(rect, ..)| rect.bottom()).reduce(f32::max);
+
+        for (i, (_, new_text, _)) in line.into_iter().enumerate() {
+            let existing_ends_with_space = text.chars().last().map(|c| c.is_whitespace());
+
+            let new_text_starts_with_space_or_punctuation = new_text
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_whitespace() || c.is_ascii_punctuation());
+
+            if 0 < i
+                && existing_ends_with_space == Some(false)
+                && !new_text_starts_with_space_or_punctuation
+            {
+                text.push(' ');
+            }
+            text.push_str(&new_text);
+        }
+    }
+    text
+}
+
 fn got_copy_event(ctx: &Context) -> bool {
     ctx.input(|i| {
         i.events
@@ -707,3 +738,21 @@ fn estimate_row_height(galley: &Galley) -> f32 {
         galley.size().y
     }
 }
+
+#[test]
+fn test_text_in_visual_order() {
+    let piece = |x: f32, y: f32, text: &str| {
+        let rect = Rect::from_min_size(Pos2::new(x, y), crate::vec2(40.0, 10.0));
+        (rect, text.to_owned(), 10.0)
+    };
+
+    // Painted out of order, e.g. a right-to-left layout above a paragraph break:
+    let text = text_in_visual_order(vec![
+        piece(50.0, 0.0, "world"),
+        piece(0.0, 30.0, "Second paragraph."),
+        piece(0.0, 0.0, "Hello"),
+        piece(100.0, 1.0, "!"),
+        piece(0.0, 12.0, "Next line"),
+    ]);
+    assert_eq!(text, "Hello world!\nNext line\n\nSecond paragraph.");
+}
@@ -0,0 +1,436 @@
+//! Named design tokens (color roles, a spacing scale and a corner radius scale)
+//! from which a whole [`Style`] can be derived.
+//!
+//! Use [`ThemeTokens::apply`] to restyle everything from a handful of values,
+//! [`crate::Context::set_theme_tokens`] to switch themes at runtime,
+//! and [`crate::Ui::push_theme`] to override the theme of just a part of the ui.
+//!
+//! Widgets look up the color of a role with [`Visuals::color`],
+//! which works for any [`Visuals`], whether it was made from tokens or not.
+//! The built-in frames, text edits, hyperlinks, progress bars, spinners, scroll bars
+//! and rich text do too, so custom widgets doing the same will match them.
+
+use epaint::{Color32, CornerRadius, Margin};
+
+use crate::{vec2, Style, Visuals};
+
+/// The purpose of a color in a theme.
+///
+/// See [`Visuals::color`] and [`ThemeTokens::color`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ColorRole {
+    /// Background of panels.
+    Background,
+
+    /// Background of windows, menus and other things floating above the background.
+    Surface,
+
+    /// Background of things sunken into a surface, e.g. a [`crate::TextEdit`].
+    SurfaceSunken,
+
+    /// Background of interactive widgets, e.g. buttons.
+    Control,
+
+    /// Background of a hovered interactive widget.
+    ControlHovered,
+
+    /// Background of an interactive widget that is being clicked or dragged.
+    ControlActive,
+
+    /// Normal text.
+    Text,
+
+    /// Text that should not stand out.
+    TextWeak,
+
+    /// Text that should stand out, e.g. headings and hovered buttons.
+    TextStrong,
+
+    /// Separators, window outlines, etc.
+    Border,
+
+    /// Background of selected things.
+    Accent,
+
+    /// Text and outlines on top of [`Self::Accent`].
+    OnAccent,
+
+    Hyperlink,
+
+    Warning,
+
+    Error,
+
+    /// Background of inline code.
+    CodeBackground,
+}
+
+impl ColorRole {
+    pub const ALL: [Self; 16] = [
+        Self::Background,
+        Self::Surface,
+        Self::SurfaceSunken,
+        Self::Control,
+        Self::ControlHovered,
+        Self::ControlActive,
+        Self::Text,
+        Self::TextWeak,
+        Self::TextStrong,
+        Self::Border,
+        Self::Accent,
+        Self::OnAccent,
+        Self::Hyperlink,
+        Self::Warning,
+        Self::Error,
+        Self::CodeBackground,
+    ];
+}
+
+/// The color of each [`ColorRole`] (except [`ColorRole::TextWeak`], which is derived).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ColorTokens {
+    pub background: Color32,
+    pub surface: Color32,
+    pub surface_sunken: Color32,
+    pub control: Color32,
+    pub control_hovered: Color32,
+    pub control_active: Color32,
+    pub text: Color32,
+    pub text_strong: Color32,
+    pub border: Color32,
+    pub accent: Color32,
+    pub on_accent: Color32,
+    pub hyperlink: Color32,
+    pub warning: Color32,
+    pub error: Color32,
+    pub code_background: Color32,
+}
+
+impl ColorTokens {
+    /// The colors of [`Visuals::dark`].
+    pub fn dark() -> Self {
+        Self {
+            background: Color32::from_gray(27),
+            surface: Color32::from_gray(27),
+            surface_sunken: Color32::from_gray(10),
+            control: Color32::from_gray(60),
+            control_hovered: Color32::from_gray(70),
+            control_active: Color32::from_gray(55),
+            text: Color32::from_gray(140),
+            text_strong: Color32::WHITE,
+            border: Color32::from_gray(60),
+            accent: Color32::from_rgb(0, 92, 128),
+            on_accent: Color32::from_rgb(192, 222, 255),
+            hyperlink: Color32::from_rgb(90, 170, 255),
+            warning: Color32::from_rgb(255, 143, 0),
+            error: Color32::from_rgb(255, 0, 0),
+            code_background: Color32::from_gray(64),
+        }
+    }
+
+    /// The colors of [`Visuals::light`].
+    pub fn light() -> Self {
+        Self {
+            background: Color32::from_gray(248),
+            surface: Color32::from_gray(248),
+            surface_sunken: Color32::from_gray(255),
+            control: Color32::from_gray(230),
+            control_hovered: Color32::from_gray(220),
+            control_active: Color32::from_gray(165),
+            text: Color32::from_gray(80),
+            text_strong: Color32::BLACK,
+            border: Color32::from_gray(190),
+            accent: Color32::from_rgb(144, 209, 255),
+            on_accent: Color32::from_rgb(0, 83, 125),
+            hyperlink: Color32::from_rgb(0, 155, 255),
+            warning: Color32::from_rgb(255, 100, 0),
+            error: Color32::from_rgb(255, 0, 0),
+            code_background: Color32::from_gray(230),
+        }
+    }
+}
+
+/// Spacing, in points, from small to large.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SpacingScale {
+    /// E.g. the vertical padding of buttons.
+    pub xs: f32,
+
+    /// E.g. the vertical space between widgets, and the horizontal padding of buttons.
+    pub sm: f32,
+
+    /// E.g. the horizontal space between widgets, and the margins of windows.
+    pub md: f32,
+
+    /// E.g. the indentation of collapsing headers.
+    pub lg: f32,
+}
+
+impl Default for SpacingScale {
+    fn default() -> Self {
+        Self {
+            xs: 1.0,
+            sm: 4.0,
+            md: 8.0,
+            lg: 18.0,
+        }
+    }
+}
+
+/// Corner radii, from small to large.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RadiusScale {
+    /// Widgets, e.g. buttons.
+    pub small: u8,
+
+    /// Menus and popups.
+    pub medium: u8,
+
+    /// Windows.
+    pub large: u8,
+}
+
+impl Default for RadiusScale {
+    fn default() -> Self {
+        Self {
+            small: 2,
+            medium: 6,
+            large: 6,
+        }
+    }
+}
+
+/// A small set of named values that a whole [`Style`] can be derived from.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// let mut tokens = egui::ThemeTokens::dark();
+/// tokens.colors.accent = egui::Color32::from_rgb(160, 60, 200);
+/// tokens.radius.small = 6;
+///
+/// ui.push_theme(&tokens, |ui| {
+///     ui.label("This part of the ui uses the purple theme");
+///     let _ = ui.button("Rounder button");
+/// });
+/// # });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ThemeTokens {
+    pub dark_mode: bool,
+    pub colors: ColorTokens,
+    pub spacing: SpacingScale,
+    pub radius: RadiusScale,
+}
+
+impl ThemeTokens {
+    pub fn dark() -> Self {
+        Self {
+            dark_mode: true,
+            colors: ColorTokens::dark(),
+            spacing: SpacingScale::default(),
+            radius: RadiusScale::default(),
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            dark_mode: false,
+            colors: ColorTokens::light(),
+            spacing: SpacingScale::default(),
+            radius: RadiusScale::default(),
+        }
+    }
+
+    /// The color of the given role.
+    pub fn color(&self, role: ColorRole) -> Color32 {
+        let c = &self.colors;
+        match role {
+            ColorRole::Background => c.background,
+            ColorRole::Surface => c.surface,
+            ColorRole::SurfaceSunken => c.surface_sunken,
+            ColorRole::Control => c.control,
+            ColorRole::ControlHovered => c.control_hovered,
+            ColorRole::ControlActive => c.control_active,
+            ColorRole::Text => c.text,
+            ColorRole::TextWeak => crate::ecolor::tint_color_towards(c.text, c.surface),
+            ColorRole::TextStrong => c.text_strong,
+            ColorRole::Border => c.border,
+            ColorRole::Accent => c.accent,
+            ColorRole::OnAccent => c.on_accent,
+            ColorRole::Hyperlink => c.hyperlink,
+            ColorRole::Warning => c.warning,
+            ColorRole::Error => c.error,
+            ColorRole::CodeBackground => c.code_background,
+        }
+    }
+
+    /// Overwrite the colors, spacing and corner radii of `style` with these tokens.
+    ///
+    /// Things not covered by the tokens, like shadows, fonts and interaction settings, are left as is.
+    pub fn apply(&self, style: &mut Style) {
+        self.apply_to_visuals(&mut style.visuals);
+
+        let Self { spacing: s, .. } = *self;
+        style.spacing.item_spacing = vec2(s.md, s.sm);
+        style.spacing.button_padding = vec2(s.sm, s.xs);
+        style.spacing.menu_margin = Margin::same(s.md as i8);
+        style.spacing.window_margin = Margin::same(s.md as i8);
+        style.spacing.indent = s.lg;
+    }
+
+    /// Overwrite the colors and corner radii of `visuals` with these tokens.
+    pub fn apply_to_visuals(&self, visuals: &mut Visuals) {
+        let Self {
+            dark_mode,
+            colors: c,
+            radius,
+            ..
+        } = *self;
+
+        visuals.dark_mode = dark_mode;
+        visuals.override_text_color = None;
+        visuals.panel_fill = c.background;
+        visuals.window_fill = c.surface;
+        visuals.window_stroke.color = c.border;
+        visuals.extreme_bg_color = c.surface_sunken;
+        visuals.code_bg_color = c.code_background;
+        visuals.hyperlink_color = c.hyperlink;
+        visuals.warn_fg_color = c.warning;
+        visuals.error_fg_color = c.error;
+        visuals.selection.bg_fill = c.accent;
+        visuals.selection.stroke.color = c.on_accent;
+        visuals.text_cursor.stroke.color = c.on_accent;
+
+        visuals.window_corner_radius = CornerRadius::same(radius.large);
+        visuals.menu_corner_radius = CornerRadius::same(radius.medium);
+
+        let widgets = &mut visuals.widgets;
+        let text_inactive = c.text.lerp_to_gamma(c.text_strong, 0.5);
+
+        for (widget, bg_fill, bg_stroke, fg) in [
+            (&mut widgets.noninteractive, c.surface, c.border, c.text),
+            (
+                &mut widgets.inactive,
+                c.control,
+                Color32::TRANSPARENT,
+                text_inactive,
+            ),
+            (
+                &mut widgets.hovered,
+                c.control_hovered,
+                c.text,
+                c.text_strong,
+            ),
+            (
+                &mut widgets.active,
+                c.control_active,
+                c.text_strong,
+                c.text_strong,
+            ),
+            (&mut widgets.open, c.surface, c.border, c.text_strong),
+        ] {
+            widget.bg_fill = bg_fill;
+            widget.weak_bg_fill = bg_fill;
+            widget.bg_stroke.color = bg_stroke;
+            widget.fg_stroke.color = fg;
+            widget.corner_radius = CornerRadius::same(radius.small);
+        }
+        widgets.open.weak_bg_fill = c.control;
+    }
+
+    /// The default dark or light [`Style`], with these tokens applied.
+    pub fn to_style(self) -> Style {
+        let mut style = Style {
+            visuals: if self.dark_mode {
+                Visuals::dark()
+            } else {
+                Visuals::light()
+            },
+            ..Default::default()
+        };
+        self.apply(&mut style);
+        style
+    }
+}
+
+impl Visuals {
+    /// The color used for the given role.
+    ///
+    /// Prefer this over picking [`Visuals`] fields directly in custom widgets,
+    /// so that they follow any [`ThemeTokens`].
+    pub fn color(&self, role: ColorRole) -> Color32 {
+        match role {
+            ColorRole::Background => self.panel_fill,
+            ColorRole::Surface => self.window_fill,
+            ColorRole::SurfaceSunken => self.extreme_bg_color,
+            ColorRole::Control => self.widgets.inactive.bg_fill,
+            ColorRole::ControlHovered => self.widgets.hovered.bg_fill,
+            ColorRole::ControlActive => self.widgets.active.bg_fill,
+            ColorRole::Text => self.text_color(),
+            ColorRole::TextWeak => self.weak_text_color(),
+            ColorRole::TextStrong => self.strong_text_color(),
+            ColorRole::Border => self.widgets.noninteractive.bg_stroke.color,
+            ColorRole::Accent => self.selection.bg_fill,
+            ColorRole::OnAccent => self.selection.stroke.color,
+            ColorRole::Hyperlink => self.hyperlink_color,
+            ColorRole::Warning => self.warn_fg_color,
+            ColorRole::Error => self.error_fg_color,
+            ColorRole::CodeBackground => self.code_bg_color,
+        }
+    }
+}
+
+#[test]
+fn default_tokens_match_default_visuals() {
+    for (tokens, visuals) in [
+        (ThemeTokens::dark(), Visuals::dark()),
+        (ThemeTokens::light(), Visuals::light()),
+    ] {
+        let mut from_tokens = visuals.clone();
+        tokens.apply_to_visuals(&mut from_tokens);
+        for role in ColorRole::ALL {
+            if role == ColorRole::TextWeak {
+                continue; // derived differently
+            }
+            assert_eq!(
+                from_tokens.color(role),
+                visuals.color(role),
+                "{role:?} differs"
+            );
+            assert_eq!(from_tokens.color(role), tokens.color(role), "{role:?}");
+        }
+    }
+}
+
+#[test]
+fn widgets_use_the_color_roles() {
+    let mut tokens = ThemeTokens::dark();
+    tokens.colors.surface_sunken = Color32::from_rgb(1, 2, 3);
+    tokens.colors.accent = Color32::from_rgb(4, 5, 6);
+
+    let ctx = crate::Context::default();
+    let output = ctx.run(Default::default(), |ctx| {
+        crate::CentralPanel::default().show(ctx, |ui| {
+            ui.push_theme(&tokens, |ui| {
+                ui.text_edit_singleline(&mut String::new());
+                ui.add(crate::ProgressBar::new(0.5));
+            });
+        });
+    });
+
+    let fills: Vec<Color32> = output
+        .shapes
+        .iter()
+        .filter_map(|clipped| match &clipped.shape {
+            epaint::Shape::Rect(rect) => Some(rect.fill),
+            _ => None,
+        })
+        .collect();
+    assert!(fills.contains(&tokens.colors.surface_sunken), "{fills:?}");
+    assert!(fills.contains(&tokens.colors.accent), "{fills:?}");
+}
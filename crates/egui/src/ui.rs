@@ -3,7 +3,7 @@
 
 use emath::GuiRounding as _;
 use epaint::mutex::RwLock;
-use std::{any::Any, hash::Hash, sync::Arc};
+use std::{any::Any, borrow::Cow, hash::Hash, sync::Arc};
 
 use crate::close_tag::ClosableTag;
 use crate::containers::menu;
@@ -132,6 +132,7 @@ impl Ui {
             invisible,
             sizing_pass,
             style,
+            layout_direction,
             sense,
         } = ui_builder;
 
@@ -144,9 +145,15 @@ impl Ui {
 
         let max_rect = max_rect.unwrap_or_else(|| ctx.screen_rect());
         let clip_rect = max_rect;
-        let layout = layout.unwrap_or_default();
         let disabled = disabled || invisible;
-        let style = style.unwrap_or_else(|| ctx.style());
+        let mut style = style.unwrap_or_else(|| ctx.style());
+        if let Some(layout_direction) = layout_direction {
+            if style.layout_direction != layout_direction {
+                Arc::make_mut(&mut style).layout_direction = layout_direction;
+            }
+        }
+        let layout =
+            layout.unwrap_or_else(|| Layout::top_down(style.layout_direction.start_align()));
         let sense = sense.unwrap_or(Sense::hover());
 
         let placer = Placer::new(max_rect, layout);
@@ -183,6 +190,7 @@ impl Ui {
                 interact_rect: start_rect,
                 sense,
                 enabled: ui.enabled,
+                parent_id: Id::NULL,
             },
             true,
         );
@@ -258,6 +266,7 @@ impl Ui {
             invisible,
             sizing_pass,
             style,
+            layout_direction,
             sense,
         } = ui_builder;
 
@@ -265,7 +274,6 @@ impl Ui {
 
         let id_salt = id_salt.unwrap_or_else(|| Id::from("child"));
         let max_rect = max_rect.unwrap_or_else(|| self.available_rect_before_wrap());
-        let mut layout = layout.unwrap_or(*self.layout());
         let enabled = self.enabled && !disabled && !invisible;
         if let Some(layer_id) = layer_id {
             painter.set_layer_id(layer_id);
@@ -274,7 +282,17 @@ impl Ui {
             painter.set_invisible();
         }
         let sizing_pass = self.sizing_pass || sizing_pass;
-        let style = style.unwrap_or_else(|| self.style.clone());
+        let mut style = style.unwrap_or_else(|| self.style.clone());
+        let mut inherited_layout = *self.layout();
+        if let Some(layout_direction) = layout_direction {
+            if style.layout_direction != layout_direction {
+                Arc::make_mut(&mut style).layout_direction = layout_direction;
+            }
+            if self.style.layout_direction != layout_direction {
+                inherited_layout = inherited_layout.mirrored();
+            }
+        }
+        let mut layout = layout.unwrap_or(inherited_layout);
         let sense = sense.unwrap_or(Sense::hover());
 
         if sizing_pass {
@@ -331,6 +349,7 @@ impl Ui {
                 interact_rect: start_rect,
                 sense,
                 enabled: child_ui.enabled,
+                parent_id: self.unique_id,
             },
             true,
         );
@@ -396,6 +415,14 @@ impl Ui {
         &self.style
     }
 
+    /// Translate one of egui's built-in strings to the [`Style::locale`] of this [`Ui`].
+    ///
+    /// See [`Context::set_translator`].
+    #[inline]
+    pub fn translate<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        self.ctx().translate(&self.style.locale, text)
+    }
+
     /// Mutably borrow internal [`Style`].
     /// Changes apply to this [`Ui`] and its subsequent children.
     ///
@@ -1038,7 +1065,17 @@ impl Ui {
     where
         IdSource: Hash,
     {
-        self.id.with(&id_salt)
+        let id = self.id.with(&id_salt);
+        // This is called a lot, so only take the write lock for new ids:
+        let is_known = self
+            .ctx()
+            .memory(|mem| mem.persistent_id_owners.get(&id) == Some(&self.unique_id));
+        if !is_known {
+            self.ctx().memory_mut(|mem| {
+                mem.persistent_id_owners.insert(id, self.unique_id);
+            });
+        }
+        id
     }
 
     /// This is the `Id` that will be assigned to the next widget added to this `Ui`.
@@ -1072,11 +1109,51 @@ impl Ui {
                 interact_rect: self.clip_rect().intersect(rect),
                 sense,
                 enabled: self.enabled,
+                parent_id: self.unique_id,
             },
             true,
         )
     }
 
+    /// Check for clicks, drags and/or hover inside a closed path, e.g. a pie slice or a region on a map.
+    ///
+    /// Like [`Self::interact`], but only the area enclosed by `path` can be hovered and clicked,
+    /// not all of its bounding rectangle.
+    /// Self-intersecting paths use the even-odd rule, like [`epaint::PathShape::contains`].
+    ///
+    /// Make sure you paint the widget yourself.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::{epaint::PathShape, Color32, Sense, Stroke};
+    ///
+    /// let center = ui.max_rect().center();
+    /// let slice: Vec<egui::Pos2> = std::iter::once(center)
+    ///     .chain((0..=16).map(|i| center + 50.0 * egui::Vec2::angled(i as f32 * 0.1)))
+    ///     .collect();
+    /// let path = PathShape::convex_polygon(slice, Color32::DARK_GREEN, Stroke::NONE);
+    ///
+    /// let response = ui.interact_with_path(&path, ui.id().with("slice"), Sense::click());
+    /// let fill = if response.hovered() { Color32::GREEN } else { Color32::DARK_GREEN };
+    /// ui.painter().add(PathShape { fill, ..path });
+    /// # });
+    /// ```
+    pub fn interact_with_path(&self, path: &epaint::PathShape, id: Id, sense: Sense) -> Response {
+        let rect = Rect::from_points(&path.points);
+        self.ctx().create_widget_with_hit_path(
+            WidgetRect {
+                id,
+                layer_id: self.layer_id(),
+                rect,
+                interact_rect: self.clip_rect().intersect(rect),
+                sense,
+                enabled: self.enabled,
+                parent_id: self.unique_id,
+            },
+            path.clone(),
+        )
+    }
+
     /// Deprecated: use [`Self::interact`] instead.
     #[deprecated = "The contains_pointer argument is ignored. Use `ui.interact` instead."]
     pub fn interact_with_hovered(
@@ -1140,6 +1217,11 @@ impl Ui {
                 interact_rect: self.clip_rect().intersect(self.min_rect()),
                 sense: self.sense,
                 enabled: self.enabled,
+                parent_id: self
+                    .stack
+                    .parent
+                    .as_ref()
+                    .map_or(Id::NULL, |parent| parent.id),
             },
             false,
         );
@@ -1611,6 +1693,29 @@ impl Ui {
         }
     }
 
+    /// Smoothly scroll any parent [`crate::ScrollArea`] so that the given rectangle becomes visible,
+    /// using the given easing curve and a fixed duration (in seconds).
+    ///
+    /// `easing` maps `[0, 1]` time to `[0, 1]` progress, see [`emath::easing`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// egui::ScrollArea::vertical().show(ui, |ui| {
+    ///     let response = ui.button("Bring me into view");
+    ///     if response.clicked() {
+    ///         ui.scroll_to_rect_animated(response.rect, egui::emath::easing::cubic_out, 0.5);
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    pub fn scroll_to_rect_animated(&self, rect: Rect, easing: fn(f32) -> f32, duration: f32) {
+        self.scroll_to_rect_animation(
+            rect,
+            None,
+            style::ScrollAnimation::duration(duration).with_easing(easing),
+        );
+    }
+
     /// Adjust the scroll position of any parent [`crate::ScrollArea`] so that the cursor (where the next widget goes) becomes visible.
     ///
     /// If `align` is [`Align::TOP`] it means "put the top of the rect at the top of the scroll area", etc.
@@ -2201,8 +2306,9 @@ impl Ui {
         let mut response = self.add(DragValue::new(&mut taus).speed(0.01).suffix("τ"));
 
         if self.style().explanation_tooltips {
-            response =
-                response.on_hover_text("1τ = one turn, 0.5τ = half a turn, etc. 0.25τ = 90°");
+            response = response.on_hover_text(
+                self.translate("1τ = one turn, 0.5τ = half a turn, etc. 0.25τ = 90°"),
+            );
         }
 
         // only touch `*radians` if we actually changed the value
@@ -2379,6 +2485,51 @@ impl Ui {
         self.scope_dyn(UiBuilder::new().id_salt(id_salt), Box::new(add_contents))
     }
 
+    /// Create a child Ui with an [`Id`] from a stable key, for use in loops.
+    ///
+    /// Use a key that belongs to the item, like a database id, rather than its index:
+    /// then state like open collapsing headers stays with the item when items are added, removed or reordered.
+    ///
+    /// Using the same key twice in the same [`Ui`] shows an id clash.
+    /// In debug builds the clash lists the call sites and keys involved, also when two different keys hash to the same [`Id`],
+    /// and [`crate::Context::id_source`] tells you where such an [`Id`] came from.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let files = ["a.txt", "b.txt"];
+    /// for file in files {
+    ///     ui.push_id_stable(file, |ui| {
+    ///         ui.collapsing("Details", |ui| ui.label(file));
+    ///     });
+    /// }
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn push_id_stable<R>(
+        &mut self,
+        key: impl Hash + std::fmt::Debug,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        #[cfg(debug_assertions)]
+        let source = crate::IdSource {
+            parent: self.id,
+            key: format!("{key:?}"),
+            location: std::panic::Location::caller(),
+        };
+
+        let InnerResponse {
+            inner: (inner, id),
+            response,
+        } = self.push_id(key, |ui| (add_contents(ui), ui.id()));
+
+        #[cfg(debug_assertions)]
+        self.ctx().register_id_source(id, source);
+        self.ctx()
+            .check_for_id_clash(id, response.rect, "push_id_stable");
+
+        InnerResponse::new(inner, response)
+    }
+
     /// Push another level onto the [`UiStack`].
     ///
     /// You can use this, for instance, to tag a group of widgets.
@@ -2434,6 +2585,98 @@ impl Ui {
         InnerResponse::new(ret, response)
     }
 
+    /// Measure how much space some contents need, without showing them or allocating any space.
+    ///
+    /// The contents are laid out in an invisible, disabled child `Ui` in sizing-pass mode
+    /// (see [`Self::is_sizing_pass`]), so justified layouts and widgets that fill the available
+    /// space take up as little room as they can.
+    /// This lets you size things to their contents in the same frame,
+    /// without the one-frame lag of remembering sizes from the previous frame.
+    ///
+    /// The contents get different [`Id`]s than they would when shown for real,
+    /// so they won't clash, but they also won't see any state stored under those ids
+    /// (e.g. a [`CollapsingHeader`] will measure as collapsed).
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// // A row of buttons that all have the width of the widest one:
+    /// let labels = ["Ok", "Cancel", "Apply to all"];
+    /// let width = labels
+    ///     .iter()
+    ///     .map(|label| ui.measure(|ui| { ui.button(*label); }).x)
+    ///     .fold(0.0, f32::max);
+    /// ui.horizontal(|ui| {
+    ///     for label in labels {
+    ///         ui.add_sized([width, 0.0], egui::Button::new(label));
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    pub fn measure(&mut self, add_contents: impl FnOnce(&mut Self)) -> Vec2 {
+        let next_auto_id_salt = self.next_auto_id_salt;
+        let mut measure_ui = self.new_child(
+            UiBuilder::new()
+                .id_salt("__measure")
+                .sizing_pass()
+                .invisible(),
+        );
+        self.next_auto_id_salt = next_auto_id_salt; // Measuring should not affect the ids of what comes after
+        add_contents(&mut measure_ui);
+        measure_ui.min_size()
+    }
+
+    /// Show some contents in a scope that is exactly as wide (or tall) as the contents need.
+    ///
+    /// This first [measures](Self::measure) the contents, and then shows them with that much
+    /// space available. That way justified layouts and widgets that fill the available width
+    /// all get the size of the largest item, in the very first frame.
+    ///
+    /// The closure is called twice, so it must not have side effects other than adding widgets.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// // A column of equally wide buttons, as wide as the longest label:
+    /// ui.scope_fit_content(|ui| {
+    ///     ui.with_layout(egui::Layout::top_down_justified(egui::Align::Min), |ui| {
+    ///         ui.button("Open");
+    ///         ui.button("Open recent…");
+    ///         ui.button("Quit");
+    ///     });
+    /// });
+    /// # });
+    /// ```
+    pub fn scope_fit_content<R>(
+        &mut self,
+        mut add_contents: impl FnMut(&mut Self) -> R,
+    ) -> InnerResponse<R> {
+        let size = self.measure(|ui| {
+            add_contents(ui);
+        });
+        self.allocate_ui(size, add_contents)
+    }
+
+    /// Show some contents with a different theme, derived from the given [`crate::ThemeTokens`].
+    ///
+    /// Only this part of the ui is affected.
+    /// To change the theme everywhere, use [`Context::set_theme_tokens`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// ui.push_theme(&egui::ThemeTokens::light(), |ui| {
+    ///     ui.label("Light, even in dark mode");
+    /// });
+    /// # });
+    /// ```
+    pub fn push_theme<R>(
+        &mut self,
+        tokens: &crate::ThemeTokens,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> InnerResponse<R> {
+        let mut style = (**self.style()).clone();
+        tokens.apply(&mut style);
+        self.scope_builder(UiBuilder::new().style(style), add_contents)
+    }
+
     /// Redirect shapes to another paint layer.
     ///
     /// ```
@@ -2629,7 +2872,7 @@ impl Ui {
     }
 
     /// Start a ui with vertical layout.
-    /// Widgets will be left-justified.
+    /// Widgets will be left-justified (right-justified with [`crate::LayoutDirection::RightToLeft`]).
     ///
     /// ```
     /// # egui::__run_test_ui(|ui| {
@@ -2644,7 +2887,7 @@ impl Ui {
     #[inline]
     pub fn vertical<R>(&mut self, add_contents: impl FnOnce(&mut Ui) -> R) -> InnerResponse<R> {
         self.scope_builder(
-            UiBuilder::new().layout(Layout::top_down(Align::Min)),
+            UiBuilder::new().layout(Layout::top_down(self.style.layout_direction.start_align())),
             add_contents,
         )
     }
@@ -2752,6 +2995,51 @@ impl Ui {
         self.placer.set_row_height(height);
     }
 
+    /// Lay out items in a row that wraps, where each item can grow, shrink and align
+    /// according to its [`crate::FlexItem`].
+    ///
+    /// Use [`crate::Flex`] for more options, e.g. a vertical layout.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// ui.flex(|flex| {
+    ///     flex.add(egui::FlexItem::new(), |ui| ui.label("Search:"));
+    ///     flex.add(egui::FlexItem::new().grow(1.0).min_size([100.0, 0.0]), |ui| {
+    ///         ui.add(egui::TextEdit::singleline(&mut String::new()).desired_width(f32::INFINITY))
+    ///     });
+    /// });
+    /// # });
+    /// ```
+    pub fn flex<R>(
+        &mut self,
+        add_contents: impl FnOnce(&mut crate::FlexUi<'_>) -> R,
+    ) -> InnerResponse<R> {
+        crate::Flex::horizontal()
+            .wrap(true)
+            .show(self, add_contents)
+    }
+
+    /// Lay out cells in columns of the given [`crate::GridTrack`] sizes.
+    ///
+    /// See [`crate::GridTemplate`] for more.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::GridTrack;
+    /// ui.grid_template([GridTrack::fr(1.0), GridTrack::fr(1.0)], |grid| {
+    ///     grid.cell(|ui| ui.label("Left half"));
+    ///     grid.cell(|ui| ui.label("Right half"));
+    /// });
+    /// # });
+    /// ```
+    pub fn grid_template<R>(
+        &mut self,
+        columns: impl Into<Vec<crate::GridTrack>>,
+        add_contents: impl FnOnce(&mut crate::GridTemplateUi<'_>) -> R,
+    ) -> InnerResponse<R> {
+        crate::GridTemplate::new(columns).show(self, add_contents)
+    }
+
     /// Temporarily split a [`Ui`] into several columns.
     ///
     /// ```
@@ -2947,11 +3235,13 @@ impl Ui {
         let inner = add_contents(&mut frame.content_ui);
         let response = frame.allocate_space(self);
 
+        DragAndDrop::register_drop_region(&response);
+
         // NOTE: we use `response.contains_pointer` here instead of `hovered`, because
         // `hovered` is always false when another widget is being dragged.
         let style = if is_anything_being_dragged
             && can_accept_what_is_being_dragged
-            && response.contains_pointer()
+            && DragAndDrop::is_hovered_drop_region(&response)
         {
             self.visuals().widgets.active
         } else {
@@ -2977,6 +3267,38 @@ impl Ui {
         (InnerResponse { inner, response }, payload)
     }
 
+    /// Rubber-band selection: lets the user drag out a rectangle or draw a freehand lasso
+    /// to select some of the given `items`.
+    ///
+    /// The `response` should sense drags, typically the background of a canvas.
+    /// `items` are the ids and rectangles of the selectable things, in the same coordinates
+    /// as this [`Ui`] (e.g. the scene coordinates of a [`crate::Scene`]).
+    ///
+    /// Returns `Some` while the user is dragging, with a live preview of the selection,
+    /// and [`crate::LassoSelection::finished`] set on release.
+    /// The lasso is painted with an animated dashed outline.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let items: Vec<(egui::Id, egui::Rect)> = vec![];
+    /// # let mut selected: Vec<egui::Id> = vec![];
+    /// let response = ui.interact_bg(egui::Sense::drag());
+    /// if let Some(lasso) = ui.lasso_select(&response, egui::LassoMode::Freehand, items) {
+    ///     if lasso.finished {
+    ///         selected = lasso.selected;
+    ///     }
+    /// }
+    /// # });
+    /// ```
+    pub fn lasso_select(
+        &self,
+        response: &Response,
+        mode: crate::LassoMode,
+        items: impl IntoIterator<Item = (Id, Rect)>,
+    ) -> Option<crate::LassoSelection> {
+        crate::lasso::lasso_select(self, response, mode, items)
+    }
+
     /// Create a new Scope and transform its contents via a [`emath::TSTransform`].
     /// This only affects visuals, inputs will not be transformed. So this is mostly useful
     /// to create visual effects on interactions, e.g. scaling a button on hover / click.
@@ -3245,3 +3567,60 @@ fn ui_impl_send_sync() {
     fn assert_send_sync<T: Send + Sync>() {}
     assert_send_sync::<Ui>();
 }
+
+#[test]
+fn measure_does_not_allocate() {
+    let ctx = Context::default();
+    let _ = ctx.run(Default::default(), |ctx| {
+        crate::CentralPanel::default().show(ctx, |ui| {
+            let cursor_before = ui.cursor();
+            let next_id_before = ui.next_auto_id();
+            let size = ui.measure(|ui| {
+                ui.label("Some text");
+                ui.with_layout(Layout::top_down_justified(Align::Min), |ui| {
+                    let _ = ui.button("Justified");
+                });
+            });
+            assert_eq!(ui.cursor(), cursor_before);
+            assert_eq!(ui.next_auto_id(), next_id_before);
+            assert!(0.0 < size.x && size.x < ui.available_width() / 2.0);
+
+            let response = ui.scope_fit_content(|ui| {
+                ui.label("Some text");
+            });
+            assert_eq!(
+                response.response.rect.width(),
+                ui.measure(|ui| {
+                    ui.label("Some text");
+                })
+                .x
+            );
+        });
+    });
+}
+
+#[test]
+fn right_to_left_mirrors_layout() {
+    let ctx = Context::default();
+    ctx.style_mut(|style| style.layout_direction = crate::LayoutDirection::RightToLeft);
+    let _ = ctx.run(Default::default(), |ctx| {
+        crate::CentralPanel::default().show(ctx, |ui| {
+            assert_eq!(ui.layout().horizontal_placement(), Align::RIGHT);
+            let label = ui.label("Right");
+            assert_eq!(label.rect.right(), ui.max_rect().right());
+
+            ui.horizontal(|ui| {
+                assert_eq!(ui.layout().main_dir(), crate::Direction::RightToLeft);
+            });
+
+            ui.scope_builder(
+                UiBuilder::new().layout_direction(crate::LayoutDirection::LeftToRight),
+                |ui| {
+                    assert_eq!(ui.layout().horizontal_placement(), Align::LEFT);
+                    let label = ui.label("Left");
+                    assert_eq!(label.rect.left(), ui.max_rect().left());
+                },
+            );
+        });
+    });
+}
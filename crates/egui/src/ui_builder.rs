@@ -3,7 +3,7 @@ use std::{hash::Hash, sync::Arc};
 use crate::close_tag::ClosableTag;
 #[allow(unused_imports)] // Used for doclinks
 use crate::Ui;
-use crate::{Id, LayerId, Layout, Rect, Sense, Style, UiStackInfo};
+use crate::{Id, LayerId, Layout, LayoutDirection, Rect, Sense, Style, UiStackInfo};
 
 /// Build a [`Ui`] as the child of another [`Ui`].
 ///
@@ -22,6 +22,7 @@ pub struct UiBuilder {
     pub invisible: bool,
     pub sizing_pass: bool,
     pub style: Option<Arc<Style>>,
+    pub layout_direction: Option<LayoutDirection>,
     pub sense: Option<Sense>,
 }
 
@@ -124,6 +125,16 @@ impl UiBuilder {
         self
     }
 
+    /// Override [`Style::layout_direction`] for this part of the ui,
+    /// e.g. to show some left-to-right content in an otherwise right-to-left ui.
+    ///
+    /// If the direction differs from the parent, an inherited layout is mirrored.
+    #[inline]
+    pub fn layout_direction(mut self, layout_direction: LayoutDirection) -> Self {
+        self.layout_direction = Some(layout_direction);
+        self
+    }
+
     /// Set if you want sense clicks and/or drags. Default is [`Sense::hover`].
     ///
     /// The sense will be registered below the Senses of any widgets contained in this [`Ui`], so
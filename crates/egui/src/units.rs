@@ -0,0 +1,374 @@
+//! Formatting and parsing of values with units, like lengths, angles, durations and byte counts.
+//!
+//! The units are looked up by name in [`crate::Style::units`],
+//! e.g. by [`crate::DragValue::unit`], [`crate::Slider::unit`] and the plot axes in [`crate::plot`].
+//!
+//! ```
+//! # egui::__run_test_ui(|ui| {
+//! # let mut length_in_meters: f64 = 0.0;
+//! ui.add(egui::DragValue::new(&mut length_in_meters).unit("length"));
+//! # });
+//! ```
+
+use std::{collections::BTreeMap, ops::RangeInclusive, sync::Arc};
+
+/// The separators used when showing and parsing numbers.
+///
+/// The default uses `.` as the decimal separator and no thousands separator.
+///
+/// See [`crate::Style::number_locale`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct NumberLocale {
+    /// Between the integer and the fraction, e.g. `.` in `3.14`.
+    pub decimal_separator: char,
+
+    /// Between groups of three digits, e.g. `,` in `1,000`.
+    pub thousands_separator: Option<char>,
+}
+
+impl Default for NumberLocale {
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+            thousands_separator: None,
+        }
+    }
+}
+
+impl NumberLocale {
+    /// `1,234.5`
+    pub const ENGLISH: Self = Self {
+        decimal_separator: '.',
+        thousands_separator: Some(','),
+    };
+
+    /// `1.234,5`
+    pub const GERMAN: Self = Self {
+        decimal_separator: ',',
+        thousands_separator: Some('.'),
+    };
+
+    /// `1 234,5`, with a narrow no-break space.
+    pub const FRENCH: Self = Self {
+        decimal_separator: ',',
+        thousands_separator: Some('\u{202F}'),
+    };
+
+    /// Use the separators of this locale in numbers formatted the Rust way, like `1234.5`.
+    ///
+    /// Any other text (e.g. a unit) is kept as is.
+    pub fn localize(&self, text: &str) -> String {
+        let mut localized = String::with_capacity(text.len());
+        let mut chars = text.chars().peekable();
+        let mut in_fraction = false;
+        while let Some(c) = chars.next() {
+            if c.is_ascii_digit() && !in_fraction {
+                let mut digits = vec![c];
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    digits.push(digit);
+                }
+                for (i, &digit) in digits.iter().enumerate() {
+                    if let Some(thousands_separator) = self.thousands_separator {
+                        if i > 0 && (digits.len() - i) % 3 == 0 {
+                            localized.push(thousands_separator);
+                        }
+                    }
+                    localized.push(digit);
+                }
+            } else if c == '.' && !in_fraction && chars.peek().is_some_and(char::is_ascii_digit) {
+                localized.push(self.decimal_separator);
+                in_fraction = true;
+            } else {
+                in_fraction &= c.is_ascii_digit();
+                localized.push(c);
+            }
+        }
+        localized
+    }
+
+    /// The opposite of [`Self::localize`]: turn text typed by the user into numbers Rust can parse.
+    pub fn delocalize(&self, text: &str) -> String {
+        text.chars()
+            .filter(|&c| Some(c) != self.thousands_separator)
+            .map(|c| if c == self.decimal_separator { '.' } else { c })
+            .collect()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// How to show and parse the values of some quantity, like a length or a duration.
+///
+/// Register your own in [`crate::Style::units`].
+pub trait Unit: Send + Sync {
+    /// Format the value, given in the base unit (e.g. meters).
+    ///
+    /// The `decimals` are those of the value in the base unit,
+    /// see [`crate::style::NumberFormatter::format`].
+    fn format(&self, value: f64, decimals: RangeInclusive<usize>, locale: &NumberLocale) -> String;
+
+    /// Parse text typed by the user into a value in the base unit.
+    fn parse(&self, text: &str, locale: &NumberLocale) -> Option<f64>;
+}
+
+/// A [`Unit`] made of named scales, like `mm`, `m` and `km` for lengths.
+///
+/// Values are shown in the largest of the display scales that fits them,
+/// and can be typed in any of the scales, including expressions like `1in + 5mm`
+/// (see [`crate::util::expression`]).
+/// A plain number is in the base unit.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScaledUnit {
+    /// Name, size in the base unit, and whether to show values in it.
+    scales: Vec<(String, f64, bool)>,
+}
+
+impl ScaledUnit {
+    /// A unit without any scales; add them with [`Self::display_scale`] and [`Self::scale`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A scale that values are shown and parsed in, e.g. `("km", 1000.0)` for lengths in meters.
+    #[inline]
+    pub fn display_scale(mut self, name: impl Into<String>, size: f64) -> Self {
+        self.scales.push((name.into(), size, true));
+        self
+    }
+
+    /// A scale that values are only parsed in, e.g. `("in", 0.0254)` for lengths in meters.
+    #[inline]
+    pub fn scale(mut self, name: impl Into<String>, size: f64) -> Self {
+        self.scales.push((name.into(), size, false));
+        self
+    }
+
+    /// Lengths in meters, shown in `mm`, `m` or `km`.
+    pub fn length() -> Self {
+        Self::new()
+            .display_scale("mm", 1e-3)
+            .display_scale("m", 1.0)
+            .display_scale("km", 1e3)
+            .scale("nm", 1e-9)
+            .scale("µm", 1e-6)
+            .scale("um", 1e-6)
+            .scale("cm", 1e-2)
+            .scale("in", 0.0254)
+            .scale("\"", 0.0254)
+            .scale("ft", 0.3048)
+            .scale("'", 0.3048)
+            .scale("yd", 0.9144)
+            .scale("mi", 1609.344)
+    }
+
+    /// Angles in radians, shown in degrees.
+    pub fn angle() -> Self {
+        Self::new()
+            .display_scale("°", std::f64::consts::TAU / 360.0)
+            .scale("deg", std::f64::consts::TAU / 360.0)
+            .scale("rad", 1.0)
+            .scale("turn", std::f64::consts::TAU)
+    }
+
+    /// Durations in seconds, shown in `ms`, `s`, `min` or `h`.
+    pub fn time() -> Self {
+        Self::new()
+            .display_scale("ms", 1e-3)
+            .display_scale("s", 1.0)
+            .display_scale("min", 60.0)
+            .display_scale("h", 3600.0)
+            .scale("ns", 1e-9)
+            .scale("µs", 1e-6)
+            .scale("us", 1e-6)
+            .scale("d", 86400.0)
+    }
+
+    /// Byte counts, shown in `B`, `KiB`, `MiB`, `GiB` or `TiB`.
+    ///
+    /// Also parses the decimal `kB`, `MB`, `GB` and `TB`.
+    pub fn bytes() -> Self {
+        Self::new()
+            .display_scale("B", 1.0)
+            .display_scale("KiB", 1024.0)
+            .display_scale("MiB", 1024.0_f64.powi(2))
+            .display_scale("GiB", 1024.0_f64.powi(3))
+            .display_scale("TiB", 1024.0_f64.powi(4))
+            .scale("kB", 1e3)
+            .scale("KB", 1e3)
+            .scale("MB", 1e6)
+            .scale("GB", 1e9)
+            .scale("TB", 1e12)
+    }
+
+    /// The display scale to show the value in.
+    fn display_scale_for(&self, value: f64) -> Option<(&str, f64)> {
+        let display_scales = self
+            .scales
+            .iter()
+            .filter(|(_, _, display)| *display)
+            .map(|(name, size, _)| (name.as_str(), *size));
+        let value = value.abs();
+        let best = if value == 0.0 || !value.is_finite() {
+            // Prefer the base unit:
+            display_scales.clone().find(|(_, size)| *size == 1.0)
+        } else {
+            // The largest scale that fits:
+            display_scales
+                .clone()
+                .filter(|(_, size)| *size <= value)
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        };
+        best.or_else(|| display_scales.min_by(|(_, a), (_, b)| a.total_cmp(b)))
+    }
+}
+
+impl Unit for ScaledUnit {
+    fn format(&self, value: f64, decimals: RangeInclusive<usize>, locale: &NumberLocale) -> String {
+        let Some((name, size)) = self.display_scale_for(value) else {
+            return locale.localize(&emath::format_with_decimals_in_range(value, decimals));
+        };
+
+        // Keep the same precision in the base unit, without adding trailing zeros:
+        let extra_decimals = size.log10().round() as isize;
+        let decimals = decimals
+            .start()
+            .saturating_add_signed(extra_decimals.min(0))
+            ..=decimals.end().saturating_add_signed(extra_decimals);
+
+        let number = emath::format_with_decimals_in_range(value / size, decimals);
+        let separator = if name.starts_with(char::is_alphanumeric) {
+            " "
+        } else {
+            ""
+        };
+        format!("{}{separator}{name}", locale.localize(&number))
+    }
+
+    fn parse(&self, text: &str, locale: &NumberLocale) -> Option<f64> {
+        let text = locale.delocalize(text);
+        let scale = |name: &str| {
+            self.scales
+                .iter()
+                .find(|(scale_name, _, _)| scale_name == name)
+                .map(|(_, size, _)| *size)
+        };
+        crate::util::expression::evaluate(&text, &scale).ok()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Named [`Unit`]s, found in [`crate::Style::units`].
+///
+/// By default contains `"length"`, `"angle"`, `"time"` and `"bytes"`
+/// (see the constructors of [`ScaledUnit`]).
+#[derive(Clone)]
+pub struct UnitRegistry {
+    units: BTreeMap<String, Arc<dyn Unit>>,
+}
+
+impl Default for UnitRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.insert("length", ScaledUnit::length());
+        registry.insert("angle", ScaledUnit::angle());
+        registry.insert("time", ScaledUnit::time());
+        registry.insert("bytes", ScaledUnit::bytes());
+        registry
+    }
+}
+
+impl UnitRegistry {
+    /// A registry without any units.
+    pub fn empty() -> Self {
+        Self {
+            units: Default::default(),
+        }
+    }
+
+    /// Add a unit, replacing any other with the same name.
+    pub fn insert(&mut self, name: impl Into<String>, unit: impl Unit + 'static) {
+        self.units.insert(name.into(), Arc::new(unit));
+    }
+
+    /// The unit with this name, if any.
+    pub fn get(&self, name: &str) -> Option<&Arc<dyn Unit>> {
+        self.units.get(name)
+    }
+
+    /// The names of all the units, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.units.keys().map(|name| name.as_str())
+    }
+}
+
+impl std::fmt::Debug for UnitRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.names()).finish()
+    }
+}
+
+impl PartialEq for UnitRegistry {
+    fn eq(&self, other: &Self) -> bool {
+        self.units.len() == other.units.len()
+            && self
+                .units
+                .iter()
+                .zip(&other.units)
+                .all(|((a_name, a), (b_name, b))| a_name == b_name && Arc::ptr_eq(a, b))
+    }
+}
+
+/// Format the value in the named unit of the style, or as a plain number if there is no such unit.
+pub(crate) fn format_value(
+    style: &crate::Style,
+    unit: Option<&str>,
+    value: f64,
+    decimals: RangeInclusive<usize>,
+) -> String {
+    let locale = &style.number_locale;
+    match unit.and_then(|unit| style.units.get(unit)) {
+        Some(unit) => unit.format(value, decimals, locale),
+        None => locale.localize(&style.number_formatter.format(value, decimals)),
+    }
+}
+
+#[test]
+fn test_units() {
+    assert_eq!(
+        NumberLocale::default().localize("1234567.125"),
+        "1234567.125"
+    );
+    assert_eq!(
+        NumberLocale::ENGLISH.localize("-1234567.125"),
+        "-1,234,567.125"
+    );
+    assert_eq!(NumberLocale::GERMAN.localize("1234.5 m"), "1.234,5 m");
+    assert_eq!(NumberLocale::GERMAN.localize("0.12345"), "0,12345");
+    assert_eq!(NumberLocale::GERMAN.delocalize("1.234,5"), "1234.5");
+
+    let locale = NumberLocale::default();
+    let length = ScaledUnit::length();
+    assert_eq!(length.format(0.0, 0..=0, &locale), "0 m");
+    assert_eq!(length.format(0.25, 0..=0, &locale), "250 mm");
+    assert_eq!(length.format(0.0001, 0..=6, &locale), "0.1 mm");
+    assert_eq!(length.format(2.0, 0..=0, &locale), "2 m");
+    assert_eq!(length.format(-1500.0, 0..=0, &locale), "-1.5 km");
+    assert!((length.parse("1in + 5cm", &locale).unwrap() - 0.0754).abs() < 1e-12);
+    assert_eq!(length.parse("3", &locale), Some(3.0));
+    assert_eq!(length.parse("3 parsecs", &locale), None);
+
+    let angle = ScaledUnit::angle();
+    assert_eq!(angle.format(std::f64::consts::PI, 0..=0, &locale), "180°");
+    assert_eq!(angle.parse("0.5turn", &locale), Some(std::f64::consts::PI));
+
+    let bytes = ScaledUnit::bytes();
+    assert_eq!(bytes.format(1536.0, 0..=1, &locale), "1.5 KiB");
+    assert_eq!(bytes.parse("2 MB", &locale), Some(2e6));
+    assert_eq!(
+        bytes.format(1536.0, 0..=1, &NumberLocale::GERMAN),
+        "1,5 KiB"
+    );
+    assert_eq!(bytes.parse("1,5 KiB", &NumberLocale::GERMAN), Some(1536.0));
+}
@@ -0,0 +1,270 @@
+//! Evaluation of simple arithmetic expressions, like `2*pi/3` or `1in + 5mm`.
+//!
+//! Used by [`crate::DragValue`] when the user is done editing the value as text.
+//!
+//! Supported are:
+//! * numbers, like `3`, `0.5` and `1e-3`
+//! * `+`, `-`, `*`, `/` and `^` (power), with the usual precedence, and parentheses
+//! * implicit multiplication, like `2pi` or `1in`, but not of two numbers, like `2 3`
+//! * the constants `pi` (`π`), `tau` (`τ`) and `e`
+//! * the functions `sqrt`, `abs`, `sin`, `cos`, `tan`, `asin`, `acos`, `atan`, `ln`, `exp`, `floor`, `ceil` and `round`
+//! * any other name (e.g. a unit like `mm` or `°`) that the `units` hook knows the value of
+//!
+//! ```
+//! use egui::util::expression::evaluate;
+//!
+//! let no_units = |_: &str| None;
+//! assert_eq!(evaluate("1 + 2*3", &no_units), Ok(7.0));
+//!
+//! let units = |name: &str| match name {
+//!     "mm" => Some(1.0),
+//!     "in" => Some(25.4),
+//!     _ => None,
+//! };
+//! assert_eq!(evaluate("1in + 5mm", &units), Ok(30.4));
+//! assert!(evaluate("1ft", &units).is_err());
+//! ```
+
+use std::{iter::Peekable, str::CharIndices};
+
+/// Evaluate the expression.
+///
+/// `units` returns the value of a name that isn't a built-in constant, e.g. the size of a unit.
+/// It is asked first, so it can also override the built-in constants.
+///
+/// # Errors
+/// A human readable message if the expression is invalid.
+pub fn evaluate(text: &str, units: &dyn Fn(&str) -> Option<f64>) -> Result<f64, String> {
+    let mut parser = Parser {
+        text,
+        chars: text.char_indices().peekable(),
+        units,
+    };
+    let value = parser.expression()?;
+    parser.skip_whitespace();
+    match parser.chars.peek() {
+        None => Ok(value),
+        Some(&(_, c)) => Err(format!("Unexpected '{c}'")),
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    units: &'a dyn Fn(&str) -> Option<f64>,
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphabetic() || matches!(c, '_' | '°' | '%' | '\'' | '"')
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    /// Skip whitespace, then consume the next char if it is one of `chars`.
+    fn next_if_any(&mut self, chars: &[char]) -> Option<char> {
+        self.skip_whitespace();
+        self.chars
+            .next_if(|(_, c)| chars.contains(c))
+            .map(|(_, c)| c)
+    }
+
+    /// `term (('+' | '-') term)*`
+    fn expression(&mut self) -> Result<f64, String> {
+        let mut value = self.term()?;
+        while let Some(op) = self.next_if_any(&['+', '-', '−']) {
+            let rhs = self.term()?;
+            if op == '+' {
+                value += rhs;
+            } else {
+                value -= rhs;
+            }
+        }
+        Ok(value)
+    }
+
+    /// `unary (('*' | '/')? unary)*`, where a missing operator is an implicit multiplication.
+    ///
+    /// An implicit multiplication must be by a name or a parenthesized expression,
+    /// so that e.g. `2 3` is an error rather than `6`.
+    fn term(&mut self) -> Result<f64, String> {
+        let mut value = self.unary()?;
+        loop {
+            if let Some(op) = self.next_if_any(&['*', '×', '/', '÷']) {
+                let rhs = self.unary()?;
+                if op == '*' || op == '×' {
+                    value *= rhs;
+                } else {
+                    value /= rhs;
+                }
+            } else if self
+                .chars
+                .peek()
+                .is_some_and(|&(_, c)| c == '(' || is_name_char(c))
+            {
+                value *= self.power()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// `('+' | '-') unary | power`
+    fn unary(&mut self) -> Result<f64, String> {
+        match self.next_if_any(&['+', '-', '−']) {
+            Some('+') => self.unary(),
+            Some(_) => Ok(-self.unary()?),
+            None => self.power(),
+        }
+    }
+
+    /// `atom ('^' unary)?`
+    fn power(&mut self) -> Result<f64, String> {
+        let base = self.atom()?;
+        if self.next_if_any(&['^']).is_some() {
+            Ok(base.powf(self.unary()?))
+        } else {
+            Ok(base)
+        }
+    }
+
+    /// A number, a name, a function call, or a parenthesized expression.
+    fn atom(&mut self) -> Result<f64, String> {
+        self.skip_whitespace();
+        let Some(&(start, c)) = self.chars.peek() else {
+            return Err("Unexpected end of expression".to_owned());
+        };
+
+        if c == '(' {
+            self.chars.next();
+            let value = self.expression()?;
+            if self.next_if_any(&[')']).is_none() {
+                return Err("Expected ')'".to_owned());
+            }
+            Ok(value)
+        } else if c == '.' || c.is_ascii_digit() {
+            self.number(start)
+        } else if is_name_char(c) {
+            let mut end = start;
+            while let Some((i, c)) = self.chars.next_if(|&(_, c)| is_name_char(c)) {
+                end = i + c.len_utf8();
+            }
+            let name = &self.text[start..end];
+            if self.next_if_any(&['(']).is_some() {
+                let argument = self.expression()?;
+                if self.next_if_any(&[')']).is_none() {
+                    return Err("Expected ')'".to_owned());
+                }
+                function(name, argument).ok_or_else(|| format!("Unknown function '{name}'"))
+            } else {
+                (self.units)(name)
+                    .or_else(|| constant(name))
+                    .ok_or_else(|| format!("Unknown name '{name}'"))
+            }
+        } else {
+            Err(format!("Unexpected '{c}'"))
+        }
+    }
+
+    fn number(&mut self, start: usize) -> Result<f64, String> {
+        let mut end = start;
+        while let Some((i, c)) = self.chars.next_if(|&(_, c)| c == '.' || c.is_ascii_digit()) {
+            end = i + c.len_utf8();
+        }
+
+        // An exponent, unless the `e` is the constant, like in `2e`:
+        let mut lookahead = self.chars.clone();
+        if lookahead.next_if(|&(_, c)| c == 'e' || c == 'E').is_some() {
+            lookahead.next_if(|&(_, c)| c == '+' || c == '-');
+            if lookahead.peek().is_some_and(|(_, c)| c.is_ascii_digit()) {
+                self.chars = lookahead;
+                while let Some((i, c)) = self.chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                    end = i + c.len_utf8();
+                }
+            }
+        }
+
+        let number = &self.text[start..end];
+        number
+            .parse()
+            .map_err(|_err| format!("Invalid number '{number}'"))
+    }
+}
+
+fn constant(name: &str) -> Option<f64> {
+    match name {
+        "pi" | "π" => Some(std::f64::consts::PI),
+        "tau" | "τ" => Some(std::f64::consts::TAU),
+        "e" => Some(std::f64::consts::E),
+        _ => None,
+    }
+}
+
+fn function(name: &str, x: f64) -> Option<f64> {
+    Some(match name {
+        "sqrt" => x.sqrt(),
+        "abs" => x.abs(),
+        "sin" => x.sin(),
+        "cos" => x.cos(),
+        "tan" => x.tan(),
+        "asin" => x.asin(),
+        "acos" => x.acos(),
+        "atan" => x.atan(),
+        "ln" => x.ln(),
+        "exp" => x.exp(),
+        "floor" => x.floor(),
+        "ceil" => x.ceil(),
+        "round" => x.round(),
+        _ => return None,
+    })
+}
+
+#[test]
+fn test_evaluate() {
+    let no_units = |_: &str| None;
+    let eval = |text| evaluate(text, &no_units);
+
+    assert_eq!(eval("42"), Ok(42.0));
+    assert_eq!(eval(" 1.5e3 "), Ok(1500.0));
+    assert_eq!(eval("2 + 3 * 4"), Ok(14.0));
+    assert_eq!(eval("(2 + 3) * 4"), Ok(20.0));
+    assert_eq!(eval("-2^2"), Ok(-4.0));
+    assert_eq!(eval("2^-1"), Ok(0.5));
+    assert_eq!(eval("2^3^2"), Ok(512.0));
+    assert_eq!(eval("10 - 2 - 3"), Ok(5.0));
+    assert_eq!(eval("−1"), Ok(-1.0));
+    assert_eq!(eval("2*pi/3"), Ok(std::f64::consts::TAU / 3.0));
+    assert_eq!(eval("2pi"), Ok(std::f64::consts::TAU));
+    assert_eq!(eval("2e"), Ok(2.0 * std::f64::consts::E));
+    assert_eq!(eval("2(3 + 1)"), Ok(8.0));
+    assert_eq!(eval("sqrt(16) + abs(-1)"), Ok(5.0));
+
+    assert!(eval("").is_err());
+    assert!(eval("2 +").is_err());
+    assert!(eval("(1").is_err());
+    assert!(eval("1)").is_err());
+    assert!(eval("foo").is_err());
+    assert!(eval("foo(1)").is_err());
+    assert!(eval("1..2").is_err());
+    assert!(
+        eval("2 3").is_err(),
+        "No implicit multiplication of numbers"
+    );
+    assert!(
+        eval("pi 2").is_err(),
+        "No implicit multiplication of numbers"
+    );
+
+    let units = |name: &str| match name {
+        "mm" => Some(1.0),
+        "cm" => Some(10.0),
+        "in" | "\"" => Some(25.4),
+        "°" => Some(std::f64::consts::TAU / 360.0),
+        _ => None,
+    };
+    assert_eq!(evaluate("1in + 5mm", &units), Ok(30.4));
+    assert_eq!(evaluate("2\" - 1cm", &units), Ok(40.8));
+    assert_eq!(evaluate("180°", &units), Ok(std::f64::consts::PI));
+}
@@ -515,6 +515,34 @@ impl IdTypeMap {
         self.map.len()
     }
 
+    /// The [`Id`]s that have values stored, once per stored value.
+    pub fn ids(&self) -> impl Iterator<Item = Id> + '_ {
+        self.map
+            .iter()
+            .map(|(hash, element)| Id::from_hash(hash ^ Element::type_id(element).value()))
+    }
+
+    /// A copy of the values stored for the [`Id`]s that pass the filter.
+    pub fn filter_ids(&self, mut keep: impl FnMut(Id) -> bool) -> Self {
+        let map = self
+            .map
+            .iter()
+            .filter(|(hash, element)| {
+                keep(Id::from_hash(**hash ^ Element::type_id(element).value()))
+            })
+            .map(|(hash, element)| (*hash, element.clone()))
+            .collect();
+        Self {
+            map,
+            max_bytes_per_type: self.max_bytes_per_type,
+        }
+    }
+
+    /// Move all values of `other` into `self`, replacing values of the same [`Id`] and type.
+    pub fn extend(&mut self, other: Self) {
+        self.map.extend(other.map);
+    }
+
     /// Count how many values are stored but not yet deserialized.
     #[inline]
     pub fn count_serialized(&self) -> usize {
@@ -69,7 +69,7 @@
 
 use std::sync::Arc;
 
-use epaint::{Pos2, Vec2};
+use epaint::{Pos2, Rect, Vec2};
 
 use crate::{Context, Id};
 
@@ -198,6 +198,86 @@ impl From<IconData> for epaint::ColorImage {
     }
 }
 
+// ----------------------------------------------------------------------------
+
+/// A monitor (screen) connected to the computer, see [`crate::ViewportInfo::monitors`].
+///
+/// All rectangles are in the same space as [`crate::ViewportInfo::outer_rect`],
+/// i.e. desktop coordinates in ui points.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct MonitorInfo {
+    /// Name of the monitor, if known.
+    pub name: Option<String>,
+
+    /// The whole monitor.
+    pub rect: Rect,
+
+    /// The part of the monitor not covered by task bars, docks and the like.
+    ///
+    /// Same as [`Self::rect`] if the integration doesn't know.
+    pub work_rect: Rect,
+
+    /// The OS native pixels-per-point of this monitor.
+    pub scale_factor: f32,
+
+    /// Is this the primary monitor?
+    pub is_primary: bool,
+}
+
+/// Where a window is relative to the monitor it is on,
+/// for restoring it to the same monitor later, even if the desktop layout changed.
+///
+/// ```
+/// # let info = egui::ViewportInfo::default();
+/// // Remember:
+/// let placement = egui::MonitorPlacement::of_viewport(&info);
+///
+/// // …and restore:
+/// let mut builder = egui::ViewportBuilder::default();
+/// if let Some(placement) = &placement {
+///     builder = builder.with_monitor_placement(placement, &info.monitors);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct MonitorPlacement {
+    /// [`MonitorInfo::name`] of the monitor.
+    pub monitor: Option<String>,
+
+    /// The outer position of the window, relative to the top left of the monitor work area.
+    pub offset: Vec2,
+
+    pub inner_size: Vec2,
+}
+
+impl MonitorPlacement {
+    /// Where the viewport is now, if known.
+    pub fn of_viewport(info: &crate::ViewportInfo) -> Option<Self> {
+        let monitor = info.monitors.get(info.monitor?)?;
+        Some(Self {
+            monitor: monitor.name.clone(),
+            offset: info.outer_rect?.min - monitor.work_rect.min,
+            inner_size: info.inner_rect?.size(),
+        })
+    }
+
+    /// The outer position of the window on the same monitor, if it is still connected,
+    /// or else on the primary monitor.
+    ///
+    /// The position is moved so that the window stays within the monitor work area as far as possible.
+    pub fn outer_position(&self, monitors: &[MonitorInfo]) -> Option<Pos2> {
+        let monitor = monitors
+            .iter()
+            .find(|monitor| self.monitor.is_some() && monitor.name == self.monitor)
+            .or_else(|| monitors.iter().find(|monitor| monitor.is_primary))
+            .or_else(|| monitors.first())?;
+        let work_rect = monitor.work_rect;
+        let max = (work_rect.max - self.inner_size).max(work_rect.min);
+        Some((work_rect.min + self.offset).clamp(work_rect.min, max))
+    }
+}
+
 impl From<&IconData> for epaint::ColorImage {
     fn from(icon: &IconData) -> Self {
         profiling::function_scope!();
@@ -307,6 +387,8 @@ pub struct ViewportBuilder {
     pub window_level: Option<WindowLevel>,
 
     pub mouse_passthrough: Option<bool>,
+    pub mouse_passthrough_regions: Option<Vec<Rect>>,
+    pub shape: Option<WindowShape>,
 
     // X11
     pub window_type: Option<X11WindowType>,
@@ -508,6 +590,30 @@ impl ViewportBuilder {
         self
     }
 
+    /// Center the window in the work area of this monitor.
+    ///
+    /// Uses [`Self::inner_size`], so set that first. Window decorations are not taken into account.
+    /// Use [`ViewportCommand::CenterOnMonitor`] to move an existing window.
+    #[inline]
+    pub fn with_centered_on_monitor(self, monitor: &MonitorInfo) -> Self {
+        let size = self.inner_size.unwrap_or_default();
+        self.with_position(monitor.work_rect.center() - 0.5 * size)
+    }
+
+    /// Restore a [`MonitorPlacement`], given the currently connected [`crate::ViewportInfo::monitors`].
+    #[inline]
+    pub fn with_monitor_placement(
+        mut self,
+        placement: &MonitorPlacement,
+        monitors: &[MonitorInfo],
+    ) -> Self {
+        self.inner_size = Some(placement.inner_size);
+        if let Some(position) = placement.outer_position(monitors) {
+            self.position = Some(position);
+        }
+        self
+    }
+
     /// Sets whether clamp the window's size to monitor's size. The default is `true` on linux, otherwise it is `false`.
     ///
     /// Note: On some Linux systems, a window size larger than the monitor causes crashes
@@ -619,6 +725,34 @@ impl ViewportBuilder {
         self
     }
 
+    /// On desktop: mouse clicks inside these regions pass through the window.
+    ///
+    /// The regions are in points, relative to the inner rect of the window.
+    /// An empty list means no pass-through regions.
+    ///
+    /// This requires a platform window shape handler in the integration
+    /// (e.g. `egui_winit::set_window_shape_handler`), since `winit` can only
+    /// toggle pass-through for the whole window.
+    #[inline]
+    pub fn with_mouse_passthrough_regions(mut self, regions: Vec<Rect>) -> Self {
+        self.mouse_passthrough_regions = Some(regions);
+        self
+    }
+
+    /// On desktop: give the window a non-rectangular shape.
+    ///
+    /// Everything outside the shape is neither shown nor receives mouse input.
+    /// An empty [`WindowShape`] means a normal rectangular window.
+    ///
+    /// Generally you would use this in conjunction with [`Self::with_transparent`]
+    /// and [`Self::with_decorations`] set to `false`.
+    /// Like [`Self::with_mouse_passthrough_regions`] this requires a platform window shape handler.
+    #[inline]
+    pub fn with_shape(mut self, shape: WindowShape) -> Self {
+        self.shape = Some(shape);
+        self
+    }
+
     /// ### On X11
     /// This sets the window type.
     /// Maps directly to [`_NET_WM_WINDOW_TYPE`](https://specifications.freedesktop.org/wm-spec/wm-spec-1.5.html).
@@ -659,6 +793,8 @@ impl ViewportBuilder {
             maximize_button: new_maximize_button,
             window_level: new_window_level,
             mouse_passthrough: new_mouse_passthrough,
+            mouse_passthrough_regions: new_mouse_passthrough_regions,
+            shape: new_shape,
             taskbar: new_taskbar,
             window_type: new_window_type,
         } = new_vp_builder;
@@ -761,6 +897,20 @@ impl ViewportBuilder {
             }
         }
 
+        if let Some(new_regions) = new_mouse_passthrough_regions {
+            if Some(&new_regions) != self.mouse_passthrough_regions.as_ref() {
+                self.mouse_passthrough_regions = Some(new_regions.clone());
+                commands.push(ViewportCommand::MousePassthroughRegions(new_regions));
+            }
+        }
+
+        if let Some(new_shape) = new_shape {
+            if Some(&new_shape) != self.shape.as_ref() {
+                self.shape = Some(new_shape.clone());
+                commands.push(ViewportCommand::Shape(new_shape));
+            }
+        }
+
         if let Some(new_window_level) = new_window_level {
             if Some(new_window_level) != self.window_level {
                 self.window_level = Some(new_window_level);
@@ -857,6 +1007,156 @@ impl ViewportBuilder {
     }
 }
 
+/// The shape of a non-rectangular window, see [`ViewportBuilder::with_shape`].
+///
+/// Made up of closed polygons in points, relative to the inner rect of the window.
+/// A point is inside the shape if it is inside an odd number of polygons,
+/// so a polygon inside another one cuts a hole in it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct WindowShape {
+    pub polygons: Vec<Vec<Pos2>>,
+}
+
+impl WindowShape {
+    /// A shape made up of one polygon.
+    pub fn from_polygon(points: Vec<Pos2>) -> Self {
+        Self {
+            polygons: vec![points],
+        }
+    }
+
+    /// A shape following the points of a path, e.g. a [`epaint::PathShape`]
+    /// or a flattened [`epaint::CubicBezierShape`].
+    ///
+    /// The path is always treated as closed.
+    pub fn from_path(path: &epaint::PathShape) -> Self {
+        Self::from_polygon(path.points.clone())
+    }
+
+    /// A rectangular shape, e.g. to cut a hole with [`Self::with_polygon`].
+    pub fn from_rect(rect: Rect) -> Self {
+        Self::from_polygon(vec![
+            rect.left_top(),
+            rect.right_top(),
+            rect.right_bottom(),
+            rect.left_bottom(),
+        ])
+    }
+
+    /// Add another polygon.
+    #[inline]
+    pub fn with_polygon(mut self, points: Vec<Pos2>) -> Self {
+        self.polygons.push(points);
+        self
+    }
+
+    /// Does this shape have any polygons?
+    pub fn is_empty(&self) -> bool {
+        self.polygons.iter().all(|polygon| polygon.len() < 3)
+    }
+
+    /// Is the point inside the shape (using the even-odd rule)?
+    pub fn contains(&self, pos: Pos2) -> bool {
+        let mut inside = false;
+        for polygon in &self.polygons {
+            let Some(mut prev) = polygon.last().copied() else {
+                continue;
+            };
+            for &point in polygon {
+                if (point.y > pos.y) != (prev.y > pos.y) {
+                    let t = (pos.y - point.y) / (prev.y - point.y);
+                    if pos.x < point.x + t * (prev.x - point.x) {
+                        inside = !inside;
+                    }
+                }
+                prev = point;
+            }
+        }
+        inside
+    }
+
+    /// The smallest rectangle containing all polygons.
+    pub fn bounding_rect(&self) -> Rect {
+        Rect::from_points(&self.polygons.concat())
+    }
+
+    /// Cover the shape with rectangles, for platforms that can only shape windows that way (like X11).
+    ///
+    /// Call this on a shape in pixels (see [`Self::scaled`]).
+    /// The rectangles cover the pixels within `bounds` whose centers are inside the shape,
+    /// as a row of rectangles for each run of pixel rows that are the same.
+    pub fn to_rects(&self, bounds: Rect) -> Vec<Rect> {
+        let mut rects: Vec<Rect> = Vec::new();
+        let mut crossings = Vec::new();
+        // The spans of the previous row, and the index of their rectangle:
+        let mut previous_spans: Vec<(f32, f32, usize)> = Vec::new();
+
+        for row in bounds.min.y.round() as i32..bounds.max.y.round() as i32 {
+            let y = row as f32 + 0.5;
+
+            crossings.clear();
+            for polygon in &self.polygons {
+                let Some(mut prev) = polygon.last().copied() else {
+                    continue;
+                };
+                for &point in polygon {
+                    if (point.y > y) != (prev.y > y) {
+                        let t = (y - point.y) / (prev.y - point.y);
+                        crossings.push(point.x + t * (prev.x - point.x));
+                    }
+                    prev = point;
+                }
+            }
+            crossings.sort_by(f32::total_cmp);
+
+            // Between each pair of crossings we are inside, and want the pixels with centers in there:
+            let spans = crossings.chunks_exact(2).filter_map(|pair| {
+                let min = (pair[0] - 0.5).ceil().max(bounds.min.x.round());
+                let max = (pair[1] - 0.5).ceil().min(bounds.max.x.round());
+                (min < max).then_some((min, max))
+            });
+
+            let same_as_previous = spans.clone().count() == previous_spans.len()
+                && spans.clone().zip(&previous_spans).all(
+                    |((min, max), &(prev_min, prev_max, _))| min == prev_min && max == prev_max,
+                );
+            if same_as_previous {
+                for &(_, _, index) in &previous_spans {
+                    rects[index].max.y += 1.0;
+                }
+            } else {
+                previous_spans.clear();
+                for (min, max) in spans {
+                    previous_spans.push((min, max, rects.len()));
+                    rects.push(Rect::from_min_max(
+                        Pos2::new(min, row as f32),
+                        Pos2::new(max, row as f32 + 1.0),
+                    ));
+                }
+            }
+        }
+
+        rects
+    }
+
+    /// Scale all points, e.g. by `pixels_per_point` to go from points to physical pixels.
+    pub fn scaled(&self, factor: f32) -> Self {
+        Self {
+            polygons: self
+                .polygons
+                .iter()
+                .map(|polygon| {
+                    polygon
+                        .iter()
+                        .map(|p| (factor * p.to_vec2()).to_pos2())
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum WindowLevel {
@@ -1003,6 +1303,10 @@ pub enum ViewportCommand {
     /// Set the outer position of the viewport, i.e. moves the window.
     OuterPosition(Pos2),
 
+    /// Center the window in the work area of this monitor,
+    /// an index into [`crate::ViewportInfo::monitors`].
+    CenterOnMonitor(usize),
+
     /// Should be bigger than 0
     InnerSize(Vec2),
 
@@ -1086,11 +1390,23 @@ pub enum ViewportCommand {
     /// Enable mouse pass-through: mouse clicks pass through the window, used for non-interactable overlays.
     MousePassthrough(bool),
 
+    /// Let mouse clicks pass through these regions of the window (in points), see
+    /// [`ViewportBuilder::with_mouse_passthrough_regions`].
+    MousePassthroughRegions(Vec<Rect>),
+
+    /// Set the shape of the window, see [`ViewportBuilder::with_shape`].
+    Shape(WindowShape),
+
     /// Take a screenshot of the next frame after this.
     ///
     /// The results are returned in [`crate::Event::Screenshot`].
     Screenshot(crate::UserData),
 
+    /// Read the pick id rendered at this position (in points) in the next frame.
+    ///
+    /// The result is returned in [`crate::Event::Pick`]. See [`crate::Context::read_pick`].
+    ReadPick(Pos2),
+
     /// Request cut of the current selection
     ///
     /// This is equivalent to the system keyboard shortcut for cut (e.g. CTRL + X).
@@ -1202,3 +1518,93 @@ pub struct ImmediateViewport<'a> {
     /// The user-code that shows the GUI.
     pub viewport_ui_cb: Box<dyn FnMut(&Context) + 'a>,
 }
+
+#[test]
+fn test_monitor_placement_outer_position() {
+    use epaint::{pos2, vec2};
+
+    let monitor = |name: &str, x: f32, is_primary| {
+        let rect = Rect::from_min_size(pos2(x, 0.0), vec2(1000.0, 800.0));
+        MonitorInfo {
+            name: Some(name.to_owned()),
+            rect,
+            work_rect: rect,
+            scale_factor: 1.0,
+            is_primary,
+        }
+    };
+    let placement = MonitorPlacement {
+        monitor: Some("right".to_owned()),
+        offset: vec2(100.0, 50.0),
+        inner_size: vec2(300.0, 200.0),
+    };
+
+    let monitors = [monitor("left", 0.0, true), monitor("right", 1000.0, false)];
+    assert_eq!(
+        placement.outer_position(&monitors),
+        Some(pos2(1100.0, 50.0))
+    );
+
+    // The monitor is gone, so use the primary one:
+    assert_eq!(
+        placement.outer_position(&monitors[..1]),
+        Some(pos2(100.0, 50.0))
+    );
+
+    // Keep the window on the monitor:
+    let far = MonitorPlacement {
+        offset: vec2(950.0, 50.0),
+        ..placement
+    };
+    assert_eq!(far.outer_position(&monitors), Some(pos2(1700.0, 50.0)));
+}
+
+#[test]
+fn test_window_shape_contains() {
+    let outer = Rect::from_min_max(Pos2::ZERO, Pos2::new(100.0, 100.0));
+    let hole = Rect::from_min_max(Pos2::new(40.0, 40.0), Pos2::new(60.0, 60.0));
+    let shape = WindowShape::from_rect(outer)
+        .with_polygon(WindowShape::from_rect(hole).polygons[0].clone());
+
+    assert!(shape.contains(Pos2::new(10.0, 10.0)));
+    assert!(
+        !shape.contains(Pos2::new(50.0, 50.0)),
+        "The inner polygon cuts a hole"
+    );
+    assert!(!shape.contains(Pos2::new(150.0, 50.0)));
+    assert_eq!(shape.bounding_rect(), outer);
+    assert!(WindowShape::default().is_empty());
+}
+
+#[test]
+fn test_window_shape_to_rects() {
+    let outer = Rect::from_min_max(Pos2::ZERO, Pos2::new(10.0, 10.0));
+    let hole = Rect::from_min_max(Pos2::new(3.0, 3.0), Pos2::new(7.0, 7.0));
+    let shape = WindowShape::from_rect(outer)
+        .with_polygon(WindowShape::from_rect(hole).polygons[0].clone());
+
+    let rect = |x0, y0, x1, y1| Rect::from_min_max(Pos2::new(x0, y0), Pos2::new(x1, y1));
+    assert_eq!(
+        shape.to_rects(outer),
+        vec![
+            rect(0.0, 0.0, 10.0, 3.0),
+            rect(0.0, 3.0, 3.0, 7.0),
+            rect(7.0, 3.0, 10.0, 7.0),
+            rect(0.0, 7.0, 10.0, 10.0),
+        ]
+    );
+
+    // Clipped to the bounds:
+    let bounds = rect(0.0, 0.0, 5.0, 2.0);
+    assert_eq!(shape.to_rects(bounds), vec![bounds]);
+
+    // A triangle covers the pixels with their centers inside:
+    let triangle =
+        WindowShape::from_polygon(vec![Pos2::ZERO, Pos2::new(4.0, 0.0), Pos2::new(0.0, 4.0)]);
+    for rect in triangle.to_rects(outer) {
+        assert!(triangle.contains(rect.min + epaint::vec2(0.5, 0.5)));
+        assert!(triangle.contains(Pos2::new(rect.max.x - 0.5, rect.min.y + 0.5)));
+    }
+    let area: f32 = triangle.to_rects(outer).iter().map(|r| r.area()).sum();
+    assert_eq!(area, 3.0 + 2.0 + 1.0);
+}
@@ -0,0 +1,474 @@
+//! A debug tool listing the widgets of the previous pass, see [`Context::widget_inspector_ui`].
+
+use crate::{
+    id::IdSet, CollapsingHeader, Color32, Context, Grid, Id, IdMap, IdSource, Rect, ScrollArea,
+    Stroke, StrokeKind, Ui, WidgetRect, WidgetRects,
+};
+
+/// When [`Id`]s clash, a widget can end up its own ancestor,
+/// so walks through the tree stop when they go around in circles, or this deep.
+const MAX_DEPTH: usize = 100;
+
+/// The same [`Id`] was used by two widgets at different places during one pass.
+///
+/// See [`crate::Context::check_for_id_clash`].
+#[derive(Clone, Debug)]
+pub struct IdClash {
+    pub id: Id,
+
+    /// What kind of widget it was, e.g. "widget" or "window".
+    pub what: String,
+
+    /// Where the [`Id`] was used first.
+    pub first_rect: Rect,
+
+    /// Where the [`Id`] was used again.
+    pub second_rect: Rect,
+
+    /// Every call site that made the [`Id`], if it was made with [`crate::Ui::push_id_stable`].
+    ///
+    /// Always empty in release builds.
+    pub sources: Vec<IdSource>,
+}
+
+impl IdClash {
+    /// Explain where the clashing [`Id`]s came from, if we know.
+    pub fn sources_report(&self) -> Option<String> {
+        let first = self.sources.first()?;
+        let same_key = self
+            .sources
+            .iter()
+            .all(|source| source.parent == first.parent && source.key == first.key);
+        let mut report = if same_key {
+            format!(
+                "push_id_stable was given the key {} more than once:",
+                first.key
+            )
+        } else {
+            "Hash collision: different keys gave the same Id:".to_owned()
+        };
+        for source in &self.sources {
+            report += &format!("\n  {source}");
+        }
+        Some(report)
+    }
+}
+
+#[derive(Clone, Default)]
+struct InspectorState {
+    selected: Option<Id>,
+    filter: String,
+}
+
+/// The widgets of a pass, arranged by their [`WidgetRect::parent_id`].
+struct WidgetTree<'a> {
+    widgets: &'a WidgetRects,
+    children: IdMap<Vec<WidgetRect>>,
+    roots: Vec<WidgetRect>,
+}
+
+impl<'a> WidgetTree<'a> {
+    fn new(widgets: &'a WidgetRects, skip: Id) -> Self {
+        let mut children: IdMap<Vec<WidgetRect>> = IdMap::default();
+        let mut roots = vec![];
+        for (_, layer_widgets) in widgets.layers() {
+            for widget in layer_widgets {
+                if widget.id == skip {
+                    continue;
+                }
+                if widget.parent_id != Id::NULL
+                    && widgets.contains(widget.parent_id)
+                    && !is_in_a_cycle(widgets, widget)
+                {
+                    children.entry(widget.parent_id).or_default().push(*widget);
+                } else {
+                    roots.push(*widget);
+                }
+            }
+        }
+        Self {
+            widgets,
+            children,
+            roots,
+        }
+    }
+
+    /// The ancestors of the widget, closest first.
+    fn ancestors(&self, widget: &WidgetRect) -> Vec<&'a WidgetRect> {
+        let mut seen = IdSet::default();
+        seen.insert(widget.id);
+        let mut ancestors = vec![];
+        let mut parent = self.widgets.get(widget.parent_id);
+        while let Some(widget) = parent {
+            if !seen.insert(widget.id) || ancestors.len() == MAX_DEPTH {
+                break;
+            }
+            ancestors.push(widget);
+            parent = self.widgets.get(widget.parent_id);
+        }
+        ancestors
+    }
+
+    fn name(&self, widget: &WidgetRect) -> String {
+        let id = widget.id.short_debug_format();
+        match self.widgets.info(widget.id) {
+            Some(info) => match &info.label {
+                Some(label) => format!("{:?} {id} {label:?}", info.typ),
+                None => format!("{:?} {id}", info.typ),
+            },
+            None => format!("Widget {id}"),
+        }
+    }
+}
+
+/// Is the widget its own ancestor (or too deep down to tell)?
+fn is_in_a_cycle(widgets: &WidgetRects, widget: &WidgetRect) -> bool {
+    let mut seen = IdSet::default();
+    let mut parent = widgets.get(widget.parent_id);
+    while let Some(ancestor) = parent {
+        if ancestor.id == widget.id || MAX_DEPTH < seen.len() {
+            return true;
+        }
+        if !seen.insert(ancestor.id) {
+            return false; // A cycle further up
+        }
+        parent = widgets.get(ancestor.parent_id);
+    }
+    false
+}
+
+/// Show the widget inspector, see [`Context::widget_inspector_ui`].
+pub(crate) fn widget_inspector_ui(ctx: &Context, ui: &mut Ui) {
+    let state_id = Id::new("egui_widget_inspector");
+    let mut state: InspectorState = ctx.data(|d| d.get_temp(state_id).unwrap_or_default());
+
+    let (widgets, id_clashes) =
+        ctx.prev_pass_state(|pass| (pass.widgets.clone(), pass.id_clashes.clone()));
+
+    // Don't list the inspector itself:
+    let tree = WidgetTree::new(&widgets, ui.unique_id());
+    let mut hovered = None;
+
+    ui.horizontal(|ui| {
+        ui.label("Filter:");
+        ui.text_edit_singleline(&mut state.filter);
+    });
+
+    CollapsingHeader::new(format!("🔥 Id clashes ({})", id_clashes.len()))
+        .default_open(!id_clashes.is_empty())
+        .show(ui, |ui| {
+            if id_clashes.is_empty() {
+                ui.label("No Id clashes in the previous pass.");
+            }
+            for clash in &id_clashes {
+                id_clash_ui(ui, &tree, clash, &mut hovered);
+            }
+        });
+
+    CollapsingHeader::new(format!("Widgets ({})", widgets.len()))
+        .default_open(true)
+        .show(ui, |ui| {
+            ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                let filter = state.filter.to_lowercase();
+                if filter.is_empty() {
+                    for widget in &tree.roots {
+                        widget_tree_ui(ui, &tree, widget, 0, &mut state.selected, &mut hovered);
+                    }
+                } else {
+                    for (_, layer_widgets) in widgets.layers() {
+                        for widget in layer_widgets {
+                            let name = tree.name(widget);
+                            if name.to_lowercase().contains(&filter) {
+                                widget_row_ui(ui, name, widget, &mut state.selected, &mut hovered);
+                            }
+                        }
+                    }
+                }
+            });
+        });
+
+    if let Some(widget) = state.selected.and_then(|id| widgets.get(id)) {
+        ui.separator();
+        widget_details_ui(ui, &tree, widget);
+        highlight(ctx, widget, ui.visuals().selection.stroke.color);
+    }
+    if let Some(widget) = hovered {
+        highlight(ctx, &widget, ui.visuals().warn_fg_color);
+    }
+
+    ctx.data_mut(|d| d.insert_temp(state_id, state));
+}
+
+fn widget_tree_ui(
+    ui: &mut Ui,
+    tree: &WidgetTree<'_>,
+    widget: &WidgetRect,
+    depth: usize,
+    selected: &mut Option<Id>,
+    hovered: &mut Option<WidgetRect>,
+) {
+    let name = tree.name(widget);
+    match tree.children.get(&widget.id) {
+        Some(children) if depth < MAX_DEPTH => {
+            let response = CollapsingHeader::new(name)
+                .id_salt(widget.id)
+                .show(ui, |ui| {
+                    for child in children {
+                        widget_tree_ui(ui, tree, child, depth + 1, selected, hovered);
+                    }
+                })
+                .header_response;
+            if response.hovered() {
+                *hovered = Some(*widget);
+            }
+            if response.clicked() {
+                *selected = Some(widget.id);
+            }
+        }
+        _ => widget_row_ui(ui, name, widget, selected, hovered),
+    }
+}
+
+fn widget_row_ui(
+    ui: &mut Ui,
+    name: String,
+    widget: &WidgetRect,
+    selected: &mut Option<Id>,
+    hovered: &mut Option<WidgetRect>,
+) {
+    let response = ui.selectable_label(*selected == Some(widget.id), name);
+    if response.hovered() {
+        *hovered = Some(*widget);
+    }
+    if response.clicked() {
+        *selected = Some(widget.id);
+    }
+}
+
+fn widget_details_ui(ui: &mut Ui, tree: &WidgetTree<'_>, widget: &WidgetRect) {
+    let ctx = ui.ctx().clone();
+    let id = widget.id;
+    Grid::new("widget_details").num_columns(2).show(ui, |ui| {
+        ui.label("Id");
+        ui.label(id.short_debug_format());
+        ui.end_row();
+
+        ui.label("Parent");
+        ui.label(match tree.widgets.get(widget.parent_id) {
+            Some(parent) => tree.name(parent),
+            None => "-".to_owned(),
+        });
+        ui.end_row();
+
+        ui.label("Layer");
+        ui.label(widget.layer_id.short_debug_format());
+        ui.end_row();
+
+        ui.label("Rect");
+        ui.label(format!("{:?}", widget.rect));
+        ui.end_row();
+
+        ui.label("Interact rect");
+        ui.label(format!("{:?}", widget.interact_rect));
+        ui.end_row();
+
+        ui.label("Sense");
+        ui.label(format!("{:?}", widget.sense));
+        ui.end_row();
+
+        ui.label("State");
+        let mut states = vec![];
+        if !widget.enabled {
+            states.push("disabled");
+        }
+        if ctx.viewport(|vp| vp.interact_widgets.hovered.contains(&id)) {
+            states.push("hovered");
+        }
+        if ctx.memory(|mem| mem.has_focus(id)) {
+            states.push("focused");
+        }
+        if ctx.is_being_dragged(id) {
+            states.push("dragged");
+        }
+        ui.label(if states.is_empty() {
+            "-".to_owned()
+        } else {
+            states.join(", ")
+        });
+        ui.end_row();
+
+        if let Some(info) = tree.widgets.info(id) {
+            ui.label("Info");
+            ui.label(format!("{info:?}"));
+            ui.end_row();
+        }
+    });
+}
+
+fn id_clash_ui(
+    ui: &mut Ui,
+    tree: &WidgetTree<'_>,
+    clash: &IdClash,
+    hovered: &mut Option<WidgetRect>,
+) {
+    let IdClash {
+        id,
+        what,
+        first_rect,
+        second_rect,
+        sources: _,
+    } = clash;
+
+    ui.label(format!(
+        "{what} {} was used at {first_rect:?} and again at {second_rect:?}",
+        id.short_debug_format()
+    ));
+    if let Some(report) = clash.sources_report() {
+        ui.monospace(report);
+    }
+
+    // The ancestors tell us where the `Id` came from:
+    if let Some(widget) = tree.widgets.get(*id) {
+        let mut path: Vec<String> = std::iter::once(widget)
+            .chain(tree.ancestors(widget))
+            .map(|widget| tree.name(widget))
+            .collect();
+        path.reverse();
+        ui.label(format!("Inside: {}", path.join(" › ")));
+    }
+
+    ui.weak(
+        "Ids are derived from the id_salt and the parent Ui, so two widgets with the same \
+         salt in the same Ui get the same Id. This happens when e.g. Windows or \
+         CollapsingHeaders share names, or when Grids and ScrollAreas aren't given unique \
+         id_salts. Use ui.push_id or a unique id_salt.",
+    );
+
+    ui.horizontal(|ui| {
+        for (text, rect) in [("Locate first", first_rect), ("Locate second", second_rect)] {
+            if ui.button(text).hovered() {
+                *hovered = Some(WidgetRect {
+                    id: *id,
+                    layer_id: tree
+                        .widgets
+                        .get(*id)
+                        .map_or_else(crate::LayerId::background, |w| w.layer_id),
+                    rect: *rect,
+                    interact_rect: *rect,
+                    sense: crate::Sense::hover(),
+                    enabled: true,
+                    parent_id: Id::NULL,
+                });
+            }
+        }
+    });
+    ui.separator();
+}
+
+/// Paint an outline around the widget, on top of everything else.
+fn highlight(ctx: &Context, widget: &WidgetRect, color: Color32) {
+    let rect = match ctx.layer_transform_to_global(widget.layer_id) {
+        Some(transform) => transform * widget.rect,
+        None => widget.rect,
+    };
+    if rect.is_finite() {
+        ctx.debug_painter()
+            .rect_stroke(rect, 0.0, Stroke::new(2.0, color), StrokeKind::Outside);
+    }
+}
+
+#[test]
+fn test_widget_tree_and_id_clashes() {
+    let ctx = Context::default();
+    ctx.options_mut(|o| o.warn_on_id_clash = false);
+
+    let mut parent_id = Id::NULL;
+    let mut button_id = Id::NULL;
+    let _ = ctx.run(Default::default(), |ctx| {
+        crate::CentralPanel::default().show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                parent_id = ui.unique_id();
+                button_id = ui.button("Click me").id;
+            });
+            let clash = Id::new("clash");
+            ui.interact(
+                Rect::from_min_size(crate::pos2(0.0, 0.0), crate::Vec2::splat(10.0)),
+                clash,
+                crate::Sense::click(),
+            );
+            ui.interact(
+                Rect::from_min_size(crate::pos2(50.0, 50.0), crate::Vec2::splat(10.0)),
+                clash,
+                crate::Sense::click(),
+            );
+        });
+    });
+
+    ctx.prev_pass_state(|pass| {
+        let button = pass.widgets.get(button_id).unwrap();
+        assert_eq!(button.parent_id, parent_id);
+        assert!(pass.widgets.contains(parent_id));
+
+        assert_eq!(pass.id_clashes.len(), 1);
+        let clash = &pass.id_clashes[0];
+        assert_eq!(clash.id, Id::new("clash"));
+        assert_eq!(clash.first_rect.min, crate::pos2(0.0, 0.0));
+        assert_eq!(clash.second_rect.min, crate::pos2(50.0, 50.0));
+    });
+
+    for _ in 0..2 {
+        let _ = ctx.run(Default::default(), |ctx| {
+            ctx.show_widget_inspector(&mut true);
+        });
+    }
+}
+
+#[test]
+fn test_widget_tree_with_cycles() {
+    let widget = |id: &str, parent: &str| WidgetRect {
+        id: Id::new(id),
+        layer_id: crate::LayerId::background(),
+        rect: Rect::ZERO,
+        interact_rect: Rect::ZERO,
+        sense: crate::Sense::hover(),
+        enabled: true,
+        parent_id: if parent.is_empty() {
+            Id::NULL
+        } else {
+            Id::new(parent)
+        },
+    };
+
+    // Clashing `Id`s can make any of these:
+    let mut widgets = WidgetRects::default();
+    for (id, parent) in [
+        ("root", ""),
+        ("child", "root"),
+        ("self", "self"),
+        ("a", "b"),
+        ("b", "a"),
+        ("below_a", "a"),
+    ] {
+        widgets.insert(crate::LayerId::background(), widget(id, parent));
+    }
+
+    let tree = WidgetTree::new(&widgets, Id::NULL);
+    let roots: Vec<Id> = tree.roots.iter().map(|w| w.id).collect();
+    assert_eq!(
+        roots,
+        [Id::new("root"), Id::new("self"), Id::new("a"), Id::new("b")]
+    );
+    assert_eq!(tree.children[&Id::new("a")][0].id, Id::new("below_a"));
+
+    let below_a = widgets.get(Id::new("below_a")).unwrap();
+    assert_eq!(tree.ancestors(below_a).len(), 2);
+
+    let ctx = Context::default();
+    let _ = ctx.run(Default::default(), |ctx| {
+        crate::CentralPanel::default().show(ctx, |ui| {
+            for widget in &tree.roots {
+                widget_tree_ui(ui, &tree, widget, 0, &mut None, &mut None);
+            }
+        });
+    });
+}
@@ -1,5 +1,7 @@
 use ahash::HashMap;
 
+use epaint::PathShape;
+
 use crate::{Id, IdMap, LayerId, Rect, Sense, WidgetInfo};
 
 /// Used to store each widget's [Id], [Rect] and [Sense] each frame.
@@ -40,6 +42,12 @@ pub struct WidgetRect {
 
     /// Is the widget enabled?
     pub enabled: bool,
+
+    /// The [`crate::Ui`] this widget was created in,
+    /// or [`Id::NULL`] for top-level widgets or if not known.
+    ///
+    /// Used to show the widget tree in [`crate::Context::widget_inspector_ui`].
+    pub parent_id: Id,
 }
 
 impl WidgetRect {
@@ -51,6 +59,7 @@ impl WidgetRect {
             interact_rect,
             sense,
             enabled,
+            parent_id,
         } = self;
         Self {
             id,
@@ -59,6 +68,7 @@ impl WidgetRect {
             interact_rect: transform * interact_rect,
             sense,
             enabled,
+            parent_id,
         }
     }
 }
@@ -80,6 +90,12 @@ pub struct WidgetRects {
     /// Only filled in if the widget is interacted with,
     /// or if this is a debug build.
     infos: IdMap<WidgetInfo>,
+
+    /// The exact outline of some widgets, in local layer coordinates.
+    ///
+    /// These widgets can only be hit inside their outline,
+    /// not everywhere in their [`WidgetRect::interact_rect`].
+    hit_paths: IdMap<PathShape>,
 }
 
 impl PartialEq for WidgetRects {
@@ -115,6 +131,17 @@ impl WidgetRects {
         self.by_id.contains_key(&id)
     }
 
+    /// The number of widgets, across all layers.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.by_id.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.by_id.is_empty()
+    }
+
     /// All widgets in this layer, sorted back-to-front.
     #[inline]
     pub fn get_layer(&self, layer_id: LayerId) -> impl Iterator<Item = &WidgetRect> + '_ {
@@ -127,6 +154,7 @@ impl WidgetRects {
             by_layer,
             by_id,
             infos,
+            hit_paths,
         } = self;
 
         for rects in by_layer.values_mut() {
@@ -136,6 +164,19 @@ impl WidgetRects {
         by_id.clear();
 
         infos.clear();
+
+        hit_paths.clear();
+    }
+
+    /// Forget all widgets in the given layer.
+    pub(crate) fn remove_layer(&mut self, layer_id: LayerId) {
+        if let Some(rects) = self.by_layer.remove(&layer_id) {
+            for rect in rects {
+                self.by_id.remove(&rect.id);
+                self.infos.remove(&rect.id);
+                self.hit_paths.remove(&rect.id);
+            }
+        }
     }
 
     /// Insert the given widget rect in the given layer.
@@ -144,6 +185,7 @@ impl WidgetRects {
             by_layer,
             by_id,
             infos: _,
+            hit_paths: _,
         } = self;
 
         let layer_widgets = by_layer.entry(layer_id).or_default();
@@ -173,6 +215,9 @@ impl WidgetRects {
                 existing.interact_rect = widget_rect.interact_rect; // last wins
                 existing.sense |= widget_rect.sense;
                 existing.enabled |= widget_rect.enabled;
+                if existing.parent_id == Id::NULL {
+                    existing.parent_id = widget_rect.parent_id;
+                }
 
                 if existing.layer_id == widget_rect.layer_id {
                     layer_widgets[*idx_in_layer] = *existing;
@@ -188,4 +233,16 @@ impl WidgetRects {
     pub fn info(&self, id: Id) -> Option<&WidgetInfo> {
         self.infos.get(&id)
     }
+
+    /// Only let the widget be hit inside the given path, in local layer coordinates.
+    ///
+    /// The path is treated as closed. See [`PathShape::contains`].
+    pub fn set_hit_path(&mut self, id: Id, path: PathShape) {
+        self.hit_paths.insert(id, path);
+    }
+
+    /// The outline set with [`Self::set_hit_path`], if any.
+    pub fn hit_path(&self, id: Id) -> Option<&PathShape> {
+        self.hit_paths.get(&id)
+    }
 }
@@ -3,8 +3,9 @@ use std::{borrow::Cow, sync::Arc};
 use emath::GuiRounding as _;
 
 use crate::{
-    text::{LayoutJob, TextWrapping},
-    Align, Color32, FontFamily, FontSelection, Galley, Style, TextStyle, TextWrapMode, Ui, Visuals,
+    text::{LayoutJob, TextWrapping, UnderlineStyle},
+    Align, Color32, ColorRole, FontFamily, FontSelection, Galley, Style, TextStyle, TextWrapMode,
+    Ui, Visuals,
 };
 
 /// Text and optional style choices for it.
@@ -38,6 +39,7 @@ pub struct RichText {
     weak: bool,
     strikethrough: bool,
     underline: bool,
+    underline_style: UnderlineStyle,
     italics: bool,
     raised: bool,
 }
@@ -59,6 +61,7 @@ impl Default for RichText {
             weak: Default::default(),
             strikethrough: Default::default(),
             underline: Default::default(),
+            underline_style: Default::default(),
             italics: Default::default(),
             raised: Default::default(),
         }
@@ -251,6 +254,16 @@ impl RichText {
         self
     }
 
+    /// Draw a line under the text, in this style.
+    ///
+    /// For instance [`UnderlineStyle::Wavy`] to mark a misspelled word.
+    #[inline]
+    pub fn underline_style(mut self, underline_style: UnderlineStyle) -> Self {
+        self.underline = true;
+        self.underline_style = underline_style;
+        self
+    }
+
     /// Draw a line through the text, crossing it out.
     ///
     /// If you want to control the strikethrough line color, use [`LayoutJob`] instead.
@@ -395,6 +408,7 @@ impl RichText {
             weak: _,   // already used by `get_text_color`
             strikethrough,
             underline,
+            underline_style,
             italics,
             raised,
         } = self;
@@ -423,7 +437,7 @@ impl RichText {
 
         let mut background_color = background_color;
         if code {
-            background_color = style.visuals.code_bg_color;
+            background_color = style.visuals.color(ColorRole::CodeBackground);
         }
         let underline = if underline {
             crate::Stroke::new(1.0, line_color)
@@ -452,7 +466,10 @@ impl RichText {
                 background: background_color,
                 italics,
                 underline,
+                underline_style,
                 strikethrough,
+                underline_override: Default::default(),
+                strikethrough_override: Default::default(),
                 valign,
                 expand_bg,
             },
@@ -463,9 +480,9 @@ impl RichText {
         if let Some(text_color) = self.text_color {
             Some(text_color)
         } else if self.strong {
-            Some(visuals.strong_text_color())
+            Some(visuals.color(ColorRole::TextStrong))
         } else if self.weak {
-            Some(visuals.weak_text_color())
+            Some(visuals.color(ColorRole::TextWeak))
         } else {
             visuals.override_text_color
         }
@@ -0,0 +1,155 @@
+use crate::{Button, InnerResponse, TextStyle, TextWrapMode, Ui, WidgetText};
+
+/// A path of clickable crumbs, like `Home › Documents › Report.txt`.
+///
+/// If the crumbs don't fit in the available width, the ones in the middle
+/// are collapsed into a `…` menu. The first and the last crumb are always shown.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut path = vec!["Home", "Documents", "Report.txt"];
+/// if let Some(clicked) = egui::Breadcrumbs::new().show(ui, path.iter().copied()).inner {
+///     path.truncate(clicked + 1);
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct Breadcrumbs {
+    separator: WidgetText,
+}
+
+impl Default for Breadcrumbs {
+    fn default() -> Self {
+        Self {
+            separator: "›".into(),
+        }
+    }
+}
+
+impl Breadcrumbs {
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// What to show between the crumbs.
+    ///
+    /// Default: `›`.
+    #[inline]
+    pub fn separator(mut self, separator: impl Into<WidgetText>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Show the crumbs from left to right, the last one in strong text.
+    ///
+    /// Returns the index of the clicked crumb, if any.
+    pub fn show<T: Into<WidgetText>>(
+        self,
+        ui: &mut Ui,
+        crumbs: impl IntoIterator<Item = T>,
+    ) -> InnerResponse<Option<usize>> {
+        let Self { separator } = self;
+
+        let mut crumbs: Vec<WidgetText> = crumbs.into_iter().map(Into::into).collect();
+        if let Some(last) = crumbs.pop() {
+            crumbs.push(last.strong());
+        }
+
+        let layout_text = |ui: &Ui, text: WidgetText| {
+            text.into_galley(
+                ui,
+                Some(TextWrapMode::Extend),
+                f32::INFINITY,
+                TextStyle::Button,
+            )
+        };
+        let button_padding = 2.0 * ui.spacing().button_padding.x;
+        let item_spacing = ui.spacing().item_spacing.x;
+
+        let galleys: Vec<_> = crumbs
+            .into_iter()
+            .map(|crumb| layout_text(ui, crumb))
+            .collect();
+        let widths: Vec<f32> = galleys
+            .iter()
+            .map(|galley| galley.size().x + button_padding)
+            .collect();
+        let separator = layout_text(ui, separator.weak());
+        let separator_width = separator.size().x + 2.0 * item_spacing;
+        let overflow_width = layout_text(ui, "…".into()).size().x + button_padding;
+
+        let hidden = num_hidden(
+            &widths,
+            separator_width,
+            overflow_width,
+            ui.available_width(),
+        );
+        let hidden_range = 1..1 + hidden;
+
+        ui.horizontal(|ui| {
+            let mut clicked = None;
+            for (index, galley) in galleys.iter().enumerate() {
+                if hidden_range.contains(&index) {
+                    if index == hidden_range.start {
+                        ui.label(separator.clone());
+                        ui.menu_button("…", |ui| {
+                            for index in hidden_range.clone() {
+                                if ui.button(galleys[index].clone()).clicked() {
+                                    clicked = Some(index);
+                                }
+                            }
+                        });
+                    }
+                    continue;
+                }
+
+                if 0 < index {
+                    ui.label(separator.clone());
+                }
+                if ui.add(Button::new(galley.clone()).frame(false)).clicked() {
+                    clicked = Some(index);
+                }
+            }
+            clicked
+        })
+    }
+}
+
+/// How many crumbs after the first one to collapse into the overflow menu,
+/// given the widths of the crumbs and the separators between them.
+fn num_hidden(
+    widths: &[f32],
+    separator_width: f32,
+    overflow_width: f32,
+    available_width: f32,
+) -> usize {
+    let max_hidden = widths.len().saturating_sub(2);
+    let width_with_hidden = |hidden: usize| {
+        let visible_width: f32 = widths
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !(1..1 + hidden).contains(index))
+            .map(|(_, width)| width)
+            .sum();
+        let num_items = widths.len() - hidden + usize::from(0 < hidden);
+        let overflow_width = if 0 < hidden { overflow_width } else { 0.0 };
+        visible_width + overflow_width + num_items.saturating_sub(1) as f32 * separator_width
+    };
+    (0..=max_hidden)
+        .find(|&hidden| width_with_hidden(hidden) <= available_width)
+        .unwrap_or(max_hidden)
+}
+
+#[test]
+fn test_num_hidden() {
+    let widths = [50.0; 5];
+    assert_eq!(num_hidden(&widths, 10.0, 20.0, 1000.0), 0);
+    assert_eq!(num_hidden(&widths, 10.0, 20.0, 290.0), 0);
+    assert_eq!(num_hidden(&widths, 10.0, 20.0, 289.0), 1);
+    assert_eq!(num_hidden(&widths, 10.0, 20.0, 259.0), 2);
+    assert_eq!(num_hidden(&widths, 10.0, 20.0, 200.0), 2);
+    assert_eq!(num_hidden(&widths, 10.0, 20.0, 199.0), 3);
+    assert_eq!(num_hidden(&widths, 10.0, 20.0, 0.0), 3);
+    assert_eq!(num_hidden(&widths[..2], 10.0, 20.0, 0.0), 0);
+}
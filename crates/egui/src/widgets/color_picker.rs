@@ -1,13 +1,16 @@
 //! Color picker widgets.
 
+use std::sync::Arc;
+
 use crate::util::fixed_cache::FixedCache;
 use crate::{
-    epaint, lerp, remap_clamp, Area, Context, DragValue, Frame, Id, Key, Order, Painter, Response,
-    Sense, Ui, UiKind, Widget, WidgetInfo, WidgetType,
+    epaint, lerp, remap_clamp, Area, Context, DragValue, Frame, Id, Key, NumExt as _, Order,
+    Painter, Response, Sense, TextureHandle, TextureOptions, Ui, UiKind, Widget, WidgetInfo,
+    WidgetType,
 };
 use epaint::{
     ecolor::{Color32, Hsva, HsvaGamma, Rgba},
-    pos2, vec2, Mesh, Rect, Shape, Stroke, StrokeKind, Vec2,
+    pos2, vec2, ColorImage, Mesh, Rect, RectShape, Shape, Stroke, StrokeKind, Vec2,
 };
 
 fn contrast_color(color: impl Into<Rgba>) -> Color32 {
@@ -29,27 +32,37 @@ fn background_checkers(painter: &Painter, rect: Rect) {
         return;
     }
 
-    let dark_color = Color32::from_gray(32);
-    let bright_color = Color32::from_gray(128);
-
-    let checker_size = Vec2::splat(rect.height() / 2.0);
-    let n = (rect.width() / checker_size.x).round() as u32;
+    // Two rows of checkers for thin rectangles, and a grid of small checkers for larger ones:
+    let checker_size = (rect.height() / 2.0).at_most(8.0);
+    let columns = (rect.width() / checker_size).round().at_least(1.0);
+    let rows = (rect.height() / checker_size).round().at_least(1.0);
 
-    let mut mesh = Mesh::default();
-    mesh.add_colored_rect(rect, dark_color);
+    // Each texel of the repeated texture is one checker:
+    let texture = checkers_texture(painter.ctx());
+    let uv = Rect::from_min_size(pos2(0.0, 0.0), vec2(columns, rows) / 2.0);
+    painter.add(RectShape::filled(rect, 0.0, Color32::WHITE).with_texture(texture.id(), uv));
+}
 
-    let mut top = true;
-    for i in 0..n {
-        let x = lerp(rect.left()..=rect.right(), i as f32 / (n as f32));
-        let small_rect = if top {
-            Rect::from_min_size(pos2(x, rect.top()), checker_size)
-        } else {
-            Rect::from_min_size(pos2(x, rect.center().y), checker_size)
-        };
-        mesh.add_colored_rect(small_rect, bright_color);
-        top = !top;
+/// A 2x2 checkerboard, repeated to fill the background of transparent colors.
+fn checkers_texture(ctx: &Context) -> TextureHandle {
+    let id = Id::new("color_picker_checkers");
+    if let Some(texture) = ctx.data(|d| d.get_temp::<TextureHandle>(id)) {
+        return texture;
     }
-    painter.add(Shape::mesh(mesh));
+
+    let dark_color = Color32::from_gray(32);
+    let bright_color = Color32::from_gray(128);
+    let image = ColorImage {
+        size: [2, 2],
+        pixels: vec![bright_color, dark_color, dark_color, bright_color],
+    };
+    let texture = ctx.load_texture(
+        "color_picker_checkers",
+        image,
+        TextureOptions::NEAREST_REPEAT,
+    );
+    ctx.data_mut(|d| d.insert_temp(id, texture.clone()));
+    texture
 }
 
 /// Show a color with background checkers to demonstrate transparency (if any).
@@ -313,15 +326,15 @@ fn color_picker_hsvag_2d(ui: &mut Ui, hsvag: &mut HsvaGamma, alpha: Alpha) {
     }
 
     let current_color_size = vec2(ui.spacing().slider_width, ui.spacing().interact_size.y);
-    show_color(ui, *hsvag, current_color_size).on_hover_text("Selected color");
+    show_color(ui, *hsvag, current_color_size).on_hover_text(ui.translate("Selected color"));
 
     if alpha == Alpha::BlendOrAdditive {
         let a = &mut hsvag.a;
         let mut additive = is_additive_alpha(*a);
         ui.horizontal(|ui| {
-            ui.label("Blending:");
-            ui.radio_value(&mut additive, false, "Normal");
-            ui.radio_value(&mut additive, true, "Additive");
+            ui.label(ui.translate("Blending:"));
+            ui.radio_value(&mut additive, false, ui.translate("Normal"));
+            ui.radio_value(&mut additive, true, ui.translate("Additive"));
 
             if additive {
                 *a = -a.abs();
@@ -338,11 +351,13 @@ fn color_picker_hsvag_2d(ui: &mut Ui, hsvag: &mut HsvaGamma, alpha: Alpha) {
     let HsvaGamma { h, s, v, a: _ } = hsvag;
 
     if false {
-        color_slider_1d(ui, s, |s| HsvaGamma { s, ..opaque }.into()).on_hover_text("Saturation");
+        color_slider_1d(ui, s, |s| HsvaGamma { s, ..opaque }.into())
+            .on_hover_text(ui.translate("Saturation"));
     }
 
     if false {
-        color_slider_1d(ui, v, |v| HsvaGamma { v, ..opaque }.into()).on_hover_text("Value");
+        color_slider_1d(ui, v, |v| HsvaGamma { v, ..opaque }.into())
+            .on_hover_text(ui.translate("Value"));
     }
 
     color_slider_2d(ui, s, v, |s, v| HsvaGamma { s, v, ..opaque }.into());
@@ -356,7 +371,7 @@ fn color_picker_hsvag_2d(ui: &mut Ui, hsvag: &mut HsvaGamma, alpha: Alpha) {
         }
         .into()
     })
-    .on_hover_text("Hue");
+    .on_hover_text(ui.translate("Hue"));
 
     let additive = is_additive_alpha(hsvag.a);
 
@@ -369,13 +384,131 @@ fn color_picker_hsvag_2d(ui: &mut Ui, hsvag: &mut HsvaGamma, alpha: Alpha) {
             if is_additive_alpha(*a) {
                 *a = 0.5; // was additive, but isn't allowed to be
             }
-            color_slider_1d(ui, a, |a| HsvaGamma { a, ..opaque }.into()).on_hover_text("Alpha");
+            color_slider_1d(ui, a, |a| HsvaGamma { a, ..opaque }.into())
+                .on_hover_text(ui.translate("Alpha"));
         } else if !additive {
-            color_slider_1d(ui, a, |a| HsvaGamma { a, ..opaque }.into()).on_hover_text("Alpha");
+            color_slider_1d(ui, a, |a| HsvaGamma { a, ..opaque }.into())
+                .on_hover_text(ui.translate("Alpha"));
+        }
+    }
+
+    if let Some(color) = swatches_ui(ui, (*hsvag).into()) {
+        *hsvag = HsvaGamma::from(Hsva::from(color));
+        if alpha == Alpha::Opaque {
+            hsvag.a = 1.0;
         }
     }
 }
 
+/// A small clickable color.
+fn swatch(ui: &mut Ui, color: Color32) -> Response {
+    let size = Vec2::splat(ui.spacing().interact_size.y);
+    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+    response.widget_info(|| WidgetInfo::new(WidgetType::ColorButton));
+
+    if ui.is_rect_visible(rect) {
+        show_color_at(ui.painter(), color, rect.shrink(1.0));
+        if response.hovered() {
+            let stroke = ui.visuals().widgets.hovered.fg_stroke;
+            ui.painter()
+                .rect_stroke(rect, 0.0, stroke, StrokeKind::Inside);
+        }
+    }
+
+    response
+}
+
+/// The eyedropper button, the recently used colors and the palettes.
+///
+/// Returns the color the user picked, if any.
+fn swatches_ui(ui: &mut Ui, current_color: Color32) -> Option<Color32> {
+    let ctx = ui.ctx().clone();
+    let eyedropper_id = ui.id().with("eyedropper");
+    let mut picked = None;
+
+    let (eyedropper, picking) = use_eyedropper_state(&ctx, |state| {
+        if state.picking_for == Some(eyedropper_id) {
+            if let Some(color) = state.picked.take() {
+                picked = Some(color);
+                state.picking_for = None;
+            }
+        }
+        (
+            state.eyedropper.clone(),
+            state.picking_for == Some(eyedropper_id),
+        )
+    });
+    let (recent_colors, palettes) = use_picker_memory(&ctx, |memory| {
+        (memory.recent_colors.clone(), memory.palettes.clone())
+    });
+
+    if eyedropper.is_some() || !recent_colors.is_empty() {
+        ui.horizontal_wrapped(|ui| {
+            if let Some(eyedropper) = eyedropper {
+                let response = ui
+                    .selectable_label(picking, "💧")
+                    .on_hover_text(ui.translate("Pick a color from the screen"));
+                if response.clicked() && !picking {
+                    use_eyedropper_state(&ctx, |state| {
+                        state.picking_for = Some(eyedropper_id);
+                        state.picked = None;
+                    });
+                    eyedropper(&ctx);
+                }
+            }
+            for color in recent_colors {
+                if swatch(ui, color)
+                    .on_hover_text(ui.translate("Recently used"))
+                    .clicked()
+                {
+                    picked = Some(color);
+                }
+            }
+        });
+    }
+
+    let mut added = None;
+    let mut removed = None;
+    for (palette_index, palette) in palettes.iter().enumerate() {
+        ui.horizontal_wrapped(|ui| {
+            ui.label(&palette.name);
+            for (color_index, &color) in palette.colors.iter().enumerate() {
+                let response = swatch(ui, color)
+                    .on_hover_text(ui.translate("Click to use, right-click to remove"));
+                if response.clicked() {
+                    picked = Some(color);
+                }
+                if response.secondary_clicked() {
+                    removed = Some((palette_index, color_index));
+                }
+            }
+            if ui
+                .small_button("➕")
+                .on_hover_text(ui.translate("Add the current color to this palette"))
+                .clicked()
+            {
+                added = Some(palette_index);
+            }
+        });
+    }
+    if added.is_some() || removed.is_some() {
+        use_picker_memory(&ctx, |memory| {
+            if let Some(palette) = added.and_then(|i| memory.palettes.get_mut(i)) {
+                palette.colors.push(current_color);
+            }
+            if let Some((palette, color)) = removed {
+                if let Some(palette) = memory.palettes.get_mut(palette) {
+                    if color < palette.colors.len() {
+                        palette.colors.remove(color);
+                    }
+                }
+            }
+        });
+    }
+
+    picked
+}
+
 fn input_type_button_ui(ui: &mut Ui) {
     let mut input_type = ui.ctx().style().visuals.numeric_color_space;
     if input_type.toggle_button_ui(ui).changed() {
@@ -397,7 +530,7 @@ fn srgba_edit_ui(ui: &mut Ui, [r, g, b, a]: &mut [u8; 4], alpha: Alpha) -> bool
 
         if ui
             .button("📋")
-            .on_hover_text("Click to copy color values")
+            .on_hover_text(ui.translate("Click to copy color values"))
             .clicked()
         {
             if alpha == Alpha::Opaque {
@@ -438,7 +571,7 @@ fn rgba_edit_ui(ui: &mut Ui, [r, g, b, a]: &mut [f32; 4], alpha: Alpha) -> bool
 
         if ui
             .button("📋")
-            .on_hover_text("Click to copy color values")
+            .on_hover_text(ui.translate("Click to copy color values"))
             .clicked()
         {
             if alpha == Alpha::Opaque {
@@ -493,7 +626,7 @@ pub fn color_edit_button_hsva(ui: &mut Ui, hsva: &mut Hsva, alpha: Alpha) -> Res
     let open = ui.memory(|mem| mem.is_popup_open(popup_id));
     let mut button_response = color_button(ui, (*hsva).into(), open);
     if ui.style().explanation_tooltips {
-        button_response = button_response.on_hover_text("Click to edit color");
+        button_response = button_response.on_hover_text(ui.translate("Click to edit color"));
     }
 
     if button_response.clicked() {
@@ -526,6 +659,10 @@ pub fn color_edit_button_hsva(ui: &mut Ui, hsva: &mut Hsva, alpha: Alpha) -> Res
         }
     }
 
+    if open && !ui.memory(|mem| mem.is_popup_open(popup_id)) {
+        add_recent_color(ui.ctx(), (*hsva).into());
+    }
+
     button_response
 }
 
@@ -572,6 +709,146 @@ pub fn color_edit_button_rgb(ui: &mut Ui, rgb: &mut [f32; 3]) -> Response {
     response
 }
 
+// ----------------------------------------------------------------------------
+
+/// Lets the user pick a color from anywhere on the screen.
+///
+/// This needs support from the integration (backend), which registers it with [`set_eyedropper`].
+/// When called, the integration should let the user pick a color (e.g. with a magnifier under the cursor),
+/// and then report the result with [`eyedropper_picked`].
+pub type Eyedropper = Arc<dyn Fn(&Context) + Send + Sync>;
+
+/// Register a screen [`Eyedropper`]. If set, all color pickers show a 💧 button for it.
+pub fn set_eyedropper(ctx: &Context, eyedropper: Option<Eyedropper>) {
+    use_eyedropper_state(ctx, |state| state.eyedropper = eyedropper);
+}
+
+/// Called by the integration when the user picked a color with the [`Eyedropper`],
+/// or with `None` if the user cancelled.
+pub fn eyedropper_picked(ctx: &Context, color: Option<Color32>) {
+    use_eyedropper_state(ctx, |state| match color {
+        Some(color) => state.picked = Some(color),
+        None => state.picking_for = None,
+    });
+    ctx.request_repaint();
+}
+
+#[derive(Clone, Default)]
+struct EyedropperState {
+    eyedropper: Option<Eyedropper>,
+
+    /// The picker that started the eyedropper.
+    picking_for: Option<Id>,
+
+    picked: Option<Color32>,
+}
+
+fn use_eyedropper_state<R>(ctx: &Context, f: impl FnOnce(&mut EyedropperState) -> R) -> R {
+    ctx.data_mut(|d| f(d.get_temp_mut_or_default(Id::new("color_picker_eyedropper"))))
+}
+
+/// A named set of colors, shown in all color pickers. See [`set_palettes`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Palette {
+    pub name: String,
+    pub colors: Vec<Color32>,
+}
+
+impl Palette {
+    pub fn new(name: impl Into<String>, colors: Vec<Color32>) -> Self {
+        Self {
+            name: name.into(),
+            colors,
+        }
+    }
+
+    /// Save as a GIMP palette (`.gpl`), a simple text format that many other programs can read too.
+    ///
+    /// The format has no alpha, so all colors are saved as opaque.
+    pub fn to_gpl(&self) -> String {
+        let mut text = format!("GIMP Palette\nName: {}\n#\n", self.name);
+        for color in &self.colors {
+            let [r, g, b, _] = color.to_srgba_unmultiplied();
+            text += &format!("{r:3} {g:3} {b:3}\t{}\n", color.to_hex());
+        }
+        text
+    }
+
+    /// Load a GIMP palette (`.gpl`), see [`Self::to_gpl`].
+    ///
+    /// # Errors
+    /// If the text is not a valid GIMP palette.
+    pub fn from_gpl(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().map(str::trim);
+        if lines.next() != Some("GIMP Palette") {
+            return Err("Missing 'GIMP Palette' header".to_owned());
+        }
+
+        let mut palette = Self::default();
+        for line in lines {
+            if let Some(name) = line.strip_prefix("Name:") {
+                name.trim().clone_into(&mut palette.name);
+            } else if line.is_empty() || line.starts_with('#') || line.contains(':') {
+                // Comments and other headers, like `Columns:`
+            } else {
+                let mut channels = line.split_whitespace().map(str::parse::<u8>);
+                let mut channel = || match channels.next() {
+                    Some(Ok(value)) => Ok(value),
+                    _ => Err(format!("Invalid color: {line:?}")),
+                };
+                palette
+                    .colors
+                    .push(Color32::from_rgb(channel()?, channel()?, channel()?));
+            }
+        }
+        Ok(palette)
+    }
+}
+
+/// The palettes shown in all color pickers.
+pub fn palettes(ctx: &Context) -> Vec<Palette> {
+    use_picker_memory(ctx, |memory| memory.palettes.clone())
+}
+
+/// Set the palettes shown in all color pickers.
+///
+/// The user can add and remove colors in the pickers, so read them back with [`palettes`]
+/// when you want to save them. They are also persisted with the rest of the egui memory.
+pub fn set_palettes(ctx: &Context, palettes: Vec<Palette>) {
+    use_picker_memory(ctx, |memory| memory.palettes = palettes);
+}
+
+/// The colors most recently picked, newest first.
+pub fn recent_colors(ctx: &Context) -> Vec<Color32> {
+    use_picker_memory(ctx, |memory| memory.recent_colors.clone())
+}
+
+/// Add a color to the recently used colors shown in all color pickers.
+///
+/// This is done automatically when a color picker popup closes.
+pub fn add_recent_color(ctx: &Context, color: Color32) {
+    const MAX_RECENT_COLORS: usize = 12;
+    use_picker_memory(ctx, |memory| {
+        memory.recent_colors.retain(|&c| c != color);
+        memory.recent_colors.insert(0, color);
+        memory.recent_colors.truncate(MAX_RECENT_COLORS);
+    });
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+struct PickerMemory {
+    recent_colors: Vec<Color32>,
+    palettes: Vec<Palette>,
+}
+
+fn use_picker_memory<R>(ctx: &Context, f: impl FnOnce(&mut PickerMemory) -> R) -> R {
+    ctx.data_mut(|d| f(d.get_persisted_mut_or_default(Id::new("color_picker_memory"))))
+}
+
+// ----------------------------------------------------------------------------
+
 // To ensure we keep hue slider when `srgba` is gray we store the full [`Hsva`] in a cache:
 fn color_cache_get(ctx: &Context, rgba: impl Into<Rgba>) -> Hsva {
     let rgba = rgba.into();
@@ -588,3 +865,144 @@ fn color_cache_set(ctx: &Context, rgba: impl Into<Rgba>, hsva: Hsva) {
 fn use_color_cache<R>(ctx: &Context, f: impl FnOnce(&mut FixedCache<Rgba, Hsva>) -> R) -> R {
     ctx.data_mut(|d| f(d.get_temp_mut_or_default(Id::NULL)))
 }
+
+#[test]
+fn test_gpl_palette() {
+    let palette = Palette::new(
+        "Traffic",
+        vec![Color32::RED, Color32::YELLOW, Color32::from_rgb(1, 200, 30)],
+    );
+    assert_eq!(Palette::from_gpl(&palette.to_gpl()), Ok(palette));
+
+    let gimp = "GIMP Palette\nName: Gray\nColumns: 2\n#\n  0   0   0\tBlack\n128 128 128\n";
+    let gray = Palette::from_gpl(gimp).unwrap();
+    assert_eq!(gray.name, "Gray");
+    assert_eq!(gray.colors, [Color32::BLACK, Color32::from_gray(128)]);
+
+    assert!(Palette::from_gpl("Name: No header").is_err());
+    assert!(Palette::from_gpl("GIMP Palette\n1 2\n").is_err());
+}
+
+#[cfg(test)]
+fn run_swatches(ctx: &Context, events: Vec<crate::Event>) -> (Option<Color32>, epaint::Pos2) {
+    let mut picked = None;
+    let mut first_swatch = epaint::Pos2::ZERO;
+    let input = crate::RawInput {
+        events,
+        ..Default::default()
+    };
+    let _ = ctx.run(input, |ctx| {
+        crate::CentralPanel::default().show(ctx, |ui| {
+            first_swatch = ui.cursor().min + vec2(4.0, 4.0);
+            picked = swatches_ui(ui, Color32::BLACK);
+        });
+    });
+    (picked, first_swatch)
+}
+
+#[cfg(test)]
+fn click_first_swatch(ctx: &Context) -> Option<Color32> {
+    use crate::{Event, PointerButton};
+
+    let (_, pos) = run_swatches(ctx, vec![]);
+    let button = |pressed| Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Default::default(),
+    };
+    run_swatches(ctx, vec![Event::PointerMoved(pos), button(true)]);
+    run_swatches(ctx, vec![button(false)]).0
+}
+
+#[test]
+fn test_checkers_are_a_pattern_fill() {
+    let ctx = Context::default();
+    let run = || {
+        let output = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                show_color(ui, Color32::from_black_alpha(100), vec2(64.0, 32.0));
+            });
+        });
+        output
+            .shapes
+            .into_iter()
+            .find_map(|clipped| match clipped.shape {
+                Shape::Rect(rect) => rect.brush,
+                _ => None,
+            })
+            .expect("The checkers should be a textured rect")
+    };
+
+    // 8 by 4 checkers of 8 points, with each texel of the texture a checker:
+    let brush = run();
+    assert_eq!(brush.uv, Rect::from_min_max(pos2(0.0, 0.0), pos2(4.0, 2.0)));
+
+    // The texture is made once:
+    assert_eq!(run().fill_texture_id, brush.fill_texture_id);
+}
+
+#[test]
+fn test_recent_colors() {
+    let ctx = Context::default();
+    add_recent_color(&ctx, Color32::RED);
+    add_recent_color(&ctx, Color32::GREEN);
+    add_recent_color(&ctx, Color32::RED);
+    assert_eq!(
+        recent_colors(&ctx),
+        [Color32::RED, Color32::GREEN],
+        "Newest first, without duplicates"
+    );
+
+    for gray in 0..20 {
+        add_recent_color(&ctx, Color32::from_gray(gray));
+    }
+    assert_eq!(recent_colors(&ctx).len(), 12);
+    assert_eq!(recent_colors(&ctx)[0], Color32::from_gray(19));
+
+    // Clicking a recent color picks it:
+    add_recent_color(&ctx, Color32::BLUE);
+    assert_eq!(click_first_swatch(&ctx), Some(Color32::BLUE));
+}
+
+#[test]
+fn test_eyedropper() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let ctx = Context::default();
+    let calls = Arc::new(AtomicUsize::new(0));
+    set_eyedropper(
+        &ctx,
+        Some(Arc::new({
+            let calls = calls.clone();
+            move |_ctx: &Context| {
+                calls.fetch_add(1, Ordering::Relaxed);
+            }
+        })),
+    );
+
+    // The eyedropper button comes first:
+    assert_eq!(click_first_swatch(&ctx), None);
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+    // The picked color goes to the picker that asked for it, once:
+    eyedropper_picked(&ctx, Some(Color32::RED));
+    assert_eq!(run_swatches(&ctx, vec![]).0, Some(Color32::RED));
+    assert_eq!(run_swatches(&ctx, vec![]).0, None);
+
+    // Cancelling picks nothing:
+    click_first_swatch(&ctx);
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+    eyedropper_picked(&ctx, None);
+    assert_eq!(run_swatches(&ctx, vec![]).0, None);
+    assert_eq!(
+        use_eyedropper_state(&ctx, |state| state.picking_for),
+        None,
+        "Not picking anymore"
+    );
+
+    // Without an eyedropper there is no button:
+    set_eyedropper(&ctx, None);
+    assert_eq!(click_first_swatch(&ctx), None);
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+}
@@ -3,14 +3,15 @@
 use std::{cmp::Ordering, ops::RangeInclusive};
 
 use crate::{
-    emath, text, Button, CursorIcon, Key, Modifiers, NumExt, Response, RichText, Sense, TextEdit,
-    TextWrapMode, Ui, Widget, WidgetInfo, MINUS_CHAR_STR,
+    emath, text, Button, CursorIcon, Key, Modifiers, NumExt, NumberLocale, Response, RichText,
+    Sense, TextEdit, TextWrapMode, Ui, Unit, Widget, WidgetInfo, MINUS_CHAR_STR,
 };
 
 // ----------------------------------------------------------------------------
 
 type NumFormatter<'a> = Box<dyn 'a + Fn(f64, RangeInclusive<usize>) -> String>;
 type NumParser<'a> = Box<dyn 'a + Fn(&str) -> Option<f64>>;
+type UnitResolver<'a> = Box<dyn 'a + Fn(&str) -> Option<f64>>;
 
 // ----------------------------------------------------------------------------
 
@@ -38,6 +39,7 @@ fn set(get_set_value: &mut GetSetValue<'_>, value: f64) {
 pub struct DragValue<'a> {
     get_set_value: GetSetValue<'a>,
     speed: f64,
+    logarithmic: bool,
     prefix: String,
     suffix: String,
     range: RangeInclusive<f64>,
@@ -46,6 +48,9 @@ pub struct DragValue<'a> {
     max_decimals: Option<usize>,
     custom_formatter: Option<NumFormatter<'a>>,
     custom_parser: Option<NumParser<'a>>,
+    expressions: bool,
+    units: Option<UnitResolver<'a>>,
+    unit: Option<String>,
     update_while_editing: bool,
 }
 
@@ -69,6 +74,7 @@ impl<'a> DragValue<'a> {
         Self {
             get_set_value: Box::new(get_set_value),
             speed: 1.0,
+            logarithmic: false,
             prefix: Default::default(),
             suffix: Default::default(),
             range: f64::NEG_INFINITY..=f64::INFINITY,
@@ -77,6 +83,9 @@ impl<'a> DragValue<'a> {
             max_decimals: None,
             custom_formatter: None,
             custom_parser: None,
+            expressions: true,
+            units: None,
+            unit: None,
             update_while_editing: true,
         }
     }
@@ -90,6 +99,23 @@ impl<'a> DragValue<'a> {
         self
     }
 
+    /// Change the value in steps proportional to its magnitude, like a logarithmic [`crate::Slider`].
+    ///
+    /// The [`Self::speed`] is then in orders of magnitude per point,
+    /// so with a speed of `0.01`, dragging 100 points makes the value ten times larger.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut frequency: f32 = 440.0;
+    /// ui.add(egui::DragValue::new(&mut frequency).logarithmic(true).speed(0.01));
+    /// # });
+    /// ```
+    #[inline]
+    pub fn logarithmic(mut self, logarithmic: bool) -> Self {
+        self.logarithmic = logarithmic;
+        self
+    }
+
     /// Sets valid range for the value.
     ///
     /// By default all values are clamped to this range, even when not interacted with.
@@ -415,6 +441,60 @@ impl<'a> DragValue<'a> {
         .custom_parser(|s| i64::from_str_radix(s, 16).map(|n| n as f64).ok())
     }
 
+    /// Accept arithmetic expressions like `2*pi/3` when text-editing the value.
+    ///
+    /// The expression is evaluated when the user presses enter or deselects the value,
+    /// and only if the text isn't a plain number.
+    /// See [`crate::util::expression`] for what is supported.
+    ///
+    /// Expressions are never used together with a [`Self::custom_parser`]
+    /// (which includes [`Self::binary`], [`Self::octal`] and [`Self::hexadecimal`]),
+    /// so that text the parser rejects isn't read as a decimal expression instead.
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn expressions(mut self, expressions: bool) -> Self {
+        self.expressions = expressions;
+        self
+    }
+
+    /// Name the units that can be used in expressions, like `1in + 5mm`.
+    ///
+    /// The function returns the value of one of the named unit, in the unit of the value,
+    /// or `None` if the name is unknown.
+    ///
+    /// This also turns on [`Self::expressions`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut length_in_mm: f64 = 0.0;
+    /// ui.add(egui::DragValue::new(&mut length_in_mm).suffix(" mm").units(|unit| match unit {
+    ///     "mm" => Some(1.0),
+    ///     "cm" => Some(10.0),
+    ///     "in" => Some(25.4),
+    ///     _ => None,
+    /// }));
+    /// # });
+    /// ```
+    #[inline]
+    pub fn units(mut self, units: impl 'a + Fn(&str) -> Option<f64>) -> Self {
+        self.expressions = true;
+        self.units = Some(Box::new(units));
+        self
+    }
+
+    /// Show and parse the value in one of the units of [`crate::Style::units`], e.g. `"length"`.
+    ///
+    /// The value is in the base unit (e.g. meters), and is shown in whatever scale fits it best, e.g. `1.5 km`.
+    /// Ignored if there is no unit with this name.
+    ///
+    /// [`Self::custom_formatter`] and [`Self::custom_parser`] take precedence over this.
+    #[inline]
+    pub fn unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+
     /// Update the value on each key press when text-editing the value.
     ///
     /// Default: `true`.
@@ -431,6 +511,7 @@ impl Widget for DragValue<'_> {
         let Self {
             mut get_set_value,
             speed,
+            logarithmic,
             range,
             clamp_existing_to_range,
             prefix,
@@ -439,9 +520,28 @@ impl Widget for DragValue<'_> {
             max_decimals,
             custom_formatter,
             custom_parser,
+            expressions,
+            units,
+            unit,
             update_while_editing,
         } = self;
 
+        let locale = ui.style().number_locale;
+        let unit = unit.and_then(|unit| ui.style().units.get(&unit).cloned());
+        let unit = unit.as_deref();
+
+        let parse_committed = |value_text: &str| {
+            parse(&custom_parser, unit, &locale, value_text).or_else(|| {
+                (expressions && custom_parser.is_none())
+                    .then(|| {
+                        let units = |name: &str| units.as_ref().and_then(|units| units(name));
+                        let value_text = locale.delocalize(value_text);
+                        crate::util::expression::evaluate(&value_text, &units).ok()
+                    })
+                    .flatten()
+            })
+        };
+
         let shift = ui.input(|i| i.modifiers.shift_only());
         // The widget has the same ID whether it's in edit or button mode.
         let id = ui.next_auto_id();
@@ -465,7 +565,18 @@ impl Widget for DragValue<'_> {
         let mut value = old_value;
         let aim_rad = ui.input(|i| i.aim_radius() as f64);
 
-        let auto_decimals = (aim_rad / speed.abs()).log10().ceil().clamp(0.0, 15.0) as usize;
+        // Move `value` by `amount`, which is in orders of magnitude when logarithmic:
+        let step = |value: f64, amount: f64| {
+            if logarithmic {
+                value_from_log(log_from_value(value) + amount)
+            } else {
+                value + amount
+            }
+        };
+        // How much the value changes per point around the current value:
+        let value_speed = (step(old_value, speed) - old_value).abs();
+
+        let auto_decimals = (aim_rad / value_speed).log10().ceil().clamp(0.0, 15.0) as usize;
         let auto_decimals = auto_decimals + is_slow_speed as usize;
         let max_decimals = max_decimals
             .unwrap_or(auto_decimals + 2)
@@ -516,7 +627,7 @@ impl Widget for DragValue<'_> {
         }
 
         if change != 0.0 {
-            value += speed * change;
+            value = step(value, speed * change);
             value = emath::round_to_decimals(value, auto_decimals);
         }
 
@@ -525,12 +636,14 @@ impl Widget for DragValue<'_> {
             ui.data_mut(|data| data.remove::<String>(id));
         }
 
-        let value_text = match custom_formatter {
-            Some(custom_formatter) => custom_formatter(value, auto_decimals..=max_decimals),
-            None => ui
-                .style()
-                .number_formatter
-                .format(value, auto_decimals..=max_decimals),
+        let value_text = match (custom_formatter, unit) {
+            (Some(custom_formatter), _) => custom_formatter(value, auto_decimals..=max_decimals),
+            (None, Some(unit)) => unit.format(value, auto_decimals..=max_decimals, &locale),
+            (None, None) => locale.localize(
+                &ui.style()
+                    .number_formatter
+                    .format(value, auto_decimals..=max_decimals),
+            ),
         };
 
         let text_style = ui.style().drag_value_text_style.clone();
@@ -540,7 +653,7 @@ impl Widget for DragValue<'_> {
             if let Some(value_text) = value_text {
                 // We were editing the value as text last frame, but lost focus.
                 // Make sure we applied the last text value:
-                let parsed_value = parse(&custom_parser, &value_text);
+                let parsed_value = parse_committed(&value_text);
                 if let Some(mut parsed_value) = parsed_value {
                     // User edits always clamps:
                     parsed_value = clamp_value_to_range(parsed_value, range.clone());
@@ -569,20 +682,21 @@ impl Widget for DragValue<'_> {
                     .font(text_style),
             );
 
-            let update = if update_while_editing {
+            let committed = response.lost_focus() && !ui.input(|i| i.key_pressed(Key::Escape));
+            let parsed_value = if committed {
+                // The edit is done, so this is when we evaluate expressions.
+                parse_committed(&value_text)
+            } else if update_while_editing && response.changed() {
                 // Update when the edit content has changed.
-                response.changed()
+                parse(&custom_parser, unit, &locale, &value_text)
             } else {
                 // Update only when the edit has lost focus.
-                response.lost_focus() && !ui.input(|i| i.key_pressed(Key::Escape))
+                None
             };
-            if update {
-                let parsed_value = parse(&custom_parser, &value_text);
-                if let Some(mut parsed_value) = parsed_value {
-                    // User edits always clamps:
-                    parsed_value = clamp_value_to_range(parsed_value, range.clone());
-                    set(&mut get_set_value, parsed_value);
-                }
+            if let Some(mut parsed_value) = parsed_value {
+                // User edits always clamps:
+                parsed_value = clamp_value_to_range(parsed_value, range.clone());
+                set(&mut get_set_value, parsed_value);
             }
             ui.data_mut(|data| data.insert_temp(id, value_text));
             response
@@ -637,15 +751,13 @@ impl Widget for DragValue<'_> {
 
                 let speed = if is_slow_speed { speed / 10.0 } else { speed };
 
-                let delta_value = delta_points as f64 * speed;
-
-                if delta_value != 0.0 {
+                if delta_points != 0.0 {
                     // Since we round the value being dragged, we need to store the full precision value in memory:
                     let precise_value = ui.data_mut(|data| data.get_temp::<f64>(id));
                     let precise_value = precise_value.unwrap_or(value);
-                    let precise_value = precise_value + delta_value;
+                    let precise_value = step(precise_value, delta_points as f64 * speed);
 
-                    let aim_delta = aim_rad * speed;
+                    let aim_delta = aim_rad * (step(precise_value, speed) - precise_value).abs();
                     let rounded_new_value = emath::smart_aim::best_in_range_f64(
                         precise_value - aim_delta,
                         precise_value + aim_delta,
@@ -681,7 +793,7 @@ impl Widget for DragValue<'_> {
             if range.end().is_finite() {
                 builder.set_max_numeric_value(*range.end());
             }
-            builder.set_numeric_value_step(speed);
+            builder.set_numeric_value_step(value_speed);
             builder.add_action(Action::SetValue);
             if value < *range.end() {
                 builder.add_action(Action::Increment);
@@ -720,10 +832,16 @@ impl Widget for DragValue<'_> {
     }
 }
 
-fn parse(custom_parser: &Option<NumParser<'_>>, value_text: &str) -> Option<f64> {
-    match &custom_parser {
-        Some(parser) => parser(value_text),
-        None => default_parser(value_text),
+fn parse(
+    custom_parser: &Option<NumParser<'_>>,
+    unit: Option<&dyn Unit>,
+    locale: &NumberLocale,
+    value_text: &str,
+) -> Option<f64> {
+    match (custom_parser, unit) {
+        (Some(parser), _) => parser(value_text),
+        (None, Some(unit)) => unit.parse(value_text, locale),
+        (None, None) => default_parser(&locale.delocalize(value_text)),
     }
 }
 
@@ -742,6 +860,22 @@ fn default_parser(text: &str) -> Option<f64> {
     text.parse().ok()
 }
 
+/// Values smaller than this are dragged linearly by a logarithmic [`DragValue`],
+/// so that it can cross zero.
+const LOG_LINEAR_MAGNITUDE: f64 = 1e-6;
+
+/// Maps a value to orders of magnitude for a logarithmic [`DragValue`].
+///
+/// Symmetric around (and continuous at) zero.
+fn log_from_value(value: f64) -> f64 {
+    value.signum() * (1.0 + value.abs() / LOG_LINEAR_MAGNITUDE).log10()
+}
+
+/// Inverse of [`log_from_value`].
+fn value_from_log(log: f64) -> f64 {
+    log.signum() * (10.0_f64.powf(log.abs()) - 1.0) * LOG_LINEAR_MAGNITUDE
+}
+
 /// Clamp the given value with careful handling of negative zero, and other corner cases.
 pub(crate) fn clamp_value_to_range(x: f64, range: RangeInclusive<f64>) -> f64 {
     let (mut min, mut max) = (*range.start(), *range.end());
@@ -788,6 +922,98 @@ mod tests {
         total_assert_eq!(1.0_f64, clamp_value_to_range(-5.0, 5.0..=1.0));
     }
 
+    #[test]
+    fn test_logarithmic_steps() {
+        use super::{log_from_value, value_from_log};
+
+        for value in [0.0, 1e-9, 0.5, 440.0, -20_000.0, 1e12] {
+            let roundtrip = value_from_log(log_from_value(value));
+            assert!((roundtrip - value).abs() <= 1e-9 * value.abs().max(1.0));
+        }
+
+        let tenfold = value_from_log(log_from_value(440.0) + 1.0);
+        assert!((tenfold - 4400.0).abs() < 1e-3, "One order of magnitude up");
+
+        assert!(
+            value_from_log(log_from_value(1e-3) - 20.0) < 0.0,
+            "Can drag past zero"
+        );
+    }
+
+    /// Type `text` into a [`DragValue`] made by `drag_value`, press enter, and return the new value.
+    fn type_into(text: &str, drag_value: impl Fn(&mut f64) -> super::DragValue<'_>) -> f64 {
+        use crate::{Context, Event, Key, Modifiers, RawInput};
+
+        let ctx = Context::default();
+        let mut value = 0.0;
+        let mut run = |events: Vec<Event>| {
+            let mut id = None;
+            let _ = ctx.run(
+                RawInput {
+                    events,
+                    ..Default::default()
+                },
+                |ctx| {
+                    crate::CentralPanel::default().show(ctx, |ui| {
+                        id = Some(ui.add(drag_value(&mut value)).id);
+                    });
+                },
+            );
+            id.unwrap()
+        };
+        let key = |key, modifiers| Event::Key {
+            key,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers,
+        };
+
+        let id = run(vec![]);
+        ctx.memory_mut(|mem| mem.request_focus(id));
+        run(vec![]);
+        run(vec![
+            key(Key::A, Modifiers::COMMAND),
+            Event::Text(text.to_owned()),
+            key(Key::Enter, Modifiers::NONE),
+        ]);
+        run(vec![]);
+        value
+    }
+
+    #[test]
+    fn test_expressions() {
+        assert_eq!(type_into("2*3", |v| super::DragValue::new(v)), 6.0);
+        assert_eq!(
+            type_into("1in", |v| super::DragValue::new(v)
+                .units(|unit| (unit == "in").then_some(25.4))),
+            25.4
+        );
+        assert_eq!(
+            type_into("1 + 2 3", |v| super::DragValue::new(v)),
+            0.0,
+            "No implicit multiplication of numbers"
+        );
+        assert_eq!(
+            type_into("2*3", |v| super::DragValue::new(v).expressions(false)),
+            0.0
+        );
+
+        // A custom parser rejecting the text is not overruled by an expression:
+        assert_eq!(
+            type_into("11", |v| super::DragValue::new(v).binary(1, false)),
+            3.0
+        );
+        assert_eq!(
+            type_into("2", |v| super::DragValue::new(v).binary(1, false)),
+            0.0
+        );
+        assert_eq!(
+            type_into("9", |v| super::DragValue::new(v).octal(1, false)),
+            0.0
+        );
+    }
+
     #[test]
     fn test_default_parser() {
         assert_eq!(super::default_parser("123"), Some(123.0));
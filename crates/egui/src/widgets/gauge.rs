@@ -0,0 +1,557 @@
+use std::f32::consts::{PI, TAU};
+
+use crate::{
+    emath::remap_clamp,
+    epaint::{ArcShape, PathStroke},
+    lerp, pos2, vec2, Align2, Color32, CornerRadius, Pos2, Rangef, Rect, Response, Sense, Shape,
+    Stroke, StrokeKind, TextStyle, Ui, Vec2, Widget, WidgetInfo, WidgetText, WidgetType,
+};
+
+/// What a [`Gauge`] looks like.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GaugeKind {
+    /// A 270° band that fills up with the value, with the value in the middle.
+    Radial,
+
+    /// A half circle with a needle pointing at the value, and labeled ticks.
+    Speedometer,
+
+    /// A battery that fills up from the left, showing the percentage.
+    Battery,
+}
+
+/// A range of values shown in its own color, e.g. the red zone of a speedometer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct GaugeZone {
+    range: Rangef,
+
+    /// The colors at the start and the end of the range.
+    colors: [Color32; 2],
+}
+
+/// Shows a value in a range, for dashboards and telemetry.
+///
+/// The needle (or fill) animates towards new values.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let (rpm, speed, charge) = (3200.0, 87.0, 0.64);
+/// ui.add(
+///     egui::Gauge::radial(rpm)
+///         .range(0.0..=8000.0)
+///         .zone(6000.0..=8000.0, egui::Color32::RED)
+///         .unit(" rpm"),
+/// );
+/// ui.add(
+///     egui::Gauge::speedometer(speed)
+///         .range(0.0..=200.0)
+///         .gradient_zone(120.0..=200.0, [egui::Color32::YELLOW, egui::Color32::RED])
+///         .unit(" km/h"),
+/// );
+/// ui.add(egui::Gauge::battery(charge));
+/// # });
+/// ```
+#[must_use = "You should put this widget in a ui with `ui.add(widget);`"]
+pub struct Gauge {
+    kind: GaugeKind,
+    value: f32,
+    range: Rangef,
+    size: Option<f32>,
+    zones: Vec<GaugeZone>,
+    num_ticks: usize,
+    decimals: usize,
+    unit: String,
+    text: Option<WidgetText>,
+    animate: bool,
+}
+
+impl Gauge {
+    pub fn new(kind: GaugeKind, value: f32) -> Self {
+        Self {
+            kind,
+            value,
+            range: Rangef::new(0.0, 100.0),
+            size: None,
+            zones: Vec::new(),
+            num_ticks: if kind == GaugeKind::Speedometer {
+                11
+            } else {
+                0
+            },
+            decimals: 0,
+            unit: String::new(),
+            text: None,
+            animate: true,
+        }
+    }
+
+    /// See [`GaugeKind::Radial`]. The default range is `0..=100`.
+    pub fn radial(value: f32) -> Self {
+        Self::new(GaugeKind::Radial, value)
+    }
+
+    /// See [`GaugeKind::Speedometer`]. The default range is `0..=100`.
+    pub fn speedometer(value: f32) -> Self {
+        Self::new(GaugeKind::Speedometer, value)
+    }
+
+    /// See [`GaugeKind::Battery`]. The charge is in the `0..=1` range.
+    ///
+    /// By default, the battery is red below 20%.
+    pub fn battery(charge: f32) -> Self {
+        Self {
+            range: Rangef::new(0.0, 1.0),
+            ..Self::new(GaugeKind::Battery, charge)
+        }
+    }
+
+    /// The values at the start and the end of the gauge.
+    #[inline]
+    pub fn range(mut self, range: impl Into<Rangef>) -> Self {
+        self.range = range.into();
+        self
+    }
+
+    /// The width of the gauge.
+    ///
+    /// Default: `120` for round gauges, `60` for batteries.
+    #[inline]
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Show this range of values in a color.
+    ///
+    /// Later zones are painted on top of earlier ones.
+    #[inline]
+    pub fn zone(self, range: impl Into<Rangef>, color: impl Into<Color32>) -> Self {
+        let color = color.into();
+        self.gradient_zone(range, [color, color])
+    }
+
+    /// Show this range of values with a gradient, from the first color at the start of the range
+    /// to the second one at the end.
+    #[inline]
+    pub fn gradient_zone(mut self, range: impl Into<Rangef>, colors: [Color32; 2]) -> Self {
+        self.zones.push(GaugeZone {
+            range: range.into(),
+            colors,
+        });
+        self
+    }
+
+    /// How many ticks to draw along the scale, including both ends.
+    ///
+    /// The ticks of a speedometer are labeled with their value.
+    ///
+    /// Default: `11` for speedometers, no ticks otherwise.
+    #[inline]
+    pub fn num_ticks(mut self, num_ticks: usize) -> Self {
+        self.num_ticks = num_ticks;
+        self
+    }
+
+    /// How many decimals to show in the readout and the tick labels.
+    ///
+    /// Default: `0`.
+    #[inline]
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    /// Shown after the value in the readout, e.g. `" km/h"`.
+    #[inline]
+    pub fn unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = unit.into();
+        self
+    }
+
+    /// Show this instead of the value.
+    #[inline]
+    pub fn text(mut self, text: impl Into<WidgetText>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Move the needle (or fill) smoothly towards new values.
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn animate(mut self, animate: bool) -> Self {
+        self.animate = animate;
+        self
+    }
+
+    /// The color of the zone at this value, if any.
+    fn zone_color(&self, value: f32) -> Option<Color32> {
+        zone_color(&self.zones, value)
+    }
+}
+
+fn zone_color(zones: &[GaugeZone], value: f32) -> Option<Color32> {
+    zones
+        .iter()
+        .rev()
+        .find(|zone| zone.range.contains(value))
+        .map(|zone| {
+            let t = remap_clamp(value, zone.range, 0.0..=1.0);
+            zone.colors[0].lerp_to_gamma(zone.colors[1], t)
+        })
+}
+
+impl Widget for Gauge {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let default_size = if self.kind == GaugeKind::Battery {
+            60.0
+        } else {
+            120.0
+        };
+        let width = self.size.unwrap_or(default_size);
+        let desired_size = match self.kind {
+            GaugeKind::Radial => vec2(width, width),
+            GaugeKind::Speedometer => vec2(width, 0.65 * width),
+            GaugeKind::Battery => vec2(width, 0.45 * width),
+        };
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        let fraction = remap_clamp(self.value, self.range, 0.0..=1.0);
+        let readout = self.text.clone().unwrap_or_else(|| {
+            if self.kind == GaugeKind::Battery {
+                format!("{:.0}%", 100.0 * fraction).into()
+            } else {
+                format!("{:.*}{}", self.decimals, self.value, self.unit).into()
+            }
+        });
+        response.widget_info(|| {
+            WidgetInfo::labeled(
+                WidgetType::ProgressIndicator,
+                ui.is_enabled(),
+                readout.text(),
+            )
+        });
+
+        if !ui.is_rect_visible(rect) {
+            return response;
+        }
+
+        let shown_fraction = if self.animate {
+            ui.ctx()
+                .animate_value_with_time(response.id.with("gauge"), fraction, 0.25)
+        } else {
+            fraction
+        };
+
+        match self.kind {
+            GaugeKind::Radial => paint_radial(ui, rect, &self, shown_fraction, readout),
+            GaugeKind::Speedometer => paint_speedometer(ui, rect, &self, shown_fraction, readout),
+            GaugeKind::Battery => paint_battery(ui, rect, &self, shown_fraction, readout),
+        }
+
+        response
+    }
+}
+
+/// The start angle and sweep (clockwise) of round gauges, in radians counter-clockwise from the x-axis.
+fn arc_angles(kind: GaugeKind) -> (f32, f32) {
+    match kind {
+        GaugeKind::Speedometer => (PI, PI),
+        GaugeKind::Radial | GaugeKind::Battery => (1.25 * PI, 0.75 * TAU),
+    }
+}
+
+fn polar(center: Pos2, radius: f32, angle: f32) -> Pos2 {
+    center + radius * vec2(angle.cos(), -angle.sin())
+}
+
+/// A thick arc between two radii: an [`ArcShape`] stroked as wide as the band.
+///
+/// `color_at` is given the fraction of the gauge, from `fractions.min` to `fractions.max`.
+fn band(
+    kind: GaugeKind,
+    center: Pos2,
+    radii: Rangef,
+    fractions: Rangef,
+    color_at: impl Fn(f32) -> Color32 + Send + Sync + 'static,
+) -> Shape {
+    let (start, sweep) = arc_angles(kind);
+    let radius = radii.center();
+    if fractions.span() * sweep * radius < 0.5 {
+        return Shape::Noop;
+    }
+    let angles = Rangef::new(start - fractions.min * sweep, start - fractions.max * sweep);
+
+    let arc = ArcShape::new(
+        center,
+        polar(center, radius, angles.min),
+        polar(center, radius, angles.max),
+        Vec2::splat(radius),
+        0.0,
+        PI < fractions.span() * sweep,
+        true,
+        Color32::TRANSPARENT,
+        PathStroke::NONE,
+    );
+
+    // The fraction at a point of the band, from its angle around the center:
+    let stroke = PathStroke::new_uv(radii.span(), move |_bounds, pos| {
+        let angle = (center.y - pos.y).atan2(pos.x - center.x);
+        let mut swept = (start - angle).rem_euclid(TAU);
+        if 0.5 * (sweep + TAU) < swept {
+            swept -= TAU; // Just before the start
+        }
+        color_at((swept / sweep).clamp(fractions.min, fractions.max))
+    });
+
+    // `ArcShape` itself is painted as a closed loop, so stroke its points as an open line:
+    Shape::line(arc.flatten(Some(1.0)), stroke)
+}
+
+/// The zones of the gauge, as bands between two radii.
+fn paint_zones(ui: &Ui, gauge: &Gauge, center: Pos2, radii: Rangef) {
+    for zone in &gauge.zones {
+        let fractions = Rangef::new(
+            remap_clamp(zone.range.min, gauge.range, 0.0..=1.0),
+            remap_clamp(zone.range.max, gauge.range, 0.0..=1.0),
+        );
+        let colors = zone.colors;
+        ui.painter().add(band(
+            gauge.kind,
+            center,
+            radii,
+            fractions,
+            move |fraction| {
+                let t = remap_clamp(fraction, fractions, 0.0..=1.0);
+                colors[0].lerp_to_gamma(colors[1], t)
+            },
+        ));
+    }
+}
+
+fn paint_ticks(ui: &Ui, gauge: &Gauge, center: Pos2, radii: Rangef, label_radius: Option<f32>) {
+    let (start, sweep) = arc_angles(gauge.kind);
+    let stroke = ui.visuals().widgets.noninteractive.fg_stroke;
+    let font_id = TextStyle::Small.resolve(ui.style());
+    for tick in 0..gauge.num_ticks {
+        let fraction = tick as f32 / (gauge.num_ticks - 1).max(1) as f32;
+        let angle = start - fraction * sweep;
+        ui.painter().line_segment(
+            [
+                polar(center, radii.min, angle),
+                polar(center, radii.max, angle),
+            ],
+            stroke,
+        );
+        if let Some(label_radius) = label_radius {
+            let value = lerp(gauge.range, fraction);
+            ui.painter().text(
+                polar(center, label_radius, angle),
+                Align2::CENTER_CENTER,
+                format!("{value:.*}", gauge.decimals),
+                font_id.clone(),
+                ui.visuals().weak_text_color(),
+            );
+        }
+    }
+}
+
+fn paint_readout(ui: &Ui, pos: Pos2, readout: WidgetText, text_style: TextStyle) {
+    let galley = readout.into_galley(ui, None, f32::INFINITY, text_style);
+    let rect = Align2::CENTER_CENTER.anchor_size(pos, galley.size());
+    ui.painter()
+        .galley(rect.min, galley, ui.visuals().strong_text_color());
+}
+
+fn paint_radial(ui: &Ui, rect: Rect, gauge: &Gauge, fraction: f32, readout: WidgetText) {
+    let center = rect.center();
+    let outer = 0.5 * rect.width() - 1.0;
+    let band_width = 0.15 * outer;
+    let band_radii = Rangef::new(outer - band_width, outer);
+
+    let visuals = ui.visuals();
+    let background = visuals.widgets.inactive.bg_fill;
+    ui.painter().add(band(
+        gauge.kind,
+        center,
+        band_radii,
+        Rangef::new(0.0, 1.0),
+        move |_| background,
+    ));
+
+    // The zones as a thin band inside, so they are visible behind the value:
+    paint_zones(
+        ui,
+        gauge,
+        center,
+        Rangef::new(
+            band_radii.min - 0.3 * band_width,
+            band_radii.min - 0.1 * band_width,
+        ),
+    );
+
+    let default_fill = visuals.selection.bg_fill;
+    let (zones, range) = (gauge.zones.clone(), gauge.range);
+    ui.painter().add(band(
+        gauge.kind,
+        center,
+        band_radii,
+        Rangef::new(0.0, fraction),
+        move |fraction| zone_color(&zones, lerp(range, fraction)).unwrap_or(default_fill),
+    ));
+
+    let tick_start = band_radii.min - 0.45 * band_width;
+    paint_ticks(
+        ui,
+        gauge,
+        center,
+        Rangef::new(tick_start - 0.5 * band_width, tick_start),
+        None,
+    );
+
+    paint_readout(ui, center, readout, TextStyle::Heading);
+}
+
+fn paint_speedometer(ui: &Ui, rect: Rect, gauge: &Gauge, fraction: f32, readout: WidgetText) {
+    let outer = 0.5 * rect.width() - 1.0;
+    let center = pos2(rect.center().x, rect.top() + outer + 1.0);
+    let band_width = 0.1 * outer;
+    let band_radii = Rangef::new(outer - band_width, outer);
+
+    let visuals = ui.visuals();
+    let background = visuals.widgets.inactive.bg_fill;
+    ui.painter().add(band(
+        gauge.kind,
+        center,
+        band_radii,
+        Rangef::new(0.0, 1.0),
+        move |_| background,
+    ));
+    paint_zones(ui, gauge, center, band_radii);
+    paint_ticks(
+        ui,
+        gauge,
+        center,
+        Rangef::new(band_radii.min - 0.5 * band_width, band_radii.max),
+        Some(band_radii.min - 1.8 * band_width),
+    );
+
+    let (start, sweep) = arc_angles(gauge.kind);
+    let needle_color = visuals.strong_text_color();
+    let tip = polar(center, band_radii.max, start - fraction * sweep);
+    ui.painter()
+        .line_segment([center, tip], Stroke::new(2.0, needle_color));
+    ui.painter()
+        .circle_filled(center, 0.06 * outer, needle_color);
+
+    let readout_pos = pos2(center.x, 0.5 * (center.y + rect.bottom()) + 0.06 * outer);
+    paint_readout(ui, readout_pos, readout, TextStyle::Body);
+}
+
+fn paint_battery(ui: &Ui, rect: Rect, gauge: &Gauge, fraction: f32, readout: WidgetText) {
+    let visuals = ui.visuals();
+    let terminal_width = 0.06 * rect.width();
+    let body = Rect::from_min_max(rect.min, pos2(rect.right() - terminal_width, rect.bottom()));
+    let terminal = Rect::from_center_size(
+        pos2(rect.right() - 0.5 * terminal_width, rect.center().y),
+        vec2(terminal_width, 0.4 * rect.height()),
+    );
+    let stroke = visuals.widgets.noninteractive.fg_stroke;
+    let corner_radius = CornerRadius::same((0.15 * rect.height()) as u8);
+    let fill_corner_radius = corner_radius - 2;
+
+    ui.painter()
+        .rect_stroke(body, corner_radius, stroke, StrokeKind::Inside);
+    ui.painter().rect_filled(
+        terminal,
+        CornerRadius {
+            nw: 0,
+            sw: 0,
+            ..CornerRadius::same(2)
+        },
+        stroke.color,
+    );
+
+    let inner = body.shrink(stroke.width + 2.0);
+    let default_fill = if gauge.zones.is_empty() && fraction < 0.2 {
+        visuals.error_fg_color
+    } else {
+        visuals.selection.bg_fill
+    };
+    let fill = gauge
+        .zone_color(lerp(gauge.range, fraction))
+        .unwrap_or(default_fill);
+    let fill_rect = Rect::from_min_size(inner.min, vec2(fraction * inner.width(), inner.height()));
+    ui.painter()
+        .rect_filled(fill_rect, fill_corner_radius, fill);
+
+    paint_readout(ui, body.center(), readout, TextStyle::Small);
+}
+
+#[test]
+fn test_zone_color() {
+    let gauge = Gauge::radial(0.0)
+        .zone(0.0..=50.0, Color32::GREEN)
+        .gradient_zone(40.0..=100.0, [Color32::BLACK, Color32::WHITE]);
+    assert_eq!(gauge.zone_color(10.0), Some(Color32::GREEN));
+    assert_eq!(
+        gauge.zone_color(40.0),
+        Some(Color32::BLACK),
+        "Later zones win"
+    );
+    assert_eq!(gauge.zone_color(100.0), Some(Color32::WHITE));
+    assert_eq!(gauge.zone_color(120.0), None);
+}
+
+#[test]
+fn test_band() {
+    use crate::epaint::{Mesh, TessellationOptions, Tessellator};
+
+    let center = pos2(100.0, 100.0);
+    let shape = band(
+        GaugeKind::Speedometer,
+        center,
+        Rangef::new(40.0, 50.0),
+        Rangef::new(0.0, 1.0),
+        |fraction| Color32::BLACK.lerp_to_gamma(Color32::WHITE, fraction),
+    );
+    let options = TessellationOptions {
+        feathering: false,
+        ..Default::default()
+    };
+    let mut mesh = Mesh::default();
+    Tessellator::new(1.0, options, [1, 1], vec![]).tessellate_shape(shape, &mut mesh);
+    assert!(!mesh.is_empty());
+
+    for vertex in &mesh.vertices {
+        let radius = vertex.pos.distance(center);
+        assert!((39.9..=50.1).contains(&radius), "{radius}");
+        assert!(
+            vertex.pos.y <= center.y + 0.1,
+            "A speedometer is the top half"
+        );
+    }
+
+    // Black on the left, white on the right:
+    let leftmost = mesh
+        .vertices
+        .iter()
+        .min_by(|a, b| a.pos.x.total_cmp(&b.pos.x))
+        .unwrap();
+    let rightmost = mesh
+        .vertices
+        .iter()
+        .max_by(|a, b| a.pos.x.total_cmp(&b.pos.x))
+        .unwrap();
+    assert_eq!(leftmost.color, Color32::BLACK);
+    assert_eq!(rightmost.color, Color32::WHITE);
+
+    let nothing = band(
+        GaugeKind::Radial,
+        center,
+        Rangef::new(40.0, 50.0),
+        Rangef::new(0.5, 0.5),
+        |_| Color32::RED,
+    );
+    assert_eq!(nothing, Shape::Noop);
+}
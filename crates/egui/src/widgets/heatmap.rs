@@ -0,0 +1,385 @@
+use std::sync::Arc;
+
+use epaint::{color::Colormap, textures::TextureOptions, ColorImage, TextureHandle};
+
+use crate::{
+    emath::format_with_decimals_in_range, pos2, remap, vec2, Color32, Context, Id, IdMap, Rangef,
+    Rect, Response, Sense, Stroke, StrokeKind, Ui, Vec2, ViewportId,
+};
+
+/// The cell of a [`Heatmap`] under the pointer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HeatmapCell {
+    /// The column, counted from the left.
+    pub x: usize,
+
+    /// The row, counted from the top.
+    pub y: usize,
+
+    pub value: f32,
+}
+
+/// What [`Heatmap::show`] returns.
+pub struct HeatmapResponse {
+    pub response: Response,
+
+    /// The cell under the pointer, if any.
+    pub hovered: Option<HeatmapCell>,
+}
+
+/// Shows a matrix of numbers as an image, coloring each value with a [`Colormap`].
+///
+/// The values are stored row by row, from top to bottom.
+/// The image is uploaded as a texture, and only re-uploaded when the values change.
+/// The texture is freed once the heatmap isn't shown for a pass.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::epaint::color::Colormap;
+///
+/// let (width, height) = (32, 16);
+/// let values: Vec<f32> = (0..width * height)
+///     .map(|i| ((i % width) as f32 * 0.3).sin() * ((i / width) as f32 * 0.2).cos())
+///     .collect();
+///
+/// let response = egui::Heatmap::new(&values, width)
+///     .colormap(Colormap::Magma)
+///     .range(-1.0..=1.0)
+///     .show(ui);
+/// if let Some(cell) = response.hovered {
+///     ui.label(format!("{} at ({}, {})", cell.value, cell.x, cell.y));
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct Heatmap<'a> {
+    values: &'a [f32],
+    width: usize,
+    colormap: Colormap,
+    range: Option<Rangef>,
+    cell_size: Vec2,
+    size: Option<Vec2>,
+    id_salt: Option<Id>,
+    hover_readout: bool,
+}
+
+impl<'a> Heatmap<'a> {
+    /// `values` has `width` columns, stored row by row.
+    ///
+    /// A partial last row is ignored.
+    pub fn new(values: &'a [f32], width: usize) -> Self {
+        Self {
+            values,
+            width,
+            colormap: Colormap::default(),
+            range: None,
+            cell_size: Vec2::splat(8.0),
+            size: None,
+            id_salt: None,
+            hover_readout: true,
+        }
+    }
+
+    /// Default: [`Colormap::Viridis`].
+    #[inline]
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// The values mapped to the start and end of the colormap.
+    /// Values outside the range get the color of the closest end.
+    ///
+    /// Default: from the smallest to the largest value.
+    #[inline]
+    pub fn range(mut self, range: impl Into<Rangef>) -> Self {
+        self.range = Some(range.into());
+        self
+    }
+
+    /// The size of each cell on screen.
+    ///
+    /// Default: `8x8`.
+    #[inline]
+    pub fn cell_size(mut self, cell_size: impl Into<Vec2>) -> Self {
+        self.cell_size = cell_size.into();
+        self
+    }
+
+    /// The size of the whole heatmap on screen, overriding [`Self::cell_size`].
+    #[inline]
+    pub fn size(mut self, size: impl Into<Vec2>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+
+    /// Where to cache the texture.
+    ///
+    /// Needed when the number of widgets before the heatmap changes between frames,
+    /// or the texture is re-uploaded every time they do.
+    #[inline]
+    pub fn id_salt(mut self, id_salt: impl std::hash::Hash) -> Self {
+        self.id_salt = Some(Id::new(id_salt));
+        self
+    }
+
+    /// Show the value under the pointer in a tooltip, and outline its cell.
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn hover_readout(mut self, hover_readout: bool) -> Self {
+        self.hover_readout = hover_readout;
+        self
+    }
+
+    /// Number of columns and rows.
+    fn grid_size(&self) -> [usize; 2] {
+        if self.width == 0 {
+            [0, 0]
+        } else {
+            [self.width, self.values.len() / self.width]
+        }
+    }
+
+    /// The values in the grid, without a partial last row.
+    fn grid_values(&self) -> &'a [f32] {
+        let [width, height] = self.grid_size();
+        &self.values[..width * height]
+    }
+
+    /// The range mapped to the colormap: the one set, or that of the finite values.
+    fn value_range(&self) -> Rangef {
+        self.range.unwrap_or_else(|| {
+            self.grid_values()
+                .iter()
+                .filter(|value| value.is_finite())
+                .fold(Rangef::NOTHING, |range, &value| {
+                    Rangef::new(range.min.min(value), range.max.max(value))
+                })
+        })
+    }
+
+    /// Color the values, one pixel per value.
+    ///
+    /// `NaN` values are transparent.
+    pub fn to_image(&self) -> ColorImage {
+        let range = self.value_range();
+        let pixels = self
+            .grid_values()
+            .iter()
+            .map(|&value| {
+                let t = if value.is_nan() {
+                    f32::NAN
+                } else if range.span() > 0.0 {
+                    remap(value, range, 0.0..=1.0)
+                } else {
+                    0.5
+                };
+                self.colormap.color_at(t)
+            })
+            .collect();
+        ColorImage {
+            size: self.grid_size(),
+            pixels,
+        }
+    }
+
+    pub fn show(self, ui: &mut Ui) -> HeatmapResponse {
+        let [width, height] = self.grid_size();
+        let size = self
+            .size
+            .unwrap_or_else(|| self.cell_size * vec2(width as f32, height as f32));
+        let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+
+        let hovered = response.hover_pos().and_then(|pos| {
+            if width == 0 || height == 0 {
+                return None;
+            }
+            let x = remap(pos.x, rect.x_range(), 0.0..=width as f32).floor() as usize;
+            let y = remap(pos.y, rect.y_range(), 0.0..=height as f32).floor() as usize;
+            let (x, y) = (x.min(width - 1), y.min(height - 1));
+            Some(HeatmapCell {
+                x,
+                y,
+                value: self.values[y * width + x],
+            })
+        });
+
+        if !ui.is_rect_visible(rect) || width == 0 || height == 0 {
+            return HeatmapResponse { response, hovered };
+        }
+
+        let texture = self.texture(ui, response.id);
+        let uv = Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0));
+        ui.painter().image(texture.id(), rect, uv, Color32::WHITE);
+
+        let response = match hovered {
+            Some(cell) if self.hover_readout => {
+                let cell_size = rect.size() / vec2(width as f32, height as f32);
+                let cell_rect = Rect::from_min_size(
+                    rect.min + cell_size * vec2(cell.x as f32, cell.y as f32),
+                    cell_size,
+                );
+                let stroke = Stroke::new(1.0, ui.visuals().strong_text_color());
+                ui.painter()
+                    .rect_stroke(cell_rect, 0.0, stroke, StrokeKind::Outside);
+
+                let value = format_with_decimals_in_range(cell.value as f64, 0..=3);
+                response.on_hover_text_at_pointer(format!("[{}, {}] {value}", cell.x, cell.y))
+            }
+            _ => response,
+        };
+
+        HeatmapResponse { response, hovered }
+    }
+
+    /// The texture of the values, only re-uploaded when they (or the colors) change.
+    fn texture(&self, ui: &Ui, id: Id) -> TextureHandle {
+        let id = self.id_salt.map_or(id, |id_salt| ui.id().with(id_salt));
+        let values_hash = epaint::util::hash((self.width, HashedValues(self.grid_values())));
+        let ctx = ui.ctx();
+        let viewport_id = ctx.viewport_id();
+        let pass_nr = ctx.cumulative_pass_nr();
+
+        let cached = HeatmapTextures::get(ctx, id);
+        let texture = match cached {
+            Some(cached)
+                if cached.values_hash == values_hash
+                    && cached.colormap == self.colormap
+                    && cached.range == self.range =>
+            {
+                cached.texture
+            }
+            Some(mut cached) => {
+                cached.texture.set(self.to_image(), TextureOptions::NEAREST);
+                cached.texture
+            }
+            None => ctx.load_texture(format!("{id:?}"), self.to_image(), TextureOptions::NEAREST),
+        };
+
+        HeatmapTextures::insert(
+            ctx,
+            id,
+            CachedTexture {
+                values_hash,
+                colormap: self.colormap.clone(),
+                range: self.range,
+                texture: texture.clone(),
+                viewport_id,
+                last_used_pass: pass_nr,
+            },
+        );
+        texture
+    }
+}
+
+/// Hashes the bits of the values, without collecting them first.
+struct HashedValues<'a>(&'a [f32]);
+
+impl std::hash::Hash for HashedValues<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for value in self.0 {
+            value.to_bits().hash(state);
+        }
+    }
+}
+
+#[derive(Clone)]
+struct CachedTexture {
+    values_hash: u64,
+    colormap: Colormap,
+    range: Option<Rangef>,
+    texture: TextureHandle,
+
+    /// When it was last shown, and where.
+    viewport_id: ViewportId,
+    last_used_pass: u64,
+}
+
+/// The textures of all heatmaps, so that we can free those that are no longer shown.
+#[derive(Clone, Default)]
+struct HeatmapTextures(IdMap<CachedTexture>);
+
+impl HeatmapTextures {
+    fn get(ctx: &Context, id: Id) -> Option<CachedTexture> {
+        ctx.data(|data| data.get_temp::<Self>(Id::NULL)?.0.get(&id).cloned())
+    }
+
+    fn insert(ctx: &Context, id: Id, texture: CachedTexture) {
+        let first = ctx.data_mut(|data| {
+            let first = data.get_temp::<Self>(Id::NULL).is_none();
+            data.get_temp_mut_or_default::<Self>(Id::NULL)
+                .0
+                .insert(id, texture);
+            first
+        });
+        if first {
+            // Freeing is idempotent, so registering again (e.g. after the memory was reset) is fine.
+            ctx.on_end_pass("heatmap_textures", Arc::new(Self::end_pass));
+        }
+    }
+
+    /// Free the textures of the heatmaps not shown in this pass.
+    fn end_pass(ctx: &Context) {
+        let viewport_id = ctx.viewport_id();
+        let pass_nr = ctx.cumulative_pass_nr();
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_default::<Self>(Id::NULL)
+                .0
+                .retain(|_, texture| {
+                    texture.viewport_id != viewport_id || texture.last_used_pass == pass_nr
+                });
+        });
+    }
+}
+
+#[test]
+fn test_heatmap_to_image() {
+    let values = [0.0, 1.0, 2.0, f32::NAN, 4.0, 5.0, 6.0];
+    let heatmap = Heatmap::new(&values, 3);
+    let image = heatmap.to_image();
+    assert_eq!(image.size, [3, 2]);
+    assert_eq!(image.pixels[0], Colormap::Viridis.color_at(0.0));
+    assert_eq!(image.pixels[3], Color32::TRANSPARENT);
+    assert_eq!(image.pixels[5], Colormap::Viridis.color_at(1.0));
+
+    let image = heatmap.range(0.0..=10.0).to_image();
+    assert_eq!(image.pixels[5], Colormap::Viridis.color_at(0.5));
+
+    let flat = [3.0; 4];
+    let image = Heatmap::new(&flat, 2).to_image();
+    assert_eq!(image.pixels[0], Colormap::Viridis.color_at(0.5));
+}
+
+#[test]
+fn test_heatmap_texture_is_reused_and_freed() {
+    let ctx = Context::default();
+    let run = |values: Option<&[f32]>, colormap: Colormap| {
+        let output = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                if let Some(values) = values {
+                    let _ = Heatmap::new(values, 2).colormap(colormap.clone()).show(ui);
+                }
+            });
+        });
+        (
+            ctx.tex_manager().read().allocated().len(),
+            output.textures_delta.set.len(),
+        )
+    };
+
+    let (without, _) = run(None, Colormap::Viridis);
+    let values = [0.0, 1.0, 2.0, 3.0];
+    assert_eq!(run(Some(&values), Colormap::Viridis), (without + 1, 1));
+    assert_eq!(
+        run(Some(&values), Colormap::Viridis),
+        (without + 1, 0),
+        "Reused"
+    );
+    assert_eq!(
+        run(Some(&values), Colormap::Magma),
+        (without + 1, 1),
+        "Recolored"
+    );
+    assert_eq!(run(None, Colormap::Magma).0, without, "Freed");
+}
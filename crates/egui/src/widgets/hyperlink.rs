@@ -1,6 +1,6 @@
 use crate::{
-    epaint, text_selection, CursorIcon, Label, Response, Sense, Stroke, Ui, Widget, WidgetInfo,
-    WidgetText, WidgetType,
+    epaint, text_selection, ColorRole, CursorIcon, Label, Response, Sense, Stroke, Ui, Widget,
+    WidgetInfo, WidgetText, WidgetType,
 };
 
 use self::text_selection::LabelSelectionState;
@@ -44,7 +44,7 @@ impl Widget for Link {
             .widget_info(|| WidgetInfo::labeled(WidgetType::Link, ui.is_enabled(), galley.text()));
 
         if ui.is_rect_visible(response.rect) {
-            let color = ui.visuals().hyperlink_color;
+            let color = ui.visuals().color(ColorRole::Hyperlink);
             let visuals = ui.style().interact(&response);
 
             let underline = if response.hovered() || response.has_focus() {
@@ -3,13 +3,13 @@ use std::{borrow::Cow, slice::Iter, sync::Arc, time::Duration};
 use emath::{Align, Float as _, Rot2};
 use epaint::{
     text::{LayoutJob, TextFormat, TextWrapping},
-    RectShape,
+    MarginF32, RectShape,
 };
 
 use crate::{
     load::{Bytes, SizeHint, SizedTexture, TextureLoadResult, TexturePoll},
-    pos2, Color32, Context, CornerRadius, Id, Mesh, Painter, Rect, Response, Sense, Shape, Spinner,
-    TextStyle, TextureOptions, Ui, Vec2, Widget, WidgetInfo, WidgetType,
+    pos2, Color32, ColorRole, Context, CornerRadius, Id, Mesh, Painter, Rect, Response, Sense,
+    Shape, Spinner, TextStyle, TextureOptions, Ui, Vec2, Widget, WidgetInfo, WidgetType,
 };
 
 /// A widget which displays an image.
@@ -55,6 +55,7 @@ pub struct Image<'a> {
     size: ImageSize,
     pub(crate) show_loading_spinner: Option<bool>,
     alt_text: Option<String>,
+    animate: bool,
 }
 
 impl<'a> Image<'a> {
@@ -81,6 +82,7 @@ impl<'a> Image<'a> {
                 size,
                 show_loading_spinner: None,
                 alt_text: None,
+                animate: true,
             }
         }
 
@@ -237,6 +239,20 @@ impl<'a> Image<'a> {
         self
     }
 
+    /// Draw the image as a "nine-slice" (9-patch), for skinned buttons, panels etc.
+    ///
+    /// The margins are the size of the borders of the texture, in the same unit as the
+    /// texture size (usually texels). The corners are drawn at that size,
+    /// the edges are stretched along their length, and the center is stretched to fill the rest.
+    ///
+    /// Usually combined with [`Self::fit_to_exact_size`] or [`Self::paint_at`].
+    /// Rounding ([`Self::corner_radius`]) is ignored for nine-slice images.
+    #[inline]
+    pub fn nine_slice(mut self, margins: impl Into<MarginF32>) -> Self {
+        self.image_options.nine_slice = Some(margins.into());
+        self
+    }
+
     /// Round the corners of the image.
     ///
     /// The default is no rounding ([`CornerRadius::ZERO`]).
@@ -273,6 +289,15 @@ impl<'a> Image<'a> {
         self
     }
 
+    /// Play animated images (GIF, WebP, APNG).
+    ///
+    /// If `false`, the first frame is shown. Default: `true`.
+    #[inline]
+    pub fn animate(mut self, animate: bool) -> Self {
+        self.animate = animate;
+        self
+    }
+
     /// Set alt text for the image. This will be shown when the image fails to load.
     /// It will also be read to screen readers.
     #[inline]
@@ -331,15 +356,19 @@ impl<'a> Image<'a> {
     #[inline]
     pub fn source(&'a self, ctx: &Context) -> ImageSource<'a> {
         match &self.source {
-            ImageSource::Uri(uri) if is_animated_image_uri(uri) => {
-                let frame_uri =
-                    encode_animated_image_uri(uri, animated_image_frame_index(ctx, uri));
+            ImageSource::Uri(uri) if is_animated_image_uri(uri) || is_known_animation(ctx, uri) => {
+                let frame_uri = encode_animated_image_uri(
+                    uri,
+                    animated_image_frame_index(ctx, uri, self.animate),
+                );
                 ImageSource::Uri(Cow::Owned(frame_uri))
             }
 
             ImageSource::Bytes { uri, bytes } if are_animated_image_bytes(bytes) => {
-                let frame_uri =
-                    encode_animated_image_uri(uri, animated_image_frame_index(ctx, uri));
+                let frame_uri = encode_animated_image_uri(
+                    uri,
+                    animated_image_frame_index(ctx, uri, self.animate),
+                );
                 ctx.include_bytes(uri.clone(), bytes.clone());
                 ImageSource::Uri(Cow::Owned(frame_uri))
             }
@@ -657,7 +686,7 @@ pub fn paint_texture_load_result(
             job.append(
                 "⚠",
                 0.0,
-                TextFormat::simple(font_id.clone(), ui.visuals().error_fg_color),
+                TextFormat::simple(font_id.clone(), ui.visuals().color(ColorRole::Error)),
             );
             if let Some(alt) = alt {
                 job.append(
@@ -795,6 +824,9 @@ pub struct ImageOptions {
     /// Due to limitations in the current implementation,
     /// this will turn off any rotation of the image.
     pub corner_radius: CornerRadius,
+
+    /// Draw the image as a "nine-slice" with these border sizes (in texels), see [`Image::nine_slice`].
+    pub nine_slice: Option<MarginF32>,
 }
 
 impl Default for ImageOptions {
@@ -805,6 +837,7 @@ impl Default for ImageOptions {
             tint: Color32::WHITE,
             rotation: None,
             corner_radius: CornerRadius::ZERO,
+            nine_slice: None,
         }
     }
 }
@@ -823,6 +856,24 @@ pub fn paint_texture_at(
         ));
     }
 
+    if let Some(margins) = options.nine_slice {
+        // The margins are in texels, so convert them to UV coordinates:
+        let uv_per_texel = options.uv.size() / texture.size.max(Vec2::splat(1.0));
+        let uv_margins = MarginF32 {
+            left: margins.left * uv_per_texel.x,
+            right: margins.right * uv_per_texel.x,
+            top: margins.top * uv_per_texel.y,
+            bottom: margins.bottom * uv_per_texel.y,
+        };
+        let mut mesh = Mesh::with_texture(texture.id);
+        mesh.add_nine_slice(rect, options.uv, margins, uv_margins, options.tint);
+        if let Some((rot, origin)) = options.rotation {
+            mesh.rotate(rot, rect.min + origin * rect.size());
+        }
+        painter.add(Shape::mesh(mesh));
+        return;
+    }
+
     match options.rotation {
         Some((rot, origin)) => {
             // TODO(emilk): implement this using `PathShape` (add texture support to it).
@@ -847,7 +898,11 @@ pub fn paint_texture_at(
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
-/// Stores the durations between each frame of an animated image
+/// Stores the durations between each frame of an animated image.
+///
+/// Image loaders store this in [`crate::Context::data`] under `Id::new(uri)` when they load an animation.
+/// That also makes any uri animate, even if it doesn't look like an animated image
+/// (e.g. an animated PNG with a `.png` extension).
 pub struct FrameDurations(Arc<Vec<Duration>>);
 
 impl FrameDurations {
@@ -878,15 +933,30 @@ pub fn decode_animated_image_uri(uri: &str) -> Result<(&str, usize), String> {
     Ok((uri, index))
 }
 
-/// Calculates at which frame the animated image is
-fn animated_image_frame_index(ctx: &Context, uri: &str) -> usize {
-    let now = ctx.input(|input| Duration::from_secs_f64(input.time));
+/// Has a loader found this uri to be an animation?
+fn is_known_animation(ctx: &Context, uri: &str) -> bool {
+    ctx.data(|data| data.get_temp::<FrameDurations>(Id::new(uri)).is_some())
+}
+
+/// Calculates at which frame the animated image is.
+///
+/// Each animation starts at its first frame the first time it is shown.
+fn animated_image_frame_index(ctx: &Context, uri: &str, animate: bool) -> usize {
+    if !animate {
+        return 0;
+    }
 
     let durations: Option<FrameDurations> = ctx.data(|data| data.get_temp(Id::new(uri)));
 
     if let Some(durations) = durations {
+        let now = ctx.input(|input| input.time);
+        let start_time = ctx.data_mut(|data| {
+            *data.get_temp_mut_or_insert_with(Id::new(uri).with("animation_start"), || now)
+        });
+        let elapsed = Duration::from_secs_f64((now - start_time).max(0.0));
+
         let frames: Duration = durations.all().sum();
-        let pos_ms = now.as_millis() % frames.as_millis().max(1);
+        let pos_ms = elapsed.as_millis() % frames.as_millis().max(1);
 
         let mut cumulative_ms = 0;
 
@@ -926,10 +996,73 @@ pub fn has_webp_header(bytes: &[u8]) -> bool {
     bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP"
 }
 
+/// Checks if uri is an animated png file.
+///
+/// Animated PNGs with the normal `.png` extension are found when they are loaded instead,
+/// see [`FrameDurations`].
+fn is_apng_uri(uri: &str) -> bool {
+    uri.ends_with(".apng") || uri.contains(".apng#")
+}
+
+/// Checks if bytes are an animated png, i.e. have an `acTL` chunk before the image data.
+pub fn has_apng_header(bytes: &[u8]) -> bool {
+    const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    if !bytes.starts_with(PNG_SIGNATURE) {
+        return false;
+    }
+
+    let mut chunks = &bytes[PNG_SIGNATURE.len()..];
+    while chunks.len() >= 8 {
+        let length = u32::from_be_bytes([chunks[0], chunks[1], chunks[2], chunks[3]]) as usize;
+        match &chunks[4..8] {
+            b"acTL" => return true,
+            b"IDAT" => return false,
+            _ => {}
+        }
+        // Skip length, type, data and crc:
+        let Some(rest) = chunks.get(12 + length..) else {
+            return false;
+        };
+        chunks = rest;
+    }
+    false
+}
+
 fn is_animated_image_uri(uri: &str) -> bool {
-    is_gif_uri(uri) || is_webp_uri(uri)
+    is_gif_uri(uri) || is_webp_uri(uri) || is_apng_uri(uri)
 }
 
 fn are_animated_image_bytes(bytes: &[u8]) -> bool {
-    has_gif_magic_header(bytes) || has_webp_header(bytes)
+    has_gif_magic_header(bytes) || has_webp_header(bytes) || has_apng_header(bytes)
+}
+
+#[test]
+fn test_has_apng_header() {
+    fn chunk(kind: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(kind);
+        chunk.extend_from_slice(data);
+        chunk.extend_from_slice(&[0; 4]); // crc
+        chunk
+    }
+
+    let png = |chunks: &[Vec<u8>]| {
+        let mut bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+        for chunk in chunks {
+            bytes.extend_from_slice(chunk);
+        }
+        bytes
+    };
+
+    let ihdr = chunk(b"IHDR", &[0; 13]);
+    let actl = chunk(b"acTL", &[0; 8]);
+    let idat = chunk(b"IDAT", &[1, 2, 3]);
+    assert!(has_apng_header(&png(&[
+        ihdr.clone(),
+        actl.clone(),
+        idat.clone()
+    ])));
+    assert!(!has_apng_header(&png(&[ihdr.clone(), idat.clone(), actl])));
+    assert!(!has_apng_header(&png(&[ihdr, idat])));
+    assert!(!has_apng_header(b"GIF89a"));
 }
@@ -0,0 +1,606 @@
+use epaint::{
+    ClippedShape, ColorImage, FontImage, Fonts, LineCap, LineJoin, Mesh, Primitive, RectShape,
+};
+
+use crate::{
+    containers::Scene, load::SizedTexture, pos2, vec2, Color32, Context, FontId, Id, Pos2, Rect,
+    Response, Sense, Shape, Stroke, StrokeKind, TextEdit, Ui, Vec2,
+};
+
+/// What dragging on an [`ImageAnnotator`] does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum AnnotationTool {
+    /// Pan the image. Scrolling pans, and ctrl/cmd-scrolling zooms, with any tool.
+    Pan,
+
+    /// Draw freehand, e.g. to sign.
+    #[default]
+    Ink,
+
+    /// Drag from the tail to the tip of an arrow.
+    Arrow,
+
+    /// Drag from one corner to the other.
+    Rect,
+
+    /// Drag from what to point at to where the text goes, or click, then type.
+    Callout,
+}
+
+impl AnnotationTool {
+    pub const ALL: [Self; 5] = [Self::Pan, Self::Ink, Self::Arrow, Self::Rect, Self::Callout];
+
+    /// A short name, e.g. for a toolbar.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Pan => "✋ Pan",
+            Self::Ink => "✏ Ink",
+            Self::Arrow => "➡ Arrow",
+            Self::Rect => "⬜ Rect",
+            Self::Callout => "💬 Callout",
+        }
+    }
+}
+
+/// Something drawn on top of an image, in the pixel coordinates of the image.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Annotation {
+    Ink {
+        points: Vec<Pos2>,
+        stroke: Stroke,
+    },
+    Arrow {
+        from: Pos2,
+        to: Pos2,
+        stroke: Stroke,
+    },
+    Rect {
+        rect: Rect,
+        stroke: Stroke,
+    },
+
+    /// Text in a box, with a line to what it is about.
+    Callout {
+        /// What the callout points at.
+        anchor: Pos2,
+
+        /// The top left corner of the text box.
+        pos: Pos2,
+        text: String,
+        color: Color32,
+        font_size: f32,
+    },
+}
+
+impl Annotation {
+    /// Paint the annotation, in the coordinates of the image.
+    pub fn to_shape(&self, fonts: &Fonts) -> Shape {
+        match self {
+            Self::Ink { points, stroke } => {
+                let stroke = stroke
+                    .with_line_cap(LineCap::Round)
+                    .with_line_join(LineJoin::Round);
+                if let [point] = points.as_slice() {
+                    Shape::circle_filled(*point, 0.5 * stroke.width, stroke.color)
+                } else {
+                    Shape::line(points.clone(), stroke)
+                }
+            }
+            Self::Arrow { from, to, stroke } => arrow(*from, *to, *stroke),
+            Self::Rect { rect, stroke } => {
+                Shape::rect_stroke(*rect, 0.0, *stroke, StrokeKind::Middle)
+            }
+            Self::Callout {
+                anchor,
+                pos,
+                text,
+                color,
+                font_size,
+            } => {
+                let galley =
+                    fonts.layout_no_wrap(text.clone(), FontId::proportional(*font_size), *color);
+                let text_box = Rect::from_min_size(*pos, galley.size()).expand(0.3 * font_size);
+                Shape::Vec(vec![
+                    callout_leader(*anchor, text_box, *color, *font_size),
+                    RectShape::new(
+                        text_box,
+                        0.2 * font_size,
+                        Color32::WHITE.gamma_multiply(0.9),
+                        Stroke::new(0.1 * font_size, *color),
+                        StrokeKind::Inside,
+                    )
+                    .into(),
+                    Shape::galley(*pos, galley, *color),
+                ])
+            }
+        }
+    }
+}
+
+/// A line from `from` to `to` with a head at `to`.
+fn arrow(from: Pos2, to: Pos2, stroke: Stroke) -> Shape {
+    let stroke = stroke
+        .with_line_cap(LineCap::Round)
+        .with_line_join(LineJoin::Round);
+    let vec = to - from;
+    let head_length = (4.0 * stroke.width).max(8.0).min(0.5 * vec.length());
+    let back = -head_length * vec.normalized();
+    let rot = emath::Rot2::from_angle(std::f32::consts::TAU / 12.0);
+    Shape::Vec(vec![
+        Shape::line_segment([from, to], stroke),
+        Shape::line(vec![to + rot * back, to, to + rot.inverse() * back], stroke),
+    ])
+}
+
+/// A line from the anchor of a callout to the edge of its text box, with a dot at the anchor.
+fn callout_leader(anchor: Pos2, text_box: Rect, color: Color32, font_size: f32) -> Shape {
+    let stroke = Stroke::new(0.1 * font_size, color).with_line_cap(LineCap::Round);
+    Shape::Vec(vec![
+        Shape::line_segment([anchor, text_box.clamp(anchor)], stroke),
+        Shape::circle_filled(anchor, 0.2 * font_size, color),
+    ])
+}
+
+/// The annotations of an image, with undo and redo.
+///
+/// Store this in your app state, and pass it to [`ImageAnnotator::show`].
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Annotations {
+    items: Vec<Annotation>,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    undos: Vec<Vec<Annotation>>,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    redos: Vec<Vec<Annotation>>,
+}
+
+impl Annotations {
+    /// All annotations, in the order they were added.
+    pub fn items(&self) -> &[Annotation] {
+        &self.items
+    }
+
+    /// Add an annotation on top of the others.
+    pub fn push(&mut self, annotation: Annotation) {
+        self.edit(|items| items.push(annotation));
+    }
+
+    /// Remove all annotations. Can be undone.
+    pub fn clear(&mut self) {
+        if !self.items.is_empty() {
+            self.edit(Vec::clear);
+        }
+    }
+
+    /// Change the annotations in a way that can be undone.
+    pub fn edit(&mut self, edit: impl FnOnce(&mut Vec<Annotation>)) {
+        self.undos.push(self.items.clone());
+        self.redos.clear();
+        edit(&mut self.items);
+    }
+
+    pub fn has_undo(&self) -> bool {
+        !self.undos.is_empty()
+    }
+
+    pub fn has_redo(&self) -> bool {
+        !self.redos.is_empty()
+    }
+
+    pub fn undo(&mut self) {
+        if let Some(items) = self.undos.pop() {
+            self.redos.push(std::mem::replace(&mut self.items, items));
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(items) = self.redos.pop() {
+            self.undos.push(std::mem::replace(&mut self.items, items));
+        }
+    }
+
+    /// The annotations as shapes, in the pixel coordinates of the image.
+    ///
+    /// Use [`Shape::transform`] to put them somewhere else, e.g. in a document you are exporting.
+    pub fn to_shapes(&self, ctx: &Context) -> Vec<Shape> {
+        ctx.fonts(|fonts| {
+            self.items
+                .iter()
+                .map(|annotation| annotation.to_shape(fonts))
+                .collect()
+        })
+    }
+
+    /// Paint the annotations into a copy of `image`.
+    ///
+    /// This is done on the CPU, with the anti-aliasing egui uses on screen.
+    pub fn burn_in(&self, ctx: &Context, image: &ColorImage) -> ColorImage {
+        let mut image = image.clone();
+        let [width, height] = image.size;
+        let clip_rect = Rect::from_min_size(Pos2::ZERO, vec2(width as f32, height as f32));
+
+        // One point per pixel, just like the annotations:
+        let shapes = self
+            .to_shapes(ctx)
+            .into_iter()
+            .map(|shape| ClippedShape { clip_rect, shape })
+            .collect();
+        let primitives = ctx.tessellator(1.0).tessellate_shapes(shapes);
+
+        let font_image = ctx.fonts(|fonts| fonts.image());
+        for primitive in primitives {
+            if let Primitive::Mesh(mesh) = primitive.primitive {
+                paint_mesh(&mut image, &mesh, &font_image);
+            }
+        }
+        image
+    }
+}
+
+/// Rasterize the triangles of `mesh` onto `image`, which is in the same coordinates.
+///
+/// The mesh is assumed to only use the font texture, like text and tessellated shapes do.
+fn paint_mesh(image: &mut ColorImage, mesh: &Mesh, font_image: &FontImage) {
+    let [width, height] = image.size;
+    let coverage_at = |uv: Pos2| {
+        let [tex_width, tex_height] = font_image.size;
+        let x = ((uv.x * tex_width as f32) as usize).min(tex_width - 1);
+        let y = ((uv.y * tex_height as f32) as usize).min(tex_height - 1);
+        font_image.pixels[y * tex_width + x]
+    };
+
+    for triangle in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| &mesh.vertices[triangle[i] as usize]);
+        let area = cross(b.pos - a.pos, c.pos - a.pos);
+        if area == 0.0 {
+            continue;
+        }
+
+        let bounds = Rect::from_points(&[a.pos, b.pos, c.pos]);
+        let x_range = (bounds.min.x.floor().max(0.0) as usize)
+            ..(bounds.max.x.ceil().max(0.0) as usize).min(width);
+        let y_range = (bounds.min.y.floor().max(0.0) as usize)
+            ..(bounds.max.y.ceil().max(0.0) as usize).min(height);
+
+        for y in y_range {
+            for x in x_range.clone() {
+                let p = pos2(x as f32 + 0.5, y as f32 + 0.5);
+                // Barycentric coordinates, all positive inside the triangle:
+                let wa = cross(c.pos - b.pos, p - b.pos) / area;
+                let wb = cross(a.pos - c.pos, p - c.pos) / area;
+                let wc = 1.0 - wa - wb;
+                if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                    continue;
+                }
+
+                let uv = pos2(
+                    wa * a.uv.x + wb * b.uv.x + wc * c.uv.x,
+                    wa * a.uv.y + wb * b.uv.y + wc * c.uv.y,
+                );
+                let coverage = coverage_at(uv);
+                let src = [0, 1, 2, 3].map(|i| {
+                    let channel = |color: Color32| color.to_array()[i] as f32;
+                    coverage
+                        * (wa * channel(a.color) + wb * channel(b.color) + wc * channel(c.color))
+                });
+
+                // Blend the premultiplied colors:
+                let dst = &mut image.pixels[y * width + x];
+                let keep = 1.0 - src[3] / 255.0;
+                let [r, g, b, a] = [0, 1, 2, 3].map(|i| {
+                    (src[i] + keep * dst.to_array()[i] as f32)
+                        .round()
+                        .clamp(0.0, 255.0) as u8
+                });
+                *dst = Color32::from_rgba_premultiplied(r, g, b, a);
+            }
+        }
+    }
+}
+
+/// The z component of the cross product, i.e. twice the signed area of the triangle spanned by `a` and `b`.
+fn cross(a: Vec2, b: Vec2) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+/// What an [`ImageAnnotator`] remembers between frames.
+#[derive(Clone, Debug)]
+struct AnnotatorState {
+    /// The part of the image shown, in image pixels.
+    scene_rect: Rect,
+
+    /// The annotation being drawn.
+    drawing: Option<Annotation>,
+
+    /// Where the pointer was when the annotation being drawn was started.
+    drag_start: Pos2,
+
+    /// The annotation being drawn is a callout, and its text is being typed.
+    typing: bool,
+}
+
+impl Default for AnnotatorState {
+    fn default() -> Self {
+        Self {
+            // Makes the scene fit the image:
+            scene_rect: Rect::ZERO,
+            drawing: None,
+            drag_start: Pos2::ZERO,
+            typing: false,
+        }
+    }
+}
+
+/// Draw on top of an image: ink (e.g. a signature), arrows, rectangles and text callouts.
+///
+/// The image can be panned and zoomed.
+/// The annotations are stored in [`Annotations`], which can be undone,
+/// exported as shapes, or burned into the image.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let texture = ui.ctx().load_texture("photo", egui::ColorImage::new([64, 64], egui::Color32::GRAY), Default::default());
+/// # let mut annotations = egui::Annotations::default();
+/// # let mut tool = egui::AnnotationTool::default();
+/// ui.horizontal(|ui| {
+///     for option in egui::AnnotationTool::ALL {
+///         ui.selectable_value(&mut tool, option, option.label());
+///     }
+///     if ui.add_enabled(annotations.has_undo(), egui::Button::new("Undo")).clicked() {
+///         annotations.undo();
+///     }
+/// });
+/// egui::ImageAnnotator::new("annotator", &texture)
+///     .tool(tool)
+///     .show(ui, &mut annotations);
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct ImageAnnotator {
+    id_salt: Id,
+    texture: SizedTexture,
+    tool: AnnotationTool,
+    stroke: Stroke,
+    font_size: f32,
+}
+
+impl ImageAnnotator {
+    pub fn new(id_salt: impl std::hash::Hash, texture: impl Into<SizedTexture>) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            texture: texture.into(),
+            tool: AnnotationTool::default(),
+            stroke: Stroke::new(3.0, Color32::RED),
+            font_size: 16.0,
+        }
+    }
+
+    /// Default: [`AnnotationTool::Ink`].
+    #[inline]
+    pub fn tool(mut self, tool: AnnotationTool) -> Self {
+        self.tool = tool;
+        self
+    }
+
+    /// The stroke of new annotations, in image pixels. Callouts use its color.
+    ///
+    /// Default: 3 pixels of red.
+    #[inline]
+    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// The size of the text of new callouts, in image pixels.
+    ///
+    /// Default: `16.0`.
+    #[inline]
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Uses all of the available space.
+    ///
+    /// Double-click with [`AnnotationTool::Pan`] to fit the image.
+    pub fn show(self, ui: &mut Ui, annotations: &mut Annotations) -> Response {
+        let id = ui.make_persistent_id(self.id_salt);
+        let mut state: AnnotatorState = ui.data(|data| data.get_temp(id)).unwrap_or_default();
+
+        let image_rect = Rect::from_min_size(Pos2::ZERO, self.texture.size);
+        let scene = Scene::new()
+            .zoom_range(0.0..=16.0)
+            .fit_on_double_click(true);
+        let mut scene_rect = state.scene_rect;
+        let response = scene
+            .show(ui, &mut scene_rect, |ui| {
+                let sense = if self.tool == AnnotationTool::Pan {
+                    Sense::hover()
+                } else {
+                    Sense::click_and_drag()
+                };
+                let response = ui.interact(image_rect, id.with("image"), sense);
+                ui.advance_cursor_after_rect(image_rect);
+
+                let painter = ui.painter_at(image_rect);
+                let uv = Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0));
+                painter.image(self.texture.id, image_rect, uv, Color32::WHITE);
+                let shapes = annotations.to_shapes(ui.ctx());
+                painter.extend(shapes);
+
+                self.draw(ui, &response, &mut state, annotations);
+                response
+            })
+            .inner;
+
+        state.scene_rect = scene_rect;
+        ui.data_mut(|data| data.insert_temp(id, state));
+        response
+    }
+
+    /// Draw new annotations with the current tool.
+    fn draw(
+        &self,
+        ui: &mut Ui,
+        response: &Response,
+        state: &mut AnnotatorState,
+        annotations: &mut Annotations,
+    ) {
+        let pointer = response.interact_pointer_pos();
+        if let (Some(pos), false) = (pointer, state.typing) {
+            let stroke = self.stroke;
+            if response.drag_started()
+                || (response.clicked() && self.tool == AnnotationTool::Callout)
+            {
+                state.drag_start = pos;
+                state.drawing = match self.tool {
+                    AnnotationTool::Pan => None,
+                    AnnotationTool::Ink => Some(Annotation::Ink {
+                        points: vec![pos],
+                        stroke,
+                    }),
+                    AnnotationTool::Arrow => Some(Annotation::Arrow {
+                        from: pos,
+                        to: pos,
+                        stroke,
+                    }),
+                    AnnotationTool::Rect => Some(Annotation::Rect {
+                        rect: Rect::from_two_pos(pos, pos),
+                        stroke,
+                    }),
+                    AnnotationTool::Callout => Some(Annotation::Callout {
+                        anchor: pos,
+                        pos: pos + vec2(1.0, -2.0) * self.font_size,
+                        text: String::new(),
+                        color: stroke.color,
+                        font_size: self.font_size,
+                    }),
+                };
+            }
+
+            match &mut state.drawing {
+                Some(Annotation::Ink { points, .. })
+                    if points.last().map_or(true, |last| 1.0 <= last.distance(pos)) =>
+                {
+                    points.push(pos);
+                }
+                Some(Annotation::Arrow { from, to, .. }) => {
+                    *to = pos;
+                    if response.drag_stopped() && from.distance(*to) < 2.0 {
+                        state.drawing = None;
+                    }
+                }
+                Some(Annotation::Rect { rect, .. }) => {
+                    *rect = Rect::from_two_pos(state.drag_start, pos);
+                }
+                Some(Annotation::Callout {
+                    anchor,
+                    pos: text_pos,
+                    ..
+                }) if response.dragged() && 2.0 <= anchor.distance(pos) => {
+                    *text_pos = pos;
+                }
+                _ => {}
+            }
+
+            if response.drag_stopped() || response.clicked() {
+                if matches!(state.drawing, Some(Annotation::Callout { .. })) {
+                    state.typing = true;
+                } else if let Some(annotation) = state.drawing.take() {
+                    annotations.push(annotation);
+                }
+            }
+        }
+
+        if state.typing {
+            let mut done_typing = None;
+            if let Some(Annotation::Callout {
+                anchor,
+                pos,
+                text,
+                color,
+                font_size,
+            }) = &mut state.drawing
+            {
+                let font_id = FontId::proportional(*font_size);
+                let text_rect = Rect::from_min_size(*pos, vec2(12.0 * *font_size, *font_size));
+                let text_box = text_rect.expand(0.3 * *font_size);
+                ui.painter()
+                    .add(callout_leader(*anchor, text_box, *color, *font_size));
+                let edit = ui.put(
+                    text_rect,
+                    TextEdit::singleline(text)
+                        .font(font_id)
+                        .text_color(*color)
+                        .hint_text("Type, then press enter"),
+                );
+                if !edit.has_focus() && !edit.lost_focus() {
+                    edit.request_focus();
+                }
+                if edit.lost_focus() {
+                    done_typing = Some(!text.is_empty());
+                }
+            }
+
+            if let Some(keep) = done_typing {
+                // An empty callout is dropped:
+                state.typing = false;
+                if let Some(callout) = state.drawing.take().filter(|_| keep) {
+                    annotations.push(callout);
+                }
+            }
+        } else if let Some(drawing) = &state.drawing {
+            let shape = ui.ctx().fonts(|fonts| drawing.to_shape(fonts));
+            ui.painter().add(shape);
+        }
+    }
+}
+
+#[test]
+fn test_annotations_undo() {
+    let dot = |x: f32| Annotation::Ink {
+        points: vec![pos2(x, 0.0)],
+        stroke: Stroke::new(1.0, Color32::RED),
+    };
+
+    let mut annotations = Annotations::default();
+    annotations.push(dot(1.0));
+    annotations.push(dot(2.0));
+    annotations.undo();
+    assert_eq!(annotations.items(), &[dot(1.0)]);
+    annotations.redo();
+    assert_eq!(annotations.items(), &[dot(1.0), dot(2.0)]);
+
+    annotations.clear();
+    assert!(annotations.items().is_empty());
+    annotations.undo();
+    assert_eq!(annotations.items().len(), 2);
+
+    // A new edit forgets what was undone:
+    annotations.undo();
+    annotations.push(dot(3.0));
+    assert!(!annotations.has_redo());
+    assert_eq!(annotations.items(), &[dot(1.0), dot(3.0)]);
+}
+
+#[test]
+fn test_burn_in() {
+    let ctx = Context::default();
+    let _ = ctx.run(Default::default(), |_| {});
+
+    let mut annotations = Annotations::default();
+    annotations.push(Annotation::Rect {
+        rect: Rect::from_min_max(pos2(4.0, 4.0), pos2(12.0, 12.0)),
+        stroke: Stroke::new(4.0, Color32::RED),
+    });
+    let image = ColorImage::new([16, 16], Color32::BLACK);
+    let burned = annotations.burn_in(&ctx, &image);
+
+    assert_eq!(burned.pixels[16 * 8 + 4], Color32::RED, "on the left edge");
+    assert_eq!(burned.pixels[16 * 8 + 8], Color32::BLACK, "inside");
+    assert_eq!(burned.pixels[0], Color32::BLACK, "outside");
+}
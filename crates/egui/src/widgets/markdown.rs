@@ -0,0 +1,436 @@
+use std::iter::Peekable;
+
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+use crate::{
+    text::LayoutJob, Align, Checkbox, ColorRole, Frame, Grid, Id, Image, Label, Layout, Response,
+    RichText, ScrollArea, TextStyle, TextWrapMode, Ui, Widget, WidgetText,
+};
+
+/// Turns the source of a code block and its language (e.g. `"rust"`, or empty) into colored text.
+type CodeHighlighter<'a> = Box<dyn Fn(&Ui, &str, &str) -> LayoutJob + 'a>;
+
+/// Shows markdown text: headings, emphasis, lists, tables, code blocks, links and images.
+///
+/// Images are loaded with the [loader system](crate::load),
+/// so you need to install loaders for the kinds of images you use,
+/// e.g. with `egui_extras::install_image_loaders`.
+///
+/// Requires the `markdown` feature.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// ui.add(egui::Markdown::new(
+///     "# Help\n\
+///      Press **Ctrl+S** to save, or see [the docs](https://docs.rs/egui).\n\
+///      \n\
+///      * Lists\n\
+///      * and `code`",
+/// ));
+/// # });
+/// ```
+#[must_use = "You should put this widget in a ui with `ui.add(widget);`"]
+pub struct Markdown<'a> {
+    source: &'a str,
+    id_salt: Option<Id>,
+    code_highlighter: Option<CodeHighlighter<'a>>,
+}
+
+impl<'a> Markdown<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            id_salt: None,
+            code_highlighter: None,
+        }
+    }
+
+    /// A source for the unique [`Id`]s of the scroll areas and grids in the markdown,
+    /// e.g. `.id_salt("help")`.
+    ///
+    /// By default the ids come from the position of the markdown in its [`Ui`],
+    /// so set this to keep e.g. how far code blocks are scrolled when other widgets come and go before it.
+    #[inline]
+    pub fn id_salt(mut self, id_salt: impl std::hash::Hash) -> Self {
+        self.id_salt = Some(Id::new(id_salt));
+        self
+    }
+
+    /// Color the code blocks, e.g. with `egui_extras::syntax_highlighting`.
+    ///
+    /// The function is given the source of the code block and its language, which may be empty.
+    /// It is called every frame, so you may want to cache the results.
+    ///
+    /// By default code blocks are shown in plain monospace text.
+    #[inline]
+    pub fn code_highlighter(
+        mut self,
+        code_highlighter: impl Fn(&Ui, &str, &str) -> LayoutJob + 'a,
+    ) -> Self {
+        self.code_highlighter = Some(Box::new(code_highlighter));
+        self
+    }
+}
+
+impl Widget for Markdown<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            source,
+            id_salt,
+            code_highlighter,
+        } = self;
+
+        let options =
+            Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+        let mut renderer = Renderer {
+            events: Parser::new_ext(source, options).peekable(),
+            code_highlighter,
+            style: InlineStyle::default(),
+            link: None,
+            id: id_salt.map_or_else(
+                || ui.next_auto_id(),
+                |id_salt| ui.make_persistent_id(id_salt),
+            ),
+            next_id: 0,
+        };
+        ui.vertical(|ui| renderer.blocks(ui, None)).response
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct InlineStyle {
+    heading: Option<HeadingLevel>,
+    strong: bool,
+    emphasis: bool,
+    strikethrough: bool,
+}
+
+struct Renderer<'a, I: Iterator<Item = Event<'a>>> {
+    events: Peekable<I>,
+    code_highlighter: Option<CodeHighlighter<'a>>,
+    style: InlineStyle,
+
+    /// The url of the link we're in, if any.
+    link: Option<String>,
+
+    /// The ids of scroll areas, grids etc. are made from this, and a counter.
+    id: Id,
+    next_id: usize,
+}
+
+impl<'a, I: Iterator<Item = Event<'a>>> Renderer<'a, I> {
+    fn next_id(&mut self) -> Id {
+        self.next_id += 1;
+        self.id.with(self.next_id)
+    }
+
+    /// Show block-level elements, until the end of `until` (or of the document).
+    fn blocks(&mut self, ui: &mut Ui, until: Option<TagEnd>) {
+        while let Some(event) = self.events.peek() {
+            if is_inline(event) {
+                ui.horizontal_wrapped(|ui| {
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    self.inlines(ui);
+                });
+                continue;
+            }
+
+            let Some(event) = self.events.next() else {
+                break;
+            };
+            match event {
+                Event::End(end) if Some(end) == until => return,
+                Event::Start(Tag::Heading { level, .. }) => self.style.heading = Some(level),
+                Event::End(TagEnd::Heading(_)) => self.style.heading = None,
+                Event::End(TagEnd::Paragraph) => ui.add_space(ui.spacing().item_spacing.y),
+                Event::Start(Tag::BlockQuote(kind)) => {
+                    let response = ui.indent(self.next_id(), |ui| {
+                        self.blocks(ui, Some(TagEnd::BlockQuote(kind)));
+                    });
+                    let rect = response.response.rect;
+                    let x = rect.left() - 0.5 * ui.spacing().indent;
+                    ui.painter().vline(
+                        x,
+                        rect.y_range(),
+                        ui.visuals().widgets.noninteractive.bg_stroke,
+                    );
+                }
+                Event::Start(Tag::CodeBlock(kind)) => {
+                    let language = match kind {
+                        CodeBlockKind::Fenced(language) => language.to_string(),
+                        CodeBlockKind::Indented => String::new(),
+                    };
+                    let mut code = String::new();
+                    for event in self.events.by_ref() {
+                        match event {
+                            Event::Text(text) => code.push_str(&text),
+                            Event::End(TagEnd::CodeBlock) => break,
+                            _ => {}
+                        }
+                    }
+                    self.code_block(ui, code.trim_end_matches('\n'), &language);
+                }
+                Event::Start(Tag::List(first_number)) => self.list(ui, first_number),
+                Event::Start(Tag::Table(_)) => self.table(ui),
+                Event::Rule => {
+                    ui.separator();
+                }
+                Event::Html(html) => {
+                    ui.label(RichText::new(html.trim_end()).weak());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn code_block(&mut self, ui: &mut Ui, code: &str, language: &str) {
+        let text: WidgetText = match &self.code_highlighter {
+            Some(code_highlighter) => code_highlighter(ui, code, language).into(),
+            None => RichText::new(code).monospace().into(),
+        };
+        let id_salt = self.next_id();
+        Frame::new()
+            .fill(ui.visuals().color(ColorRole::CodeBackground))
+            .corner_radius(ui.visuals().widgets.noninteractive.corner_radius)
+            .inner_margin(ui.spacing().button_padding.x)
+            .show(ui, |ui| {
+                ScrollArea::horizontal().id_salt(id_salt).show(ui, |ui| {
+                    ui.add(Label::new(text).wrap_mode(TextWrapMode::Extend));
+                    ui.set_min_width(ui.available_width());
+                });
+            });
+    }
+
+    fn list(&mut self, ui: &mut Ui, first_number: Option<u64>) {
+        let mut number = first_number;
+        while let Some(event) = self.events.next() {
+            match event {
+                Event::Start(Tag::Item) => {
+                    let marker = match &mut number {
+                        Some(number) => {
+                            *number += 1;
+                            format!("{}.", *number - 1)
+                        }
+                        None => "•".to_owned(),
+                    };
+                    ui.with_layout(Layout::left_to_right(Align::TOP), |ui| {
+                        ui.label(marker);
+                        ui.vertical(|ui| self.blocks(ui, Some(TagEnd::Item)));
+                    });
+                }
+                Event::End(TagEnd::List(_)) => break,
+                _ => {}
+            }
+        }
+    }
+
+    fn table(&mut self, ui: &mut Ui) {
+        Grid::new(self.next_id()).striped(true).show(ui, |ui| {
+            while let Some(event) = self.events.next() {
+                match event {
+                    Event::Start(Tag::TableHead) => self.style.strong = true,
+                    Event::End(TagEnd::TableHead) => {
+                        self.style.strong = false;
+                        ui.end_row();
+                    }
+                    Event::End(TagEnd::TableRow) => ui.end_row(),
+                    Event::Start(Tag::TableCell) => {
+                        ui.horizontal_wrapped(|ui| {
+                            ui.spacing_mut().item_spacing.x = 0.0;
+                            self.inlines(ui);
+                        });
+                    }
+                    Event::End(TagEnd::Table) => break,
+                    _ => {}
+                }
+            }
+        });
+    }
+
+    /// Show inline elements, until the next block-level event.
+    fn inlines(&mut self, ui: &mut Ui) {
+        while self.events.peek().is_some_and(is_inline) {
+            let Some(event) = self.events.next() else {
+                break;
+            };
+            match event {
+                Event::Text(text) => {
+                    let text = self.rich_text(ui, &text);
+                    self.text(ui, text);
+                }
+                Event::Code(code) => {
+                    let code = self.rich_text(ui, &code).code();
+                    self.text(ui, code);
+                }
+                Event::InlineMath(math) | Event::DisplayMath(math) => {
+                    let math = self.rich_text(ui, &math).monospace();
+                    self.text(ui, math);
+                }
+                Event::InlineHtml(html) => {
+                    ui.label(RichText::new(html.as_ref()).weak());
+                }
+                Event::FootnoteReference(label) => {
+                    let label = self.rich_text(ui, &format!("[{label}]")).small_raised();
+                    self.text(ui, label);
+                }
+                Event::SoftBreak => {
+                    let space = self.rich_text(ui, " ");
+                    self.text(ui, space);
+                }
+                Event::HardBreak => ui.end_row(),
+                Event::TaskListMarker(mut checked) => {
+                    ui.add_enabled(false, Checkbox::without_text(&mut checked));
+                }
+                Event::Start(Tag::Strong) => self.style.strong = true,
+                Event::End(TagEnd::Strong) => self.style.strong = false,
+                Event::Start(Tag::Emphasis) => self.style.emphasis = true,
+                Event::End(TagEnd::Emphasis) => self.style.emphasis = false,
+                Event::Start(Tag::Strikethrough) => self.style.strikethrough = true,
+                Event::End(TagEnd::Strikethrough) => self.style.strikethrough = false,
+                Event::Start(Tag::Link { dest_url, .. }) => self.link = Some(dest_url.to_string()),
+                Event::End(TagEnd::Link) => self.link = None,
+                Event::Start(Tag::Image { dest_url, .. }) => {
+                    let mut alt_text = String::new();
+                    for event in self.events.by_ref() {
+                        match event {
+                            Event::Text(text) | Event::Code(text) => alt_text.push_str(&text),
+                            Event::End(TagEnd::Image) => break,
+                            _ => {}
+                        }
+                    }
+                    ui.add(
+                        Image::new(dest_url.to_string())
+                            .max_width(ui.max_rect().width())
+                            .alt_text(alt_text),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn rich_text(&self, ui: &Ui, text: &str) -> RichText {
+        let InlineStyle {
+            heading,
+            strong,
+            emphasis,
+            strikethrough,
+        } = self.style;
+
+        let mut rich_text = RichText::new(text);
+        match heading {
+            Some(HeadingLevel::H1) => rich_text = rich_text.heading(),
+            Some(HeadingLevel::H2) => {
+                let heading_size = TextStyle::Heading.resolve(ui.style()).size;
+                rich_text = rich_text.size(0.8 * heading_size).strong();
+            }
+            Some(_) => rich_text = rich_text.strong(),
+            None => {}
+        }
+        if strong {
+            rich_text = rich_text.strong();
+        }
+        if emphasis {
+            rich_text = rich_text.italics();
+        }
+        if strikethrough {
+            rich_text = rich_text.strikethrough();
+        }
+        rich_text
+    }
+
+    /// A piece of text, which is a link if we are in one.
+    fn text(&self, ui: &mut Ui, text: RichText) {
+        if let Some(url) = &self.link {
+            ui.hyperlink_to(text, url);
+        } else {
+            ui.label(text);
+        }
+    }
+}
+
+fn is_inline(event: &Event<'_>) -> bool {
+    match event {
+        Event::Start(tag) => matches!(
+            tag,
+            Tag::Emphasis
+                | Tag::Strong
+                | Tag::Strikethrough
+                | Tag::Superscript
+                | Tag::Subscript
+                | Tag::Link { .. }
+                | Tag::Image { .. }
+        ),
+        Event::End(tag) => matches!(
+            tag,
+            TagEnd::Emphasis
+                | TagEnd::Strong
+                | TagEnd::Strikethrough
+                | TagEnd::Superscript
+                | TagEnd::Subscript
+                | TagEnd::Link
+                | TagEnd::Image
+        ),
+        Event::Text(_)
+        | Event::Code(_)
+        | Event::InlineMath(_)
+        | Event::DisplayMath(_)
+        | Event::InlineHtml(_)
+        | Event::FootnoteReference(_)
+        | Event::SoftBreak
+        | Event::HardBreak
+        | Event::TaskListMarker(_) => true,
+        Event::Html(_) | Event::Rule => false,
+    }
+}
+
+#[test]
+fn test_markdown_renders_everything() {
+    use crate::{CentralPanel, Context, RawInput};
+
+    let source = "# Title\n\
+        Some *text* with [a link](https://example.com).\n\
+        \n\
+        1. First\n\
+        2. Second, with\n   * a nested [link](https://example.org)\n\
+        \n\
+        > Quoted\n\
+        \n\
+        | A | B |\n\
+        |---|---|\n\
+        | 1 | [2](https://example.net) |\n\
+        \n\
+        ```rust\nfn main() {}\n```\n\
+        ---\n\
+        The end";
+
+    let ctx = Context::default();
+    let mut rect = None;
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            rect = Some(ui.add(Markdown::new(source)).rect);
+        });
+    });
+
+    // Every block got its own row:
+    let row_height = ctx.style().text_styles[&TextStyle::Body].size;
+    assert!(10.0 * row_height < rect.unwrap().height());
+}
+
+#[test]
+fn test_markdown_ids_do_not_clash() {
+    use crate::{CentralPanel, Context, RawInput};
+
+    let source = "```\nfn main() {}\n```\n\n| A | B |\n|---|---|\n| 1 | 2 |";
+    let ctx = Context::default();
+    for _ in 0..2 {
+        let _ = ctx.run(RawInput::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.add(Markdown::new(source));
+                ui.add(Markdown::new(source));
+                ui.add(Markdown::new(source).id_salt("help"));
+            });
+        });
+    }
+    assert!(ctx.prev_pass_state(|state| state.id_clashes.is_empty()));
+}
@@ -0,0 +1,237 @@
+use crate::{
+    containers::scene::fit_to_rect_in_scene, emath::TSTransform, epaint::ClippedShape, Color32,
+    Rangef, Rect, Response, Sense, Stroke, StrokeKind, Ui, Vec2,
+};
+
+/// A scaled-down overview of a large canvas, with a draggable rectangle showing the visible part.
+///
+/// The minimap replays a list of shapes, usually recorded from the layer of a [`crate::Scene`]
+/// (see [`crate::Scene::layer_id`]) right after showing it.
+/// Both the shapes and the viewport are in the coordinates of that canvas.
+/// The clip rectangles of the shapes are ignored, so everything that was painted shows up,
+/// but note that many widgets skip painting when they are outside the visible area.
+///
+/// Dragging the viewport rectangle, or clicking anywhere in the minimap, pans the viewport.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut scene_rect = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(100.0, 100.0));
+/// let scene_layer = egui::Scene::layer_id(ui);
+/// egui::Scene::new().show(ui, &mut scene_rect, |ui| {
+///     ui.label("A big canvas");
+/// });
+///
+/// let shapes = ui.ctx().graphics(|graphics| {
+///     graphics
+///         .get(scene_layer)
+///         .map(|list| list.all_entries().cloned().collect::<Vec<_>>())
+///         .unwrap_or_default()
+/// });
+/// egui::Minimap::new().show(ui, &shapes, &mut scene_rect);
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+#[must_use = "You should call .show()"]
+pub struct Minimap {
+    size: Vec2,
+    fill: Option<Color32>,
+    viewport_stroke: Option<Stroke>,
+}
+
+impl Default for Minimap {
+    fn default() -> Self {
+        Self {
+            size: Vec2::splat(150.0),
+            fill: None,
+            viewport_stroke: None,
+        }
+    }
+}
+
+impl Minimap {
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The size of the minimap in points.
+    ///
+    /// Default: `150x150`.
+    #[inline]
+    pub fn size(mut self, size: impl Into<Vec2>) -> Self {
+        self.size = size.into();
+        self
+    }
+
+    /// The background color.
+    ///
+    /// Default: [`crate::Visuals::extreme_bg_color`].
+    #[inline]
+    pub fn fill(mut self, fill: Color32) -> Self {
+        self.fill = Some(fill);
+        self
+    }
+
+    /// The outline of the viewport rectangle.
+    ///
+    /// Default: the stroke of selected widgets.
+    #[inline]
+    pub fn viewport_stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.viewport_stroke = Some(stroke.into());
+        self
+    }
+
+    /// Show the `shapes` scaled down, together with the `viewport`.
+    ///
+    /// `viewport` is changed when the user pans in the minimap,
+    /// in which case the returned [`Response`] is marked as changed.
+    pub fn show(self, ui: &mut Ui, shapes: &[ClippedShape], viewport: &mut Rect) -> Response {
+        let (rect, mut response) = ui.allocate_exact_size(self.size, Sense::click_and_drag());
+
+        let world_rect = shapes
+            .iter()
+            .map(|shape| shape.shape.visual_bounding_rect())
+            .filter(|bounds| bounds.is_finite() && bounds.is_positive())
+            .fold(*viewport, Rect::union);
+        let to_minimap = fit_to_rect_in_scene(rect, world_rect, Rangef::new(0.0, f32::INFINITY));
+
+        // Dragging the viewport past the shapes grows the world rect, which would shrink the map
+        // and make the viewport jump under the pointer, so keep the scale until the drag ends:
+        let frozen_id = response.id.with("frozen_transform");
+        let to_minimap = if response.dragged() {
+            ui.data_mut(|data| *data.get_temp_mut_or_insert_with(frozen_id, || to_minimap))
+        } else {
+            ui.data_mut(|data| data.remove::<TSTransform>(frozen_id));
+            to_minimap
+        };
+        if !to_minimap.is_valid() {
+            return response;
+        }
+
+        if let Some(pointer) = response.interact_pointer_pos() {
+            let pointer_in_world = to_minimap.inverse() * pointer;
+            let grabbed_viewport = viewport.contains(pointer_in_world);
+            let new_viewport =
+                if !grabbed_viewport && (response.drag_started() || response.clicked()) {
+                    // Jump so that the viewport is centered on the pointer:
+                    Rect::from_center_size(pointer_in_world, viewport.size())
+                } else {
+                    viewport.translate(response.drag_delta() / to_minimap.scaling)
+                };
+            if new_viewport != *viewport {
+                *viewport = new_viewport;
+                response.mark_changed();
+            }
+        }
+
+        if ui.is_rect_visible(rect) {
+            let visuals = ui.visuals();
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, 0.0, self.fill.unwrap_or(visuals.extreme_bg_color));
+
+            for ClippedShape { shape, .. } in shapes {
+                let mut shape = shape.clone();
+                shape.transform(to_minimap);
+                painter.add(shape);
+            }
+
+            let stroke = self.viewport_stroke.unwrap_or(visuals.selection.stroke);
+            painter.rect(
+                to_minimap * *viewport,
+                0.0,
+                stroke.color.gamma_multiply(0.1),
+                stroke,
+                StrokeKind::Inside,
+            );
+        }
+
+        response
+    }
+}
+
+#[cfg(test)]
+fn run_minimap(
+    ctx: &crate::Context,
+    viewport: &mut Rect,
+    events: Vec<crate::Event>,
+) -> TSTransform {
+    let canvas = Rect::from_min_size(crate::Pos2::ZERO, Vec2::splat(100.0));
+    let shapes = [ClippedShape {
+        clip_rect: Rect::EVERYTHING,
+        shape: crate::Shape::rect_filled(canvas, 0.0, Color32::RED),
+    }];
+    let input = crate::RawInput {
+        events,
+        ..Default::default()
+    };
+    let mut minimap_rect = Rect::NOTHING;
+    let _ = ctx.run(input, |ctx| {
+        crate::CentralPanel::default().show(ctx, |ui| {
+            minimap_rect = Minimap::new()
+                .size(Vec2::splat(100.0))
+                .show(ui, &shapes, viewport)
+                .rect;
+        });
+    });
+
+    // How the canvas was shown before the viewport moved past it:
+    fit_to_rect_in_scene(minimap_rect, canvas, Rangef::new(0.0, f32::INFINITY))
+}
+
+#[cfg(test)]
+fn pointer_button(pos: crate::Pos2, pressed: bool) -> crate::Event {
+    crate::Event::PointerButton {
+        pos,
+        button: crate::PointerButton::Primary,
+        pressed,
+        modifiers: Default::default(),
+    }
+}
+
+#[test]
+fn test_minimap_click_centers_the_viewport() {
+    use crate::{pos2, Event};
+
+    let ctx = crate::Context::default();
+    let mut viewport = Rect::from_min_size(pos2(0.0, 0.0), Vec2::splat(50.0));
+    let to_minimap = run_minimap(&ctx, &mut viewport, vec![]);
+
+    let pos = to_minimap * pos2(75.0, 75.0);
+    let events = vec![Event::PointerMoved(pos), pointer_button(pos, true)];
+    run_minimap(&ctx, &mut viewport, events);
+    run_minimap(&ctx, &mut viewport, vec![pointer_button(pos, false)]);
+    assert!((viewport.center() - pos2(75.0, 75.0)).length() < 0.01);
+    assert_eq!(viewport.size(), Vec2::splat(50.0));
+}
+
+#[test]
+fn test_minimap_scale_is_frozen_while_dragging() {
+    use crate::{pos2, vec2, Event};
+
+    let ctx = crate::Context::default();
+    let mut viewport = Rect::from_min_size(pos2(0.0, 0.0), Vec2::splat(50.0));
+    let to_minimap = run_minimap(&ctx, &mut viewport, vec![]);
+
+    let start = to_minimap * viewport.center();
+    let events = vec![Event::PointerMoved(start), pointer_button(start, true)];
+    run_minimap(&ctx, &mut viewport, events);
+
+    // Drag the viewport past the canvas, which makes the world bigger:
+    for i in 1..=10 {
+        let before = viewport.min.x;
+        let pos = start + vec2(10.0 * i as f32, 0.0);
+        run_minimap(&ctx, &mut viewport, vec![Event::PointerMoved(pos)]);
+        let step = viewport.min.x - before;
+        assert!(
+            (step - 10.0 / to_minimap.scaling).abs() < 0.01,
+            "Every step should be at the scale the drag started with, but step {i} was {step}"
+        );
+    }
+    assert!(140.0 < viewport.max.x);
+
+    // Releasing doesn't move the viewport, even though the minimap is rescaled to fit:
+    let dragged = viewport;
+    let end = start + vec2(100.0, 0.0);
+    run_minimap(&ctx, &mut viewport, vec![pointer_button(end, false)]);
+    assert_eq!(viewport, dragged);
+}
@@ -6,42 +6,65 @@
 
 use crate::{epaint, Response, Ui};
 
+mod breadcrumbs;
 mod button;
 mod checkbox;
 pub mod color_picker;
 pub(crate) mod drag_value;
+mod gauge;
+mod heatmap;
 mod hyperlink;
 mod image;
+mod image_annotator;
 mod image_button;
 mod label;
+#[cfg(feature = "markdown")]
+mod markdown;
+mod minimap;
 mod progress_bar;
 mod radio_button;
+mod range_slider;
+mod selectable_list;
 mod selected_label;
 mod separator;
 mod slider;
 mod spinner;
+mod tab_strip;
 pub mod text_edit;
+mod video_surface;
 
 pub use self::{
+    breadcrumbs::Breadcrumbs,
     button::Button,
     checkbox::Checkbox,
     drag_value::DragValue,
+    gauge::{Gauge, GaugeKind},
+    heatmap::{Heatmap, HeatmapCell, HeatmapResponse},
     hyperlink::{Hyperlink, Link},
     image::{
-        decode_animated_image_uri, has_gif_magic_header, has_webp_header, paint_texture_at,
-        FrameDurations, Image, ImageFit, ImageOptions, ImageSize, ImageSource,
+        decode_animated_image_uri, has_apng_header, has_gif_magic_header, has_webp_header,
+        paint_texture_at, FrameDurations, Image, ImageFit, ImageOptions, ImageSize, ImageSource,
     },
+    image_annotator::{Annotation, AnnotationTool, Annotations, ImageAnnotator},
     image_button::ImageButton,
     label::Label,
+    minimap::Minimap,
     progress_bar::ProgressBar,
     radio_button::RadioButton,
+    range_slider::RangeSlider,
+    selectable_list::{ListSelection, SelectableList},
     selected_label::SelectableLabel,
     separator::Separator,
-    slider::{Slider, SliderClamping, SliderOrientation},
+    slider::{Slider, SliderClamping, SliderOrientation, SliderTick, SliderTicks},
     spinner::Spinner,
-    text_edit::{TextBuffer, TextEdit},
+    tab_strip::{TabStrip, TabStripResponse},
+    text_edit::{InputMask, SpellChecker, SpellDiagnostic, TextBuffer, TextEdit},
+    video_surface::{VideoFrame, VideoSurface, YuvMatrix},
 };
 
+#[cfg(feature = "markdown")]
+pub use self::markdown::Markdown;
+
 // ----------------------------------------------------------------------------
 
 /// Anything implementing Widget can be added to a [`Ui`] with [`Ui::add`].
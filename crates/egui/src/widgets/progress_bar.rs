@@ -1,6 +1,6 @@
 use crate::{
-    lerp, vec2, Color32, CornerRadius, NumExt, Pos2, Rect, Response, Rgba, Sense, Shape, Stroke,
-    TextStyle, TextWrapMode, Ui, Vec2, Widget, WidgetInfo, WidgetText, WidgetType,
+    lerp, vec2, Color32, ColorRole, CornerRadius, NumExt, Pos2, Rect, Response, Rgba, Sense, Shape,
+    Stroke, TextStyle, TextWrapMode, Ui, Vec2, Widget, WidgetInfo, WidgetText, WidgetType,
 };
 
 enum ProgressBarText {
@@ -142,8 +142,11 @@ impl Widget for ProgressBar {
             let has_custom_cr = corner_radius.is_some();
             let half_height = outer_rect.height() / 2.0;
             let corner_radius = corner_radius.unwrap_or_else(|| half_height.into());
-            ui.painter()
-                .rect_filled(outer_rect, corner_radius, visuals.extreme_bg_color);
+            ui.painter().rect_filled(
+                outer_rect,
+                corner_radius,
+                visuals.color(ColorRole::SurfaceSunken),
+            );
             let min_width =
                 2.0 * f32::max(corner_radius.sw as _, corner_radius.nw as _).at_most(half_height);
             let filled_width = (outer_rect.width() * progress).at_least(min_width);
@@ -162,7 +165,8 @@ impl Widget for ProgressBar {
                 inner_rect,
                 corner_radius,
                 Color32::from(
-                    Rgba::from(fill.unwrap_or(visuals.selection.bg_fill)) * color_factor as f32,
+                    Rgba::from(fill.unwrap_or(visuals.color(ColorRole::Accent)))
+                        * color_factor as f32,
                 ),
             );
 
@@ -202,7 +206,7 @@ impl Widget for ProgressBar {
                     + vec2(ui.spacing().item_spacing.x, 0.0);
                 let text_color = visuals
                     .override_text_color
-                    .unwrap_or(visuals.selection.stroke.color);
+                    .unwrap_or(visuals.color(ColorRole::OnAccent));
                 ui.painter()
                     .with_clip_rect(outer_rect)
                     .galley(text_pos, galley, text_color);
@@ -0,0 +1,659 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+    emath, epaint, lerp, pos2, remap_clamp, style, style::HandleShape, vec2, EventFilter, Id, Key,
+    Label, NumExt, Pos2, Rangef, Rect, Response, Sense, TextStyle, TextWrapMode, Ui, Vec2, Widget,
+    WidgetInfo, WidgetText,
+};
+
+use super::SliderOrientation;
+
+// ----------------------------------------------------------------------------
+
+/// Combined into one function (rather than two) to make it easier
+/// for the borrow checker.
+///
+/// The first argument is the index of the thumb.
+type GetSetValues<'a> = Box<dyn 'a + FnMut(usize, Option<f64>) -> f64>;
+
+// ----------------------------------------------------------------------------
+
+/// Control two (or more) numbers with a single slider, e.g. to select a range.
+///
+/// Each value gets its own draggable thumb.
+/// The thumbs can not cross each other, and are always kept at least [`Self::min_gap`] apart.
+/// The part of the rail between the first and the last thumb is highlighted.
+///
+/// Each thumb is a separate focusable widget, so it can be moved with the arrow keys
+/// and is exposed to screen readers on its own.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut min_price: f32 = 10.0;
+/// # let mut max_price: f32 = 50.0;
+/// ui.add(egui::RangeSlider::new(&mut min_price, &mut max_price, 0.0..=100.0).text("Price"));
+/// # });
+/// ```
+///
+/// The default [`RangeSlider`] size is set by [`crate::style::Spacing::slider_width`].
+#[must_use = "You should put this widget in a ui with `ui.add(widget);`"]
+pub struct RangeSlider<'a> {
+    get_set_value: GetSetValues<'a>,
+    num_thumbs: usize,
+    range: RangeInclusive<f64>,
+    min_gap: f64,
+    step: Option<f64>,
+    orientation: SliderOrientation,
+    text: WidgetText,
+    thumb_labels: Vec<String>,
+    handle_shape: Option<HandleShape>,
+}
+
+impl<'a> RangeSlider<'a> {
+    /// A slider with two thumbs, one for the `low` and one for the `high` end of a range.
+    ///
+    /// `range` must be increasing, i.e. `range.start() <= range.end()`.
+    pub fn new<Num: emath::Numeric>(
+        low: &'a mut Num,
+        high: &'a mut Num,
+        range: RangeInclusive<Num>,
+    ) -> Self {
+        let range_f64 = range.start().to_f64()..=range.end().to_f64();
+        let slf = Self::from_get_set(2, range_f64, move |index, v: Option<f64>| {
+            let value = if index == 0 { &mut *low } else { &mut *high };
+            if let Some(v) = v {
+                *value = Num::from_f64(v);
+            }
+            value.to_f64()
+        })
+        .thumb_labels(["Start", "End"]);
+
+        if Num::INTEGRAL {
+            slf.integer()
+        } else {
+            slf
+        }
+    }
+
+    /// A slider with one thumb for each of the `values`.
+    ///
+    /// The values should be sorted in increasing order.
+    /// `range` must be increasing, i.e. `range.start() <= range.end()`.
+    pub fn from_values<Num: emath::Numeric>(
+        values: &'a mut [Num],
+        range: RangeInclusive<Num>,
+    ) -> Self {
+        let num_thumbs = values.len();
+        let range_f64 = range.start().to_f64()..=range.end().to_f64();
+        let slf = Self::from_get_set(num_thumbs, range_f64, move |index, v: Option<f64>| {
+            if let Some(v) = v {
+                values[index] = Num::from_f64(v);
+            }
+            values[index].to_f64()
+        });
+
+        if Num::INTEGRAL {
+            slf.integer()
+        } else {
+            slf
+        }
+    }
+
+    /// The `get_set_value` closure is given the index of a thumb,
+    /// and an optional new value for it. It should return the current value of that thumb.
+    pub fn from_get_set(
+        num_thumbs: usize,
+        range: RangeInclusive<f64>,
+        get_set_value: impl 'a + FnMut(usize, Option<f64>) -> f64,
+    ) -> Self {
+        Self {
+            get_set_value: Box::new(get_set_value),
+            num_thumbs,
+            range,
+            min_gap: 0.0,
+            step: None,
+            orientation: SliderOrientation::Horizontal,
+            text: Default::default(),
+            thumb_labels: Vec::new(),
+            handle_shape: None,
+        }
+    }
+
+    /// Show a text next to the slider (e.g. explaining what the slider controls).
+    #[inline]
+    pub fn text(mut self, text: impl Into<WidgetText>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Names of the individual thumbs, used by screen readers (e.g. "Start", "End").
+    #[inline]
+    pub fn thumb_labels(mut self, labels: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.thumb_labels = labels.into_iter().map(|l| l.to_string()).collect();
+        self
+    }
+
+    /// The thumbs are always kept at least this far apart (in value space). Default: `0.0`.
+    #[inline]
+    pub fn min_gap(mut self, min_gap: f64) -> Self {
+        self.min_gap = min_gap.at_least(0.0);
+        self
+    }
+
+    /// Vertical or horizontal slider? The default is horizontal.
+    #[inline]
+    pub fn orientation(mut self, orientation: SliderOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Make this a vertical slider.
+    #[inline]
+    pub fn vertical(mut self) -> Self {
+        self.orientation = SliderOrientation::Vertical;
+        self
+    }
+
+    /// Sets the minimal change of the values.
+    ///
+    /// Value `0.0` effectively disables the feature.
+    ///
+    /// Default: `0.0` (disabled).
+    #[inline]
+    pub fn step_by(mut self, step: f64) -> Self {
+        self.step = if step != 0.0 { Some(step) } else { None };
+        self
+    }
+
+    /// Change the shape of the thumbs.
+    #[inline]
+    pub fn handle_shape(mut self, handle_shape: HandleShape) -> Self {
+        self.handle_shape = Some(handle_shape);
+        self
+    }
+
+    /// Helpers for integer ranges.
+    ///
+    /// If you use one of the integer constructors (e.g. `RangeSlider::new` with `i32`) this is called for you.
+    pub fn integer(self) -> Self {
+        self.step_by(1.0)
+    }
+
+    fn get_value(&mut self, index: usize) -> f64 {
+        (self.get_set_value)(index, None)
+    }
+
+    /// The range the thumb at `index` may move in without crossing its neighbors.
+    fn thumb_range(&mut self, index: usize) -> RangeInclusive<f64> {
+        let mut min = *self.range.start();
+        let mut max = *self.range.end();
+        if index > 0 {
+            min = min.max(self.get_value(index - 1) + self.min_gap);
+        }
+        if index + 1 < self.num_thumbs {
+            max = max.min(self.get_value(index + 1) - self.min_gap);
+        }
+        min..=max.max(min)
+    }
+
+    fn set_value(&mut self, index: usize, mut value: f64) {
+        let thumb_range = self.thumb_range(index);
+
+        if let Some(step) = self.step {
+            let start = *self.range.start();
+            value = start + ((value - start) / step).round() * step;
+            // Rounding may have pushed us past a neighbor:
+            let step = step.abs();
+            while value > *thumb_range.end() && value - step >= *thumb_range.start() {
+                value -= step;
+            }
+            while value < *thumb_range.start() && value + step <= *thumb_range.end() {
+                value += step;
+            }
+        }
+
+        value = value.clamp(*thumb_range.start(), *thumb_range.end());
+        (self.get_set_value)(index, Some(value));
+    }
+
+    fn normalized_from_value(&self, value: f64) -> f64 {
+        remap_clamp(value, self.range.clone(), 0.0..=1.0)
+    }
+
+    fn value_from_position(&self, position: f32, position_range: Rangef) -> f64 {
+        let normalized = remap_clamp(position, position_range, 0.0..=1.0) as f64;
+        lerp(self.range.clone(), normalized)
+    }
+
+    fn position_from_value(&self, value: f64, position_range: Rangef) -> f32 {
+        lerp(position_range, self.normalized_from_value(value) as f32)
+    }
+
+    fn pointer_position(&self, pointer_position_2d: Pos2) -> f32 {
+        match self.orientation {
+            SliderOrientation::Horizontal => pointer_position_2d.x,
+            SliderOrientation::Vertical => pointer_position_2d.y,
+        }
+    }
+
+    fn marker_center(&self, position_1d: f32, rail_rect: &Rect) -> Pos2 {
+        match self.orientation {
+            SliderOrientation::Horizontal => pos2(position_1d, rail_rect.center().y),
+            SliderOrientation::Vertical => pos2(rail_rect.center().x, position_1d),
+        }
+    }
+
+    fn handle_radius(&self, rect: &Rect) -> f32 {
+        let limit = match self.orientation {
+            SliderOrientation::Horizontal => rect.height(),
+            SliderOrientation::Vertical => rect.width(),
+        };
+        limit / 2.5
+    }
+
+    fn handle_half_size(&self, radius: f32, handle_shape: HandleShape) -> Vec2 {
+        match handle_shape {
+            HandleShape::Circle => Vec2::splat(radius),
+            HandleShape::Rect { aspect_ratio } => match self.orientation {
+                SliderOrientation::Horizontal => vec2(radius * aspect_ratio, radius),
+                SliderOrientation::Vertical => vec2(radius, radius * aspect_ratio),
+            },
+        }
+    }
+
+    fn position_range(&self, rect: &Rect, handle_shape: HandleShape) -> Rangef {
+        let handle_radius = self.handle_radius(rect);
+        let handle_radius = match handle_shape {
+            HandleShape::Circle => handle_radius,
+            HandleShape::Rect { aspect_ratio } => handle_radius * aspect_ratio,
+        };
+        match self.orientation {
+            SliderOrientation::Horizontal => rect.x_range().shrink(handle_radius),
+            // The vertical case has to be flipped because the largest slider value maps to the
+            // lowest y value (which is at the top)
+            SliderOrientation::Vertical => rect.y_range().shrink(handle_radius).flip(),
+        }
+    }
+
+    fn rail_rect(&self, rect: &Rect, radius: f32) -> Rect {
+        match self.orientation {
+            SliderOrientation::Horizontal => Rect::from_min_max(
+                pos2(rect.left(), rect.center().y - radius),
+                pos2(rect.right(), rect.center().y + radius),
+            ),
+            SliderOrientation::Vertical => Rect::from_min_max(
+                pos2(rect.center().x - radius, rect.top()),
+                pos2(rect.center().x + radius, rect.bottom()),
+            ),
+        }
+    }
+
+    fn thumb_label(&self, index: usize) -> String {
+        let thumb = self
+            .thumb_labels
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| format!("Thumb {}", index + 1));
+        let text = self.text.text();
+        if text.is_empty() {
+            thumb
+        } else {
+            format!("{text} {thumb}")
+        }
+    }
+
+    /// Keyboard and accessibility input for a focused thumb.
+    fn thumb_keyboard_input(
+        &mut self,
+        ui: &Ui,
+        index: usize,
+        thumb_response: &Response,
+        position_range: Rangef,
+    ) {
+        let mut decrement = 0usize;
+        let mut increment = 0usize;
+
+        if thumb_response.has_focus() {
+            ui.ctx().memory_mut(|m| {
+                m.set_focus_lock_filter(
+                    thumb_response.id,
+                    EventFilter {
+                        // pressing arrows in the orientation of the
+                        // slider should not move focus to next widget
+                        horizontal_arrows: matches!(
+                            self.orientation,
+                            SliderOrientation::Horizontal
+                        ),
+                        vertical_arrows: matches!(self.orientation, SliderOrientation::Vertical),
+                        ..Default::default()
+                    },
+                );
+            });
+
+            let (dec_key, inc_key) = match self.orientation {
+                SliderOrientation::Horizontal => (Key::ArrowLeft, Key::ArrowRight),
+                // Note that this is for moving the slider position,
+                // so up = decrement y coordinate:
+                SliderOrientation::Vertical => (Key::ArrowUp, Key::ArrowDown),
+            };
+
+            ui.input(|input| {
+                decrement += input.num_presses(dec_key);
+                increment += input.num_presses(inc_key);
+            });
+        }
+
+        #[cfg(feature = "accesskit")]
+        {
+            use accesskit::Action;
+            ui.input(|input| {
+                decrement +=
+                    input.num_accesskit_action_requests(thumb_response.id, Action::Decrement);
+                increment +=
+                    input.num_accesskit_action_requests(thumb_response.id, Action::Increment);
+            });
+        }
+
+        let kb_step = increment as f32 - decrement as f32;
+        if kb_step != 0.0 {
+            let prev_value = self.get_value(index);
+            let new_value = if let Some(step) = self.step {
+                prev_value + (kb_step as f64 * step)
+            } else {
+                let ui_point_per_step = 1.0; // move this many ui points for each kb_step
+                let prev_position = self.position_from_value(prev_value, position_range);
+                self.value_from_position(
+                    prev_position + ui_point_per_step * kb_step,
+                    position_range,
+                )
+            };
+            self.set_value(index, new_value);
+        }
+
+        #[cfg(feature = "accesskit")]
+        {
+            use accesskit::{Action, ActionData};
+            ui.input(|input| {
+                for request in input.accesskit_action_requests(thumb_response.id, Action::SetValue)
+                {
+                    if let Some(ActionData::NumericValue(new_value)) = request.data {
+                        self.set_value(index, new_value);
+                    }
+                }
+            });
+        }
+    }
+
+    /// Just the slider, no text
+    fn slider_ui(&mut self, ui: &mut Ui) -> Response {
+        let thickness = ui
+            .text_style_height(&TextStyle::Body)
+            .at_least(ui.spacing().interact_size.y);
+        let desired_size = match self.orientation {
+            SliderOrientation::Horizontal => vec2(ui.spacing().slider_width, thickness),
+            SliderOrientation::Vertical => vec2(thickness, ui.spacing().slider_width),
+        };
+        let mut response = ui.allocate_response(desired_size, Sense::click());
+        let rect = response.rect;
+
+        let handle_shape = self
+            .handle_shape
+            .unwrap_or_else(|| ui.style().visuals.handle_shape);
+        let position_range = self.position_range(&rect, handle_shape);
+        let radius = self.handle_radius(&rect);
+        let handle_half_size = self.handle_half_size(radius, handle_shape);
+        let rail_radius = (ui.spacing().slider_rail_height / 2.0).at_least(0.0);
+        let rail_rect = self.rail_rect(&rect, rail_radius);
+
+        let old_values: Vec<f64> = (0..self.num_thumbs).map(|i| self.get_value(i)).collect();
+
+        // Clicking the rail moves the closest thumb there:
+        if response.clicked() {
+            if let Some(pointer_position_2d) = response.interact_pointer_pos() {
+                let position = self.pointer_position(pointer_position_2d);
+                let closest = (0..self.num_thumbs).min_by(|&a, &b| {
+                    let distance = |i: usize| {
+                        (self.position_from_value(old_values[i], position_range) - position).abs()
+                    };
+                    distance(a).total_cmp(&distance(b))
+                });
+                if let Some(index) = closest {
+                    let value = self.value_from_position(position, position_range);
+                    self.set_value(index, value);
+                }
+            }
+        }
+
+        // When two thumbs are on top of each other, the one that can still move
+        // towards the center of the slider should be on top, or the user could get stuck.
+        // Widgets that are interacted with later are on top.
+        let mut order: Vec<usize> = (0..self.num_thumbs).collect();
+        order.sort_by_key(|&i| {
+            if self.normalized_from_value(old_values[i]) < 0.5 {
+                (0, i)
+            } else {
+                (1, usize::MAX - i)
+            }
+        });
+
+        let mut thumb_responses: Vec<Option<Response>> = vec![None; self.num_thumbs];
+        for index in order {
+            let value = self.get_value(index);
+            let center =
+                self.marker_center(self.position_from_value(value, position_range), &rail_rect);
+            let thumb_rect = Rect::from_center_size(center, 2.0 * handle_half_size).expand(
+                ui.style()
+                    .interaction
+                    .resize_grab_radius_side
+                    .at_most(radius),
+            );
+            let thumb_id = response.id.with(Id::new("thumb")).with(index);
+            let thumb_response = ui.interact(thumb_rect, thumb_id, Sense::drag());
+
+            if let Some(pointer_position_2d) = thumb_response.interact_pointer_pos() {
+                if thumb_response.dragged() {
+                    let position = self.pointer_position(pointer_position_2d);
+                    let value = self.value_from_position(position, position_range);
+                    self.set_value(index, value);
+                }
+            }
+            self.thumb_keyboard_input(ui, index, &thumb_response, position_range);
+
+            thumb_responses[index] = Some(thumb_response);
+        }
+
+        let values: Vec<f64> = (0..self.num_thumbs).map(|i| self.get_value(i)).collect();
+        let changed = values != old_values;
+
+        // Paint it:
+        if ui.is_rect_visible(rect) {
+            let widget_visuals = &ui.visuals().widgets;
+            let corner_radius = widget_visuals.inactive.corner_radius;
+            ui.painter()
+                .rect_filled(rail_rect, corner_radius, widget_visuals.inactive.bg_fill);
+
+            if let (Some(first), Some(last)) = (values.first(), values.last()) {
+                let start = self
+                    .marker_center(self.position_from_value(*first, position_range), &rail_rect);
+                let end =
+                    self.marker_center(self.position_from_value(*last, position_range), &rail_rect);
+                let selected_rect = match self.orientation {
+                    SliderOrientation::Horizontal => {
+                        Rect::from_x_y_ranges(start.x..=end.x, rail_rect.y_range())
+                    }
+                    SliderOrientation::Vertical => {
+                        Rect::from_x_y_ranges(rail_rect.x_range(), end.y..=start.y)
+                    }
+                };
+                let selection = ui.visuals().selection;
+                ui.painter().rect(
+                    selected_rect,
+                    corner_radius,
+                    selection.bg_fill,
+                    selection.stroke,
+                    epaint::StrokeKind::Inside,
+                );
+            }
+
+            for (index, value) in values.iter().enumerate() {
+                let Some(thumb_response) = &thumb_responses[index] else {
+                    continue;
+                };
+                let visuals = ui.style().interact(thumb_response);
+                let center = self
+                    .marker_center(self.position_from_value(*value, position_range), &rail_rect);
+                if handle_shape == style::HandleShape::Circle {
+                    ui.painter().add(epaint::CircleShape {
+                        center,
+                        radius: radius + visuals.expansion,
+                        fill: visuals.bg_fill,
+                        stroke: visuals.fg_stroke,
+                    });
+                } else {
+                    let v = handle_half_size + Vec2::splat(visuals.expansion);
+                    ui.painter().rect(
+                        Rect::from_center_size(center, 2.0 * v),
+                        visuals.corner_radius,
+                        visuals.bg_fill,
+                        visuals.fg_stroke,
+                        epaint::StrokeKind::Inside,
+                    );
+                }
+            }
+        }
+
+        let enabled = ui.is_enabled();
+        for (index, thumb_response) in thumb_responses.into_iter().enumerate() {
+            let Some(mut thumb_response) = thumb_response else {
+                continue;
+            };
+            let value = values[index];
+            if value != old_values[index] {
+                thumb_response.mark_changed();
+            }
+            let label = self.thumb_label(index);
+            thumb_response.widget_info(|| WidgetInfo::slider(enabled, value, &label));
+
+            #[cfg(feature = "accesskit")]
+            {
+                let thumb_range = self.thumb_range(index);
+                let step = self.step;
+                ui.ctx()
+                    .accesskit_node_builder(thumb_response.id, |builder| {
+                        use accesskit::Action;
+                        builder.set_min_numeric_value(*thumb_range.start());
+                        builder.set_max_numeric_value(*thumb_range.end());
+                        if let Some(step) = step {
+                            builder.set_numeric_value_step(step);
+                        }
+                        builder.add_action(Action::SetValue);
+                        if value < *thumb_range.end() {
+                            builder.add_action(Action::Increment);
+                        }
+                        if value > *thumb_range.start() {
+                            builder.add_action(Action::Decrement);
+                        }
+                    });
+            }
+
+            response |= thumb_response;
+        }
+
+        if changed {
+            response.mark_changed();
+        }
+        response
+    }
+}
+
+impl Widget for RangeSlider<'_> {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        let orientation = self.orientation;
+        let add_contents = |ui: &mut Ui| {
+            let mut slider_response = self.slider_ui(ui);
+            if !self.text.is_empty() {
+                let label_response =
+                    ui.add(Label::new(self.text.clone()).wrap_mode(TextWrapMode::Extend));
+                slider_response = slider_response.labelled_by(label_response.id);
+            }
+            slider_response
+        };
+
+        let inner_response = match orientation {
+            SliderOrientation::Horizontal => ui.horizontal(add_contents),
+            SliderOrientation::Vertical => ui.vertical(add_contents),
+        };
+
+        inner_response.inner | inner_response.response
+    }
+}
+
+/// Drag the thumb at the value `from` to where the value `to` would be (which may be outside the range),
+/// returning the values afterwards.
+#[cfg(test)]
+fn drag_thumb(mut values: [f64; 2], min_gap: f64, from: f64, to: f64) -> [f64; 2] {
+    use crate::{CentralPanel, Context, Event, PointerButton, RawInput};
+
+    let ctx = Context::default();
+    let run = |values: &mut [f64; 2], events: Vec<Event>| {
+        let mut id = Id::NULL;
+        let input = RawInput {
+            events,
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let [low, high] = values;
+                let slider = RangeSlider::new(low, high, 0.0..=100.0)
+                    .min_gap(min_gap)
+                    .step_by(1.0);
+                id = ui.add(slider).id;
+            });
+        });
+        id
+    };
+
+    // Find where the values are from where the thumbs are:
+    let start = values;
+    let id = run(&mut values, vec![]);
+    let thumb_center = |index: usize| {
+        let thumb_id = id.with(Id::new("thumb")).with(index);
+        ctx.read_response(thumb_id).unwrap().rect.center()
+    };
+    let (low, high) = (thumb_center(0), thumb_center(1));
+    let pos = |value: f64| low.lerp(high, ((value - start[0]) / (start[1] - start[0])) as f32);
+
+    let button = |pos, pressed| Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Default::default(),
+    };
+    let (from, to) = (pos(from), pos(to));
+    run(
+        &mut values,
+        vec![Event::PointerMoved(from), button(from, true)],
+    );
+    run(&mut values, vec![Event::PointerMoved(to)]);
+    run(&mut values, vec![button(to, false)]);
+    values
+}
+
+#[test]
+fn test_range_slider_drag_each_thumb() {
+    assert_eq!(drag_thumb([20.0, 80.0], 0.0, 20.0, 40.0), [40.0, 80.0]);
+    assert_eq!(drag_thumb([20.0, 80.0], 0.0, 80.0, 60.0), [20.0, 60.0]);
+}
+
+#[test]
+fn test_range_slider_thumbs_do_not_cross() {
+    assert_eq!(drag_thumb([20.0, 80.0], 0.0, 20.0, 95.0), [80.0, 80.0]);
+    assert_eq!(drag_thumb([20.0, 80.0], 5.0, 20.0, 95.0), [75.0, 80.0]);
+    assert_eq!(drag_thumb([20.0, 80.0], 5.0, 80.0, 0.0), [20.0, 25.0]);
+}
+
+#[test]
+fn test_range_slider_clamps_to_the_range() {
+    assert_eq!(drag_thumb([20.0, 80.0], 0.0, 80.0, 150.0), [20.0, 100.0]);
+    assert_eq!(drag_thumb([20.0, 80.0], 0.0, 20.0, -50.0), [0.0, 80.0]);
+}
@@ -0,0 +1,340 @@
+use std::collections::BTreeSet;
+
+use crate::{EventFilter, Id, Key, KeyboardShortcut, Modifiers, Response, Sense, StrokeKind, Ui};
+
+/// Which items of a [`SelectableList`] are selected.
+///
+/// Store this between frames, e.g. in your app state, next to the items.
+///
+/// The selection follows the usual conventions of file managers:
+/// * Click to select a single item
+/// * Ctrl/Cmd-click to add or remove an item
+/// * Shift-click to select everything between the _anchor_ (the item clicked before) and the clicked item
+/// * Arrow keys, Home and End to move, with Shift to extend the selection
+/// * Ctrl/Cmd+A to select everything
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ListSelection {
+    selected: BTreeSet<usize>,
+
+    /// Where a range selected with shift starts.
+    anchor: Option<usize>,
+
+    /// The item that was last clicked, or moved to with the keyboard.
+    cursor: Option<usize>,
+}
+
+impl ListSelection {
+    /// The indices of the selected items.
+    #[inline]
+    pub fn selected(&self) -> &BTreeSet<usize> {
+        &self.selected
+    }
+
+    #[inline]
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.contains(&index)
+    }
+
+    /// Where the next range selected with shift starts.
+    #[inline]
+    pub fn anchor(&self) -> Option<usize> {
+        self.anchor
+    }
+
+    /// The item that was last clicked, or moved to with the keyboard.
+    #[inline]
+    pub fn cursor(&self) -> Option<usize> {
+        self.cursor
+    }
+
+    /// Deselect everything.
+    pub fn clear(&mut self) {
+        self.selected.clear();
+    }
+
+    /// Select all of the `len` items.
+    pub fn select_all(&mut self, len: usize) {
+        self.selected = (0..len).collect();
+    }
+
+    /// Select only this item, and start the next range from it.
+    pub fn select_only(&mut self, index: usize) {
+        self.selected.clear();
+        self.selected.insert(index);
+        self.anchor = Some(index);
+        self.cursor = Some(index);
+    }
+
+    /// Add or remove this item, and start the next range from it.
+    pub fn toggle(&mut self, index: usize) {
+        if !self.selected.remove(&index) {
+            self.selected.insert(index);
+        }
+        self.anchor = Some(index);
+        self.cursor = Some(index);
+    }
+
+    /// Select everything from the anchor to this item.
+    ///
+    /// If `extend` is `false`, everything else is deselected.
+    pub fn select_range_to(&mut self, index: usize, extend: bool) {
+        let anchor = *self.anchor.get_or_insert(index);
+        if !extend {
+            self.selected.clear();
+        }
+        self.selected.extend(anchor.min(index)..=anchor.max(index));
+        self.cursor = Some(index);
+    }
+
+    /// Update the selection as if the item was clicked with the given modifiers held down.
+    pub fn click(&mut self, index: usize, modifiers: Modifiers) {
+        if modifiers.shift {
+            self.select_range_to(index, modifiers.command);
+        } else if modifiers.command {
+            self.toggle(index);
+        } else {
+            self.select_only(index);
+        }
+    }
+
+    /// Move the cursor to this item, as with the arrow keys and the given modifiers held down.
+    ///
+    /// With Ctrl/Cmd only the cursor moves, and Space toggles the item under it.
+    fn move_to(&mut self, index: usize, modifiers: Modifiers) {
+        if modifiers.shift {
+            self.select_range_to(index, false);
+        } else if modifiers.command {
+            self.cursor = Some(index);
+        } else {
+            self.select_only(index);
+        }
+    }
+
+    /// Forget items that no longer exist.
+    fn retain_len(&mut self, len: usize) {
+        self.selected.retain(|&index| index < len);
+        self.anchor = self.anchor.filter(|&index| index < len);
+        self.cursor = self.cursor.filter(|&index| index < len);
+    }
+}
+
+/// A list of items where the user can select several at once, with the mouse and keyboard.
+///
+/// You show each item yourself, e.g. with [`Ui::selectable_label`],
+/// and the list handles the clicks, modifiers and keys.
+/// See [`ListSelection`] for how the selection works.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// let files = ["a.txt", "b.txt", "c.txt"];
+/// let mut selection = egui::ListSelection::default();
+/// let response = egui::SelectableList::new("files", files.len()).show(
+///     ui,
+///     &mut selection,
+///     |ui, index, selected| ui.selectable_label(selected, files[index]),
+/// );
+/// if response.changed() {
+///     println!("Selected: {:?}", selection.selected());
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct SelectableList {
+    id_salt: Id,
+    len: usize,
+}
+
+impl SelectableList {
+    /// A list of `len` items.
+    pub fn new(id_salt: impl std::hash::Hash, len: usize) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            len,
+        }
+    }
+
+    /// Show the items top-down.
+    ///
+    /// `add_item` is given the index of the item and whether it is selected,
+    /// and should return the [`Response`] of what the user clicks to select it.
+    ///
+    /// The returned [`Response`] covers the whole list,
+    /// and is marked as changed if the selection changed.
+    /// It has keyboard focus after an item has been clicked.
+    pub fn show(
+        self,
+        ui: &mut Ui,
+        selection: &mut ListSelection,
+        mut add_item: impl FnMut(&mut Ui, usize, bool) -> Response,
+    ) -> Response {
+        let Self { id_salt, len } = self;
+        let id = ui.make_persistent_id(id_salt);
+
+        let before = selection.selected.clone();
+        selection.retain_len(len);
+
+        let has_focus = ui.memory(|mem| mem.has_focus(id));
+        let mut moved_cursor = false;
+        if has_focus && 0 < len {
+            ui.memory_mut(|mem| {
+                mem.set_focus_lock_filter(
+                    id,
+                    EventFilter {
+                        vertical_arrows: true,
+                        ..Default::default()
+                    },
+                );
+            });
+
+            let select_all = ui.input_mut(|i| {
+                i.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND, Key::A))
+            });
+            if select_all {
+                selection.select_all(len);
+            }
+
+            let (modifiers, target, toggle) = ui.input(|i| {
+                let cursor = selection.cursor;
+                let target = if i.key_pressed(Key::ArrowDown) {
+                    Some(cursor.map_or(0, |c| (c + 1).min(len - 1)))
+                } else if i.key_pressed(Key::ArrowUp) {
+                    Some(cursor.map_or(0, |c| c.saturating_sub(1)))
+                } else if i.key_pressed(Key::Home) {
+                    Some(0)
+                } else if i.key_pressed(Key::End) {
+                    Some(len - 1)
+                } else {
+                    None
+                };
+                (i.modifiers, target, i.key_pressed(Key::Space))
+            });
+            if let Some(target) = target {
+                selection.move_to(target, modifiers);
+                moved_cursor = true;
+            }
+            if toggle {
+                if let Some(cursor) = selection.cursor {
+                    selection.toggle(cursor);
+                }
+            }
+        }
+
+        let list_rect = ui
+            .vertical(|ui| {
+                for index in 0..len {
+                    let response = add_item(ui, index, selection.is_selected(index));
+                    if response.clicked() {
+                        selection.click(index, ui.input(|i| i.modifiers));
+                        ui.memory_mut(|mem| mem.request_focus(id));
+                    }
+                    if has_focus && selection.cursor == Some(index) {
+                        if moved_cursor {
+                            response.scroll_to_me(None);
+                        }
+                        let visuals = ui.visuals();
+                        ui.painter().rect_stroke(
+                            response.rect,
+                            visuals.widgets.hovered.corner_radius,
+                            visuals.selection.stroke,
+                            StrokeKind::Inside,
+                        );
+                    }
+                }
+            })
+            .response
+            .rect;
+
+        let mut response = ui.interact(list_rect, id, Sense::focusable_noninteractive());
+        if selection.selected != before {
+            response.mark_changed();
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selected(selection: &ListSelection) -> Vec<usize> {
+        selection.selected().iter().copied().collect()
+    }
+
+    #[test]
+    fn click_with_modifiers() {
+        let mut selection = ListSelection::default();
+
+        selection.click(2, Modifiers::NONE);
+        assert_eq!(selected(&selection), [2]);
+
+        selection.click(5, Modifiers::SHIFT);
+        assert_eq!(selected(&selection), [2, 3, 4, 5]);
+
+        // The anchor stays, so shift-clicking again replaces the range:
+        selection.click(0, Modifiers::SHIFT);
+        assert_eq!(selected(&selection), [0, 1, 2]);
+
+        selection.click(7, Modifiers::COMMAND);
+        assert_eq!(selected(&selection), [0, 1, 2, 7]);
+        assert_eq!(selection.anchor(), Some(7));
+
+        // Ctrl+shift adds a range to the selection:
+        selection.click(9, Modifiers::COMMAND | Modifiers::SHIFT);
+        assert_eq!(selected(&selection), [0, 1, 2, 7, 8, 9]);
+
+        selection.click(1, Modifiers::COMMAND);
+        assert_eq!(selected(&selection), [0, 2, 7, 8, 9]);
+
+        selection.click(8, Modifiers::NONE);
+        assert_eq!(selected(&selection), [8]);
+    }
+
+    #[test]
+    fn keyboard() {
+        use crate::{CentralPanel, Context, Event, RawInput};
+
+        let ctx = Context::default();
+        let mut selection = ListSelection::default();
+        let mut run = |key: Option<(Key, Modifiers)>| {
+            let events = key
+                .map(|(key, modifiers)| Event::Key {
+                    key,
+                    physical_key: None,
+                    pressed: true,
+                    repeat: false,
+                    modifiers,
+                })
+                .into_iter()
+                .collect();
+            let input = RawInput {
+                events,
+                modifiers: key.map_or(Modifiers::NONE, |(_, modifiers)| modifiers),
+                ..Default::default()
+            };
+            let mut response = None;
+            let _ = ctx.run(input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    response = Some(SelectableList::new("list", 10).show(
+                        ui,
+                        &mut selection,
+                        |ui, index, selected| ui.selectable_label(selected, index.to_string()),
+                    ));
+                });
+            });
+            response.unwrap()
+        };
+
+        let id = run(None).id;
+        ctx.memory_mut(|mem| mem.request_focus(id));
+        run(None);
+        assert!(run(Some((Key::ArrowDown, Modifiers::NONE))).changed());
+        run(Some((Key::ArrowDown, Modifiers::SHIFT)));
+        run(Some((Key::ArrowDown, Modifiers::SHIFT)));
+        assert!(ctx.memory(|mem| mem.has_focus(id)), "Arrows keep the focus");
+        run(Some((Key::A, Modifiers::COMMAND)));
+        assert_eq!(selection.selected().len(), 10);
+        assert_eq!(selection.anchor(), Some(0));
+        assert_eq!(selection.cursor(), Some(2));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{vec2, Response, Sense, Ui, Vec2, Widget};
+use crate::{pos2, vec2, Response, Sense, Ui, Vec2, Widget};
 
 /// A visual separator. A horizontal or vertical line (depending on [`crate::Layout`]).
 ///
@@ -114,15 +114,15 @@ impl Widget for Separator {
             let stroke = ui.visuals().widgets.noninteractive.bg_stroke;
             let painter = ui.painter();
             if is_horizontal_line {
-                painter.hline(
-                    (rect.left() - grow)..=(rect.right() + grow),
-                    rect.center().y,
+                let y = rect.center().y;
+                painter.hairline(
+                    [pos2(rect.left() - grow, y), pos2(rect.right() + grow, y)],
                     stroke,
                 );
             } else {
-                painter.vline(
-                    rect.center().x,
-                    (rect.top() - grow)..=(rect.bottom() + grow),
+                let x = rect.center().x;
+                painter.hairline(
+                    [pos2(x, rect.top() - grow), pos2(x, rect.bottom() + grow)],
                     stroke,
                 );
             }
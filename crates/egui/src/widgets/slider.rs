@@ -3,9 +3,9 @@
 use std::ops::RangeInclusive;
 
 use crate::{
-    emath, epaint, lerp, pos2, remap, remap_clamp, style, style::HandleShape, vec2, Color32,
-    DragValue, EventFilter, Key, Label, NumExt, Pos2, Rangef, Rect, Response, Sense, TextStyle,
-    TextWrapMode, Ui, Vec2, Widget, WidgetInfo, WidgetText, MINUS_CHAR_STR,
+    emath, epaint, lerp, pos2, remap, remap_clamp, style, style::HandleShape, vec2, Align2,
+    Color32, DragValue, EventFilter, Key, Label, NumExt, Pos2, Rangef, Rect, Response, Sense,
+    TextStyle, TextWrapMode, Ui, Vec2, Widget, WidgetInfo, WidgetText, MINUS_CHAR_STR,
 };
 
 use super::drag_value::clamp_value_to_range;
@@ -14,6 +14,13 @@ use super::drag_value::clamp_value_to_range;
 
 type NumFormatter<'a> = Box<dyn 'a + Fn(f64, RangeInclusive<usize>) -> String>;
 type NumParser<'a> = Box<dyn 'a + Fn(&str) -> Option<f64>>;
+type TickGenerator<'a> = Box<dyn 'a + Fn(RangeInclusive<f64>) -> Vec<SliderTick>>;
+
+/// A user-provided mapping between slider values and normalized `[0, 1]` positions.
+struct CustomScale<'a> {
+    to_normalized: Box<dyn 'a + Fn(f64) -> f64>,
+    from_normalized: Box<dyn 'a + Fn(f64) -> f64>,
+}
 
 // ----------------------------------------------------------------------------
 
@@ -75,6 +82,54 @@ pub enum SliderClamping {
     Always,
 }
 
+/// A tick mark on a [`Slider`], see [`Slider::ticks`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SliderTick {
+    /// Where along the slider the tick goes.
+    pub value: f64,
+
+    /// Major ticks are longer, and labeled if [`Slider::tick_labels`] is on.
+    pub major: bool,
+}
+
+impl SliderTick {
+    #[inline]
+    pub fn major(value: f64) -> Self {
+        Self { value, major: true }
+    }
+
+    #[inline]
+    pub fn minor(value: f64) -> Self {
+        Self {
+            value,
+            major: false,
+        }
+    }
+}
+
+/// Where to put the tick marks of a [`Slider`].
+pub enum SliderTicks<'a> {
+    /// Pick nicely rounded values automatically, following the scale of the slider.
+    ///
+    /// Linear sliders get evenly spaced ticks (e.g. every 0.5, with minor ticks every 0.1),
+    /// logarithmic sliders get a major tick at each power of ten, with minor ticks in between.
+    Auto,
+
+    /// A major tick every `major` units (starting at zero),
+    /// with each interval divided into `minor_divisions` parts by minor ticks.
+    Spacing { major: f64, minor_divisions: usize },
+
+    /// Compute the ticks yourself, given the range of the slider.
+    Custom(TickGenerator<'a>),
+}
+
+impl<'a> SliderTicks<'a> {
+    /// Compute the ticks yourself, given the range of the slider.
+    pub fn custom(generator: impl 'a + Fn(RangeInclusive<f64>) -> Vec<SliderTick>) -> Self {
+        Self::Custom(Box::new(generator))
+    }
+}
+
 /// Control a number with a slider.
 ///
 /// The slider range defines the values you get when pulling the slider to the far edges.
@@ -115,8 +170,12 @@ pub struct Slider<'a> {
     max_decimals: Option<usize>,
     custom_formatter: Option<NumFormatter<'a>>,
     custom_parser: Option<NumParser<'a>>,
+    unit: Option<String>,
+    custom_scale: Option<CustomScale<'a>>,
     trailing_fill: Option<bool>,
     handle_shape: Option<HandleShape>,
+    ticks: Option<SliderTicks<'a>>,
+    tick_labels: bool,
 }
 
 impl<'a> Slider<'a> {
@@ -165,8 +224,12 @@ impl<'a> Slider<'a> {
             max_decimals: None,
             custom_formatter: None,
             custom_parser: None,
+            unit: None,
+            custom_scale: None,
             trailing_fill: None,
             handle_shape: None,
+            ticks: None,
+            tick_labels: true,
         }
     }
 
@@ -247,6 +310,62 @@ impl<'a> Slider<'a> {
         self
     }
 
+    /// Use your own mapping between values and positions along the slider.
+    ///
+    /// `to_normalized` maps a value in the slider range to `[0, 1]`,
+    /// and `from_normalized` is its inverse.
+    /// This takes precedence over [`Self::logarithmic`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut volume: f32 = 0.5;
+    /// // A quadratic scale, giving more precision to small values:
+    /// ui.add(
+    ///     egui::Slider::new(&mut volume, 0.0..=1.0)
+    ///         .custom_scale(|value| value.sqrt(), |normalized| normalized * normalized),
+    /// );
+    /// # });
+    /// ```
+    pub fn custom_scale(
+        mut self,
+        to_normalized: impl 'a + Fn(f64) -> f64,
+        from_normalized: impl 'a + Fn(f64) -> f64,
+    ) -> Self {
+        self.custom_scale = Some(CustomScale {
+            to_normalized: Box::new(to_normalized),
+            from_normalized: Box::new(from_normalized),
+        });
+        self
+    }
+
+    /// Show tick marks along the slider.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut frequency: f64 = 440.0;
+    /// ui.add(
+    ///     egui::Slider::new(&mut frequency, 20.0..=20_000.0)
+    ///         .logarithmic(true)
+    ///         .ticks(egui::SliderTicks::Auto),
+    /// );
+    /// # });
+    /// ```
+    #[inline]
+    pub fn ticks(mut self, ticks: SliderTicks<'a>) -> Self {
+        self.ticks = Some(ticks);
+        self
+    }
+
+    /// Show the value of each major tick next to it. Default: `true`.
+    ///
+    /// Only has an effect together with [`Self::ticks`].
+    /// The labels use the same formatting as the value display.
+    #[inline]
+    pub fn tick_labels(mut self, tick_labels: bool) -> Self {
+        self.tick_labels = tick_labels;
+        self
+    }
+
     /// Controls when the values will be clamped to the range.
     ///
     /// ### With `.clamping(SliderClamping::Always)` (default)
@@ -483,6 +602,15 @@ impl<'a> Slider<'a> {
         self
     }
 
+    /// Show and parse the value (and tick labels) in one of the units of [`crate::Style::units`], e.g. `"angle"`.
+    ///
+    /// See [`DragValue::unit`].
+    #[inline]
+    pub fn unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+
     /// Set `custom_formatter` and `custom_parser` to display and parse numbers as binary integers. Floating point
     /// numbers are *not* supported.
     ///
@@ -634,18 +762,67 @@ impl<'a> Slider<'a> {
     /// For instance, `position` is the mouse position and `position_range` is the physical location of the slider on the screen.
     fn value_from_position(&self, position: f32, position_range: Rangef) -> f64 {
         let normalized = remap_clamp(position, position_range, 0.0..=1.0) as f64;
-        value_from_normalized(normalized, self.range(), &self.spec)
+        if let Some(scale) = &self.custom_scale {
+            (scale.from_normalized)(normalized)
+        } else {
+            value_from_normalized(normalized, self.range(), &self.spec)
+        }
     }
 
     fn position_from_value(&self, value: f64, position_range: Rangef) -> f32 {
-        let normalized = normalized_from_value(value, self.range(), &self.spec);
+        let normalized = if let Some(scale) = &self.custom_scale {
+            (scale.to_normalized)(value).clamp(0.0, 1.0)
+        } else {
+            normalized_from_value(value, self.range(), &self.spec)
+        };
         lerp(position_range, normalized as f32)
     }
+
+    fn compute_ticks(&self, slider_width: f32) -> Vec<SliderTick> {
+        let Some(ticks) = &self.ticks else {
+            return vec![];
+        };
+        let range = self.range();
+        let (min, max) = (
+            range.start().min(*range.end()),
+            range.start().max(*range.end()),
+        );
+        let max_major_ticks = (slider_width / 40.0).floor().at_least(2.0) as usize;
+        let mut ticks = match ticks {
+            SliderTicks::Auto => {
+                if let Some(scale) = &self.custom_scale {
+                    // Evenly spaced in screen space
+                    let num_major = max_major_ticks.min(5);
+                    (0..=4 * num_major)
+                        .map(|i| {
+                            let normalized = i as f64 / (4 * num_major) as f64;
+                            SliderTick {
+                                value: (scale.from_normalized)(normalized),
+                                major: i % 4 == 0,
+                            }
+                        })
+                        .collect()
+                } else if self.spec.logarithmic {
+                    logarithmic_ticks(min, max, &self.spec)
+                } else {
+                    linear_ticks(min, max, max_major_ticks)
+                }
+            }
+            SliderTicks::Spacing {
+                major,
+                minor_divisions,
+            } => spaced_ticks(min, max, *major, *minor_divisions),
+            SliderTicks::Custom(generator) => generator(range),
+        };
+        ticks.retain(|tick| tick.value.is_finite() && min <= tick.value && tick.value <= max);
+        ticks
+    }
 }
 
 impl Slider<'_> {
     /// Just the slider, no text
     fn allocate_slider_space(&self, ui: &mut Ui, thickness: f32) -> Response {
+        let thickness = thickness + self.tick_space(ui);
         let desired_size = match self.orientation {
             SliderOrientation::Horizontal => vec2(ui.spacing().slider_width, thickness),
             SliderOrientation::Vertical => vec2(thickness, ui.spacing().slider_width),
@@ -653,13 +830,42 @@ impl Slider<'_> {
         ui.allocate_response(desired_size, Sense::drag())
     }
 
+    /// Extra room needed next to the rail for tick marks and their labels.
+    fn tick_space(&self, ui: &Ui) -> f32 {
+        if self.ticks.is_none() {
+            0.0
+        } else if self.tick_labels {
+            let label_size = match self.orientation {
+                SliderOrientation::Horizontal => ui.text_style_height(&TextStyle::Small),
+                // Labels are placed beside a vertical slider:
+                SliderOrientation::Vertical => 4.0 * ui.text_style_height(&TextStyle::Small),
+            };
+            MAJOR_TICK_LENGTH + label_size
+        } else {
+            MAJOR_TICK_LENGTH
+        }
+    }
+
+    /// The part of the allocated rect that holds the rail and the handle (excludes the ticks).
+    fn slider_rect(&self, ui: &Ui, rect: Rect) -> Rect {
+        let tick_space = self.tick_space(ui);
+        match self.orientation {
+            SliderOrientation::Horizontal => {
+                Rect::from_min_max(rect.min, pos2(rect.max.x, rect.max.y - tick_space))
+            }
+            SliderOrientation::Vertical => {
+                Rect::from_min_max(rect.min, pos2(rect.max.x - tick_space, rect.max.y))
+            }
+        }
+    }
+
     /// Just the slider, no text
     fn slider_ui(&mut self, ui: &Ui, response: &Response) {
-        let rect = &response.rect;
+        let rect = &self.slider_rect(ui, response.rect);
         let handle_shape = self
             .handle_shape
             .unwrap_or_else(|| ui.style().visuals.handle_shape);
-        let position_range = self.position_range(rect, &handle_shape);
+        let position_range = self.position_range(ui, rect, &handle_shape);
 
         if let Some(pointer_position_2d) = response.interact_pointer_pos() {
             let position = self.pointer_position(pointer_position_2d);
@@ -695,11 +901,13 @@ impl Slider<'_> {
                 );
             });
 
+            // The arrows move the handle the way they point, so they are swapped when right-to-left:
             let (dec_key, inc_key) = match self.orientation {
+                SliderOrientation::Horizontal if ui.style().layout_direction.is_right_to_left() => {
+                    (Key::ArrowRight, Key::ArrowLeft)
+                }
                 SliderOrientation::Horizontal => (Key::ArrowLeft, Key::ArrowRight),
-                // Note that this is for moving the slider position,
-                // so up = decrement y coordinate:
-                SliderOrientation::Vertical => (Key::ArrowUp, Key::ArrowDown),
+                SliderOrientation::Vertical => (Key::ArrowDown, Key::ArrowUp),
             };
 
             ui.input(|input| {
@@ -720,14 +928,15 @@ impl Slider<'_> {
         let kb_step = increment as f32 - decrement as f32;
 
         if kb_step != 0.0 {
-            let ui_point_per_step = 1.0; // move this many ui points for each kb_step
+            // Move this many ui points for each kb_step, towards where the larger values are:
+            let ui_point_per_step = position_range.span().signum();
             let prev_value = self.get_value();
             let prev_position = self.position_from_value(prev_value, position_range);
             let new_position = prev_position + ui_point_per_step * kb_step;
             let new_value = match self.step {
                 Some(step) => prev_value + (kb_step as f64 * step),
                 None if self.smart_aim => {
-                    let aim_radius = 0.49 * ui_point_per_step; // Chosen so we don't include `prev_value` in the search.
+                    let aim_radius = 0.49; // Chosen so we don't include `prev_value` in the search.
                     emath::smart_aim::best_in_range_f64(
                         self.value_from_position(new_position - aim_radius, position_range),
                         self.value_from_position(new_position + aim_radius, position_range),
@@ -780,7 +989,11 @@ impl Slider<'_> {
                 // The trailing rect has to be drawn differently depending on the orientation.
                 match self.orientation {
                     SliderOrientation::Horizontal => {
-                        trailing_rail_rect.max.x = center.x + corner_radius.nw as f32;
+                        if ui.style().layout_direction.is_right_to_left() {
+                            trailing_rail_rect.min.x = center.x - corner_radius.ne as f32;
+                        } else {
+                            trailing_rail_rect.max.x = center.x + corner_radius.nw as f32;
+                        }
                     }
                     SliderOrientation::Vertical => {
                         trailing_rail_rect.min.y = center.y - corner_radius.se as f32;
@@ -794,6 +1007,8 @@ impl Slider<'_> {
                 );
             }
 
+            self.paint_ticks(ui, rect, position_range);
+
             let radius = self.handle_radius(rect);
 
             let handle_shape = self
@@ -827,6 +1042,89 @@ impl Slider<'_> {
         }
     }
 
+    fn paint_ticks(&self, ui: &Ui, rect: &Rect, position_range: Rangef) {
+        let ticks = self.compute_ticks(position_range.span().abs());
+        if ticks.is_empty() {
+            return;
+        }
+
+        let painter = ui.painter();
+        let visuals = ui.visuals();
+        let major_stroke = epaint::Stroke::new(1.0, visuals.widgets.noninteractive.fg_stroke.color)
+            .with_line_cap(epaint::LineCap::Butt);
+        let minor_stroke = epaint::Stroke::new(1.0, visuals.weak_text_color())
+            .with_line_cap(epaint::LineCap::Butt);
+        let font_id = TextStyle::Small.resolve(ui.style());
+        let text_color = visuals.text_color();
+
+        // Skip minor ticks that would be too close together to be told apart:
+        let min_minor_spacing = 3.0;
+        let minor_spacing = ticks
+            .windows(2)
+            .map(|w| {
+                (self.position_from_value(w[1].value, position_range)
+                    - self.position_from_value(w[0].value, position_range))
+                .abs()
+            })
+            .filter(|spacing| *spacing > 0.0)
+            .fold(f32::INFINITY, f32::min);
+        let show_minor = min_minor_spacing <= minor_spacing;
+
+        let mut last_label_rect = Rect::NOTHING;
+        for tick in ticks {
+            if !tick.major && !show_minor {
+                continue;
+            }
+            let position =
+                painter.round_to_pixel_center(self.position_from_value(tick.value, position_range));
+            let (length, stroke) = if tick.major {
+                (MAJOR_TICK_LENGTH, major_stroke)
+            } else {
+                (MINOR_TICK_LENGTH, minor_stroke)
+            };
+            let tick_line = match self.orientation {
+                SliderOrientation::Horizontal => [
+                    pos2(position, rect.bottom()),
+                    pos2(position, rect.bottom() + length),
+                ],
+                SliderOrientation::Vertical => [
+                    pos2(rect.right(), position),
+                    pos2(rect.right() + length, position),
+                ],
+            };
+            painter.line_segment(tick_line, stroke);
+
+            if tick.major && self.tick_labels {
+                let text = self.format_tick(ui, tick.value);
+                let galley = painter.layout_no_wrap(text, font_id.clone(), text_color);
+                let label_rect = match self.orientation {
+                    SliderOrientation::Horizontal => Align2::CENTER_TOP.anchor_size(
+                        pos2(position, rect.bottom() + MAJOR_TICK_LENGTH),
+                        galley.size(),
+                    ),
+                    SliderOrientation::Vertical => Align2::LEFT_CENTER.anchor_size(
+                        pos2(rect.right() + MAJOR_TICK_LENGTH + 2.0, position),
+                        galley.size(),
+                    ),
+                };
+                if !label_rect.intersects(last_label_rect) {
+                    painter.galley(label_rect.min, galley, text_color);
+                    last_label_rect = label_rect;
+                }
+            }
+        }
+    }
+
+    fn format_tick(&self, ui: &Ui, value: f64) -> String {
+        let decimals =
+            self.min_decimals..=self.max_decimals.unwrap_or(3).at_least(self.min_decimals);
+        if let Some(custom_formatter) = &self.custom_formatter {
+            custom_formatter(value, decimals)
+        } else {
+            crate::units::format_value(ui.style(), self.unit.as_deref(), value, decimals)
+        }
+    }
+
     fn marker_center(&self, position_1d: f32, rail_rect: &Rect) -> Pos2 {
         match self.orientation {
             SliderOrientation::Horizontal => pos2(position_1d, rail_rect.center().y),
@@ -841,14 +1139,22 @@ impl Slider<'_> {
         }
     }
 
-    fn position_range(&self, rect: &Rect, handle_shape: &style::HandleShape) -> Rangef {
+    fn position_range(&self, ui: &Ui, rect: &Rect, handle_shape: &style::HandleShape) -> Rangef {
         let handle_radius = self.handle_radius(rect);
         let handle_radius = match handle_shape {
             style::HandleShape::Circle => handle_radius,
             style::HandleShape::Rect { aspect_ratio } => handle_radius * aspect_ratio,
         };
         match self.orientation {
-            SliderOrientation::Horizontal => rect.x_range().shrink(handle_radius),
+            SliderOrientation::Horizontal => {
+                let range = rect.x_range().shrink(handle_radius);
+                // Values increase to the left in a right-to-left ui:
+                if ui.style().layout_direction.is_right_to_left() {
+                    range.flip()
+                } else {
+                    range
+                }
+            }
             // The vertical case has to be flipped because the largest slider value maps to the
             // lowest y value (which is at the top)
             SliderOrientation::Vertical => rect.y_range().shrink(handle_radius).flip(),
@@ -918,6 +1224,9 @@ impl Slider<'_> {
             if let Some(parser) = &self.custom_parser {
                 dv = dv.custom_parser(parser);
             }
+            if let Some(unit) = &self.unit {
+                dv = dv.unit(unit.clone());
+            }
             dv
         });
         if value != self.get_value() {
@@ -985,7 +1294,8 @@ impl Slider<'_> {
             let handle_shape = self
                 .handle_shape
                 .unwrap_or_else(|| ui.style().visuals.handle_shape);
-            let position_range = self.position_range(&response.rect, &handle_shape);
+            let slider_rect = self.slider_rect(ui, response.rect);
+            let position_range = self.position_range(ui, &slider_rect, &handle_shape);
             let value_response = self.value_ui(ui, position_range);
             if value_response.gained_focus()
                 || value_response.has_focus()
@@ -1031,6 +1341,192 @@ impl Widget for Slider<'_> {
     }
 }
 
+// ----------------------------------------------------------------------------
+// Tick placement.
+
+const MAJOR_TICK_LENGTH: f32 = 6.0;
+const MINOR_TICK_LENGTH: f32 = 3.0;
+
+/// Nicely rounded ticks (1, 2 or 5 times a power of ten), at most `max_major` major ones.
+fn linear_ticks(min: f64, max: f64, max_major: usize) -> Vec<SliderTick> {
+    let span = max - min;
+    if !span.is_finite() || span <= 0.0 {
+        return vec![];
+    }
+
+    let base = 10.0_f64.powf((span / max_major.at_least(1) as f64).log10().floor());
+    let (major, minor_divisions) = [(1.0, 5), (2.0, 4), (5.0, 5), (10.0, 5)]
+        .into_iter()
+        .map(|(factor, divisions)| (factor * base, divisions))
+        .find(|(step, _)| span / step <= max_major as f64)
+        .unwrap_or((10.0 * base, 5));
+
+    spaced_ticks(min, max, major, minor_divisions)
+}
+
+/// A major tick every `major` units, starting at zero.
+fn spaced_ticks(min: f64, max: f64, major: f64, minor_divisions: usize) -> Vec<SliderTick> {
+    let major = major.abs();
+    if major == 0.0
+        || !major.is_finite()
+        || !min.is_finite()
+        || !max.is_finite()
+        || (max - min) / major > 1000.0
+    {
+        return vec![];
+    }
+    let minor_divisions = minor_divisions.at_least(1);
+    let minor = major / minor_divisions as f64;
+
+    let first = (min / minor).ceil() as i64;
+    let last = (max / minor).floor() as i64;
+    (first..=last)
+        .map(|i| SliderTick {
+            value: i as f64 * minor,
+            major: i.rem_euclid(minor_divisions as i64) == 0,
+        })
+        .collect()
+}
+
+/// At most this many decades get ticks on a logarithmic slider.
+///
+/// Enough for any sensible slider, while keeping huge ranges (e.g. up to `f64::MAX`) cheap.
+const MAX_DECADES: i32 = 64;
+
+/// A major tick at each power of ten, with minor ticks at 2-9 times each power of ten.
+fn logarithmic_ticks(min: f64, max: f64, spec: &SliderSpec) -> Vec<SliderTick> {
+    fn positive_ticks(min: f64, max: f64, ticks: &mut Vec<SliderTick>, sign: f64) {
+        if min >= max || !min.is_finite() || !max.is_finite() {
+            return;
+        }
+        let first_decade = min.log10().floor() as i32;
+        let last_decade = (max.log10().ceil() as i32).min(first_decade + MAX_DECADES);
+        for decade in first_decade..=last_decade {
+            let base = 10.0_f64.powi(decade);
+            for multiple in 1..=9 {
+                let value = multiple as f64 * base;
+                if min <= value && value <= max {
+                    ticks.push(SliderTick {
+                        value: sign * value,
+                        major: multiple == 1,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut ticks = vec![];
+    let clamp_magnitude = |x: f64| x.clamp(spec.smallest_positive, spec.largest_finite);
+    if min < 0.0 {
+        let negative_max = if max < 0.0 { -max } else { 0.0 };
+        positive_ticks(
+            clamp_magnitude(negative_max),
+            clamp_magnitude(-min),
+            &mut ticks,
+            -1.0,
+        );
+        ticks.reverse();
+    }
+    if min <= 0.0 && 0.0 <= max {
+        ticks.push(SliderTick::major(0.0));
+    }
+    if 0.0 < max {
+        positive_ticks(
+            clamp_magnitude(min.at_least(0.0)),
+            clamp_magnitude(max),
+            &mut ticks,
+            1.0,
+        );
+    }
+    ticks
+}
+
+#[test]
+fn test_slider_ticks() {
+    let ticks = linear_ticks(0.0, 10.0, 5);
+    let major: Vec<f64> = ticks.iter().filter(|t| t.major).map(|t| t.value).collect();
+    assert_eq!(major, vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+    assert_eq!(ticks.len(), 21, "Expected minor ticks every 0.5");
+
+    let spec = SliderSpec {
+        logarithmic: true,
+        smallest_positive: 1e-6,
+        largest_finite: f64::INFINITY,
+    };
+    let ticks = logarithmic_ticks(1.0, 1000.0, &spec);
+    let major: Vec<f64> = ticks.iter().filter(|t| t.major).map(|t| t.value).collect();
+    assert_eq!(major, vec![1.0, 10.0, 100.0, 1000.0]);
+    assert!(ticks.iter().any(|t| t.value == 20.0 && !t.major));
+
+    let ticks = logarithmic_ticks(
+        -100.0,
+        100.0,
+        &SliderSpec {
+            smallest_positive: 1.0,
+            ..spec
+        },
+    );
+    assert!(ticks.windows(2).all(|w| w[0].value < w[1].value));
+    assert!(ticks.contains(&SliderTick::major(0.0)));
+
+    // Unbounded or huge ranges must not loop over billions of decades:
+    let ticks = logarithmic_ticks(0.0, f64::INFINITY, &spec);
+    assert_eq!(ticks, vec![SliderTick::major(0.0)]);
+    let ticks = logarithmic_ticks(1.0, f64::MAX, &spec);
+    assert!(ticks.len() <= 9 * (MAX_DECADES as usize + 1));
+}
+
+#[test]
+fn test_slider_arrow_keys_right_to_left() {
+    use crate::{style::LayoutDirection, CentralPanel, Context, Event, RawInput};
+
+    for (direction, key, increases) in [
+        (LayoutDirection::LeftToRight, Key::ArrowRight, true),
+        (LayoutDirection::LeftToRight, Key::ArrowLeft, false),
+        (LayoutDirection::RightToLeft, Key::ArrowLeft, true),
+        (LayoutDirection::RightToLeft, Key::ArrowRight, false),
+    ] {
+        for step in [None, Some(1.0)] {
+            let ctx = Context::default();
+            ctx.style_mut(|style| style.layout_direction = direction);
+            let mut value = 5.0;
+            let mut run = |events: Vec<Event>| {
+                let mut id = None;
+                let input = RawInput {
+                    events,
+                    ..Default::default()
+                };
+                let _ = ctx.run(input, |ctx| {
+                    CentralPanel::default().show(ctx, |ui| {
+                        let mut slider = Slider::new(&mut value, 0.0..=10.0);
+                        if let Some(step) = step {
+                            slider = slider.step_by(step);
+                        }
+                        id = Some(ui.add(slider).id);
+                    });
+                });
+                id.unwrap()
+            };
+
+            let id = run(vec![]);
+            ctx.memory_mut(|memory| memory.request_focus(id));
+            run(vec![Event::Key {
+                key,
+                physical_key: None,
+                pressed: true,
+                repeat: false,
+                modifiers: Default::default(),
+            }]);
+
+            assert_eq!(
+                5.0 < value,
+                increases,
+                "{direction:?} {key:?} {step:?} gave {value}"
+            );
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Helpers for converting slider range to/from normalized [0-1] range.
 // Always clamps.
@@ -1,6 +1,6 @@
 use epaint::{emath::lerp, vec2, Color32, Pos2, Rect, Shape, Stroke};
 
-use crate::{Response, Sense, Ui, Widget, WidgetInfo, WidgetType};
+use crate::{ColorRole, Response, Sense, Ui, Widget, WidgetInfo, WidgetType};
 
 /// A spinner widget used to indicate loading.
 ///
@@ -41,7 +41,7 @@ impl Spinner {
 
             let color = self
                 .color
-                .unwrap_or_else(|| ui.visuals().strong_text_color());
+                .unwrap_or_else(|| ui.visuals().color(ColorRole::TextStrong));
             let radius = (rect.height() / 2.0) - 2.0;
             let n_points = 20;
             let time = ui.input(|i| i.time);
@@ -0,0 +1,408 @@
+use std::hash::Hash;
+
+use crate::{
+    vec2, CornerRadius, CursorIcon, Id, Rect, Response, ScrollArea, Sense, StrokeKind, TextStyle,
+    TextWrapMode, Ui, WidgetInfo, WidgetText, WidgetType,
+};
+
+/// What happened in a [`TabStrip`] this frame.
+pub struct TabStripResponse<T> {
+    /// Covers the whole strip.
+    ///
+    /// Marked as changed if the active tab changed, or tabs were moved or closed.
+    pub response: Response,
+
+    /// The tab the user closed, already removed from the list.
+    pub closed: Option<T>,
+}
+
+/// A row of tabs, like in an editor or a browser.
+///
+/// The user can:
+/// * Click a tab to activate it
+/// * Drag a tab to move it
+/// * Close a tab with its close button, or with a middle-click
+/// * Scroll the strip, or pick a tab from the `⏷` menu, when the tabs don't fit
+///
+/// Tabs are identified by their hash, so that the drag survives the tab moving around.
+/// Each tab must therefore hash differently, e.g. by having a unique id.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut files = vec!["main.rs".to_owned(), "lib.rs".to_owned()];
+/// # let mut active = 0;
+/// let response = egui::TabStrip::new("files").show(ui, &mut files, &mut active, |file| {
+///     file.as_str().into()
+/// });
+/// if let Some(closed) = response.closed {
+///     println!("Closed {closed}");
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct TabStrip {
+    id_salt: Id,
+    closable: bool,
+    reorderable: bool,
+}
+
+impl TabStrip {
+    pub fn new(id_salt: impl Hash) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            closable: true,
+            reorderable: true,
+        }
+    }
+
+    /// Show a close button on each tab, and close tabs with a middle-click.
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+
+    /// Can the user drag the tabs to move them?
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn reorderable(mut self, reorderable: bool) -> Self {
+        self.reorderable = reorderable;
+        self
+    }
+
+    /// Show the tabs, with the one at index `active` highlighted.
+    ///
+    /// Moving and closing tabs changes `tabs`, and `active` follows the active tab.
+    /// If the active tab is closed, the one after it (or the last one) becomes active.
+    pub fn show<T: Hash>(
+        self,
+        ui: &mut Ui,
+        tabs: &mut Vec<T>,
+        active: &mut usize,
+        mut title: impl FnMut(&T) -> WidgetText,
+    ) -> TabStripResponse<T> {
+        let Self {
+            id_salt,
+            closable,
+            reorderable,
+        } = self;
+
+        let id = ui.make_persistent_id(id_salt);
+        let initial_active = *active;
+        let mut changed = false;
+        let mut closed = None;
+
+        let height = ui.spacing().interact_size.y;
+        let padding = ui.spacing().button_padding.x;
+        let close_size = ui.spacing().icon_width;
+        let tab_spacing = ui.spacing().item_spacing.x;
+
+        let galleys: Vec<_> = tabs
+            .iter()
+            .map(|tab| {
+                title(tab).into_galley(
+                    ui,
+                    Some(TextWrapMode::Extend),
+                    f32::INFINITY,
+                    TextStyle::Button,
+                )
+            })
+            .collect();
+        let tab_widths: Vec<f32> = galleys
+            .iter()
+            .map(|galley| {
+                let close_width = if closable { padding + close_size } else { 0.0 };
+                2.0 * padding + galley.size().x + close_width
+            })
+            .collect();
+        let total_width = tab_widths.iter().sum::<f32>()
+            + tab_spacing * tab_widths.len().saturating_sub(1) as f32;
+
+        let overflow_id = id.with("overflow");
+        let overflows = ui.available_width() < total_width;
+        let mut scroll_to_active = ui.data_mut(|d| d.remove_temp::<bool>(overflow_id).is_some());
+
+        let strip_response = ui.horizontal(|ui| {
+            let menu_width = if overflows { height + tab_spacing } else { 0.0 };
+            let mut tab_responses = Vec::with_capacity(tabs.len());
+
+            ScrollArea::horizontal()
+                .id_salt(id)
+                .max_width(ui.available_width() - menu_width)
+                .scroll_bar_visibility(crate::scroll_area::ScrollBarVisibility::AlwaysHidden)
+                .show(ui, |ui| {
+                    ui.spacing_mut().item_spacing.x = tab_spacing;
+                    for ((index, tab), galley) in tabs.iter().enumerate().zip(&galleys) {
+                        let tab_id = id.with(tab);
+                        let (_, rect) = ui.allocate_space(vec2(tab_widths[index], height));
+                        let response = ui.interact(rect, tab_id, Sense::click_and_drag());
+                        let selected = index == *active;
+                        response.widget_info(|| {
+                            WidgetInfo::selected(
+                                WidgetType::SelectableLabel,
+                                ui.is_enabled(),
+                                selected,
+                                galley.text(),
+                            )
+                        });
+
+                        let close_response = closable.then(|| {
+                            let close_rect = Rect::from_center_size(
+                                rect.right_center() - vec2(padding + 0.5 * close_size, 0.0),
+                                vec2(close_size, close_size),
+                            );
+                            ui.interact(close_rect, tab_id.with("close"), Sense::click())
+                        });
+
+                        if ui.is_rect_visible(rect) {
+                            let visuals = ui.style().interact_selectable(&response, selected);
+                            let corner_radius = CornerRadius {
+                                sw: 0,
+                                se: 0,
+                                ..visuals.corner_radius
+                            };
+                            if selected || response.hovered() || response.dragged() {
+                                ui.painter().rect(
+                                    rect,
+                                    corner_radius,
+                                    visuals.weak_bg_fill,
+                                    visuals.bg_stroke,
+                                    StrokeKind::Inside,
+                                );
+                            }
+                            let text_pos =
+                                rect.left_center() + vec2(padding, -0.5 * galley.size().y);
+                            ui.painter()
+                                .galley(text_pos, galley.clone(), visuals.text_color());
+
+                            if let Some(close_response) = &close_response {
+                                let visuals = ui.style().interact(close_response);
+                                let cross = close_response.rect.shrink(3.0);
+                                let stroke = visuals.fg_stroke;
+                                ui.painter()
+                                    .line_segment([cross.left_top(), cross.right_bottom()], stroke);
+                                ui.painter()
+                                    .line_segment([cross.right_top(), cross.left_bottom()], stroke);
+                            }
+                        }
+
+                        if selected && scroll_to_active {
+                            response.scroll_to_me(None);
+                        }
+
+                        tab_responses.push((response, close_response));
+                    }
+                });
+
+            let mut close_index = None;
+            let mut move_to = None;
+            for (index, (response, close_response)) in tab_responses.iter().enumerate() {
+                if close_response.as_ref().is_some_and(|r| r.clicked())
+                    || (closable && response.middle_clicked())
+                {
+                    close_index = Some(index);
+                } else if response.clicked() || response.drag_started() {
+                    *active = index;
+                }
+
+                if reorderable && response.dragged() {
+                    ui.ctx().set_cursor_icon(CursorIcon::Grabbing);
+                    if let Some(pointer) = response.interact_pointer_pos() {
+                        // The tab goes past the others whose centers it is past.
+                        // Comparing with the centers (and not the edges) keeps a narrow tab
+                        // dragged over a wide one from swapping back and forth every frame.
+                        let target = tab_responses
+                            .iter()
+                            .enumerate()
+                            .filter(|&(other_index, (other, _))| {
+                                other_index != index && other.rect.center().x < pointer.x
+                            })
+                            .count();
+                        if target != index {
+                            move_to = Some((index, target));
+                        }
+                    }
+                }
+            }
+
+            if let Some((from, to)) = move_to {
+                let tab = tabs.remove(from);
+                tabs.insert(to, tab);
+                *active = index_after_move(*active, from, to);
+                changed = true;
+            }
+            if let Some(index) = close_index {
+                closed = Some(tabs.remove(index));
+                *active = index_after_close(*active, index, tabs.len());
+                changed = true;
+            }
+
+            if overflows {
+                // Tabs may have moved or closed above, so lay out the titles anew:
+                let menu_response = ui.menu_button("⏷", |ui| {
+                    for (index, tab) in tabs.iter().enumerate() {
+                        if ui.selectable_label(index == *active, title(tab)).clicked() {
+                            *active = index;
+                            scroll_to_active = true;
+                            ui.close();
+                        }
+                    }
+                });
+                menu_response
+                    .response
+                    .on_hover_text(ui.translate("All tabs"));
+            }
+        });
+
+        if scroll_to_active || *active != initial_active {
+            ui.data_mut(|d| d.insert_temp(overflow_id, true));
+            ui.ctx().request_repaint();
+        }
+
+        let mut response = strip_response.response;
+        if changed || *active != initial_active {
+            response.mark_changed();
+        }
+        TabStripResponse { response, closed }
+    }
+}
+
+/// Where the tab at `active` ends up after moving the tab at `from` to `to`.
+fn index_after_move(active: usize, from: usize, to: usize) -> usize {
+    if active == from {
+        to
+    } else if from < active && active <= to {
+        active - 1
+    } else if to <= active && active < from {
+        active + 1
+    } else {
+        active
+    }
+}
+
+/// Where the tab at `active` ends up after closing the tab at `closed`,
+/// leaving `len` tabs.
+fn index_after_close(active: usize, closed: usize, len: usize) -> usize {
+    let active = if closed < active { active - 1 } else { active };
+    active.min(len.saturating_sub(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn active_follows_moves_and_closes() {
+        assert_eq!(index_after_move(2, 2, 0), 0);
+        assert_eq!(index_after_move(1, 0, 3), 0);
+        assert_eq!(index_after_move(1, 3, 0), 2);
+        assert_eq!(index_after_move(4, 0, 3), 4);
+
+        assert_eq!(index_after_close(3, 1, 4), 2);
+        assert_eq!(index_after_close(1, 1, 4), 1);
+        assert_eq!(index_after_close(3, 3, 3), 2);
+        assert_eq!(index_after_close(0, 0, 0), 0);
+    }
+
+    #[test]
+    fn middle_click_closes() {
+        use crate::{pos2, CentralPanel, Context, Event, PointerButton, RawInput};
+
+        let ctx = Context::default();
+        let mut tabs = vec!["a", "b", "c"];
+        let mut active = 1;
+        let mut closed = None;
+        let mut run = |time: f64, events: Vec<Event>| {
+            let input = RawInput {
+                time: Some(time),
+                events,
+                ..Default::default()
+            };
+            let _ = ctx.run(input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    let response =
+                        TabStrip::new("tabs").show(ui, &mut tabs, &mut active, |tab| (*tab).into());
+                    closed = closed.or(response.closed);
+                });
+            });
+        };
+
+        // Somewhere on the first tab:
+        let pos = pos2(15.0, 15.0);
+        let button = |pressed| Event::PointerButton {
+            pos,
+            button: PointerButton::Middle,
+            pressed,
+            modifiers: Default::default(),
+        };
+        run(0.0, vec![Event::PointerMoved(pos)]);
+        run(0.02, vec![button(true)]);
+        run(0.04, vec![button(false)]);
+
+        assert_eq!(closed, Some("a"));
+        assert_eq!(tabs, ["b", "c"]);
+        assert_eq!(active, 0, "The active tab moved one step to the left");
+    }
+
+    #[test]
+    fn dragging_a_narrow_tab_over_a_wide_one_moves_it_once() {
+        use crate::{pos2, CentralPanel, Context, Event, PointerButton, RawInput, Rect};
+
+        let ctx = Context::default();
+        let mut tabs = vec!["A much wider tab than the other", "b"];
+        let mut active = 0;
+        let mut orders = vec![];
+        let mut run = |time: f64, events: Vec<Event>| {
+            let mut strip_rect = Rect::NOTHING;
+            let input = RawInput {
+                time: Some(time),
+                events,
+                ..Default::default()
+            };
+            let _ = ctx.run(input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    let response =
+                        TabStrip::new("tabs").show(ui, &mut tabs, &mut active, |tab| (*tab).into());
+                    strip_rect = response.response.rect;
+                });
+            });
+            orders.push(tabs.clone());
+            strip_rect
+        };
+
+        let strip_rect = run(0.0, vec![]);
+
+        // Grab "b" at the right end of the strip, and drag it slowly all the way to the left:
+        let y = strip_rect.center().y;
+        let mut x = strip_rect.right() - 3.0;
+        let button = |x, pressed| Event::PointerButton {
+            pos: pos2(x, y),
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        let mut time = 0.0;
+        run(time, vec![Event::PointerMoved(pos2(x, y))]);
+        run(time, vec![button(x, true)]);
+        while strip_rect.left() < x {
+            x -= 2.0;
+            time += 0.02;
+            run(time, vec![Event::PointerMoved(pos2(x, y))]);
+        }
+        run(time, vec![button(x, false)]);
+
+        orders.dedup();
+        assert_eq!(
+            orders,
+            [
+                vec!["A much wider tab than the other", "b"],
+                vec!["b", "A much wider tab than the other"],
+            ],
+            "The tabs swapped once, and never back"
+        );
+        assert_eq!(active, 0, "The dragged tab is active");
+    }
+}
@@ -12,12 +12,16 @@ use crate::{
     output::OutputEvent,
     response, text_selection,
     text_selection::{text_cursor_state::cursor_rect, visuals::paint_text_selection, CCursorRange},
-    vec2, Align, Align2, Color32, Context, CursorIcon, Event, EventFilter, FontSelection, Id,
-    ImeEvent, Key, KeyboardShortcut, Margin, Modifiers, NumExt, Response, Sense, Shape, TextBuffer,
-    TextStyle, TextWrapMode, Ui, Vec2, Widget, WidgetInfo, WidgetText, WidgetWithState,
+    vec2, Align, Align2, Color32, ColorRole, Context, CursorIcon, Event, EventFilter,
+    FontSelection, Id, ImeEvent, Key, KeyboardShortcut, Margin, Modifiers, NumExt, Pos2, Response,
+    Sense, Shape, Stroke, TextBuffer, TextStyle, TextWrapMode, Ui, Vec2, Widget, WidgetInfo,
+    WidgetText, WidgetWithState,
 };
 
-use super::{TextEditOutput, TextEditState};
+use super::{spell_check, InputMask, SpellChecker, SpellDiagnostic, TextEditOutput, TextEditState};
+
+/// Checks the text of a [`TextEdit`], returning an error message if it is invalid.
+type Validator<'t> = Box<dyn Fn(&str) -> Result<(), String> + 't>;
 
 /// A text region that the user can edit the contents of.
 ///
@@ -87,6 +91,10 @@ pub struct TextEdit<'t> {
     char_limit: usize,
     return_key: Option<KeyboardShortcut>,
     background_color: Option<Color32>,
+    spell_checker: Option<&'t dyn SpellChecker>,
+    input_mask: Option<InputMask>,
+    validators: Vec<Validator<'t>>,
+    commit_on_enter: bool,
 }
 
 impl WidgetWithState for TextEdit<'_> {
@@ -146,6 +154,10 @@ impl<'t> TextEdit<'t> {
             char_limit: usize::MAX,
             return_key: Some(KeyboardShortcut::new(Modifiers::NONE, Key::Enter)),
             background_color: None,
+            spell_checker: None,
+            input_mask: None,
+            validators: Vec::new(),
+            commit_on_enter: false,
         }
     }
 
@@ -391,6 +403,87 @@ impl<'t> TextEdit<'t> {
         self.return_key = return_key.into();
         self
     }
+
+    /// Check the spelling of the text.
+    ///
+    /// The problems found get wavy underlines in [`crate::Visuals::error_fg_color`],
+    /// and right-clicking one of them shows a menu of suggested replacements.
+    /// The text is only checked again when it changes.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut my_string = String::new();
+    /// let checker = |word: &str| (word == "teh").then(|| vec!["the".to_owned()]);
+    /// ui.add(egui::TextEdit::multiline(&mut my_string).spell_checker(&checker));
+    /// # });
+    /// ```
+    #[inline]
+    pub fn spell_checker(mut self, spell_checker: &'t dyn SpellChecker) -> Self {
+        self.spell_checker = Some(spell_checker);
+        self
+    }
+
+    /// Restrict the text to a fixed format, like a phone number or a date.
+    ///
+    /// Characters that don't fit are dropped,
+    /// and the literal parts of the format are inserted as the user types.
+    /// Text that doesn't fill in the whole format is shown as invalid (see [`Self::validator`]).
+    ///
+    /// If no [`Self::hint_text`] is set, the [`InputMask::placeholder`] is shown instead.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut phone_number = String::new();
+    /// ui.add(egui::TextEdit::singleline(&mut phone_number).input_mask(egui::InputMask::phone()));
+    /// # });
+    /// ```
+    #[inline]
+    pub fn input_mask(mut self, input_mask: InputMask) -> Self {
+        self.input_mask = Some(input_mask);
+        self
+    }
+
+    /// Check the text with this, which returns an error message if the text is invalid.
+    ///
+    /// Call this several times to add more checks; the first error found is shown.
+    /// Invalid text is outlined with a dashed line in [`crate::Visuals::error_fg_color`],
+    /// and the error message is shown below the [`TextEdit`] while it is hovered or focused.
+    ///
+    /// The error is also returned in [`TextEditOutput::validation_error`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut my_string = String::new();
+    /// ui.add(
+    ///     egui::TextEdit::singleline(&mut my_string)
+    ///         .validator(|text| if text.is_empty() { Err("Required".to_owned()) } else { Ok(()) })
+    ///         .validator(|text| match text.parse::<u32>() {
+    ///             Ok(_) => Ok(()),
+    ///             Err(err) => Err(err.to_string()),
+    ///         }),
+    /// );
+    /// # });
+    /// ```
+    #[inline]
+    pub fn validator(mut self, validator: impl Fn(&str) -> Result<(), String> + 't) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    /// Only change the text when the user presses enter.
+    ///
+    /// Until then the edits are made to a draft,
+    /// which is thrown away if the [`TextEdit`] loses focus in any other way, e.g. by pressing escape.
+    /// Invalid text (see [`Self::validator`]) is not committed; the [`TextEdit`] keeps the focus instead.
+    ///
+    /// [`Response::changed`] is only true on the frame the text is committed.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn commit_on_enter(mut self, commit_on_enter: bool) -> Self {
+        self.commit_on_enter = commit_on_enter;
+        self
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -423,7 +516,7 @@ impl TextEdit<'_> {
         let where_to_put_background = ui.painter().add(Shape::Noop);
         let background_color = self
             .background_color
-            .unwrap_or(ui.visuals().extreme_bg_color);
+            .unwrap_or(ui.visuals().color(ColorRole::SurfaceSunken));
         let output = self.show_content(ui);
 
         if frame {
@@ -460,6 +553,28 @@ impl TextEdit<'_> {
             ui.painter().set(where_to_put_background, shape);
         }
 
+        if let Some(validation_error) = &output.validation_error {
+            let error_color = ui.visuals().color(ColorRole::Error);
+            let stroke = Stroke::new(1.0, error_color);
+            let expansion = ui.style().interact(&output.response).expansion;
+            let rect = output.response.rect.expand(expansion + stroke.width / 2.0);
+            let outline = [
+                rect.left_top(),
+                rect.right_top(),
+                rect.right_bottom(),
+                rect.left_bottom(),
+                rect.left_top(),
+            ];
+            ui.painter()
+                .extend(Shape::dashed_line(&outline, stroke, 4.0, 2.0));
+
+            if output.response.has_focus() || output.response.hovered() {
+                output
+                    .response
+                    .show_tooltip_text(crate::RichText::new(validation_error).color(error_color));
+            }
+        }
+
         output
     }
 
@@ -488,6 +603,10 @@ impl TextEdit<'_> {
             char_limit,
             return_key,
             background_color: _,
+            spell_checker,
+            input_mask,
+            validators,
+            commit_on_enter,
         } = self;
 
         let text_color = text_color
@@ -495,7 +614,32 @@ impl TextEdit<'_> {
             // .unwrap_or_else(|| ui.style().interact(&response).text_color()); // too bright
             .unwrap_or_else(|| ui.visuals().widgets.inactive.text_color());
 
+        let id = id.unwrap_or_else(|| {
+            if let Some(id_salt) = id_salt {
+                ui.make_persistent_id(id_salt)
+            } else {
+                ui.next_auto_id() // Since we are only storing the cursor a persistent Id is not super important
+            }
+        });
+
+        // With `commit_on_enter` we edit a draft, and only write it to the text when the user presses enter:
+        let draft_id = id.with("draft");
+        let mut draft = (commit_on_enter && text.is_mutable()).then(|| {
+            let has_focus = ui.memory(|mem| mem.has_focus(id));
+            ui.data(|data| data.get_temp::<String>(draft_id))
+                .filter(|_| has_focus)
+                .unwrap_or_else(|| text.as_str().to_owned())
+        });
+        let (text, committed_text): (&mut dyn TextBuffer, _) = match &mut draft {
+            Some(draft) => (draft, Some(text)),
+            None => (text, None),
+        };
+
         let prev_text = text.as_str().to_owned();
+        let hint_text = match &input_mask {
+            Some(input_mask) if hint_text.is_empty() => input_mask.placeholder().into(),
+            _ => hint_text,
+        };
         let hint_text_str = hint_text.text().to_owned();
 
         let font_id = font_selection.resolve(ui.style());
@@ -532,16 +676,9 @@ impl TextEdit<'_> {
         let desired_height = (desired_height_rows.at_least(1) as f32) * row_height;
         let desired_inner_size = vec2(desired_inner_width, galley.size().y.max(desired_height));
         let desired_outer_size = (desired_inner_size + margin.sum()).at_least(min_size);
-        let (auto_id, outer_rect) = ui.allocate_space(desired_outer_size);
+        let (_, outer_rect) = ui.allocate_space(desired_outer_size);
         let rect = outer_rect - margin; // inner rect (excluding frame/margin).
 
-        let id = id.unwrap_or_else(|| {
-            if let Some(id_salt) = id_salt {
-                ui.make_persistent_id(id_salt)
-            } else {
-                auto_id // Since we are only storing the cursor a persistent Id is not super important
-            }
-        });
         let mut state = TextEditState::load(ui.ctx(), id).unwrap_or_default();
 
         // On touch screens (e.g. mobile in `eframe` web), should
@@ -644,6 +781,27 @@ impl TextEdit<'_> {
             cursor_range = Some(new_cursor_range);
         }
 
+        if let Some(input_mask) = &input_mask {
+            if text.is_mutable() {
+                let cursor_index =
+                    cursor_range.map_or(0, |cursor_range| cursor_range.primary.index);
+                let (masked, cursor_index) =
+                    input_mask.format_with_cursor(text.as_str(), cursor_index);
+                if masked != text.as_str() {
+                    text.replace_with(&masked);
+                    galley = layouter(ui, text.as_str(), wrap_width);
+                    if cursor_range.is_some() {
+                        let new_cursor_range = CCursorRange::one(CCursor::new(cursor_index));
+                        state.cursor.set_char_range(Some(new_cursor_range));
+                        cursor_range = Some(new_cursor_range);
+                    }
+                    response.mark_changed();
+                }
+            }
+        }
+
+        let validation_error = validate(ui, text.as_str(), input_mask.as_ref(), &validators);
+
         let mut galley_pos = align
             .align_size_within_rect(galley.size(), rect)
             .intersect(rect) // limit pos to the response rect area
@@ -678,6 +836,18 @@ impl TextEdit<'_> {
             state.singleline_offset = align_offset;
         }
 
+        let spell_diagnostics = match spell_checker {
+            Some(spell_checker) if !password => {
+                spell_diagnostics(ui, id, text.as_str(), spell_checker)
+            }
+            _ => Default::default(),
+        };
+        if !spell_diagnostics.is_empty() && galley.job.text == text.as_str() {
+            let stroke = Stroke::new(1.0, ui.visuals().color(ColorRole::Error));
+            let job = spell_check::underline_diagnostics(&galley.job, &spell_diagnostics, stroke);
+            galley = ui.fonts(|f| f.layout_job(job));
+        }
+
         let selection_changed = if let (Some(cursor_range), Some(prev_cursor_range)) =
             (cursor_range, prev_cursor_range)
         {
@@ -688,7 +858,7 @@ impl TextEdit<'_> {
 
         if ui.is_rect_visible(rect) {
             if text.as_str().is_empty() && !hint_text.is_empty() {
-                let hint_text_color = ui.visuals().weak_text_color();
+                let hint_text_color = ui.visuals().color(ColorRole::TextWeak);
                 let hint_text_font_id = hint_text_font.unwrap_or(font_id.into());
                 let galley = if multiline {
                     hint_text.into_galley(
@@ -784,6 +954,17 @@ impl TextEdit<'_> {
             }
         }
 
+        if interactive && text.is_mutable() && spell_checker.is_some() {
+            spell_suggestion_menu(
+                ui,
+                &mut response,
+                text,
+                &galley,
+                galley_pos,
+                &spell_diagnostics,
+            );
+        }
+
         // Ensures correct IME behavior when the text input area gains or loses focus.
         if state.ime_enabled && (response.gained_focus() || response.lost_focus()) {
             state.ime_enabled = false;
@@ -794,6 +975,25 @@ impl TextEdit<'_> {
             ui.input_mut(|i| i.events.retain(|e| !matches!(e, Event::Ime(_))));
         }
 
+        if let Some(committed_text) = committed_text {
+            let committed = response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            let changed =
+                committed && validation_error.is_none() && committed_text.as_str() != text.as_str();
+            if committed && validation_error.is_some() {
+                ui.memory_mut(|mem| mem.request_focus(id));
+            }
+            if changed {
+                committed_text.replace_with(text.as_str());
+            }
+            if ui.memory(|mem| mem.has_focus(id)) {
+                let draft = text.as_str().to_owned();
+                ui.data_mut(|data| data.insert_temp(draft_id, draft));
+            } else {
+                ui.data_mut(|data| data.remove::<String>(draft_id));
+            }
+            response.flags.set(response::Flags::CHANGED, changed);
+        }
+
         state.clone().store(ui.ctx(), id);
 
         if response.changed() {
@@ -852,10 +1052,31 @@ impl TextEdit<'_> {
             text_clip_rect,
             state,
             cursor_range,
+            validation_error,
         }
     }
 }
 
+/// The first problem found with the text, if any.
+fn validate(
+    ui: &Ui,
+    text: &str,
+    input_mask: Option<&InputMask>,
+    validators: &[Validator<'_>],
+) -> Option<String> {
+    if let Some(input_mask) = input_mask {
+        if !text.is_empty() && !input_mask.is_complete(text) {
+            return Some(
+                ui.translate("Expected {placeholder}")
+                    .replace("{placeholder}", &input_mask.placeholder()),
+            );
+        }
+    }
+    validators
+        .iter()
+        .find_map(|validator| validator(text).err())
+}
+
 fn mask_if_password(is_password: bool, text: &str) -> String {
     fn mask_password(text: &str) -> String {
         std::iter::repeat(epaint::text::PASSWORD_REPLACEMENT_CHAR)
@@ -870,6 +1091,92 @@ fn mask_if_password(is_password: bool, text: &str) -> String {
     }
 }
 
+/// The problems found by the spell checker, only checking the text again when it has changed.
+fn spell_diagnostics(
+    ui: &Ui,
+    id: Id,
+    text: &str,
+    spell_checker: &dyn SpellChecker,
+) -> Arc<Vec<SpellDiagnostic>> {
+    let cache_id = id.with("spell_check");
+    let cached = ui.data(|data| data.get_temp::<(String, Arc<Vec<SpellDiagnostic>>)>(cache_id));
+    if let Some((checked_text, diagnostics)) = cached {
+        if checked_text == text {
+            return diagnostics;
+        }
+    }
+
+    let mut diagnostics = spell_checker.check(&spell_check::words(text));
+    diagnostics.retain(|diagnostic| {
+        let range = &diagnostic.byte_range;
+        range.start < range.end && text.get(range.clone()).is_some()
+    });
+    let diagnostics = Arc::new(diagnostics);
+    ui.data_mut(|data| data.insert_temp(cache_id, (text.to_owned(), diagnostics.clone())));
+    diagnostics
+}
+
+/// Show the suggestions for the problem the user right-clicked, and replace it with the chosen one.
+fn spell_suggestion_menu(
+    ui: &Ui,
+    response: &mut Response,
+    text: &mut dyn TextBuffer,
+    galley: &Galley,
+    galley_pos: Pos2,
+    diagnostics: &[SpellDiagnostic],
+) {
+    let menu_id = response.id.with("spell_suggestions");
+
+    if response.secondary_clicked() {
+        let clicked = response.interact_pointer_pos().and_then(|pointer_pos| {
+            let ccursor = galley.cursor_from_pos(pointer_pos - galley_pos);
+            let byte_index = text.byte_index_from_char_index(ccursor.index);
+            diagnostics
+                .iter()
+                .find(|diagnostic| {
+                    diagnostic.byte_range.start <= byte_index
+                        && byte_index <= diagnostic.byte_range.end
+                })
+                .cloned()
+        });
+        ui.data_mut(|data| match clicked {
+            Some(diagnostic) => data.insert_temp(menu_id, diagnostic),
+            None => data.remove::<SpellDiagnostic>(menu_id),
+        });
+    }
+
+    let Some(diagnostic) = ui.data(|data| data.get_temp::<SpellDiagnostic>(menu_id)) else {
+        return;
+    };
+
+    let mut replacement = None;
+    response.context_menu(|ui| {
+        if diagnostic.suggestions.is_empty() {
+            ui.weak(ui.translate("No suggestions"));
+        }
+        for suggestion in &diagnostic.suggestions {
+            if ui.button(suggestion).clicked() {
+                replacement = Some(suggestion.clone());
+                ui.close();
+            }
+        }
+    });
+
+    let Some(replacement) = replacement else {
+        return;
+    };
+    let byte_range = diagnostic.byte_range;
+    if text.as_str().get(byte_range.clone()).is_none() {
+        return; // The text has changed since the menu was opened
+    }
+    let start = text.as_str()[..byte_range.start].chars().count();
+    let end = start + text.as_str()[byte_range].chars().count();
+    text.delete_char_range(start..end);
+    text.insert_text(&replacement, start);
+    ui.data_mut(|data| data.remove::<SpellDiagnostic>(menu_id));
+    response.mark_changed();
+}
+
 // ----------------------------------------------------------------------------
 
 /// Check for (keyboard) events to edit the cursor and/or text.
@@ -1207,3 +1514,33 @@ fn check_for_mutating_key_press(
         _ => None,
     }
 }
+
+#[test]
+fn test_validate_translates_the_whole_message() {
+    use crate::{Context, Id, Style, UiBuilder};
+
+    let ctx = Context::default();
+    ctx.set_translator(|locale, text| match (locale, text) {
+        ("ja", "Expected {placeholder}") => Some("{placeholder} が必要です".to_owned()),
+        _ => None,
+    });
+    let mask = InputMask::new("99:99");
+
+    let ui = Ui::new(ctx.clone(), Id::new("en"), UiBuilder::new());
+    assert_eq!(
+        validate(&ui, "12", Some(&mask), &[]).as_deref(),
+        Some("Expected __:__")
+    );
+
+    let style = Style {
+        locale: "ja".to_owned(),
+        ..Default::default()
+    };
+    let ui = Ui::new(ctx, Id::new("ja"), UiBuilder::new().style(style));
+    assert_eq!(
+        validate(&ui, "12", Some(&mask), &[]).as_deref(),
+        Some("__:__ が必要です"),
+        "The value goes where the translation puts it"
+    );
+    assert_eq!(validate(&ui, "12:34", Some(&mask), &[]), None);
+}
@@ -0,0 +1,197 @@
+/// Restricts the text of a [`super::TextEdit`] to a fixed format, like a phone number or a date.
+///
+/// The pattern is made of slots that the user fills in,
+/// and literal characters that are inserted as the user types:
+/// * `9`: a digit
+/// * `a`: a letter
+/// * `h`: a hexadecimal digit
+/// * `*`: a letter or a digit
+/// * `\`: the next character is a literal, e.g. `\9`
+/// * anything else is a literal
+///
+/// See [`super::TextEdit::input_mask`].
+///
+/// ```
+/// # use egui::InputMask;
+/// let mask = InputMask::new("99:99");
+/// assert_eq!(mask.format("1234"), "12:34");
+/// assert_eq!(mask.format("1x2"), "12");
+/// assert!(mask.is_complete("12:34"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputMask {
+    pattern: Vec<MaskChar>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MaskChar {
+    Digit,
+    Letter,
+    HexDigit,
+    Alphanumeric,
+    Literal(char),
+}
+
+impl MaskChar {
+    fn accepts(self, c: char) -> bool {
+        match self {
+            Self::Digit => c.is_ascii_digit(),
+            Self::Letter => c.is_alphabetic(),
+            Self::HexDigit => c.is_ascii_hexdigit(),
+            Self::Alphanumeric => c.is_alphanumeric(),
+            Self::Literal(literal) => c == literal,
+        }
+    }
+
+    fn is_literal(self) -> bool {
+        matches!(self, Self::Literal(_))
+    }
+}
+
+impl InputMask {
+    /// See [`Self`] for the syntax of the pattern.
+    pub fn new(pattern: &str) -> Self {
+        let mut chars = pattern.chars();
+        let mut mask = vec![];
+        while let Some(c) = chars.next() {
+            mask.push(match c {
+                '9' => MaskChar::Digit,
+                'a' => MaskChar::Letter,
+                'h' => MaskChar::HexDigit,
+                '*' => MaskChar::Alphanumeric,
+                '\\' => MaskChar::Literal(chars.next().unwrap_or('\\')),
+                c => MaskChar::Literal(c),
+            });
+        }
+        Self { pattern: mask }
+    }
+
+    /// A North American phone number, like `(555) 123-4567`.
+    pub fn phone() -> Self {
+        Self::new("(999) 999-9999")
+    }
+
+    /// An ISO 8601 date, like `2024-12-31`.
+    pub fn date() -> Self {
+        Self::new("9999-99-99")
+    }
+
+    /// A hexadecimal RGB color, like `#ff8000`.
+    pub fn hex_color() -> Self {
+        Self::new("#hhhhhh")
+    }
+
+    /// The pattern with `_` for every slot, e.g. `(___) ___-____`.
+    pub fn placeholder(&self) -> String {
+        self.pattern
+            .iter()
+            .map(|mask_char| match mask_char {
+                MaskChar::Literal(c) => *c,
+                _ => '_',
+            })
+            .collect()
+    }
+
+    /// Format the text according to the mask.
+    ///
+    /// Characters that don't fit are dropped, and literals are inserted where needed.
+    pub fn format(&self, text: &str) -> String {
+        self.format_with_cursor(text, 0).0
+    }
+
+    /// Is the text formatted according to the mask, with all slots filled in?
+    pub fn is_complete(&self, text: &str) -> bool {
+        text.chars().count() == self.pattern.len() && self.format(text) == text
+    }
+
+    /// Like [`Self::format`], but also moves the cursor (a char index) along with the characters around it.
+    pub fn format_with_cursor(&self, text: &str, cursor: usize) -> (String, usize) {
+        let mut formatted = vec![];
+        let mut new_cursor = 0;
+
+        // How much of `formatted` to keep, i.e. excluding literals not followed by anything:
+        let mut keep = 0;
+
+        let mut input = text.chars().enumerate().peekable();
+        for (pattern_index, &mask_char) in self.pattern.iter().enumerate() {
+            if input.peek().is_none() {
+                // End on the literals that finish the pattern, so that it can be complete:
+                let rest = &self.pattern[pattern_index..];
+                if keep > 0 && rest.iter().all(|mask_char| mask_char.is_literal()) {
+                    formatted.extend(rest.iter().filter_map(|mask_char| match mask_char {
+                        MaskChar::Literal(c) => Some(*c),
+                        _ => None,
+                    }));
+                    keep = formatted.len();
+                }
+                break;
+            }
+
+            if let MaskChar::Literal(literal) = mask_char {
+                formatted.push(literal);
+                if let Some((index, _)) = input.next_if(|&(_, c)| c == literal) {
+                    keep = formatted.len();
+                    if index < cursor {
+                        new_cursor = keep;
+                    }
+                }
+                continue;
+            }
+
+            // Fill the slot with the next character that fits:
+            for (index, c) in input.by_ref() {
+                let fits = mask_char.accepts(c);
+                if fits {
+                    formatted.push(c);
+                    keep = formatted.len();
+                }
+                if index < cursor {
+                    new_cursor = formatted.len();
+                }
+                if fits {
+                    break;
+                }
+            }
+        }
+
+        formatted.truncate(keep);
+        (formatted.into_iter().collect(), new_cursor.min(keep))
+    }
+}
+
+#[test]
+fn test_input_mask() {
+    let phone = InputMask::phone();
+    assert_eq!(phone.format(""), "");
+    assert_eq!(phone.format("5"), "(5");
+    assert_eq!(phone.format("555"), "(555");
+    assert_eq!(phone.format("5551"), "(555) 1");
+    assert_eq!(phone.format("(555) 123-4567"), "(555) 123-4567");
+    assert_eq!(phone.format("555 123 4567 89"), "(555) 123-4567");
+    assert!(phone.is_complete("(555) 123-4567"));
+    assert!(!phone.is_complete("(555) 123-456"));
+    assert_eq!(phone.placeholder(), "(___) ___-____");
+
+    let date = InputMask::date();
+    assert_eq!(date.format("2024"), "2024");
+    assert_eq!(date.format("2024-"), "2024-");
+    assert_eq!(date.format("2024x"), "2024");
+    assert_eq!(date.format("20241231"), "2024-12-31");
+
+    let color = InputMask::hex_color();
+    assert_eq!(color.format("ff8g00"), "#ff800");
+    assert!(color.is_complete("#FF8000"));
+
+    // Typing a digit right after a full group inserts the literal before it:
+    assert_eq!(
+        date.format_with_cursor("20245", 5),
+        ("2024-5".to_owned(), 6)
+    );
+    // Dropped characters don't move the cursor:
+    assert_eq!(date.format_with_cursor("20x24", 3), ("2024".to_owned(), 2));
+
+    let percent = InputMask::new("99\\%");
+    assert_eq!(percent.format("12"), "12%");
+    assert_eq!(percent.format("1"), "1");
+    assert!(percent.is_complete("12%"));
+}
@@ -1,9 +1,16 @@
 mod builder;
+mod input_mask;
 mod output;
+mod spell_check;
 mod state;
 mod text_buffer;
 
 pub use {
-    crate::text_selection::TextCursorState, builder::TextEdit, output::TextEditOutput,
-    state::TextEditState, text_buffer::TextBuffer,
+    crate::text_selection::TextCursorState,
+    builder::TextEdit,
+    input_mask::InputMask,
+    output::TextEditOutput,
+    spell_check::{SpellChecker, SpellDiagnostic},
+    state::TextEditState,
+    text_buffer::TextBuffer,
 };
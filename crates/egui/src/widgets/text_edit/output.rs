@@ -21,6 +21,10 @@ pub struct TextEditOutput {
 
     /// Where the text cursor is.
     pub cursor_range: Option<CCursorRange>,
+
+    /// The first problem found with the text by [`TextEdit::validator`](crate::TextEdit::validator)
+    /// or [`TextEdit::input_mask`](crate::TextEdit::input_mask), if any.
+    pub validation_error: Option<String>,
 }
 
 impl TextEditOutput {
@@ -0,0 +1,165 @@
+use std::ops::Range;
+
+use epaint::{
+    text::{LayoutJob, LayoutSection, UnderlineStyle},
+    Stroke,
+};
+
+/// A problem with some of the text in a [`super::TextEdit`], usually a misspelled word.
+///
+/// Returned by a [`SpellChecker`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpellDiagnostic {
+    /// The bytes of the text with the problem, usually those of a whole word.
+    pub byte_range: Range<usize>,
+
+    /// Replacements to offer when the user right-clicks the problem, best first.
+    pub suggestions: Vec<String>,
+}
+
+/// Checks the spelling of the text in a [`super::TextEdit`].
+///
+/// The problems found are shown with wavy underlines,
+/// and right-clicking one of them shows a menu of suggested replacements.
+///
+/// See [`super::TextEdit::spell_checker`].
+///
+/// Any `Fn(&str) -> Option<Vec<String>>` is a [`SpellChecker`] that checks one word at a time,
+/// returning the suggestions for misspelled words.
+pub trait SpellChecker {
+    /// Check the words of the text.
+    ///
+    /// Each word comes with its byte range in the text.
+    /// Returns the problems found, in any order.
+    fn check(&self, words: &[(Range<usize>, &str)]) -> Vec<SpellDiagnostic>;
+}
+
+impl<F> SpellChecker for F
+where
+    F: Fn(&str) -> Option<Vec<String>>,
+{
+    fn check(&self, words: &[(Range<usize>, &str)]) -> Vec<SpellDiagnostic> {
+        words
+            .iter()
+            .filter_map(|(byte_range, word)| {
+                self(word).map(|suggestions| SpellDiagnostic {
+                    byte_range: byte_range.clone(),
+                    suggestions,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The words of the text, with their byte ranges.
+///
+/// Apostrophes between letters are part of the word, like in "don't".
+pub(super) fn words(text: &str) -> Vec<(Range<usize>, &str)> {
+    let is_word_char = |c: char| c.is_alphanumeric();
+    let is_apostrophe = |c: char| c == '\'' || c == '’';
+
+    let mut words = vec![];
+    let mut start = None;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let next_is_word_char = chars.peek().is_some_and(|&(_, next)| is_word_char(next));
+        let in_word = is_word_char(c) || (start.is_some() && is_apostrophe(c) && next_is_word_char);
+        match (start, in_word) {
+            (None, true) => start = Some(i),
+            (Some(word_start), false) => {
+                words.push((word_start..i, &text[word_start..i]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(word_start) = start {
+        words.push((word_start..text.len(), &text[word_start..]));
+    }
+    words
+}
+
+/// Underline the text of the diagnostics with wavy lines.
+pub(super) fn underline_diagnostics(
+    job: &LayoutJob,
+    diagnostics: &[SpellDiagnostic],
+    stroke: Stroke,
+) -> LayoutJob {
+    let mut sections = vec![];
+    for section in &job.sections {
+        let mut start = section.byte_range.start;
+        let mut leading_space = section.leading_space;
+        while start < section.byte_range.end {
+            // Split the section where an underline starts or ends:
+            let underlined = diagnostics
+                .iter()
+                .find(|diagnostic| diagnostic.byte_range.contains(&start));
+            let end = if let Some(diagnostic) = underlined {
+                diagnostic.byte_range.end
+            } else {
+                diagnostics
+                    .iter()
+                    .map(|diagnostic| diagnostic.byte_range.start)
+                    .filter(|&diagnostic_start| start < diagnostic_start)
+                    .min()
+                    .unwrap_or(usize::MAX)
+            };
+            let end = end.min(section.byte_range.end);
+
+            let mut format = section.format.clone();
+            if underlined.is_some() {
+                format.underline = stroke;
+                format.underline_style = UnderlineStyle::Wavy;
+            }
+            sections.push(LayoutSection {
+                leading_space,
+                byte_range: start..end,
+                format,
+            });
+            leading_space = 0.0;
+            start = end;
+        }
+    }
+
+    LayoutJob {
+        sections,
+        ..job.clone()
+    }
+}
+
+#[test]
+fn test_spell_check() {
+    let text = "Thsi isn't  wrnog, 'quoted'";
+    let words = words(text);
+    assert_eq!(
+        words.iter().map(|(_, word)| *word).collect::<Vec<_>>(),
+        ["Thsi", "isn't", "wrnog", "quoted"]
+    );
+    assert!(words
+        .iter()
+        .all(|(range, word)| &text[range.clone()] == *word));
+
+    let checker = |word: &str| match word {
+        "Thsi" => Some(vec!["This".to_owned()]),
+        "wrnog" => Some(vec!["wrong".to_owned(), "wrung".to_owned()]),
+        _ => None,
+    };
+    let diagnostics = checker.check(&words);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(&text[diagnostics[1].byte_range.clone()], "wrnog");
+
+    let job = LayoutJob::single_section(text.to_owned(), Default::default());
+    let stroke = Stroke::new(1.0, epaint::Color32::RED);
+    let underlined = underline_diagnostics(&job, &diagnostics, stroke);
+    let underlined_text: Vec<&str> = underlined
+        .sections
+        .iter()
+        .filter(|section| section.format.underline == stroke)
+        .map(|section| &text[section.byte_range.clone()])
+        .collect();
+    assert_eq!(underlined_text, ["Thsi", "wrnog"]);
+    assert_eq!(
+        underlined.sections.last().unwrap().byte_range.end,
+        text.len()
+    );
+}
@@ -0,0 +1,443 @@
+use std::collections::VecDeque;
+
+use crate::{
+    pos2, vec2, Color32, ColorImage, Context, Mesh, MeshShader, MeshShaderId, Rect, Response,
+    Sense, Shape, TextureHandle, TextureOptions, Ui, Vec2,
+};
+
+/// The conversion matrix used for [`VideoFrame::Yuv420`] frames.
+///
+/// Both assume limited ("studio") range, i.e. luma in `16..=235` and chroma in `16..=240`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum YuvMatrix {
+    /// ITU-R BT.601, used by most SD video.
+    Bt601,
+
+    /// ITU-R BT.709, used by most HD video.
+    #[default]
+    Bt709,
+}
+
+impl YuvMatrix {
+    /// The rows of the affine transform from gamma `[y, u, v, 1]` in `0..=1` to gamma RGB.
+    pub fn rows(self) -> [[f32; 4]; 3] {
+        let (r_v, g_u, g_v, b_u) = match self {
+            Self::Bt601 => (1.402, -0.344_136, -0.714_136, 1.772),
+            Self::Bt709 => (1.5748, -0.187_324, -0.468_124, 1.8556),
+        };
+        let y_scale = 255.0 / 219.0;
+        let c_scale = 255.0 / 224.0;
+        let y_offset = -y_scale * 16.0 / 255.0;
+        let c_offset = -c_scale * 128.0 / 255.0;
+
+        let row = |u: f32, v: f32| {
+            [
+                y_scale,
+                u * c_scale,
+                v * c_scale,
+                y_offset + (u + v) * c_offset,
+            ]
+        };
+        [row(0.0, r_v), row(g_u, g_v), row(b_u, 0.0)]
+    }
+
+    /// Convert a single YUV sample to RGB on the CPU.
+    pub fn to_rgb(self, y: u8, u: u8, v: u8) -> Color32 {
+        let input = [y, u, v].map(|c| c as f32 / 255.0);
+        let [r, g, b] = self.rows().map(|row| {
+            let value = row[0] * input[0] + row[1] * input[1] + row[2] * input[2] + row[3];
+            (value.clamp(0.0, 1.0) * 255.0).round() as u8
+        });
+        Color32::from_rgb(r, g, b)
+    }
+
+    /// The [`Self::rows`] as uniforms for the YUV mesh shader of the backend.
+    fn uniforms(self) -> Vec<u8> {
+        self.rows()
+            .iter()
+            .flatten()
+            .flat_map(|value| value.to_ne_bytes())
+            .collect()
+    }
+}
+
+/// A decoded video frame, ready to be pushed to a [`VideoSurface`].
+#[derive(Clone, Debug)]
+pub enum VideoFrame {
+    /// A frame that is already RGB.
+    Rgba(ColorImage),
+
+    /// A planar YUV 4:2:0 frame, as produced by most video decoders.
+    ///
+    /// The planes are tightly packed: the `y` plane has `width * height` samples,
+    /// and the `u` and `v` planes each have `width.div_ceil(2) * height.div_ceil(2)` samples.
+    Yuv420 {
+        /// `[width, height]` of the luma plane.
+        size: [usize; 2],
+        y: Vec<u8>,
+        u: Vec<u8>,
+        v: Vec<u8>,
+    },
+}
+
+impl From<ColorImage> for VideoFrame {
+    #[inline]
+    fn from(image: ColorImage) -> Self {
+        Self::Rgba(image)
+    }
+}
+
+impl VideoFrame {
+    /// `[width, height]` of the frame in pixels.
+    pub fn size(&self) -> [usize; 2] {
+        match self {
+            Self::Rgba(image) => image.size,
+            Self::Yuv420 { size, .. } => *size,
+        }
+    }
+
+    /// Turn the frame into an image, either converting YUV to RGB,
+    /// or packing the (upsampled) Y, U and V samples into the R, G and B channels.
+    ///
+    /// # Panics
+    /// If the planes of a [`Self::Yuv420`] frame are too small.
+    fn into_image(self, matrix: Option<YuvMatrix>) -> ColorImage {
+        match self {
+            Self::Rgba(image) => image,
+            Self::Yuv420 { size, y, u, v } => {
+                let [width, height] = size;
+                let chroma_width = width.div_ceil(2);
+                assert!(
+                    y.len() >= width * height
+                        && u.len() >= chroma_width * height.div_ceil(2)
+                        && v.len() >= chroma_width * height.div_ceil(2),
+                    "YUV planes are too small for a {width}x{height} frame"
+                );
+
+                let mut pixels = Vec::with_capacity(width * height);
+                for row in 0..height {
+                    for col in 0..width {
+                        let luma = y[row * width + col];
+                        let chroma = (row / 2) * chroma_width + col / 2;
+                        pixels.push(match matrix {
+                            Some(matrix) => matrix.to_rgb(luma, u[chroma], v[chroma]),
+                            None => Color32::from_rgb(luma, u[chroma], v[chroma]),
+                        });
+                    }
+                }
+                ColorImage { size, pixels }
+            }
+        }
+    }
+}
+
+struct QueuedFrame {
+    pts: f64,
+    size: [usize; 2],
+    texture: TextureHandle,
+
+    /// The texture holds packed YUV, to be converted by [`VideoSurface::yuv_shader`].
+    packed_yuv: bool,
+}
+
+/// Shows a stream of video frames, picking the right one for the current playback time.
+///
+/// Unlike most widgets this is not a builder: keep it around in your app state,
+/// push decoded frames with [`Self::push_frame`] as they arrive,
+/// and call [`Self::show`] each pass with the current playback clock.
+///
+/// Frames are uploaded into a small ring of textures that is reused,
+/// so playing a video doesn't allocate a new texture per frame.
+/// The frame is letterboxed to keep its aspect ratio,
+/// and a repaint is scheduled for when the next frame is due.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut surface = egui::VideoSurface::new("video");
+/// # let clock = 0.0;
+/// # let decoded = egui::ColorImage::new([16, 9], egui::Color32::RED);
+/// surface.push_frame(ui.ctx(), 0.0, decoded);
+/// surface.show(ui, clock);
+/// # });
+/// ```
+///
+/// ## YUV
+/// [`VideoFrame::Yuv420`] frames are converted to RGB on the CPU by default.
+/// If your backend supports custom mesh shaders, you can instead let the GPU do the conversion
+/// by registering the backend's YUV shader (e.g. `egui_wgpu::YUV_MESH_SHADER`)
+/// and passing its id to [`Self::yuv_shader`].
+pub struct VideoSurface {
+    name: String,
+    capacity: usize,
+    options: TextureOptions,
+    yuv_matrix: YuvMatrix,
+    yuv_shader: Option<MeshShaderId>,
+    letterbox_color: Color32,
+
+    /// Sorted by presentation time.
+    frames: VecDeque<QueuedFrame>,
+
+    /// Textures of frames that are no longer needed, waiting to be reused.
+    spare: Vec<TextureHandle>,
+}
+
+impl VideoSurface {
+    /// The default [`Self::capacity`].
+    pub const DEFAULT_CAPACITY: usize = 4;
+
+    /// The name is used for debugging the textures.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            capacity: Self::DEFAULT_CAPACITY,
+            options: TextureOptions::LINEAR,
+            yuv_matrix: YuvMatrix::default(),
+            yuv_shader: None,
+            letterbox_color: Color32::BLACK,
+            frames: VecDeque::new(),
+            spare: Vec::new(),
+        }
+    }
+
+    /// How many frames to keep queued at most, including the one being shown.
+    ///
+    /// When pushing to a full queue, the oldest frame is dropped.
+    /// Default: [`Self::DEFAULT_CAPACITY`].
+    #[inline]
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// How to sample the frames.
+    ///
+    /// Default: [`TextureOptions::LINEAR`].
+    #[inline]
+    pub fn texture_options(mut self, options: TextureOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// How [`VideoFrame::Yuv420`] frames are converted to RGB.
+    #[inline]
+    pub fn yuv_matrix(mut self, yuv_matrix: YuvMatrix) -> Self {
+        self.yuv_matrix = yuv_matrix;
+        self
+    }
+
+    /// Convert [`VideoFrame::Yuv420`] frames on the GPU using this mesh shader.
+    ///
+    /// Only set this if the backend has registered its YUV shader under this id,
+    /// or the frames will be shown with garbled colors.
+    #[inline]
+    pub fn yuv_shader(mut self, yuv_shader: Option<MeshShaderId>) -> Self {
+        self.yuv_shader = yuv_shader;
+        self
+    }
+
+    /// The color of the bars around the frame when the aspect ratios don't match.
+    ///
+    /// Default: [`Color32::BLACK`].
+    #[inline]
+    pub fn letterbox_color(mut self, letterbox_color: Color32) -> Self {
+        self.letterbox_color = letterbox_color;
+        self
+    }
+
+    /// Number of queued frames.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Is the queue full? A decoder can use this to pace itself.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.frames.len() >= self.capacity
+    }
+
+    /// The presentation time of the last queued frame, if any.
+    pub fn last_pts(&self) -> Option<f64> {
+        self.frames.back().map(|frame| frame.pts)
+    }
+
+    /// Drop all queued frames, e.g. after seeking.
+    pub fn clear(&mut self) {
+        self.spare
+            .extend(self.frames.drain(..).map(|frame| frame.texture));
+    }
+
+    /// Queue a decoded frame, to be shown at the presentation time `pts` (in seconds).
+    ///
+    /// The frame is uploaded right away.
+    ///
+    /// # Panics
+    /// If the planes of a [`VideoFrame::Yuv420`] frame are too small for its size.
+    pub fn push_frame(&mut self, ctx: &Context, pts: f64, frame: impl Into<VideoFrame>) {
+        let frame = frame.into();
+        let size = frame.size();
+        let packed_yuv = self.yuv_shader.is_some() && matches!(frame, VideoFrame::Yuv420 { .. });
+        let image = frame.into_image((!packed_yuv).then_some(self.yuv_matrix));
+
+        while self.is_full() {
+            if let Some(oldest) = self.frames.pop_front() {
+                self.spare.push(oldest.texture);
+            }
+        }
+
+        let texture = if let Some(mut texture) = self.spare.pop() {
+            texture.set(image, self.options);
+            texture
+        } else {
+            ctx.load_texture(self.name.clone(), image, self.options)
+        };
+
+        let index = self.frames.partition_point(|frame| frame.pts <= pts);
+        self.frames.insert(
+            index,
+            QueuedFrame {
+                pts,
+                size,
+                texture,
+                packed_yuv,
+            },
+        );
+    }
+
+    /// The index of the frame to show at the playback time `clock`:
+    /// the last one that is due, or the first one if none are due yet.
+    fn frame_index(&self, clock: f64) -> Option<usize> {
+        if self.frames.is_empty() {
+            None
+        } else {
+            Some(
+                self.frames
+                    .partition_point(|frame| frame.pts <= clock)
+                    .max(1)
+                    - 1,
+            )
+        }
+    }
+
+    /// The presentation time of the frame that would be shown at the playback time `clock`.
+    pub fn pts_at(&self, clock: f64) -> Option<f64> {
+        self.frame_index(clock).map(|index| self.frames[index].pts)
+    }
+
+    /// Show the frame for the playback time `clock` (in seconds), filling the available space.
+    pub fn show(&mut self, ui: &mut Ui, clock: f64) -> Response {
+        self.show_sized(ui, ui.available_size(), clock)
+    }
+
+    /// Show the frame for the playback time `clock` (in seconds) in a widget of the given size.
+    pub fn show_sized(&mut self, ui: &mut Ui, size: Vec2, clock: f64) -> Response {
+        let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+        if ui.is_rect_visible(rect) {
+            self.paint_at(ui, rect, clock);
+        }
+        response
+    }
+
+    /// Paint the frame for the playback time `clock` (in seconds), letterboxed into `rect`.
+    ///
+    /// Frames older than the one shown are dropped, and a repaint is requested
+    /// for when the next frame is due.
+    pub fn paint_at(&mut self, ui: &Ui, rect: Rect, clock: f64) {
+        let Some(index) = self.frame_index(clock) else {
+            ui.painter().rect_filled(rect, 0.0, self.letterbox_color);
+            return;
+        };
+        for _ in 0..index {
+            if let Some(old) = self.frames.pop_front() {
+                self.spare.push(old.texture);
+            }
+        }
+
+        let frame = &self.frames[0];
+        let image_rect = letterbox_rect(rect, vec2(frame.size[0] as f32, frame.size[1] as f32));
+
+        let painter = ui.painter();
+        painter.rect_filled(rect, 0.0, self.letterbox_color);
+
+        let mut mesh = Mesh::with_texture(frame.texture.id());
+        mesh.add_rect_with_uv(
+            image_rect,
+            Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+            Color32::WHITE,
+        );
+        if let (true, Some(shader)) = (frame.packed_yuv, self.yuv_shader) {
+            mesh = mesh.with_shader(MeshShader {
+                id: shader,
+                uniforms: self.yuv_matrix.uniforms(),
+            });
+        }
+        painter.add(Shape::mesh(mesh));
+
+        if let Some(next) = self.frames.get(1) {
+            ui.ctx()
+                .request_repaint_after_secs((next.pts - clock).max(0.0) as f32);
+        }
+    }
+}
+
+/// The largest rectangle with the aspect ratio of `size`, centered in `rect`.
+fn letterbox_rect(rect: Rect, size: Vec2) -> Rect {
+    if size.x <= 0.0 || size.y <= 0.0 {
+        return rect;
+    }
+    let scale = (rect.width() / size.x).min(rect.height() / size.y);
+    Rect::from_center_size(rect.center(), size * scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yuv_matrix() {
+        for matrix in [YuvMatrix::Bt601, YuvMatrix::Bt709] {
+            assert_eq!(matrix.to_rgb(16, 128, 128), Color32::BLACK);
+            assert_eq!(matrix.to_rgb(235, 128, 128), Color32::WHITE);
+            let red = matrix.to_rgb(81, 90, 240);
+            assert!(red.r() > 200 && red.g() < 40 && red.b() < 40, "{red:?}");
+        }
+    }
+
+    #[test]
+    fn test_letterbox_rect() {
+        let rect = Rect::from_min_size(pos2(0.0, 0.0), vec2(200.0, 100.0));
+        assert_eq!(
+            letterbox_rect(rect, vec2(10.0, 10.0)),
+            Rect::from_min_max(pos2(50.0, 0.0), pos2(150.0, 100.0))
+        );
+    }
+
+    #[test]
+    fn test_frame_selection() {
+        let ctx = Context::default();
+        let mut surface = VideoSurface::new("test").capacity(3);
+        let image = || ColorImage::new([2, 2], Color32::RED);
+
+        assert_eq!(surface.pts_at(0.0), None);
+        surface.push_frame(&ctx, 0.2, image());
+        surface.push_frame(&ctx, 0.1, image());
+        surface.push_frame(&ctx, 0.3, image());
+        assert!(surface.is_full());
+
+        assert_eq!(surface.pts_at(0.0), Some(0.1));
+        assert_eq!(surface.pts_at(0.25), Some(0.2));
+        assert_eq!(surface.pts_at(1.0), Some(0.3));
+
+        // Pushing to a full queue drops the oldest frame and reuses its texture:
+        surface.push_frame(&ctx, 0.4, image());
+        assert_eq!(surface.len(), 3);
+        assert_eq!(surface.pts_at(0.0), Some(0.2));
+        assert_eq!(surface.last_pts(), Some(0.4));
+    }
+}
@@ -53,28 +53,27 @@ impl crate::View for SceneDemo {
             .show(ui, |ui| {
                 let scene = Scene::new()
                     .max_inner_size([350.0, 1000.0])
-                    .zoom_range(0.1..=2.0);
+                    .zoom_range(0.1..=2.0)
+                    .fit_on_double_click(true);
 
                 let mut reset_view = false;
                 let mut inner_rect = Rect::NAN;
-                let response = scene
-                    .show(ui, &mut self.scene_rect, |ui| {
-                        reset_view = ui.button("Reset view").clicked();
+                scene.show(ui, &mut self.scene_rect, |ui| {
+                    reset_view = ui.button("Reset view").clicked();
 
-                        ui.add_space(16.0);
+                    ui.add_space(16.0);
 
-                        self.widget_gallery.ui(ui);
+                    self.widget_gallery.ui(ui);
 
-                        ui.put(
-                            Rect::from_min_size(Pos2::new(0.0, -64.0), Vec2::new(200.0, 16.0)),
-                            egui::Label::new("You can put a widget anywhere").selectable(false),
-                        );
+                    ui.put(
+                        Rect::from_min_size(Pos2::new(0.0, -64.0), Vec2::new(200.0, 16.0)),
+                        egui::Label::new("You can put a widget anywhere").selectable(false),
+                    );
 
-                        inner_rect = ui.min_rect();
-                    })
-                    .response;
+                    inner_rect = ui.min_rect();
+                });
 
-                if reset_view || response.double_clicked() {
+                if reset_view {
                     self.scene_rect = inner_rect;
                 }
             });
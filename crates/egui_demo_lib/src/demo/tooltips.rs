@@ -113,5 +113,26 @@ impl Tooltips {
                 .on_hover_ui(tooltip_ui)
                 .on_disabled_hover_ui(disabled_tooltip_ui);
         });
+
+        ui.separator(); // ---------------------------------------------------------
+
+        ui.label("Tooltips can have a title, a body and a keyboard shortcut. These are in a group, so once one is shown, the others show up right away:");
+
+        ui.horizontal(|ui| {
+            use egui::{Key, KeyboardShortcut, Modifiers, Tooltip};
+
+            for (icon, title, body, key) in [
+                ("🗋", "New", "Create an empty document.", Key::N),
+                ("🗁", "Open", "Open a document from disk.", Key::O),
+                ("💾", "Save", "Write the document to disk.", Key::S),
+            ] {
+                ui.button(icon).on_hover_rich(
+                    Tooltip::rich(title)
+                        .body(body)
+                        .shortcut(KeyboardShortcut::new(Modifiers::COMMAND, key))
+                        .group("toolbar"),
+                );
+            }
+        });
     }
 }
@@ -10,6 +10,7 @@
 /// - `http` feature: `http(s)://` loader
 /// - `image` feature: Loader of png, jpeg etc using the [`image`] crate
 /// - `svg` feature: `.svg` loader
+/// - `gif`, `webp`, `apng` features: loaders of (animated) gif, webp and png images
 ///
 /// Calling this multiple times on the same [`egui::Context`] is safe.
 /// It will never install duplicate loaders.
@@ -54,6 +55,11 @@
 /// The content type specified by [`BytesPoll::Ready::mime`][`egui::load::BytesPoll::Ready::mime`] always takes precedence,
 /// and must include `svg` for it to be considered supported. For example, `image/svg+xml` would be loaded by the `svg` loader.
 ///
+/// The `gif`, `webp` and `apng` loaders are [`ImageLoader`][`egui::load::ImageLoader`]s for animated images.
+/// [`egui::Image`] asks them for the right frame of the animation, and repaints when the next frame is due,
+/// so `ui.image("file://foo.gif")` just animates.
+/// Animated PNGs usually have a plain `.png` extension, so they start animating once they have been loaded.
+///
 /// See [`egui::load`] for more information about how loaders work.
 pub fn install_image_loaders(ctx: &egui::Context) {
     #[cfg(all(not(target_arch = "wasm32"), feature = "file"))]
@@ -90,6 +96,12 @@ pub fn install_image_loaders(ctx: &egui::Context) {
         log::trace!("installed WebPLoader");
     }
 
+    #[cfg(feature = "apng")]
+    if !ctx.is_loader_installed(self::apng_loader::ApngLoader::ID) {
+        ctx.add_image_loader(std::sync::Arc::new(self::apng_loader::ApngLoader::default()));
+        log::trace!("installed ApngLoader");
+    }
+
     #[cfg(feature = "svg")]
     if !ctx.is_loader_installed(self::svg_loader::SvgLoader::ID) {
         ctx.add_image_loader(std::sync::Arc::new(self::svg_loader::SvgLoader::default()));
@@ -113,6 +125,8 @@ mod file_loader;
 #[cfg(feature = "http")]
 mod ehttp_loader;
 
+#[cfg(feature = "apng")]
+mod apng_loader;
 #[cfg(feature = "gif")]
 mod gif_loader;
 #[cfg(feature = "image")]
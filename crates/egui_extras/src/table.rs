@@ -900,7 +900,7 @@ impl Table<'_> {
                     ui.visuals().widgets.noninteractive.bg_stroke
                 };
 
-                ui.painter().line_segment([p0, p1], stroke);
+                ui.painter().hairline([p0, p1], stroke);
             }
 
             available_width -= *column_width + spacing_x;
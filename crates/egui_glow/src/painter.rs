@@ -86,11 +86,13 @@ pub struct Painter {
 
     program: glow::Program,
     u_screen_size: glow::UniformLocation,
+    u_output_display_p3: glow::UniformLocation,
     u_sampler: glow::UniformLocation,
     is_webgl_1: bool,
     vao: crate::vao::VertexArrayObject,
     srgb_textures: bool,
     supports_srgb_framebuffer: bool,
+    output_color_space: egui::ecolor::ColorSpace,
     vbo: glow::Buffer,
     element_array_buffer: glow::Buffer,
 
@@ -101,8 +103,14 @@ pub struct Painter {
     /// Stores outdated OpenGL textures that are yet to be deleted
     textures_to_destroy: Vec<glow::Texture>,
 
+    /// Render these into their textures in the next call to [`Self::paint_and_update_textures`].
+    pending_snapshots: Vec<egui::TextureSnapshot>,
+
     /// Used to make sure we are destroyed correctly.
     destroyed: bool,
+
+    /// Resources shared by all [`egui::BackendCallback`]s.
+    pub callback_resources: egui::CallbackResources,
 }
 
 /// A callback function that can be used to compose an [`egui::PaintCallback`] for custom rendering
@@ -217,6 +225,9 @@ impl Painter {
             gl.delete_shader(vert);
             gl.delete_shader(frag);
             let u_screen_size = gl.get_uniform_location(program, "u_screen_size").unwrap();
+            let u_output_display_p3 = gl
+                .get_uniform_location(program, "u_output_display_p3")
+                .unwrap();
             let u_sampler = gl.get_uniform_location(program, "u_sampler").unwrap();
 
             let vbo = gl.create_buffer()?;
@@ -263,17 +274,21 @@ impl Painter {
                 max_texture_side,
                 program,
                 u_screen_size,
+                u_output_display_p3,
                 u_sampler,
                 is_webgl_1,
                 vao,
                 srgb_textures,
                 supports_srgb_framebuffer,
+                output_color_space: Default::default(),
                 vbo,
                 element_array_buffer,
                 textures: Default::default(),
                 next_native_tex_id: 1 << 32,
                 textures_to_destroy: Vec::new(),
+                pending_snapshots: Vec::new(),
                 destroyed: false,
+                callback_resources: Default::default(),
             })
         }
     }
@@ -287,6 +302,16 @@ impl Painter {
         self.max_texture_side
     }
 
+    /// Set the color space of the framebuffer, e.g. [`egui::ecolor::ColorSpace::DisplayP3`]
+    /// if the window was created with a wide-gamut surface.
+    ///
+    /// Vertex colors are then converted in the shader, and textures are converted on upload
+    /// according to [`egui::TextureOptions::color_space`].
+    /// Call this before any textures are uploaded, since textures are not converted again.
+    pub fn set_output_color_space(&mut self, color_space: egui::ecolor::ColorSpace) {
+        self.output_color_space = color_space;
+    }
+
     /// The framebuffer we use as an intermediate render target,
     /// or `None` if we are painting to the screen framebuffer directly.
     ///
@@ -343,6 +368,10 @@ impl Painter {
 
             self.gl
                 .uniform_2_f32(Some(&self.u_screen_size), width_in_points, height_in_points);
+            self.gl.uniform_1_i32(
+                Some(&self.u_output_display_p3),
+                i32::from(self.output_color_space == egui::ecolor::ColorSpace::DisplayP3),
+            );
             self.gl.uniform_1_i32(Some(&self.u_sampler), 0);
             self.gl.active_texture(glow::TEXTURE0);
 
@@ -359,6 +388,8 @@ impl Painter {
     }
 
     /// You are expected to have cleared the color buffer before calling this.
+    ///
+    /// Any snapshots queued with [`Self::render_snapshots`] are rendered into their textures first.
     pub fn paint_and_update_textures(
         &mut self,
         screen_size_px: [u32; 2],
@@ -372,6 +403,10 @@ impl Painter {
             self.set_texture(*id, image_delta);
         }
 
+        for snapshot in std::mem::take(&mut self.pending_snapshots) {
+            self.render_snapshot(&snapshot);
+        }
+
         self.paint_primitives(screen_size_px, pixels_per_point, clipped_primitives);
 
         for &id in &textures_delta.free {
@@ -379,6 +414,86 @@ impl Painter {
         }
     }
 
+    /// Render these [`egui::TextureSnapshot`]s into their textures in the next call to
+    /// [`Self::paint_and_update_textures`], after the frame's texture updates.
+    pub fn render_snapshots(&mut self, snapshots: Vec<egui::TextureSnapshot>) {
+        self.pending_snapshots.extend(snapshots);
+    }
+
+    /// Render a [`egui::TextureSnapshot`] into its texture, replacing the previous contents.
+    ///
+    /// Paint callbacks are not supported.
+    ///
+    /// On OpenGL ES and WebGL, where writes to sRGB textures are always converted,
+    /// the result comes out a bit too bright.
+    pub fn render_snapshot(&mut self, snapshot: &egui::TextureSnapshot) {
+        profiling::function_scope!();
+
+        let Some(texture) = self.texture(snapshot.texture_id) else {
+            log::warn!("Missing snapshot texture: {:?}", snapshot.texture_id);
+            return;
+        };
+        let size_px = snapshot.size.map(|side| side as u32);
+        let height_in_points = size_px[1] as f32 / snapshot.pixels_per_point;
+
+        // OpenGL textures are bottom-up, so we flip everything upside down:
+        let flip_y = |rect: Rect| {
+            Rect::from_x_y_ranges(
+                rect.x_range(),
+                height_in_points - rect.max.y..=height_in_points - rect.min.y,
+            )
+        };
+        let primitives: Vec<egui::ClippedPrimitive> = snapshot
+            .primitives
+            .iter()
+            .filter_map(|clipped| {
+                let Primitive::Mesh(mesh) = &clipped.primitive else {
+                    return None;
+                };
+                let mut mesh = mesh.clone();
+                for vertex in &mut mesh.vertices {
+                    vertex.pos.y = height_in_points - vertex.pos.y;
+                }
+                Some(egui::ClippedPrimitive {
+                    clip_rect: flip_y(clipped.clip_rect),
+                    primitive: Primitive::Mesh(mesh),
+                })
+            })
+            .collect();
+
+        unsafe {
+            let previous_framebuffer = self.gl.get_parameter_framebuffer(glow::FRAMEBUFFER_BINDING);
+            let framebuffer = match self.gl.create_framebuffer() {
+                Ok(framebuffer) => framebuffer,
+                Err(err) => {
+                    log::error!("Failed to create a framebuffer for a snapshot: {err}");
+                    return;
+                }
+            };
+            self.gl
+                .bind_framebuffer(glow::FRAMEBUFFER, Some(framebuffer));
+            self.gl.framebuffer_texture_2d(
+                glow::FRAMEBUFFER,
+                glow::COLOR_ATTACHMENT0,
+                glow::TEXTURE_2D,
+                Some(texture),
+                0,
+            );
+            check_for_gl_error!(&self.gl, "snapshot framebuffer");
+
+            clear(&self.gl, size_px, [0.0; 4]);
+
+            // The texture is converted to the output color space when it is painted, not now:
+            let output_color_space = std::mem::take(&mut self.output_color_space);
+            self.paint_primitives(size_px, snapshot.pixels_per_point, &primitives);
+            self.output_color_space = output_color_space;
+
+            self.gl
+                .bind_framebuffer(glow::FRAMEBUFFER, previous_framebuffer);
+            self.gl.delete_framebuffer(framebuffer);
+        }
+    }
+
     /// Main entry-point for painting a frame.
     ///
     /// You should call `target.clear_color(..)` before
@@ -408,6 +523,8 @@ impl Painter {
         profiling::function_scope!();
         self.assert_not_destroyed();
 
+        self.prepare_backend_callbacks(clipped_primitives);
+
         unsafe { self.prepare_painting(screen_size_px, pixels_per_point) };
 
         for egui::ClippedPrimitive {
@@ -444,8 +561,15 @@ impl Painter {
 
                         if let Some(callback) = callback.callback.downcast_ref::<CallbackFn>() {
                             (callback.f)(info, self);
+                        } else if let Some(callback) = callback
+                            .callback
+                            .downcast_ref::<egui::epaint::BoxedBackendCallback>(
+                        ) {
+                            let resources = std::mem::take(&mut self.callback_resources);
+                            callback.callback().paint(&info, self, &resources);
+                            self.callback_resources = resources;
                         } else {
-                            log::warn!("Warning: Unsupported render callback. Expected egui_glow::CallbackFn");
+                            log::warn!("Warning: Unsupported render callback. Expected egui_glow::CallbackFn or egui::BackendCallback");
                         }
 
                         check_for_gl_error!(&self.gl, "callback");
@@ -467,6 +591,23 @@ impl Painter {
         }
     }
 
+    /// Call [`egui::BackendCallback::prepare`] on all the callbacks we are about to paint.
+    fn prepare_backend_callbacks(&mut self, clipped_primitives: &[egui::ClippedPrimitive]) {
+        let mut resources = std::mem::take(&mut self.callback_resources);
+        for clipped_primitive in clipped_primitives {
+            if let Primitive::Callback(callback) = &clipped_primitive.primitive {
+                if let Some(callback) = callback
+                    .callback
+                    .downcast_ref::<egui::epaint::BoxedBackendCallback>()
+                {
+                    profiling::scope!("prepare callback");
+                    callback.callback().prepare(self, &mut resources);
+                }
+            }
+        }
+        self.callback_resources = resources;
+    }
+
     #[inline(never)] // Easier profiling
     fn paint_mesh(&mut self, mesh: &Mesh) {
         debug_assert!(mesh.is_valid(), "Mesh is not valid");
@@ -528,9 +669,15 @@ impl Painter {
                     "Mismatch between texture size and texel count"
                 );
 
-                let data: &[u8] = bytemuck::cast_slice(image.pixels.as_ref());
-
-                self.upload_texture_srgb(delta.pos, image.size, delta.options, data);
+                if delta.options.color_space == self.output_color_space {
+                    let data: &[u8] = bytemuck::cast_slice(image.pixels.as_ref());
+                    self.upload_texture_srgb(delta.pos, image.size, delta.options, Some(data));
+                } else {
+                    let mut image = (**image).clone();
+                    image.convert_color_space(delta.options.color_space, self.output_color_space);
+                    let data: &[u8] = bytemuck::cast_slice(image.pixels.as_ref());
+                    self.upload_texture_srgb(delta.pos, image.size, delta.options, Some(data));
+                }
             }
             egui::ImageData::Font(image) => {
                 assert_eq!(
@@ -547,7 +694,19 @@ impl Painter {
                         .collect()
                 };
 
-                self.upload_texture_srgb(delta.pos, image.size, delta.options, &data);
+                self.upload_texture_srgb(delta.pos, image.size, delta.options, Some(&data));
+            }
+            egui::ImageData::Compressed(image) => {
+                log::error!(
+                    "egui_glow doesn't support compressed textures ({:?}). Use egui-wgpu, or a ColorImage",
+                    image.format()
+                );
+            }
+            egui::ImageData::Empty(size) => {
+                // Allocate without uploading anything. Partial updates of nothing do nothing.
+                if delta.pos.is_none() {
+                    self.upload_texture_srgb(None, *size, delta.options, None);
+                }
             }
         };
     }
@@ -557,15 +716,17 @@ impl Painter {
         pos: Option<[usize; 2]>,
         [w, h]: [usize; 2],
         options: egui::TextureOptions,
-        data: &[u8],
+        data: Option<&[u8]>,
     ) {
         profiling::function_scope!();
-        assert_eq!(
-            data.len(),
-            w * h * 4,
-            "Mismatch between texture size and texel count, by {}",
-            data.len() % (w * h * 4)
-        );
+        if let Some(data) = data {
+            assert_eq!(
+                data.len(),
+                w * h * 4,
+                "Mismatch between texture size and texel count, by {}",
+                data.len() % (w * h * 4)
+            );
+        }
         assert!(
             w <= self.max_texture_side && h <= self.max_texture_side,
             "Got a texture image of size {}x{}, but the maximum supported texture side is only {}",
@@ -625,7 +786,7 @@ impl Painter {
                     h as _,
                     src_format,
                     glow::UNSIGNED_BYTE,
-                    glow::PixelUnpackData::Slice(Some(data)),
+                    glow::PixelUnpackData::Slice(data),
                 );
                 check_for_gl_error!(&self.gl, "tex_sub_image_2d");
             } else {
@@ -640,7 +801,7 @@ impl Painter {
                     border,
                     src_format,
                     glow::UNSIGNED_BYTE,
-                    glow::PixelUnpackData::Slice(Some(data)),
+                    glow::PixelUnpackData::Slice(data),
                 );
                 check_for_gl_error!(&self.gl, "tex_image_2d");
             }
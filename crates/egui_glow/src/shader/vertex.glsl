@@ -13,12 +13,29 @@
 #endif
 
 uniform vec2 u_screen_size;
+uniform int u_output_display_p3; // 1 if the output is in Display P3, 0 if sRGB
 I vec2 a_pos;
 I vec4 a_srgba; // 0-255 sRGB
 I vec2 a_tc;
 O vec4 v_rgba_in_gamma;
 O vec2 v_tc;
 
+// 0-1 linear  from  0-1 sRGB gamma
+vec3 linear_from_srgb_gamma(vec3 rgb) {
+    bvec3 cutoff = lessThan(rgb, vec3(0.04045));
+    vec3 lower = rgb / vec3(12.92);
+    vec3 higher = pow((rgb + vec3(0.055)) / vec3(1.055), vec3(2.4));
+    return mix(higher, lower, vec3(cutoff));
+}
+
+// 0-1 sRGB gamma  from  0-1 linear
+vec3 srgb_gamma_from_linear(vec3 rgb) {
+    bvec3 cutoff = lessThan(rgb, vec3(0.0031308));
+    vec3 lower = rgb * vec3(12.92);
+    vec3 higher = vec3(1.055) * pow(rgb, vec3(1.0 / 2.4)) - vec3(0.055);
+    return mix(higher, lower, vec3(cutoff));
+}
+
 void main() {
     gl_Position = vec4(
                       2.0 * a_pos.x / u_screen_size.x - 1.0,
@@ -26,5 +43,15 @@ void main() {
                       0.0,
                       1.0);
     v_rgba_in_gamma = a_srgba / 255.0;
+    if (u_output_display_p3 != 0 && v_rgba_in_gamma.a > 0.0) {
+        // Vertex colors are always sRGB, so convert them for the Display P3 output.
+        // The colors are premultiplied, so unmultiply before going to linear space:
+        mat3 display_p3_from_srgb = mat3(
+            0.8224621, 0.0331941, 0.0170827,
+            0.1775380, 0.9668058, 0.0723974,
+            0.0, 0.0, 0.9105199);
+        vec3 rgb_linear = linear_from_srgb_gamma(v_rgba_in_gamma.rgb / v_rgba_in_gamma.a);
+        v_rgba_in_gamma.rgb = srgb_gamma_from_linear(display_p3_from_srgb * rgb_linear) * v_rgba_in_gamma.a;
+    }
     v_tc = a_tc;
 }
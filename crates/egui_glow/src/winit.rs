@@ -16,8 +16,10 @@ pub struct EguiGlow {
 
     // output from the last update:
     shapes: Vec<egui::epaint::ClippedShape>,
+    tessellated: Option<Vec<egui::ClippedPrimitive>>,
     pixels_per_point: f32,
     textures_delta: egui::TexturesDelta,
+    snapshots: Vec<egui::TextureSnapshot>,
 }
 
 impl EguiGlow {
@@ -52,8 +54,10 @@ impl EguiGlow {
             painter,
             viewport_info: Default::default(),
             shapes: Default::default(),
+            tessellated: None,
             pixels_per_point: native_pixels_per_point.unwrap_or(1.0),
             textures_delta: Default::default(),
+            snapshots: Default::default(),
         }
     }
 
@@ -72,8 +76,10 @@ impl EguiGlow {
         let egui::FullOutput {
             platform_output,
             textures_delta,
+            snapshots,
             shapes,
             pixels_per_point,
+            tessellated,
             viewport_output,
         } = self.egui_ctx.run(raw_input, run_ui);
 
@@ -84,6 +90,7 @@ impl EguiGlow {
             let mut actions_requested: HashSet<egui_winit::ActionRequested> = Default::default();
             egui_winit::process_viewport_commands(
                 &self.egui_ctx,
+                ViewportId::ROOT,
                 &mut self.viewport_info,
                 commands,
                 window,
@@ -98,8 +105,10 @@ impl EguiGlow {
             .handle_platform_output(window, platform_output);
 
         self.shapes = shapes;
+        self.tessellated = tessellated;
         self.pixels_per_point = pixels_per_point;
         self.textures_delta.append(textures_delta);
+        self.snapshots.extend(snapshots);
     }
 
     /// Paint the results of the last call to [`Self::run`].
@@ -111,8 +120,15 @@ impl EguiGlow {
             self.painter.set_texture(id, &image_delta);
         }
 
+        for snapshot in std::mem::take(&mut self.snapshots) {
+            self.painter.render_snapshot(&snapshot);
+        }
+
         let pixels_per_point = self.pixels_per_point;
-        let clipped_primitives = self.egui_ctx.tessellate(shapes, pixels_per_point);
+        let clipped_primitives = self
+            .tessellated
+            .take()
+            .unwrap_or_else(|| self.egui_ctx.tessellate(shapes, pixels_per_point));
         let dimensions: [u32; 2] = window.inner_size().into();
         self.painter
             .paint_primitives(dimensions, pixels_per_point, &clipped_primitives);
@@ -72,6 +72,14 @@ impl<State> HarnessBuilder<State> {
         self
     }
 
+    /// Render on the CPU with a [`crate::software::SoftwareTestRenderer`].
+    ///
+    /// Doesn't need a GPU, so snapshots can be taken on any CI machine.
+    #[cfg(feature = "snapshot")]
+    pub fn software(self) -> Self {
+        self.renderer(crate::software::SoftwareTestRenderer::new())
+    }
+
     /// Enable wgpu rendering with a default setup suitable for testing.
     ///
     /// This sets up a [`crate::wgpu::WgpuTestRenderer`] with the default setup.
@@ -15,6 +15,8 @@ use std::time::Duration;
 
 mod app_kind;
 mod renderer;
+#[cfg(feature = "snapshot")]
+pub mod software;
 #[cfg(feature = "wgpu")]
 mod texture_to_image;
 #[cfg(feature = "wgpu")]
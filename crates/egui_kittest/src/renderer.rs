@@ -22,7 +22,8 @@ pub trait TestRenderer {
 
 /// A lazy renderer that initializes the renderer on the first render call.
 ///
-/// By default, this will create a wgpu renderer if the wgpu feature is enabled.
+/// By default, this will create a wgpu renderer if the wgpu feature is enabled,
+/// and otherwise a CPU software renderer if the snapshot feature is enabled.
 pub enum LazyRenderer {
     Uninitialized {
         texture_ops: Vec<egui::TexturesDelta>,
@@ -37,7 +38,9 @@ impl Default for LazyRenderer {
     fn default() -> Self {
         #[cfg(feature = "wgpu")]
         return Self::new(crate::wgpu::WgpuTestRenderer::new);
-        #[cfg(not(feature = "wgpu"))]
+        #[cfg(all(not(feature = "wgpu"), feature = "snapshot"))]
+        return Self::new(crate::software::SoftwareTestRenderer::new);
+        #[cfg(not(any(feature = "wgpu", feature = "snapshot")))]
         return Self::Uninitialized {
             texture_ops: Vec::new(),
             builder: None,
@@ -75,7 +78,7 @@ impl TestRenderer for LazyRenderer {
             } => {
                 let mut renderer = build.take().ok_or({
                     "No default renderer available. \
-                    Enable the wgpu feature or set one via HarnessBuilder::renderer"
+                    Enable the wgpu or snapshot feature or set one via HarnessBuilder::renderer"
                 })?();
                 for delta in texture_ops.drain(..) {
                     renderer.handle_delta(&delta);
@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use egui::epaint::{ClippedPrimitive, ImageData, Primitive, TextureId, Vertex};
+use egui::{Color32, TexturesDelta};
+use image::RgbaImage;
+
+struct Texture {
+    size: [usize; 2],
+    pixels: Vec<Color32>,
+}
+
+impl Texture {
+    /// Bilinear sample at the given uv coordinate.
+    fn sample(&self, uv: egui::Pos2) -> Color32 {
+        let [w, h] = self.size;
+        if w == 0 || h == 0 {
+            return Color32::TRANSPARENT;
+        }
+        let x = (uv.x * w as f32 - 0.5).clamp(0.0, (w - 1) as f32);
+        let y = (uv.y * h as f32 - 0.5).clamp(0.0, (h - 1) as f32);
+        let (x0, y0) = (x as usize, y as usize);
+        let (x1, y1) = ((x0 + 1).min(w - 1), (y0 + 1).min(h - 1));
+        let (tx, ty) = (x.fract(), y.fract());
+        let px = |x: usize, y: usize| self.pixels[y * w + x];
+        let top = px(x0, y0).lerp_to_gamma(px(x1, y0), tx);
+        let bottom = px(x0, y1).lerp_to_gamma(px(x1, y1), tx);
+        top.lerp_to_gamma(bottom, ty)
+    }
+}
+
+/// Renders a [`crate::Harness`] on the CPU, without any GPU or graphics drivers.
+///
+/// This is slower and less accurate than the wgpu renderer,
+/// but works everywhere, which makes it a good fit for snapshot tests on CI.
+/// Paint callbacks are not supported and are skipped.
+#[derive(Default)]
+pub struct SoftwareTestRenderer {
+    textures: HashMap<TextureId, Texture>,
+}
+
+impl SoftwareTestRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl crate::TestRenderer for SoftwareTestRenderer {
+    fn handle_delta(&mut self, delta: &TexturesDelta) {
+        for (id, image_delta) in &delta.set {
+            let (size, pixels): ([usize; 2], Vec<Color32>) = match &image_delta.image {
+                ImageData::Color(image) => (image.size, image.pixels.clone()),
+                ImageData::Font(image) => (image.size, image.srgba_pixels(None).collect()),
+                ImageData::Compressed(image) => {
+                    // We can't decode these, so make them stand out:
+                    let size = image.size();
+                    (size, vec![Color32::DEBUG_COLOR; size[0] * size[1]])
+                }
+                ImageData::Empty(size) => {
+                    if image_delta.pos.is_some() {
+                        continue; // Nothing to update with
+                    }
+                    (*size, vec![Color32::TRANSPARENT; size[0] * size[1]])
+                }
+            };
+
+            match image_delta.pos {
+                None => {
+                    self.textures.insert(*id, Texture { size, pixels });
+                }
+                Some([x, y]) => {
+                    let Some(texture) = self.textures.get_mut(id) else {
+                        continue;
+                    };
+                    let [w, _] = texture.size;
+                    for row in 0..size[1] {
+                        let dst = (y + row) * w + x;
+                        let src = row * size[0];
+                        texture.pixels[dst..dst + size[0]]
+                            .copy_from_slice(&pixels[src..src + size[0]]);
+                    }
+                }
+            }
+        }
+        for id in &delta.free {
+            self.textures.remove(id);
+        }
+    }
+
+    fn render(
+        &mut self,
+        ctx: &egui::Context,
+        output: &egui::FullOutput,
+    ) -> Result<RgbaImage, String> {
+        let pixels_per_point = ctx.pixels_per_point();
+        let size = ctx.screen_rect().size() * pixels_per_point;
+        let [width, height] = [size.x.round() as usize, size.y.round() as usize];
+
+        let mut canvas = Canvas {
+            width,
+            pixels: vec![Color32::TRANSPARENT; width * height],
+        };
+
+        let primitives = ctx.tessellate(output.shapes.clone(), pixels_per_point);
+        for ClippedPrimitive {
+            clip_rect,
+            primitive,
+        } in &primitives
+        {
+            let Primitive::Mesh(mesh) = primitive else {
+                continue; // Paint callbacks need a real GPU
+            };
+            let Some(texture) = self.textures.get(&mesh.texture_id) else {
+                return Err(format!("Missing texture {:?}", mesh.texture_id));
+            };
+            let clip = [
+                (clip_rect.min.x * pixels_per_point).round().max(0.0) as usize,
+                (clip_rect.min.y * pixels_per_point).round().max(0.0) as usize,
+                ((clip_rect.max.x * pixels_per_point).round().max(0.0) as usize).min(width),
+                ((clip_rect.max.y * pixels_per_point).round().max(0.0) as usize).min(height),
+            ];
+            for triangle in mesh.indices.chunks_exact(3) {
+                let [a, b, c] = [0, 1, 2].map(|i| &mesh.vertices[triangle[i] as usize]);
+                canvas.fill_triangle([a, b, c], pixels_per_point, clip, texture);
+            }
+        }
+
+        let bytes = canvas
+            .pixels
+            .iter()
+            .flat_map(Color32::to_srgba_unmultiplied)
+            .collect();
+        RgbaImage::from_raw(width as u32, height as u32, bytes)
+            .ok_or_else(|| "Failed to create image".to_owned())
+    }
+}
+
+struct Canvas {
+    width: usize,
+    pixels: Vec<Color32>,
+}
+
+impl Canvas {
+    /// Rasterize a triangle by sampling each pixel center, blending with premultiplied alpha.
+    fn fill_triangle(
+        &mut self,
+        vertices: [&Vertex; 3],
+        pixels_per_point: f32,
+        [clip_min_x, clip_min_y, clip_max_x, clip_max_y]: [usize; 4],
+        texture: &Texture,
+    ) {
+        let [p0, p1, p2] = vertices.map(|v| v.pos.to_vec2() * pixels_per_point);
+        let area = (p1 - p0).x * (p2 - p0).y - (p1 - p0).y * (p2 - p0).x;
+        if area.abs() < f32::EPSILON {
+            return;
+        }
+
+        let min = p0.min(p1).min(p2);
+        let max = p0.max(p1).max(p2);
+        let x_range = (min.x.floor().max(0.0) as usize).max(clip_min_x)
+            ..(max.x.ceil().max(0.0) as usize).min(clip_max_x);
+        let y_range = (min.y.floor().max(0.0) as usize).max(clip_min_y)
+            ..(max.y.ceil().max(0.0) as usize).min(clip_max_y);
+
+        let edge = |a: egui::Vec2, b: egui::Vec2, p: egui::Vec2| {
+            ((b - a).x * (p - a).y - (b - a).y * (p - a).x) / area
+        };
+
+        for y in y_range {
+            for x in x_range.clone() {
+                let p = egui::vec2(x as f32 + 0.5, y as f32 + 0.5);
+                let w0 = edge(p1, p2, p);
+                let w1 = edge(p2, p0, p);
+                let w2 = edge(p0, p1, p);
+                if w0 < 0.0 || w1 < 0.0 || w2 < 0.0 {
+                    continue;
+                }
+
+                let uv = (vertices[0].uv.to_vec2() * w0
+                    + vertices[1].uv.to_vec2() * w1
+                    + vertices[2].uv.to_vec2() * w2)
+                    .to_pos2();
+                let color = interpolate(vertices.map(|v| v.color), [w0, w1, w2]);
+                let src = color * texture.sample(uv);
+
+                let dst = &mut self.pixels[y * self.width + x];
+                *dst = blend(src, *dst);
+            }
+        }
+    }
+}
+
+fn interpolate(colors: [Color32; 3], weights: [f32; 3]) -> Color32 {
+    let channel = |i: usize| {
+        let value: f32 = (0..3).map(|v| f32::from(colors[v][i]) * weights[v]).sum();
+        value.round().clamp(0.0, 255.0) as u8
+    };
+    Color32::from_rgba_premultiplied(channel(0), channel(1), channel(2), channel(3))
+}
+
+/// `src` over `dst`, both premultiplied.
+fn blend(src: Color32, dst: Color32) -> Color32 {
+    let inv_alpha = 255 - u16::from(src.a());
+    let channel =
+        |s: u8, d: u8| (u16::from(s) + (u16::from(d) * inv_alpha + 127) / 255).min(255) as u8;
+    Color32::from_rgba_premultiplied(
+        channel(src.r(), dst.r()),
+        channel(src.g(), dst.g()),
+        channel(src.b(), dst.b()),
+        channel(src.a(), dst.a()),
+    )
+}
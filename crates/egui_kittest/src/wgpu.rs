@@ -149,6 +149,14 @@ impl crate::TestRenderer for WgpuTestRenderer {
             size_in_pixels: [size.x.round() as u32, size.y.round() as u32],
         };
 
+        for snapshot in &output.snapshots {
+            renderer.render_snapshot(
+                &self.render_state.device,
+                &self.render_state.queue,
+                snapshot,
+            );
+        }
+
         let tessellated = ctx.tessellate(output.shapes.clone(), ctx.pixels_per_point());
 
         let user_buffers = renderer.update_buffers(
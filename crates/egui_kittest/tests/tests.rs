@@ -57,3 +57,25 @@ fn test_modifiers() {
     assert!(state.cmd_z_pressed, "Cmd+Z wasn't pressed");
     assert!(state.cmd_y_pressed, "Cmd+Y wasn't pressed");
 }
+
+#[cfg(feature = "snapshot")]
+#[test]
+fn test_software_renderer() {
+    let mut harness = Harness::builder()
+        .with_size(egui::Vec2::new(40.0, 40.0))
+        .software()
+        .build(|ctx| {
+            ctx.layer_painter(egui::LayerId::background()).rect_filled(
+                egui::Rect::from_min_size(egui::pos2(10.0, 10.0), egui::Vec2::splat(20.0)),
+                0.0,
+                egui::Color32::RED,
+            );
+        });
+
+    let image = harness
+        .render()
+        .expect("The software renderer needs no GPU");
+    assert_eq!(image.dimensions(), (40, 40));
+    assert_eq!(image.get_pixel(20, 20).0, [255, 0, 0, 255]);
+    assert_eq!(image.get_pixel(2, 2).0[3], 0, "Outside the rect");
+}
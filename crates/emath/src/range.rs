@@ -126,6 +126,22 @@ impl Rangef {
         }
     }
 
+    /// The smallest range containing both ranges.
+    ///
+    /// ```
+    /// # use emath::Rangef;
+    /// assert_eq!(Rangef::new(0.0, 10.0).union(Rangef::new(5.0, 15.0)), Rangef::new(0.0, 15.0));
+    /// assert_eq!(Rangef::new(0.0, 10.0).union(Rangef::new(20.0, 30.0)), Rangef::new(0.0, 30.0));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
     /// Do the two ranges intersect?
     ///
     /// ```
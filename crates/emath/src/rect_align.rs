@@ -1,4 +1,4 @@
-use crate::{Align2, Pos2, Rect, Vec2};
+use crate::{Align, Align2, Pos2, Rect, Vec2};
 
 /// Position a child [`Rect`] relative to a parent [`Rect`].
 ///
@@ -171,6 +171,52 @@ impl RectAlign {
         pivot.anchor_size(anchor, size)
     }
 
+    /// Like [`Self::align_rect`], but shifted to fit within `available_space`.
+    ///
+    /// The child is only shifted along the side of the parent it is on
+    /// (e.g. sideways for [`Self::BOTTOM_START`]), so it never covers the parent rect.
+    /// If it is larger than `available_space`, its left or top edge is kept visible.
+    pub fn align_rect_within(
+        &self,
+        parent_rect: &Rect,
+        size: Vec2,
+        gap: f32,
+        available_space: Rect,
+    ) -> Rect {
+        let rect = self.align_rect(parent_rect, size, gap);
+
+        let is_beside =
+            |parent: Align, child: Align| parent != Align::Center && parent == child.flip();
+        let shift_into = |min: f32, max: f32, available_min: f32, available_max: f32| {
+            if available_max - available_min < max - min || min < available_min {
+                available_min - min
+            } else if available_max < max {
+                available_max - max
+            } else {
+                0.0
+            }
+        };
+
+        let mut shift = Vec2::ZERO;
+        if !is_beside(self.parent.x(), self.child.x()) {
+            shift.x = shift_into(
+                rect.min.x,
+                rect.max.x,
+                available_space.min.x,
+                available_space.max.x,
+            );
+        }
+        if !is_beside(self.parent.y(), self.child.y()) {
+            shift.y = shift_into(
+                rect.min.y,
+                rect.max.y,
+                available_space.min.y,
+                available_space.max.y,
+            );
+        }
+        rect.translate(shift)
+    }
+
     /// Returns a [`Align2`] and a [`Pos2`] that you can e.g. use with `Area::fixed_pos`
     /// and `Area::pivot` to align an `Area` to some rect.
     pub fn pivot_pos(&self, parent_rect: &Rect, gap: f32) -> (Align2, Pos2) {
@@ -277,3 +323,25 @@ impl RectAlign {
         best
     }
 }
+
+#[test]
+fn test_align_rect_within() {
+    use crate::{pos2, vec2};
+
+    let screen = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+    let anchor = Rect::from_min_size(pos2(80.0, 10.0), vec2(10.0, 10.0));
+    let size = vec2(40.0, 20.0);
+
+    // Below the anchor, shifted left to fit, but not up over the anchor:
+    let rect = RectAlign::BOTTOM_START.align_rect_within(&anchor, size, 0.0, screen);
+    assert_eq!(rect, Rect::from_min_size(pos2(60.0, 20.0), size));
+
+    // To the right of the anchor it can't fit without covering it, so it sticks out:
+    let rect = RectAlign::RIGHT_START.align_rect_within(&anchor, size, 0.0, screen);
+    assert_eq!(rect.min.x, 90.0);
+
+    // Too wide for the screen, so the left edge stays visible:
+    let wide = vec2(200.0, 20.0);
+    let rect = RectAlign::BOTTOM_START.align_rect_within(&anchor, wide, 0.0, screen);
+    assert_eq!(rect.min.x, 0.0);
+}
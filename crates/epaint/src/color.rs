@@ -1,7 +1,7 @@
 use std::{fmt::Debug, sync::Arc};
 
-use ecolor::Color32;
-use emath::{Pos2, Rect};
+use ecolor::{Color32, Oklab, Rgba};
+use emath::{Pos2, Rect, Vec2};
 
 /// How paths will be colored.
 #[derive(Clone)]
@@ -16,6 +16,11 @@ pub enum ColorMode {
     /// **This cannot be serialized**
     #[cfg_attr(feature = "serde", serde(skip))]
     UV(Arc<dyn Fn(Rect, Pos2) -> Color32 + Send + Sync>),
+
+    /// A linear gradient across the path's bounding box.
+    ///
+    /// Like for [`Self::UV`], the bounding box includes the stroke width and feathering.
+    Gradient(Arc<Gradient>),
 }
 
 impl Default for ColorMode {
@@ -29,6 +34,7 @@ impl Debug for ColorMode {
         match self {
             Self::Solid(arg0) => f.debug_tuple("Solid").field(arg0).finish(),
             Self::UV(_arg0) => f.debug_tuple("UV").field(&"<closure>").finish(),
+            Self::Gradient(gradient) => f.debug_tuple("Gradient").field(gradient).finish(),
         }
     }
 }
@@ -37,6 +43,7 @@ impl PartialEq for ColorMode {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Self::Solid(l0), Self::Solid(r0)) => l0 == r0,
+            (Self::Gradient(l0), Self::Gradient(r0)) => l0 == r0,
             (Self::UV(_l0), Self::UV(_r0)) => false,
             _ => false,
         }
@@ -46,3 +53,265 @@ impl PartialEq for ColorMode {
 impl ColorMode {
     pub const TRANSPARENT: Self = Self::Solid(Color32::TRANSPARENT);
 }
+
+// ----------------------------------------------------------------------------
+
+/// In what color space to blend the colors of a [`Gradient`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ColorInterpolation {
+    /// Blend the gamma-encoded sRGB values.
+    ///
+    /// This is what the GPU does between vertices, but the middle of e.g. a red-green gradient gets muddy.
+    #[default]
+    Gamma,
+
+    /// Blend in linear RGB. Physically correct mixing of light, but perceptually uneven.
+    Linear,
+
+    /// Blend in the perceptual [`Oklab`] color space, which gives smooth gradients without muddy middles.
+    Oklab,
+}
+
+impl ColorInterpolation {
+    /// Interpolate from `a` (at `t=0`) to `b` (at `t=1`).
+    pub fn lerp(self, a: Color32, b: Color32, t: f32) -> Color32 {
+        match self {
+            Self::Gamma => a.lerp_to_gamma(b, t),
+            Self::Linear => {
+                let (a, b) = (Rgba::from(a), Rgba::from(b));
+                (a * (1.0 - t) + b * t).into()
+            }
+            Self::Oklab => Oklab::from(a).lerp(Oklab::from(b), t).into(),
+        }
+    }
+}
+
+/// A linear gradient, used with [`ColorMode::Gradient`].
+///
+/// The gradient goes across the bounding box of what is painted, in the given direction.
+///
+/// ```
+/// # use epaint::{color::{ColorInterpolation, Gradient}, Color32};
+/// let gradient = Gradient::horizontal(Color32::BLUE, Color32::YELLOW)
+///     .interpolation(ColorInterpolation::Oklab);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Gradient {
+    /// The direction the gradient goes in, e.g. [`Vec2::X`] for left-to-right.
+    pub direction: Vec2,
+
+    /// The colors, and where they are along the gradient (0-1), sorted by position.
+    pub stops: Vec<(f32, Color32)>,
+
+    pub interpolation: ColorInterpolation,
+}
+
+impl Gradient {
+    /// A gradient from `from` to `to` in the given direction.
+    pub fn linear(direction: Vec2, from: Color32, to: Color32) -> Self {
+        Self {
+            direction,
+            stops: vec![(0.0, from), (1.0, to)],
+            interpolation: ColorInterpolation::default(),
+        }
+    }
+
+    /// From `left` to `right`.
+    pub fn horizontal(left: Color32, right: Color32) -> Self {
+        Self::linear(Vec2::X, left, right)
+    }
+
+    /// From `top` to `bottom`.
+    pub fn vertical(top: Color32, bottom: Color32) -> Self {
+        Self::linear(Vec2::Y, top, bottom)
+    }
+
+    /// Add a color stop at `t` (0-1) along the gradient.
+    #[inline]
+    pub fn stop(mut self, t: f32, color: Color32) -> Self {
+        let index = self.stops.partition_point(|(stop, _)| *stop <= t);
+        self.stops.insert(index, (t, color));
+        self
+    }
+
+    /// In what color space to blend the stops.
+    #[inline]
+    pub fn interpolation(mut self, interpolation: ColorInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// The color at `t` (0-1) along the gradient.
+    pub fn color_at_t(&self, t: f32) -> Color32 {
+        let Some(&(first_t, first_color)) = self.stops.first() else {
+            return Color32::TRANSPARENT;
+        };
+        if t <= first_t {
+            return first_color;
+        }
+        for window in self.stops.windows(2) {
+            let [(t0, c0), (t1, c1)] = [window[0], window[1]];
+            if t <= t1 {
+                let f = if t1 > t0 { (t - t0) / (t1 - t0) } else { 1.0 };
+                return self.interpolation.lerp(c0, c1, f);
+            }
+        }
+        self.stops.last().map_or(first_color, |(_, color)| *color)
+    }
+
+    /// The color at `pos`, when the gradient is spread across `rect`.
+    pub fn color_at(&self, rect: Rect, pos: Pos2) -> Color32 {
+        let dir = self.direction.normalized();
+        let half_length = 0.5 * (rect.width() * dir.x.abs() + rect.height() * dir.y.abs());
+        let t = if half_length > 0.0 {
+            0.5 + (pos - rect.center()).dot(dir) / (2.0 * half_length)
+        } else {
+            0.0
+        };
+        self.color_at_t(t)
+    }
+
+    /// Color every vertex of a mesh according to where it is in the mesh's bounding box.
+    ///
+    /// Use this to fill shapes with a gradient, e.g. after tessellating them.
+    /// Note that the colors are only computed at the vertices,
+    /// so use enough of them to capture the gradient.
+    pub fn paint_mesh(&self, mesh: &mut crate::Mesh) {
+        let rect = mesh.calc_bounds();
+        for vertex in &mut mesh.vertices {
+            vertex.color = self.color_at(rect, vertex.pos);
+        }
+    }
+
+    /// Transform every color stop.
+    pub fn map_colors(&mut self, mut f: impl FnMut(Color32) -> Color32) {
+        for (_, color) in &mut self.stops {
+            *color = f(*color);
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Maps values in `0..=1` to colors, e.g. to show a matrix of numbers as an image.
+///
+/// ```
+/// # use epaint::{color::Colormap, Color32};
+/// let color = Colormap::Viridis.color_at(0.5);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Colormap {
+    /// Dark blue through green to yellow.
+    ///
+    /// Perceptually uniform, and readable by most color blind people and in grayscale.
+    #[default]
+    Viridis,
+
+    /// Black through purple and red to pale yellow.
+    ///
+    /// Perceptually uniform, with more contrast than [`Self::Viridis`] for small values.
+    Magma,
+
+    /// The stops of a [`Gradient`], ignoring its direction.
+    Gradient(Gradient),
+}
+
+impl Colormap {
+    /// Samples of viridis, evenly spaced from 0 to 1.
+    const VIRIDIS: [Color32; 9] = [
+        Color32::from_rgb(0x44, 0x01, 0x54),
+        Color32::from_rgb(0x48, 0x28, 0x78),
+        Color32::from_rgb(0x3e, 0x49, 0x89),
+        Color32::from_rgb(0x31, 0x68, 0x8e),
+        Color32::from_rgb(0x26, 0x82, 0x8e),
+        Color32::from_rgb(0x1f, 0x9e, 0x89),
+        Color32::from_rgb(0x35, 0xb7, 0x79),
+        Color32::from_rgb(0x6e, 0xce, 0x58),
+        Color32::from_rgb(0xfd, 0xe7, 0x25),
+    ];
+
+    /// Samples of magma, evenly spaced from 0 to 1.
+    const MAGMA: [Color32; 9] = [
+        Color32::from_rgb(0x00, 0x00, 0x04),
+        Color32::from_rgb(0x1c, 0x10, 0x44),
+        Color32::from_rgb(0x4f, 0x12, 0x7b),
+        Color32::from_rgb(0x81, 0x25, 0x81),
+        Color32::from_rgb(0xb5, 0x36, 0x7a),
+        Color32::from_rgb(0xe5, 0x50, 0x64),
+        Color32::from_rgb(0xfb, 0x87, 0x61),
+        Color32::from_rgb(0xfe, 0xc2, 0x87),
+        Color32::from_rgb(0xfc, 0xfd, 0xbf),
+    ];
+
+    /// The color at `t`, which is clamped to `0..=1`.
+    ///
+    /// `NaN` is transparent.
+    pub fn color_at(&self, t: f32) -> Color32 {
+        if t.is_nan() {
+            return Color32::TRANSPARENT;
+        }
+        let t = t.clamp(0.0, 1.0);
+        let samples = match self {
+            Self::Viridis => &Self::VIRIDIS,
+            Self::Magma => &Self::MAGMA,
+            Self::Gradient(gradient) => return gradient.color_at_t(t),
+        };
+        let position = t * (samples.len() - 1) as f32;
+        let index = (position as usize).min(samples.len() - 2);
+        samples[index].lerp_to_gamma(samples[index + 1], position - index as f32)
+    }
+}
+
+#[test]
+fn test_gradient() {
+    let gradient = Gradient::horizontal(Color32::RED, Color32::BLUE).stop(0.5, Color32::WHITE);
+    let rect = Rect::from_min_max(Pos2::ZERO, Pos2::new(100.0, 10.0));
+    assert_eq!(gradient.color_at(rect, Pos2::new(-10.0, 5.0)), Color32::RED);
+    assert_eq!(
+        gradient.color_at(rect, Pos2::new(50.0, 0.0)),
+        Color32::WHITE
+    );
+    assert_eq!(
+        gradient.color_at(rect, Pos2::new(100.0, 5.0)),
+        Color32::BLUE
+    );
+
+    for interpolation in [
+        ColorInterpolation::Gamma,
+        ColorInterpolation::Linear,
+        ColorInterpolation::Oklab,
+    ] {
+        let gradient = gradient.clone().interpolation(interpolation);
+        assert_eq!(gradient.color_at_t(0.0), Color32::RED);
+        assert_eq!(gradient.color_at_t(1.0), Color32::BLUE);
+    }
+}
+
+#[test]
+fn test_colormap() {
+    for colormap in [Colormap::Viridis, Colormap::Magma] {
+        let samples = match colormap {
+            Colormap::Magma => Colormap::MAGMA,
+            _ => Colormap::VIRIDIS,
+        };
+        assert_eq!(colormap.color_at(0.0), samples[0]);
+        assert_eq!(colormap.color_at(-1.0), samples[0]);
+        assert_eq!(colormap.color_at(0.5), samples[4]);
+        assert_eq!(colormap.color_at(1.0), samples[8]);
+        assert_eq!(colormap.color_at(f32::NAN), Color32::TRANSPARENT);
+    }
+
+    let gradient = Colormap::Gradient(Gradient::horizontal(Color32::BLACK, Color32::WHITE));
+    assert_eq!(gradient.color_at(1.0), Color32::WHITE);
+}
+
+#[test]
+fn color_mode_size() {
+    assert_eq!(
+        std::mem::size_of::<ColorMode>(), 24,
+        "ColorMode changed size! If it shrank - good! Update this test. If it grew - bad! Try to find a way to avoid it."
+    );
+}
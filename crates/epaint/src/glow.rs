@@ -0,0 +1,307 @@
+use crate::{
+    CapsuleShape, CircleShape, Color32, EllipseShape, LineCap, LineJoin, Mesh, PathShape,
+    PathStroke, Pos2, RectShape, Shape, Stroke, StrokeKind, TessellationOptions, Tessellator, Vec2,
+};
+
+/// An outline around the outside of some shapes, e.g. to show that they are selected.
+///
+/// The outline follows the outer edge of each shape, including its stroke,
+/// and can be blurred into a soft glow.
+///
+/// Paint it _before_ the shapes it outlines,
+/// since it also covers some of the inside of them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Glow {
+    /// How far the outline reaches outside of the shapes, before blurring.
+    pub width: f32,
+
+    /// The width of the blur, i.e. the width of the fuzzy edge.
+    ///
+    /// Half of it is outside of [`Self::width`].
+    /// A value of 0 means a sharp outline.
+    pub blur: f32,
+
+    pub color: Color32,
+}
+
+impl Glow {
+    /// No glow at all.
+    pub const NONE: Self = Self {
+        width: 0.0,
+        blur: 0.0,
+        color: Color32::TRANSPARENT,
+    };
+
+    /// A sharp outline.
+    #[inline]
+    pub fn new(width: f32, color: impl Into<Color32>) -> Self {
+        Self {
+            width,
+            blur: 0.0,
+            color: color.into(),
+        }
+    }
+
+    /// Blur the edge of the outline by this much.
+    #[inline]
+    pub fn with_blur(mut self, blur: f32) -> Self {
+        self.blur = blur;
+        self
+    }
+
+    /// How far the glow reaches outside of the shapes, including the blur.
+    pub fn margin(&self) -> f32 {
+        self.width + 0.5 * self.blur
+    }
+
+    /// The outline of `shape`, which can be a [`Shape::Vec`] of many shapes.
+    pub fn as_shape(&self, shape: &Shape) -> Shape {
+        if self.width <= 0.0 && self.blur <= 0.0 || self.color == Color32::TRANSPARENT {
+            return Shape::Noop;
+        }
+
+        let mut outlines = vec![];
+        self.add_outlines(shape, &mut outlines);
+
+        if self.blur <= 0.0 {
+            return Shape::Vec(outlines);
+        }
+
+        // Blur by feathering the edges of the outline by the width of the blur:
+        let options = TessellationOptions {
+            feathering: true,
+            feathering_size_in_pixels: self.blur,
+            prerasterized_discs: false,
+            ..Default::default()
+        };
+        let mut tessellator = Tessellator::new(1.0, options, [1, 1], vec![]);
+        let mut mesh = Mesh::default();
+        for outline in outlines {
+            tessellator.tessellate_shape(outline, &mut mesh);
+        }
+        mesh.into()
+    }
+
+    fn add_outlines(&self, shape: &Shape, out: &mut Vec<Shape>) {
+        let Self { width, color, .. } = *self;
+
+        match shape {
+            Shape::Noop | Shape::Callback(_) => {}
+            Shape::Vec(shapes)
+            | Shape::Pick { shapes, .. }
+            | Shape::PixelSnapped { shapes, .. } => {
+                for shape in shapes {
+                    self.add_outlines(shape, out);
+                }
+            }
+            Shape::Circle(circle) => {
+                let extent = 0.5 * visible_width(circle.stroke) + width;
+                out.push(
+                    CircleShape::stroke(
+                        circle.center,
+                        circle.radius + 0.5 * extent,
+                        Stroke::new(extent, color),
+                    )
+                    .into(),
+                );
+            }
+            Shape::Ellipse(ellipse) => {
+                let extent = 0.5 * visible_width(ellipse.stroke) + width;
+                out.push(
+                    EllipseShape::stroke(
+                        ellipse.center,
+                        ellipse.radius + Vec2::splat(0.5 * extent),
+                        Stroke::new(extent, color),
+                    )
+                    .with_angle(ellipse.angle)
+                    .into(),
+                );
+            }
+            Shape::Capsule(capsule) => {
+                let extent = match capsule.stroke_kind {
+                    StrokeKind::Inside => 0.0,
+                    StrokeKind::Middle => 0.5 * visible_width(capsule.stroke),
+                    StrokeKind::Outside => visible_width(capsule.stroke),
+                } + width;
+                out.push(
+                    CapsuleShape::new(
+                        capsule.start,
+                        capsule.end,
+                        capsule.radius,
+                        Color32::TRANSPARENT,
+                        Stroke::new(extent, color),
+                        StrokeKind::Outside,
+                    )
+                    .into(),
+                );
+            }
+            Shape::LineSegment { points, stroke } => {
+                self.add_path_outline(
+                    points,
+                    false,
+                    visible_width(*stroke),
+                    StrokeKind::Middle,
+                    out,
+                );
+            }
+            Shape::Path(path) => {
+                self.add_path_outline(
+                    &path.points,
+                    path.closed,
+                    path_stroke_width(&path.stroke),
+                    path.stroke.kind,
+                    out,
+                );
+            }
+            Shape::Rect(rect) => {
+                let extent = match rect.stroke_kind {
+                    StrokeKind::Inside => 0.0,
+                    StrokeKind::Middle => 0.5 * visible_width(rect.stroke),
+                    StrokeKind::Outside => visible_width(rect.stroke),
+                } + width;
+                out.push(
+                    RectShape::stroke(
+                        rect.rect,
+                        rect.corner_radius,
+                        Stroke::new(extent, color),
+                        StrokeKind::Outside,
+                    )
+                    .into(),
+                );
+            }
+            Shape::QuadraticBezier(bezier) => {
+                self.add_path_outline(
+                    &bezier.flatten(None),
+                    bezier.closed,
+                    path_stroke_width(&bezier.stroke),
+                    bezier.stroke.kind,
+                    out,
+                );
+            }
+            Shape::CubicBezier(bezier) => {
+                self.add_path_outline(
+                    &bezier.flatten(None),
+                    bezier.closed,
+                    path_stroke_width(&bezier.stroke),
+                    bezier.stroke.kind,
+                    out,
+                );
+            }
+            Shape::Arc(arc) => {
+                self.add_path_outline(
+                    &arc.flatten(None),
+                    arc.fill != Color32::TRANSPARENT,
+                    path_stroke_width(&arc.stroke),
+                    arc.stroke.kind,
+                    out,
+                );
+            }
+            Shape::Text(_) | Shape::Mesh(_) => {
+                // Outline the bounding box, like a text selection:
+                out.push(
+                    RectShape::stroke(
+                        shape.visual_bounding_rect(),
+                        0.5 * width,
+                        Stroke::new(width, color),
+                        StrokeKind::Outside,
+                    )
+                    .into(),
+                );
+            }
+        }
+    }
+
+    fn add_path_outline(
+        &self,
+        points: &[Pos2],
+        closed: bool,
+        stroke_width: f32,
+        stroke_kind: StrokeKind,
+        out: &mut Vec<Shape>,
+    ) {
+        let Self { width, color, .. } = *self;
+
+        if closed {
+            let extent = match stroke_kind {
+                StrokeKind::Inside => 0.0,
+                StrokeKind::Middle => 0.5 * stroke_width,
+                StrokeKind::Outside => stroke_width,
+            } + width;
+
+            // Outside strokes only go outwards on clockwise paths, so wind it like the tessellator winds fills:
+            let mut points = points.to_vec();
+            if signed_area(&points) < 0.0 {
+                points.reverse();
+            }
+            let stroke = PathStroke::new(extent, color)
+                .outside()
+                .with_line_join(LineJoin::Round);
+            out.push(PathShape::closed_line(points, stroke).into());
+        } else {
+            let stroke = PathStroke::new(stroke_width + 2.0 * width, color)
+                .with_line_cap(LineCap::Round)
+                .with_line_join(LineJoin::Round);
+            out.push(PathShape::line(points.to_vec(), stroke).into());
+        }
+    }
+}
+
+fn visible_width(stroke: Stroke) -> f32 {
+    if stroke.is_empty() {
+        0.0
+    } else {
+        stroke.width
+    }
+}
+
+fn path_stroke_width(stroke: &PathStroke) -> f32 {
+    if stroke.is_empty() {
+        0.0
+    } else {
+        stroke.width
+    }
+}
+
+/// Twice the area of the polygon, positive if it is clockwise on screen.
+fn signed_area(points: &[Pos2]) -> f32 {
+    let Some(&last) = points.last() else {
+        return 0.0;
+    };
+    let mut previous = last;
+    let mut area = 0.0;
+    for &p in points {
+        area += previous.x * p.y - p.x * previous.y;
+        previous = p;
+    }
+    area
+}
+
+#[test]
+fn test_glow_reaches_outside() {
+    use crate::{pos2, Rect};
+
+    let glow = Glow::new(4.0, Color32::YELLOW);
+    let square = vec![
+        pos2(0.0, 0.0),
+        pos2(0.0, 10.0),
+        pos2(10.0, 10.0),
+        pos2(10.0, 0.0),
+    ];
+    let inner = Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 10.0));
+
+    // Either winding order gives an outline on the outside:
+    for points in [square.clone(), square.into_iter().rev().collect()] {
+        let shape = Shape::convex_polygon(points, Color32::RED, Stroke::NONE);
+        let outline = glow.with_blur(2.0).as_shape(&shape);
+        let bounds = outline.visual_bounding_rect();
+        assert!(bounds.contains_rect(inner.expand(4.0)), "{bounds:?}");
+        assert!(inner.expand(6.0).contains_rect(bounds), "{bounds:?}");
+    }
+
+    let line = Shape::line_segment([pos2(0.0, 0.0), pos2(10.0, 0.0)], (2.0, Color32::RED));
+    let bounds = glow.as_shape(&line).visual_bounding_rect();
+    assert_eq!(bounds.height(), 10.0);
+
+    assert_eq!(Glow::NONE.as_shape(&line), Shape::Noop);
+}
@@ -7,7 +7,7 @@ use std::sync::Arc;
 ///
 /// In order to paint the image on screen, you first need to convert it to
 ///
-/// See also: [`ColorImage`], [`FontImage`].
+/// See also: [`ColorImage`], [`FontImage`], [`CompressedImage`].
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum ImageData {
@@ -16,6 +16,17 @@ pub enum ImageData {
 
     /// Used for the font texture.
     Font(FontImage),
+
+    /// A block-compressed image, passed as-is to the GPU.
+    ///
+    /// Not all backends and GPUs support this, see [`CompressedFormat`].
+    Compressed(Arc<CompressedImage>),
+
+    /// Just the size of an RGBA texture, to be filled in later with partial updates.
+    ///
+    /// The backend allocates the texture without uploading anything.
+    /// Until filled in, it is transparent on most backends, but may be garbage on some.
+    Empty([usize; 2]),
 }
 
 impl ImageData {
@@ -23,6 +34,8 @@ impl ImageData {
         match self {
             Self::Color(image) => image.size,
             Self::Font(image) => image.size,
+            Self::Compressed(image) => image.size(),
+            Self::Empty(size) => *size,
         }
     }
 
@@ -34,9 +47,17 @@ impl ImageData {
         self.size()[1]
     }
 
+    /// For compressed images this is rounded up, i.e. 1 for most formats.
     pub fn bytes_per_pixel(&self) -> usize {
         match self {
-            Self::Color(_) | Self::Font(_) => 4,
+            Self::Color(_) | Self::Font(_) | Self::Empty(_) => 4,
+            Self::Compressed(image) => {
+                let [block_width, block_height] = image.format().block_size();
+                image
+                    .format()
+                    .bytes_per_block()
+                    .div_ceil(block_width * block_height)
+            }
         }
     }
 }
@@ -247,6 +268,23 @@ impl ColorImage {
             pixels: output,
         }
     }
+
+    /// Convert all pixels from one color space to another, e.g. from sRGB to Display P3.
+    ///
+    /// This is used by backends rendering to a surface with a different color space
+    /// than the one in [`TextureOptions::color_space`].
+    pub fn convert_color_space(&mut self, from: ecolor::ColorSpace, to: ecolor::ColorSpace) {
+        let Some(matrix) = from.conversion_matrix(to) else {
+            return;
+        };
+        profiling::function_scope!();
+        for pixel in &mut self.pixels {
+            // Grays (incl. the white of e.g. icons) look the same in all color spaces:
+            if pixel.r() != pixel.g() || pixel.g() != pixel.b() {
+                *pixel = ecolor::apply_color_matrix(*pixel, &matrix);
+            }
+        }
+    }
 }
 
 impl std::ops::Index<(usize, usize)> for ColorImage {
@@ -420,6 +458,175 @@ impl From<FontImage> for ImageData {
 
 // ----------------------------------------------------------------------------
 
+/// A GPU block compression format, see [`CompressedImage`].
+///
+/// All formats store sRGB colors, which must have premultiplied alpha like all egui textures.
+/// Which formats are available depends on the GPU: BC is common on desktop,
+/// while ETC2 and ASTC are common on mobile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum CompressedFormat {
+    /// BC1 (DXT1): RGB with 1-bit alpha, 8 bytes per 4x4 block.
+    Bc1,
+
+    /// BC3 (DXT5): RGBA, 16 bytes per 4x4 block.
+    Bc3,
+
+    /// BC7: high quality RGBA, 16 bytes per 4x4 block.
+    Bc7,
+
+    /// ETC2: RGB, 8 bytes per 4x4 block.
+    Etc2Rgb8,
+
+    /// ETC2 with EAC alpha: RGBA, 16 bytes per 4x4 block.
+    Etc2Rgba8,
+
+    /// ASTC with 4x4 blocks: RGBA, 16 bytes per block.
+    Astc4x4,
+}
+
+impl CompressedFormat {
+    /// Width and height of a block, in texels.
+    #[inline]
+    pub fn block_size(self) -> [usize; 2] {
+        match self {
+            Self::Bc1
+            | Self::Bc3
+            | Self::Bc7
+            | Self::Etc2Rgb8
+            | Self::Etc2Rgba8
+            | Self::Astc4x4 => [4, 4],
+        }
+    }
+
+    #[inline]
+    pub fn bytes_per_block(self) -> usize {
+        match self {
+            Self::Bc1 | Self::Etc2Rgb8 => 8,
+            Self::Bc3 | Self::Bc7 | Self::Etc2Rgba8 | Self::Astc4x4 => 16,
+        }
+    }
+
+    /// Is this position on a block boundary, i.e. where a partial update can start?
+    #[inline]
+    pub fn is_block_aligned(self, pos: [usize; 2]) -> bool {
+        let [block_width, block_height] = self.block_size();
+        pos[0] % block_width == 0 && pos[1] % block_height == 0
+    }
+}
+
+/// A block-compressed image, e.g. loaded from a KTX2 or DDS file.
+///
+/// Compressed textures use a quarter or less of the video memory of a [`ColorImage`],
+/// which matters for apps with many large images or atlases.
+///
+/// The fields are only set by [`Self::new`], which checks that they fit together.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "UncheckedCompressedImage"))]
+pub struct CompressedImage {
+    size: [usize; 2],
+    format: CompressedFormat,
+    data: Vec<u8>,
+}
+
+impl CompressedImage {
+    /// Panics if the size isn't a multiple of the block size, or `data` has the wrong length.
+    pub fn new(size: [usize; 2], format: CompressedFormat, data: Vec<u8>) -> Self {
+        Self::try_new(size, format, data).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Like [`Self::new`], but returns an error instead of panicking.
+    ///
+    /// # Errors
+    /// If the size isn't a multiple of the block size, or `data` has the wrong length.
+    pub fn try_new(
+        size: [usize; 2],
+        format: CompressedFormat,
+        data: Vec<u8>,
+    ) -> Result<Self, String> {
+        if !format.is_block_aligned(size) {
+            return Err(format!(
+                "Size {size:?} is not a multiple of the block size of {format:?}"
+            ));
+        }
+        let image = Self { size, format, data };
+        let expected_len = image.bytes_per_row() * image.block_rows();
+        if image.data.len() != expected_len {
+            return Err(format!(
+                "Wrong data length for a {size:?} {format:?} image: {} bytes, expected {expected_len}",
+                image.data.len()
+            ));
+        }
+        Ok(image)
+    }
+
+    /// width, height in texels. Always a multiple of the block size.
+    #[inline]
+    pub fn size(&self) -> [usize; 2] {
+        self.size
+    }
+
+    #[inline]
+    pub fn format(&self) -> CompressedFormat {
+        self.format
+    }
+
+    /// The blocks, row by row, from top to bottom.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Bytes per row of blocks.
+    pub fn bytes_per_row(&self) -> usize {
+        self.size[0] / self.format.block_size()[0] * self.format.bytes_per_block()
+    }
+
+    /// Number of rows of blocks.
+    pub fn block_rows(&self) -> usize {
+        self.size[1] / self.format.block_size()[1]
+    }
+}
+
+impl std::fmt::Debug for CompressedImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompressedImage")
+            .field("size", &self.size)
+            .field("format", &self.format)
+            .field("data", &format!("{} bytes", self.data.len()))
+            .finish()
+    }
+}
+
+/// A [`CompressedImage`] as deserialized, before it is checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedCompressedImage {
+    size: [usize; 2],
+    format: CompressedFormat,
+    data: Vec<u8>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedCompressedImage> for CompressedImage {
+    type Error = String;
+
+    fn try_from(image: UncheckedCompressedImage) -> Result<Self, String> {
+        let UncheckedCompressedImage { size, format, data } = image;
+        Self::try_new(size, format, data)
+    }
+}
+
+impl From<CompressedImage> for ImageData {
+    #[inline]
+    fn from(image: CompressedImage) -> Self {
+        Self::Compressed(Arc::new(image))
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// A change to an image.
 ///
 /// Either a whole new image, or an update to a rectangular region of it.
@@ -453,9 +660,19 @@ impl ImageDelta {
     }
 
     /// Update a sub-region of an existing texture.
+    ///
+    /// Panics if the image is a [`CompressedImage`] and `pos` isn't on a block boundary.
     pub fn partial(pos: [usize; 2], image: impl Into<ImageData>, options: TextureOptions) -> Self {
+        let image = image.into();
+        if let ImageData::Compressed(image) = &image {
+            assert!(
+                image.format().is_block_aligned(pos),
+                "A partial update of a {:?} texture must start on a block boundary, not at {pos:?}",
+                image.format()
+            );
+        }
         Self {
-            image: image.into(),
+            image,
             options,
             pos: Some(pos),
         }
@@ -467,3 +684,31 @@ impl ImageDelta {
         self.pos.is_none()
     }
 }
+
+#[test]
+fn test_compressed_image() {
+    let image = CompressedImage::new([8, 12], CompressedFormat::Bc1, vec![0; 2 * 3 * 8]);
+    assert_eq!(image.bytes_per_row(), 16);
+    assert_eq!(image.block_rows(), 3);
+    assert_eq!(ImageData::from(image).bytes_per_pixel(), 1);
+}
+
+#[test]
+#[should_panic = "multiple of the block size"]
+fn test_compressed_image_size() {
+    CompressedImage::new([6, 4], CompressedFormat::Bc7, vec![0; 32]);
+}
+
+#[test]
+fn test_compressed_image_data_length() {
+    assert!(CompressedImage::try_new([4, 4], CompressedFormat::Bc7, vec![0; 16]).is_ok());
+    assert!(CompressedImage::try_new([4, 4], CompressedFormat::Bc7, vec![0; 8]).is_err());
+}
+
+#[test]
+#[should_panic = "must start on a block boundary"]
+fn test_compressed_partial_update_alignment() {
+    let image = CompressedImage::new([4, 4], CompressedFormat::Bc1, vec![0; 8]);
+    let _ = ImageDelta::partial([4, 8], image.clone(), Default::default());
+    let _ = ImageDelta::partial([2, 0], image, Default::default());
+}
@@ -27,7 +27,9 @@ mod brush;
 pub mod color;
 mod corner_radius;
 mod corner_radius_f32;
+mod glow;
 pub mod image;
+mod live_path;
 mod margin;
 mod margin_f32;
 mod mesh;
@@ -50,22 +52,25 @@ pub use self::{
     color::ColorMode,
     corner_radius::CornerRadius,
     corner_radius_f32::CornerRadiusF32,
-    image::{ColorImage, FontImage, ImageData, ImageDelta},
+    glow::Glow,
+    image::{ColorImage, CompressedFormat, CompressedImage, FontImage, ImageData, ImageDelta},
+    live_path::LivePath,
     margin::Margin,
     margin_f32::*,
-    mesh::{Mesh, Mesh16, Vertex},
+    mesh::{Mesh, Mesh16, MeshIndexType, MeshShader, MeshShaderId, Vertex},
     shadow::Shadow,
     shapes::{
-        ArcShape, CircleShape, CubicBezierShape, EllipseShape, PaintCallback, PaintCallbackInfo,
-        PathShape, QuadraticBezierShape, RectShape, Shape, TextShape,
+        ArcShape, BackendCallback, BoxedBackendCallback, CallbackResources, CapsuleShape,
+        CircleShape, CubicBezierShape, EllipseShape, PaintCallback, PaintCallbackInfo, PathShape,
+        QuadraticBezierShape, RectShape, Shape, TextShape,
     },
     stats::PaintStats,
     stroke::{LineCap, LineJoin, PathStroke, Stroke, StrokeKind},
-    tessellator::{TessellationOptions, Tessellator},
+    tessellator::{PixelSnapping, TessellationOptions, Tessellator},
     text::{FontFamily, FontId, Fonts, Galley},
     texture_atlas::TextureAtlas,
-    texture_handle::TextureHandle,
-    textures::TextureManager,
+    texture_handle::{TextureHandle, TextureUploader},
+    textures::{TextureManager, TextureSnapshot},
     viewport::ViewportInPixels,
 };
 
@@ -75,7 +80,7 @@ pub type Rounding = CornerRadius;
 #[allow(deprecated)]
 pub use tessellator::tessellate_shapes;
 
-pub use ecolor::{Color32, Hsva, HsvaGamma, Rgba};
+pub use ecolor::{Color32, Hsva, HsvaGamma, Rgba, RgbaF16};
 pub use emath::{pos2, vec2, Pos2, Rect, Vec2};
 
 #[deprecated = "Use the ahash crate directly."]
@@ -0,0 +1,361 @@
+use std::sync::Arc;
+
+use crate::{Color32, Mesh, Pos2, Shape, Stroke, TessellationOptions, Vec2};
+
+/// A line that you keep adding points to, e.g. a live oscilloscope or telemetry trace.
+///
+/// The tessellated [`Mesh`] is kept between frames, and only the newly added points
+/// are tessellated, so painting a long trace every frame stays cheap.
+/// The last point is tessellated again too, since its corner depends on the points after it,
+/// so the result is exactly the same as tessellating the whole line at once.
+///
+/// The mesh is rebuilt from scratch only when the stroke, `pixels_per_point`
+/// or [`TessellationOptions::feathering`] change.
+///
+/// To be extendable the line is tessellated on its own, not by the [`crate::Tessellator`]:
+/// it always has butt caps, and miter joins that are beveled when sharper than a right angle,
+/// whatever the [`Stroke::cap`] and [`Stroke::join`].
+///
+/// ```
+/// # use epaint::{pos2, Color32, LivePath, Stroke};
+/// let mut trace = LivePath::new(Stroke::new(1.0, Color32::GREEN));
+/// trace.extend([pos2(0.0, 0.0), pos2(1.0, 2.0)]);
+/// let shape = trace.shape(1.0, &Default::default());
+///
+/// trace.push(pos2(2.0, 1.0)); // Only the new segment (and the corner before it) is tessellated:
+/// let shape = trace.shape(1.0, &Default::default());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LivePath {
+    points: Vec<Pos2>,
+    stroke: Stroke,
+
+    /// The tessellation of the first [`Self::num_tessellated`] points.
+    mesh: Arc<Mesh>,
+    num_tessellated: usize,
+
+    /// Where the last tessellated point starts in [`Self::mesh`], to tessellate it again.
+    last_point: PointStart,
+
+    /// The feathering [`Self::mesh`] was tessellated with.
+    tessellated_with: Option<f32>,
+}
+
+/// Where the tessellation of a point starts in the mesh.
+#[derive(Clone, Copy, Debug, Default)]
+struct PointStart {
+    num_vertices: usize,
+    num_indices: usize,
+
+    /// The vertices across the line at the point before, to connect to.
+    previous: Option<u32>,
+}
+
+impl LivePath {
+    pub fn new(stroke: impl Into<Stroke>) -> Self {
+        Self {
+            stroke: stroke.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a point to the end of the line.
+    #[inline]
+    pub fn push(&mut self, point: Pos2) {
+        self.points.push(point);
+    }
+
+    /// Add many points to the end of the line.
+    pub fn extend(&mut self, points: impl IntoIterator<Item = Pos2>) {
+        self.points.extend(points);
+    }
+
+    /// Remove all points, e.g. to start a new sweep.
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.invalidate();
+    }
+
+    #[inline]
+    pub fn points(&self) -> &[Pos2] {
+        &self.points
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    #[inline]
+    pub fn stroke(&self) -> &Stroke {
+        &self.stroke
+    }
+
+    /// Change the stroke. This means the whole line needs to be tessellated again.
+    pub fn set_stroke(&mut self, stroke: impl Into<Stroke>) {
+        let stroke = stroke.into();
+        if stroke != self.stroke {
+            self.stroke = stroke;
+            self.invalidate();
+        }
+    }
+
+    fn invalidate(&mut self) {
+        self.mesh = Default::default();
+        self.num_tessellated = 0;
+        self.last_point = Default::default();
+    }
+
+    /// The tessellated line, tessellating only the points added since last time.
+    pub fn mesh(&mut self, pixels_per_point: f32, options: &TessellationOptions) -> Arc<Mesh> {
+        let feathering = if options.feathering {
+            options.feathering_size_in_pixels / pixels_per_point
+        } else {
+            0.0
+        };
+        if self.tessellated_with != Some(feathering) {
+            self.invalidate();
+            self.tessellated_with = Some(feathering);
+        }
+
+        if self.num_tessellated < self.points.len()
+            && 2 <= self.points.len()
+            && !self.stroke.is_empty()
+        {
+            self.tessellate_new_points(feathering);
+        }
+
+        self.mesh.clone()
+    }
+
+    /// The tessellated line as a [`Shape`], see [`Self::mesh`].
+    pub fn shape(&mut self, pixels_per_point: f32, options: &TessellationOptions) -> Shape {
+        Shape::Mesh(self.mesh(pixels_per_point, options))
+    }
+
+    fn tessellate_new_points(&mut self, feathering: f32) {
+        let Self {
+            points,
+            stroke,
+            mesh,
+            num_tessellated,
+            last_point,
+            ..
+        } = self;
+
+        // Each point gets one or two rows of vertices across the line, with these offsets along its normal:
+        let lanes: &[(f32, Color32)] = if feathering <= 0.0 {
+            &[
+                (0.5 * stroke.width, stroke.color),
+                (-0.5 * stroke.width, stroke.color),
+            ]
+        } else if stroke.width <= 0.9 * feathering {
+            // A thin line is a ridge two feather-widths wide, and fainter instead of thinner:
+            let color = stroke.color.gamma_multiply(stroke.width / feathering);
+            &[
+                (feathering, Color32::TRANSPARENT),
+                (0.0, color),
+                (-feathering, Color32::TRANSPARENT),
+            ]
+        } else {
+            let inner = 0.5 * (stroke.width - feathering);
+            let outer = 0.5 * (stroke.width + feathering);
+            &[
+                (outer, Color32::TRANSPARENT),
+                (inner, stroke.color),
+                (-inner, stroke.color),
+                (-outer, Color32::TRANSPARENT),
+            ]
+        };
+
+        // The mesh is only shared with the shapes of earlier frames, which are gone by the time
+        // the next frame is painted, so this doesn't copy (see `test_live_path_is_not_copied` in egui):
+        let mesh = Arc::make_mut(mesh);
+
+        // Redo the last point, which has an end cap instead of the corner it needs now:
+        let first = num_tessellated.saturating_sub(1);
+        mesh.vertices.truncate(last_point.num_vertices);
+        mesh.indices.truncate(last_point.num_indices);
+        let mut previous = last_point.previous;
+
+        let n = points.len();
+        for i in first..n {
+            if i == n - 1 {
+                *last_point = PointStart {
+                    num_vertices: mesh.vertices.len(),
+                    num_indices: mesh.indices.len(),
+                    previous,
+                };
+            }
+
+            let pos = points[i];
+            let (normal, corner_normal) = point_normals(points, i);
+            for normal in std::iter::once(normal).chain(corner_normal) {
+                // Butt caps, feathered over the last feather-width of the line:
+                let cap = if 0.0 < feathering && (i == 0 || i == n - 1) {
+                    let outward = if i == 0 {
+                        normal.rot90()
+                    } else {
+                        -normal.rot90()
+                    };
+                    Some(0.5 * feathering * outward)
+                } else {
+                    None
+                };
+                let row_pos = pos - cap.unwrap_or_default();
+
+                let row = mesh.vertices.len() as u32;
+                for &(offset, color) in lanes {
+                    mesh.colored_vertex(row_pos + offset * normal, color);
+                }
+                if let Some(previous) = previous {
+                    for lane in 0..lanes.len() as u32 - 1 {
+                        let (a, b) = (previous + lane, row + lane);
+                        mesh.add_triangle(a, a + 1, b);
+                        mesh.add_triangle(a + 1, b, b + 1);
+                    }
+                }
+                previous = Some(row);
+
+                if let Some(cap) = cap {
+                    let last_lane = lanes.len() as u32 - 1;
+                    let first_edge = mesh.vertices.len() as u32;
+                    mesh.colored_vertex(pos + cap + lanes[0].0 * normal, Color32::TRANSPARENT);
+                    mesh.colored_vertex(
+                        pos + cap + lanes[last_lane as usize].0 * normal,
+                        Color32::TRANSPARENT,
+                    );
+                    let last_edge = first_edge + 1;
+                    for lane in 0..last_lane - 1 {
+                        mesh.add_triangle(row + lane, row + lane + 1, first_edge);
+                    }
+                    mesh.add_triangle(row + last_lane - 1, row + last_lane, last_edge);
+                    mesh.add_triangle(row + last_lane - 1, last_edge, first_edge);
+                }
+            }
+        }
+
+        *num_tessellated = n;
+    }
+}
+
+/// The normals at a point of the line, scaled to keep the line its width around corners.
+///
+/// A second one for corners sharper than a right angle, which are cut off (beveled),
+/// like [`crate::tessellator::Path::add_open_points`] does.
+fn point_normals(points: &[Pos2], i: usize) -> (Vec2, Option<Vec2>) {
+    let segment_normal = |a: usize, b: usize| (points[b] - points[a]).normalized().rot90();
+    let n = points.len();
+    if i == 0 {
+        return (segment_normal(0, 1), None);
+    }
+    if i == n - 1 {
+        return (segment_normal(n - 2, n - 1), None);
+    }
+
+    let mut n0 = segment_normal(i - 1, i);
+    let mut n1 = segment_normal(i, i + 1);
+    // Handle duplicated points:
+    if n0 == Vec2::ZERO {
+        n0 = n1;
+    } else if n1 == Vec2::ZERO {
+        n1 = n0;
+    }
+
+    let normal = (n0 + n1) / 2.0;
+    let length_sq = normal.length_sq();
+    if length_sq < 0.5 {
+        let center_normal = normal.normalized();
+        let n0c = (n0 + center_normal) / 2.0;
+        let n1c = (n1 + center_normal) / 2.0;
+        (n0c / n0c.length_sq(), Some(n1c / n1c.length_sq()))
+    } else {
+        (normal / length_sq, None)
+    }
+}
+
+#[test]
+fn test_live_path() {
+    use crate::pos2;
+
+    let options = TessellationOptions::default();
+    let mut path = LivePath::new(Stroke::new(1.0, Color32::WHITE));
+    assert!(path.mesh(1.0, &options).is_empty());
+
+    path.extend([pos2(0.0, 0.0), pos2(10.0, 0.0), pos2(10.0, 10.0)]);
+    let first = path.mesh(1.0, &options);
+    assert!(!first.is_empty());
+    let last_point_start = path.last_point.num_vertices;
+
+    // Nothing new, so nothing to do:
+    assert!(Arc::ptr_eq(&first, &path.mesh(1.0, &options)));
+
+    // New points extend the old mesh:
+    path.push(pos2(20.0, 10.0));
+    let second = path.mesh(1.0, &options);
+    assert!(first.vertices.len() < second.vertices.len());
+    assert_eq!(
+        first.vertices[..last_point_start],
+        second.vertices[..last_point_start],
+        "Only the last point was tessellated again"
+    );
+
+    // A new scale means starting over:
+    let rebuilt = path.mesh(2.0, &options);
+    assert_ne!(rebuilt.vertices[..4], second.vertices[..4]);
+
+    path.clear();
+    assert!(path.mesh(2.0, &options).is_empty());
+}
+
+#[test]
+fn test_live_path_pieces_match_the_whole() {
+    use crate::pos2;
+
+    // A zig-zag, with a sharp spike and a duplicated point:
+    let points = [
+        pos2(0.0, 0.0),
+        pos2(10.0, 5.0),
+        pos2(20.0, 0.0),
+        pos2(21.0, 30.0),
+        pos2(22.0, 0.0),
+        pos2(22.0, 0.0),
+        pos2(40.0, 3.0),
+    ];
+
+    for width in [0.5, 1.0, 4.0] {
+        for feathering in [false, true] {
+            let options = TessellationOptions {
+                feathering,
+                ..Default::default()
+            };
+            let stroke = Stroke::new(width, Color32::WHITE);
+
+            let mut whole = LivePath::new(stroke);
+            whole.extend(points);
+            let whole = whole.mesh(1.5, &options);
+
+            let mut pieces = LivePath::new(stroke);
+            for &point in &points {
+                pieces.push(point);
+                pieces.mesh(1.5, &options);
+            }
+            let pieces = pieces.mesh(1.5, &options);
+
+            assert_eq!(pieces.vertices, whole.vertices, "{width} {feathering}");
+            assert_eq!(pieces.indices, whole.indices, "{width} {feathering}");
+            assert!(
+                pieces
+                    .indices
+                    .iter()
+                    .all(|&i| (i as usize) < pieces.vertices.len()),
+                "{width} {feathering}"
+            );
+        }
+    }
+}
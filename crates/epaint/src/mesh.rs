@@ -1,4 +1,5 @@
-use crate::{emath, Color32, TextureId, WHITE_UV};
+use crate::{emath, Color32, MarginF32, Rgba, TextureId, WHITE_UV};
+use ecolor::RgbaF16;
 use emath::{Pos2, Rect, Rot2, TSTransform, Vec2};
 
 /// The 2D vertex type.
@@ -48,7 +49,8 @@ pub struct Vertex {
 pub struct Mesh {
     /// Draw as triangles (i.e. the length is always multiple of three).
     ///
-    /// If you only support 16-bit indices you can use [`Mesh::split_to_u16`].
+    /// If you only support 16-bit indices you can use [`Mesh::split_to_u16`],
+    /// or tessellate with [`crate::TessellationOptions::mesh_index_type`] set to [`MeshIndexType::U16`].
     ///
     /// egui is NOT consistent with what winding order it uses, so turn off backface culling.
     pub indices: Vec<u32>,
@@ -58,6 +60,26 @@ pub struct Mesh {
 
     /// The texture to use when drawing these triangles.
     pub texture_id: TextureId,
+
+    /// Extended range (HDR) colors, replacing [`Vertex::color`] for the first `hdr_colors.len()` vertices.
+    ///
+    /// This is empty for normal meshes.
+    /// Use it for colors brighter than white when rendering to a floating point render target.
+    /// Renderers that don't support HDR ignore this, and use [`Vertex::color`] as usual.
+    ///
+    /// See [`Self::colored_vertex_hdr`] and [`Self::brighten`].
+    pub hdr_colors: Vec<RgbaF16>,
+
+    /// Draw this mesh with a custom shader instead of the default one.
+    ///
+    /// Backends that don't support custom shaders draw the mesh normally.
+    pub shader: Option<MeshShader>,
+
+    /// Written to the pick target by backends that support picking, see [`crate::Shape::Pick`].
+    ///
+    /// Zero means the mesh can't be picked.
+    /// This is set by the [`crate::Tessellator`], and is not changed by [`Self::append`].
+    pub pick_id: u32,
     // TODO(emilk): bounding rectangle
 }
 
@@ -69,11 +91,19 @@ impl Mesh {
         }
     }
 
+    /// Draw this mesh with a custom shader, see [`Self::shader`].
+    #[inline]
+    pub fn with_shader(mut self, shader: MeshShader) -> Self {
+        self.shader = Some(shader);
+        self
+    }
+
     /// Restore to default state, but without freeing memory.
     pub fn clear(&mut self) {
         self.indices.clear();
         self.vertices.clear();
         self.vertices = Default::default();
+        self.hdr_colors.clear();
     }
 
     /// Returns the amount of memory used by the vertices and indices.
@@ -81,6 +111,7 @@ impl Mesh {
         std::mem::size_of::<Self>()
             + self.vertices.len() * std::mem::size_of::<Vertex>()
             + self.indices.len() * std::mem::size_of::<u32>()
+            + self.hdr_colors.len() * std::mem::size_of::<RgbaF16>()
     }
 
     /// Are all indices within the bounds of the contained vertices?
@@ -88,12 +119,39 @@ impl Mesh {
         profiling::function_scope!();
 
         if let Ok(n) = u32::try_from(self.vertices.len()) {
-            self.indices.iter().all(|&i| i < n)
+            self.indices.iter().all(|&i| i < n) && self.hdr_colors.len() <= self.vertices.len()
         } else {
             false
         }
     }
 
+    /// Does this mesh have any extended range colors? See [`Self::hdr_colors`].
+    #[inline]
+    pub fn is_hdr(&self) -> bool {
+        !self.hdr_colors.is_empty()
+    }
+
+    /// The extended range color of the vertex at the given index.
+    ///
+    /// This is the [`Vertex::color`] unless it was replaced by [`Self::hdr_colors`].
+    #[inline]
+    pub fn hdr_color(&self, index: usize) -> RgbaF16 {
+        self.hdr_colors
+            .get(index)
+            .copied()
+            .unwrap_or_else(|| self.vertices[index].color.into())
+    }
+
+    /// Make sure every vertex has an entry in [`Self::hdr_colors`].
+    fn fill_hdr_colors(&mut self) {
+        let start = self.hdr_colors.len();
+        self.hdr_colors.extend(
+            self.vertices[start..]
+                .iter()
+                .map(|vertex| RgbaF16::from(vertex.color)),
+        );
+    }
+
     pub fn is_empty(&self) -> bool {
         self.indices.is_empty() && self.vertices.is_empty()
     }
@@ -122,7 +180,9 @@ impl Mesh {
         debug_assert!(other.is_valid(), "Other mesh is invalid");
 
         if self.is_empty() {
+            let pick_id = self.pick_id;
             *self = other;
+            self.pick_id = pick_id;
         } else {
             self.append_ref(&other);
         }
@@ -131,17 +191,28 @@ impl Mesh {
     /// Append all the indices and vertices of `other` to `self` without
     /// taking ownership.
     ///
-    /// Panics when `other` mesh has a different texture.
+    /// Panics when `other` mesh has a different texture or shader.
     pub fn append_ref(&mut self, other: &Self) {
         debug_assert!(other.is_valid(), "Other mesh is invalid");
 
         if self.is_empty() {
             self.texture_id = other.texture_id;
+            self.shader.clone_from(&other.shader);
         } else {
             assert_eq!(
                 self.texture_id, other.texture_id,
                 "Can't merge Mesh using different textures"
             );
+            assert_eq!(
+                self.shader, other.shader,
+                "Can't merge Mesh using different shaders"
+            );
+        }
+
+        if other.is_hdr() {
+            self.fill_hdr_colors();
+            self.hdr_colors
+                .extend((0..other.vertices.len()).map(|i| other.hdr_color(i)));
         }
 
         let index_offset = self.vertices.len() as u32;
@@ -166,6 +237,31 @@ impl Mesh {
         });
     }
 
+    /// Add a colored vertex with an extended range color, which may be brighter than white.
+    ///
+    /// Renderers without HDR support will see the color clamped to the normal range.
+    ///
+    /// Panics when the mesh has assigned a texture.
+    pub fn colored_vertex_hdr(&mut self, pos: Pos2, color: Rgba) {
+        self.fill_hdr_colors();
+        self.colored_vertex(pos, color.into());
+        self.hdr_colors.push(color.into());
+    }
+
+    /// Multiply the colors (but not alpha) of all vertices with `factor`,
+    /// which may take them out of the normal range, e.g. to make something glow on an HDR display.
+    ///
+    /// Renderers without HDR support will see the colors clamped to the normal range.
+    pub fn brighten(&mut self, factor: f32) {
+        self.fill_hdr_colors();
+        for (vertex, hdr_color) in self.vertices.iter_mut().zip(&mut self.hdr_colors) {
+            let [r, g, b, a] = Rgba::from(*hdr_color).to_array();
+            let brightened = Rgba::from_rgba_premultiplied(r * factor, g * factor, b * factor, a);
+            vertex.color = brightened.into();
+            *hdr_color = brightened.into();
+        }
+    }
+
     /// Add a triangle.
     #[inline(always)]
     pub fn add_triangle(&mut self, a: u32, b: u32, c: u32) {
@@ -218,6 +314,93 @@ impl Mesh {
         });
     }
 
+    /// Add an arbitrary textured quadrilateral, e.g. a rotated or skewed image.
+    ///
+    /// The corners are given clockwise, starting at the one where the top-left of the texture goes,
+    /// and `uv` are the texture coordinates of those same corners.
+    ///
+    /// The quad is split into two triangles along the diagonal from the first to the third corner,
+    /// so a texture on a quad that isn't a parallelogram will look bent along that diagonal.
+    pub fn add_quad_with_uv(&mut self, corners: [Pos2; 4], uv: [Pos2; 4], color: Color32) {
+        let idx = self.vertices.len() as u32;
+        self.add_triangle(idx, idx + 1, idx + 2);
+        self.add_triangle(idx, idx + 2, idx + 3);
+        for (pos, uv) in corners.into_iter().zip(uv) {
+            self.vertices.push(Vertex { pos, uv, color });
+        }
+    }
+
+    /// Add a "nine-slice" (9-patch) rectangle, used for skinned buttons, panels etc.
+    ///
+    /// The texture is split into a 3x3 grid by the margins:
+    /// the corners keep their size, the edges are stretched along their length,
+    /// and the center is stretched in both directions.
+    ///
+    /// `margins` is the size of the borders in `rect` (in points),
+    /// and `uv_margins` is the size of the same borders in the texture (in UV coordinates).
+    /// If `rect` is too small for the margins, they are shrunk to fit.
+    pub fn add_nine_slice(
+        &mut self,
+        rect: Rect,
+        uv: Rect,
+        margins: MarginF32,
+        uv_margins: MarginF32,
+        color: Color32,
+    ) {
+        let fit = |size: f32, a: f32, b: f32| {
+            if a + b > size && a + b > 0.0 {
+                size / (a + b)
+            } else {
+                1.0
+            }
+        };
+        let x_scale = fit(rect.width(), margins.left, margins.right);
+        let y_scale = fit(rect.height(), margins.top, margins.bottom);
+
+        let xs = [
+            rect.left(),
+            rect.left() + x_scale * margins.left,
+            rect.right() - x_scale * margins.right,
+            rect.right(),
+        ];
+        let ys = [
+            rect.top(),
+            rect.top() + y_scale * margins.top,
+            rect.bottom() - y_scale * margins.bottom,
+            rect.bottom(),
+        ];
+        let us = [
+            uv.left(),
+            uv.left() + uv_margins.left,
+            uv.right() - uv_margins.right,
+            uv.right(),
+        ];
+        let vs = [
+            uv.top(),
+            uv.top() + uv_margins.top,
+            uv.bottom() - uv_margins.bottom,
+            uv.bottom(),
+        ];
+
+        let idx = self.vertices.len() as u32;
+        for (&y, &v) in ys.iter().zip(&vs) {
+            for (&x, &u) in xs.iter().zip(&us) {
+                self.vertices.push(Vertex {
+                    pos: Pos2::new(x, y),
+                    uv: Pos2::new(u, v),
+                    color,
+                });
+            }
+        }
+        for row in 0..3 {
+            for column in 0..3 {
+                let top_left = idx + 4 * row + column;
+                self.add_triangle(top_left, top_left + 1, top_left + 4);
+                self.add_triangle(top_left + 4, top_left + 1, top_left + 5);
+            }
+        }
+    }
+
     /// Uniformly colored rectangle.
     #[inline(always)]
     pub fn add_colored_rect(&mut self, rect: Rect, color: Color32) {
@@ -232,38 +415,68 @@ impl Mesh {
     ///
     /// Splits this mesh into many smaller meshes (if needed)
     /// where the smaller meshes have 16-bit indices.
+    ///
+    /// Any [`Self::hdr_colors`] are dropped.
     pub fn split_to_u16(self) -> Vec<Mesh16> {
-        debug_assert!(self.is_valid(), "Mesh is invalid");
-
-        const MAX_SIZE: u32 = u16::MAX as u32;
+        self.split(MeshIndexType::U16.max_vertices())
+            .into_iter()
+            .map(|mesh| Mesh16 {
+                indices: mesh.indices.iter().map(|&i| i as u16).collect(),
+                vertices: mesh.vertices,
+                texture_id: mesh.texture_id,
+            })
+            .collect()
+    }
 
-        if self.vertices.len() <= MAX_SIZE as usize {
+    /// Splits this mesh into many smaller meshes (if needed)
+    /// where each of the smaller meshes has at most `max_vertices` vertices.
+    ///
+    /// The triangles stay in the same order, so the result paints the same as the original.
+    ///
+    /// # Panics
+    /// If a single triangle spans more than `max_vertices` vertices.
+    /// See [`Self::split_or_keep`] for a version that doesn't panic.
+    pub fn split(self, max_vertices: usize) -> Vec<Self> {
+        if self.vertices.len() <= max_vertices {
             // Common-case optimization:
-            return vec![Mesh16 {
-                indices: self.indices.iter().map(|&i| i as u16).collect(),
-                vertices: self.vertices,
-                texture_id: self.texture_id,
-            }];
+            return vec![self];
         }
+        self.split_pieces(max_vertices)
+            .unwrap_or_else(|| panic!("One triangle spanned more than {max_vertices} vertices"))
+    }
+
+    /// Like [`Self::split`], but returns the mesh unsplit
+    /// if a single triangle spans more than `max_vertices` vertices.
+    pub fn split_or_keep(self, max_vertices: usize) -> Vec<Self> {
+        if self.vertices.len() <= max_vertices {
+            return vec![self];
+        }
+        self.split_pieces(max_vertices)
+            .unwrap_or_else(|| vec![self])
+    }
+
+    /// `None` if a single triangle spans more than `max_vertices` vertices.
+    fn split_pieces(&self, max_vertices: usize) -> Option<Vec<Self>> {
+        debug_assert!(self.is_valid(), "Mesh is invalid");
 
         let mut output = vec![];
         let mut index_cursor = 0;
 
         while index_cursor < self.indices.len() {
             let span_start = index_cursor;
-            let mut min_vindex = self.indices[index_cursor];
-            let mut max_vindex = self.indices[index_cursor];
+            let mut min_vindex = self.indices[index_cursor] as usize;
+            let mut max_vindex = self.indices[index_cursor] as usize;
 
             while index_cursor < self.indices.len() {
                 let (mut new_min, mut new_max) = (min_vindex, max_vindex);
                 for i in 0..3 {
-                    let idx = self.indices[index_cursor + i];
+                    let idx = self.indices[index_cursor + i] as usize;
                     new_min = new_min.min(idx);
                     new_max = new_max.max(idx);
                 }
 
                 let new_span_size = new_max - new_min + 1; // plus one, because it is an inclusive range
-                if new_span_size <= MAX_SIZE {
+                if new_span_size <= max_vertices {
                     // Triangle fits
                     min_vindex = new_min;
                     max_vindex = new_max;
@@ -273,23 +486,28 @@ impl Mesh {
                 }
             }
 
-            assert!(
-                index_cursor > span_start,
-                "One triangle spanned more than {MAX_SIZE} vertices"
-            );
+            if index_cursor == span_start {
+                return None; // One triangle spanned more than `max_vertices` vertices
+            }
 
-            let mesh = Mesh16 {
+            let num_hdr_colors = self.hdr_colors.len();
+            let mesh = Self {
                 indices: self.indices[span_start..index_cursor]
                     .iter()
-                    .map(|vi| u16::try_from(vi - min_vindex).unwrap())
+                    .map(|&vi| vi - min_vindex as u32)
                     .collect(),
-                vertices: self.vertices[(min_vindex as usize)..=(max_vindex as usize)].to_vec(),
+                vertices: self.vertices[min_vindex..=max_vindex].to_vec(),
                 texture_id: self.texture_id,
+                hdr_colors: self.hdr_colors
+                    [min_vindex.min(num_hdr_colors)..(max_vindex + 1).min(num_hdr_colors)]
+                    .to_vec(),
+                shader: self.shader.clone(),
+                pick_id: self.pick_id,
             };
             debug_assert!(mesh.is_valid(), "Mesh is invalid");
             output.push(mesh);
         }
-        output
+        Some(output)
     }
 
     /// Translate location by this much, in-place
@@ -306,6 +524,13 @@ impl Mesh {
         }
     }
 
+    /// Scale by `scale` around `(0, 0)`, separately along x and y, and then move by `translation`, in-place.
+    pub fn transform_non_uniform(&mut self, translation: Vec2, scale: Vec2) {
+        for v in &mut self.vertices {
+            v.pos = (scale * v.pos.to_vec2() + translation).to_pos2();
+        }
+    }
+
     /// Rotate by some angle about an origin, in-place.
     ///
     /// Origin is a position in screen space.
@@ -318,6 +543,86 @@ impl Mesh {
 
 // ----------------------------------------------------------------------------
 
+/// Identifies a custom shader registered with a backend,
+/// e.g. with `egui_wgpu::Renderer::register_mesh_shader`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct MeshShaderId(pub u64);
+
+/// A custom shader for drawing a [`Mesh`], together with its uniforms.
+///
+/// Used for effects like dashed lines, glows, or SDF icons,
+/// which are then drawn in order with the rest of the ui.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct MeshShader {
+    /// The shader to use.
+    pub id: MeshShaderId,
+
+    /// Raw uniform data for this mesh, laid out the way the shader expects.
+    ///
+    /// Backends may limit the size, e.g. to [`Self::MAX_UNIFORMS_SIZE`].
+    pub uniforms: Vec<u8>,
+}
+
+impl MeshShader {
+    /// The largest [`Self::uniforms`] all backends accept.
+    pub const MAX_UNIFORMS_SIZE: usize = 256;
+
+    /// A shader without uniforms.
+    pub fn new(id: MeshShaderId) -> Self {
+        Self {
+            id,
+            uniforms: Vec::new(),
+        }
+    }
+
+    /// Set the uniforms from a plain-old-data value, e.g. a `#[repr(C)]` struct.
+    #[cfg(feature = "bytemuck")]
+    #[inline]
+    pub fn with_uniforms(mut self, uniforms: &impl bytemuck::Pod) -> Self {
+        self.uniforms = bytemuck::bytes_of(uniforms).to_vec();
+        self
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// What index type the render backend uses for its index buffers.
+///
+/// This decides how many vertices a single [`Mesh`] can have before it must be split,
+/// see [`crate::TessellationOptions::mesh_index_type`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum MeshIndexType {
+    /// 16-bit indices, for legacy backends (e.g. WebGL1 without extensions).
+    ///
+    /// Large meshes are split into many smaller ones.
+    U16,
+
+    /// 32-bit indices, as used by [`Mesh::indices`].
+    ///
+    /// This lets large scenes use a few big meshes instead of many small ones.
+    #[default]
+    U32,
+}
+
+impl MeshIndexType {
+    /// The most vertices a single [`Mesh`] can have with this index type.
+    ///
+    /// For 16-bit indices this leaves out the index `0xFFFF`,
+    /// which some backends reserve for primitive restart.
+    #[inline]
+    pub fn max_vertices(self) -> usize {
+        match self {
+            Self::U16 => u16::MAX as usize,
+            Self::U32 => u32::MAX as usize,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// A version of [`Mesh`] that uses 16-bit indices.
 ///
 /// This is produced by [`Mesh::split_to_u16`] and is meant to be used for legacy render backends.
@@ -344,3 +649,123 @@ impl Mesh16 {
         }
     }
 }
+
+#[test]
+fn test_hdr_colors_survive_append() {
+    let mut normal = Mesh::default();
+    normal.colored_vertex(Pos2::ZERO, Color32::RED);
+
+    let mut hdr = Mesh::default();
+    hdr.colored_vertex_hdr(Pos2::ZERO, Rgba::from_rgb(4.0, 2.0, 1.0));
+    assert_eq!(
+        hdr.vertices[0].color,
+        Color32::WHITE,
+        "clamped for LDR renderers"
+    );
+
+    normal.append_ref(&hdr);
+    assert!(normal.is_valid());
+    assert_eq!(normal.hdr_colors.len(), 2);
+    assert_eq!(normal.hdr_color(0), RgbaF16::from(Color32::RED));
+    assert_eq!(
+        Rgba::from(normal.hdr_color(1)),
+        Rgba::from_rgb(4.0, 2.0, 1.0)
+    );
+
+    normal.brighten(0.5);
+    assert_eq!(
+        Rgba::from(normal.hdr_color(1)),
+        Rgba::from_rgb(2.0, 1.0, 0.5)
+    );
+}
+
+#[test]
+fn test_nine_slice() {
+    let rect = Rect::from_min_max(Pos2::new(0.0, 0.0), Pos2::new(100.0, 20.0));
+    let uv = Rect::from_min_max(Pos2::new(0.0, 0.0), Pos2::new(1.0, 1.0));
+
+    let mut mesh = Mesh::default();
+    mesh.add_nine_slice(
+        rect,
+        uv,
+        MarginF32::same(4.0),
+        MarginF32::same(0.25),
+        Color32::WHITE,
+    );
+    assert!(mesh.is_valid());
+    assert_eq!(mesh.vertices.len(), 16);
+    assert_eq!(mesh.indices.len(), 9 * 6);
+    assert_eq!(mesh.vertices[5].pos, Pos2::new(4.0, 4.0));
+    assert_eq!(mesh.vertices[5].uv, Pos2::new(0.25, 0.25));
+    assert_eq!(mesh.vertices[10].pos, Pos2::new(96.0, 16.0));
+
+    // Margins that don't fit are shrunk:
+    let mut mesh = Mesh::default();
+    let small = Rect::from_min_max(Pos2::ZERO, Pos2::new(6.0, 6.0));
+    mesh.add_nine_slice(
+        small,
+        uv,
+        MarginF32::same(4.0),
+        MarginF32::same(0.25),
+        Color32::WHITE,
+    );
+    assert_eq!(mesh.vertices[5].pos, Pos2::new(3.0, 3.0));
+    assert_eq!(mesh.vertices[6].pos, Pos2::new(3.0, 3.0));
+}
+
+#[test]
+fn test_add_quad_with_uv() {
+    let corners = [
+        Pos2::new(0.0, 0.0),
+        Pos2::new(2.0, 1.0),
+        Pos2::new(1.0, 3.0),
+        Pos2::new(-1.0, 2.0),
+    ];
+    let uv = [
+        Pos2::new(0.0, 0.0),
+        Pos2::new(1.0, 0.0),
+        Pos2::new(1.0, 1.0),
+        Pos2::new(0.0, 1.0),
+    ];
+    let mut mesh = Mesh::with_texture(TextureId::User(1));
+    mesh.add_quad_with_uv(corners, uv, Color32::WHITE);
+    assert!(mesh.is_valid());
+    assert_eq!(mesh.indices, [0, 1, 2, 0, 2, 3]);
+    assert_eq!(mesh.vertices[3].pos, corners[3]);
+    assert_eq!(mesh.vertices[3].uv, uv[3]);
+}
+
+#[test]
+fn test_split() {
+    let mut mesh = Mesh {
+        pick_id: 7,
+        ..Default::default()
+    };
+    for i in 0..10 {
+        let x = i as f32;
+        mesh.add_colored_rect(
+            Rect::from_min_size(Pos2::new(x, 0.0), Vec2::splat(1.0)),
+            Color32::RED,
+        );
+    }
+    mesh.colored_vertex_hdr(Pos2::ZERO, Rgba::from_rgb(4.0, 2.0, 1.0));
+
+    assert_eq!(mesh.clone().split(100), vec![mesh.clone()]);
+
+    let pieces = mesh.clone().split(9);
+    assert_eq!(pieces.len(), 5, "two quads per piece");
+    for piece in &pieces {
+        assert!(piece.is_valid());
+        assert!(piece.vertices.len() <= 9);
+        assert_eq!(piece.pick_id, 7);
+    }
+    assert_eq!(
+        pieces.iter().map(|m| m.indices.len()).sum::<usize>(),
+        mesh.indices.len()
+    );
+    assert_eq!(pieces[0].hdr_colors.len(), 8);
+    assert_eq!(pieces[4].hdr_colors.len(), 8);
+
+    // A triangle over the limit can't be split off:
+    assert_eq!(mesh.clone().split_or_keep(2), vec![mesh]);
+}
@@ -1,8 +1,8 @@
 use std::sync::Arc;
 
 use crate::{
-    color, ArcShape, CircleShape, Color32, ColorMode, CubicBezierShape, EllipseShape, Mesh,
-    PathShape, QuadraticBezierShape, RectShape, Shape, TextShape,
+    color, ArcShape, CapsuleShape, CircleShape, Color32, ColorMode, CubicBezierShape, EllipseShape,
+    Mesh, PathShape, QuadraticBezierShape, RectShape, Rgba, RgbaF16, Shape, TextShape,
 };
 
 /// Remember to handle [`Color32::PLACEHOLDER`] specially!
@@ -14,7 +14,7 @@ pub fn adjust_colors(
     match shape {
         Shape::Noop => {}
 
-        Shape::Vec(shapes) => {
+        Shape::Vec(shapes) | Shape::Pick { shapes, .. } | Shape::PixelSnapped { shapes, .. } => {
             for shape in shapes {
                 adjust_colors(shape, adjust_color);
             }
@@ -57,6 +57,15 @@ pub fn adjust_colors(
             radius: _,
             fill,
             stroke,
+            angle: _,
+        })
+        | Shape::Capsule(CapsuleShape {
+            start: _,
+            end: _,
+            radius: _,
+            fill,
+            stroke,
+            stroke_kind: _,
         })
         | Shape::Rect(RectShape {
             rect: _,
@@ -102,10 +111,17 @@ pub fn adjust_colors(
                 indices: _,
                 vertices,
                 texture_id: _,
+                hdr_colors,
+                shader: _,
+                pick_id: _,
             } = Arc::make_mut(mesh);
 
-            for v in vertices {
+            for (i, v) in vertices.iter_mut().enumerate() {
+                let before = v.color;
                 adjust_color(&mut v.color);
+                if let Some(hdr_color) = hdr_colors.get_mut(i) {
+                    *hdr_color = adjust_hdr_color(*hdr_color, before, v.color);
+                }
             }
         }
 
@@ -131,6 +147,24 @@ pub fn adjust_colors(
     }
 }
 
+/// Apply the same change to an extended range color as was done to its normal-range color.
+///
+/// This is exact for multiplicative changes, like fading out.
+fn adjust_hdr_color(hdr_color: RgbaF16, before: Color32, after: Color32) -> RgbaF16 {
+    if before == after {
+        return hdr_color;
+    }
+    let (hdr, before, after) = (Rgba::from(hdr_color), Rgba::from(before), Rgba::from(after));
+    let channel = |i: usize| {
+        if before[i] > 0.0 {
+            hdr[i] * after[i] / before[i]
+        } else {
+            after[i]
+        }
+    };
+    Rgba::from_rgba_premultiplied(channel(0), channel(1), channel(2), channel(3)).into()
+}
+
 fn adjust_color_mode(
     color_mode: &mut ColorMode,
     adjust_color: impl Fn(&mut Color32) + Send + Sync + Copy + 'static,
@@ -145,5 +179,11 @@ fn adjust_color_mode(
                 color
             })));
         }
+        color::ColorMode::Gradient(gradient) => {
+            Arc::make_mut(gradient).map_colors(|mut color| {
+                adjust_color(&mut color);
+                color
+            });
+        }
     }
 }
@@ -137,6 +137,23 @@ impl ArcShape {
         self.radii *= transform.scaling;
         self.stroke.width *= transform.scaling;
     }
+
+    /// Scale the arc by `scale` around `(0, 0)`, separately along x and y, and then move it by `translation`.
+    ///
+    /// The stroke width is scaled by the geometric mean of the scale factors.
+    pub fn transform_non_uniform(&mut self, translation: Vec2, scale: Vec2) {
+        let transform_pos = |p: Pos2| (scale * p.to_vec2() + translation).to_pos2();
+        self.center = transform_pos(self.center);
+        self.start = transform_pos(self.start);
+        self.end = transform_pos(self.end);
+        self.radii = self.radii * scale.abs();
+        self.stroke.width *= (scale.x * scale.y).abs().sqrt();
+        if scale.x * scale.y < 0.0 {
+            // Mirrored, so swap the ends to keep going around the same way:
+            std::mem::swap(&mut self.start, &mut self.end);
+        }
+        self.start_angle = (self.start.y - self.center.y).atan2(self.start.x - self.center.x);
+    }
 }
 
 impl Default for ArcShape {
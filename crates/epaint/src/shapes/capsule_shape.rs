@@ -0,0 +1,148 @@
+use std::f32::consts::PI;
+
+use crate::{Color32, Pos2, Rect, Shape, Stroke, StrokeKind, Vec2};
+
+/// How to paint a capsule (also known as a stadium or pill):
+/// a rectangle with a half circle at each end.
+///
+/// Unlike a [`crate::RectShape`] with maximal corner radius,
+/// the ends stay round whatever the [`StrokeKind`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CapsuleShape {
+    /// The center of one of the round ends.
+    pub start: Pos2,
+
+    /// The center of the other round end.
+    pub end: Pos2,
+
+    /// The radius of the round ends, i.e. half the thickness of the capsule.
+    pub radius: f32,
+
+    pub fill: Color32,
+    pub stroke: Stroke,
+
+    /// Is the stroke on the inside, outside, or centered on the outline of the capsule?
+    pub stroke_kind: StrokeKind,
+}
+
+impl CapsuleShape {
+    /// The capsule with the round ends at `start` and `end`.
+    #[inline]
+    pub fn new(
+        start: Pos2,
+        end: Pos2,
+        radius: f32,
+        fill: impl Into<Color32>,
+        stroke: impl Into<Stroke>,
+        stroke_kind: StrokeKind,
+    ) -> Self {
+        Self {
+            start,
+            end,
+            radius,
+            fill: fill.into(),
+            stroke: stroke.into(),
+            stroke_kind,
+        }
+    }
+
+    /// The largest capsule that fits in `rect`, with round ends at the short sides of it.
+    pub fn from_rect(
+        rect: Rect,
+        fill: impl Into<Color32>,
+        stroke: impl Into<Stroke>,
+        stroke_kind: StrokeKind,
+    ) -> Self {
+        let radius = 0.5 * rect.size().min_elem();
+        let (start, end) = if rect.height() <= rect.width() {
+            (
+                Pos2::new(rect.left() + radius, rect.center().y),
+                Pos2::new(rect.right() - radius, rect.center().y),
+            )
+        } else {
+            (
+                Pos2::new(rect.center().x, rect.top() + radius),
+                Pos2::new(rect.center().x, rect.bottom() - radius),
+            )
+        };
+        Self::new(start, end, radius, fill, stroke, stroke_kind)
+    }
+
+    /// How far the stroke reaches outside of the capsule.
+    fn stroke_extent(&self) -> f32 {
+        if self.stroke.is_empty() {
+            return 0.0;
+        }
+        match self.stroke_kind {
+            StrokeKind::Inside => 0.0,
+            StrokeKind::Middle => 0.5 * self.stroke.width,
+            StrokeKind::Outside => self.stroke.width,
+        }
+    }
+
+    /// The visual bounding rectangle (includes stroke width)
+    pub fn visual_bounding_rect(&self) -> Rect {
+        if self.fill == Color32::TRANSPARENT && self.stroke.is_empty() {
+            Rect::NOTHING
+        } else {
+            Rect::from_two_pos(self.start, self.end).expand(self.radius + self.stroke_extent())
+        }
+    }
+
+    /// Is the point inside the capsule (not counting the stroke)?
+    pub fn contains(&self, point: Pos2) -> bool {
+        let axis = self.end - self.start;
+        let t = if axis == Vec2::ZERO {
+            0.0
+        } else {
+            ((point - self.start).dot(axis) / axis.length_sq()).clamp(0.0, 1.0)
+        };
+        point.distance_sq(self.start + t * axis) <= self.radius * self.radius
+    }
+
+    /// The outline of the capsule, clockwise, with `points_per_end` points around each round end.
+    pub fn outline(&self, points_per_end: usize) -> Vec<Pos2> {
+        let points_per_end = points_per_end.max(2);
+        let axis = self.end - self.start;
+        let angle = if axis == Vec2::ZERO {
+            0.0
+        } else {
+            axis.angle()
+        };
+
+        let mut points = Vec::with_capacity(2 * points_per_end);
+        for (center, start_angle) in [(self.end, angle - 0.5 * PI), (self.start, angle + 0.5 * PI)]
+        {
+            for i in 0..points_per_end {
+                let t = i as f32 / (points_per_end - 1) as f32;
+                points.push(center + self.radius * Vec2::angled(start_angle + t * PI));
+            }
+        }
+        points
+    }
+}
+
+impl From<CapsuleShape> for Shape {
+    #[inline(always)]
+    fn from(shape: CapsuleShape) -> Self {
+        Self::Capsule(shape)
+    }
+}
+
+#[test]
+fn test_capsule_from_rect() {
+    let rect = Rect::from_min_max(Pos2::new(0.0, 0.0), Pos2::new(40.0, 10.0));
+    let capsule = CapsuleShape::from_rect(rect, Color32::RED, Stroke::NONE, StrokeKind::Inside);
+    assert_eq!(capsule.start, Pos2::new(5.0, 5.0));
+    assert_eq!(capsule.end, Pos2::new(35.0, 5.0));
+    assert_eq!(capsule.radius, 5.0);
+    assert_eq!(capsule.visual_bounding_rect(), rect);
+
+    assert!(capsule.contains(Pos2::new(1.0, 5.0)));
+    assert!(!capsule.contains(Pos2::new(0.5, 0.5)));
+
+    let outline = Rect::from_points(&capsule.outline(17));
+    assert!((outline.min - rect.min).length() < 1e-4);
+    assert!((outline.max - rect.max).length() < 1e-4);
+}
@@ -1,3 +1,5 @@
+use emath::Rot2;
+
 use crate::*;
 
 /// How to paint an ellipse.
@@ -10,6 +12,9 @@ pub struct EllipseShape {
     pub radius: Vec2,
     pub fill: Color32,
     pub stroke: Stroke,
+
+    /// Rotate the ellipse by this many radians clockwise, around its center.
+    pub angle: f32,
 }
 
 impl EllipseShape {
@@ -20,6 +25,7 @@ impl EllipseShape {
             radius,
             fill: fill_color.into(),
             stroke: Default::default(),
+            angle: 0.0,
         }
     }
 
@@ -30,7 +36,62 @@ impl EllipseShape {
             radius,
             fill: Default::default(),
             stroke: stroke.into(),
+            angle: 0.0,
+        }
+    }
+
+    /// Rotate the ellipse by this many radians clockwise, around its center.
+    #[inline]
+    pub fn with_angle(mut self, angle: f32) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    /// Half the width and height of the rotated ellipse, excluding the stroke.
+    pub fn half_extents(&self) -> Vec2 {
+        let (sin, cos) = self.angle.sin_cos();
+        let Vec2 { x: a, y: b } = self.radius;
+        Vec2::new(
+            (a * a * cos * cos + b * b * sin * sin).sqrt(),
+            (a * a * sin * sin + b * b * cos * cos).sqrt(),
+        )
+    }
+
+    /// Is the point inside the ellipse (not counting the stroke)?
+    pub fn contains(&self, point: Pos2) -> bool {
+        if self.radius.x <= 0.0 || self.radius.y <= 0.0 {
+            return false;
         }
+        let local = Rot2::from_angle(-self.angle) * (point - self.center) / self.radius;
+        local.length_sq() <= 1.0
+    }
+
+    /// Scale the ellipse by `scale` around `(0, 0)`, separately along x and y, and then move it by `translation`.
+    ///
+    /// A stretched rotated ellipse is still an ellipse, but with different radii and angle.
+    /// The stroke width is scaled by the geometric mean of the scale factors.
+    pub fn transform_non_uniform(&mut self, translation: Vec2, scale: Vec2) {
+        self.center = (scale * self.center.to_vec2() + translation).to_pos2();
+        self.stroke.width *= (scale.x * scale.y).abs().sqrt();
+
+        if self.angle == 0.0 {
+            self.radius = self.radius * scale.abs();
+            return;
+        }
+
+        // Decompose the linear part, `scale * rotation * radius`, into `rotation * radii`
+        // with the closed form singular value decomposition of a 2x2 matrix:
+        let rot = Rot2::from_angle(self.angle);
+        let x_axis = scale * (rot * Vec2::new(self.radius.x, 0.0));
+        let y_axis = scale * (rot * Vec2::new(0.0, self.radius.y));
+        let e = 0.5 * (x_axis.x + y_axis.y);
+        let f = 0.5 * (x_axis.x - y_axis.y);
+        let g = 0.5 * (x_axis.y + y_axis.x);
+        let h = 0.5 * (x_axis.y - y_axis.x);
+        let q = e.hypot(h);
+        let r = f.hypot(g);
+        self.radius = Vec2::new(q + r, (q - r).abs());
+        self.angle = 0.5 * (h.atan2(e) + g.atan2(f));
     }
 
     /// The visual bounding rectangle (includes stroke width)
@@ -38,10 +99,8 @@ impl EllipseShape {
         if self.fill == Color32::TRANSPARENT && self.stroke.is_empty() {
             Rect::NOTHING
         } else {
-            Rect::from_center_size(
-                self.center,
-                self.radius * 2.0 + Vec2::splat(self.stroke.width),
-            )
+            // The stroke is on the outside:
+            Rect::from_center_size(self.center, 2.0 * self.half_extents()).expand(self.stroke.width)
         }
     }
 }
@@ -52,3 +111,22 @@ impl From<EllipseShape> for Shape {
         Self::Ellipse(shape)
     }
 }
+
+#[test]
+fn test_rotated_ellipse() {
+    use std::f32::consts::FRAC_PI_2;
+
+    let ellipse =
+        EllipseShape::filled(Pos2::ZERO, Vec2::new(2.0, 1.0), Color32::RED).with_angle(FRAC_PI_2);
+    let rect = ellipse.visual_bounding_rect();
+    assert!((rect.width() - 2.0).abs() < 1e-5, "{rect:?}");
+    assert!((rect.height() - 4.0).abs() < 1e-5, "{rect:?}");
+    assert!(ellipse.contains(Pos2::new(0.0, 1.9)));
+    assert!(!ellipse.contains(Pos2::new(1.9, 0.0)));
+
+    // Stretching the rotated ellipse vertically makes it taller:
+    let mut stretched = ellipse;
+    stretched.transform_non_uniform(Vec2::ZERO, Vec2::new(1.0, 3.0));
+    assert!((stretched.radius - Vec2::new(6.0, 1.0)).length() < 1e-4);
+    assert!((stretched.half_extents() - Vec2::new(1.0, 6.0)).length() < 1e-4);
+}
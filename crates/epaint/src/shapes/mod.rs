@@ -1,5 +1,6 @@
 mod arc_shape;
 mod bezier_shape;
+mod capsule_shape;
 mod circle_shape;
 mod ellipse_shape;
 mod paint_callback;
@@ -11,9 +12,12 @@ mod text_shape;
 pub use self::{
     arc_shape::ArcShape,
     bezier_shape::{CubicBezierShape, QuadraticBezierShape},
+    capsule_shape::CapsuleShape,
     circle_shape::CircleShape,
     ellipse_shape::EllipseShape,
-    paint_callback::{PaintCallback, PaintCallbackInfo},
+    paint_callback::{
+        BackendCallback, BoxedBackendCallback, CallbackResources, PaintCallback, PaintCallbackInfo,
+    },
     path_shape::PathShape,
     rect_shape::RectShape,
     shape::Shape,
@@ -1,4 +1,7 @@
-use std::{any::Any, sync::Arc};
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
 
 use crate::*;
 
@@ -54,7 +57,8 @@ impl PaintCallbackInfo {
 
 /// If you want to paint some 3D shapes inside an egui region, you can use this.
 ///
-/// This is advanced usage, and is backend specific.
+/// This is advanced usage, and is backend specific,
+/// unless you implement [`BackendCallback`] and use [`Self::new`].
 #[derive(Clone)]
 pub struct PaintCallback {
     /// Where to paint.
@@ -68,6 +72,8 @@ pub struct PaintCallback {
     /// `glow` backend requires that callback be an `egui_glow::CallbackFn` while the `wgpu`
     /// backend requires a `egui_wgpu::Callback`.
     ///
+    /// Both backends also accept a [`BackendCallback`], see [`Self::new`].
+    ///
     /// If the type cannot be downcast to the type expected by the current backend the callback
     /// will not be drawn.
     ///
@@ -81,6 +87,17 @@ pub struct PaintCallback {
     pub callback: Arc<dyn Any + Send + Sync>,
 }
 
+impl PaintCallback {
+    /// A callback that works with all backends supporting [`BackendCallback`],
+    /// i.e. both `egui_glow` and `egui-wgpu`.
+    pub fn new(rect: Rect, callback: impl BackendCallback + 'static) -> Self {
+        Self {
+            rect,
+            callback: Arc::new(BoxedBackendCallback(Box::new(callback))),
+        }
+    }
+}
+
 impl std::fmt::Debug for PaintCallback {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("CustomShape")
@@ -101,3 +118,132 @@ impl From<PaintCallback> for Shape {
         Self::Callback(shape)
     }
 }
+
+// ----------------------------------------------------------------------------
+
+/// A backend-agnostic paint callback, for libraries that ship custom-rendered widgets
+/// (3D viewports, video, …) that should work with both `egui_glow` and `egui-wgpu`.
+///
+/// Put it in a [`PaintCallback`] with [`PaintCallback::new`].
+///
+/// Each backend passes its own state as `backend`, which you downcast:
+/// * `egui_glow`: an `egui_glow::Painter`, for both methods.
+/// * `egui-wgpu`: an `egui_wgpu::CallbackPrepare` in [`Self::prepare`],
+///   and the `wgpu::RenderPass<'static>` in [`Self::paint`].
+///
+/// ```
+/// # use epaint::{BackendCallback, CallbackResources, PaintCallbackInfo};
+/// struct Triangle;
+///
+/// struct TriangleResources {/* buffers, pipelines, … */}
+///
+/// impl BackendCallback for Triangle {
+///     fn prepare(&self, backend: &mut dyn std::any::Any, resources: &mut CallbackResources) {
+///         resources.get_or_insert_with(|| {
+///             // e.g. `backend.downcast_mut::<egui_glow::Painter>()` to create the buffers
+///             TriangleResources {}
+///         });
+///     }
+///
+///     fn paint(
+///         &self,
+///         info: &PaintCallbackInfo,
+///         backend: &mut dyn std::any::Any,
+///         resources: &CallbackResources,
+///     ) {
+///         let Some(triangle) = resources.get::<TriangleResources>() else {
+///             return;
+///         };
+///         // Downcast `backend` and draw `triangle`.
+///     }
+/// }
+/// ```
+pub trait BackendCallback: Send + Sync {
+    /// Called before painting starts, once each frame the callback is painted.
+    ///
+    /// Create and upload any resources you need here, and keep them in `resources`.
+    fn prepare(&self, backend: &mut dyn Any, resources: &mut CallbackResources) {
+        _ = (backend, resources);
+    }
+
+    /// Paint into [`PaintCallbackInfo::viewport`].
+    ///
+    /// The backend has already set the viewport, and restores its own state afterwards.
+    fn paint(&self, info: &PaintCallbackInfo, backend: &mut dyn Any, resources: &CallbackResources);
+}
+
+/// The value of [`PaintCallback::callback`] for a [`BackendCallback`].
+///
+/// Backends downcast to this, and then call the methods of [`Self::callback`].
+pub struct BoxedBackendCallback(Box<dyn BackendCallback>);
+
+impl BoxedBackendCallback {
+    #[inline]
+    pub fn callback(&self) -> &dyn BackendCallback {
+        self.0.as_ref()
+    }
+}
+
+/// Resources shared by all [`BackendCallback`]s, e.g. buffers and pipelines.
+///
+/// This holds at most one value of each type, so wrap your resources in a type of your own.
+/// Each backend keeps one of these around for as long as it lives.
+#[derive(Default)]
+pub struct CallbackResources(ahash::HashMap<TypeId, Box<dyn Any + Send + Sync>>);
+
+impl CallbackResources {
+    /// Insert a value, returning the previous value of the same type.
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) -> Option<T> {
+        self.0
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|previous| previous.downcast().ok())
+            .map(|previous| *previous)
+    }
+
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.0.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    pub fn get_mut<T: Any + Send + Sync>(&mut self) -> Option<&mut T> {
+        self.0.get_mut(&TypeId::of::<T>())?.downcast_mut()
+    }
+
+    /// Get the value of type `T`, inserting one first if there is none.
+    pub fn get_or_insert_with<T: Any + Send + Sync>(
+        &mut self,
+        create: impl FnOnce() -> T,
+    ) -> &mut T {
+        self.0
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(create()))
+            .downcast_mut()
+            .expect("The value of a type id is always of that type")
+    }
+
+    pub fn remove<T: Any + Send + Sync>(&mut self) -> Option<T> {
+        self.0
+            .remove(&TypeId::of::<T>())
+            .and_then(|value| value.downcast().ok())
+            .map(|value| *value)
+    }
+}
+
+#[test]
+fn test_callback_resources() {
+    struct Buffers(Vec<u32>);
+
+    let mut resources = CallbackResources::default();
+    assert!(resources.get::<Buffers>().is_none());
+
+    resources.get_or_insert_with(|| Buffers(vec![1])).0.push(2);
+    assert_eq!(resources.get::<Buffers>().unwrap().0, [1, 2]);
+
+    let previous = resources.insert(Buffers(vec![3])).unwrap();
+    assert_eq!(previous.0, [1, 2]);
+    assert_eq!(resources.get_or_insert_with(|| Buffers(vec![])).0, [3]);
+
+    resources.insert(42_u32);
+    assert_eq!(resources.remove::<u32>(), Some(42));
+    assert!(resources.get::<u32>().is_none());
+    assert!(resources.get_mut::<Buffers>().is_some());
+}
@@ -71,6 +71,25 @@ impl PathShape {
             Rect::from_points(&self.points).expand(self.stroke.width / 2.0)
         }
     }
+
+    /// Is the point inside the area enclosed by the path?
+    ///
+    /// The path is treated as closed, even if [`Self::closed`] is `false`.
+    /// Self-intersecting paths use the even-odd rule.
+    pub fn contains(&self, point: Pos2) -> bool {
+        let mut inside = false;
+        let n = self.points.len();
+        for i in 0..n {
+            let a = self.points[i];
+            let b = self.points[(i + n - 1) % n];
+            if (a.y > point.y) != (b.y > point.y)
+                && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+            {
+                inside = !inside;
+            }
+        }
+        inside
+    }
 }
 
 impl From<PathShape> for Shape {
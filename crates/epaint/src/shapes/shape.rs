@@ -2,16 +2,16 @@
 
 use std::sync::Arc;
 
-use emath::{pos2, Align2, Pos2, Rangef, Rect, TSTransform, Vec2};
+use emath::{pos2, vec2, Align2, Pos2, Rangef, Rect, Rot2, TSTransform, Vec2};
 
 use crate::{
     stroke::PathStroke,
     text::{FontId, Fonts, Galley},
-    Color32, CornerRadius, Mesh, Stroke, StrokeKind, TextureId,
+    Color32, CornerRadius, Mesh, PixelSnapping, Stroke, StrokeKind, TextureId,
 };
 
 use super::{
-    ArcShape, CircleShape, CubicBezierShape, EllipseShape, PaintCallback, PathShape,
+    ArcShape, CapsuleShape, CircleShape, CubicBezierShape, EllipseShape, PaintCallback, PathShape,
     QuadraticBezierShape, RectShape, TextShape,
 };
 
@@ -48,6 +48,9 @@ pub enum Shape {
     /// Rectangle with optional outline and fill.
     Rect(RectShape),
 
+    /// A rectangle with round ends, with optional outline and fill.
+    Capsule(CapsuleShape),
+
     /// Text.
     ///
     /// This needs to be recreated if `pixels_per_point` (dpi scale) changes.
@@ -71,6 +74,21 @@ pub enum Shape {
 
     /// Backend-specific painting.
     Callback(PaintCallback),
+
+    /// A group of shapes tagged with a pick id, see [`Mesh::pick_id`].
+    ///
+    /// Backends that support picking render the id into a separate target,
+    /// so you can find out exactly which group covers a pixel with `Context::read_pick`.
+    /// Otherwise this is painted just like [`Self::Vec`].
+    Pick { pick_id: u32, shapes: Vec<Self> },
+
+    /// A group of shapes that overrides [`crate::TessellationOptions::pixel_snapping`].
+    ///
+    /// Otherwise this is painted just like [`Self::Vec`].
+    PixelSnapped {
+        snapping: PixelSnapping,
+        shapes: Vec<Self>,
+    },
 }
 
 #[test]
@@ -142,6 +160,15 @@ impl Shape {
         }
     }
 
+    /// A thin line between two points that is always crisp, like a separator or a border.
+    ///
+    /// The line is snapped to the physical pixel grid with [`PixelSnapping::ALL`],
+    /// so the stroke covers a whole number of physical pixels (at least one) at any `pixels_per_point`.
+    /// For instance, a stroke of width `1.0` becomes exactly one physical pixel at `pixels_per_point = 1.25`.
+    pub fn hairline(points: [Pos2; 2], stroke: impl Into<Stroke>) -> Self {
+        Self::pixel_snapped(PixelSnapping::ALL, vec![Self::line_segment(points, stroke)])
+    }
+
     /// A line through many points.
     ///
     /// Use [`Self::line_segment`] instead if your line only connects two points.
@@ -299,6 +326,32 @@ impl Shape {
         Self::Rect(RectShape::stroke(rect, corner_radius, stroke, stroke_kind))
     }
 
+    /// The largest capsule (pill) that fits in `rect`.
+    ///
+    /// See also [`Self::capsule_stroke`].
+    #[inline]
+    pub fn capsule_filled(rect: Rect, fill_color: impl Into<Color32>) -> Self {
+        Self::Capsule(CapsuleShape::from_rect(
+            rect,
+            fill_color,
+            Stroke::NONE,
+            StrokeKind::Inside,
+        ))
+    }
+
+    /// The largest capsule (pill) that fits in `rect`.
+    ///
+    /// See also [`Self::capsule_filled`].
+    #[inline]
+    pub fn capsule_stroke(rect: Rect, stroke: impl Into<Stroke>, stroke_kind: StrokeKind) -> Self {
+        Self::Capsule(CapsuleShape::from_rect(
+            rect,
+            Color32::TRANSPARENT,
+            stroke,
+            stroke_kind,
+        ))
+    }
+
     #[allow(clippy::needless_pass_by_value)]
     pub fn text(
         fonts: &Fonts,
@@ -346,6 +399,30 @@ impl Shape {
         Self::Mesh(mesh)
     }
 
+    /// Tag these shapes with a pick id, see [`Self::Pick`].
+    ///
+    /// `pick_id` should not be zero, since that means "nothing".
+    #[inline]
+    pub fn pick(pick_id: u32, shapes: impl Into<Vec<Self>>) -> Self {
+        debug_assert!(pick_id != 0, "Pick id 0 is reserved for nothing");
+        Self::Pick {
+            pick_id,
+            shapes: shapes.into(),
+        }
+    }
+
+    /// Align these shapes to the physical pixel grid like this,
+    /// regardless of [`crate::TessellationOptions::pixel_snapping`].
+    ///
+    /// See [`Self::PixelSnapped`].
+    #[inline]
+    pub fn pixel_snapped(snapping: PixelSnapping, shapes: impl Into<Vec<Self>>) -> Self {
+        Self::PixelSnapped {
+            snapping,
+            shapes: shapes.into(),
+        }
+    }
+
     /// An image at the given position.
     ///
     /// `uv` should normally be `Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0))`
@@ -358,11 +435,54 @@ impl Shape {
         Self::mesh(mesh)
     }
 
+    /// An image rotated by `angle` radians (clockwise) around its center.
+    ///
+    /// `size` is the size of the image before rotation.
+    /// See [`Self::image`] for `uv` and `tint`.
+    pub fn image_rotated(
+        texture_id: TextureId,
+        center: Pos2,
+        size: Vec2,
+        angle: f32,
+        uv: Rect,
+        tint: Color32,
+    ) -> Self {
+        let rot = Rot2::from_angle(angle);
+        let half = size / 2.0;
+        let corners = [
+            vec2(-half.x, -half.y),
+            vec2(half.x, -half.y),
+            vec2(half.x, half.y),
+            vec2(-half.x, half.y),
+        ]
+        .map(|offset| center + rot * offset);
+        Self::image_quad(texture_id, corners, uv, tint)
+    }
+
+    /// An image stretched onto an arbitrary quadrilateral.
+    ///
+    /// The corners are given clockwise, starting with where the top-left of `uv` goes.
+    /// See [`Mesh::add_quad_with_uv`] for how quads that aren't parallelograms look.
+    pub fn image_quad(texture_id: TextureId, corners: [Pos2; 4], uv: Rect, tint: Color32) -> Self {
+        let mut mesh = Mesh::with_texture(texture_id);
+        mesh.add_quad_with_uv(
+            corners,
+            [
+                uv.left_top(),
+                uv.right_top(),
+                uv.right_bottom(),
+                uv.left_bottom(),
+            ],
+            tint,
+        );
+        Self::mesh(mesh)
+    }
+
     /// The visual bounding rectangle (includes stroke widths)
     pub fn visual_bounding_rect(&self) -> Rect {
         match self {
             Self::Noop => Rect::NOTHING,
-            Self::Vec(shapes) => {
+            Self::Vec(shapes) | Self::Pick { shapes, .. } | Self::PixelSnapped { shapes, .. } => {
                 let mut rect = Rect::NOTHING;
                 for shape in shapes {
                     rect = rect.union(shape.visual_bounding_rect());
@@ -380,6 +500,7 @@ impl Shape {
             }
             Self::Path(path_shape) => path_shape.visual_bounding_rect(),
             Self::Rect(rect_shape) => rect_shape.visual_bounding_rect(),
+            Self::Capsule(capsule_shape) => capsule_shape.visual_bounding_rect(),
             Self::Text(text_shape) => text_shape.visual_bounding_rect(),
             Self::Mesh(mesh) => mesh.calc_bounds(),
             Self::QuadraticBezier(bezier) => bezier.visual_bounding_rect(),
@@ -452,6 +573,16 @@ impl Shape {
         }
     }
 
+    /// The custom shader of a [`Self::Mesh`], if any.
+    #[inline]
+    pub fn mesh_shader(&self) -> Option<&crate::MeshShader> {
+        if let Self::Mesh(mesh) = self {
+            mesh.shader.as_ref()
+        } else {
+            None
+        }
+    }
+
     /// Scale the shape by `factor`, in-place.
     ///
     /// A wrapper around [`Self::transform`].
@@ -475,7 +606,7 @@ impl Shape {
     pub fn transform(&mut self, transform: TSTransform) {
         match self {
             Self::Noop => {}
-            Self::Vec(shapes) => {
+            Self::Vec(shapes) | Self::Pick { shapes, .. } | Self::PixelSnapped { shapes, .. } => {
                 for shape in shapes {
                     shape.transform(transform);
                 }
@@ -508,6 +639,12 @@ impl Shape {
                 rect_shape.stroke.width *= transform.scaling;
                 rect_shape.blur_width *= transform.scaling;
             }
+            Self::Capsule(capsule_shape) => {
+                capsule_shape.start = transform * capsule_shape.start;
+                capsule_shape.end = transform * capsule_shape.end;
+                capsule_shape.radius *= transform.scaling;
+                capsule_shape.stroke.width *= transform.scaling;
+            }
             Self::Text(text_shape) => {
                 text_shape.pos = transform * text_shape.pos;
 
@@ -546,6 +683,183 @@ impl Shape {
             }
         }
     }
+
+    /// Scale the shape by `scale` around `(0, 0)`, separately along x and y, and then move it by `translation`, in-place.
+    ///
+    /// This is like [`Self::transform`], but the shape can be stretched,
+    /// e.g. to map a plot's data space to the screen.
+    /// Circles become ellipses, and the stroke widths are scaled by the geometric mean of the scale factors.
+    /// Text is stretched too, but rotated text can't be sheared,
+    /// so it is only stretched along its own axes.
+    ///
+    /// A negative scale factor mirrors the shape.
+    pub fn transform_non_uniform(&mut self, translation: Vec2, scale: Vec2) {
+        let transform_pos = |p: Pos2| (scale * p.to_vec2() + translation).to_pos2();
+        let stroke_scale = (scale.x * scale.y).abs().sqrt();
+        let abs_scale = scale.abs();
+
+        match self {
+            Self::Noop => {}
+            Self::Vec(shapes) | Self::Pick { shapes, .. } | Self::PixelSnapped { shapes, .. } => {
+                for shape in shapes {
+                    shape.transform_non_uniform(translation, scale);
+                }
+            }
+            Self::Circle(circle_shape) => {
+                if abs_scale.x == abs_scale.y {
+                    circle_shape.center = transform_pos(circle_shape.center);
+                    circle_shape.radius *= abs_scale.x;
+                    circle_shape.stroke.width *= stroke_scale;
+                } else {
+                    let CircleShape {
+                        center,
+                        radius,
+                        fill,
+                        mut stroke,
+                    } = *circle_shape;
+                    stroke.width *= stroke_scale;
+                    *self = Self::Ellipse(EllipseShape {
+                        center: transform_pos(center),
+                        radius: radius * abs_scale,
+                        fill,
+                        stroke,
+                        angle: 0.0,
+                    });
+                }
+            }
+            Self::Ellipse(ellipse_shape) => {
+                ellipse_shape.transform_non_uniform(translation, scale);
+            }
+            Self::LineSegment { points, stroke } => {
+                for p in points {
+                    *p = transform_pos(*p);
+                }
+                stroke.width *= stroke_scale;
+            }
+            Self::Path(path_shape) => {
+                for p in &mut path_shape.points {
+                    *p = transform_pos(*p);
+                }
+                path_shape.stroke.width *= stroke_scale;
+            }
+            Self::Rect(rect_shape) => {
+                rect_shape.rect = Rect::from_two_pos(
+                    transform_pos(rect_shape.rect.min),
+                    transform_pos(rect_shape.rect.max),
+                );
+                let mut cr = rect_shape.corner_radius * abs_scale.x.min(abs_scale.y);
+                if scale.x < 0.0 {
+                    std::mem::swap(&mut cr.nw, &mut cr.ne);
+                    std::mem::swap(&mut cr.sw, &mut cr.se);
+                }
+                if scale.y < 0.0 {
+                    std::mem::swap(&mut cr.nw, &mut cr.sw);
+                    std::mem::swap(&mut cr.ne, &mut cr.se);
+                }
+                rect_shape.corner_radius = cr;
+                rect_shape.stroke.width *= stroke_scale;
+                rect_shape.blur_width *= stroke_scale;
+                if let Some(brush) = &mut rect_shape.brush {
+                    // Mirror the texture together with the rect:
+                    let uv = &mut Arc::make_mut(brush).uv;
+                    if scale.x < 0.0 {
+                        std::mem::swap(&mut uv.min.x, &mut uv.max.x);
+                    }
+                    if scale.y < 0.0 {
+                        std::mem::swap(&mut uv.min.y, &mut uv.max.y);
+                    }
+                }
+            }
+            Self::Capsule(capsule_shape) => {
+                if abs_scale.x == abs_scale.y {
+                    capsule_shape.start = transform_pos(capsule_shape.start);
+                    capsule_shape.end = transform_pos(capsule_shape.end);
+                    capsule_shape.radius *= abs_scale.x;
+                    capsule_shape.stroke.width *= stroke_scale;
+                } else {
+                    // A stretched capsule is no longer a capsule:
+                    let CapsuleShape {
+                        fill,
+                        stroke,
+                        stroke_kind,
+                        ..
+                    } = *capsule_shape;
+                    let mut points = capsule_shape.outline(33);
+                    if scale.x * scale.y < 0.0 {
+                        points.reverse(); // keep it clockwise
+                    }
+                    for p in &mut points {
+                        *p = transform_pos(*p);
+                    }
+                    let mut stroke = PathStroke::from(stroke);
+                    stroke.width *= stroke_scale;
+                    stroke.kind = stroke_kind;
+                    *self = Self::Path(PathShape::convex_polygon(points, fill, stroke));
+                }
+            }
+            Self::Text(text_shape) => {
+                text_shape.pos = transform_pos(text_shape.pos);
+                text_shape.underline.width *= stroke_scale;
+
+                // The galley is stretched along its own axes, so rotated text is
+                // stretched along the rotated axes and then turned to follow its baseline.
+                // Any shear that would come from stretching rotated text is lost.
+                let scale = if text_shape.angle == 0.0 {
+                    scale
+                } else {
+                    let rot = Rot2::from_angle(text_shape.angle);
+                    let x_axis = scale * (rot * Vec2::X);
+                    let y_axis = scale * (rot * Vec2::Y);
+                    text_shape.angle = x_axis.angle();
+                    let y_axis = Rot2::from_angle(text_shape.angle).inverse() * y_axis;
+                    vec2(x_axis.length(), y_axis.y)
+                };
+
+                // Stretch text:
+                let scale_rect = |rect: Rect| {
+                    Rect::from_two_pos(
+                        (scale * rect.min.to_vec2()).to_pos2(),
+                        (scale * rect.max.to_vec2()).to_pos2(),
+                    )
+                };
+                let galley = Arc::make_mut(&mut text_shape.galley);
+                for row in &mut galley.rows {
+                    row.rect = scale_rect(row.rect);
+                    row.visuals.mesh_bounds = scale_rect(row.visuals.mesh_bounds);
+                    for v in &mut row.visuals.mesh.vertices {
+                        v.pos = (scale * v.pos.to_vec2()).to_pos2();
+                    }
+                }
+
+                galley.mesh_bounds = scale_rect(galley.mesh_bounds);
+                galley.rect = scale_rect(galley.rect);
+            }
+            Self::Mesh(mesh) => {
+                Arc::make_mut(mesh).transform_non_uniform(translation, scale);
+            }
+            Self::QuadraticBezier(bezier) => {
+                for p in &mut bezier.points {
+                    *p = transform_pos(*p);
+                }
+                bezier.stroke.width *= stroke_scale;
+            }
+            Self::CubicBezier(bezier) => {
+                for p in &mut bezier.points {
+                    *p = transform_pos(*p);
+                }
+                bezier.stroke.width *= stroke_scale;
+            }
+            Self::Callback(shape) => {
+                shape.rect = Rect::from_two_pos(
+                    transform_pos(shape.rect.min),
+                    transform_pos(shape.rect.max),
+                );
+            }
+            Self::Arc(arc) => {
+                arc.transform_non_uniform(translation, scale);
+            }
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -625,3 +939,73 @@ fn dashes_from_line(
         position_on_segment -= segment_length;
     }
 }
+
+#[test]
+fn test_transform_non_uniform() {
+    let mut circle = Shape::circle_stroke(pos2(1.0, 1.0), 2.0, Stroke::new(1.0, Color32::RED));
+    circle.transform_non_uniform(vec2(10.0, 0.0), vec2(4.0, 1.0));
+    match circle {
+        Shape::Ellipse(ellipse) => {
+            assert_eq!(ellipse.center, pos2(14.0, 1.0));
+            assert_eq!(ellipse.radius, vec2(8.0, 2.0));
+            assert_eq!(ellipse.stroke.width, 2.0);
+        }
+        _ => panic!("A stretched circle should be an ellipse"),
+    }
+
+    let mut rect = Shape::rect_filled(
+        Rect::from_min_max(pos2(1.0, 1.0), pos2(2.0, 3.0)),
+        CornerRadius {
+            nw: 4,
+            ..Default::default()
+        },
+        Color32::RED,
+    );
+    rect.transform_non_uniform(Vec2::ZERO, vec2(-2.0, 1.0));
+    match rect {
+        Shape::Rect(rect) => {
+            assert_eq!(
+                rect.rect,
+                Rect::from_min_max(pos2(-4.0, 1.0), pos2(-2.0, 3.0))
+            );
+            assert_eq!(rect.corner_radius.ne, 4);
+        }
+        _ => panic!("Expected a rect"),
+    }
+
+    let uv = Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0));
+    let mut textured = Shape::Rect(
+        RectShape::filled(uv, 0.0, Color32::WHITE).with_texture(TextureId::default(), uv),
+    );
+    textured.transform_non_uniform(Vec2::ZERO, vec2(-1.0, 2.0));
+    match textured {
+        Shape::Rect(rect) => {
+            let brush = rect.brush.expect("Still textured");
+            assert_eq!(brush.uv.min, pos2(1.0, 0.0), "The texture is mirrored");
+            assert_eq!(brush.uv.max, pos2(0.0, 1.0));
+        }
+        _ => panic!("Expected a rect"),
+    }
+
+    let fonts = Fonts::new(1.0, 1024, Default::default());
+    let galley = fonts.layout_no_wrap("Text".to_owned(), FontId::default(), Color32::WHITE);
+    let size = galley.size();
+    let text = TextShape::new(Pos2::ZERO, galley, Color32::WHITE)
+        .with_underline(Stroke::new(1.0, Color32::WHITE))
+        .with_angle(std::f32::consts::FRAC_PI_2);
+    let row_height = text.galley.rows[0].rect.height();
+    let mut shape = Shape::Text(text);
+    shape.transform_non_uniform(Vec2::ZERO, vec2(2.0, 1.0));
+    match shape {
+        Shape::Text(text) => {
+            assert!(
+                (text.angle - std::f32::consts::FRAC_PI_2).abs() < 1e-6,
+                "Still rotated"
+            );
+            assert!((text.galley.size() - vec2(size.x, 2.0 * size.y)).length() < 1e-3);
+            assert!((text.galley.rows[0].rect.height() - 2.0 * row_height).abs() < 1e-3);
+            assert!((text.underline.width - std::f32::consts::SQRT_2).abs() < 1e-6);
+        }
+        _ => panic!("Expected text"),
+    }
+}
@@ -177,6 +177,9 @@ pub struct PaintStats {
     pub clipped_primitives: AllocInfo,
     pub vertices: AllocInfo,
     pub indices: AllocInfo,
+
+    /// Extra meshes because of too many vertices, see [`crate::Tessellator::num_mesh_splits`].
+    pub num_mesh_splits: usize,
 }
 
 impl PaintStats {
@@ -194,7 +197,9 @@ impl PaintStats {
 
     fn add(&mut self, shape: &Shape) {
         match shape {
-            Shape::Vec(shapes) => {
+            Shape::Vec(shapes)
+            | Shape::Pick { shapes, .. }
+            | Shape::PixelSnapped { shapes, .. } => {
                 // self += PaintStats::from_shapes(&shapes); // TODO(emilk)
                 self.shapes += AllocInfo::from_slice(shapes);
                 self.shape_vec += AllocInfo::from_slice(shapes);
@@ -207,6 +212,7 @@ impl PaintStats {
             | Shape::Ellipse { .. }
             | Shape::LineSegment { .. }
             | Shape::Rect { .. }
+            | Shape::Capsule(_)
             | Shape::CubicBezier(_)
             | Shape::QuadraticBezier(_)
             | Shape::Arc(_) => {}
@@ -210,6 +210,19 @@ impl PathStroke {
         }
     }
 
+    /// Create a new `PathStroke` colored by a [`Gradient`](crate::color::Gradient).
+    #[inline]
+    pub fn new_gradient(width: impl Into<f32>, gradient: crate::color::Gradient) -> Self {
+        Self {
+            width: width.into(),
+            color: ColorMode::Gradient(Arc::new(gradient)),
+            kind: StrokeKind::Middle,
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            miter_limit: 4.0,
+        }
+    }
+
     #[inline]
     pub fn with_kind(mut self, kind: StrokeKind) -> Self {
         self.kind = kind;
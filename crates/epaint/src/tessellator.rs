@@ -9,9 +9,10 @@ use emath::{pos2, remap, vec2, GuiRounding as _, NumExt, Pos2, Rect, Rot2, Vec2}
 
 use crate::{
     color::ColorMode, emath, stroke::PathStroke, texture_atlas::PreparedDisc, ArcShape,
-    CircleShape, ClippedPrimitive, ClippedShape, Color32, CornerRadiusF32, CubicBezierShape,
-    EllipseShape, LineCap, LineJoin, Mesh, PathShape, Primitive, QuadraticBezierShape, RectShape,
-    Shape, Stroke, StrokeKind, TextShape, TextureId, Vertex, WHITE_UV,
+    CapsuleShape, CircleShape, ClippedPrimitive, ClippedShape, Color32, CornerRadiusF32,
+    CubicBezierShape, EllipseShape, LineCap, LineJoin, Mesh, MeshIndexType, PathShape, Primitive,
+    QuadraticBezierShape, RectShape, Shape, Stroke, StrokeKind, TextShape, TextureId, Vertex,
+    WHITE_UV,
 };
 
 // ----------------------------------------------------------------------------
@@ -649,6 +650,45 @@ pub enum PathType {
     Closed,
 }
 
+/// How to align the geometry of shapes to the physical pixel grid.
+///
+/// This avoids blurry hairlines when `pixels_per_point` is fractional, e.g. `1.25`.
+///
+/// Set it for all shapes with [`TessellationOptions::pixel_snapping`],
+/// and override it for some shapes with [`crate::Shape::pixel_snapped`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PixelSnapping {
+    /// Round positions, so that strokes cover whole pixels.
+    ///
+    /// Points of strokes that are an odd number of pixels wide are rounded to the center of a pixel,
+    /// everything else to the boundary between pixels.
+    pub position: bool,
+
+    /// Round the width of strokes to a whole number of physical pixels (and at least one).
+    pub stroke: bool,
+}
+
+impl PixelSnapping {
+    /// Leave the geometry as it is.
+    pub const NONE: Self = Self {
+        position: false,
+        stroke: false,
+    };
+
+    /// Round positions, but not stroke widths.
+    pub const POSITION: Self = Self {
+        position: true,
+        stroke: false,
+    };
+
+    /// Round both positions and stroke widths, for perfectly crisp lines.
+    pub const ALL: Self = Self {
+        position: true,
+        stroke: true,
+    };
+}
+
 /// Tessellation quality options
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -697,6 +737,46 @@ pub struct TessellationOptions {
     /// You can override this with [`crate::RectShape::round_to_pixels`].
     pub round_rects_to_pixels: bool,
 
+    /// Align the lines, paths, circles and rectangles to the physical pixel grid.
+    ///
+    /// This is in addition to [`Self::round_line_segments_to_pixels`] and [`Self::round_rects_to_pixels`].
+    ///
+    /// You can override this for some shapes with [`crate::Shape::pixel_snapped`].
+    ///
+    /// Default: [`PixelSnapping::NONE`].
+    pub pixel_snapping: PixelSnapping,
+
+    /// If `true` (default), paths with many more points than the pixels they cover
+    /// are simplified before tessellation.
+    ///
+    /// Of each run of points within the same column of physical pixels,
+    /// only the first, last, top-most and bottom-most are kept.
+    ///
+    /// This keeps e.g. a million-point track fast to paint when zoomed out,
+    /// without any visible difference.
+    pub decimate_paths: bool,
+
+    /// The index type of the render backend.
+    ///
+    /// Meshes with more vertices than this index type can address are split into several,
+    /// so with [`MeshIndexType::U16`] every output mesh can be converted with [`Mesh::split_to_u16`]
+    /// without further splitting.
+    ///
+    /// Default: [`MeshIndexType::U32`], which keeps large scenes in a few big meshes.
+    pub mesh_index_type: MeshIndexType,
+
+    /// Split output meshes with more vertices than this, even if [`Self::mesh_index_type`] could fit them.
+    ///
+    /// This can be useful for backends with small vertex buffers.
+    /// The number of splits is reported by [`Tessellator::num_mesh_splits`].
+    ///
+    /// Values below 3 are treated as 3, the vertices of one triangle.
+    /// A mesh with a single triangle that spans more vertices than this
+    /// (e.g. a [`crate::Shape::Mesh`] whose triangle uses its first and last vertex) is left unsplit.
+    ///
+    /// Default: `usize::MAX`, i.e. only [`Self::mesh_index_type`] decides.
+    pub max_vertices_per_mesh: usize,
+
     /// Output the clip rectangles to be painted.
     pub debug_paint_clip_rects: bool,
 
@@ -732,6 +812,10 @@ impl Default for TessellationOptions {
             round_text_to_pixels: true,
             round_line_segments_to_pixels: true,
             round_rects_to_pixels: true,
+            pixel_snapping: PixelSnapping::NONE,
+            decimate_paths: true,
+            mesh_index_type: MeshIndexType::U32,
+            max_vertices_per_mesh: usize::MAX,
             debug_paint_text_rects: false,
             debug_paint_clip_rects: false,
             debug_ignore_clip_rects: false,
@@ -987,7 +1071,7 @@ fn stroke_and_fill_path(
     }
 
     // Expand the bounding box to include the thickness of the path
-    let uv_bbox = if matches!(stroke.color, ColorMode::UV(_)) {
+    let uv_bbox = if matches!(stroke.color, ColorMode::UV(_) | ColorMode::Gradient(_)) {
         Rect::from_points(&path.iter().map(|p| p.pos).collect::<Vec<Pos2>>())
             .expand((stroke.width / 2.0) + feathering)
     } else {
@@ -996,8 +1080,13 @@ fn stroke_and_fill_path(
     let get_color = |col: &ColorMode, pos: Pos2| match col {
         ColorMode::Solid(col) => *col,
         ColorMode::UV(fun) => fun(uv_bbox, pos),
+        ColorMode::Gradient(gradient) => gradient.color_at(uv_bbox, pos),
     };
 
+    // The caps and joins go after the body of the stroke, which indexes its vertices from `idx`.
+    let mut caps_and_joins = Mesh::with_texture(out.texture_id);
+    caps_and_joins.shader.clone_from(&out.shader);
+
     if 0.0 < feathering {
         let color_outer = Color32::TRANSPARENT;
         let color_middle = &stroke.color;
@@ -1040,7 +1129,7 @@ fn stroke_and_fill_path(
                         feathering,
                         color_middle,
                         color_outer,
-                        out,
+                        &mut caps_and_joins,
                         |cm, pos| mul_color(get_color(cm, pos), stroke.width / feathering),
                     );
                 }
@@ -1087,7 +1176,7 @@ fn stroke_and_fill_path(
                                 stroke.miter_limit,
                                 color_middle,
                                 color_outer,
-                                out,
+                                &mut caps_and_joins,
                                 |cm, pos| mul_color(get_color(cm, pos), opacity),
                             );
                         }
@@ -1117,7 +1206,7 @@ fn stroke_and_fill_path(
                     feathering,
                     color_middle,
                     color_outer,
-                    out,
+                    &mut caps_and_joins,
                     |cm, pos| mul_color(get_color(cm, pos), opacity),
                 );
             }
@@ -1175,7 +1264,7 @@ fn stroke_and_fill_path(
                                     stroke.miter_limit,
                                     color_middle,
                                     color_outer,
-                                    out,
+                                    &mut caps_and_joins,
                                     get_color,
                                 );
                             }
@@ -1243,14 +1332,14 @@ fn stroke_and_fill_path(
                             feathering,
                             color_middle,
                             color_outer,
-                            out,
+                            &mut caps_and_joins,
                             get_color,
                         );
                     }
 
                     // Process line segments and joins
                     let mut i0 = 0;
-                    for i1 in 1..n {
+                    for i1 in 0..n {
                         let p1 = path[i1 as usize];
                         let p = p1.pos;
                         let curr_normal = p1.normal;
@@ -1272,7 +1361,7 @@ fn stroke_and_fill_path(
                                     stroke.miter_limit,
                                     color_middle,
                                     color_outer,
-                                    out,
+                                    &mut caps_and_joins,
                                     get_color,
                                 );
                             }
@@ -1291,6 +1380,10 @@ fn stroke_and_fill_path(
                         );
                         out.colored_vertex(p - n * outer_rad, color_outer);
 
+                        if i1 == 0 {
+                            continue;
+                        }
+
                         out.add_triangle(idx + 4 * i0 + 0, idx + 4 * i0 + 1, idx + 4 * i1 + 0);
                         out.add_triangle(idx + 4 * i0 + 1, idx + 4 * i1 + 0, idx + 4 * i1 + 1);
 
@@ -1317,13 +1410,17 @@ fn stroke_and_fill_path(
                             feathering,
                             color_middle,
                             color_outer,
-                            out,
+                            &mut caps_and_joins,
                             get_color,
                         );
                     }
                 }
             }
         }
+
+        if !caps_and_joins.is_empty() {
+            out.append_ref(&caps_and_joins);
+        }
     } else {
         // not anti-aliased:
         out.reserve_triangles(2 * n as usize);
@@ -1414,6 +1511,15 @@ pub struct Tessellator {
 
     scratchpad_points: Vec<Pos2>,
     scratchpad_path: Path,
+
+    /// The [`Mesh::pick_id`] of the [`Shape::Pick`] group we are in, if any.
+    pick_id: u32,
+
+    /// The override of the [`Shape::PixelSnapped`] group we are in, if any.
+    pixel_snapping: Option<PixelSnapping>,
+
+    /// See [`Self::num_mesh_splits`].
+    num_mesh_splits: usize,
 }
 
 impl Tessellator {
@@ -1445,14 +1551,75 @@ impl Tessellator {
             clip_rect: Rect::EVERYTHING,
             scratchpad_points: Default::default(),
             scratchpad_path: Default::default(),
+            pick_id: 0,
+            pixel_snapping: None,
+            num_mesh_splits: 0,
         }
     }
 
+    /// How many extra meshes were output because a mesh had too many vertices,
+    /// since this [`Tessellator`] was created.
+    ///
+    /// See [`TessellationOptions::mesh_index_type`] and [`TessellationOptions::max_vertices_per_mesh`].
+    #[inline]
+    pub fn num_mesh_splits(&self) -> usize {
+        self.num_mesh_splits
+    }
+
+    /// The most vertices an output mesh may have.
+    fn max_vertices_per_mesh(&self) -> usize {
+        self.options
+            .max_vertices_per_mesh
+            .min(self.options.mesh_index_type.max_vertices())
+            .at_least(3)
+    }
+
     /// Set the [`Rect`] to use for culling.
     pub fn set_clip_rect(&mut self, clip_rect: Rect) {
         self.clip_rect = clip_rect;
     }
 
+    /// The pixel snapping of the shape being tessellated.
+    fn pixel_snapping(&self) -> PixelSnapping {
+        self.pixel_snapping.unwrap_or(self.options.pixel_snapping)
+    }
+
+    /// Should we round positions, given the legacy option for this kind of shape?
+    fn round_positions(&self, round_kind_to_pixels: bool) -> bool {
+        match self.pixel_snapping {
+            Some(snapping) => snapping.position,
+            None => round_kind_to_pixels || self.options.pixel_snapping.position,
+        }
+    }
+
+    /// Does the path have many more points than the pixels it covers,
+    /// e.g. a long GPS track or a dense plot that is zoomed out?
+    fn is_too_dense(&self, path_shape: &PathShape) -> bool {
+        const MIN_POINTS: usize = 256; // Not worth it for fewer points
+
+        if path_shape.points.len() < MIN_POINTS {
+            return false;
+        }
+        let size_in_pixels = path_shape.visual_bounding_rect().size() * self.pixels_per_point;
+        let covered_pixels = (size_in_pixels.x + size_in_pixels.y).at_least(1.0);
+        2.0 * covered_pixels < path_shape.points.len() as f32
+    }
+
+    fn snap_stroke_width(&self, width: f32) -> f32 {
+        if self.pixel_snapping().stroke && 0.0 < width {
+            (width * self.pixels_per_point).round().at_least(1.0) / self.pixels_per_point
+        } else {
+            width
+        }
+    }
+
+    fn snap_pos(&self, pos: Pos2, stroke_width: f32) -> Pos2 {
+        let mut pos = pos;
+        round_line_segment(&mut pos.x, stroke_width, self.pixels_per_point);
+        round_line_segment(&mut pos.y, stroke_width, self.pixels_per_point);
+        pos
+    }
+
     /// Tessellate a clipped shape into a list of primitives.
     pub fn tessellate_clipped_shape(
         &mut self,
@@ -1472,6 +1639,24 @@ impl Tessellator {
             return;
         }
 
+        if let Shape::Pick { pick_id, shapes } = shape {
+            let outer_pick_id = std::mem::replace(&mut self.pick_id, pick_id);
+            for shape in shapes {
+                self.tessellate_clipped_shape(ClippedShape { clip_rect, shape }, out_primitives);
+            }
+            self.pick_id = outer_pick_id;
+            return;
+        }
+
+        if let Shape::PixelSnapped { snapping, shapes } = shape {
+            let outer_snapping = self.pixel_snapping.replace(snapping);
+            for shape in shapes {
+                self.tessellate_clipped_shape(ClippedShape { clip_rect, shape }, out_primitives);
+            }
+            self.pixel_snapping = outer_snapping;
+            return;
+        }
+
         if let Shape::Callback(callback) = shape {
             out_primitives.push(ClippedPrimitive {
                 clip_rect,
@@ -1487,6 +1672,8 @@ impl Tessellator {
                     || match &output_clipped_primitive.primitive {
                         Primitive::Mesh(output_mesh) => {
                             output_mesh.texture_id != shape.texture_id()
+                                || output_mesh.shader.as_ref() != shape.mesh_shader()
+                                || output_mesh.pick_id != self.pick_id
                         }
                         Primitive::Callback(_) => true,
                     }
@@ -1496,7 +1683,10 @@ impl Tessellator {
         if start_new_mesh {
             out_primitives.push(ClippedPrimitive {
                 clip_rect,
-                primitive: Primitive::Mesh(Mesh::default()),
+                primitive: Primitive::Mesh(Mesh {
+                    pick_id: self.pick_id,
+                    ..Default::default()
+                }),
             });
         }
 
@@ -1505,6 +1695,19 @@ impl Tessellator {
         if let Primitive::Mesh(out_mesh) = &mut out.primitive {
             self.clip_rect = clip_rect;
             self.tessellate_shape(shape, out_mesh);
+
+            let max_vertices = self.max_vertices_per_mesh();
+            if max_vertices < out_mesh.vertices.len() {
+                // Only the last piece keeps growing, so each vertex is only split off once:
+                // If a triangle spans too many vertices, a mesh that is too big is better than none:
+                let pieces = std::mem::take(out_mesh).split_or_keep(max_vertices);
+                self.num_mesh_splits += pieces.len().saturating_sub(1);
+                out_primitives.pop();
+                out_primitives.extend(pieces.into_iter().map(|mesh| ClippedPrimitive {
+                    clip_rect,
+                    primitive: Primitive::Mesh(mesh),
+                }));
+            }
         } else {
             unreachable!();
         }
@@ -1524,6 +1727,21 @@ impl Tessellator {
                     self.tessellate_shape(shape, out);
                 }
             }
+            Shape::Pick { pick_id, shapes } => {
+                if out.is_empty() {
+                    out.pick_id = pick_id;
+                }
+                for shape in shapes {
+                    self.tessellate_shape(shape, out);
+                }
+            }
+            Shape::PixelSnapped { snapping, shapes } => {
+                let outer_snapping = self.pixel_snapping.replace(snapping);
+                for shape in shapes {
+                    self.tessellate_shape(shape, out);
+                }
+                self.pixel_snapping = outer_snapping;
+            }
             Shape::Circle(circle) => {
                 self.tessellate_circle(circle, out);
             }
@@ -1556,6 +1774,9 @@ impl Tessellator {
             Shape::Rect(rect_shape) => {
                 self.tessellate_rect(&rect_shape, out);
             }
+            Shape::Capsule(capsule_shape) => {
+                self.tessellate_capsule(capsule_shape, out);
+            }
             Shape::Text(text_shape) => {
                 if self.options.debug_paint_text_rects {
                     let rect = text_shape.galley.rect.translate(text_shape.pos.to_vec2());
@@ -1585,16 +1806,22 @@ impl Tessellator {
     /// * `out`: triangles are appended to this.
     pub fn tessellate_circle(&mut self, shape: CircleShape, out: &mut Mesh) {
         let CircleShape {
-            center,
+            mut center,
             radius,
             mut fill,
-            stroke,
+            mut stroke,
         } = shape;
 
         if radius <= 0.0 {
             return;
         }
 
+        let snapping = self.pixel_snapping();
+        stroke.width = self.snap_stroke_width(stroke.width);
+        if snapping.position {
+            center = self.snap_pos(center, stroke.width);
+        }
+
         if self.options.coarse_tessellation_culling
             && !self
                 .clip_rect
@@ -1645,6 +1872,7 @@ impl Tessellator {
             radius,
             fill,
             stroke,
+            angle,
         } = shape;
 
         if radius.x <= 0.0 || radius.y <= 0.0 {
@@ -1654,7 +1882,7 @@ impl Tessellator {
         if self.options.coarse_tessellation_culling
             && !self
                 .clip_rect
-                .expand2(radius + Vec2::splat(stroke.width))
+                .expand2(shape.half_extents() + Vec2::splat(stroke.width))
                 .contains(center)
         {
             return;
@@ -1695,6 +1923,13 @@ impl Tessellator {
         points.push(center + Vec2::new(0.0, -radius.y));
         points.extend(quarter.iter().rev().map(|p| center + Vec2::new(p.x, -p.y)));
 
+        if angle != 0.0 {
+            let rot = Rot2::from_angle(angle);
+            for p in &mut points {
+                *p = center + rot * (*p - center);
+            }
+        }
+
         let path_stroke = PathStroke::from(stroke).outside();
         self.scratchpad_path.clear();
         self.scratchpad_path.add_line_loop(&points);
@@ -1702,6 +1937,39 @@ impl Tessellator {
             .fill_and_stroke(self.feathering, fill, &path_stroke, out);
     }
 
+    /// Tessellate a single [`CapsuleShape`] into a [`Mesh`].
+    ///
+    /// * `shape`: the capsule to tessellate.
+    /// * `out`: triangles are appended to this.
+    pub fn tessellate_capsule(&mut self, shape: CapsuleShape, out: &mut Mesh) {
+        if shape.radius <= 0.0 {
+            return;
+        }
+
+        if self.options.coarse_tessellation_culling
+            && !shape.visual_bounding_rect().intersects(self.clip_rect)
+        {
+            return;
+        }
+
+        // Same number of points per half circle as `PathPoints::add_circle` uses:
+        let points_per_end = match shape.radius {
+            r if r <= 2.0 => 5,
+            r if r <= 5.0 => 9,
+            r if r < 18.0 => 17,
+            r if r < 50.0 => 33,
+            _ => 65,
+        };
+
+        let mut path_stroke = PathStroke::from(shape.stroke);
+        path_stroke.kind = shape.stroke_kind;
+        self.scratchpad_path.clear();
+        self.scratchpad_path
+            .add_line_loop(&shape.outline(points_per_end));
+        self.scratchpad_path
+            .fill_and_stroke(self.feathering, shape.fill, &path_stroke, out);
+    }
+
     /// Tessellate a single [`Mesh`] into a [`Mesh`].
     ///
     /// * `mesh`: the mesh to tessellate.
@@ -1731,7 +1999,7 @@ impl Tessellator {
         stroke: impl Into<Stroke>,
         out: &mut Mesh,
     ) {
-        let stroke = stroke.into();
+        let mut stroke = stroke.into();
         if stroke.is_empty() {
             return;
         }
@@ -1744,7 +2012,9 @@ impl Tessellator {
             return;
         }
 
-        if self.options.round_line_segments_to_pixels {
+        stroke.width = self.snap_stroke_width(stroke.width);
+
+        if self.round_positions(self.options.round_line_segments_to_pixels) {
             let feathering = self.feathering;
             let pixels_per_point = self.pixels_per_point;
 
@@ -1754,7 +2024,7 @@ impl Tessellator {
             if a.x == b.x {
                 // Vertical line
                 let mut x = a.x;
-                round_line_segment(&mut x, &stroke, self.pixels_per_point);
+                round_line_segment(&mut x, stroke.width, self.pixels_per_point);
                 a.x = x;
                 b.x = x;
 
@@ -1775,7 +2045,7 @@ impl Tessellator {
             if a.y == b.y {
                 // Horizontal line
                 let mut y = a.y;
-                round_line_segment(&mut y, &stroke, self.pixels_per_point);
+                round_line_segment(&mut y, stroke.width, self.pixels_per_point);
                 a.y = y;
                 b.y = y;
 
@@ -1830,6 +2100,31 @@ impl Tessellator {
             stroke,
         } = path_shape;
 
+        let snapping = self.pixel_snapping();
+        let mut stroke = stroke.clone();
+        stroke.width = self.snap_stroke_width(stroke.width);
+        let stroke = &stroke;
+        let decimate = self.options.decimate_paths && self.is_too_dense(path_shape);
+        let points = if decimate || snapping.position {
+            let mut new_points = std::mem::take(&mut self.scratchpad_points);
+            new_points.clear();
+            if decimate {
+                decimate_polyline(points, 1.0 / self.pixels_per_point, &mut new_points);
+            } else {
+                new_points.extend_from_slice(points);
+            }
+            if snapping.position {
+                let stroke_width = if stroke.is_empty() { 0.0 } else { stroke.width };
+                for point in &mut new_points {
+                    *point = self.snap_pos(*point, stroke_width);
+                }
+            }
+            self.scratchpad_points = new_points;
+            &self.scratchpad_points
+        } else {
+            points
+        };
+
         self.scratchpad_path.clear();
 
         if *closed {
@@ -1875,8 +2170,10 @@ impl Tessellator {
         } = *rect_shape;
 
         let mut corner_radius = CornerRadiusF32::from(corner_radius);
-        let round_to_pixels = round_to_pixels.unwrap_or(self.options.round_rects_to_pixels);
+        let round_to_pixels = round_to_pixels
+            .unwrap_or_else(|| self.round_positions(self.options.round_rects_to_pixels));
         let pixel_size = 1.0 / self.pixels_per_point;
+        stroke.width = self.snap_stroke_width(stroke.width);
 
         if stroke.width == 0.0 {
             stroke.color = Color32::TRANSPARENT;
@@ -2320,7 +2617,44 @@ impl Tessellator {
     }
 }
 
-fn round_line_segment(coord: &mut f32, stroke: &Stroke, pixels_per_point: f32) {
+/// Of each run of consecutive points within the same column of pixels,
+/// keep only the first, last, top-most and bottom-most points (like the M4 algorithm).
+///
+/// The lines between those cover the same pixels as all the points did,
+/// so this is hard to tell apart from the original.
+fn decimate_polyline(points: &[Pos2], pixel_size: f32, out: &mut Vec<Pos2>) {
+    let column = |point: Pos2| (point.x / pixel_size).floor();
+
+    let mut start = 0;
+    while start < points.len() {
+        let start_column = column(points[start]);
+        let (mut top, mut bottom) = (start, start);
+        let mut end = start + 1;
+        while end < points.len() && column(points[end]) == start_column {
+            if points[end].y < points[top].y {
+                top = end;
+            }
+            if points[bottom].y < points[end].y {
+                bottom = end;
+            }
+            end += 1;
+        }
+
+        let mut keep = [start, top, bottom, end - 1];
+        keep.sort_unstable();
+        let mut previous = None;
+        for index in keep {
+            if previous != Some(index) {
+                out.push(points[index]);
+                previous = Some(index);
+            }
+        }
+
+        start = end;
+    }
+}
+
+fn round_line_segment(coord: &mut f32, stroke_width: f32, pixels_per_point: f32) {
     // If the stroke is an odd number of pixels wide,
     // we want to round the center of it to the center of a pixel.
     //
@@ -2335,7 +2669,7 @@ fn round_line_segment(coord: &mut f32, stroke: &Stroke, pixels_per_point: f32) {
 
     let pixel_size = 1.0 / pixels_per_point;
 
-    if stroke.width <= pixel_size || is_nearest_integer_odd(pixels_per_point * stroke.width) {
+    if stroke_width <= pixel_size || is_nearest_integer_odd(pixels_per_point * stroke_width) {
         *coord = coord.round_to_pixel_center(pixels_per_point);
     } else {
         *coord = coord.round_to_pixels(pixels_per_point);
@@ -2460,7 +2794,11 @@ impl Tessellator {
                 | Shape::Mesh(_)
                 | Shape::LineSegment { .. }
                 | Shape::Rect(_)
-                | Shape::Callback(_) => false,
+                | Shape::Capsule(_)
+                | Shape::Callback(_)
+                | Shape::Pick { .. } => false, // keep the pick group together
+
+                Shape::PixelSnapped { shapes, .. } => shapes.iter().any(should_parallelize),
             }
         }
 
@@ -2549,6 +2887,263 @@ fn test_tessellator() {
     assert_eq!(primitives.len(), 2);
 }
 
+#[test]
+fn test_tessellate_mesh_shaders() {
+    use crate::*;
+
+    let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0));
+    let shader = MeshShader {
+        id: MeshShaderId(7),
+        uniforms: vec![1, 2, 3, 4],
+    };
+
+    let mut plain = Mesh::default();
+    plain.add_colored_rect(rect, Color32::WHITE);
+    let shaded = plain.clone().with_shader(shader.clone());
+
+    let clipped_shapes = [plain.clone(), shaded.clone(), shaded, plain]
+        .into_iter()
+        .map(|mesh| ClippedShape {
+            clip_rect: rect,
+            shape: Shape::mesh(mesh),
+        })
+        .collect();
+
+    let primitives = Tessellator::new(1.0, Default::default(), [1024, 1024], vec![])
+        .tessellate_shapes(clipped_shapes);
+
+    let shaders: Vec<_> = primitives
+        .iter()
+        .map(|primitive| match &primitive.primitive {
+            Primitive::Mesh(mesh) => mesh.shader.clone(),
+            Primitive::Callback(_) => panic!("Unexpected callback"),
+        })
+        .collect();
+    assert_eq!(shaders, vec![None, Some(shader), None]);
+}
+
+#[test]
+fn test_tessellate_pick_groups() {
+    use crate::*;
+
+    let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 10.0));
+    let square = || Shape::rect_filled(rect, 0.0, Color32::WHITE);
+
+    let clipped_shapes = [
+        square(),
+        Shape::pick(1, vec![square(), square()]),
+        Shape::pick(2, vec![square(), Shape::pick(3, vec![square()]), square()]),
+        square(),
+    ]
+    .into_iter()
+    .map(|shape| ClippedShape {
+        clip_rect: Rect::EVERYTHING,
+        shape,
+    })
+    .collect();
+
+    let primitives = Tessellator::new(1.0, Default::default(), [1024, 1024], vec![])
+        .tessellate_shapes(clipped_shapes);
+
+    let pick_ids: Vec<_> = primitives
+        .iter()
+        .map(|primitive| match &primitive.primitive {
+            Primitive::Mesh(mesh) => mesh.pick_id,
+            Primitive::Callback(_) => panic!("Unexpected callback"),
+        })
+        .collect();
+    assert_eq!(pick_ids, vec![0, 1, 2, 3, 2, 0]);
+}
+
+#[test]
+fn test_pixel_snapping() {
+    use crate::*;
+
+    let options = TessellationOptions {
+        feathering: false,
+        ..Default::default()
+    };
+    let line = PathShape::line(
+        vec![pos2(0.3, 0.3), pos2(10.3, 0.3)],
+        Stroke::new(0.8, Color32::WHITE),
+    );
+    let assert_y_range = |shape: Shape, expected: (f32, f32)| {
+        let mut mesh = Mesh::default();
+        Tessellator::new(1.0, options, [1024, 1024], vec![]).tessellate_shape(shape, &mut mesh);
+        let bounds = mesh.calc_bounds();
+        assert!(
+            (bounds.min.y - expected.0).abs() < 1e-5 && (bounds.max.y - expected.1).abs() < 1e-5,
+            "Expected y range {expected:?}, got {:?}",
+            bounds.y_range()
+        );
+    };
+
+    // Not snapped by default:
+    assert_y_range(line.clone().into(), (-0.1, 0.7));
+
+    // Centered on a pixel, and one pixel wide:
+    let snapped = Shape::pixel_snapped(PixelSnapping::ALL, vec![line.clone().into()]);
+    assert_y_range(snapped, (0.0, 1.0));
+
+    // Only the position:
+    let snapped = Shape::pixel_snapped(PixelSnapping::POSITION, vec![line.into()]);
+    assert_y_range(snapped, (0.1, 0.9));
+}
+
+#[test]
+fn test_hairline() {
+    use crate::*;
+
+    let options = TessellationOptions {
+        feathering: false,
+        ..Default::default()
+    };
+    for pixels_per_point in [1.0, 1.25, 1.5, 2.0] {
+        let hairline = Shape::hairline([pos2(0.0, 3.3), pos2(10.0, 3.3)], (1.0, Color32::WHITE));
+        let mut mesh = Mesh::default();
+        Tessellator::new(pixels_per_point, options, [1024, 1024], vec![])
+            .tessellate_shape(hairline, &mut mesh);
+        let bounds = mesh.calc_bounds();
+        let (min, max) = (
+            bounds.min.y * pixels_per_point,
+            bounds.max.y * pixels_per_point,
+        );
+
+        // Covers whole physical pixels:
+        assert!(
+            (min - min.round()).abs() < 1e-4 && (max - max.round()).abs() < 1e-4,
+            "Blurry hairline at pixels_per_point {pixels_per_point}: {min}..{max}"
+        );
+        assert!(1.0 <= (max - min).round(), "{min}..{max}");
+    }
+}
+
+#[test]
+fn test_decimate_paths() {
+    use crate::*;
+
+    // A zig-zag with a million points that covers just 10x10 points:
+    let points: Vec<Pos2> = (0..1_000_000)
+        .map(|i| pos2(i as f32 * 1e-5, (i % 2) as f32 * 10.0))
+        .collect();
+    let path = PathShape::line(points, Stroke::new(1.0, Color32::WHITE));
+
+    let num_vertices = |decimate_paths| {
+        let options = TessellationOptions {
+            decimate_paths,
+            ..Default::default()
+        };
+        let mut mesh = Mesh::default();
+        Tessellator::new(1.0, options, [1024, 1024], vec![]).tessellate_path(&path, &mut mesh);
+        mesh.vertices.len()
+    };
+
+    let decimated = num_vertices(true);
+    assert!(10 * decimated < num_vertices(false), "{decimated}");
+
+    let mut out = vec![];
+    decimate_polyline(
+        &[
+            pos2(0.0, 0.0),
+            pos2(0.2, 5.0),
+            pos2(0.4, -5.0),
+            pos2(0.6, 1.0),
+            pos2(0.8, 2.0),
+            pos2(1.5, 0.0),
+        ],
+        1.0,
+        &mut out,
+    );
+    assert_eq!(
+        out,
+        [
+            pos2(0.0, 0.0),
+            pos2(0.2, 5.0),
+            pos2(0.4, -5.0),
+            pos2(0.8, 2.0),
+            pos2(1.5, 0.0),
+        ]
+    );
+}
+
+#[test]
+fn test_mesh_splits() {
+    use crate::*;
+
+    // 100 filled rectangles of 4 vertices each:
+    let shapes: Vec<ClippedShape> = (0..100)
+        .map(|i| ClippedShape {
+            clip_rect: Rect::EVERYTHING,
+            shape: Shape::rect_filled(
+                Rect::from_min_size(pos2(i as f32, 0.0), vec2(1.0, 1.0)),
+                0.0,
+                Color32::WHITE,
+            ),
+        })
+        .collect();
+
+    let options = TessellationOptions {
+        feathering: false,
+        ..Default::default()
+    };
+    let mut tessellator = Tessellator::new(1.0, options, [1024, 1024], vec![]);
+    assert_eq!(tessellator.tessellate_shapes(shapes.clone()).len(), 1);
+    assert_eq!(tessellator.num_mesh_splits(), 0);
+
+    let options = TessellationOptions {
+        max_vertices_per_mesh: 100,
+        ..options
+    };
+    let mut tessellator = Tessellator::new(1.0, options, [1024, 1024], vec![]);
+    let primitives = tessellator.tessellate_shapes(shapes);
+    assert_eq!(primitives.len(), 4);
+    assert_eq!(tessellator.num_mesh_splits(), 3);
+    for primitive in &primitives {
+        let Primitive::Mesh(mesh) = &primitive.primitive else {
+            panic!("Expected a mesh");
+        };
+        assert!(mesh.vertices.len() <= 100);
+    }
+
+    // Too small limits are raised to one triangle:
+    let mut triangles = Mesh::default();
+    for i in 0..10 {
+        let x = i as f32;
+        for pos in [pos2(x, 0.0), pos2(x + 1.0, 0.0), pos2(x, 1.0)] {
+            triangles.colored_vertex(pos, Color32::WHITE);
+        }
+        triangles.add_triangle(3 * i, 3 * i + 1, 3 * i + 2);
+    }
+    let options = TessellationOptions {
+        max_vertices_per_mesh: 0,
+        ..options
+    };
+    let mut tessellator = Tessellator::new(1.0, options, [1024, 1024], vec![]);
+    let primitives = tessellator.tessellate_shapes(vec![ClippedShape {
+        clip_rect: Rect::EVERYTHING,
+        shape: Shape::mesh(triangles),
+    }]);
+    assert_eq!(primitives.len(), 10);
+
+    // A triangle that can't be split off is left in one mesh:
+    let mut mesh = Mesh::default();
+    for i in 0..10 {
+        mesh.colored_vertex(pos2(i as f32, (i % 2) as f32), Color32::WHITE);
+    }
+    mesh.add_triangle(0, 1, 9);
+    let options = TessellationOptions {
+        max_vertices_per_mesh: 5,
+        ..options
+    };
+    let mut tessellator = Tessellator::new(1.0, options, [1024, 1024], vec![]);
+    let primitives = tessellator.tessellate_shapes(vec![ClippedShape {
+        clip_rect: Rect::EVERYTHING,
+        shape: Shape::mesh(mesh),
+    }]);
+    assert_eq!(primitives.len(), 1);
+    assert_eq!(tessellator.num_mesh_splits(), 0);
+}
+
 #[test]
 fn path_bounding_box() {
     use crate::*;
@@ -2594,6 +3189,30 @@ fn path_bounding_box() {
     }
 }
 
+#[test]
+fn stroke_caps_and_joins_come_after_the_body() {
+    use crate::*;
+
+    for cap in [LineCap::Butt, LineCap::Round, LineCap::Square] {
+        for (width, feathering) in [(4.0, 1.0), (0.5, 1.0)] {
+            let mut path = Path::default();
+            path.add_open_points(&[pos2(0.0, 0.0), pos2(10.0, 0.0), pos2(10.0, 10.0)]);
+            let stroke = Stroke::new(width, Color32::WHITE)
+                .with_line_cap(cap)
+                .with_line_join(LineJoin::Round);
+            let mut mesh = Mesh::default();
+            path.stroke(feathering, PathType::Open, &stroke.into(), &mut mesh);
+
+            assert!(mesh.is_valid());
+            // The body of the stroke indexes its vertices from the start, one edge per point:
+            let edges = if width < feathering { 3 } else { 4 };
+            for vertex in &mesh.vertices[..edges] {
+                assert_eq!(vertex.pos.x, 0.0, "{cap:?}, width {width}");
+            }
+        }
+    }
+}
+
 /// Helper function to generate vertices and indices for line caps
 fn generate_line_cap(
     cap_type: LineCap,
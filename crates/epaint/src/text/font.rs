@@ -1,12 +1,12 @@
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use emath::{vec2, GuiRounding, Vec2};
+use emath::{pos2, vec2, GuiRounding, Pos2, Vec2};
 
 use crate::{
     mutex::{Mutex, RwLock},
-    text::FontTweak,
-    TextureAtlas,
+    text::{DecorationMetrics, FontTweak},
+    Color32, CubicBezierShape, QuadraticBezierShape, Stroke, TextureAtlas,
 };
 
 // ----------------------------------------------------------------------------
@@ -77,6 +77,11 @@ pub struct FontImpl {
     y_offset_in_points: f32,
 
     ascent: f32,
+
+    /// From the font tables, if it has them.
+    underline: Option<DecorationMetrics>,
+    strikethrough: Option<DecorationMetrics>,
+
     pixels_per_point: f32,
     glyph_info_cache: RwLock<ahash::HashMap<char, GlyphInfo>>, // TODO(emilk): standard Mutex
     atlas: Arc<Mutex<TextureAtlas>>,
@@ -88,6 +93,7 @@ impl FontImpl {
         pixels_per_point: f32,
         name: String,
         ab_glyph_font: ab_glyph::FontArc,
+        face_index: u32,
         scale_in_pixels: f32,
         tweak: FontTweak,
     ) -> Self {
@@ -126,6 +132,19 @@ impl FontImpl {
         // Round to closest pixel:
         let y_offset_in_points = (y_offset_points * pixels_per_point).round() / pixels_per_point;
 
+        // Font units are y-up, and measure the top of the line:
+        let points_per_unit = scaled.v_scale_factor() * tweak.scale / pixels_per_point;
+        let [underline, strikethrough] =
+            decorations_from_font_tables(ab_glyph_font.font_data(), face_index).map(|metrics| {
+                metrics.map(|(position, thickness)| {
+                    let thickness = points_per_unit * thickness;
+                    DecorationMetrics {
+                        offset: 0.5 * thickness - points_per_unit * position,
+                        thickness,
+                    }
+                })
+            });
+
         Self {
             name,
             ab_glyph_font,
@@ -133,12 +152,20 @@ impl FontImpl {
             height_in_points: ascent - descent + line_gap,
             y_offset_in_points,
             ascent: ascent + baseline_offset,
+            underline,
+            strikethrough,
             pixels_per_point,
             glyph_info_cache: Default::default(),
             atlas,
         }
     }
 
+    /// The name of the font in [`crate::text::FontDefinitions::font_data`].
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Code points that will always be replaced by the replacement character.
     ///
     /// See also [`invisible_char`].
@@ -269,6 +296,89 @@ impl FontImpl {
         self.ascent
     }
 
+    /// Where the font suggests drawing underlines, if it says.
+    #[inline]
+    pub fn underline_metrics(&self) -> Option<DecorationMetrics> {
+        self.underline
+    }
+
+    /// Where the font suggests drawing strikethroughs, if it says.
+    #[inline]
+    pub fn strikethrough_metrics(&self) -> Option<DecorationMetrics> {
+        self.strikethrough
+    }
+
+    /// The outline of a glyph as closed contours, in points relative to where its baseline starts.
+    ///
+    /// Curves are flattened to within `tolerance` points,
+    /// by default a tenth of a physical pixel.
+    fn glyph_contours(
+        &self,
+        glyph_id: ab_glyph::GlyphId,
+        tolerance: Option<f32>,
+    ) -> Vec<Vec<Pos2>> {
+        use ab_glyph::{Font as _, OutlineCurve, ScaleFont as _};
+
+        let Some(outline) = self.ab_glyph_font.outline(glyph_id) else {
+            return vec![];
+        };
+
+        let tolerance = tolerance.unwrap_or(0.1 / self.pixels_per_point);
+        let scaled = self.ab_glyph_font.as_scaled(self.scale_in_pixels as f32);
+        let scale = vec2(scaled.h_scale_factor(), -scaled.v_scale_factor()) / self.pixels_per_point;
+        let to_pos = |point: ab_glyph::Point| {
+            pos2(
+                scale.x * point.x,
+                scale.y * point.y + self.y_offset_in_points,
+            )
+        };
+
+        let mut contours: Vec<Vec<Pos2>> = vec![];
+        let mut last_point = None;
+        for curve in &outline.curves {
+            let (start, end) = match *curve {
+                OutlineCurve::Line(start, end)
+                | OutlineCurve::Quad(start, _, end)
+                | OutlineCurve::Cubic(start, _, _, end) => (start, end),
+            };
+            let points = match *curve {
+                OutlineCurve::Line(p0, p1) => vec![to_pos(p0), to_pos(p1)],
+                OutlineCurve::Quad(p0, p1, p2) => QuadraticBezierShape::from_points_stroke(
+                    [to_pos(p0), to_pos(p1), to_pos(p2)],
+                    false,
+                    Color32::TRANSPARENT,
+                    Stroke::NONE,
+                )
+                .flatten(Some(tolerance)),
+                OutlineCurve::Cubic(p0, p1, p2, p3) => CubicBezierShape::from_points_stroke(
+                    [to_pos(p0), to_pos(p1), to_pos(p2), to_pos(p3)],
+                    false,
+                    Color32::TRANSPARENT,
+                    Stroke::NONE,
+                )
+                .flatten(Some(tolerance)),
+            };
+
+            if last_point == Some(start) {
+                if let Some(contour) = contours.last_mut() {
+                    contour.extend_from_slice(&points[1..]);
+                }
+            } else {
+                contours.push(points);
+            }
+            last_point = Some(end);
+        }
+
+        for contour in &mut contours {
+            // Closed contours end where they start:
+            if 1 < contour.len() && contour.first() == contour.last() {
+                contour.pop();
+            }
+        }
+        contours.retain(|contour| 3 <= contour.len());
+        contours
+    }
+
     fn allocate_glyph(&self, glyph_id: ab_glyph::GlyphId) -> GlyphInfo {
         assert!(glyph_id.0 != 0, "Can't allocate glyph for id 0");
         use ab_glyph::{Font as _, ScaleFont};
@@ -474,6 +584,33 @@ impl Font {
         (Some(font_impl), glyph_info)
     }
 
+    /// The outline of the glyph for this character as closed contours,
+    /// in points relative to where its baseline starts.
+    ///
+    /// Holes wind in the opposite direction to the contours around them.
+    /// Curves are flattened to within `tolerance` points,
+    /// by default a tenth of a physical pixel.
+    ///
+    /// Invisible characters, like spaces, have no contours.
+    pub fn glyph_contours(&mut self, c: char, tolerance: Option<f32>) -> Vec<Vec<Pos2>> {
+        match self.font_impl_and_glyph_info(c) {
+            (Some(font_impl), glyph_info) if !glyph_info.uv_rect.is_nothing() => {
+                font_impl.glyph_contours(glyph_info.id, tolerance)
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Where the primary font suggests drawing underlines, if it says.
+    pub fn underline_metrics(&self) -> Option<DecorationMetrics> {
+        self.fonts.first()?.underline_metrics()
+    }
+
+    /// Where the primary font suggests drawing strikethroughs, if it says.
+    pub fn strikethrough_metrics(&self) -> Option<DecorationMetrics> {
+        self.fonts.first()?.strikethrough_metrics()
+    }
+
     pub(crate) fn ascent(&self) -> f32 {
         if let Some(first) = self.fonts.first() {
             first.ascent()
@@ -493,6 +630,20 @@ impl Font {
     }
 }
 
+/// The position and thickness of the underline and strikeout in font units, from the `post` and `OS/2` tables.
+///
+/// The positions are of the top of the lines, relative to the baseline, with positive values going up.
+/// `index` is the index of the face in a font collection.
+fn decorations_from_font_tables(data: &[u8], index: u32) -> [Option<(f32, f32)>; 2] {
+    let Ok(face) = ttf_parser::Face::parse(data, index) else {
+        return [None, None];
+    };
+    [face.underline_metrics(), face.strikeout_metrics()].map(|metrics| {
+        let metrics = metrics.filter(|metrics| metrics.thickness > 0)?;
+        Some((metrics.position as f32, metrics.thickness as f32))
+    })
+}
+
 /// Code points that will always be invisible (zero width).
 ///
 /// See also [`FontImpl::ignore_character`].
@@ -1,14 +1,17 @@
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
 
 use crate::{
     mutex::{Mutex, MutexGuard},
     text::{
         font::{Font, FontImpl},
-        Galley, LayoutJob,
+        DecorationMetrics, Galley, GlyphOutline, LayoutJob,
     },
     TextureAtlas,
 };
-use emath::{NumExt as _, OrderedFloat};
+use emath::{NumExt as _, OrderedFloat, Pos2};
 
 #[cfg(feature = "default_fonts")]
 use epaint_default_fonts::{EMOJI_ICON, HACK_REGULAR, NOTO_EMOJI_REGULAR, UBUNTU_LIGHT};
@@ -52,6 +55,14 @@ impl FontId {
     pub const fn monospace(size: f32) -> Self {
         Self::new(size, FontFamily::Monospace)
     }
+
+    /// Can this font, or any of its fallbacks, display this character?
+    ///
+    /// Same as [`Fonts::has_glyph`].
+    #[inline]
+    pub fn has_glyph(&self, fonts: &Fonts, c: char) -> bool {
+        fonts.has_glyph(self, c)
+    }
 }
 
 #[allow(clippy::derived_hash_with_manual_eq)]
@@ -410,6 +421,78 @@ impl FontDefinitions {
 
 // ----------------------------------------------------------------------------
 
+/// Which fonts showed which characters, from [`Fonts::glyph_usage`].
+///
+/// Use this to embed only the used subset of each font when exporting,
+/// or to find characters that need another fallback font.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GlyphUsage {
+    /// For each font, by its name in [`FontDefinitions::font_data`],
+    /// the characters it showed.
+    pub fonts: BTreeMap<String, BTreeSet<char>>,
+
+    /// Characters that no font had, and that were shown as the replacement character.
+    pub missing: BTreeSet<char>,
+}
+
+impl GlyphUsage {
+    /// Is no text shown at all?
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty() && self.missing.is_empty()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Controls how large the font atlas may grow, and when it is cleared.
+///
+/// Set with [`Fonts::set_atlas_options`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FontAtlasOptions {
+    /// The largest width and height of the font atlas, in texels.
+    ///
+    /// The atlas is also never larger than the `max_texture_side` of the backend,
+    /// and never narrower than 1024.
+    ///
+    /// A smaller atlas uses less GPU memory, but is cleared more often.
+    ///
+    /// Default: `16384`.
+    pub max_side: usize,
+
+    /// When the font atlas is fuller than this (in `0..=1`) at the start of a pass, it is rebuilt.
+    ///
+    /// The text shown in the last pass is laid out again right away, so its glyphs are rasterized
+    /// into the new atlas, and all glyphs that have not been used since are evicted.
+    ///
+    /// Default: `0.8`.
+    pub max_fill_ratio: f32,
+
+    /// Glyphs rasterized for a `pixels_per_point` that has not been used for this many passes are dropped.
+    ///
+    /// Each zoom level and each monitor DPI needs its own [`Fonts`] with its own atlas.
+    /// While zooming, many of these are only used for a pass or two,
+    /// and keeping them around wastes memory.
+    ///
+    /// This is used by `egui`, which keeps one [`Fonts`] per `pixels_per_point`.
+    ///
+    /// Default: `60`.
+    pub evict_after_passes: u32,
+}
+
+impl Default for FontAtlasOptions {
+    fn default() -> Self {
+        Self {
+            max_side: 16 * 1024,
+            max_fill_ratio: 0.8,
+            evict_after_passes: 60,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// The collection of fonts used by `epaint`.
 ///
 /// Required in order to paint text. Create one and reuse. Cheap to clone.
@@ -433,36 +516,83 @@ impl Fonts {
         max_texture_side: usize,
         definitions: FontDefinitions,
     ) -> Self {
+        let atlas_options = FontAtlasOptions::default();
         let fonts_and_cache = FontsAndCache {
-            fonts: FontsImpl::new(pixels_per_point, max_texture_side, definitions),
+            fonts: FontsImpl::new(
+                pixels_per_point,
+                max_texture_side,
+                atlas_options,
+                definitions,
+            ),
             galley_cache: Default::default(),
+            atlas_options,
         };
         Self(Arc::new(Mutex::new(fonts_and_cache)))
     }
 
+    /// Change how large the font atlas may grow, and when it is cleared.
+    ///
+    /// Takes effect at the next call to [`Self::begin_pass`].
+    pub fn set_atlas_options(&self, atlas_options: FontAtlasOptions) {
+        self.lock().atlas_options = atlas_options;
+    }
+
+    #[inline]
+    pub fn atlas_options(&self) -> FontAtlasOptions {
+        self.lock().atlas_options
+    }
+
     /// Call at the start of each frame with the latest known
     /// `pixels_per_point` and `max_texture_side`.
     ///
     /// Call after painting the previous frame, but before using [`Fonts`] for the new frame.
     ///
-    /// This function will react to changes in `pixels_per_point` and `max_texture_side`,
-    /// as well as notice when the font atlas is getting full, and handle that.
+    /// This function will react to changes in `pixels_per_point`, `max_texture_side`
+    /// and [`Self::atlas_options`], as well as notice when the font atlas is getting full,
+    /// and handle that by evicting the glyphs that are no longer used
+    /// (see [`FontAtlasOptions::max_fill_ratio`]).
     pub fn begin_pass(&self, pixels_per_point: f32, max_texture_side: usize) {
         let mut fonts_and_cache = self.0.lock();
+        let atlas_options = fonts_and_cache.atlas_options;
 
         let pixels_per_point_changed = fonts_and_cache.fonts.pixels_per_point != pixels_per_point;
         let max_texture_side_changed = fonts_and_cache.fonts.max_texture_side != max_texture_side;
-        let font_atlas_almost_full = fonts_and_cache.fonts.atlas.lock().fill_ratio() > 0.8;
-        let needs_recreate =
-            pixels_per_point_changed || max_texture_side_changed || font_atlas_almost_full;
+        let atlas_options_changed = fonts_and_cache.fonts.atlas_options != atlas_options;
+        let font_atlas_almost_full =
+            fonts_and_cache.fonts.atlas.lock().fill_ratio() > atlas_options.max_fill_ratio;
+        let needs_recreate = pixels_per_point_changed
+            || max_texture_side_changed
+            || atlas_options_changed
+            || font_atlas_almost_full;
 
         if needs_recreate {
             let definitions = fonts_and_cache.fonts.definitions.clone();
+            let jobs_in_use: Vec<Arc<LayoutJob>> = fonts_and_cache
+                .galley_cache
+                .galleys_used_this_pass()
+                .map(|galley| galley.job.clone())
+                .collect();
 
             *fonts_and_cache = FontsAndCache {
-                fonts: FontsImpl::new(pixels_per_point, max_texture_side, definitions),
+                fonts: FontsImpl::new(
+                    pixels_per_point,
+                    max_texture_side,
+                    atlas_options,
+                    definitions,
+                ),
                 galley_cache: Default::default(),
+                atlas_options,
             };
+
+            // Rasterize the glyphs still in use into the new atlas, and keep their galleys cached:
+            let FontsAndCache {
+                fonts,
+                galley_cache,
+                atlas_options: _,
+            } = &mut *fonts_and_cache;
+            for job in jobs_in_use {
+                galley_cache.layout(fonts, Arc::unwrap_or_clone(job));
+            }
         }
 
         fonts_and_cache.galley_cache.flush_cache();
@@ -508,6 +638,33 @@ impl Fonts {
         self.lock().fonts.atlas.lock().size()
     }
 
+    /// Rasterize these characters in these fonts into the font atlas ahead of time.
+    ///
+    /// Call this e.g. during a loading screen with the characters you expect to show,
+    /// so that the first frame showing a long CJK paragraph doesn't hitch
+    /// on rasterizing thousands of glyphs at once.
+    ///
+    /// The glyphs are lost when the font atlas is rebuilt,
+    /// e.g. when `pixels_per_point` changes or the atlas fills up
+    /// (see [`FontAtlasOptions::max_fill_ratio`]), so warm up with a set that fits the atlas.
+    ///
+    /// ```
+    /// # let fonts = epaint::Fonts::new(1.0, 1024, Default::default());
+    /// use epaint::FontId;
+    /// let hiragana = '\u{3041}'..='\u{3096}';
+    /// fonts.prewarm(hiragana, &[FontId::proportional(14.0), FontId::proportional(20.0)]);
+    /// ```
+    pub fn prewarm(&self, chars: impl IntoIterator<Item = char>, font_ids: &[FontId]) {
+        let chars: String = chars.into_iter().collect();
+        let mut fonts_and_cache = self.lock();
+        for font_id in font_ids {
+            fonts_and_cache
+                .fonts
+                .font(font_id)
+                .preload_characters(&chars);
+        }
+    }
+
     /// Width of this character in points.
     #[inline]
     pub fn glyph_width(&self, font_id: &FontId, c: char) -> f32 {
@@ -533,6 +690,57 @@ impl Fonts {
         self.lock().fonts.row_height(font_id)
     }
 
+    /// Where the font suggests drawing underlines, if it says.
+    ///
+    /// This is the default for [`crate::text::TextFormat::underline_override`].
+    pub fn underline_metrics(&self, font_id: &FontId) -> Option<DecorationMetrics> {
+        self.lock().fonts.font(font_id).underline_metrics()
+    }
+
+    /// Where the font suggests drawing strikethroughs, if it says.
+    ///
+    /// This is the default for [`crate::text::TextFormat::strikethrough_override`].
+    pub fn strikethrough_metrics(&self, font_id: &FontId) -> Option<DecorationMetrics> {
+        self.lock().fonts.font(font_id).strikethrough_metrics()
+    }
+
+    /// Which fonts showed which characters in the text laid out since [`Self::begin_pass`].
+    ///
+    /// Call this at the end of the pass.
+    pub fn glyph_usage(&self) -> GlyphUsage {
+        let mut usage = GlyphUsage::default();
+        let FontsAndCache {
+            fonts,
+            galley_cache,
+            atlas_options: _,
+        } = &mut *self.lock();
+        for galley in galley_cache.galleys_used_this_pass() {
+            fonts.add_glyph_usage(galley, &mut usage);
+        }
+        usage
+    }
+
+    /// The outline of each visible glyph of the galley, in the same coordinates as the galley.
+    ///
+    /// Curves are flattened to within `tolerance` points,
+    /// by default a tenth of a physical pixel.
+    /// Use this to export text as curves, or to warp it.
+    /// See also [`Self::galley_contours`].
+    pub fn glyph_outlines(&self, galley: &Galley, tolerance: Option<f32>) -> Vec<GlyphOutline> {
+        self.lock().fonts.glyph_outlines(galley, tolerance)
+    }
+
+    /// The outlines of all glyphs of the galley merged together,
+    /// as closed contours in the same coordinates as the galley.
+    ///
+    /// See [`Self::glyph_outlines`].
+    pub fn galley_contours(&self, galley: &Galley, tolerance: Option<f32>) -> Vec<Vec<Pos2>> {
+        self.glyph_outlines(galley, tolerance)
+            .into_iter()
+            .flat_map(|outline| outline.contours)
+            .collect()
+    }
+
     /// List of all known font families.
     pub fn families(&self) -> Vec<FontFamily> {
         self.lock()
@@ -613,6 +821,9 @@ impl Fonts {
 pub struct FontsAndCache {
     pub fonts: FontsImpl,
     galley_cache: GalleyCache,
+
+    /// Applied to [`Self::fonts`] at the next [`Fonts::begin_pass`].
+    atlas_options: FontAtlasOptions,
 }
 
 impl FontsAndCache {
@@ -629,6 +840,7 @@ impl FontsAndCache {
 pub struct FontsImpl {
     pixels_per_point: f32,
     max_texture_side: usize,
+    atlas_options: FontAtlasOptions,
     definitions: FontDefinitions,
     atlas: Arc<Mutex<TextureAtlas>>,
     font_impl_cache: FontImplCache,
@@ -641,6 +853,7 @@ impl FontsImpl {
     pub fn new(
         pixels_per_point: f32,
         max_texture_side: usize,
+        atlas_options: FontAtlasOptions,
         definitions: FontDefinitions,
     ) -> Self {
         assert!(
@@ -648,7 +861,7 @@ impl FontsImpl {
             "pixels_per_point out of range: {pixels_per_point}"
         );
 
-        let texture_width = max_texture_side.at_most(16 * 1024);
+        let texture_width = max_texture_side.at_most(atlas_options.max_side.at_least(1024));
         let initial_height = 32; // Keep initial font atlas small, so it is fast to upload to GPU. This will expand as needed anyways.
         let atlas = TextureAtlas::new([texture_width, initial_height]);
 
@@ -660,6 +873,7 @@ impl FontsImpl {
         Self {
             pixels_per_point,
             max_texture_side,
+            atlas_options,
             definitions,
             atlas,
             font_impl_cache,
@@ -719,6 +933,57 @@ impl FontsImpl {
     fn row_height(&mut self, font_id: &FontId) -> f32 {
         self.font(font_id).row_height()
     }
+
+    /// Add which fonts showed which characters of the galley.
+    pub fn add_glyph_usage(&mut self, galley: &Galley, usage: &mut GlyphUsage) {
+        for row in &galley.rows {
+            for glyph in &row.glyphs {
+                let format = &galley.job.sections[glyph.section_index as usize].format;
+                let font = self.font(&format.font_id);
+                if !font.has_glyph(glyph.chr) {
+                    usage.missing.insert(glyph.chr);
+                } else if let (Some(font_impl), _) = font.font_impl_and_glyph_info(glyph.chr) {
+                    usage
+                        .fonts
+                        .entry(font_impl.name().to_owned())
+                        .or_default()
+                        .insert(glyph.chr);
+                }
+            }
+        }
+    }
+
+    /// The outline of each visible glyph of the galley, in the same coordinates as the galley.
+    pub fn glyph_outlines(&mut self, galley: &Galley, tolerance: Option<f32>) -> Vec<GlyphOutline> {
+        let mut outlines = vec![];
+        for row in &galley.rows {
+            for glyph in &row.glyphs {
+                let format = &galley.job.sections[glyph.section_index as usize].format;
+                let mut contours = self
+                    .font(&format.font_id)
+                    .glyph_contours(glyph.chr, tolerance);
+                if contours.is_empty() {
+                    continue;
+                }
+
+                // Slant like the mesh does, around the bottom of the glyph image:
+                let bottom = glyph.pos.y + glyph.uv_rect.offset.y + glyph.uv_rect.size.y;
+                for point in contours.iter_mut().flatten() {
+                    *point += glyph.pos.to_vec2();
+                    if format.italics {
+                        point.x += 0.25 * (bottom - point.y);
+                    }
+                }
+
+                outlines.push(GlyphOutline {
+                    chr: glyph.chr,
+                    section_index: glyph.section_index,
+                    contours,
+                });
+            }
+        }
+        outlines
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -787,6 +1052,13 @@ impl GalleyCache {
         self.cache.len()
     }
 
+    fn galleys_used_this_pass(&self) -> impl Iterator<Item = &Galley> + '_ {
+        self.cache
+            .values()
+            .filter(|cached| cached.last_used == self.generation)
+            .map(|cached| &*cached.galley)
+    }
+
     /// Must be called once per frame to clear the [`Galley`] cache.
     pub fn flush_cache(&mut self) {
         let current_generation = self.generation;
@@ -802,7 +1074,7 @@ impl GalleyCache {
 struct FontImplCache {
     atlas: Arc<Mutex<TextureAtlas>>,
     pixels_per_point: f32,
-    ab_glyph_fonts: BTreeMap<String, (FontTweak, ab_glyph::FontArc)>,
+    ab_glyph_fonts: BTreeMap<String, (FontTweak, ab_glyph::FontArc, u32)>,
 
     /// Map font pixel sizes and names to the cached [`FontImpl`].
     cache: ahash::HashMap<(u32, String), Arc<FontImpl>>,
@@ -819,7 +1091,7 @@ impl FontImplCache {
             .map(|(name, font_data)| {
                 let tweak = font_data.tweak;
                 let ab_glyph = ab_glyph_font_from_font_data(name, font_data);
-                (name.clone(), (tweak, ab_glyph))
+                (name.clone(), (tweak, ab_glyph, font_data.index))
             })
             .collect();
 
@@ -834,7 +1106,7 @@ impl FontImplCache {
     pub fn font_impl(&mut self, scale_in_points: f32, font_name: &str) -> Arc<FontImpl> {
         use ab_glyph::Font as _;
 
-        let (tweak, ab_glyph_font) = self
+        let (tweak, ab_glyph_font, face_index) = self
             .ab_glyph_fonts
             .get(font_name)
             .unwrap_or_else(|| panic!("No font data found for {font_name:?}"))
@@ -860,6 +1132,7 @@ impl FontImplCache {
                     self.pixels_per_point,
                     font_name.to_owned(),
                     ab_glyph_font,
+                    face_index,
                     scale_in_pixels,
                     tweak,
                 ))
@@ -867,3 +1140,80 @@ impl FontImplCache {
             .clone()
     }
 }
+
+#[test]
+fn test_glyph_usage() {
+    let fonts = Fonts::new(1.0, 1024, FontDefinitions::default());
+    let font_id = FontId::proportional(14.0);
+    let missing = '\u{10FFFD}'; // Private use, in no font
+    assert!(font_id.has_glyph(&fonts, 'a'));
+    assert!(!font_id.has_glyph(&fonts, missing));
+
+    fonts.layout_no_wrap(format!("ab😀{missing}"), font_id, crate::Color32::WHITE);
+    let usage = fonts.glyph_usage();
+    assert_eq!(usage.fonts["Ubuntu-Light"], BTreeSet::from(['a', 'b']));
+    assert_eq!(usage.fonts["NotoEmoji-Regular"], BTreeSet::from(['😀']));
+    assert_eq!(usage.missing, BTreeSet::from([missing]));
+
+    fonts.begin_pass(1.0, 1024);
+    assert!(fonts.glyph_usage().is_empty());
+}
+
+#[test]
+fn test_atlas_options() {
+    let fonts = Fonts::new(1.0, 8192, FontDefinitions::default());
+    assert_eq!(fonts.font_image_size()[0], 8192);
+
+    fonts.set_atlas_options(FontAtlasOptions {
+        max_side: 2048,
+        ..Default::default()
+    });
+    assert_eq!(fonts.font_image_size()[0], 8192, "Applied at the next pass");
+    fonts.begin_pass(1.0, 8192);
+    assert_eq!(fonts.font_image_size()[0], 2048);
+}
+
+#[test]
+fn test_prewarm() {
+    let fonts = Fonts::new(1.0, 1024, FontDefinitions::default());
+    let font_id = FontId::proportional(14.0);
+    fonts.layout_no_wrap("a".to_owned(), font_id.clone(), crate::Color32::WHITE);
+    let _ = fonts.font_image_delta();
+
+    fonts.prewarm('α'..='ω', &[font_id.clone()]);
+    assert!(fonts.font_image_delta().is_some(), "Glyphs were rasterized");
+
+    // Laying out the warmed up glyphs doesn't touch the atlas:
+    fonts.layout_no_wrap("αβγ".to_owned(), font_id, crate::Color32::WHITE);
+    assert!(fonts.font_image_delta().is_none());
+}
+
+#[test]
+fn test_full_atlas_evicts_unused_glyphs() {
+    let fonts = Fonts::new(1.0, 1024, FontDefinitions::default());
+    let (small, large) = (FontId::proportional(14.0), FontId::proportional(100.0));
+    let color = crate::Color32::WHITE;
+
+    fonts.begin_pass(1.0, 1024);
+    fonts.layout_no_wrap("stale".to_owned(), large, color);
+    fonts.begin_pass(1.0, 1024);
+    fonts.layout_no_wrap("kept".to_owned(), small.clone(), color);
+    let fill_ratio = fonts.texture_atlas().lock().fill_ratio();
+
+    // Fill up the atlas:
+    fonts.set_atlas_options(FontAtlasOptions {
+        max_fill_ratio: 0.0,
+        ..Default::default()
+    });
+    fonts.begin_pass(1.0, 1024);
+    assert!(
+        fonts.texture_atlas().lock().fill_ratio() < fill_ratio,
+        "The large glyphs were evicted"
+    );
+
+    // The text in use was laid out with the new atlas, so showing it needs no new glyphs:
+    let _ = fonts.font_image_delta();
+    fonts.layout_no_wrap("kept".to_owned(), small, color);
+    assert!(fonts.font_image_delta().is_none());
+    assert_eq!(fonts.lock().galley_cache.num_galleys_in_cache(), 1);
+}
@@ -5,7 +5,10 @@ use emath::{pos2, vec2, Align, GuiRounding as _, NumExt, Pos2, Rect, Vec2};
 
 use crate::{stroke::PathStroke, text::font::Font, Color32, Mesh, Stroke, Vertex};
 
-use super::{FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, Row, RowVisuals};
+use super::{
+    DecorationMetrics, DecorationOverride, FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, Row,
+    RowVisuals, UnderlineStyle,
+};
 
 // ----------------------------------------------------------------------------
 
@@ -121,8 +124,10 @@ pub fn layout(fonts: &mut FontsImpl, job: Arc<LayoutJob>) -> Galley {
         }
     }
 
+    let format_summary = format_summary(fonts, &job);
+
     // Calculate the Y positions and tessellate the text:
-    galley_from_rows(point_scale, job, rows, elided)
+    galley_from_rows(point_scale, job, &format_summary, rows, elided)
 }
 
 // Ignores the Y coordinate.
@@ -592,6 +597,7 @@ fn halign_and_justify_row(
 fn galley_from_rows(
     point_scale: PointScale,
     job: Arc<LayoutJob>,
+    format_summary: &FormatSummary,
     mut rows: Vec<Row>,
     elided: bool,
 ) -> Galley {
@@ -633,14 +639,12 @@ fn galley_from_rows(
         cursor_y = point_scale.round_to_pixel(cursor_y); // TODO(emilk): it would be better to do the calculations in pixels instead.
     }
 
-    let format_summary = format_summary(&job);
-
     let mut mesh_bounds = Rect::NOTHING;
     let mut num_vertices = 0;
     let mut num_indices = 0;
 
     for row in &mut rows {
-        row.visuals = tessellate_row(point_scale, &job, &format_summary, row);
+        row.visuals = tessellate_row(point_scale, &job, format_summary, row);
         mesh_bounds = mesh_bounds.union(row.visuals.mesh_bounds);
         num_vertices += row.visuals.mesh.vertices.len();
         num_indices += row.visuals.mesh.indices.len();
@@ -687,18 +691,55 @@ struct FormatSummary {
     any_background: bool,
     any_underline: bool,
     any_strikethrough: bool,
+
+    /// The underline and strikethrough metrics of the font of each section,
+    /// if there are any underlines or strikethroughs.
+    decorations: Vec<[Option<DecorationMetrics>; 2]>,
 }
 
-fn format_summary(job: &LayoutJob) -> FormatSummary {
+fn format_summary(fonts: &mut FontsImpl, job: &LayoutJob) -> FormatSummary {
     let mut format_summary = FormatSummary::default();
     for section in &job.sections {
         format_summary.any_background |= section.format.background != Color32::TRANSPARENT;
         format_summary.any_underline |= section.format.underline != Stroke::NONE;
         format_summary.any_strikethrough |= section.format.strikethrough != Stroke::NONE;
     }
+    if format_summary.any_underline || format_summary.any_strikethrough {
+        format_summary.decorations = job
+            .sections
+            .iter()
+            .map(|section| {
+                let font = fonts.font(&section.format.font_id);
+                [font.underline_metrics(), font.strikethrough_metrics()]
+            })
+            .collect();
+    }
     format_summary
 }
 
+/// The stroke and y coordinate of an underline or strikethrough.
+///
+/// Prefers the override, then the font metrics, then `fallback_y` and the width of `stroke`.
+fn decoration_stroke_and_y(
+    glyph: &Glyph,
+    stroke: Stroke,
+    decoration_override: DecorationOverride,
+    font_metrics: Option<DecorationMetrics>,
+    fallback_y: f32,
+) -> (Stroke, f32) {
+    let DecorationOverride { offset, thickness } = decoration_override;
+    let y = offset
+        .or(font_metrics.map(|metrics| metrics.offset))
+        .map_or(fallback_y, |offset| glyph.pos.y + offset);
+    let stroke = Stroke {
+        width: thickness
+            .or(font_metrics.map(|metrics| metrics.thickness))
+            .unwrap_or(stroke.width),
+        ..stroke
+    };
+    (stroke, y)
+}
+
 fn tessellate_row(
     point_scale: PointScale,
     job: &LayoutJob,
@@ -726,18 +767,30 @@ fn tessellate_row(
     if format_summary.any_underline {
         add_row_hline(point_scale, row, &mut mesh, |glyph| {
             let format = &job.sections[glyph.section_index as usize].format;
-            let stroke = format.underline;
-            let y = glyph.logical_rect().bottom();
-            (stroke, y)
+            let [font_metrics, _] = format_summary.decorations[glyph.section_index as usize];
+            let (stroke, y) = decoration_stroke_and_y(
+                glyph,
+                format.underline,
+                format.underline_override,
+                font_metrics,
+                glyph.logical_rect().bottom(),
+            );
+            (stroke, y, format.underline_style)
         });
     }
 
     if format_summary.any_strikethrough {
         add_row_hline(point_scale, row, &mut mesh, |glyph| {
             let format = &job.sections[glyph.section_index as usize].format;
-            let stroke = format.strikethrough;
-            let y = glyph.logical_rect().center().y;
-            (stroke, y)
+            let [_, font_metrics] = format_summary.decorations[glyph.section_index as usize];
+            let (stroke, y) = decoration_stroke_and_y(
+                glyph,
+                format.strikethrough,
+                format.strikethrough_override,
+                font_metrics,
+                glyph.logical_rect().center().y,
+            );
+            (stroke, y, UnderlineStyle::Straight)
         });
     }
 
@@ -849,16 +902,22 @@ fn tessellate_glyphs(point_scale: PointScale, job: &LayoutJob, row: &Row, mesh:
     }
 }
 
-/// Add a horizontal line over a row of glyphs with a stroke and y decided by a callback.
+/// Add a horizontal line over a row of glyphs with a stroke, y and style decided by a callback.
 fn add_row_hline(
     point_scale: PointScale,
     row: &Row,
     mesh: &mut Mesh,
-    stroke_and_y: impl Fn(&Glyph) -> (Stroke, f32),
+    stroke_y_and_style: impl Fn(&Glyph) -> (Stroke, f32, UnderlineStyle),
 ) {
-    let mut end_line = |start: Option<(Stroke, Pos2)>, stop_x: f32| {
-        if let Some((stroke, start)) = start {
-            add_hline(point_scale, [start, pos2(stop_x, start.y)], stroke, mesh);
+    let mut end_line = |start: Option<(Stroke, Pos2, UnderlineStyle)>, stop_x: f32| {
+        if let Some((stroke, start, style)) = start {
+            add_hline(
+                point_scale,
+                [start, pos2(stop_x, start.y)],
+                stroke,
+                style,
+                mesh,
+            );
         }
     };
 
@@ -866,19 +925,19 @@ fn add_row_hline(
     let mut last_right_x = f32::NAN;
 
     for glyph in &row.glyphs {
-        let (stroke, y) = stroke_and_y(glyph);
+        let (stroke, y, style) = stroke_y_and_style(glyph);
 
         if stroke == Stroke::NONE {
             end_line(line_start.take(), last_right_x);
-        } else if let Some((existing_stroke, start)) = line_start {
-            if existing_stroke == stroke && start.y == y {
+        } else if let Some((existing_stroke, start, existing_style)) = line_start {
+            if existing_stroke == stroke && start.y == y && existing_style == style {
                 // continue the same line
             } else {
                 end_line(line_start.take(), last_right_x);
-                line_start = Some((stroke, pos2(glyph.pos.x, y)));
+                line_start = Some((stroke, pos2(glyph.pos.x, y), style));
             }
         } else {
-            line_start = Some((stroke, pos2(glyph.pos.x, y)));
+            line_start = Some((stroke, pos2(glyph.pos.x, y), style));
         }
 
         last_right_x = glyph.max_x();
@@ -887,14 +946,75 @@ fn add_row_hline(
     end_line(line_start.take(), last_right_x);
 }
 
-fn add_hline(point_scale: PointScale, [start, stop]: [Pos2; 2], stroke: Stroke, mesh: &mut Mesh) {
+fn add_hline(
+    point_scale: PointScale,
+    [start, stop]: [Pos2; 2],
+    stroke: Stroke,
+    style: UnderlineStyle,
+    mesh: &mut Mesh,
+) {
     let antialiased = true;
 
     if antialiased {
         let mut path = crate::tessellator::Path::default(); // TODO(emilk): reuse this to avoid re-allocations.
-        path.add_line_segment([start, stop]);
         let feathering = 1.0 / point_scale.pixels_per_point();
-        path.stroke_open(feathering, &PathStroke::from(stroke), mesh);
+        let path_stroke = PathStroke::from(stroke);
+
+        // The patterns are scaled by the line width, but never thinner than a pixel:
+        let unit = stroke.width.max(1.0 / point_scale.pixels_per_point());
+
+        let mut stroke_segment =
+            |path: &mut crate::tessellator::Path, from: f32, to: f32, y: f32| {
+                path.clear();
+                path.add_line_segment([pos2(from, y), pos2(to, y)]);
+                path.stroke_open(feathering, &path_stroke, mesh);
+            };
+
+        match style {
+            UnderlineStyle::Straight => {
+                stroke_segment(&mut path, start.x, stop.x, start.y);
+            }
+            UnderlineStyle::Double => {
+                stroke_segment(&mut path, start.x, stop.x, start.y - unit);
+                stroke_segment(&mut path, start.x, stop.x, start.y + unit);
+            }
+            UnderlineStyle::Dotted | UnderlineStyle::Dashed => {
+                let (dash, gap) = if style == UnderlineStyle::Dotted {
+                    (unit, unit)
+                } else {
+                    (3.0 * unit, 2.0 * unit)
+                };
+                let mut x = start.x;
+                while x < stop.x {
+                    stroke_segment(&mut path, x, (x + dash).min(stop.x), start.y);
+                    x += dash + gap;
+                }
+            }
+            UnderlineStyle::Wavy => {
+                // The phase follows the absolute x, so neighboring lines join up:
+                let amplitude = unit.max(1.0);
+                let wavelength = 4.0 * amplitude;
+                let step = wavelength / 8.0;
+                let y =
+                    |x: f32| start.y + amplitude * (std::f32::consts::TAU * x / wavelength).sin();
+
+                let mut points = vec![pos2(start.x, y(start.x))];
+                let mut x = (start.x / step).floor() * step + step;
+                while x < stop.x {
+                    points.push(pos2(x, y(x)));
+                    x += step;
+                }
+                points.push(pos2(stop.x, y(stop.x)));
+
+                path.clear();
+                path.add_open_points(&points);
+                path.stroke_open(
+                    feathering,
+                    &path_stroke.with_line_join(crate::LineJoin::Round),
+                    mesh,
+                );
+            }
+        }
     } else {
         // Thin lines often lost, so this is a bad idea
 
@@ -1051,7 +1171,7 @@ mod tests {
 
     #[test]
     fn test_zero_max_width() {
-        let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
+        let mut fonts = FontsImpl::new(1.0, 1024, Default::default(), FontDefinitions::default());
         let mut layout_job = LayoutJob::single_section("W".into(), TextFormat::default());
         layout_job.wrap.max_width = 0.0;
         let galley = layout(&mut fonts, layout_job.into());
@@ -1062,7 +1182,7 @@ mod tests {
     fn test_truncate_with_newline() {
         // No matter where we wrap, we should be appending the newline character.
 
-        let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
+        let mut fonts = FontsImpl::new(1.0, 1024, Default::default(), FontDefinitions::default());
         let text_format = TextFormat {
             font_id: FontId::monospace(12.0),
             ..Default::default()
@@ -1107,7 +1227,7 @@ mod tests {
 
     #[test]
     fn test_cjk() {
-        let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
+        let mut fonts = FontsImpl::new(1.0, 1024, Default::default(), FontDefinitions::default());
         let mut layout_job = LayoutJob::single_section(
             "日本語とEnglishの混在した文章".into(),
             TextFormat::default(),
@@ -1122,7 +1242,7 @@ mod tests {
 
     #[test]
     fn test_pre_cjk() {
-        let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
+        let mut fonts = FontsImpl::new(1.0, 1024, Default::default(), FontDefinitions::default());
         let mut layout_job = LayoutJob::single_section(
             "日本語とEnglishの混在した文章".into(),
             TextFormat::default(),
@@ -1137,7 +1257,7 @@ mod tests {
 
     #[test]
     fn test_truncate_width() {
-        let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
+        let mut fonts = FontsImpl::new(1.0, 1024, Default::default(), FontDefinitions::default());
         let mut layout_job =
             LayoutJob::single_section("# DNA\nMore text".into(), TextFormat::default());
         layout_job.wrap.max_width = f32::INFINITY;
@@ -1152,4 +1272,109 @@ mod tests {
         let row = &galley.rows[0];
         assert_eq!(row.rect.max.x, row.glyphs.last().unwrap().max_x());
     }
+
+    #[test]
+    fn test_underline_styles() {
+        let mut fonts = FontsImpl::new(1.0, 1024, Default::default(), FontDefinitions::default());
+        let mut underline_bounds = |underline_style| {
+            let format = TextFormat {
+                underline: Stroke::new(1.0, Color32::RED),
+                underline_style,
+                ..Default::default()
+            };
+            let galley = layout(
+                &mut fonts,
+                LayoutJob::single_section("Misspeled".into(), format).into(),
+            );
+            let visuals = &galley.rows[0].visuals;
+            let vertices = &visuals.mesh.vertices[visuals.glyph_vertex_range.end..];
+            Rect::from_points(&vertices.iter().map(|v| v.pos).collect::<Vec<_>>())
+        };
+
+        let straight = underline_bounds(UnderlineStyle::Straight);
+        let wavy = underline_bounds(UnderlineStyle::Wavy);
+        let double = underline_bounds(UnderlineStyle::Double);
+        let dotted = underline_bounds(UnderlineStyle::Dotted);
+        assert!(straight.height() < wavy.height());
+        assert!(straight.height() < double.height());
+        assert!((straight.width() - dotted.width()).abs() <= 1.0);
+    }
+
+    #[test]
+    fn test_decoration_override() {
+        let mut fonts = FontsImpl::new(1.0, 1024, Default::default(), FontDefinitions::default());
+        let font_id = TextFormat::default().font_id;
+        let underline = fonts.font(&font_id).underline_metrics().unwrap();
+        let strikethrough = fonts.font(&font_id).strikethrough_metrics().unwrap();
+        assert!(0.0 < underline.offset && 0.0 < underline.thickness);
+        assert!(strikethrough.offset < 0.0 && 0.0 < strikethrough.thickness);
+
+        let mut underline_bounds = |underline_override| {
+            let format = TextFormat {
+                underline: Stroke::new(5.0, Color32::RED),
+                underline_override,
+                ..Default::default()
+            };
+            let galley = layout(
+                &mut fonts,
+                LayoutJob::single_section("Underlined".into(), format).into(),
+            );
+            let row = &galley.rows[0];
+            let vertices = &row.visuals.mesh.vertices[row.visuals.glyph_vertex_range.end..];
+            let bounds = Rect::from_points(&vertices.iter().map(|v| v.pos).collect::<Vec<_>>());
+            (bounds, row.glyphs[0].pos.y)
+        };
+
+        let (default, baseline) = underline_bounds(DecorationOverride::default());
+        assert!((default.center().y - (baseline + underline.offset)).abs() <= 1.0);
+        assert!(
+            default.height() <= underline.thickness + 1.5, // feathering
+            "The font decides the thickness, not the stroke"
+        );
+
+        let (moved, _) = underline_bounds(DecorationOverride {
+            offset: Some(-4.0),
+            thickness: Some(3.0),
+        });
+        assert!((moved.center().y - (baseline - 4.0)).abs() <= 1.0);
+        assert!((moved.height() - 3.0).abs() <= 1.0);
+    }
+    #[test]
+    fn test_glyph_outlines() {
+        let mut fonts = FontsImpl::new(1.0, 1024, Default::default(), FontDefinitions::default());
+        let galley = layout(
+            &mut fonts,
+            LayoutJob::single_section("o I".into(), TextFormat::default()).into(),
+        );
+        let outlines = fonts.glyph_outlines(&galley, None);
+        assert_eq!(
+            outlines
+                .iter()
+                .map(|outline| outline.chr)
+                .collect::<String>(),
+            "oI",
+            "The space has no outline"
+        );
+
+        // The `o` has a hole:
+        assert_eq!(outlines[0].contours.len(), 2);
+        assert_eq!(outlines[1].contours.len(), 1);
+
+        for (outline, glyph) in outlines
+            .iter()
+            .zip([0, 2].map(|i| galley.rows[0].glyphs[i]))
+        {
+            let glyph_rect =
+                Rect::from_min_size(glyph.pos + glyph.uv_rect.offset, glyph.uv_rect.size);
+            let bounds = outline.bounding_rect();
+            assert!(
+                glyph_rect.expand(1.0).contains_rect(bounds),
+                "{bounds:?} {glyph_rect:?}"
+            );
+            assert!(
+                bounds.expand(1.0).contains_rect(glyph_rect),
+                "{bounds:?} {glyph_rect:?}"
+            );
+        }
+    }
 }
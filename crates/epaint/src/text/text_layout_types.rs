@@ -8,7 +8,7 @@ use super::{
     cursor::{CCursor, LayoutCursor},
     font::UvRect,
 };
-use crate::{Color32, FontId, Mesh, Stroke};
+use crate::{stroke::PathStroke, Color32, FontId, Mesh, PathShape, Shape, Stroke};
 use emath::{pos2, vec2, Align, NumExt, OrderedFloat, Pos2, Rect, Vec2};
 
 /// Describes the task of laying out text.
@@ -281,8 +281,19 @@ pub struct TextFormat {
 
     pub underline: Stroke,
 
+    /// How [`Self::underline`] is drawn.
+    ///
+    /// Default: [`UnderlineStyle::Straight`].
+    pub underline_style: UnderlineStyle,
+
     pub strikethrough: Stroke,
 
+    /// Where and how thick to draw [`Self::underline`], instead of what the font suggests.
+    pub underline_override: DecorationOverride,
+
+    /// Where and how thick to draw [`Self::strikethrough`], instead of what the font suggests.
+    pub strikethrough_override: DecorationOverride,
+
     /// If you use a small font and [`Align::TOP`] you
     /// can get the effect of raised text.
     ///
@@ -307,7 +318,10 @@ impl Default for TextFormat {
             expand_bg: 1.0,
             italics: false,
             underline: Stroke::NONE,
+            underline_style: UnderlineStyle::Straight,
             strikethrough: Stroke::NONE,
+            underline_override: DecorationOverride::default(),
+            strikethrough_override: DecorationOverride::default(),
             valign: Align::BOTTOM,
         }
     }
@@ -325,7 +339,10 @@ impl std::hash::Hash for TextFormat {
             expand_bg,
             italics,
             underline,
+            underline_style,
             strikethrough,
+            underline_override,
+            strikethrough_override,
             valign,
         } = self;
         font_id.hash(state);
@@ -338,7 +355,10 @@ impl std::hash::Hash for TextFormat {
         emath::OrderedFloat(*expand_bg).hash(state);
         italics.hash(state);
         underline.hash(state);
+        underline_style.hash(state);
         strikethrough.hash(state);
+        underline_override.hash(state);
+        strikethrough_override.hash(state);
         valign.hash(state);
     }
 }
@@ -354,6 +374,71 @@ impl TextFormat {
     }
 }
 
+/// How the [`TextFormat::underline`] is drawn.
+///
+/// The lines use the width and color of the underline stroke,
+/// and the patterns are scaled by its width.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum UnderlineStyle {
+    /// A single straight line.
+    #[default]
+    Straight,
+
+    /// A wavy line, like the squiggles under misspelled words.
+    Wavy,
+
+    /// A line of dots.
+    Dotted,
+
+    /// A line of dashes.
+    Dashed,
+
+    /// Two straight lines.
+    Double,
+}
+
+/// Where to draw an underline or strikethrough, relative to the baseline of the text.
+///
+/// See [`Fonts::underline_metrics`] and [`Fonts::strikethrough_metrics`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DecorationMetrics {
+    /// How far below the baseline the center of the line is, in points.
+    ///
+    /// Negative values are above the baseline.
+    pub offset: f32,
+
+    /// The thickness of the line, in points.
+    pub thickness: f32,
+}
+
+/// Overrides the [`DecorationMetrics`] of an underline or strikethrough.
+///
+/// Anything left as `None` uses what the font suggests.
+/// If the font doesn't say, the line goes under (or through) the glyphs,
+/// and is as thick as the width of the [`Stroke`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DecorationOverride {
+    /// How far below the baseline the center of the line is, in points.
+    ///
+    /// Negative values are above the baseline.
+    pub offset: Option<f32>,
+
+    /// The thickness of the line, in points.
+    pub thickness: Option<f32>,
+}
+
+impl std::hash::Hash for DecorationOverride {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self { offset, thickness } = self;
+        offset.map(emath::OrderedFloat).hash(state);
+        thickness.map(emath::OrderedFloat).hash(state);
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 /// How to wrap and elide text.
@@ -663,6 +748,41 @@ impl Glyph {
     }
 }
 
+/// The outline of a [`Glyph`] in a [`Galley`], from [`crate::Fonts::glyph_outlines`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GlyphOutline {
+    /// The character of the glyph.
+    pub chr: char,
+
+    /// Index into [`LayoutJob::sections`].
+    pub section_index: u32,
+
+    /// Closed contours, in the same coordinates as the [`Galley`].
+    ///
+    /// Holes wind in the opposite direction to the contours around them.
+    pub contours: Vec<Vec<Pos2>>,
+}
+
+impl GlyphOutline {
+    /// The bounding rectangle of all the contours.
+    pub fn bounding_rect(&self) -> Rect {
+        self.contours.iter().fold(Rect::NOTHING, |rect, contour| {
+            rect.union(Rect::from_points(contour))
+        })
+    }
+
+    /// Paint the contours as closed lines, e.g. to show the text as outlines only.
+    pub fn to_shape(&self, stroke: impl Into<PathStroke>) -> Shape {
+        let stroke = stroke.into();
+        Shape::Vec(
+            self.contours
+                .iter()
+                .map(|contour| PathShape::closed_line(contour.clone(), stroke.clone()).into())
+                .collect(),
+        )
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 impl Row {
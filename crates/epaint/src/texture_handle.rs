@@ -1,8 +1,10 @@
 use std::sync::Arc;
 
 use crate::{
-    emath::NumExt, mutex::RwLock, textures::TextureOptions, ImageData, ImageDelta, TextureId,
-    TextureManager,
+    emath::{NumExt, Pos2, Rect},
+    mutex::RwLock,
+    textures::TextureOptions,
+    ColorImage, ImageData, ImageDelta, TextureId, TextureManager,
 };
 
 /// Used to paint images.
@@ -86,6 +88,15 @@ impl TextureHandle {
             .set(self.id, ImageDelta::partial(pos, image.into(), options));
     }
 
+    /// Get a [`TextureUploader`] for updating this texture from another thread, a bit at a time.
+    pub fn uploader(&self) -> TextureUploader {
+        TextureUploader {
+            tex_mngr: self.tex_mngr.clone(),
+            id: self.id,
+            on_update: None,
+        }
+    }
+
     /// width x height
     pub fn size(&self) -> [usize; 2] {
         self.tex_mngr
@@ -136,3 +147,89 @@ impl From<&mut TextureHandle> for TextureId {
         handle.id()
     }
 }
+
+// ----------------------------------------------------------------------------
+
+/// Updates a texture from any thread, spread out over several frames.
+///
+/// Use this to hand off decoding of large images to a background thread:
+/// allocate a placeholder texture of the right size, decode the image on the background thread,
+/// and send it with [`Self::set_tiled`]. The tiles are then uploaded a few per frame,
+/// as set by [`TextureManager::set_upload_budget`], so neither the UI thread nor any single frame stalls.
+///
+/// Get one with [`TextureHandle::uploader`] (or `egui::Context::texture_uploader`).
+///
+/// The uploader does not keep the texture alive: once all [`TextureHandle`]s are dropped,
+/// further updates are ignored.
+#[derive(Clone)]
+pub struct TextureUploader {
+    tex_mngr: Arc<RwLock<TextureManager>>,
+    id: TextureId,
+    on_update: Option<Arc<dyn Fn() + Send + Sync>>,
+}
+
+impl TextureUploader {
+    /// Call this after each update, e.g. to wake up the UI thread with a repaint.
+    #[inline]
+    pub fn on_update(mut self, on_update: Arc<dyn Fn() + Send + Sync>) -> Self {
+        self.on_update = Some(on_update);
+        self
+    }
+
+    #[inline]
+    pub fn id(&self) -> TextureId {
+        self.id
+    }
+
+    /// Queue an update of a region of the texture.
+    pub fn set_partial(
+        &self,
+        pos: [usize; 2],
+        image: impl Into<ImageData>,
+        options: TextureOptions,
+    ) {
+        self.tex_mngr
+            .write()
+            .set_streamed(self.id, ImageDelta::partial(pos, image.into(), options));
+        self.updated();
+    }
+
+    /// Queue the whole image, split into tiles of at most `tile_size` x `tile_size` texels.
+    ///
+    /// If the texture doesn't have the same size as `image`, the whole image is set at once instead.
+    pub fn set_tiled(&self, image: &ColorImage, options: TextureOptions, tile_size: usize) {
+        let tile_size = tile_size.at_least(1);
+        let [width, height] = image.size;
+        {
+            let mut tex_mngr = self.tex_mngr.write();
+            let Some(meta) = tex_mngr.meta(self.id) else {
+                return; // freed
+            };
+            if meta.size == image.size {
+                for y in (0..height).step_by(tile_size) {
+                    for x in (0..width).step_by(tile_size) {
+                        let max = [
+                            (x + tile_size).at_most(width),
+                            (y + tile_size).at_most(height),
+                        ];
+                        let region = Rect::from_min_max(
+                            Pos2::new(x as f32, y as f32),
+                            Pos2::new(max[0] as f32, max[1] as f32),
+                        );
+                        let tile = image.region(&region, None);
+                        tex_mngr.set_streamed(self.id, ImageDelta::partial([x, y], tile, options));
+                    }
+                }
+            } else {
+                tex_mngr.set(self.id, ImageDelta::full(image.clone(), options));
+            }
+        }
+        self.updated();
+    }
+
+    fn updated(&self) {
+        if let Some(on_update) = &self.on_update {
+            on_update();
+        }
+    }
+}
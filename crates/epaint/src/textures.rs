@@ -1,4 +1,4 @@
-use crate::{ImageData, ImageDelta, TextureId};
+use crate::{ecolor::ColorSpace, ClippedPrimitive, ImageData, ImageDelta, TextureId};
 
 // ----------------------------------------------------------------------------
 
@@ -14,9 +14,18 @@ pub struct TextureManager {
     metas: ahash::HashMap<TextureId, TextureMeta>,
 
     delta: TexturesDelta,
+
+    /// Partial updates that are sent to the backend a few at a time, see [`Self::set_streamed`].
+    streamed: std::collections::VecDeque<(TextureId, ImageDelta)>,
+
+    /// Max bytes of streamed updates per frame. `None` means [`Self::DEFAULT_UPLOAD_BUDGET`].
+    upload_budget: Option<usize>,
 }
 
 impl TextureManager {
+    /// The default for [`Self::set_upload_budget`]: 4 MiB, e.g. one 1024x1024 RGBA tile per frame.
+    pub const DEFAULT_UPLOAD_BUDGET: usize = 4 * 1024 * 1024;
+
     /// Allocate a new texture.
     ///
     /// The given name can be useful for later debugging.
@@ -54,12 +63,30 @@ impl TextureManager {
                         && pos[1] + delta.image.height() <= meta.size[1],
                     "Partial texture update is outside the bounds of texture {id:?}",
                 );
+
+                // Streamed updates queued before this one must not overwrite it when they are sent later,
+                // so drop those it covers, and send those it partially covers before it:
+                let region = [pos, delta.image.size()];
+                let mut i = 0;
+                while i < self.streamed.len() {
+                    let (streamed_id, streamed) = &self.streamed[i];
+                    let streamed_pos = streamed.pos.unwrap_or_default();
+                    let streamed_region = [streamed_pos, streamed.image.size()];
+                    if streamed_id != &id || !regions_overlap(region, streamed_region) {
+                        i += 1;
+                    } else if region_contains(region, streamed_region) {
+                        self.streamed.remove(i);
+                    } else {
+                        self.delta.set.extend(self.streamed.remove(i));
+                    }
+                }
             } else {
                 // whole update
                 meta.size = delta.image.size();
                 meta.bytes_per_pixel = delta.image.bytes_per_pixel();
                 // since we update the whole image, we can discard all old enqueued deltas
                 self.delta.set.retain(|(x, _)| x != &id);
+                self.streamed.retain(|(x, _)| x != &id);
             }
             self.delta.set.push((id, delta));
         } else {
@@ -67,6 +94,45 @@ impl TextureManager {
         }
     }
 
+    /// Queue a partial update of an existing texture, to be sent to the backend in a later frame.
+    ///
+    /// Streamed updates are spread out over several frames so that at most
+    /// [`Self::set_upload_budget`] bytes are uploaded per frame.
+    /// This lets huge images be uploaded tile by tile without stalling a single frame.
+    ///
+    /// Whole-texture updates are not streamed, but applied as with [`Self::set`].
+    ///
+    /// Unlike [`Self::set`], updates to textures that have been freed are silently ignored,
+    /// since they are often produced by background threads that don't know the texture is gone.
+    pub fn set_streamed(&mut self, id: TextureId, delta: ImageDelta) {
+        let Some(meta) = self.metas.get(&id) else {
+            return;
+        };
+        if let Some(pos) = delta.pos {
+            debug_assert!(
+                pos[0] + delta.image.width() <= meta.size[0]
+                    && pos[1] + delta.image.height() <= meta.size[1],
+                "Partial texture update is outside the bounds of texture {id:?}",
+            );
+            self.streamed.push_back((id, delta));
+        } else {
+            self.set(id, delta);
+        }
+    }
+
+    /// How many bytes of streamed updates (see [`Self::set_streamed`]) to send to the backend per frame.
+    ///
+    /// At least one update is always sent per frame, even if it is larger than this.
+    /// Default: [`Self::DEFAULT_UPLOAD_BUDGET`].
+    pub fn set_upload_budget(&mut self, bytes_per_frame: usize) {
+        self.upload_budget = Some(bytes_per_frame);
+    }
+
+    /// Are there streamed updates that have not yet been sent to the backend?
+    pub fn has_pending_uploads(&self) -> bool {
+        !self.streamed.is_empty()
+    }
+
     /// Free an existing texture.
     pub fn free(&mut self, id: TextureId) {
         if let std::collections::hash_map::Entry::Occupied(mut entry) = self.metas.entry(id) {
@@ -74,6 +140,7 @@ impl TextureManager {
             meta.retain_count -= 1;
             if meta.retain_count == 0 {
                 entry.remove();
+                self.streamed.retain(|(x, _)| x != &id);
                 self.delta.free.push(id);
             }
         } else {
@@ -98,7 +165,22 @@ impl TextureManager {
     /// Take and reset changes since last frame.
     ///
     /// These should be applied to the painting subsystem each frame.
+    ///
+    /// This includes as many streamed updates as fit in the upload budget,
+    /// see [`Self::set_streamed`].
     pub fn take_delta(&mut self) -> TexturesDelta {
+        let mut budget = self.upload_budget.unwrap_or(Self::DEFAULT_UPLOAD_BUDGET);
+        let mut is_first = true;
+        while let Some((_, delta)) = self.streamed.front() {
+            let bytes = delta.image.width() * delta.image.height() * delta.image.bytes_per_pixel();
+            if !is_first && budget < bytes {
+                break;
+            }
+            budget = budget.saturating_sub(bytes);
+            is_first = false;
+            self.delta.set.extend(self.streamed.pop_front());
+        }
+
         std::mem::take(&mut self.delta)
     }
 
@@ -118,6 +200,22 @@ impl TextureManager {
     }
 }
 
+/// Does the region `[pos, size]` overlap the other one?
+fn regions_overlap(
+    [[x, y], [w, h]]: [[usize; 2]; 2],
+    [[ox, oy], [ow, oh]]: [[usize; 2]; 2],
+) -> bool {
+    x < ox + ow && ox < x + w && y < oy + oh && oy < y + h
+}
+
+/// Does the region `[pos, size]` contain the other one?
+fn region_contains(
+    [[x, y], [w, h]]: [[usize; 2]; 2],
+    [[ox, oy], [ow, oh]]: [[usize; 2]; 2],
+) -> bool {
+    x <= ox && ox + ow <= x + w && y <= oy && oy + oh <= y + h
+}
+
 /// Meta-data about an allocated texture.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TextureMeta {
@@ -169,6 +267,12 @@ pub struct TextureOptions {
     ///
     /// - This may not be available on all backends (currently only `egui_glow`).
     pub mipmap_mode: Option<TextureFilter>,
+
+    /// The color space of the texels.
+    ///
+    /// Backends that render to a different color space (e.g. to a Display P3 surface)
+    /// convert the texture to it when uploading it.
+    pub color_space: ColorSpace,
 }
 
 impl TextureOptions {
@@ -178,6 +282,7 @@ impl TextureOptions {
         minification: TextureFilter::Linear,
         wrap_mode: TextureWrapMode::ClampToEdge,
         mipmap_mode: None,
+        color_space: ColorSpace::Srgb,
     };
 
     /// Nearest magnification and minification.
@@ -186,6 +291,7 @@ impl TextureOptions {
         minification: TextureFilter::Nearest,
         wrap_mode: TextureWrapMode::ClampToEdge,
         mipmap_mode: None,
+        color_space: ColorSpace::Srgb,
     };
 
     /// Linear magnification and minification, but with the texture repeated.
@@ -194,6 +300,7 @@ impl TextureOptions {
         minification: TextureFilter::Linear,
         wrap_mode: TextureWrapMode::Repeat,
         mipmap_mode: None,
+        color_space: ColorSpace::Srgb,
     };
 
     /// Linear magnification and minification, but with the texture mirrored and repeated.
@@ -202,6 +309,7 @@ impl TextureOptions {
         minification: TextureFilter::Linear,
         wrap_mode: TextureWrapMode::MirroredRepeat,
         mipmap_mode: None,
+        color_space: ColorSpace::Srgb,
     };
 
     /// Nearest magnification and minification, but with the texture repeated.
@@ -210,6 +318,7 @@ impl TextureOptions {
         minification: TextureFilter::Nearest,
         wrap_mode: TextureWrapMode::Repeat,
         mipmap_mode: None,
+        color_space: ColorSpace::Srgb,
     };
 
     /// Nearest magnification and minification, but with the texture mirrored and repeated.
@@ -218,6 +327,7 @@ impl TextureOptions {
         minification: TextureFilter::Nearest,
         wrap_mode: TextureWrapMode::MirroredRepeat,
         mipmap_mode: None,
+        color_space: ColorSpace::Srgb,
     };
 
     pub const fn with_mipmap_mode(self, mipmap_mode: Option<TextureFilter>) -> Self {
@@ -226,6 +336,14 @@ impl TextureOptions {
             ..self
         }
     }
+
+    /// Tag the texels as being in the given color space, e.g. for wide-gamut images.
+    pub const fn with_color_space(self, color_space: ColorSpace) -> Self {
+        Self {
+            color_space,
+            ..self
+        }
+    }
 }
 
 impl Default for TextureOptions {
@@ -330,3 +448,107 @@ impl std::fmt::Debug for TexturesDelta {
         debug_struct.finish()
     }
 }
+
+// ----------------------------------------------------------------------------
+
+/// Paint jobs to render into an existing texture instead of onto the screen,
+/// e.g. a thumbnail of some ui.
+///
+/// The backend should render these after applying [`TexturesDelta::set`]
+/// and before painting the rest of the frame.
+#[derive(Clone, Debug)]
+pub struct TextureSnapshot {
+    /// The texture to render into. Its previous contents are cleared to transparent.
+    pub texture_id: TextureId,
+
+    /// The size of the texture, in texels.
+    pub size: [usize; 2],
+
+    /// Texels per point.
+    pub pixels_per_point: f32,
+
+    /// What to paint, with positions in points relative to the top-left corner of the texture.
+    pub primitives: Vec<ClippedPrimitive>,
+}
+
+#[test]
+fn test_streamed_uploads() {
+    use crate::ColorImage;
+    use ecolor::Color32;
+
+    let mut tex_mngr = TextureManager::default();
+    let options = TextureOptions::default();
+    let id = tex_mngr.alloc(
+        "test".to_owned(),
+        ColorImage::new([64, 64], Color32::TRANSPARENT).into(),
+        options,
+    );
+    assert_eq!(tex_mngr.take_delta().set.len(), 1);
+
+    // Each tile is 16x16x4 = 1024 bytes, so two fit per frame:
+    tex_mngr.set_upload_budget(2048);
+    for i in 0..5 {
+        let tile = ColorImage::new([16, 16], Color32::RED);
+        tex_mngr.set_streamed(id, ImageDelta::partial([16 * i % 64, 0], tile, options));
+    }
+    assert_eq!(tex_mngr.take_delta().set.len(), 2);
+    assert_eq!(tex_mngr.take_delta().set.len(), 2);
+    assert!(tex_mngr.has_pending_uploads());
+    assert_eq!(tex_mngr.take_delta().set.len(), 1);
+    assert!(!tex_mngr.has_pending_uploads());
+
+    // Pending updates are dropped when the texture is freed:
+    let tile = ColorImage::new([16, 16], Color32::RED);
+    tex_mngr.set_streamed(id, ImageDelta::partial([0, 0], tile.clone(), options));
+    tex_mngr.free(id);
+    assert!(!tex_mngr.has_pending_uploads());
+    tex_mngr.set_streamed(id, ImageDelta::partial([0, 0], tile, options));
+    assert!(!tex_mngr.has_pending_uploads());
+}
+
+#[test]
+fn test_set_after_streamed_uploads() {
+    use crate::ColorImage;
+    use ecolor::Color32;
+
+    let mut tex_mngr = TextureManager::default();
+    let options = TextureOptions::default();
+    let id = tex_mngr.alloc("test".to_owned(), ImageData::Empty([64, 64]), options);
+    let other = tex_mngr.alloc("other".to_owned(), ImageData::Empty([64, 64]), options);
+    let delta = tex_mngr.take_delta();
+    assert!(
+        matches!(delta.set[0].1.image, ImageData::Empty([64, 64])),
+        "Allocated without uploading any pixels"
+    );
+
+    let tile = |size| ColorImage::new([size, size], Color32::RED);
+    tex_mngr.set_upload_budget(0);
+    tex_mngr.set_streamed(id, ImageDelta::partial([0, 0], tile(16), options));
+    tex_mngr.set_streamed(id, ImageDelta::partial([16, 0], tile(16), options));
+    tex_mngr.set_streamed(id, ImageDelta::partial([48, 48], tile(16), options));
+    tex_mngr.set_streamed(other, ImageDelta::partial([0, 0], tile(16), options));
+
+    // Covers the first tile, and half of the second one:
+    tex_mngr.set(id, ImageDelta::partial([0, 0], tile(24), options));
+
+    let positions = |delta: TexturesDelta| -> Vec<_> {
+        delta
+            .set
+            .into_iter()
+            .map(|(id, delta)| (id, delta.pos.unwrap()))
+            .collect()
+    };
+    assert_eq!(
+        positions(tex_mngr.take_delta()),
+        [(id, [16, 0]), (id, [0, 0]), (id, [48, 48])],
+        "The half-covered tile is sent before the update, and the covered one is dropped"
+    );
+    assert_eq!(positions(tex_mngr.take_delta()), [(other, [0, 0])]);
+    assert!(!tex_mngr.has_pending_uploads());
+
+    // A whole update drops all of them:
+    tex_mngr.set_streamed(id, ImageDelta::partial([0, 0], tile(16), options));
+    tex_mngr.set(id, ImageDelta::full(tile(64), options));
+    assert_eq!(tex_mngr.take_delta().set.len(), 1);
+    assert!(!tex_mngr.has_pending_uploads());
+}